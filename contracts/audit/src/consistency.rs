@@ -29,13 +29,15 @@
 /// |-------------------|-----------|-----------|
 /// | `generate`        | O(n)      | O(log n)  |
 /// | `verify`          | O(log n)  | O(log n)  |
+/// | `to_bytes`        | O(log n)  | O(log n)  |
+/// | `from_bytes`      | O(log n)  | O(log n)  |
 use alloc::vec::Vec;
 
 use sha2::{Digest as Sha2Digest, Sha256};
 
 use crate::{
     merkle_log::{compute_root, MerkleRoot},
-    types::{AuditError, Digest},
+    types::{push_hash_list, take_array, take_hash_list, AuditError, Digest},
 };
 
 // ── Domain-separation prefix (RFC 6962) ───────────────────────────────────────
@@ -97,6 +99,60 @@ pub struct ConsistencyProof {
 }
 
 impl ConsistencyProof {
+    /// Size of the fixed header: both sizes, both roots and the hash count.
+    const HEADER_LEN: usize = 8 + 8 + 32 + 32 + 4;
+
+    /// Length in bytes of [`Self::to_bytes`]'s output.
+    pub fn serialized_size(&self) -> usize {
+        Self::HEADER_LEN + self.proof_hashes.len() * 32
+    }
+
+    /// Serialize to the compact wire format used by transparency monitors.
+    ///
+    /// Layout (integers big-endian):
+    ///
+    /// ```text
+    /// size_v1 (u64) ‖ size_v2 (u64) ‖ root_v1 (32) ‖ root_v2 (32)
+    ///   ‖ hash_count (u32) ‖ proof_hashes (hash_count × 32)
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.serialized_size());
+        out.extend_from_slice(&self.size_v1.to_be_bytes());
+        out.extend_from_slice(&self.size_v2.to_be_bytes());
+        out.extend_from_slice(&self.root_v1);
+        out.extend_from_slice(&self.root_v2);
+        push_hash_list(&mut out, &self.proof_hashes);
+        out
+    }
+
+    /// Parse a proof produced by [`Self::to_bytes`].
+    ///
+    /// Decoding does not verify the proof; call [`Self::verify`] afterwards.
+    ///
+    /// # Errors
+    ///
+    /// * [`AuditError::MalformedProof`] — input is truncated, has trailing
+    ///   bytes, or declares `size_v1 > size_v2`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AuditError> {
+        let mut at = 0usize;
+        let size_v1 = u64::from_be_bytes(take_array(bytes, &mut at)?);
+        let size_v2 = u64::from_be_bytes(take_array(bytes, &mut at)?);
+        let root_v1 = take_array(bytes, &mut at)?;
+        let root_v2 = take_array(bytes, &mut at)?;
+        if size_v1 > size_v2 {
+            return Err(AuditError::MalformedProof);
+        }
+        let proof_hashes = take_hash_list(bytes, &mut at)?;
+
+        Ok(Self {
+            size_v1,
+            size_v2,
+            root_v1,
+            root_v2,
+            proof_hashes,
+        })
+    }
+
     /// Verify this consistency proof.
     ///
     /// Reconstructs both `root_v1` and `root_v2` from `proof_hashes` and
@@ -276,35 +332,47 @@ impl ConsistencyProver {
         root_v1: MerkleRoot,
         size_v1: u64,
     ) -> Result<ConsistencyProof, AuditError> {
-        let size_v2 = self.leaf_hashes.len() as u64;
-
-        if size_v1 > size_v2 {
-            return Err(AuditError::InvalidConsistencyProof);
-        }
-
-        let root_v2 = compute_root(&self.leaf_hashes);
+        prove_consistency_over(&self.leaf_hashes, root_v1, size_v1)
+    }
+}
 
-        if size_v1 == size_v2 || size_v1 == 0 {
-            return Ok(ConsistencyProof {
-                size_v1,
-                size_v2,
-                root_v1,
-                root_v2,
-                proof_hashes: Vec::new(),
-            });
-        }
+/// Generate a consistency proof over a borrowed leaf-hash slice.
+///
+/// Shared by [`ConsistencyProver`] and
+/// [`SealedMerkleLog`](crate::sealed::SealedMerkleLog), which proves without
+/// copying its leaves.
+pub(crate) fn prove_consistency_over(
+    leaf_hashes: &[Digest],
+    root_v1: MerkleRoot,
+    size_v1: u64,
+) -> Result<ConsistencyProof, AuditError> {
+    let size_v2 = leaf_hashes.len() as u64;
+
+    if size_v1 > size_v2 {
+        return Err(AuditError::InvalidConsistencyProof);
+    }
 
-        let proof_hashes =
-            collect_proof_hashes(&self.leaf_hashes, size_v1 as usize, size_v2 as usize);
+    let root_v2 = compute_root(leaf_hashes);
 
-        Ok(ConsistencyProof {
+    if size_v1 == size_v2 || size_v1 == 0 {
+        return Ok(ConsistencyProof {
             size_v1,
             size_v2,
             root_v1,
             root_v2,
-            proof_hashes,
-        })
+            proof_hashes: Vec::new(),
+        });
     }
+
+    let proof_hashes = collect_proof_hashes(leaf_hashes, size_v1 as usize, size_v2 as usize);
+
+    Ok(ConsistencyProof {
+        size_v1,
+        size_v2,
+        root_v1,
+        root_v2,
+        proof_hashes,
+    })
 }
 
 // ─── LogHistory: multi-checkpoint consistency management ─────────────────────
@@ -530,4 +598,66 @@ mod tests {
         }
         assert!(proof.verify().is_err());
     }
+
+    // ── Wire format ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn serialized_proof_round_trips_and_verifies() {
+        let log = build_log(13);
+        let all_hashes = get_hashes(&log, 13);
+        let prover = ConsistencyProver::new(all_hashes.clone());
+
+        for old in 1..=13u64 {
+            let root_old = compute_root(&all_hashes[..old as usize]);
+            let proof = prover.generate(root_old, old).unwrap();
+            let bytes = proof.to_bytes();
+            assert_eq!(bytes.len(), proof.serialized_size());
+            assert_eq!(
+                bytes.len(),
+                ConsistencyProof::HEADER_LEN + proof.proof_hashes.len() * 32
+            );
+
+            let decoded = ConsistencyProof::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded.size_v1, old);
+            assert_eq!(decoded.size_v2, 13);
+            assert_eq!(decoded.root_v1, root_old);
+            assert_eq!(decoded.root_v2, proof.root_v2);
+            assert_eq!(decoded.proof_hashes, proof.proof_hashes);
+            assert!(decoded.verify().is_ok(), "decoded {old}→13 must verify");
+        }
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_and_trailing_input() {
+        let log = build_log(8);
+        let all_hashes = get_hashes(&log, 8);
+        let root_3 = compute_root(&all_hashes[..3]);
+        let bytes = ConsistencyProver::new(all_hashes)
+            .generate(root_3, 3)
+            .unwrap()
+            .to_bytes();
+
+        for len in 0..bytes.len() {
+            assert_eq!(
+                ConsistencyProof::from_bytes(&bytes[..len]).unwrap_err(),
+                AuditError::MalformedProof,
+                "truncated to {len} bytes"
+            );
+        }
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            ConsistencyProof::from_bytes(&trailing).unwrap_err(),
+            AuditError::MalformedProof
+        );
+
+        // Sizes out of order are rejected before the payload is inspected.
+        let mut swapped = bytes;
+        swapped[..8].copy_from_slice(&9u64.to_be_bytes());
+        assert_eq!(
+            ConsistencyProof::from_bytes(&swapped).unwrap_err(),
+            AuditError::MalformedProof
+        );
+    }
 }
@@ -7,6 +7,8 @@
 //!   making retroactive modification detectable in O(k·L) time.
 //! * **Merkle trees** — entries are committed into an append-only Merkle log
 //!   whose root can be published as a compact tamper-evidence beacon.
+//! * **Signed entries** — `MerkleLog::append_signed` binds an entry to an
+//!   Ed25519 signer (folded into the leaf hash) for non-repudiation.
 //! * **Consistency proofs** (RFC 6962 §2.1.2) — any two roots can be proven
 //!   consistent without replaying the full log (O(log n) proof size).
 //! * **Searchable symmetric encryption (SSE-1)** — keyword search without
@@ -19,6 +21,7 @@
 //! | [`types`]         | Core domain types (`LogEntry`, `AuditError`, …)           |
 //! | [`merkle_log`]    | `MerkleLog` — append-only log per segment                  |
 //! | [`consistency`]   | `ConsistencyProver` / `ConsistencyProof` (RFC 6962)        |
+//! | [`sealed`]        | `SealedMerkleLog` — immutable, shareable proof source      |
 //! | [`search`]        | `SearchEngine` / `ForwardIndex` — SSE-1 keyword search     |
 //!
 //! ## Quick start
@@ -49,10 +52,35 @@
 //! assert_eq!(engine.query("alice"), vec![seq]);
 //! ```
 //!
+//! ## Canonical entry encoding
+//!
+//! Every Merkle leaf is [`types::LogEntry::canonical_hash`], computed as
+//! `SHA-256(0x00 ‖ leaf_bytes)` over the following fields, in order:
+//!
+//! | Field       | Encoding                                            |
+//! |-------------|-----------------------------------------------------|
+//! | `sequence`  | u64, 8 bytes big-endian                             |
+//! | `timestamp` | u64, 8 bytes big-endian                             |
+//! | `actor`     | u32 big-endian byte length ‖ UTF-8 bytes            |
+//! | `action`    | u32 big-endian byte length ‖ UTF-8 bytes            |
+//! | `target`    | u32 big-endian byte length ‖ UTF-8 bytes            |
+//! | `result`    | u32 big-endian byte length ‖ UTF-8 bytes            |
+//! | `prev_hash` | 32 raw bytes                                        |
+//! | `segment`   | u32 big-endian byte length ‖ UTF-8 label bytes      |
+//! | `signer`    | 32 raw bytes, signed entries only                   |
+//! | `signature` | 64 raw bytes, signed entries only                   |
+//!
+//! Everything up to and including `segment` is
+//! [`types::LogEntry::canonical_bytes`], which is also the message an
+//! entry's signer signs.  The encoding depends only on field values, not on
+//! how an implementation stores entries, so external verifiers can recompute
+//! leaves and check proofs independently.
+//!
 //! ## `no_std` compatibility
 //!
 //! The crate is `#![no_std]` with `extern crate alloc`.  It compiles for Wasm
-//! targets (Soroban/Stellar) without modification.
+//! targets (Soroban/Stellar) without modification.  The optional `std` feature
+//! only enables the multi-threaded [`sealed`] tests.
 
 #![no_std]
 
@@ -61,6 +89,7 @@ extern crate alloc;
 pub mod consistency;
 pub mod contract;
 pub mod merkle_log;
+pub mod sealed;
 pub mod search;
 pub mod types;
 
@@ -15,20 +15,31 @@
 ///
 /// # Time and Space Complexity
 ///
-/// | Operation           | Time      | Space      |
-/// |---------------------|-----------|------------|
-/// | `append`            | O(log n)  | O(1) amort |
-/// | `inclusion_proof`   | O(log n)  | O(log n)   |
-/// | `verify_root`       | O(1)      | O(1)       |
-/// | `compact`           | O(k log n)| O(k)       |
-/// | `query_range`       | O(k)      | O(k)       |
+/// | Operation               | Time       | Space        |
+/// |-------------------------|------------|--------------|
+/// | `append`                | O(log n)   | O(1) amort   |
+/// | `append_batch`          | O(k log n) | O(k)         |
+/// | `inclusion_proof`       | O(log n)   | O(log n)     |
+/// | `range_inclusion_proof` | O(n)       | O(k + log n) |
+/// | `verify_root`           | O(1)       | O(1)         |
+/// | `compact`               | O(k log n) | O(k)         |
+/// | `prune_before`          | O(k log n) | O(1)         |
+/// | `query_range`           | O(k)       | O(k)         |
+/// | `export_state`          | O(n)       | O(n)         |
+/// | `from_snapshot`         | O(n · (L + c)) | O(n) extra |
 ///
-/// where n is the total number of entries and k is the range width.
+/// where n is the total number of entries, k is the range width and c the
+/// number of published checkpoints.
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
 
+use ed25519_dalek::{Signature as Ed25519Signature, Verifier, VerifyingKey};
 use sha2::{Digest as Sha2Digest, Sha256};
 
-use crate::types::{AuditError, Digest, LogEntry, LogSegmentId, RetentionPolicy, WitnessSignature};
+use crate::consistency::{prove_consistency_over, ConsistencyProof};
+use crate::types::{
+    push_hash_list, take_array, take_hash_list, AuditError, Digest, LogEntry, LogSegmentId,
+    PublicKey, RetentionPolicy, Signature, WitnessSignature,
+};
 
 // ── Merkle-tree domain-separation prefixes (RFC 6962) ─────────────────────────
 const LEAF_PREFIX: u8 = 0x00;
@@ -49,6 +60,18 @@ pub fn hash_leaf(data: &[u8]) -> Digest {
     h.finalize().into()
 }
 
+/// Verify an Ed25519 `signature` by `signer` over `message`.
+fn verify_ed25519(
+    message: &[u8],
+    signature: &Signature,
+    signer: &PublicKey,
+) -> Result<(), AuditError> {
+    let key = VerifyingKey::from_bytes(signer).map_err(|_| AuditError::InvalidSignature)?;
+    let sig = Ed25519Signature::from_bytes(signature);
+    key.verify(message, &sig)
+        .map_err(|_| AuditError::InvalidSignature)
+}
+
 /// Hash an internal node: `SHA256(0x01 ‖ left ‖ right)`.
 #[inline]
 fn hash_node(left: &Digest, right: &Digest) -> Digest {
@@ -101,13 +124,67 @@ pub struct InclusionProof {
     pub leaf_index: u64,
     /// Total number of leaves in the tree.
     pub tree_size: u64,
-    /// Hash of the proven leaf (= `hash_leaf(entry.canonical_bytes())`).
+    /// Hash of the proven leaf (= [`LogEntry::canonical_hash`]).
     pub leaf_hash: Digest,
     /// Sibling hashes from leaf to root (left-to-right = bottom-to-top).
     pub siblings: Vec<Digest>,
 }
 
 impl InclusionProof {
+    /// Size of the fixed header: index, tree size, leaf hash and hash count.
+    const HEADER_LEN: usize = 8 + 8 + 32 + 4;
+
+    /// Length in bytes of [`Self::to_bytes`]'s output.
+    pub fn serialized_size(&self) -> usize {
+        Self::HEADER_LEN + self.siblings.len() * 32
+    }
+
+    /// Serialize to a compact wire format for relaying proofs off-chain.
+    ///
+    /// Layout (integers big-endian):
+    ///
+    /// ```text
+    /// leaf_index (u64) ‖ tree_size (u64) ‖ leaf_hash (32)
+    ///   ‖ hash_count (u32) ‖ siblings (hash_count × 32)
+    /// ```
+    ///
+    /// No per-sibling direction flags are stored: as in [`Self::verify`],
+    /// each sibling's side follows from `leaf_index` and `tree_size`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.serialized_size());
+        out.extend_from_slice(&self.leaf_index.to_be_bytes());
+        out.extend_from_slice(&self.tree_size.to_be_bytes());
+        out.extend_from_slice(&self.leaf_hash);
+        push_hash_list(&mut out, &self.siblings);
+        out
+    }
+
+    /// Parse a proof produced by [`Self::to_bytes`].
+    ///
+    /// Decoding does not verify the proof; call [`Self::verify`] afterwards.
+    ///
+    /// # Errors
+    ///
+    /// * [`AuditError::MalformedProof`] — input is truncated, has trailing
+    ///   bytes, or declares `leaf_index >= tree_size`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AuditError> {
+        let mut at = 0usize;
+        let leaf_index = u64::from_be_bytes(take_array(bytes, &mut at)?);
+        let tree_size = u64::from_be_bytes(take_array(bytes, &mut at)?);
+        let leaf_hash = take_array(bytes, &mut at)?;
+        if leaf_index >= tree_size {
+            return Err(AuditError::MalformedProof);
+        }
+        let siblings = take_hash_list(bytes, &mut at)?;
+
+        Ok(Self {
+            leaf_index,
+            tree_size,
+            leaf_hash,
+            siblings,
+        })
+    }
+
     /// Verify that `self.leaf_hash` is correctly included in a tree with the
     /// given `root`.
     ///
@@ -151,6 +228,71 @@ impl InclusionProof {
     }
 }
 
+/// A Merkle proof that the contiguous leaves `leaf_hashes` sit at positions
+/// `first_index..first_index + leaf_hashes.len()` of a tree of `tree_size`
+/// leaves.
+///
+/// Instead of one audit path per leaf, a range proof carries only the hashes
+/// bordering the range: at each level at most one node to its left and one
+/// to its right (left first), bottom to top.  A single-leaf range carries
+/// exactly the siblings of the matching [`InclusionProof`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RangeProof {
+    /// 0-based index of the first proven leaf.
+    pub first_index: u64,
+    /// Total number of leaves in the tree.
+    pub tree_size: u64,
+    /// Hashes of the proven leaves, in order.
+    pub leaf_hashes: Vec<Digest>,
+    /// Border hashes from leaf to root (left before right at each level).
+    pub siblings: Vec<Digest>,
+}
+
+impl RangeProof {
+    /// Verify that every hash in `self.leaf_hashes` is included, in order
+    /// and contiguously, in a tree with the given `root`.
+    ///
+    /// Complexity: O(k + log n) for a range of k leaves.
+    pub fn verify(&self, root: &MerkleRoot) -> Result<(), AuditError> {
+        let count = self.leaf_hashes.len() as u64;
+        if count == 0 || self.first_index.saturating_add(count) > self.tree_size {
+            return Err(AuditError::InvalidInclusionProof);
+        }
+
+        let mut nodes = self.leaf_hashes.clone();
+        let mut lo = self.first_index;
+        let mut size = self.tree_size;
+        let mut sib_iter = self.siblings.iter();
+
+        while size > 1 {
+            let hi = lo + nodes.len() as u64 - 1;
+            if lo % 2 == 1 {
+                let sibling = sib_iter.next().ok_or(AuditError::InvalidInclusionProof)?;
+                nodes.insert(0, *sibling);
+                lo -= 1;
+            }
+            if hi % 2 == 0 && hi < size - 1 {
+                let sibling = sib_iter.next().ok_or(AuditError::InvalidInclusionProof)?;
+                nodes.push(*sibling);
+            }
+            // `nodes` now spans whole pairs, except a lone right-hand node
+            // at the end of the level, which is promoted as-is.
+            nodes = pair_up(&nodes);
+            lo /= 2;
+            size = size.div_ceil(2);
+        }
+
+        if sib_iter.next().is_some() {
+            return Err(AuditError::InvalidInclusionProof);
+        }
+        if nodes.len() == 1 && &nodes[0] == root {
+            Ok(())
+        } else {
+            Err(AuditError::InvalidInclusionProof)
+        }
+    }
+}
+
 // ── Checkpoint ────────────────────────────────────────────────────────────────
 
 /// A published root checkpoint, created each time `publish_root` is called.
@@ -191,6 +333,43 @@ pub struct CompactionReceipt {
     pub compacted_at: u64,
 }
 
+// ── LogSnapshot ───────────────────────────────────────────────────────────────
+
+/// Complete exported state of a [`MerkleLog`], produced by
+/// [`MerkleLog::export_state`].
+///
+/// A monitor can rebuild the log with [`MerkleLog::from_snapshot`] and then
+/// serve or check proofs without trusting the exporter: the import verifies
+/// the recorded root and every checkpoint root against the leaf spine, every
+/// live entry against its leaf, and every witness signature against the
+/// witness keys the monitor trusts.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LogSnapshot {
+    /// Segment the log covers.
+    pub segment: LogSegmentId,
+    /// Live entries in ascending sequence order.
+    pub entries: Vec<LogEntry>,
+    /// The node spine: every leaf hash committed to the tree, in order,
+    /// including those of pruned entries.  The root is derived from these.
+    pub leaf_hashes: Vec<Digest>,
+    /// Merkle root over `leaf_hashes` at export time.
+    pub root: MerkleRoot,
+    /// Published root checkpoints.
+    pub checkpoints: Vec<RootCheckpoint>,
+    /// Witness co-signatures collected by the log.
+    pub witnesses: Vec<WitnessSignature>,
+    /// Next sequence number the log would assign.
+    pub next_seq: u64,
+    /// Timestamp of the most recently appended entry.
+    pub last_timestamp: u64,
+    /// Retention policy in force, if any.
+    pub retention: Option<RetentionPolicy>,
+    /// First sequence whose entry body is still held.
+    pub pruned_before: u64,
+    /// Number of leading leaves whose entry bodies were pruned.
+    pub pruned_leaves: u64,
+}
+
 // ── MerkleLog ─────────────────────────────────────────────────────────────────
 
 /// An append-only, hash-chain + Merkle-tree audit log for a single segment.
@@ -227,6 +406,13 @@ pub struct MerkleLog {
 
     /// Retention policy for this segment (if any).
     retention: Option<RetentionPolicy>,
+
+    /// First sequence whose entry body is still held; bodies below it were
+    /// dropped by `prune_before` (their leaf hashes are kept).
+    pruned_before: u64,
+
+    /// Number of leading `leaf_hashes` whose entry bodies were pruned.
+    pruned_leaves: usize,
 }
 
 impl MerkleLog {
@@ -245,6 +431,8 @@ impl MerkleLog {
             next_seq: 1,
             last_timestamp: 0,
             retention: None,
+            pruned_before: 1,
+            pruned_leaves: 0,
         }
     }
 
@@ -287,6 +475,71 @@ impl MerkleLog {
         target: impl Into<String>,
         result: impl Into<String>,
     ) -> Result<u64, AuditError> {
+        let entry = self.prepare_entry(timestamp, actor, action, target, result)?;
+        Ok(self.commit(entry))
+    }
+
+    /// Append many `(timestamp, actor, action, target, result)` entries at
+    /// once, e.g. during a backfill.
+    ///
+    /// Ordering is validated for the whole batch before anything is
+    /// committed, so either every entry is appended or none is.  The Merkle
+    /// root is derived from the leaf hashes on demand, so the resulting
+    /// [`Self::current_root`] is identical to that of the same entries
+    /// passed one by one to [`Self::append`].
+    ///
+    /// # Returns
+    /// The assigned sequence numbers, in input order.
+    ///
+    /// # Errors
+    /// [`AuditError::OutOfOrderTimestamp`] for the first entry whose
+    /// timestamp pre-dates its predecessor (in the log or in the batch).
+    ///
+    /// # Complexity
+    /// O(k log n) for k entries, with a single reservation for the new leaves.
+    pub fn append_batch(
+        &mut self,
+        entries: &[(u64, &str, &str, &str, &str)],
+    ) -> Result<Vec<u64>, AuditError> {
+        let mut minimum = self.last_timestamp;
+        for (offset, &(timestamp, ..)) in entries.iter().enumerate() {
+            let sequence = self.next_seq + offset as u64;
+            if sequence > 1 && timestamp < minimum {
+                return Err(AuditError::OutOfOrderTimestamp {
+                    sequence,
+                    supplied: timestamp,
+                    minimum,
+                });
+            }
+            minimum = timestamp;
+        }
+
+        self.leaf_hashes.reserve(entries.len());
+        let mut sequences = Vec::with_capacity(entries.len());
+        for &(timestamp, actor, action, target, result) in entries {
+            let entry = self.prepare_entry(timestamp, actor, action, target, result)?;
+            sequences.push(self.commit(entry));
+        }
+        Ok(sequences)
+    }
+
+    /// Build the entry that the next `append` would commit, without
+    /// committing it.
+    ///
+    /// Signers call this, sign the returned entry's
+    /// [`LogEntry::canonical_bytes`], and pass both to [`Self::append_signed`].
+    /// `entry_hash` is left zeroed; it is computed on commit.
+    ///
+    /// # Errors
+    /// Same as [`Self::append`].
+    pub fn prepare_entry(
+        &self,
+        timestamp: u64,
+        actor: impl Into<String>,
+        action: impl Into<String>,
+        target: impl Into<String>,
+        result: impl Into<String>,
+    ) -> Result<LogEntry, AuditError> {
         let seq = self.next_seq;
 
         // Enforce chronological ordering: reject timestamps that pre-date the
@@ -299,8 +552,6 @@ impl MerkleLog {
             });
         }
 
-        self.next_seq += 1;
-
         // Hash chain: previous entry's hash, or zero-hash for the first entry.
         let prev_hash: Digest = if seq == 1 {
             [0u8; 32]
@@ -312,8 +563,7 @@ impl MerkleLog {
                 .unwrap_or([0u8; 32])
         };
 
-        // Partially construct the entry (entry_hash is computed below).
-        let mut entry = LogEntry {
+        Ok(LogEntry {
             sequence: seq,
             timestamp,
             actor: actor.into(),
@@ -323,18 +573,98 @@ impl MerkleLog {
             prev_hash,
             entry_hash: [0u8; 32], // placeholder
             segment: self.segment.clone(),
-        };
+            signature: None,
+            signer: None,
+        })
+    }
+
+    /// Append an entry signed by `signer`.
+    ///
+    /// `entry` must come from [`Self::prepare_entry`] with no intervening
+    /// append.  The Ed25519 `signature` is verified over the entry's canonical
+    /// bytes before anything is committed; the signature and signer are then
+    /// folded into the leaf hash.
+    ///
+    /// # Errors
+    /// * [`AuditError::UnexpectedSequence`] — the log has moved on since the
+    ///   entry was prepared.
+    /// * [`AuditError::HashChainBroken`] — `prev_hash` or `segment` does not
+    ///   match the current log tail.
+    /// * [`AuditError::OutOfOrderTimestamp`] — as for [`Self::append`].
+    /// * [`AuditError::InvalidSignature`] — the signature does not verify.
+    ///
+    /// # Complexity
+    /// O(log n) plus one signature verification.
+    pub fn append_signed(
+        &mut self,
+        entry: LogEntry,
+        signature: Signature,
+        signer: PublicKey,
+    ) -> Result<u64, AuditError> {
+        if entry.sequence != self.next_seq {
+            return Err(AuditError::UnexpectedSequence {
+                expected: self.next_seq,
+                supplied: entry.sequence,
+            });
+        }
+        let expected = self.prepare_entry(
+            entry.timestamp,
+            entry.actor.clone(),
+            entry.action.clone(),
+            entry.target.clone(),
+            entry.result.clone(),
+        )?;
+        if entry.prev_hash != expected.prev_hash || entry.segment != expected.segment {
+            return Err(AuditError::HashChainBroken {
+                at_sequence: entry.sequence,
+            });
+        }
 
-        // Compute the entry hash over its canonical bytes.
-        let canonical = entry.canonical_bytes();
-        let leaf_hash = hash_leaf(&canonical);
+        verify_ed25519(&expected.canonical_bytes(), &signature, &signer)?;
+
+        let mut entry = expected;
+        entry.signature = Some(signature);
+        entry.signer = Some(signer);
+        Ok(self.commit(entry))
+    }
+
+    /// Verify that the entry at `sequence` was signed by `pubkey`.
+    ///
+    /// # Errors
+    /// * [`AuditError::EntryNotFound`] — no live entry at `sequence`.
+    /// * [`AuditError::EntryNotSigned`] — the entry is unsigned or was signed
+    ///   by a different key.
+    /// * [`AuditError::InvalidSignature`] — the stored signature does not
+    ///   verify.
+    pub fn verify_entry_signature(
+        &self,
+        sequence: u64,
+        pubkey: &PublicKey,
+    ) -> Result<(), AuditError> {
+        let entry = self.get_entry(sequence)?;
+        match (&entry.signature, &entry.signer) {
+            (Some(signature), Some(signer)) if signer == pubkey => {
+                verify_ed25519(&entry.canonical_bytes(), signature, signer)
+            }
+            _ => Err(AuditError::EntryNotSigned { sequence }),
+        }
+    }
+
+    /// Hash and store a prepared entry, advancing the log tail.
+    fn commit(&mut self, mut entry: LogEntry) -> u64 {
+        let seq = entry.sequence;
+        self.next_seq += 1;
+
+        // Compute the entry hash over its leaf bytes (canonical bytes plus
+        // any signature).
+        let leaf_hash = entry.canonical_hash();
         entry.entry_hash = leaf_hash;
 
+        self.last_timestamp = entry.timestamp;
         self.leaf_hashes.push(leaf_hash);
         self.entries.insert(seq, entry);
-        self.last_timestamp = timestamp;
 
-        Ok(seq)
+        seq
     }
 
     // ── Root publishing ───────────────────────────────────────────────────────
@@ -388,13 +718,27 @@ impl MerkleLog {
 
     /// Retrieve a single entry by sequence number.
     ///
+    /// # Errors
+    /// * [`AuditError::Pruned`] — the entry's body was dropped by
+    ///   [`Self::prune_before`].
+    /// * [`AuditError::EntryNotFound`] — no such entry.
+    ///
     /// Complexity: O(log n).
     pub fn get_entry(&self, sequence: u64) -> Result<&LogEntry, AuditError> {
+        if sequence >= 1 && sequence < self.pruned_before {
+            return Err(AuditError::Pruned { sequence });
+        }
         self.entries
             .get(&sequence)
             .ok_or(AuditError::EntryNotFound { sequence })
     }
 
+    /// First sequence whose entry body has not been pruned.
+    #[inline]
+    pub fn pruned_before(&self) -> u64 {
+        self.pruned_before
+    }
+
     /// Retrieve all entries whose sequence number falls in `[from, to]`.
     ///
     /// Complexity: O(k + log n) where k = `to - from + 1`.
@@ -420,6 +764,12 @@ impl MerkleLog {
         &self.checkpoints
     }
 
+    /// Leaf hashes in sequence order, as committed to the Merkle tree.
+    #[inline]
+    pub(crate) fn leaf_hashes(&self) -> &[Digest] {
+        &self.leaf_hashes
+    }
+
     // ── Inclusion proof ───────────────────────────────────────────────────────
 
     /// Generate a Merkle inclusion proof for the entry at `sequence`.
@@ -430,22 +780,51 @@ impl MerkleLog {
     ///
     /// Complexity: O(log n) time and space.
     pub fn inclusion_proof(&self, sequence: u64) -> Result<InclusionProof, AuditError> {
-        if sequence < 1 || sequence > self.leaf_hashes.len() as u64 {
+        if sequence < 1 {
             return Err(AuditError::EntryNotFound { sequence });
         }
         // Convert 1-based sequence to 0-based leaf index.
-        let leaf_index = (sequence - 1) as usize;
-        let tree_size = self.leaf_hashes.len();
-        let leaf_hash = self.leaf_hashes[leaf_index];
+        prove_inclusion_over(&self.leaf_hashes, (sequence - 1) as usize)
+    }
+
+    /// Generate one proof that every entry in `[from, to]` is included in
+    /// the tree whose root is `self.current_root()`.
+    ///
+    /// The proof shares a single audit path across the range, so it is much
+    /// smaller than `to - from + 1` separate inclusion proofs.
+    ///
+    /// # Errors
+    /// * [`AuditError::InvalidRange`] — if `from` is zero or `from > to`.
+    /// * [`AuditError::EntryNotFound`] — if `to` is past the last leaf.
+    ///
+    /// Complexity: O(n) time, O(k + log n) proof size.
+    pub fn range_inclusion_proof(&self, from: u64, to: u64) -> Result<RangeProof, AuditError> {
+        prove_range_over(&self.leaf_hashes, from, to)
+    }
 
-        let siblings = merkle_siblings(&self.leaf_hashes, leaf_index, tree_size);
+    // ── Consistency proof ─────────────────────────────────────────────────────
 
-        Ok(InclusionProof {
-            leaf_index: leaf_index as u64,
-            tree_size: tree_size as u64,
-            leaf_hash,
-            siblings,
-        })
+    /// Prove that the current tree is an append-only extension of the tree
+    /// as it was when it held `old_size` leaves.
+    ///
+    /// The old root is recomputed from the stored leaf hashes, so callers do
+    /// not need to track earlier roots themselves.
+    ///
+    /// # Errors
+    /// * [`AuditError::InvalidSize`] — if `old_size` is zero or larger than
+    ///   the current number of leaves.
+    ///
+    /// Complexity: O(n).
+    pub fn consistency_proof_since(&self, old_size: u64) -> Result<ConsistencyProof, AuditError> {
+        let current = self.leaf_hashes.len() as u64;
+        if old_size == 0 || old_size > current {
+            return Err(AuditError::InvalidSize {
+                requested: old_size,
+                current,
+            });
+        }
+        let old_root = compute_root(&self.leaf_hashes[..old_size as usize]);
+        prove_consistency_over(&self.leaf_hashes, old_root, old_size)
     }
 
     // ── Hash-chain verification ───────────────────────────────────────────────
@@ -477,6 +856,42 @@ impl MerkleLog {
         Ok(())
     }
 
+    /// Scan every live entry for retroactive modification.
+    ///
+    /// Unlike [`Self::verify_chain`], this recomputes each entry's leaf hash
+    /// from its contents, so an edited field is caught even when the stored
+    /// `entry_hash` was left untouched.  Each entry's `prev_hash` is checked
+    /// against its predecessor's recomputed hash (or the zero-hash for
+    /// sequence 1).  Links into a compacted range cannot be checked here;
+    /// the [`CompactionReceipt`] covers those.
+    ///
+    /// # Errors
+    /// [`AuditError::HashChainBroken`] at the first tampered sequence.
+    ///
+    /// Complexity: O(n · L) where L = average entry byte len.
+    pub fn verify_full_chain(&self) -> Result<(), AuditError> {
+        let mut prev: Option<(u64, Digest)> = None;
+        for entry in self.entries.values() {
+            let broken = AuditError::HashChainBroken {
+                at_sequence: entry.sequence,
+            };
+            let computed = entry.canonical_hash();
+            if computed != entry.entry_hash {
+                return Err(broken);
+            }
+            let expected_prev = match prev {
+                Some((seq, hash)) if seq + 1 == entry.sequence => Some(hash),
+                None if entry.sequence == 1 => Some([0u8; 32]),
+                _ => None,
+            };
+            if expected_prev.is_some_and(|hash| hash != entry.prev_hash) {
+                return Err(broken);
+            }
+            prev = Some((entry.sequence, computed));
+        }
+        Ok(())
+    }
+
     // ── Compaction (verifiable deletion) ─────────────────────────────────────
 
     /// Delete entries in `[from_seq, to_seq]` after enforcing retention policy.
@@ -495,6 +910,12 @@ impl MerkleLog {
     /// (`requires_witness_for_deletion`), at least one witness co-signature
     /// must exist before compaction is permitted.
     ///
+    /// ### Checkpoints
+    /// Checkpoints whose tree contains a deleted leaf no longer describe a
+    /// prefix of this log.  They are dropped together with their witness
+    /// endorsements (the receipt's `old_root` records what was removed), so
+    /// a sensitive segment needs fresh witnesses before compacting again.
+    ///
     /// Complexity: O(k log n + n) where k = deleted range size.
     pub fn compact(
         &mut self,
@@ -541,15 +962,26 @@ impl MerkleLog {
             self.entries.remove(&seq);
         }
 
-        // Rebuild leaf_hashes from the remaining entries (preserving order).
+        // Rebuild leaf_hashes from the remaining entries (preserving order),
+        // keeping the hashes of any pruned prefix whose bodies are gone.
         // O(n) — necessary after deletion since the leaf array is contiguous.
-        self.leaf_hashes = self
-            .entries
-            .values()
-            .map(|e| e.entry_hash)
-            .collect::<Vec<_>>();
+        let mut rebuilt = self.leaf_hashes[..self.pruned_leaves].to_vec();
+        rebuilt.extend(self.entries.values().map(|e| e.entry_hash));
+        let unchanged = self
+            .leaf_hashes
+            .iter()
+            .zip(&rebuilt)
+            .take_while(|(old, new)| old == new)
+            .count() as u64;
+        self.leaf_hashes = rebuilt;
+        self.checkpoints.retain(|cp| cp.tree_size <= unchanged);
+        self.witnesses = self
+            .checkpoints
+            .iter()
+            .flat_map(|cp| cp.endorsements.iter().cloned())
+            .collect();
         // Re-hash them as leaves (they were already stored as leaf-hashes).
-        // Note: entry_hash == hash_leaf(canonical), so we reuse directly.
+        // Note: entry_hash == canonical_hash(), so we reuse directly.
 
         let new_root = self.current_root();
         let new_size = self.leaf_hashes.len() as u64;
@@ -563,10 +995,286 @@ impl MerkleLog {
             compacted_at: now,
         })
     }
+
+    // ── Pruning (memory reclamation) ─────────────────────────────────────────
+
+    /// Drop the bodies of all entries with sequence below `seq` to bound
+    /// memory in long-running segments.
+    ///
+    /// Unlike [`Self::compact`] the tree is unchanged: leaf hashes are kept,
+    /// so the current root, inclusion proofs for `seq..` and consistency
+    /// proofs all still verify.  Pruned entries answer
+    /// [`AuditError::Pruned`] on lookup.  The newest entry is always kept so
+    /// the hash chain can be extended.
+    ///
+    /// # Returns
+    /// The Merkle root over the leaves of every pruned entry (the first
+    /// `seq - 1` leaves of an uncompacted log), for anchoring alongside an
+    /// archive of the dropped bodies.  Calling again with a lower `seq` is a
+    /// no-op that returns the same root.
+    ///
+    /// # Errors
+    /// [`AuditError::InvalidSize`] — if `seq` is zero or not below the next
+    /// sequence to be assigned.  The bound is on sequences, not leaves, so a
+    /// compacted log can still be pruned up to its newest entry.
+    ///
+    /// Complexity: O(k log n + p) for k newly pruned entries and a prefix of
+    /// p leaves.
+    pub fn prune_before(&mut self, seq: u64) -> Result<MerkleRoot, AuditError> {
+        if seq == 0 || seq >= self.next_seq {
+            return Err(AuditError::InvalidSize {
+                requested: seq,
+                current: self.next_seq - 1,
+            });
+        }
+        if seq > self.pruned_before {
+            // Live entries line up with the leaves after the pruned prefix,
+            // so the leaf count to drop is the number of entries below `seq`;
+            // compaction may have removed sequences in between.
+            let retained = self.entries.split_off(&seq);
+            let dropped = self.entries.len();
+            self.entries = retained;
+            self.pruned_leaves += dropped;
+            self.pruned_before = seq;
+        }
+        Ok(compute_root(&self.leaf_hashes[..self.pruned_leaves]))
+    }
+
+    // ── Snapshot export / import ─────────────────────────────────────────────
+
+    /// Export the full log state for independent reconstruction.
+    ///
+    /// Complexity: O(n) — clones every entry and leaf hash, plus one root
+    /// computation.
+    pub fn export_state(&self) -> LogSnapshot {
+        LogSnapshot {
+            segment: self.segment.clone(),
+            entries: self.entries.values().cloned().collect(),
+            leaf_hashes: self.leaf_hashes.clone(),
+            root: self.current_root(),
+            checkpoints: self.checkpoints.clone(),
+            witnesses: self.witnesses.clone(),
+            next_seq: self.next_seq,
+            last_timestamp: self.last_timestamp,
+            retention: self.retention.clone(),
+            pruned_before: self.pruned_before,
+            pruned_leaves: self.pruned_leaves as u64,
+        }
+    }
+
+    /// Rebuild a log from a [`LogSnapshot`], verifying it on the way in.
+    ///
+    /// `witness_keys` maps each trusted `witness_id` to its Ed25519 key.
+    ///
+    /// The snapshot is accepted only if:
+    /// * `root` equals the root recomputed from `leaf_hashes`;
+    /// * the `pruned_leaves` prefix is no longer than the sequences below
+    ///   `pruned_before`, and `leaf_hashes` no longer than the sequences
+    ///   below `next_seq`;
+    /// * the live entries, in sequence order, are exactly the leaves after
+    ///   the `pruned_leaves` prefix, all belong to `segment`, and fall within
+    ///   `[pruned_before, next_seq)`;
+    /// * every entry's stored hash matches its contents, its `prev_hash`
+    ///   links hold (see [`Self::verify_full_chain`]) and any signature
+    ///   verifies;
+    /// * every checkpoint root equals the root of the first `tree_size`
+    ///   leaves, each endorsement names its checkpoint's root and size and
+    ///   carries a valid signature from a key in `witness_keys`, and
+    ///   `witnesses` lists exactly those endorsements in order.
+    ///
+    /// # Errors
+    /// [`AuditError::CorruptSnapshot`] if any check fails.
+    ///
+    /// Complexity: O(n · (L + c)) where L = average entry byte len and c =
+    /// number of checkpoints.
+    pub fn from_snapshot(
+        snapshot: LogSnapshot,
+        witness_keys: &[(&str, PublicKey)],
+    ) -> Result<Self, AuditError> {
+        if compute_root(&snapshot.leaf_hashes) != snapshot.root {
+            return Err(AuditError::CorruptSnapshot);
+        }
+        let leaf_count = snapshot.leaf_hashes.len() as u64;
+        if snapshot.pruned_before == 0
+            || snapshot.pruned_before > snapshot.next_seq
+            || snapshot.pruned_leaves >= snapshot.pruned_before
+            || leaf_count >= snapshot.next_seq
+        {
+            return Err(AuditError::CorruptSnapshot);
+        }
+        Self::verify_checkpoints(&snapshot, witness_keys)?;
+
+        let pruned_leaves =
+            usize::try_from(snapshot.pruned_leaves).map_err(|_| AuditError::CorruptSnapshot)?;
+        let live_leaves = snapshot
+            .leaf_hashes
+            .get(pruned_leaves..)
+            .ok_or(AuditError::CorruptSnapshot)?;
+        if live_leaves.len() != snapshot.entries.len() {
+            return Err(AuditError::CorruptSnapshot);
+        }
+
+        let mut entries = BTreeMap::new();
+        let mut last_seq = 0;
+        for (entry, leaf) in snapshot.entries.into_iter().zip(live_leaves) {
+            let in_bounds = entry.sequence > last_seq
+                && entry.sequence >= snapshot.pruned_before
+                && entry.sequence < snapshot.next_seq
+                && entry.timestamp <= snapshot.last_timestamp;
+            if !in_bounds || entry.segment != snapshot.segment || entry.entry_hash != *leaf {
+                return Err(AuditError::CorruptSnapshot);
+            }
+            if let (Some(signature), Some(signer)) = (&entry.signature, &entry.signer) {
+                verify_ed25519(&entry.canonical_bytes(), signature, signer)
+                    .map_err(|_| AuditError::CorruptSnapshot)?;
+            }
+            last_seq = entry.sequence;
+            entries.insert(entry.sequence, entry);
+        }
+
+        let log = Self {
+            segment: snapshot.segment,
+            entries,
+            leaf_hashes: snapshot.leaf_hashes,
+            checkpoints: snapshot.checkpoints,
+            witnesses: snapshot.witnesses,
+            next_seq: snapshot.next_seq,
+            last_timestamp: snapshot.last_timestamp,
+            retention: snapshot.retention,
+            pruned_before: snapshot.pruned_before,
+            pruned_leaves,
+        };
+        log.verify_full_chain()
+            .map_err(|_| AuditError::CorruptSnapshot)?;
+        Ok(log)
+    }
+
+    /// Checkpoint and witness checks for [`Self::from_snapshot`].
+    fn verify_checkpoints(
+        snapshot: &LogSnapshot,
+        witness_keys: &[(&str, PublicKey)],
+    ) -> Result<(), AuditError> {
+        let mut endorsements = Vec::new();
+        for cp in &snapshot.checkpoints {
+            let size = usize::try_from(cp.tree_size).map_err(|_| AuditError::CorruptSnapshot)?;
+            let prefix = snapshot
+                .leaf_hashes
+                .get(..size)
+                .ok_or(AuditError::CorruptSnapshot)?;
+            if compute_root(prefix) != cp.root {
+                return Err(AuditError::CorruptSnapshot);
+            }
+            for sig in &cp.endorsements {
+                if sig.root != cp.root || sig.tree_size != cp.tree_size {
+                    return Err(AuditError::CorruptSnapshot);
+                }
+                let (_, key) = witness_keys
+                    .iter()
+                    .find(|(id, _)| *id == sig.witness_id)
+                    .ok_or(AuditError::CorruptSnapshot)?;
+                let signature: Signature = sig
+                    .signature
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| AuditError::CorruptSnapshot)?;
+                verify_ed25519(&sig.signed_bytes(&snapshot.segment), &signature, key)
+                    .map_err(|_| AuditError::CorruptSnapshot)?;
+                endorsements.push(sig);
+            }
+        }
+        if !endorsements.iter().copied().eq(snapshot.witnesses.iter()) {
+            return Err(AuditError::CorruptSnapshot);
+        }
+        Ok(())
+    }
 }
 
 // ── Sibling-path helper ───────────────────────────────────────────────────────
 
+/// Generate an inclusion proof for the leaf at 0-based `leaf_index` over an
+/// arbitrary leaf-hash slice.
+///
+/// Lets callers that keep their own leaf set (e.g. a contract committing to
+/// a snapshot of its state) produce proofs that verify with
+/// [`InclusionProof::verify`] against [`compute_root`] of the same slice.
+///
+/// # Errors
+/// * [`AuditError::EntryNotFound`] — if `leaf_index` is out of range; the
+///   reported sequence is the 1-based position `leaf_index + 1`.
+///
+/// Complexity: O(n) time, O(log n) proof size.
+pub fn prove_inclusion_over(
+    leaves: &[Digest],
+    leaf_index: usize,
+) -> Result<InclusionProof, AuditError> {
+    let tree_size = leaves.len();
+    if leaf_index >= tree_size {
+        return Err(AuditError::EntryNotFound {
+            sequence: leaf_index as u64 + 1,
+        });
+    }
+    Ok(InclusionProof {
+        leaf_index: leaf_index as u64,
+        tree_size: tree_size as u64,
+        leaf_hash: leaves[leaf_index],
+        siblings: merkle_siblings(leaves, leaf_index, tree_size),
+    })
+}
+
+/// Generate a [`RangeProof`] for the 1-based sequences `[from, to]` over a
+/// leaf-hash slice. See [`MerkleLog::range_inclusion_proof`].
+pub(crate) fn prove_range_over(
+    leaves: &[Digest],
+    from: u64,
+    to: u64,
+) -> Result<RangeProof, AuditError> {
+    if from == 0 || from > to {
+        return Err(AuditError::InvalidRange { from, to });
+    }
+    if to > leaves.len() as u64 {
+        return Err(AuditError::EntryNotFound { sequence: to });
+    }
+
+    let first = (from - 1) as usize;
+    let mut lo = first;
+    let mut hi = (to - 1) as usize;
+    let mut level: Vec<Digest> = leaves.to_vec();
+    let mut siblings = Vec::new();
+    while level.len() > 1 {
+        if lo % 2 == 1 {
+            siblings.push(level[lo - 1]);
+        }
+        if hi % 2 == 0 && hi + 1 < level.len() {
+            siblings.push(level[hi + 1]);
+        }
+        level = pair_up(&level);
+        lo /= 2;
+        hi /= 2;
+    }
+
+    Ok(RangeProof {
+        first_index: first as u64,
+        tree_size: leaves.len() as u64,
+        leaf_hashes: leaves[first..to as usize].to_vec(),
+        siblings,
+    })
+}
+
+/// Hash adjacent pairs of `level`, promoting a trailing lone node.
+fn pair_up(level: &[Digest]) -> Vec<Digest> {
+    let mut next = Vec::with_capacity(level.len().div_ceil(2));
+    let mut i = 0;
+    while i < level.len() {
+        if i + 1 < level.len() {
+            next.push(hash_node(&level[i], &level[i + 1]));
+        } else {
+            next.push(level[i]);
+        }
+        i += 2;
+    }
+    next
+}
+
 /// Compute the sibling-hash path for `leaf_index` in a tree of `tree_size`
 /// leaves.
 ///
@@ -630,6 +1338,32 @@ mod tests {
         assert_eq!(root, entry.entry_hash);
     }
 
+    #[test]
+    fn canonical_hash_known_answer() {
+        let mut log = MerkleLog::new(LogSegmentId::new("healthcare.access").unwrap());
+        log.append(1_700_000_000, "alice", "record.read", "patient:42", "ok")
+            .unwrap();
+        let entry = log.get_entry(1).unwrap();
+
+        assert_eq!(
+            hex::encode(entry.canonical_bytes()),
+            "0000000000000001\
+             000000006553f100\
+             00000005616c696365\
+             0000000b7265636f72642e72656164\
+             0000000a70617469656e743a3432\
+             000000026f6b\
+             0000000000000000000000000000000000000000000000000000000000000000\
+             000000116865616c7468636172652e616363657373"
+        );
+        assert_eq!(
+            hex::encode(entry.canonical_hash()),
+            "887a5cf165211163775d45a8e97d5d1d1d9d6555678b11b1de9c7399ed078906"
+        );
+        assert_eq!(entry.entry_hash, entry.canonical_hash());
+        assert_eq!(log.current_root(), entry.canonical_hash());
+    }
+
     #[test]
     fn hash_chain_is_linked() {
         let mut log = MerkleLog::new(seg());
@@ -660,6 +1394,56 @@ mod tests {
         ));
     }
 
+    fn tampered_log() -> MerkleLog {
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=7u64 {
+            log.append(1_000 + i, "alice", "record.read", "r:1", "ok")
+                .unwrap();
+        }
+        assert!(log.verify_full_chain().is_ok());
+        log
+    }
+
+    #[test]
+    fn full_chain_scan_pinpoints_edited_field() {
+        let mut log = tampered_log();
+        // Edit the actor of entry 4 without touching any hash.
+        log.entries.get_mut(&4).unwrap().actor = String::from("mallory");
+        // The ranged link check is blind to this; the full scan is not.
+        assert!(log.verify_chain(1, 7).is_ok());
+        assert_eq!(
+            log.verify_full_chain().unwrap_err(),
+            AuditError::HashChainBroken { at_sequence: 4 }
+        );
+    }
+
+    #[test]
+    fn full_chain_scan_catches_rehashed_entry_at_successor() {
+        let mut log = tampered_log();
+        // Edit entry 4 and recompute its own hash: the break shows up at 5,
+        // whose prev_hash still commits to the original entry 4.
+        let entry = log.entries.get_mut(&4).unwrap();
+        entry.result = String::from("denied");
+        entry.entry_hash = entry.canonical_hash();
+        assert_eq!(
+            log.verify_full_chain().unwrap_err(),
+            AuditError::HashChainBroken { at_sequence: 5 }
+        );
+    }
+
+    #[test]
+    fn full_chain_scan_skips_compacted_gap() {
+        let mut log = tampered_log();
+        log.compact(2, 3, 10_000, 0).unwrap();
+        assert!(log.verify_full_chain().is_ok());
+
+        log.entries.get_mut(&6).unwrap().prev_hash = [0xAB; 32];
+        assert_eq!(
+            log.verify_full_chain().unwrap_err(),
+            AuditError::HashChainBroken { at_sequence: 6 }
+        );
+    }
+
     #[test]
     fn inclusion_proof_verifies() {
         let mut log = MerkleLog::new(seg());
@@ -673,6 +1457,173 @@ mod tests {
         }
     }
 
+    #[test]
+    fn serialized_inclusion_proof_round_trips_and_verifies() {
+        let mut log = MerkleLog::new(seg());
+        for i in 0..11u64 {
+            log.append(i, "user", "action", "tgt", "ok").unwrap();
+        }
+        let root = log.current_root();
+        for seq in 1..=11u64 {
+            let proof = log.inclusion_proof(seq).unwrap();
+            let bytes = proof.to_bytes();
+            assert_eq!(bytes.len(), proof.serialized_size());
+
+            let decoded = InclusionProof::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded.leaf_index, proof.leaf_index);
+            assert_eq!(decoded.tree_size, proof.tree_size);
+            assert_eq!(decoded.leaf_hash, proof.leaf_hash);
+            assert_eq!(decoded.siblings, proof.siblings);
+            assert!(
+                decoded.verify(&root).is_ok(),
+                "decoded seq={seq} must verify"
+            );
+        }
+
+        // A decoded proof fails exactly where the original does.
+        let mut other = log.inclusion_proof(4).unwrap();
+        other.leaf_hash = [0xEE; 32];
+        let decoded = InclusionProof::from_bytes(&other.to_bytes()).unwrap();
+        assert_eq!(decoded.verify(&root), other.verify(&root));
+    }
+
+    #[test]
+    fn inclusion_proof_from_bytes_rejects_malformed_input() {
+        let mut log = MerkleLog::new(seg());
+        for i in 0..6u64 {
+            log.append(i, "user", "action", "tgt", "ok").unwrap();
+        }
+        let bytes = log.inclusion_proof(2).unwrap().to_bytes();
+
+        for len in 0..bytes.len() {
+            assert_eq!(
+                InclusionProof::from_bytes(&bytes[..len]).unwrap_err(),
+                AuditError::MalformedProof,
+                "truncated to {len} bytes"
+            );
+        }
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            InclusionProof::from_bytes(&trailing).unwrap_err(),
+            AuditError::MalformedProof
+        );
+
+        // A hash count that disagrees with the payload length.
+        let mut miscounted = bytes.clone();
+        miscounted[48..52].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert_eq!(
+            InclusionProof::from_bytes(&miscounted).unwrap_err(),
+            AuditError::MalformedProof
+        );
+
+        // A leaf index outside the tree.
+        let mut out_of_range = bytes;
+        out_of_range[..8].copy_from_slice(&6u64.to_be_bytes());
+        assert_eq!(
+            InclusionProof::from_bytes(&out_of_range).unwrap_err(),
+            AuditError::MalformedProof
+        );
+    }
+
+    #[test]
+    fn prove_inclusion_over_arbitrary_leaves() {
+        let leaves: Vec<Digest> = (0..5u8).map(|i| hash_leaf(&[i])).collect();
+        let root = compute_root(&leaves);
+        for i in 0..leaves.len() {
+            let proof = prove_inclusion_over(&leaves, i).unwrap();
+            assert!(proof.verify(&root).is_ok(), "proof failed for index={i}");
+        }
+        assert_eq!(
+            prove_inclusion_over(&leaves, 5).unwrap_err(),
+            AuditError::EntryNotFound { sequence: 6 }
+        );
+    }
+
+    #[test]
+    fn range_proof_verifies_every_range() {
+        for n in 1..=9u64 {
+            let mut log = MerkleLog::new(seg());
+            for i in 1..=n {
+                log.append(i, "user", "action", "tgt", "ok").unwrap();
+            }
+            let root = log.current_root();
+            for from in 1..=n {
+                for to in from..=n {
+                    let proof = log.range_inclusion_proof(from, to).unwrap();
+                    assert_eq!(proof.leaf_hashes.len() as u64, to - from + 1);
+                    assert!(
+                        proof.verify(&root).is_ok(),
+                        "range [{from}, {to}] failed for n={n}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn range_proof_spanning_full_tree_needs_no_siblings() {
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=7u64 {
+            log.append(i, "user", "action", "tgt", "ok").unwrap();
+        }
+        let proof = log.range_inclusion_proof(1, 7).unwrap();
+        assert!(proof.siblings.is_empty());
+        assert!(proof.verify(&log.current_root()).is_ok());
+    }
+
+    #[test]
+    fn single_entry_range_proof_matches_inclusion_proof() {
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=6u64 {
+            log.append(i, "user", "action", "tgt", "ok").unwrap();
+        }
+        for seq in 1..=6u64 {
+            let range = log.range_inclusion_proof(seq, seq).unwrap();
+            let single = log.inclusion_proof(seq).unwrap();
+            assert_eq!(range.first_index, single.leaf_index);
+            assert_eq!(range.tree_size, single.tree_size);
+            assert_eq!(range.leaf_hashes, alloc::vec![single.leaf_hash]);
+            assert_eq!(range.siblings, single.siblings);
+        }
+    }
+
+    #[test]
+    fn range_proof_rejects_bad_ranges_and_tampering() {
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=5u64 {
+            log.append(i, "user", "action", "tgt", "ok").unwrap();
+        }
+        assert_eq!(
+            log.range_inclusion_proof(3, 2).unwrap_err(),
+            AuditError::InvalidRange { from: 3, to: 2 }
+        );
+        assert_eq!(
+            log.range_inclusion_proof(0, 2).unwrap_err(),
+            AuditError::InvalidRange { from: 0, to: 2 }
+        );
+        assert_eq!(
+            log.range_inclusion_proof(4, 6).unwrap_err(),
+            AuditError::EntryNotFound { sequence: 6 }
+        );
+
+        let root = log.current_root();
+        let proof = log.range_inclusion_proof(2, 4).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.leaf_hashes[1][0] ^= 1;
+        assert!(tampered.verify(&root).is_err());
+
+        let mut shifted = proof.clone();
+        shifted.first_index += 1;
+        assert!(shifted.verify(&root).is_err());
+
+        let mut dropped = proof;
+        dropped.leaf_hashes.pop();
+        assert!(dropped.verify(&root).is_err());
+    }
+
     #[test]
     fn merkle_root_changes_after_append() {
         let mut log = MerkleLog::new(seg());
@@ -709,6 +1660,109 @@ mod tests {
         assert!(matches!(err, AuditError::RetentionPolicyViolation { .. }));
     }
 
+    fn signing_key() -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn append_signed_entry(log: &mut MerkleLog, key: &ed25519_dalek::SigningKey, ts: u64) -> u64 {
+        use ed25519_dalek::Signer;
+        let entry = log
+            .prepare_entry(ts, "alice", "record.read", "patient:42", "ok")
+            .unwrap();
+        let signature = key.sign(&entry.canonical_bytes()).to_bytes();
+        log.append_signed(entry, signature, key.verifying_key().to_bytes())
+            .unwrap()
+    }
+
+    #[test]
+    fn signed_entry_is_accepted_and_verifies() {
+        let key = signing_key();
+        let pubkey = key.verifying_key().to_bytes();
+        let mut log = MerkleLog::new(seg());
+        log.append(1, "u", "a", "t", "ok").unwrap();
+        let seq = append_signed_entry(&mut log, &key, 2);
+
+        assert_eq!(seq, 2);
+        assert!(log.verify_entry_signature(seq, &pubkey).is_ok());
+        assert!(log.verify_chain(1, 2).is_ok());
+
+        // Unsigned entries and foreign keys are reported as not signed.
+        let other = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32])
+            .verifying_key()
+            .to_bytes();
+        assert!(matches!(
+            log.verify_entry_signature(1, &pubkey),
+            Err(AuditError::EntryNotSigned { sequence: 1 })
+        ));
+        assert!(matches!(
+            log.verify_entry_signature(seq, &other),
+            Err(AuditError::EntryNotSigned { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_signature_is_rejected() {
+        use ed25519_dalek::Signer;
+        let key = signing_key();
+        let mut log = MerkleLog::new(seg());
+        let entry = log
+            .prepare_entry(1, "alice", "create", "r:1", "ok")
+            .unwrap();
+
+        // Signature over different bytes (claimed actor changed after signing).
+        let signature = key.sign(b"something else").to_bytes();
+        let err = log
+            .append_signed(entry, signature, key.verifying_key().to_bytes())
+            .unwrap_err();
+        assert_eq!(err, AuditError::InvalidSignature);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn stale_prepared_entry_is_rejected() {
+        use ed25519_dalek::Signer;
+        let key = signing_key();
+        let mut log = MerkleLog::new(seg());
+        let entry = log
+            .prepare_entry(1, "alice", "create", "r:1", "ok")
+            .unwrap();
+        let signature = key.sign(&entry.canonical_bytes()).to_bytes();
+        log.append(1, "bob", "read", "r:1", "ok").unwrap();
+
+        let err = log
+            .append_signed(entry, signature, key.verifying_key().to_bytes())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AuditError::UnexpectedSequence {
+                expected: 2,
+                supplied: 1
+            }
+        );
+    }
+
+    #[test]
+    fn signed_entries_produce_valid_inclusion_proofs() {
+        let key = signing_key();
+        let mut log = MerkleLog::new(seg());
+        for i in 0..6u64 {
+            if i % 2 == 0 {
+                append_signed_entry(&mut log, &key, i);
+            } else {
+                log.append(i, "user", "action", "tgt", "ok").unwrap();
+            }
+        }
+        let root = log.current_root();
+        for seq in 1..=6u64 {
+            let proof = log.inclusion_proof(seq).unwrap();
+            assert!(proof.verify(&root).is_ok(), "proof failed for seq={seq}");
+        }
+
+        // The signature is part of the leaf: an unsigned copy hashes differently.
+        let signed = log.get_entry(1).unwrap();
+        assert_ne!(hash_leaf(&signed.canonical_bytes()), signed.entry_hash);
+    }
+
     #[test]
     fn query_range_returns_correct_entries() {
         let mut log = MerkleLog::new(seg());
@@ -720,4 +1774,406 @@ mod tests {
         assert_eq!(range[0].sequence, 3);
         assert_eq!(range[4].sequence, 7);
     }
+
+    #[test]
+    fn append_batch_matches_sequential_appends() {
+        let actors: Vec<String> = (0..1_000u64).map(|i| alloc::format!("user:{i}")).collect();
+        let batch: Vec<(u64, &str, &str, &str, &str)> = actors
+            .iter()
+            .enumerate()
+            .map(|(i, actor)| (1_000 + i as u64 / 3, actor.as_str(), "read", "r:1", "ok"))
+            .collect();
+
+        let mut sequential = MerkleLog::new(seg());
+        for &(ts, actor, action, target, result) in &batch {
+            sequential
+                .append(ts, actor, action, target, result)
+                .unwrap();
+        }
+
+        let mut batched = MerkleLog::new(seg());
+        let seqs = batched.append_batch(&batch).unwrap();
+
+        assert_eq!(seqs, (1..=1_000u64).collect::<Vec<_>>());
+        assert_eq!(batched.current_root(), sequential.current_root());
+        assert!(batched.verify_chain(1, 1_000).is_ok());
+        let proof = batched.inclusion_proof(500).unwrap();
+        assert!(proof.verify(&sequential.current_root()).is_ok());
+    }
+
+    #[test]
+    fn append_batch_is_atomic_on_out_of_order_timestamp() {
+        let mut log = MerkleLog::new(seg());
+        log.append(10, "u", "a", "t", "ok").unwrap();
+        let root = log.current_root();
+
+        let err = log
+            .append_batch(&[(10, "u", "a", "t", "ok"), (9, "u", "a", "t", "ok")])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AuditError::OutOfOrderTimestamp {
+                sequence: 3,
+                supplied: 9,
+                minimum: 10
+            }
+        );
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.current_root(), root);
+        assert_eq!(log.append_batch(&[]).unwrap(), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn pruned_log_keeps_root_and_proofs() {
+        let mut log = MerkleLog::new(seg());
+        for i in 0..6u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        let prefix_root = log.publish_root(6);
+        for i in 6..10u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        let root = log.current_root();
+
+        assert_eq!(log.prune_before(7).unwrap(), prefix_root);
+        assert_eq!(log.pruned_before(), 7);
+        assert_eq!(log.len(), 4);
+        assert_eq!(log.current_root(), root);
+
+        let proof = log.inclusion_proof(8).unwrap();
+        assert!(proof.verify(&root).is_ok());
+        let consistency = log.consistency_proof_since(6).unwrap();
+        assert_eq!(consistency.root_v1, prefix_root);
+        assert!(consistency.verify().is_ok());
+
+        assert_eq!(
+            log.get_entry(3).unwrap_err(),
+            AuditError::Pruned { sequence: 3 }
+        );
+        assert!(log.get_entry(7).is_ok());
+
+        // The chain still extends from the retained tail.
+        log.append(10, "u", "a", "t", "ok").unwrap();
+        assert!(log.verify_full_chain().is_ok());
+        assert!(log.verify_chain(7, 11).is_ok());
+    }
+
+    #[test]
+    fn prune_before_rejects_out_of_range_and_never_unprunes() {
+        let mut log = MerkleLog::new(seg());
+        for i in 0..4u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        assert_eq!(
+            log.prune_before(5).unwrap_err(),
+            AuditError::InvalidSize {
+                requested: 5,
+                current: 4
+            }
+        );
+        assert!(log.prune_before(0).is_err());
+
+        let root = log.prune_before(4).unwrap();
+        // A lower bound later is a no-op, not a restore.
+        assert_eq!(log.prune_before(2).unwrap(), root);
+        assert_eq!(log.pruned_before(), 4);
+        assert_eq!(log.len(), 1);
+
+        // Compaction after pruning keeps the pruned leaves in the tree.
+        log.append(4, "u", "a", "t", "ok").unwrap();
+        let receipt = log.compact(5, 5, 10_000, 0).unwrap();
+        assert_eq!(receipt.new_size, 4);
+    }
+
+    #[test]
+    fn prune_before_after_compaction_maps_sequences_to_leaves() {
+        let mut log = MerkleLog::new(seg());
+        for i in 0..10u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        log.compact(2, 4, 10_000, 0).unwrap();
+        let root = log.current_root();
+        let prefix: Vec<Digest> = [1u64, 5, 6, 7]
+            .iter()
+            .map(|&seq| log.get_entry(seq).unwrap().entry_hash)
+            .collect();
+
+        // Seven leaves remain, but sequence 8 is still live and prunable.
+        assert_eq!(log.prune_before(8).unwrap(), compute_root(&prefix));
+        assert_eq!(log.pruned_before(), 8);
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.current_root(), root);
+        assert_eq!(
+            log.get_entry(7).unwrap_err(),
+            AuditError::Pruned { sequence: 7 }
+        );
+        assert!(log.get_entry(8).is_ok());
+
+        // The newest entry is kept; nothing past it can be pruned.
+        assert!(log.prune_before(10).is_ok());
+        assert_eq!(
+            log.prune_before(11).unwrap_err(),
+            AuditError::InvalidSize {
+                requested: 11,
+                current: 10
+            }
+        );
+        assert!(log.get_entry(10).is_ok());
+    }
+
+    #[test]
+    fn consistency_proof_since_current_size_is_empty() {
+        let mut log = MerkleLog::new(seg());
+        for i in 0..5u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        let proof = log.consistency_proof_since(5).unwrap();
+        assert!(proof.proof_hashes.is_empty());
+        assert_eq!(proof.root_v1, log.current_root());
+        assert_eq!(proof.root_v2, log.current_root());
+        assert!(proof.verify().is_ok());
+    }
+
+    #[test]
+    fn consistency_proof_since_covers_growth() {
+        let mut log = MerkleLog::new(seg());
+        let mut snapshots = Vec::new();
+        for i in 0..13u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+            snapshots.push((log.len(), log.current_root()));
+        }
+        for (size, root) in snapshots {
+            let proof = log.consistency_proof_since(size).unwrap();
+            assert_eq!(proof.root_v1, root, "old root mismatch for size={size}");
+            assert_eq!(proof.size_v2, 13);
+            assert!(proof.verify().is_ok(), "proof failed for size={size}");
+        }
+    }
+
+    #[test]
+    fn consistency_proof_since_rejects_invalid_size() {
+        let mut log = MerkleLog::new(seg());
+        log.append(1, "u", "a", "t", "ok").unwrap();
+        log.append(2, "u", "a", "t", "ok").unwrap();
+
+        assert_eq!(
+            log.consistency_proof_since(0).unwrap_err(),
+            AuditError::InvalidSize {
+                requested: 0,
+                current: 2
+            }
+        );
+        assert_eq!(
+            log.consistency_proof_since(3).unwrap_err(),
+            AuditError::InvalidSize {
+                requested: 3,
+                current: 2
+            }
+        );
+    }
+
+    #[test]
+    fn snapshot_round_trip_keeps_proofs_valid() {
+        let key = signing_key();
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=4u64 {
+            log.append(i, "alice", "record.read", "patient:42", "ok")
+                .unwrap();
+        }
+        append_signed_entry(&mut log, &key, 5);
+        log.append(6, "bob", "record.write", "patient:7", "ok")
+            .unwrap();
+        let root = log.publish_root(6);
+        let proof = log.inclusion_proof(3).unwrap();
+
+        let snapshot = log.export_state();
+        assert_eq!(snapshot.root, root);
+        assert_eq!(snapshot.entries.len(), 6);
+
+        let mut imported = MerkleLog::from_snapshot(snapshot, &[]).unwrap();
+        assert_eq!(imported, log);
+        assert_eq!(imported.current_root(), root);
+        assert!(proof.verify(&imported.current_root()).is_ok());
+        assert!(imported
+            .verify_entry_signature(5, &key.verifying_key().to_bytes())
+            .is_ok());
+
+        // The rebuilt log keeps extending the same chain.
+        imported
+            .append(7, "carol", "access.grant", "patient:42", "ok")
+            .unwrap();
+        assert!(imported.verify_full_chain().is_ok());
+    }
+
+    #[test]
+    fn snapshot_of_pruned_and_compacted_log_round_trips() {
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=8u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        log.prune_before(3).unwrap();
+        log.compact(5, 6, 10_000, 0).unwrap();
+        let proof = log.inclusion_proof(1).unwrap();
+
+        let imported = MerkleLog::from_snapshot(log.export_state(), &[]).unwrap();
+        assert_eq!(imported, log);
+        assert!(proof.verify(&imported.current_root()).is_ok());
+        assert_eq!(
+            imported.get_entry(2).unwrap_err(),
+            AuditError::Pruned { sequence: 2 }
+        );
+    }
+
+    #[test]
+    fn corrupt_snapshots_are_rejected() {
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=4u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        let good = log.export_state();
+        let reject = |snapshot: LogSnapshot| {
+            assert_eq!(
+                MerkleLog::from_snapshot(snapshot, &[]).unwrap_err(),
+                AuditError::CorruptSnapshot
+            );
+        };
+
+        let mut wrong_root = good.clone();
+        wrong_root.root[0] ^= 1;
+        reject(wrong_root);
+
+        // Editing an entry breaks its hash even though the spine is intact.
+        let mut edited = good.clone();
+        edited.entries[1].target = "patient:99".into();
+        reject(edited);
+
+        // Rehashing the edit moves it off its leaf.
+        let mut rehashed = good.clone();
+        rehashed.entries[1].target = "patient:99".into();
+        rehashed.entries[1].entry_hash = rehashed.entries[1].canonical_hash();
+        reject(rehashed);
+
+        let mut missing_entry = good.clone();
+        missing_entry.entries.pop();
+        reject(missing_entry);
+
+        let mut bad_prefix = good.clone();
+        bad_prefix.pruned_leaves = 9;
+        reject(bad_prefix);
+
+        let mut foreign = good.clone();
+        foreign.segment = LogSegmentId::new("other").unwrap();
+        reject(foreign);
+
+        let mut stale_tail = good.clone();
+        stale_tail.next_seq = 4;
+        reject(stale_tail);
+
+        assert!(MerkleLog::from_snapshot(good, &[]).is_ok());
+    }
+
+    fn witness(
+        key: &ed25519_dalek::SigningKey,
+        id: &str,
+        root: MerkleRoot,
+        tree_size: u64,
+    ) -> WitnessSignature {
+        use ed25519_dalek::Signer;
+        let mut sig = WitnessSignature {
+            witness_id: id.into(),
+            root,
+            tree_size,
+            signed_at: 100,
+            signature: Vec::new(),
+        };
+        sig.signature = key.sign(&sig.signed_bytes(&seg())).to_bytes().to_vec();
+        sig
+    }
+
+    #[test]
+    fn snapshot_checkpoints_and_witnesses_are_verified() {
+        let key = signing_key();
+        let keys = [("w1", key.verifying_key().to_bytes())];
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=3u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        let root = log.publish_root(3);
+        log.add_witness(witness(&key, "w1", root, 3)).unwrap();
+        log.append(4, "u", "a", "t", "ok").unwrap();
+        log.publish_root(4);
+        let good = log.export_state();
+        assert_eq!(MerkleLog::from_snapshot(good.clone(), &keys).unwrap(), log);
+
+        let reject = |snapshot: LogSnapshot, keys: &[(&str, PublicKey)]| {
+            assert_eq!(
+                MerkleLog::from_snapshot(snapshot, keys).unwrap_err(),
+                AuditError::CorruptSnapshot
+            );
+        };
+
+        // A forged checkpoint root, or one past the spine, is caught.
+        let mut forged = good.clone();
+        forged.checkpoints[1].root[0] ^= 1;
+        reject(forged, &keys);
+        let mut oversized = good.clone();
+        oversized.checkpoints[1].tree_size = 5;
+        reject(oversized, &keys);
+
+        // Witnesses must be known, sign what they endorse, and match the list.
+        reject(good.clone(), &[]);
+        let other = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        reject(good.clone(), &[("w1", other.verifying_key().to_bytes())]);
+        let mut bad_sig = good.clone();
+        bad_sig.checkpoints[0].endorsements[0].signature[0] ^= 1;
+        bad_sig.witnesses[0].signature[0] ^= 1;
+        reject(bad_sig, &keys);
+        let mut moved = good.clone();
+        let endorsement = moved.checkpoints[0].endorsements.pop().unwrap();
+        moved.checkpoints[1].endorsements.push(endorsement);
+        reject(moved, &keys);
+        let mut extra = good.clone();
+        extra.witnesses.push(extra.witnesses[0].clone());
+        reject(extra, &keys);
+    }
+
+    #[test]
+    fn snapshot_pruning_bounds_must_agree() {
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=5u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+        }
+        log.prune_before(3).unwrap();
+        let good = log.export_state();
+
+        // Two pruned leaves cannot sit below a single pruned sequence.
+        let mut inconsistent = good.clone();
+        inconsistent.pruned_before = 2;
+        assert_eq!(
+            MerkleLog::from_snapshot(inconsistent, &[]).unwrap_err(),
+            AuditError::CorruptSnapshot
+        );
+        assert!(MerkleLog::from_snapshot(good, &[]).is_ok());
+    }
+
+    #[test]
+    fn compaction_drops_checkpoints_it_invalidates() {
+        let key = signing_key();
+        let keys = [("w1", key.verifying_key().to_bytes())];
+        let mut log = MerkleLog::new(seg());
+        for i in 1..=4u64 {
+            log.append(i, "u", "a", "t", "ok").unwrap();
+            if i == 2 {
+                log.publish_root(i);
+            }
+        }
+        let root = log.publish_root(4);
+        log.add_witness(witness(&key, "w1", root, 4)).unwrap();
+
+        log.compact(3, 3, 10_000, 0).unwrap();
+        assert_eq!(log.checkpoints().len(), 1);
+        assert_eq!(log.checkpoints()[0].tree_size, 2);
+        assert_eq!(log.witness_count(), 0);
+        assert!(MerkleLog::from_snapshot(log.export_state(), &keys).is_ok());
+    }
 }
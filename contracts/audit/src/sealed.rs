@@ -0,0 +1,149 @@
+/// Immutable, shareable form of a [`MerkleLog`] for concurrent proof generation.
+///
+/// On-chain everything is single-threaded, but off-chain monitors often need
+/// to serve many inclusion and consistency proofs in parallel against a log
+/// that no longer changes.  `SealedMerkleLog` freezes a `MerkleLog` and only
+/// exposes `&self` methods with no interior mutability, so it is
+/// `Send + Sync` and can be shared behind an `Arc` without locking.
+///
+/// Appends, witness collection and compaction are **not** available on the
+/// sealed form; keep the original `MerkleLog` for writers and seal a snapshot
+/// (e.g. a clone) for readers.
+///
+/// # Time and Space Complexity
+///
+/// | Operation               | Time | Space        |
+/// |-------------------------|------|--------------|
+/// | `from_log`              | O(n) | O(1) extra   |
+/// | `root`                  | O(1) | O(1)         |
+/// | `inclusion_proof`       | O(n) | O(log n)     |
+/// | `range_inclusion_proof` | O(n) | O(k + log n) |
+/// | `consistency_proof`     | O(n) | O(log n)     |
+use alloc::vec::Vec;
+
+use crate::{
+    consistency::{prove_consistency_over, ConsistencyProof},
+    merkle_log::{InclusionProof, MerkleLog, MerkleRoot, RangeProof, RootCheckpoint},
+    types::{AuditError, LogEntry, LogSegmentId},
+};
+
+/// A read-only [`MerkleLog`] snapshot with its root computed once at seal time.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SealedMerkleLog {
+    log: MerkleLog,
+    root: MerkleRoot,
+}
+
+// Compile-time guarantee that sealed logs can be shared across threads.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SealedMerkleLog>();
+};
+
+impl SealedMerkleLog {
+    /// Seal `log`, consuming it so no further appends are possible.
+    ///
+    /// Complexity: O(n) to compute the root once.
+    pub fn from_log(log: MerkleLog) -> Self {
+        let root = log.current_root();
+        Self { log, root }
+    }
+
+    /// The segment this log covers.
+    #[inline]
+    pub fn segment(&self) -> &LogSegmentId {
+        &self.log.segment
+    }
+
+    /// The Merkle root of the sealed tree (cached).
+    #[inline]
+    pub fn root(&self) -> MerkleRoot {
+        self.root
+    }
+
+    /// Number of leaves committed to the sealed tree.
+    #[inline]
+    pub fn tree_size(&self) -> u64 {
+        self.log.leaf_hashes().len() as u64
+    }
+
+    /// Total number of live entries.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.log.len()
+    }
+
+    /// True when the sealed log contains no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.log.is_empty()
+    }
+
+    /// First sequence whose entry body was not pruned before sealing.
+    #[inline]
+    pub fn pruned_before(&self) -> u64 {
+        self.log.pruned_before()
+    }
+
+    /// Retrieve a single entry by sequence number.
+    pub fn get_entry(&self, sequence: u64) -> Result<&LogEntry, AuditError> {
+        self.log.get_entry(sequence)
+    }
+
+    /// Retrieve all entries whose sequence number falls in `[from, to]`.
+    pub fn query_range(&self, from: u64, to: u64) -> Vec<&LogEntry> {
+        self.log.query_range(from, to)
+    }
+
+    /// Checkpoints published before the log was sealed.
+    pub fn checkpoints(&self) -> &[RootCheckpoint] {
+        self.log.checkpoints()
+    }
+
+    /// Generate an inclusion proof for `sequence` against [`Self::root`].
+    pub fn inclusion_proof(&self, sequence: u64) -> Result<InclusionProof, AuditError> {
+        self.log.inclusion_proof(sequence)
+    }
+
+    /// Generate one proof covering every entry in `[from, to]`.
+    /// See [`MerkleLog::range_inclusion_proof`].
+    pub fn range_inclusion_proof(&self, from: u64, to: u64) -> Result<RangeProof, AuditError> {
+        self.log.range_inclusion_proof(from, to)
+    }
+
+    /// Prove that the sealed tree extends the snapshot `(root_v1, size_v1)`.
+    ///
+    /// # Errors
+    ///
+    /// * [`AuditError::InvalidConsistencyProof`] — if `size_v1` exceeds the
+    ///   sealed tree size.
+    pub fn consistency_proof(
+        &self,
+        root_v1: MerkleRoot,
+        size_v1: u64,
+    ) -> Result<ConsistencyProof, AuditError> {
+        prove_consistency_over(self.log.leaf_hashes(), root_v1, size_v1)
+    }
+
+    /// Prove that the sealed tree extends its own earlier `old_size`-leaf
+    /// prefix. See [`MerkleLog::consistency_proof_since`].
+    pub fn consistency_proof_since(&self, old_size: u64) -> Result<ConsistencyProof, AuditError> {
+        self.log.consistency_proof_since(old_size)
+    }
+
+    /// Verify the hash chain for live entries from `from_seq` to `to_seq`.
+    pub fn verify_chain(&self, from_seq: u64, to_seq: u64) -> Result<(), AuditError> {
+        self.log.verify_chain(from_seq, to_seq)
+    }
+
+    /// Recompute and check every live entry. See [`MerkleLog::verify_full_chain`].
+    pub fn verify_full_chain(&self) -> Result<(), AuditError> {
+        self.log.verify_full_chain()
+    }
+}
+
+impl From<MerkleLog> for SealedMerkleLog {
+    fn from(log: MerkleLog) -> Self {
+        Self::from_log(log)
+    }
+}
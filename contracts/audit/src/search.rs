@@ -26,21 +26,51 @@
 /// * Forward security (hiding future insertions from past tokens) can be added
 ///   by periodically re-keying; this is left to the application layer.
 ///
+/// ### Prefix queries
+///
+/// `SearchEngine::query_prefix` answers "keyword starts with P" without
+/// scanning plaintext: at index time every keyword is also inserted under a
+/// token for each of its prefixes (up to [`MAX_PREFIX_LEN`] bytes), derived
+/// from a separate prefix subkey so prefix tokens never coincide with exact
+/// ones.  The trade-off is extra leakage to an index observer:
+///
+/// * the number of prefix buckets an entry joins reveals the combined length
+///   of its keywords;
+/// * entries whose keywords share a prefix share a bucket, so the observer
+///   learns prefix-sharing relationships (e.g. that two targets begin with
+///   the same `"patient:"` namespace), though not the prefix itself;
+/// * a prefix query reveals its full result set, as an exact query does.
+///
+/// Keywords that share no prefix with the query remain hidden, and plaintext
+/// is never stored.  Engines that cannot accept this leakage should stick to
+/// exact `query` calls.
+///
+/// ### Deletion
+///
+/// The index keeps a reverse map from sequence number to the tokens it was
+/// indexed under, so a single entry (e.g. a redacted one) can be removed from
+/// every posting list in O(k) without scanning the whole index.  The reverse
+/// map reveals nothing beyond the posting lists themselves.
+///
 /// ### NOT provided
 ///
 /// * Post-quantum security.
-/// * Dynamic deletion of individual index entries (compaction of the underlying
-///   log invalidates entries but the index must be rebuilt separately).
 ///
 /// # Complexity
 ///
-/// | Operation        | Time     | Space  |
-/// |------------------|----------|--------|
-/// | `index_entry`    | O(k)     | O(k)   |
-/// | `search`         | O(m)     | O(m)   |
-/// | `gen_token`      | O(1)     | O(1)   |
+/// | Operation        | Time       | Space  |
+/// |------------------|------------|--------|
+/// | `index_entry`    | O(k · L)   | O(k·L) |
+/// | `deindex_entry`  | O(k · L)   | O(1)   |
+/// | `search`         | O(m)       | O(m)   |
+/// | `query_prefix`   | O(m)       | O(m)   |
+/// | `query_and`      | O(t · m)   | O(m)   |
+/// | `query_or`       | O(M log M) | O(M)   |
+/// | `gen_token`      | O(1)       | O(1)   |
 ///
-/// where k = number of keywords per entry, m = number of matching entries.
+/// where k = number of keywords per entry, L = keyword length (capped at
+/// [`MAX_PREFIX_LEN`]), m = number of matching entries, t = number of query
+/// keywords and M = total matches across all of them.
 use alloc::{collections::BTreeMap, vec::Vec};
 
 use hmac::{Hmac, Mac};
@@ -53,6 +83,17 @@ use crate::types::AuditError;
 /// A 32-byte HMAC-SHA256 search token derived from a keyword + key.
 pub type SearchToken = [u8; 32];
 
+/// Longest keyword prefix, in bytes, that is indexed for prefix queries.
+///
+/// Long keywords are bucketed under their prefixes up to this length only, so
+/// a longer prefix query cannot be answered and matches nothing.  64 bytes
+/// covers a full Stellar strkey address.
+pub const MAX_PREFIX_LEN: usize = 64;
+
+/// Domain label for the prefix subkey.  Not valid UTF-8, so no keyword passed
+/// to [`SearchKey::token_for`] can produce the same MAC input.
+const PREFIX_KEY_LABEL: &[u8] = b"\xffaudit.search.prefix";
+
 // ── SearchKey ─────────────────────────────────────────────────────────────────
 
 /// A symmetric key used to derive search tokens.
@@ -85,9 +126,18 @@ impl SearchKey {
     ///
     /// Complexity: O(|keyword|) — single HMAC call.
     pub fn token_for(&self, keyword: &str) -> SearchToken {
+        self.mac(keyword.as_bytes())
+    }
+
+    /// Derive the independent subkey used for prefix-bucket tokens.
+    fn prefix_key(&self) -> SearchKey {
+        SearchKey(self.mac(PREFIX_KEY_LABEL))
+    }
+
+    fn mac(&self, data: &[u8]) -> SearchToken {
         let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.0)
             .expect("HMAC accepts any key length ≥ 0");
-        mac.update(keyword.as_bytes());
+        mac.update(data);
         mac.finalize().into_bytes().into()
     }
 }
@@ -104,6 +154,8 @@ impl SearchKey {
 /// ```text
 /// BTreeMap<SearchToken, Vec<u64>>
 ///          ^^ 32 bytes            ^^ list of matching sequence numbers
+/// BTreeMap<u64, Vec<SearchToken>>
+///          ^^ sequence number     ^^ tokens it is indexed under
 /// ```
 ///
 /// Using a `BTreeMap` rather than `HashMap` ensures a deterministic iteration
@@ -111,6 +163,8 @@ impl SearchKey {
 pub struct ForwardIndex {
     /// token → sorted list of sequence numbers that match.
     index: BTreeMap<SearchToken, Vec<u64>>,
+    /// sequence number → tokens it was indexed under, for deletion.
+    tokens_by_seq: BTreeMap<u64, Vec<SearchToken>>,
 }
 
 impl ForwardIndex {
@@ -118,6 +172,7 @@ impl ForwardIndex {
     pub fn new() -> Self {
         Self {
             index: BTreeMap::new(),
+            tokens_by_seq: BTreeMap::new(),
         }
     }
 
@@ -134,7 +189,32 @@ impl ForwardIndex {
             // Insert in sorted order to support range-based sub-queries.
             match bucket.binary_search(&sequence) {
                 Ok(_) => { /* duplicate — idempotent */ }
-                Err(pos) => bucket.insert(pos, sequence),
+                Err(pos) => {
+                    bucket.insert(pos, sequence);
+                    self.tokens_by_seq.entry(sequence).or_default().push(token);
+                }
+            }
+        }
+    }
+
+    /// Remove `sequence` from every posting list it appears in.
+    ///
+    /// Unknown sequences are ignored.  Buckets left empty are dropped.
+    ///
+    /// Complexity: O(k · (log I + m)) where k = tokens the entry was
+    /// indexed under.
+    pub fn deindex_entry(&mut self, sequence: u64) {
+        let Some(tokens) = self.tokens_by_seq.remove(&sequence) else {
+            return;
+        };
+        for token in tokens {
+            if let Some(bucket) = self.index.get_mut(&token) {
+                if let Ok(pos) = bucket.binary_search(&sequence) {
+                    bucket.remove(pos);
+                }
+                if bucket.is_empty() {
+                    self.index.remove(&token);
+                }
             }
         }
     }
@@ -148,18 +228,58 @@ impl ForwardIndex {
         self.index.get(token).cloned().unwrap_or_default()
     }
 
+    /// Return the sequence numbers matching **every** token in `tokens`.
+    ///
+    /// Returns an empty list when `tokens` is empty or any token is absent.
+    ///
+    /// Complexity: O(t · log I + m₀ · t · log m) where m₀ is the shortest
+    /// posting list.
+    pub fn search_all(&self, tokens: &[SearchToken]) -> Vec<u64> {
+        let mut lists: Vec<&Vec<u64>> = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            match self.index.get(token) {
+                Some(bucket) => lists.push(bucket),
+                None => return Vec::new(),
+            }
+        }
+        lists.sort_by_key(|bucket| bucket.len());
+
+        let Some((shortest, rest)) = lists.split_first() else {
+            return Vec::new();
+        };
+        shortest
+            .iter()
+            .copied()
+            .filter(|seq| rest.iter().all(|bucket| bucket.binary_search(seq).is_ok()))
+            .collect()
+    }
+
+    /// Return the sequence numbers matching **any** token in `tokens`,
+    /// sorted ascending without duplicates.
+    ///
+    /// Complexity: O(t · log I + M log M) where M is the total posting count.
+    pub fn search_any(&self, tokens: &[SearchToken]) -> Vec<u64> {
+        let mut hits: Vec<u64> = tokens
+            .iter()
+            .filter_map(|token| self.index.get(token))
+            .flatten()
+            .copied()
+            .collect();
+        hits.sort_unstable();
+        hits.dedup();
+        hits
+    }
+
     /// Remove all index entries for sequences in `removed`.
     ///
     /// Called after compaction to keep the index consistent with the live log.
     ///
-    /// Complexity: O(|removed| · log I · log m) — binary search + removal per
-    /// sequence in each bucket.
+    /// Complexity: O(|removed| · k · (log I + m)) — one
+    /// [`deindex_entry`](Self::deindex_entry) per removed sequence.
     pub fn purge_sequences(&mut self, removed: &[u64]) {
-        for bucket in self.index.values_mut() {
-            bucket.retain(|seq| !removed.contains(seq));
+        for &sequence in removed {
+            self.deindex_entry(sequence);
         }
-        // Drop empty buckets to avoid unbounded growth.
-        self.index.retain(|_, bucket| !bucket.is_empty());
     }
 
     /// Number of distinct tokens currently indexed.
@@ -229,23 +349,39 @@ fn push_nonempty(vec: &mut Vec<alloc::string::String>, s: &str) {
     }
 }
 
+/// Every non-empty prefix of `keyword` ending on a char boundary, up to
+/// [`MAX_PREFIX_LEN`] bytes, including the keyword itself when short enough.
+fn keyword_prefixes(keyword: &str) -> impl Iterator<Item = &str> {
+    keyword
+        .char_indices()
+        .map(|(i, c)| i + c.len_utf8())
+        .take_while(|&end| end <= MAX_PREFIX_LEN)
+        .map(move |end| &keyword[..end])
+}
+
 // ── SearchEngine ─────────────────────────────────────────────────────────────
 
 /// High-level API combining a [`SearchKey`] and a [`ForwardIndex`].
 ///
 /// Maintains the index and exposes `index_entry` / `query` / `purge` without
-/// exposing raw token arithmetic to callers.
+/// exposing raw token arithmetic to callers.  Prefix buckets live in a second
+/// [`ForwardIndex`] so that `token_count` / `entry_count` keep describing the
+/// exact-keyword index.
 pub struct SearchEngine {
     key: SearchKey,
     index: ForwardIndex,
+    prefix_key: SearchKey,
+    prefix_index: ForwardIndex,
 }
 
 impl SearchEngine {
     /// Create a new engine with the given search key.
     pub fn new(key: SearchKey) -> Self {
         Self {
+            prefix_key: key.prefix_key(),
             key,
             index: ForwardIndex::new(),
+            prefix_index: ForwardIndex::new(),
         }
     }
 
@@ -253,7 +389,11 @@ impl SearchEngine {
     ///
     /// `extra_keywords` allows callers to supplement the default extraction.
     ///
-    /// Complexity: O(k · log I) where k = total keyword count.
+    /// Each keyword is also bucketed under its prefixes for
+    /// [`query_prefix`](Self::query_prefix).
+    ///
+    /// Complexity: O(k · L · log I) where k = total keyword count and L the
+    /// (capped) keyword length.
     pub fn index_entry(
         &mut self,
         sequence: u64,
@@ -270,6 +410,13 @@ impl SearchEngine {
 
         let tokens: Vec<SearchToken> = kws.iter().map(|kw| self.key.token_for(kw)).collect();
         self.index.index_entry(sequence, &tokens);
+
+        let prefix_tokens: Vec<SearchToken> = kws
+            .iter()
+            .flat_map(|kw| keyword_prefixes(kw))
+            .map(|prefix| self.prefix_key.token_for(prefix))
+            .collect();
+        self.prefix_index.index_entry(sequence, &prefix_tokens);
     }
 
     /// Search for entries matching `keyword`.
@@ -283,11 +430,53 @@ impl SearchEngine {
         self.index.search(&token)
     }
 
+    /// Search for entries with any keyword starting with `prefix`, sorted
+    /// ascending.  `"patient:4"` finds entries indexed under `"patient:42"`.
+    ///
+    /// An empty prefix, or one longer than [`MAX_PREFIX_LEN`] bytes, matches
+    /// nothing.  See the module docs for the leakage this index adds.
+    ///
+    /// Complexity: O(log P + m) where P = distinct prefix tokens.
+    pub fn query_prefix(&self, prefix: &str) -> Vec<u64> {
+        if prefix.is_empty() || prefix.len() > MAX_PREFIX_LEN {
+            return Vec::new();
+        }
+        self.prefix_index.search(&self.prefix_key.token_for(prefix))
+    }
+
+    /// Search for entries matching **all** of `keywords`, sorted ascending.
+    ///
+    /// An empty `keywords` slice matches nothing.
+    pub fn query_and(&self, keywords: &[&str]) -> Vec<u64> {
+        self.index.search_all(&self.tokens_for(keywords))
+    }
+
+    /// Search for entries matching **any** of `keywords`, sorted ascending.
+    ///
+    /// An empty `keywords` slice matches nothing.
+    pub fn query_or(&self, keywords: &[&str]) -> Vec<u64> {
+        self.index.search_any(&self.tokens_for(keywords))
+    }
+
+    fn tokens_for(&self, keywords: &[&str]) -> Vec<SearchToken> {
+        keywords.iter().map(|kw| self.key.token_for(kw)).collect()
+    }
+
+    /// Remove a single entry (e.g. a redacted one) from the index, so it no
+    /// longer appears in any query.
+    ///
+    /// Complexity: O(k · (log I + m)).
+    pub fn deindex_entry(&mut self, seq: u64) {
+        self.index.deindex_entry(seq);
+        self.prefix_index.deindex_entry(seq);
+    }
+
     /// Remove compacted sequences from the index.
     ///
-    /// Complexity: O(|removed| · log I · log m).
+    /// Complexity: O(|removed| · k · (log I + m)).
     pub fn purge(&mut self, removed_sequences: &[u64]) {
         self.index.purge_sequences(removed_sequences);
+        self.prefix_index.purge_sequences(removed_sequences);
     }
 
     /// Number of distinct tokens in the index.
@@ -338,6 +527,39 @@ mod tests {
         assert!(eng.query("unknown:xyz").is_empty());
     }
 
+    #[test]
+    fn multi_keyword_queries_handle_partial_overlap() {
+        let mut eng = engine();
+        eng.index_entry(1, "alice", "record.read", "patient:1", "ok", &[]);
+        eng.index_entry(2, "bob", "record.read", "patient:2", "ok", &[]);
+        eng.index_entry(3, "alice", "record.write", "patient:1", "ok", &[]);
+        eng.index_entry(4, "alice", "record.read", "patient:2", "denied", &[]);
+
+        assert_eq!(eng.query_and(&["alice", "record.read"]), vec![1, 4]);
+        assert_eq!(
+            eng.query_and(&["record.read", "alice", "patient:2"]),
+            vec![4]
+        );
+        assert_eq!(eng.query_and(&["alice"]), eng.query("alice"));
+        assert_eq!(eng.query_or(&["bob", "record.write"]), vec![2, 3]);
+        assert_eq!(eng.query_or(&["patient:1", "alice"]), vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn multi_keyword_queries_with_missing_or_no_keywords() {
+        let mut eng = engine();
+        eng.index_entry(1, "alice", "record.read", "patient:1", "ok", &[]);
+        eng.index_entry(2, "bob", "record.read", "patient:1", "ok", &[]);
+
+        // A keyword that indexes nothing empties an AND and is ignored by OR.
+        assert!(eng.query_and(&["alice", "nobody"]).is_empty());
+        assert_eq!(eng.query_or(&["nobody", "bob"]), vec![2]);
+        assert!(eng.query_or(&["nobody"]).is_empty());
+
+        assert!(eng.query_and(&[]).is_empty());
+        assert!(eng.query_or(&[]).is_empty());
+    }
+
     #[test]
     fn purge_removes_compacted_sequences() {
         let mut eng = engine();
@@ -352,6 +574,96 @@ mod tests {
         assert_eq!(hits, vec![3]);
     }
 
+    #[test]
+    fn deindex_entry_removes_it_from_every_query() {
+        let mut eng = engine();
+        eng.index_entry(1, "alice", "record.read", "patient:1", "ok", &[]);
+        eng.index_entry(2, "bob", "record.read", "patient:1", "ok", &["urgent"]);
+        eng.index_entry(3, "alice", "record.write", "patient:2", "ok", &[]);
+        let before = eng.entry_count();
+
+        eng.deindex_entry(2);
+
+        for kw in ["bob", "record.read", "record", "patient:1", "ok", "urgent"] {
+            assert!(!eng.query(kw).contains(&2), "seq 2 still found by {kw}");
+        }
+        assert_eq!(eng.query("record.read"), vec![1]);
+        assert_eq!(eng.query("record"), vec![1, 3]);
+        assert_eq!(eng.query("ok"), vec![1, 3]);
+        assert_eq!(eng.query_or(&["alice", "bob"]), vec![1, 3]);
+        assert!(eng.query_and(&["bob", "patient:1"]).is_empty());
+        // "bob" and "urgent" only matched seq 2, so their buckets are gone.
+        assert!(eng.query("bob").is_empty());
+        assert_eq!(eng.entry_count(), before - 6);
+
+        // Deindexing again, or an unknown sequence, is a no-op.
+        eng.deindex_entry(2);
+        eng.deindex_entry(99);
+        assert_eq!(eng.entry_count(), before - 6);
+    }
+
+    #[test]
+    fn deindexed_sequence_can_be_reindexed() {
+        let mut eng = engine();
+        eng.index_entry(1, "alice", "create", "r:1", "ok", &[]);
+        let tokens = eng.token_count();
+        eng.deindex_entry(1);
+        assert_eq!(eng.token_count(), 0);
+
+        eng.index_entry(1, "carol", "create", "r:1", "ok", &[]);
+        assert_eq!(eng.query("carol"), vec![1]);
+        assert!(eng.query("alice").is_empty());
+        assert_eq!(eng.token_count(), tokens);
+    }
+
+    #[test]
+    fn prefix_query_matches_several_targets() {
+        let mut eng = engine();
+        eng.index_entry(1, "alice", "record.read", "patient:42", "ok", &[]);
+        eng.index_entry(2, "bob", "record.read", "patient:4", "ok", &[]);
+        eng.index_entry(3, "alice", "record.write", "patient:407", "ok", &[]);
+        eng.index_entry(4, "carol", "record.read", "patient:5", "ok", &[]);
+
+        // Whole-token search still only matches exactly.
+        assert_eq!(eng.query("patient:4"), vec![2]);
+        assert_eq!(eng.query_prefix("patient:4"), vec![1, 2, 3]);
+        assert_eq!(eng.query_prefix("patient:40"), vec![3]);
+        assert_eq!(eng.query_prefix("patient:"), vec![1, 2, 3, 4]);
+        // A keyword is a prefix of itself, and any indexed field qualifies.
+        assert_eq!(eng.query_prefix("patient:42"), vec![1]);
+        assert_eq!(eng.query_prefix("al"), vec![1, 3]);
+
+        // Prefix buckets follow the exact index on deletion.
+        eng.deindex_entry(3);
+        assert_eq!(eng.query_prefix("patient:4"), vec![1, 2]);
+        eng.purge(&[1]);
+        assert_eq!(eng.query_prefix("patient:4"), vec![2]);
+    }
+
+    #[test]
+    fn prefix_query_matching_nothing() {
+        let mut eng = engine();
+        eng.index_entry(1, "alice", "record.read", "patient:42", "ok", &[]);
+
+        assert!(eng.query_prefix("patient:5").is_empty());
+        assert!(eng.query_prefix("patient:420").is_empty());
+        assert!(eng.query_prefix("").is_empty());
+
+        // Prefix tokens come from their own subkey, so exact lookups of a
+        // prefix and prefix lookups of unrelated text stay disjoint.
+        assert!(eng.query("pat").is_empty());
+        assert_ne!(
+            SearchKey::test_key().token_for("pat"),
+            SearchKey::test_key().prefix_key().token_for("pat")
+        );
+
+        let long = "x".repeat(MAX_PREFIX_LEN + 8);
+        eng.index_entry(2, "sys", "noop", &long, "ok", &[]);
+        assert_eq!(eng.query_prefix(&long[..MAX_PREFIX_LEN]), vec![2]);
+        assert!(eng.query_prefix(&long).is_empty());
+        assert_eq!(eng.query(&long), vec![2]);
+    }
+
     #[test]
     fn extra_keywords_are_indexed() {
         let mut eng = engine();
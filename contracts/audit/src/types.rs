@@ -15,12 +15,20 @@ pub use crate::merkle_log::MerkleRoot;
 /// Merkle-tree node hashes throughout the crate.
 pub type Digest = [u8; 32];
 
+/// A raw Ed25519 signature (64 bytes).
+pub type Signature = [u8; 64];
+
+/// A raw Ed25519 public key (32 bytes).
+pub type PublicKey = [u8; 32];
+
 // ── Segment identifier ────────────────────────────────────────────────────────
 
 /// Identifies a logical partition of the audit log.
 ///
 /// Logs can be segmented by contract address, tenant, or sensitivity level.
-/// A segment is represented by an ASCII label of at most 64 bytes.
+/// A segment is represented by a label of 1–64 bytes drawn from
+/// `[a-z0-9._-]`, e.g. `"healthcare.access"`, so ids route identically across
+/// systems regardless of case folding or encoding.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct LogSegmentId(pub(crate) [u8; 64], pub(crate) usize /* used bytes */);
 
@@ -28,7 +36,8 @@ impl LogSegmentId {
     /// Create a new segment identifier.
     ///
     /// # Errors
-    /// Returns [`AuditError::InvalidSegmentId`] when `label` exceeds 64 bytes.
+    /// Returns [`AuditError::InvalidSegmentId`] when `label` is empty, exceeds
+    /// 64 bytes, or contains a character outside `[a-z0-9._-]`.
     pub fn new(label: &str) -> Result<Self, AuditError> {
         let bytes = label.as_bytes();
         if bytes.is_empty() {
@@ -37,6 +46,9 @@ impl LogSegmentId {
         if bytes.len() > 64 {
             return Err(AuditError::InvalidSegmentId);
         }
+        if !bytes.iter().all(|&b| Self::is_allowed_byte(b)) {
+            return Err(AuditError::InvalidSegmentId);
+        }
         let mut buf = [0u8; 64];
         buf[..bytes.len()].copy_from_slice(bytes);
         Ok(Self(buf, bytes.len()))
@@ -50,11 +62,17 @@ impl LogSegmentId {
 
     /// View the label as a string slice.
     ///
-    /// # Panics (won't): segments are always created from valid `&str` above.
+    /// # Panics (won't): segments are always created from validated ASCII above.
     #[inline]
     pub fn as_str(&self) -> &str {
         core::str::from_utf8(self.as_bytes()).unwrap_or("")
     }
+
+    /// Whether `b` is in the segment charset `[a-z0-9._-]`.
+    #[inline]
+    fn is_allowed_byte(b: u8) -> bool {
+        matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'.' | b'_' | b'-')
+    }
 }
 
 impl core::fmt::Display for LogSegmentId {
@@ -106,44 +124,119 @@ pub struct LogEntry {
 
     /// Logical segment this entry belongs to.
     pub segment: LogSegmentId,
+
+    /// Ed25519 signature over [`LogEntry::canonical_bytes`], if the entry was
+    /// appended via `MerkleLog::append_signed`.
+    pub signature: Option<Signature>,
+
+    /// Ed25519 public key of the signer, present iff `signature` is.
+    pub signer: Option<PublicKey>,
 }
 
 impl LogEntry {
-    /// Serialise the entry into a canonical byte representation used for
-    /// hashing.  The format is:
+    /// Serialise the entry into its canonical byte representation.  This is
+    /// the message signed by `MerkleLog::append_signed` and the prefix of
+    /// [`Self::leaf_bytes`].  The format is:
     /// ```text
-    /// sequence(8 LE) ‖ timestamp(8 LE) ‖ actor ‖ '\0' ‖ action ‖ '\0'
-    ///  ‖ target ‖ '\0' ‖ result ‖ '\0' ‖ prev_hash(32) ‖ segment_label
+    /// sequence(8 BE) ‖ timestamp(8 BE) ‖ lp(actor) ‖ lp(action) ‖ lp(target)
+    ///  ‖ lp(result) ‖ prev_hash(32) ‖ lp(segment_label)
     /// ```
-    /// Null bytes act as field separators; no field may contain a null byte.
+    /// where `lp(s) = len(s) as u32 (4 BE) ‖ UTF-8 bytes of s`.  Length
+    /// prefixes keep field boundaries unambiguous for arbitrary content.
+    ///
+    /// The encoding depends only on field values, never on how entries are
+    /// stored, so any implementation can reproduce it from the crate docs.
     pub fn canonical_bytes(&self) -> alloc::vec::Vec<u8> {
+        let fields = [
+            self.actor.as_str(),
+            self.action.as_str(),
+            self.target.as_str(),
+            self.result.as_str(),
+        ];
         let mut buf = alloc::vec::Vec::with_capacity(
             8 + 8
-                + self.actor.len()
-                + 1
-                + self.action.len()
-                + 1
-                + self.target.len()
-                + 1
-                + self.result.len()
-                + 1
+                + fields.iter().map(|f| 4 + f.len()).sum::<usize>()
                 + 32
+                + 4
                 + self.segment.as_bytes().len(),
         );
-        buf.extend_from_slice(&self.sequence.to_le_bytes());
-        buf.extend_from_slice(&self.timestamp.to_le_bytes());
-        buf.extend_from_slice(self.actor.as_bytes());
-        buf.push(0);
-        buf.extend_from_slice(self.action.as_bytes());
-        buf.push(0);
-        buf.extend_from_slice(self.target.as_bytes());
-        buf.push(0);
-        buf.extend_from_slice(self.result.as_bytes());
-        buf.push(0);
+        buf.extend_from_slice(&self.sequence.to_be_bytes());
+        buf.extend_from_slice(&self.timestamp.to_be_bytes());
+        for field in fields {
+            push_length_prefixed(&mut buf, field.as_bytes());
+        }
         buf.extend_from_slice(&self.prev_hash);
-        buf.extend_from_slice(self.segment.as_bytes());
+        push_length_prefixed(&mut buf, self.segment.as_bytes());
         buf
     }
+
+    /// Bytes committed into the Merkle leaf for this entry.
+    ///
+    /// Unsigned entries hash exactly their canonical bytes.  Signed entries
+    /// append `signer(32) ‖ signature(64)` so that stripping or swapping the
+    /// signature changes the leaf hash and breaks inclusion proofs.
+    pub fn leaf_bytes(&self) -> alloc::vec::Vec<u8> {
+        let mut buf = self.canonical_bytes();
+        if let (Some(signer), Some(signature)) = (&self.signer, &self.signature) {
+            buf.extend_from_slice(signer);
+            buf.extend_from_slice(signature);
+        }
+        buf
+    }
+
+    /// Merkle leaf hash of this entry: `SHA-256(0x00 ‖ leaf_bytes)`.
+    ///
+    /// This is the value stored in `entry_hash` and the only function used to
+    /// derive leaves for roots and proofs.
+    pub fn canonical_hash(&self) -> Digest {
+        crate::merkle_log::hash_leaf(&self.leaf_bytes())
+    }
+}
+
+fn push_length_prefixed(buf: &mut alloc::vec::Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+// ── Proof wire-format helpers ─────────────────────────────────────────────────
+
+/// Read the next `N` bytes of `bytes` starting at `*at`, advancing `at`.
+pub(crate) fn take_array<const N: usize>(
+    bytes: &[u8],
+    at: &mut usize,
+) -> Result<[u8; N], AuditError> {
+    let end = at.checked_add(N).ok_or(AuditError::MalformedProof)?;
+    let chunk = bytes.get(*at..end).ok_or(AuditError::MalformedProof)?;
+    *at = end;
+    chunk.try_into().map_err(|_| AuditError::MalformedProof)
+}
+
+/// Append `hashes` as `hash_count (u32, big-endian) ‖ hash_count × 32`.
+pub(crate) fn push_hash_list(buf: &mut alloc::vec::Vec<u8>, hashes: &[Digest]) {
+    buf.extend_from_slice(&(hashes.len() as u32).to_be_bytes());
+    for hash in hashes {
+        buf.extend_from_slice(hash);
+    }
+}
+
+/// Read a hash list written by [`push_hash_list`] that must run exactly to
+/// the end of `bytes`.
+pub(crate) fn take_hash_list(
+    bytes: &[u8],
+    at: &mut usize,
+) -> Result<alloc::vec::Vec<Digest>, AuditError> {
+    let count = u32::from_be_bytes(take_array(bytes, at)?) as usize;
+    // Check the declared count against the remaining length up front so a
+    // hostile count cannot trigger a large allocation.
+    let rest = bytes.len() - *at;
+    if rest % 32 != 0 || rest / 32 != count {
+        return Err(AuditError::MalformedProof);
+    }
+    let mut hashes = alloc::vec::Vec::with_capacity(count);
+    for _ in 0..count {
+        hashes.push(take_array(bytes, at)?);
+    }
+    Ok(hashes)
 }
 
 // ── Witness / co-signing ──────────────────────────────────────────────────────
@@ -167,10 +260,25 @@ pub struct WitnessSignature {
     /// Timestamp at which the signature was produced.
     pub signed_at: u64,
 
-    /// Raw signature bytes (format is application-defined; stored opaquely).
+    /// Ed25519 signature by the witness over [`Self::signed_bytes`].  The
+    /// live log stores it as given; [`crate::merkle_log::MerkleLog::from_snapshot`]
+    /// verifies it against the witness's key.
     pub signature: alloc::vec::Vec<u8>,
 }
 
+impl WitnessSignature {
+    /// Bytes the witness signs: the length-prefixed `segment` label, then
+    /// `root ‖ tree_size ‖ signed_at` with integers big-endian.
+    pub fn signed_bytes(&self, segment: &LogSegmentId) -> alloc::vec::Vec<u8> {
+        let mut buf = alloc::vec::Vec::with_capacity(4 + segment.as_bytes().len() + 32 + 8 + 8);
+        push_length_prefixed(&mut buf, segment.as_bytes());
+        buf.extend_from_slice(&self.root);
+        buf.extend_from_slice(&self.tree_size.to_be_bytes());
+        buf.extend_from_slice(&self.signed_at.to_be_bytes());
+        buf
+    }
+}
+
 // ── Retention policy ──────────────────────────────────────────────────────────
 
 /// Governs how long entries in a segment must be retained.
@@ -220,7 +328,7 @@ pub enum AuditError {
     /// The requested log entry does not exist.
     EntryNotFound { sequence: u64 },
 
-    /// The segment label is too long or otherwise invalid.
+    /// The segment label is empty, too long, or outside `[a-z0-9._-]`.
     InvalidSegmentId,
 
     /// Compaction was requested but insufficient witnesses have co-signed.
@@ -251,6 +359,43 @@ pub enum AuditError {
         /// Minimum acceptable timestamp (last stored entry's timestamp).
         minimum: u64,
     },
+
+    /// A signed entry was prepared against a different log position
+    /// (another entry was appended in between).
+    UnexpectedSequence {
+        /// Sequence number the log expected next.
+        expected: u64,
+        /// Sequence number carried by the supplied entry.
+        supplied: u64,
+    },
+
+    /// The Ed25519 signature or public key is malformed or does not verify.
+    InvalidSignature,
+
+    /// The entry carries no signature, or was signed by a different key.
+    EntryNotSigned { sequence: u64 },
+
+    /// A tree size was zero or exceeded the log's current size.
+    InvalidSize {
+        /// Size supplied by the caller.
+        requested: u64,
+        /// Current number of leaves in the log.
+        current: u64,
+    },
+
+    /// A serialized proof is truncated, has trailing bytes, or declares an
+    /// impossible layout.
+    MalformedProof,
+
+    /// The entry's body was pruned; only its leaf hash remains.
+    Pruned { sequence: u64 },
+
+    /// A sequence range has `from > to` or starts before sequence 1.
+    InvalidRange { from: u64, to: u64 },
+
+    /// An exported log snapshot does not match its recorded root, or its
+    /// entries are inconsistent with the leaf hashes it carries.
+    CorruptSnapshot,
 }
 
 impl core::fmt::Display for AuditError {
@@ -266,7 +411,7 @@ impl core::fmt::Display for AuditError {
                 write!(f, "entry at sequence {sequence} not found")
             }
             AuditError::InvalidSegmentId => {
-                write!(f, "segment id must be non-empty and ≤64 bytes")
+                write!(f, "segment id must be 1–64 bytes of [a-z0-9._-]")
             }
             AuditError::InsufficientWitnesses { required, present } => {
                 write!(
@@ -293,6 +438,173 @@ impl core::fmt::Display for AuditError {
                 f,
                 "entry {sequence} timestamp {supplied} is before minimum {minimum}"
             ),
+            AuditError::UnexpectedSequence { expected, supplied } => {
+                write!(
+                    f,
+                    "entry prepared for sequence {supplied}, expected {expected}"
+                )
+            }
+            AuditError::InvalidSignature => write!(f, "invalid Ed25519 entry signature"),
+            AuditError::EntryNotSigned { sequence } => {
+                write!(f, "entry {sequence} is not signed by the given key")
+            }
+            AuditError::InvalidSize { requested, current } => {
+                write!(
+                    f,
+                    "tree size {requested} is invalid for log of size {current}"
+                )
+            }
+            AuditError::MalformedProof => write!(f, "malformed serialized proof"),
+            AuditError::Pruned { sequence } => {
+                write!(f, "entry at sequence {sequence} has been pruned")
+            }
+            AuditError::InvalidRange { from, to } => {
+                write!(f, "invalid sequence range [{from}, {to}]")
+            }
+            AuditError::CorruptSnapshot => write!(f, "log snapshot is inconsistent"),
+        }
+    }
+}
+
+impl AuditError {
+    /// Stable numeric code for this error, for hosts and off-chain consumers
+    /// that only see the failure over the wire.
+    ///
+    /// Codes follow declaration order starting at 1 and are never renumbered
+    /// or reused; new variants take the next free code. Variant payloads are
+    /// not encoded.
+    ///
+    /// | Code | Variant                    |
+    /// |------|----------------------------|
+    /// | 1    | `HashChainBroken`          |
+    /// | 2    | `InvalidInclusionProof`    |
+    /// | 3    | `InvalidConsistencyProof`  |
+    /// | 4    | `InvalidSearchToken`       |
+    /// | 5    | `EntryNotFound`            |
+    /// | 6    | `InvalidSegmentId`         |
+    /// | 7    | `InsufficientWitnesses`    |
+    /// | 8    | `RetentionPolicyViolation` |
+    /// | 9    | `RootMismatch`             |
+    /// | 10   | `InternalError`            |
+    /// | 11   | `SegmentNotFound`          |
+    /// | 12   | `SearchKeyNotSet`          |
+    /// | 13   | `OutOfOrderTimestamp`      |
+    /// | 14   | `UnexpectedSequence`       |
+    /// | 15   | `InvalidSignature`         |
+    /// | 16   | `EntryNotSigned`           |
+    /// | 17   | `InvalidSize`              |
+    /// | 18   | `MalformedProof`           |
+    /// | 19   | `Pruned`                   |
+    /// | 20   | `InvalidRange`             |
+    /// | 21   | `CorruptSnapshot`          |
+    pub fn code(&self) -> u16 {
+        match self {
+            AuditError::HashChainBroken { .. } => 1,
+            AuditError::InvalidInclusionProof => 2,
+            AuditError::InvalidConsistencyProof => 3,
+            AuditError::InvalidSearchToken => 4,
+            AuditError::EntryNotFound { .. } => 5,
+            AuditError::InvalidSegmentId => 6,
+            AuditError::InsufficientWitnesses { .. } => 7,
+            AuditError::RetentionPolicyViolation { .. } => 8,
+            AuditError::RootMismatch => 9,
+            AuditError::InternalError(_) => 10,
+            AuditError::SegmentNotFound => 11,
+            AuditError::SearchKeyNotSet => 12,
+            AuditError::OutOfOrderTimestamp { .. } => 13,
+            AuditError::UnexpectedSequence { .. } => 14,
+            AuditError::InvalidSignature => 15,
+            AuditError::EntryNotSigned { .. } => 16,
+            AuditError::InvalidSize { .. } => 17,
+            AuditError::MalformedProof => 18,
+            AuditError::Pruned { .. } => 19,
+            AuditError::InvalidRange { .. } => 20,
+            AuditError::CorruptSnapshot => 21,
+        }
+    }
+
+    /// Inverse of [`AuditError::code`]. Since payloads are not encoded,
+    /// data-carrying variants come back with zeroed fields (and an empty
+    /// message for `InternalError`). Unknown codes yield `None`.
+    pub fn from_code(code: u16) -> Option<AuditError> {
+        let err = match code {
+            1 => AuditError::HashChainBroken { at_sequence: 0 },
+            2 => AuditError::InvalidInclusionProof,
+            3 => AuditError::InvalidConsistencyProof,
+            4 => AuditError::InvalidSearchToken,
+            5 => AuditError::EntryNotFound { sequence: 0 },
+            6 => AuditError::InvalidSegmentId,
+            7 => AuditError::InsufficientWitnesses {
+                required: 0,
+                present: 0,
+            },
+            8 => AuditError::RetentionPolicyViolation {
+                sequence: 0,
+                retained_until: 0,
+            },
+            9 => AuditError::RootMismatch,
+            10 => AuditError::InternalError(""),
+            11 => AuditError::SegmentNotFound,
+            12 => AuditError::SearchKeyNotSet,
+            13 => AuditError::OutOfOrderTimestamp {
+                sequence: 0,
+                supplied: 0,
+                minimum: 0,
+            },
+            14 => AuditError::UnexpectedSequence {
+                expected: 0,
+                supplied: 0,
+            },
+            15 => AuditError::InvalidSignature,
+            16 => AuditError::EntryNotSigned { sequence: 0 },
+            17 => AuditError::InvalidSize {
+                requested: 0,
+                current: 0,
+            },
+            18 => AuditError::MalformedProof,
+            19 => AuditError::Pruned { sequence: 0 },
+            20 => AuditError::InvalidRange { from: 0, to: 0 },
+            21 => AuditError::CorruptSnapshot,
+            _ => return None,
+        };
+        Some(err)
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_codes_round_trip() {
+        let mut seen = 0;
+        for code in 0..=u16::from(u8::MAX) {
+            let Some(err) = AuditError::from_code(code) else {
+                continue;
+            };
+            assert_eq!(err.code(), code);
+            assert_eq!(AuditError::from_code(err.code()), Some(err));
+            seen += 1;
         }
+        // `code` matches exhaustively; this keeps `from_code` in step with it.
+        assert_eq!(seen, 21);
+        assert_eq!(AuditError::from_code(0), None);
+        assert_eq!(AuditError::from_code(22), None);
+    }
+
+    #[test]
+    fn error_code_ignores_payload() {
+        let err = AuditError::InvalidRange { from: 9, to: 3 };
+        assert_eq!(err.code(), 20);
+        assert_eq!(
+            AuditError::from_code(err.code()),
+            Some(AuditError::InvalidRange { from: 0, to: 0 })
+        );
+        assert_eq!(
+            AuditError::InternalError("boom").code(),
+            AuditError::InternalError("other").code()
+        );
     }
 }
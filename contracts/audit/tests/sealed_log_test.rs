@@ -0,0 +1,108 @@
+//! Sealed Merkle Log — Concurrent Proof Generation
+//!
+//! These tests exercise `SealedMerkleLog` from multiple threads at once:
+//!
+//! - Each thread generates and verifies inclusion proofs against a shared
+//!   `Arc<SealedMerkleLog>` without any locking.
+//! - Consistency proofs from earlier checkpoints verify concurrently.
+//! - Sealing preserves the root and entries of the source log.
+//!
+//! Requires the `std` feature: `cargo test -p audit --features std`.
+
+#![cfg(feature = "std")]
+
+use std::{sync::Arc, thread};
+
+use audit::{
+    merkle_log::MerkleLog,
+    sealed::SealedMerkleLog,
+    types::{AuditError, LogSegmentId},
+};
+
+// ── Helpers ────────────────────────────────────────────────────────────────
+
+fn build_log(n: u64) -> MerkleLog {
+    let mut log = MerkleLog::new(LogSegmentId::new("sealed.test").unwrap());
+    for i in 0..n {
+        log.append(1_000 + i, "monitor", "record.read", "patient:1", "ok")
+            .unwrap();
+    }
+    log
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn sealing_preserves_root_and_entries() {
+    let log = build_log(10);
+    let root = log.current_root();
+    let sealed = SealedMerkleLog::from_log(log.clone());
+
+    assert_eq!(sealed.root(), root);
+    assert_eq!(sealed.len(), 10);
+    assert_eq!(sealed.tree_size(), 10);
+    assert_eq!(sealed.get_entry(3).unwrap(), log.get_entry(3).unwrap());
+    assert!(sealed.verify_chain(1, 10).is_ok());
+    assert!(matches!(
+        sealed.inclusion_proof(11),
+        Err(AuditError::EntryNotFound { sequence: 11 })
+    ));
+}
+
+#[test]
+fn threads_verify_inclusion_proofs_concurrently() {
+    let sealed = Arc::new(SealedMerkleLog::from(build_log(64)));
+    let root = sealed.root();
+
+    let handles: Vec<_> = (0..8u64)
+        .map(|t| {
+            let sealed = Arc::clone(&sealed);
+            thread::spawn(move || {
+                for seq in (1 + t..=64).step_by(8) {
+                    let proof = sealed.inclusion_proof(seq).unwrap();
+                    assert!(proof.verify(&root).is_ok(), "seq={seq} failed");
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().expect("prover thread panicked");
+    }
+}
+
+#[test]
+fn threads_verify_consistency_proofs_concurrently() {
+    let mut log = build_log(0);
+    let mut checkpoints = Vec::new();
+    for i in 0..32u64 {
+        log.append(2_000 + i, "monitor", "record.write", "patient:2", "ok")
+            .unwrap();
+        checkpoints.push((log.len(), log.current_root()));
+    }
+    let sealed = Arc::new(SealedMerkleLog::from_log(log));
+    let checkpoints = Arc::new(checkpoints);
+
+    let handles: Vec<_> = (0..4usize)
+        .map(|t| {
+            let sealed = Arc::clone(&sealed);
+            let checkpoints = Arc::clone(&checkpoints);
+            thread::spawn(move || {
+                for &(size, root) in checkpoints.iter().skip(t).step_by(4) {
+                    let proof = sealed.consistency_proof(root, size).unwrap();
+                    assert_eq!(proof.root_v2, sealed.root());
+                    assert!(proof.verify().is_ok(), "size={size} failed");
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().expect("prover thread panicked");
+    }
+
+    assert!(matches!(
+        sealed.consistency_proof(sealed.root(), 33),
+        Err(AuditError::InvalidConsistencyProof)
+    ));
+}
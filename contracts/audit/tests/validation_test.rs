@@ -35,6 +35,38 @@ fn test_segment_id_over_max_length_rejected() {
     );
 }
 
+#[test]
+fn test_segment_id_uppercase_rejected() {
+    // Mixed case would route differently on case-folding systems.
+    assert_eq!(
+        LogSegmentId::new("Healthcare.access"),
+        Err(AuditError::InvalidSegmentId)
+    );
+    assert_eq!(
+        LogSegmentId::new("healthcare.ACCESS"),
+        Err(AuditError::InvalidSegmentId)
+    );
+}
+
+#[test]
+fn test_segment_id_disallowed_characters_rejected() {
+    for label in ["health care", "health/care", "health:care", "héalth"] {
+        assert_eq!(
+            LogSegmentId::new(label),
+            Err(AuditError::InvalidSegmentId),
+            "{label:?} must be rejected"
+        );
+    }
+}
+
+#[test]
+fn test_segment_id_dotted_namespace_round_trips() {
+    let label = "healthcare.access_v2-eu.0";
+    let seg = LogSegmentId::new(label).unwrap();
+    assert_eq!(seg.as_str(), label);
+    assert_eq!(seg.as_bytes(), label.as_bytes());
+}
+
 // ── MerkleLog append with zero / boundary values ──────────────────────────────
 
 #[test]
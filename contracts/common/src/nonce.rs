@@ -1,9 +1,15 @@
 //! # Nonce-based Replay Protection
 //!
-//! Provides per-sender strictly-monotonic nonces for cross-contract calls.
+//! Provides per-sender monotonic nonces for cross-contract calls.
 //! Each (contract, sender) pair maintains an independent counter in persistent
-//! storage.  A message is only accepted when its nonce equals the current
-//! expected value; on success the counter is atomically incremented.
+//! storage.  By default a message is only accepted when its nonce equals the
+//! current expected value; on success the counter is atomically incremented.
+//!
+//! A contract may widen this with [`set_nonce_window`] so that any unused
+//! nonce in `[current, current + window]` is accepted, letting clients submit
+//! several messages concurrently without guaranteeing their order.  Nonces
+//! consumed ahead of the counter are tracked in a per-sender bitmap, and each
+//! nonce is still accepted at most once.
 //!
 //! ## Usage pattern
 //!
@@ -29,8 +35,14 @@ use crate::CommonError;
 #[derive(Clone, Debug, Eq, PartialEq)]
 enum NonceKey {
     Nonce(Address),
+    /// Bitmap of consumed nonces above the counter; bit `i` is `current + 1 + i`.
+    Consumed(Address),
+    Window,
 }
 
+/// Widest nonce window a contract may configure; bounded by the bitmap width.
+pub const MAX_NONCE_WINDOW: u64 = 64;
+
 // ── TTL constants (mirror common convention) ─────────────────────────────────
 
 const TTL_THRESHOLD: u32 = 5_184_000;
@@ -57,6 +69,25 @@ fn store_nonce(env: &Env, sender: &Address, value: u64) {
         .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
 }
 
+fn load_consumed(env: &Env, sender: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&NonceKey::Consumed(sender.clone()))
+        .unwrap_or(0u64)
+}
+
+fn store_consumed(env: &Env, sender: &Address, consumed: u64) {
+    let key = NonceKey::Consumed(sender.clone());
+    if consumed == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &consumed);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
 // ── Public API ────────────────────────────────────────────────────────────────
 
 /// Return the current nonce for `sender` without modifying state.
@@ -78,23 +109,90 @@ pub fn get_and_increment_nonce(env: &Env, sender: &Address) -> Result<u64, Commo
     Ok(current)
 }
 
-/// **Recipient side.** Validate that `provided` equals the expected nonce for
-/// `sender`, then atomically increment the stored counter.
+/// Return how far ahead of the counter this contract accepts nonces.
+///
+/// Defaults to `0`, i.e. strictly in-order nonces.
+pub fn nonce_window(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&NonceKey::Window)
+        .unwrap_or(0u64)
+}
+
+/// Set the nonce window for this contract.  Callers are expected to gate this
+/// behind their own admin check.
+///
+/// Returns [`CommonError::InvalidInput`] if `window` exceeds [`MAX_NONCE_WINDOW`].
+pub fn set_nonce_window(env: &Env, window: u64) -> Result<(), CommonError> {
+    if window > MAX_NONCE_WINDOW {
+        return Err(CommonError::InvalidInput);
+    }
+    env.storage().instance().set(&NonceKey::Window, &window);
+    Ok(())
+}
+
+/// Consume `provided` against a `(current, consumed)` nonce state and return
+/// the state to store afterwards.  Exposed for contracts that keep their own
+/// nonce counters but want the same window semantics.
+///
+/// `consumed` holds the nonces above `current` already used: bit `i` stands
+/// for `current + 1 + i`.  Consuming `current` itself advances the counter
+/// past any run of such nonces.
+///
+/// # Errors
+/// - [`CommonError::InvalidNonce`] — `provided` lies outside
+///   `[current, current + window]` or was already consumed.
+/// - [`CommonError::NonceOverflow`] — the counter would exceed `u64::MAX`.
+pub fn advance_nonce_window(
+    current: u64,
+    consumed: u64,
+    provided: u64,
+    window: u64,
+) -> Result<(u64, u64), CommonError> {
+    let window = window.min(MAX_NONCE_WINDOW);
+    if provided < current || provided - current > window {
+        return Err(CommonError::InvalidNonce);
+    }
+
+    if provided > current {
+        let bit = 1u64 << (provided - current - 1);
+        if consumed & bit != 0 {
+            return Err(CommonError::InvalidNonce);
+        }
+        return Ok((current, consumed | bit));
+    }
+
+    let mut next = current.checked_add(1).ok_or(CommonError::NonceOverflow)?;
+    let mut consumed = consumed;
+    while consumed & 1 != 0 {
+        consumed >>= 1;
+        next = next.checked_add(1).ok_or(CommonError::NonceOverflow)?;
+    }
+    Ok((next, consumed >> 1))
+}
+
+/// **Recipient side.** Validate `provided` against the expected nonce for
+/// `sender`, then atomically record it as used.
+///
+/// With the default window of `0` this requires `provided` to equal the
+/// current counter; see [`set_nonce_window`] for out-of-order acceptance.
 ///
 /// # Errors
-/// - [`CommonError::InvalidNonce`] — `provided` does not match the expected value.
+/// - [`CommonError::InvalidNonce`] — `provided` is outside the window or was
+///   already used.
 /// - [`CommonError::NonceOverflow`] — the counter is already at `u64::MAX`.
 pub fn validate_and_increment_nonce(
     env: &Env,
     sender: &Address,
     provided: u64,
 ) -> Result<(), CommonError> {
-    let expected = load_nonce(env, sender);
-    if provided != expected {
-        return Err(CommonError::InvalidNonce);
+    let current = load_nonce(env, sender);
+    let consumed = load_consumed(env, sender);
+    let (next, consumed) = advance_nonce_window(current, consumed, provided, nonce_window(env))?;
+    if next != current {
+        store_nonce(env, sender, next);
     }
-    let next = expected.checked_add(1).ok_or(CommonError::NonceOverflow)?;
-    store_nonce(env, sender, next);
+    store_consumed(env, sender, consumed);
     Ok(())
 }
 
@@ -204,6 +302,49 @@ mod tests {
         });
     }
 
+    #[test]
+    fn window_accepts_out_of_order_nonces_once() {
+        with_contract_env(|env| {
+            let sender = Address::generate(env);
+            for n in 0..3 {
+                validate_and_increment_nonce(env, &sender, n).unwrap();
+            }
+            set_nonce_window(env, 2).unwrap();
+
+            validate_and_increment_nonce(env, &sender, 5).unwrap();
+            assert_eq!(current_nonce(env, &sender), 3);
+            validate_and_increment_nonce(env, &sender, 3).unwrap();
+            validate_and_increment_nonce(env, &sender, 4).unwrap();
+            // Consuming 4 also skips past the already-used 5.
+            assert_eq!(current_nonce(env, &sender), 6);
+
+            for replay in [3, 4, 5] {
+                let err = validate_and_increment_nonce(env, &sender, replay).unwrap_err();
+                assert_eq!(err, CommonError::InvalidNonce);
+            }
+            let err = validate_and_increment_nonce(env, &sender, 9).unwrap_err();
+            assert_eq!(err, CommonError::InvalidNonce);
+        });
+    }
+
+    #[test]
+    fn window_rejects_nonce_consumed_ahead_of_counter() {
+        with_contract_env(|env| {
+            let sender = Address::generate(env);
+            set_nonce_window(env, 4).unwrap();
+            validate_and_increment_nonce(env, &sender, 2).unwrap();
+            let err = validate_and_increment_nonce(env, &sender, 2).unwrap_err();
+            assert_eq!(err, CommonError::InvalidNonce);
+            assert_eq!(current_nonce(env, &sender), 0);
+
+            assert_eq!(
+                set_nonce_window(env, MAX_NONCE_WINDOW + 1),
+                Err(CommonError::InvalidInput)
+            );
+            assert_eq!(nonce_window(env), 4);
+        });
+    }
+
     #[test]
     fn error_codes_are_stable() {
         with_contract_env(|_env| {
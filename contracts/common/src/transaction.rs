@@ -28,6 +28,9 @@ pub enum TransactionStatus {
     Failed,
     /// Transaction was cancelled
     Cancelled,
+    /// A resumable transaction stopped at its savepoint after a failed
+    /// commit; it keeps its locks until resumed, cancelled or timed out
+    Suspended,
 }
 
 /// Types of contracts that can participate in orchestrated transactions
@@ -58,12 +61,59 @@ pub struct TransactionOperation {
     pub parameters: Vec<String>,
     /// Resources this operation locks (for deadlock detection)
     pub locked_resources: Vec<String>,
+    /// Mode `locked_resources` are locked in
+    pub lock_mode: LockMode,
     /// Whether this operation is prepared
     pub prepared: bool,
     /// Whether this operation is committed
     pub committed: bool,
     /// Error message if operation failed
     pub error: Option<String>,
+    /// Child transaction (created with `compose_transaction`) that this
+    /// operation runs in place of a contract call. When set, the contract
+    /// address and function name are ignored.
+    pub sub_transaction: Option<u64>,
+    /// Entry point called on `contract_address` in the prepare phase
+    /// (`prepare_` when unset), e.g. `prepare_add_record`
+    pub prepare_hook: Option<Symbol>,
+    /// Entry point called in the commit phase (`commit_` when unset)
+    pub commit_hook: Option<Symbol>,
+    /// Entry point called to undo the operation (`rollback_` when unset)
+    pub rollback_hook: Option<Symbol>,
+}
+
+/// How a sub-transaction's failure affects the transaction that contains it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AtomicityMode {
+    /// Failure rolls back the parent transaction
+    AllOrNothing,
+    /// Failure is recorded on the parent's operation; the parent continues
+    BestEffort,
+}
+
+/// How a transaction holds a resource lock
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LockMode {
+    /// Read access; any number of transactions may hold it together
+    Shared,
+    /// Sole access; excludes every other holder
+    Exclusive,
+}
+
+/// One transaction in a composition tree, as returned by `get_transaction_tree`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionTreeNode {
+    pub transaction_id: u64,
+    /// Containing transaction, if this is a sub-transaction
+    pub parent: Option<u64>,
+    /// Distance from the queried transaction
+    pub depth: u32,
+    pub phase: TransactionPhase,
+    pub status: TransactionStatus,
+    pub atomicity: AtomicityMode,
 }
 
 /// Transaction log entry for tracking orchestrated transactions
@@ -90,6 +140,9 @@ pub struct TransactionLog {
     pub error: Option<String>,
     /// Metadata for transaction
     pub metadata: Vec<String>,
+    /// Index of the last operation committed so far. A resumable
+    /// transaction that fails mid-commit picks up after this index.
+    pub savepoint: Option<u32>,
 }
 
 /// Deadlock detection information
@@ -176,6 +229,8 @@ pub const DEADLOCK_TRACKER: Symbol = symbol_short!("DL_TRK");
 pub const TIMEOUT_CONFIG: Symbol = symbol_short!("TO_CFG");
 pub const ACTIVE_TRANSACTIONS: Symbol = symbol_short!("ACT_TX");
 pub const RESOURCE_LOCKS: Symbol = symbol_short!("RES_LK");
+/// Number of holders of each resource locked in `LockMode::Shared`
+pub const SHARED_LOCKS: Symbol = symbol_short!("SHR_LK");
 
 /// Default timeout values (in seconds)
 pub const DEFAULT_TRANSACTION_TIMEOUT: u64 = 300; // 5 minutes
@@ -209,6 +264,8 @@ pub enum TransactionError {
     ContractCallFailed = 1010,
     /// Resource already locked
     ResourceLocked = 1011,
+    /// Sub-transaction composition would form a cycle
+    CompositionCycle = 1012,
 }
 
 /// Helper functions for transaction management
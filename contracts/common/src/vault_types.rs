@@ -17,6 +17,10 @@ pub struct VaultPolicy {
     pub shard_count: u32,
     pub emergency_threshold: u32,
     pub inactivity_timeout_secs: u64,
+    /// Ledger timestamp before which shares cannot be combined (0 = no lock)
+    pub not_before: u64,
+    /// Only guardians of the owner may request reconstruction
+    pub require_guardian: bool,
 }
 
 #[contracttype]
@@ -299,9 +299,11 @@ impl AuditLog {
             prev_hash,
             entry_hash: [0u8; 32],
             segment,
+            signature: None,
+            signer: None,
         };
 
-        entry.entry_hash = audit::merkle_log::hash_leaf(&entry.canonical_bytes());
+        entry.entry_hash = entry.canonical_hash();
         self.entries.push(entry);
     }
 
@@ -18,6 +18,11 @@ const ADMIN: Symbol = symbol_short!("ADMIN");
 const INITIALIZED: Symbol = symbol_short!("INIT");
 const EVT_CTR: Symbol = symbol_short!("EVT_CTR");
 const LAMPORT: Symbol = symbol_short!("LAMPORT");
+const DEDUP: Symbol = symbol_short!("DEDUP");
+
+/// Ledgers a publish dedup key is remembered for (about a day at 5s ledgers).
+/// Retries after this window are treated as new events.
+pub const DEDUP_TTL_LEDGERS: u32 = 17_280;
 
 // ── Error types ──────────────────────────────────────────────────────────────
 
@@ -40,6 +45,9 @@ pub enum EventError {
     EventNotFound = 13,
     DeadLetterFull = 14,
     InvalidTopicPattern = 15,
+    DeliveryNotFound = 16,
+    RetryTooEarly = 17,
+    GroupMemberLimitExceeded = 18,
 }
 
 // ── Core event types ─────────────────────────────────────────────────────────
@@ -121,6 +129,10 @@ impl EventStreamContract {
     /// The event is assigned a monotonic event ID and a Lamport timestamp,
     /// persisted in the log, and broadcast via Soroban events for external
     /// indexers.
+    ///
+    /// A `dedup_key` makes retries idempotent: republishing with the same key
+    /// from the same caller within [`DEDUP_TTL_LEDGERS`] returns the original
+    /// event ID without appending to the log again.
     #[allow(clippy::arithmetic_side_effects)]
     pub fn publish_event(
         env: Env,
@@ -128,6 +140,7 @@ impl EventStreamContract {
         topic: String,
         schema_version: u32,
         payload_hash: String,
+        dedup_key: Option<String>,
     ) -> Result<u64, EventError> {
         caller.require_auth();
         Self::require_initialized(&env)?;
@@ -139,6 +152,13 @@ impl EventStreamContract {
 
         registry::require_schema_exists(&env, &topic, schema_version)?;
 
+        let dedup_key = dedup_key.map(|key| (DEDUP, caller.clone(), key));
+        if let Some(key) = &dedup_key {
+            if let Some(event_id) = env.storage().temporary().get::<_, u64>(key) {
+                return Ok(event_id);
+            }
+        }
+
         let event_id = Self::next_event_id(&env);
         let lamport_ts = Self::tick_lamport(&env);
 
@@ -161,14 +181,14 @@ impl EventStreamContract {
         env.storage().persistent().set(&topic_key, &event_id);
 
         // Append to the global ordered index
-        let idx_key = symbol_short!("LOG_IDX");
-        let mut index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&idx_key)
-            .unwrap_or(Vec::new(&env));
-        index.push_back(event_id);
-        env.storage().persistent().set(&idx_key, &index);
+        replay::append_to_index(&env, event_id);
+
+        if let Some(key) = &dedup_key {
+            env.storage().temporary().set(key, &event_id);
+            env.storage()
+                .temporary()
+                .extend_ttl(key, DEDUP_TTL_LEDGERS, DEDUP_TTL_LEDGERS);
+        }
 
         // Dispatch to subscriber matching
         subscription::dispatch_to_subscribers(&env, &envelope);
@@ -204,17 +224,34 @@ impl EventStreamContract {
     // ── Schema registration (delegated to registry module) ───────────────────
 
     /// Register a new versioned schema for a topic.
+    ///
+    /// `compat` declares how this version relates to the previous one and
+    /// defaults to `SchemaCompat::None`.
     pub fn register_schema(
         env: Env,
         caller: Address,
         topic: String,
         version: u32,
         schema_hash: String,
+        compat: Option<registry::SchemaCompat>,
     ) -> Result<(), EventError> {
         caller.require_auth();
         Self::require_initialized(&env)?;
         Self::require_admin(&env, &caller)?;
-        registry::register_schema(&env, &topic, version, &schema_hash)
+        let compat = compat.unwrap_or(registry::SchemaCompat::None);
+        registry::register_schema(&env, &topic, version, &schema_hash, compat)
+    }
+
+    /// Report the compatibility declared for upgrading a topic's events from
+    /// `from_version` to `to_version`.
+    pub fn check_compat(
+        env: Env,
+        topic: String,
+        from_version: u32,
+        to_version: u32,
+    ) -> Result<registry::SchemaCompat, EventError> {
+        Self::require_initialized(&env)?;
+        registry::check_compat(&env, &topic, from_version, to_version)
     }
 
     /// Retrieve the schema hash for a given topic and version.
@@ -243,7 +280,64 @@ impl EventStreamContract {
     ) -> Result<u64, EventError> {
         subscriber.require_auth();
         Self::require_initialized(&env)?;
-        subscription::create_subscription(&env, &subscriber, &topic_pattern)
+        subscription::create_subscription(
+            &env,
+            &subscriber,
+            &topic_pattern,
+            subscription::DeliveryMode::BestEffort,
+            0,
+        )
+    }
+
+    /// Create a subscription with an explicit delivery guarantee.
+    ///
+    /// For `AtLeastOnce`, each dispatched event must be acked with
+    /// `ack_delivery` within `ack_timeout_secs` (0 selects the default),
+    /// otherwise it is dead-lettered by `expire_pending_deliveries`.
+    pub fn subscribe_with_delivery(
+        env: Env,
+        subscriber: Address,
+        topic_pattern: String,
+        delivery_mode: subscription::DeliveryMode,
+        ack_timeout_secs: u64,
+    ) -> Result<u64, EventError> {
+        subscriber.require_auth();
+        Self::require_initialized(&env)?;
+        subscription::create_subscription(
+            &env,
+            &subscriber,
+            &topic_pattern,
+            delivery_mode,
+            ack_timeout_secs,
+        )
+    }
+
+    /// Acknowledge an `AtLeastOnce` delivery of `event_id`.
+    pub fn ack_delivery(
+        env: Env,
+        subscriber: Address,
+        subscription_id: u64,
+        event_id: u64,
+    ) -> Result<(), EventError> {
+        subscriber.require_auth();
+        Self::require_initialized(&env)?;
+        subscription::ack_delivery(&env, &subscriber, subscription_id, event_id)
+    }
+
+    /// Return the unacked deliveries for a subscription.
+    pub fn get_pending_deliveries(
+        env: Env,
+        subscription_id: u64,
+    ) -> Result<Vec<subscription::PendingDelivery>, EventError> {
+        Self::require_initialized(&env)?;
+        subscription::get_pending_deliveries(&env, subscription_id)
+    }
+
+    /// Move timed-out `AtLeastOnce` deliveries to the dead letter queue.
+    /// Anyone may call this; returns the number of deliveries moved.
+    pub fn expire_pending_deliveries(env: Env) -> Result<u32, EventError> {
+        Self::require_initialized(&env)?;
+        Ok(subscription::expire_pending_deliveries(&env))
     }
 
     /// Remove an existing subscription.
@@ -291,6 +385,48 @@ impl EventStreamContract {
         subscription::get_consumer_group(&env, group_id)
     }
 
+    /// Add a consumer to a group. Only the group owner or the admin may do this.
+    /// Fails with `GroupMemberLimitExceeded` once the group holds
+    /// `MAX_GROUP_MEMBERS` consumers.
+    pub fn add_consumer_to_group(
+        env: Env,
+        caller: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<subscription::ConsumerGroup, EventError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_group_manager(&env, &caller, group_id)?;
+        subscription::add_consumer_to_group(&env, group_id, &member)
+    }
+
+    /// Remove a consumer from a group, e.g. after its key is compromised.
+    /// Events dispatched afterwards rotate over the remaining members only,
+    /// and its unacked assignments are reassigned to them.
+    /// Only the group owner or the admin may do this.
+    pub fn remove_consumer_from_group(
+        env: Env,
+        caller: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<subscription::ConsumerGroup, EventError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_group_manager(&env, &caller, group_id)?;
+        subscription::remove_consumer_from_group(&env, group_id, &member)
+    }
+
+    /// Return the events assigned to `member` of a group that it has not acked.
+    pub fn get_group_assignments(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<Vec<u64>, EventError> {
+        Self::require_initialized(&env)?;
+        subscription::get_consumer_group(&env, group_id)?;
+        Ok(subscription::get_group_assignments(&env, group_id, &member))
+    }
+
     /// Acknowledge that a consumer in a group has processed an event.
     pub fn ack_event(
         env: Env,
@@ -442,6 +578,14 @@ impl EventStreamContract {
         Ok(())
     }
 
+    fn require_group_manager(env: &Env, caller: &Address, group_id: u64) -> Result<(), EventError> {
+        let group = subscription::get_consumer_group(env, group_id)?;
+        if group.owner == *caller {
+            return Ok(());
+        }
+        Self::require_admin(env, caller)
+    }
+
     fn require_authorized_publisher(env: &Env, caller: &Address) -> Result<(), EventError> {
         let admin: Address = env
             .storage()
@@ -3,9 +3,27 @@
 //! Each topic can have multiple schema versions. Schemas are identified by a
 //! hash string (e.g. a content-addressable hash of the schema definition).
 //! Versions must be registered in ascending order to enforce forward evolution.
+//!
+//! Each version after the first may declare how it relates to its predecessor
+//! (`SchemaCompat`). The declaration is recorded as given; the registry cannot
+//! inspect schema bodies, so it only reports what publishers declared.
 
 use crate::EventError;
-use soroban_sdk::{symbol_short, Address, Env, String};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String};
+
+// ── Types ────────────────────────────────────────────────────────────────────
+
+/// Declared compatibility of a schema version with the version before it.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SchemaCompat {
+    /// No guarantee: consumers must handle the new version explicitly.
+    None,
+    /// Readers of the new version can still decode events of the old one.
+    Backward,
+    /// Readers of the old version can decode events of the new one.
+    Forward,
+}
 
 // ── Storage key helpers ──────────────────────────────────────────────────────
 
@@ -17,6 +35,10 @@ fn latest_version_key(topic: &String) -> (soroban_sdk::Symbol, String) {
     (symbol_short!("SCH_LAST"), topic.clone())
 }
 
+fn compat_key(topic: &String, version: u32) -> (soroban_sdk::Symbol, String, u32) {
+    (symbol_short!("SCH_CMP"), topic.clone(), version)
+}
+
 fn source_key(source: &Address) -> (soroban_sdk::Symbol, Address) {
     (symbol_short!("SRC"), source.clone())
 }
@@ -26,12 +48,14 @@ fn source_key(source: &Address) -> (soroban_sdk::Symbol, Address) {
 /// Register a new schema version for a given topic.
 ///
 /// Enforces that versions are registered in strictly ascending order so that
-/// consumers can rely on monotonically increasing version numbers.
+/// consumers can rely on monotonically increasing version numbers. `compat`
+/// is recorded against the previous version and ignored for a topic's first.
 pub fn register_schema(
     env: &Env,
     topic: &String,
     version: u32,
     schema_hash: &String,
+    compat: SchemaCompat,
 ) -> Result<(), EventError> {
     if topic.is_empty() || schema_hash.is_empty() {
         return Err(EventError::InvalidInput);
@@ -51,6 +75,12 @@ pub fn register_schema(
     let key = schema_key(topic, version);
     env.storage().persistent().set(&key, schema_hash);
     env.storage().persistent().set(&latest_key, &version);
+    if current_latest > 0 {
+        // Versions may skip numbers, so keep a link to the predecessor.
+        env.storage()
+            .persistent()
+            .set(&compat_key(topic, version), &(current_latest, compat));
+    }
 
     env.events().publish(
         (symbol_short!("SCH_REG"), topic.clone(), version),
@@ -78,6 +108,47 @@ pub fn get_latest_version(env: &Env, topic: &String) -> Result<u32, EventError>
         .ok_or(EventError::TopicNotFound)
 }
 
+/// Compatibility declared across every step from `from_version` up to
+/// `to_version`.
+///
+/// An upgrade is only as safe as its weakest step: a range reports `Backward`
+/// (or `Forward`) when every version after `from_version` declared it, and
+/// `None` otherwise. Both versions must be registered and `from_version` must
+/// be the older one.
+pub fn check_compat(
+    env: &Env,
+    topic: &String,
+    from_version: u32,
+    to_version: u32,
+) -> Result<SchemaCompat, EventError> {
+    if from_version >= to_version {
+        return Err(EventError::InvalidInput);
+    }
+    require_schema_exists(env, topic, from_version)?;
+    require_schema_exists(env, topic, to_version)?;
+
+    // Walk back from `to_version` along the predecessor links; every
+    // registered version in between is one step.
+    let mut declared: Option<SchemaCompat> = None;
+    let mut version = to_version;
+    while version > from_version {
+        // Versions registered before declarations existed carry no link.
+        let Some((prev, step)) = env
+            .storage()
+            .persistent()
+            .get::<_, (u32, SchemaCompat)>(&compat_key(topic, version))
+        else {
+            return Ok(SchemaCompat::None);
+        };
+        if step == SchemaCompat::None || declared.is_some_and(|d| d != step) {
+            return Ok(SchemaCompat::None);
+        }
+        declared = Some(step);
+        version = prev;
+    }
+    Ok(declared.unwrap_or(SchemaCompat::None))
+}
+
 /// Validate that a schema exists for the given topic and version.
 /// Used before publishing events to guarantee schema compliance.
 pub fn require_schema_exists(env: &Env, topic: &String, version: u32) -> Result<(), EventError> {
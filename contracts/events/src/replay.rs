@@ -6,6 +6,16 @@
 //!
 //! The dead letter queue captures events that failed delivery so they can be
 //! retried or inspected later.
+//!
+//! The global ordered index of event IDs is split into segments of
+//! [`LOG_SEGMENT_SIZE`] IDs so that no single storage entry grows without
+//! bound. Event IDs are appended in the order they are issued, so event `e`
+//! always lives in segment `(e - 1) / LOG_SEGMENT_SIZE`; compaction only
+//! removes IDs from their segment and never moves them, which keeps that
+//! mapping valid and lets replay seek straight to the right segment.
+//!
+//! Contracts upgraded from the single-vector `LOG_IDX` index have it split
+//! into segments on the first publish, replay or compaction afterwards.
 
 use crate::subscription::dispatch_to_subscribers;
 use crate::{EventEnvelope, EventError};
@@ -16,6 +26,24 @@ use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Vec};
 const CHKPT_CTR: soroban_sdk::Symbol = symbol_short!("CHKP_CTR");
 const DLQ_KEY: soroban_sdk::Symbol = symbol_short!("DLQ");
 const MAX_DLQ_SIZE: u32 = 100;
+const DLQ_RETRY: soroban_sdk::Symbol = symbol_short!("DLQ_RTRY");
+
+/// Wait imposed after the first retry of a dead letter; each further retry
+/// of the same delivery doubles it, up to `DLQ_MAX_BACKOFF_SECS`.
+pub const DLQ_BASE_BACKOFF_SECS: u64 = 60;
+
+/// Upper bound on the wait between retries of one delivery.
+pub const DLQ_MAX_BACKOFF_SECS: u64 = 86_400;
+
+/// Ledgers a delivery's retry history outlives its last retry (about two
+/// days at 5s ledgers). A delivery that fails again after this starts over.
+const RETRY_STATE_TTL_LEDGERS: u32 = 34_560;
+const LOG_SEGS: soroban_sdk::Symbol = symbol_short!("LOG_SEGS");
+/// Pre-segmentation index holding every event ID in one vector.
+const LEGACY_LOG_IDX: soroban_sdk::Symbol = symbol_short!("LOG_IDX");
+
+/// Number of event IDs held by each segment of the global index.
+pub const LOG_SEGMENT_SIZE: u64 = 1_000;
 
 // ── Types ────────────────────────────────────────────────────────────────────
 
@@ -36,6 +64,10 @@ pub struct DeadLetterEntry {
     pub subscriber: Address,
     pub reason: String,
     pub failed_at: u64,
+    /// Retries already made for this event and subscriber.
+    pub attempts: u32,
+    /// Earliest ledger timestamp at which `retry_dead_letter` accepts it.
+    pub next_retry_at: u64,
 }
 
 // ── Storage key helpers ──────────────────────────────────────────────────────
@@ -44,6 +76,10 @@ fn checkpoint_key(checkpoint_id: u64) -> (soroban_sdk::Symbol, u64) {
     (symbol_short!("CHKPT"), checkpoint_id)
 }
 
+fn retry_state_key(event_id: u64, subscriber: &Address) -> (soroban_sdk::Symbol, u64, Address) {
+    (DLQ_RETRY, event_id, subscriber.clone())
+}
+
 fn log_key(event_id: u64) -> (soroban_sdk::Symbol, u64) {
     (symbol_short!("LOG"), event_id)
 }
@@ -52,49 +88,131 @@ fn compacted_key(topic: &String) -> (soroban_sdk::Symbol, String) {
     (symbol_short!("T_CMPCT"), topic.clone())
 }
 
-// ── Replay ───────────────────────────────────────────────────────────────────
+fn segment_key(segment: u32) -> (soroban_sdk::Symbol, u32) {
+    (symbol_short!("LOG_SEG"), segment)
+}
 
-/// Replay events starting from `from_event_id` up to `limit` entries.
-///
-/// Walks the global ordered index and returns events whose ID is greater than
-/// or equal to `from_event_id`. This guarantees the same ordering that was
-/// established by the Lamport timestamps during publishing.
-pub fn replay_from(
-    env: &Env,
-    from_event_id: u64,
-    limit: u32,
-) -> Result<Vec<EventEnvelope>, EventError> {
-    if limit == 0 {
-        return Err(EventError::InvalidInput);
-    }
+// ── Segmented index ──────────────────────────────────────────────────────────
 
-    let idx_key = symbol_short!("LOG_IDX");
-    let index: Vec<u64> = env
+fn segment_of(event_id: u64) -> u32 {
+    (event_id.saturating_sub(1) / LOG_SEGMENT_SIZE) as u32
+}
+
+/// Number of index segments written so far.
+pub fn segment_count(env: &Env) -> u32 {
+    env.storage().instance().get(&LOG_SEGS).unwrap_or(0)
+}
+
+fn load_segment(env: &Env, segment: u32) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&segment_key(segment))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Split a legacy `LOG_IDX` vector into segments and remove it. IDs already
+/// in a segment are newer than every legacy ID, so they stay after them.
+fn migrate_legacy_index(env: &Env) {
+    let Some(legacy) = env
         .storage()
         .persistent()
-        .get(&idx_key)
-        .unwrap_or(Vec::new(env));
+        .get::<_, Vec<u64>>(&LEGACY_LOG_IDX)
+    else {
+        return;
+    };
+
+    let mut segments = segment_count(env);
+    let mut i = 0u32;
+    while i < legacy.len() {
+        let Some(first) = legacy.get(i) else {
+            break;
+        };
+        let segment = segment_of(first);
+        let mut ids = Vec::new(env);
+        while let Some(eid) = legacy.get(i) {
+            if segment_of(eid) != segment {
+                break;
+            }
+            ids.push_back(eid);
+            i = i.saturating_add(1);
+        }
+        ids.append(&load_segment(env, segment));
+        env.storage().persistent().set(&segment_key(segment), &ids);
+        segments = segments.max(segment.saturating_add(1));
+    }
+
+    env.storage().instance().set(&LOG_SEGS, &segments);
+    env.storage().persistent().remove(&LEGACY_LOG_IDX);
+}
+
+/// Append a newly issued event ID to the global ordered index.
+pub fn append_to_index(env: &Env, event_id: u64) {
+    migrate_legacy_index(env);
+
+    let segment = segment_of(event_id);
+    let mut ids = load_segment(env, segment);
+    ids.push_back(event_id);
+    env.storage().persistent().set(&segment_key(segment), &ids);
+
+    if segment >= segment_count(env) {
+        env.storage()
+            .instance()
+            .set(&LOG_SEGS, &segment.saturating_add(1));
+    }
+}
+
+/// Walk the index from `from_event_id` onwards, skipping segments that only
+/// hold earlier IDs, and collect up to `limit` envelopes accepted by `keep`.
+fn collect_from<F>(env: &Env, from_event_id: u64, limit: u32, keep: F) -> Vec<EventEnvelope>
+where
+    F: Fn(&EventEnvelope) -> bool,
+{
+    migrate_legacy_index(env);
 
     let mut result = Vec::new(env);
     let mut collected = 0u32;
 
-    for eid in index.iter() {
-        if eid >= from_event_id && collected < limit {
+    for segment in segment_of(from_event_id)..segment_count(env) {
+        for eid in load_segment(env, segment).iter() {
+            if eid < from_event_id {
+                continue;
+            }
             if let Some(envelope) = env
                 .storage()
                 .persistent()
                 .get::<_, EventEnvelope>(&log_key(eid))
             {
-                result.push_back(envelope);
-                collected += 1;
+                if keep(&envelope) {
+                    result.push_back(envelope);
+                    collected += 1;
+                    if collected >= limit {
+                        return result;
+                    }
+                }
             }
         }
-        if collected >= limit {
-            break;
-        }
     }
 
-    Ok(result)
+    result
+}
+
+// ── Replay ───────────────────────────────────────────────────────────────────
+
+/// Replay events starting from `from_event_id` up to `limit` entries.
+///
+/// Walks the global ordered index and returns events whose ID is greater than
+/// or equal to `from_event_id`. This guarantees the same ordering that was
+/// established by the Lamport timestamps during publishing.
+pub fn replay_from(
+    env: &Env,
+    from_event_id: u64,
+    limit: u32,
+) -> Result<Vec<EventEnvelope>, EventError> {
+    if limit == 0 {
+        return Err(EventError::InvalidInput);
+    }
+
+    Ok(collect_from(env, from_event_id, limit, |_| true))
 }
 
 /// Replay events for a specific topic starting from a given event ID.
@@ -110,35 +228,9 @@ pub fn replay_topic(
         return Err(EventError::InvalidInput);
     }
 
-    let idx_key = symbol_short!("LOG_IDX");
-    let index: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&idx_key)
-        .unwrap_or(Vec::new(env));
-
-    let mut result = Vec::new(env);
-    let mut collected = 0u32;
-
-    for eid in index.iter() {
-        if eid >= from_event_id && collected < limit {
-            if let Some(envelope) = env
-                .storage()
-                .persistent()
-                .get::<_, EventEnvelope>(&log_key(eid))
-            {
-                if envelope.topic == *topic {
-                    result.push_back(envelope);
-                    collected += 1;
-                }
-            }
-        }
-        if collected >= limit {
-            break;
-        }
-    }
-
-    Ok(result)
+    Ok(collect_from(env, from_event_id, limit, |envelope| {
+        envelope.topic == *topic
+    }))
 }
 
 // ── Checkpoints ──────────────────────────────────────────────────────────────
@@ -192,23 +284,21 @@ pub fn compact_topic(env: &Env, topic: &String) -> Result<u32, EventError> {
         return Err(EventError::InvalidInput);
     }
 
-    let idx_key = symbol_short!("LOG_IDX");
-    let index: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&idx_key)
-        .unwrap_or(Vec::new(env));
+    migrate_legacy_index(env);
+    let segments = segment_count(env);
 
     // Collect all event IDs for this topic
     let mut topic_event_ids = Vec::new(env);
-    for eid in index.iter() {
-        if let Some(envelope) = env
-            .storage()
-            .persistent()
-            .get::<_, EventEnvelope>(&log_key(eid))
-        {
-            if envelope.topic == *topic {
-                topic_event_ids.push_back(eid);
+    for segment in 0..segments {
+        for eid in load_segment(env, segment).iter() {
+            if let Some(envelope) = env
+                .storage()
+                .persistent()
+                .get::<_, EventEnvelope>(&log_key(eid))
+            {
+                if envelope.topic == *topic {
+                    topic_event_ids.push_back(eid);
+                }
             }
         }
     }
@@ -227,14 +317,19 @@ pub fn compact_topic(env: &Env, topic: &String) -> Result<u32, EventError> {
         }
     }
 
-    // Rebuild the global index without compacted entries
-    let mut new_index = Vec::new(env);
-    for eid in index.iter() {
-        if env.storage().persistent().has(&log_key(eid)) {
-            new_index.push_back(eid);
+    // Drop compacted entries from each index segment in place
+    for segment in 0..segments {
+        let ids = load_segment(env, segment);
+        let mut kept = Vec::new(env);
+        for eid in ids.iter() {
+            if env.storage().persistent().has(&log_key(eid)) {
+                kept.push_back(eid);
+            }
+        }
+        if kept.len() != ids.len() {
+            env.storage().persistent().set(&segment_key(segment), &kept);
         }
     }
-    env.storage().persistent().set(&idx_key, &new_index);
 
     // Record compaction metadata
     env.storage()
@@ -249,7 +344,26 @@ pub fn compact_topic(env: &Env, topic: &String) -> Result<u32, EventError> {
 
 // ── Dead letter queue ────────────────────────────────────────────────────────
 
+/// Backoff applied after the `attempts`-th retry of a delivery.
+pub fn retry_backoff(attempts: u32) -> u64 {
+    let doublings = attempts.saturating_sub(1).min(32);
+    DLQ_BASE_BACKOFF_SECS
+        .saturating_mul(1u64 << doublings)
+        .min(DLQ_MAX_BACKOFF_SECS)
+}
+
+/// Forget the retry history of a delivery, e.g. once it has been acked.
+pub fn clear_retry_state(env: &Env, event_id: u64, subscriber: &Address) {
+    env.storage()
+        .temporary()
+        .remove(&retry_state_key(event_id, subscriber));
+}
+
 /// Push a failed delivery into the dead letter queue.
+///
+/// A delivery that was already retried keeps its attempt count and backoff,
+/// so one that keeps failing cannot be retried in a tight loop. A first
+/// failure may be retried straight away.
 pub fn push_dead_letter(
     env: &Env,
     event_id: u64,
@@ -266,11 +380,20 @@ pub fn push_dead_letter(
         return Err(EventError::DeadLetterFull);
     }
 
+    let failed_at = env.ledger().timestamp();
+    let (attempts, next_retry_at) = env
+        .storage()
+        .temporary()
+        .get(&retry_state_key(event_id, subscriber))
+        .unwrap_or((0u32, failed_at));
+
     let entry = DeadLetterEntry {
         event_id,
         subscriber: subscriber.clone(),
         reason: reason.clone(),
-        failed_at: env.ledger().timestamp(),
+        failed_at,
+        attempts,
+        next_retry_at,
     };
 
     dlq.push_back(entry.clone());
@@ -292,7 +415,10 @@ pub fn get_dead_letters(env: &Env) -> Vec<DeadLetterEntry> {
 
 /// Retry a specific dead letter entry by re-dispatching the original event.
 ///
-/// Removes the entry from the DLQ upon successful re-dispatch.
+/// Removes the entry from the DLQ upon successful re-dispatch. Fails with
+/// `RetryTooEarly` before the entry's `next_retry_at`; otherwise the attempt
+/// is counted and the next retry of the same delivery is pushed back by
+/// `retry_backoff`.
 pub fn retry_dead_letter(env: &Env, dead_letter_index: u32) -> Result<(), EventError> {
     let dlq: Vec<DeadLetterEntry> = env
         .storage()
@@ -306,6 +432,11 @@ pub fn retry_dead_letter(env: &Env, dead_letter_index: u32) -> Result<(), EventE
 
     let entry = dlq.get(dead_letter_index).ok_or(EventError::InvalidInput)?;
 
+    let now = env.ledger().timestamp();
+    if now < entry.next_retry_at {
+        return Err(EventError::RetryTooEarly);
+    }
+
     // Retrieve the original event
     let envelope: EventEnvelope = env
         .storage()
@@ -313,6 +444,18 @@ pub fn retry_dead_letter(env: &Env, dead_letter_index: u32) -> Result<(), EventE
         .get(&log_key(entry.event_id))
         .ok_or(EventError::EventNotFound)?;
 
+    let attempts = entry.attempts.saturating_add(1);
+    let next_retry_at = now.saturating_add(retry_backoff(attempts));
+    let state_key = retry_state_key(entry.event_id, &entry.subscriber);
+    env.storage()
+        .temporary()
+        .set(&state_key, &(attempts, next_retry_at));
+    env.storage().temporary().extend_ttl(
+        &state_key,
+        RETRY_STATE_TTL_LEDGERS,
+        RETRY_STATE_TTL_LEDGERS,
+    );
+
     // Re-dispatch the event to subscribers
     dispatch_to_subscribers(env, &envelope);
 
@@ -4,11 +4,22 @@
 //! - `records.vision.create` — exact match
 //! - `records.vision.*` — matches any single segment after `records.vision.`
 //! - `records.*` — matches any single segment after `records.`
+//! - `records.**` — matches `records` and everything beneath it, at any depth
 //!
 //! Consumer groups allow multiple consumers to share event processing load.
 //! Each event matching the group topic is assigned to exactly one member using
 //! round-robin distribution based on the group's internal offset counter.
+//! Assignments are computed against the current member list at dispatch time,
+//! so adding or removing a member rebalances every event dispatched after it.
+//! Each member's unacked assignments are recorded; removing a member hands
+//! those events to the remaining members in the same round-robin order.
+//!
+//! Subscriptions default to best-effort delivery. An `AtLeastOnce` subscription
+//! gets a pending-delivery entry per dispatched event that must be acked via
+//! `ack_delivery`; entries left unacked past the subscription's timeout are
+//! moved to the dead letter queue by `expire_pending_deliveries` for retry.
 
+use crate::replay::{clear_retry_state, push_dead_letter};
 use crate::{EventEnvelope, EventError};
 use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Vec};
 
@@ -20,8 +31,24 @@ const WHK_CTR: soroban_sdk::Symbol = symbol_short!("WHK_CTR");
 
 const MAX_WEBHOOKS_PER_USER: u32 = 10;
 
+/// Maximum number of members a consumer group may hold.
+pub const MAX_GROUP_MEMBERS: u32 = 32;
+
+/// Ack timeout applied when an `AtLeastOnce` subscription does not set one.
+pub const DEFAULT_ACK_TIMEOUT_SECS: u64 = 3_600;
+
 // ── Types ────────────────────────────────────────────────────────────────────
 
+/// Delivery guarantee requested by a subscription.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DeliveryMode {
+    /// Fire-and-forget: a `DISPATCH` event is emitted and nothing is tracked.
+    BestEffort,
+    /// Every dispatch is tracked until acked, or dead-lettered on timeout.
+    AtLeastOnce,
+}
+
 /// A topic-based subscription owned by a single subscriber.
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -31,6 +58,19 @@ pub struct Subscription {
     pub topic_pattern: String,
     pub created_at: u64,
     pub active: bool,
+    pub delivery_mode: DeliveryMode,
+    /// Seconds an `AtLeastOnce` delivery may stay unacked (0 for best effort).
+    pub ack_timeout_secs: u64,
+}
+
+/// An `AtLeastOnce` delivery awaiting acknowledgement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingDelivery {
+    pub subscription_id: u64,
+    pub event_id: u64,
+    pub dispatched_at: u64,
+    pub deadline: u64,
 }
 
 /// A consumer group that distributes event processing across members.
@@ -93,6 +133,14 @@ fn ack_key(group_id: u64, event_id: u64) -> (soroban_sdk::Symbol, u64, u64) {
     (symbol_short!("ACK"), group_id, event_id)
 }
 
+fn assigned_key(group_id: u64, member: &Address) -> (soroban_sdk::Symbol, u64, Address) {
+    (symbol_short!("GRP_ASGN"), group_id, member.clone())
+}
+
+fn pending_key(sub_id: u64) -> (soroban_sdk::Symbol, u64) {
+    (symbol_short!("PEND_DLV"), sub_id)
+}
+
 fn all_subs_key() -> soroban_sdk::Symbol {
     symbol_short!("ALL_SUBS")
 }
@@ -108,16 +156,27 @@ fn all_webhooks_key() -> soroban_sdk::Symbol {
 // ── Subscription CRUD ────────────────────────────────────────────────────────
 
 /// Create a new subscription for the given subscriber and topic pattern.
+///
+/// `ack_timeout_secs` only applies to `AtLeastOnce` subscriptions; 0 selects
+/// [`DEFAULT_ACK_TIMEOUT_SECS`].
 #[allow(clippy::arithmetic_side_effects)]
 pub fn create_subscription(
     env: &Env,
     subscriber: &Address,
     topic_pattern: &String,
+    delivery_mode: DeliveryMode,
+    ack_timeout_secs: u64,
 ) -> Result<u64, EventError> {
-    if topic_pattern.is_empty() {
+    if !is_valid_topic_pattern(env, topic_pattern) {
         return Err(EventError::InvalidTopicPattern);
     }
 
+    let ack_timeout_secs = match delivery_mode {
+        DeliveryMode::BestEffort => 0,
+        DeliveryMode::AtLeastOnce if ack_timeout_secs == 0 => DEFAULT_ACK_TIMEOUT_SECS,
+        DeliveryMode::AtLeastOnce => ack_timeout_secs,
+    };
+
     // Check for duplicate active subscriptions on the same pattern
     let existing = get_subscriptions(env, subscriber);
     for sub in existing.iter() {
@@ -134,6 +193,8 @@ pub fn create_subscription(
         topic_pattern: topic_pattern.clone(),
         created_at: env.ledger().timestamp(),
         active: true,
+        delivery_mode,
+        ack_timeout_secs,
     };
 
     env.storage()
@@ -218,6 +279,147 @@ pub fn get_subscriptions(env: &Env, subscriber: &Address) -> Vec<Subscription> {
     result
 }
 
+// ── Delivery tracking ────────────────────────────────────────────────────────
+
+/// Return the unacked deliveries recorded for a subscription, oldest first.
+pub fn get_pending_deliveries(
+    env: &Env,
+    subscription_id: u64,
+) -> Result<Vec<PendingDelivery>, EventError> {
+    if !env.storage().persistent().has(&sub_key(subscription_id)) {
+        return Err(EventError::SubscriptionNotFound);
+    }
+    Ok(env
+        .storage()
+        .persistent()
+        .get(&pending_key(subscription_id))
+        .unwrap_or(Vec::new(env)))
+}
+
+/// Acknowledge an `AtLeastOnce` delivery. Only the subscriber can do this.
+pub fn ack_delivery(
+    env: &Env,
+    subscriber: &Address,
+    subscription_id: u64,
+    event_id: u64,
+) -> Result<(), EventError> {
+    let sub: Subscription = env
+        .storage()
+        .persistent()
+        .get(&sub_key(subscription_id))
+        .ok_or(EventError::SubscriptionNotFound)?;
+    if sub.subscriber != *subscriber {
+        return Err(EventError::Unauthorized);
+    }
+
+    let pending = get_pending_deliveries(env, subscription_id)?;
+    let mut remaining = Vec::new(env);
+    let mut found = false;
+    for delivery in pending.iter() {
+        if delivery.event_id == event_id {
+            found = true;
+        } else {
+            remaining.push_back(delivery);
+        }
+    }
+    if !found {
+        return Err(EventError::DeliveryNotFound);
+    }
+    store_pending(env, subscription_id, &remaining);
+    clear_retry_state(env, event_id, subscriber);
+
+    env.events().publish(
+        (
+            symbol_short!("DLV_ACK"),
+            subscriber.clone(),
+            subscription_id,
+        ),
+        event_id,
+    );
+
+    Ok(())
+}
+
+/// Move every unacked delivery past its deadline into the dead letter queue.
+///
+/// Returns the number of deliveries moved. Stops early, leaving the rest
+/// pending, if the dead letter queue fills up.
+pub fn expire_pending_deliveries(env: &Env) -> u32 {
+    let now = env.ledger().timestamp();
+    let reason = String::from_str(env, "ack timeout");
+    let all_sub_ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&all_subs_key())
+        .unwrap_or(Vec::new(env));
+
+    let mut moved = 0u32;
+    for sub_id in all_sub_ids.iter() {
+        let pending: Vec<PendingDelivery> =
+            match env.storage().persistent().get(&pending_key(sub_id)) {
+                Some(p) => p,
+                None => continue,
+            };
+        let sub: Subscription = match env.storage().persistent().get(&sub_key(sub_id)) {
+            Some(s) => s,
+            None => continue,
+        };
+
+        let mut remaining = Vec::new(env);
+        let mut dlq_full = false;
+        for delivery in pending.iter() {
+            if dlq_full || now < delivery.deadline {
+                remaining.push_back(delivery);
+                continue;
+            }
+            if push_dead_letter(env, delivery.event_id, &sub.subscriber, &reason).is_err() {
+                dlq_full = true;
+                remaining.push_back(delivery);
+                continue;
+            }
+            moved = moved.saturating_add(1);
+        }
+        store_pending(env, sub_id, &remaining);
+        if dlq_full {
+            break;
+        }
+    }
+    moved
+}
+
+/// Track a dispatch to an `AtLeastOnce` subscription, refreshing the deadline
+/// if the event is already pending (e.g. on dead-letter retry).
+fn record_pending_delivery(env: &Env, sub: &Subscription, event_id: u64) {
+    let now = env.ledger().timestamp();
+    let delivery = PendingDelivery {
+        subscription_id: sub.id,
+        event_id,
+        dispatched_at: now,
+        deadline: now.saturating_add(sub.ack_timeout_secs),
+    };
+
+    let mut pending: Vec<PendingDelivery> = env
+        .storage()
+        .persistent()
+        .get(&pending_key(sub.id))
+        .unwrap_or(Vec::new(env));
+    let existing = pending.iter().position(|d| d.event_id == event_id);
+    match existing {
+        Some(i) => pending.set(i as u32, delivery),
+        None => pending.push_back(delivery),
+    }
+    store_pending(env, sub.id, &pending);
+}
+
+fn store_pending(env: &Env, subscription_id: u64, pending: &Vec<PendingDelivery>) {
+    let key = pending_key(subscription_id);
+    if pending.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, pending);
+    }
+}
+
 // ── Consumer groups ──────────────────────────────────────────────────────────
 
 /// Create a consumer group that distributes events across its members.
@@ -236,6 +438,13 @@ pub fn create_consumer_group(
     if members.is_empty() {
         return Err(EventError::InvalidInput);
     }
+    if members.len() > MAX_GROUP_MEMBERS {
+        return Err(EventError::GroupMemberLimitExceeded);
+    }
+
+    if !is_valid_topic_pattern(env, topic_pattern) {
+        return Err(EventError::InvalidTopicPattern);
+    }
 
     let group_id = next_group_id(env);
 
@@ -275,6 +484,69 @@ pub fn get_consumer_group(env: &Env, group_id: u64) -> Result<ConsumerGroup, Eve
         .ok_or(EventError::ConsumerGroupNotFound)
 }
 
+/// Add `member` to a consumer group's rotation, up to [`MAX_GROUP_MEMBERS`].
+pub fn add_consumer_to_group(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+) -> Result<ConsumerGroup, EventError> {
+    let mut group = get_consumer_group(env, group_id)?;
+    if group.members.contains(member) {
+        return Err(EventError::InvalidInput);
+    }
+    if group.members.len() >= MAX_GROUP_MEMBERS {
+        return Err(EventError::GroupMemberLimitExceeded);
+    }
+
+    group.members.push_back(member.clone());
+    env.storage().persistent().set(&group_key(group_id), &group);
+
+    env.events()
+        .publish((symbol_short!("GRP_ADD"), group_id), member.clone());
+
+    Ok(group)
+}
+
+/// Remove `member` from a consumer group so no further events are assigned
+/// to it and it can no longer ack. A group must keep at least one member.
+///
+/// Events assigned to `member` that no one has acked yet are reassigned
+/// round-robin to the remaining members and dispatched again.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn remove_consumer_from_group(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+) -> Result<ConsumerGroup, EventError> {
+    let mut group = get_consumer_group(env, group_id)?;
+    let index = group
+        .members
+        .first_index_of(member)
+        .ok_or(EventError::InvalidInput)?;
+    if group.members.len() == 1 {
+        return Err(EventError::InvalidInput);
+    }
+
+    group.members.remove(index);
+
+    let orphaned = get_group_assignments(env, group_id, member);
+    env.storage()
+        .persistent()
+        .remove(&assigned_key(group_id, member));
+    for event_id in orphaned.iter() {
+        if env.storage().persistent().has(&ack_key(group_id, event_id)) {
+            continue;
+        }
+        assign_to_next_member(env, &mut group, event_id);
+    }
+    env.storage().persistent().set(&group_key(group_id), &group);
+
+    env.events()
+        .publish((symbol_short!("GRP_REM"), group_id), member.clone());
+
+    Ok(group)
+}
+
 /// Acknowledge that a consumer in a group has processed an event.
 pub fn ack_event(
     env: &Env,
@@ -303,6 +575,12 @@ pub fn ack_event(
     let key = ack_key(group_id, event_id);
     env.storage().persistent().set(&key, &true);
 
+    let mut assigned = get_group_assignments(env, group_id, consumer);
+    if let Some(i) = assigned.first_index_of(event_id) {
+        assigned.remove(i);
+        store_assignments(env, group_id, consumer, &assigned);
+    }
+
     env.events().publish(
         (symbol_short!("EVT_ACK"), consumer.clone(), group_id),
         event_id,
@@ -311,6 +589,43 @@ pub fn ack_event(
     Ok(())
 }
 
+/// Return the events assigned to `member` of a group that it has not acked,
+/// oldest first.
+pub fn get_group_assignments(env: &Env, group_id: u64, member: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&assigned_key(group_id, member))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Assign `event_id` to the member at the group's offset and advance it.
+/// The caller persists the updated group.
+#[allow(clippy::arithmetic_side_effects)]
+fn assign_to_next_member(env: &Env, group: &mut ConsumerGroup, event_id: u64) {
+    let member_count = group.members.len();
+    if member_count == 0 {
+        return;
+    }
+    let target_index = (group.offset as u32) % member_count;
+    if let Some(target) = group.members.get(target_index) {
+        let mut assigned = get_group_assignments(env, group.id, &target);
+        assigned.push_back(event_id);
+        store_assignments(env, group.id, &target, &assigned);
+        env.events()
+            .publish((symbol_short!("GRP_DISP"), target, group.id), event_id);
+    }
+    group.offset += 1;
+}
+
+fn store_assignments(env: &Env, group_id: u64, member: &Address, assigned: &Vec<u64>) {
+    let key = assigned_key(group_id, member);
+    if assigned.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, assigned);
+    }
+}
+
 // ── Webhook registration ─────────────────────────────────────────────────────
 
 /// Register a webhook for push-based event notification.
@@ -327,6 +642,10 @@ pub fn register_webhook(
         return Err(EventError::InvalidInput);
     }
 
+    if !is_valid_topic_pattern(env, topic_pattern) {
+        return Err(EventError::InvalidTopicPattern);
+    }
+
     // Enforce per-user webhook limit
     let user_key = user_webhooks_key(owner);
     let existing: Vec<u64> = env
@@ -409,8 +728,11 @@ pub fn remove_webhook(env: &Env, owner: &Address, webhook_id: u64) -> Result<(),
 /// - `records.vision.*` — matches `records.vision.<anything>` (single segment)
 /// - `records.*` — matches `records.<anything>` (single segment)
 /// - `*` — matches any single-segment topic
+/// - `records.**` — matches `records` and any topic beneath it, at any depth
+/// - `**` — matches every topic
 ///
-/// The matching operates on dot-delimited segments.
+/// The matching operates on dot-delimited segments. `**` is only meaningful
+/// as the final segment; patterns using it elsewhere match nothing.
 pub fn topic_matches(env: &Env, pattern: &String, topic: &String) -> bool {
     if *pattern == *topic {
         return true;
@@ -418,18 +740,26 @@ pub fn topic_matches(env: &Env, pattern: &String, topic: &String) -> bool {
 
     let pattern_str = string_to_segments(env, pattern);
     let topic_str = string_to_segments(env, topic);
-
-    if pattern_str.len() != topic_str.len() {
+    let star = String::from_str(env, "*");
+    let globstar = String::from_str(env, "**");
+
+    let mut fixed_len = pattern_str.len();
+    let recursive = pattern_str.last() == Some(globstar.clone());
+    if recursive {
+        fixed_len -= 1;
+        if topic_str.len() < fixed_len {
+            return false;
+        }
+    } else if pattern_str.len() != topic_str.len() {
         return false;
     }
 
-    for i in 0..pattern_str.len() {
+    for i in 0..fixed_len {
         let p_seg = pattern_str.get(i);
         let t_seg = topic_str.get(i);
         match (p_seg, t_seg) {
             (Some(p), Some(t)) => {
-                let star = String::from_str(env, "*");
-                if p != star && p != t {
+                if p == globstar || (p != star && p != t) {
                     return false;
                 }
             }
@@ -440,6 +770,21 @@ pub fn topic_matches(env: &Env, pattern: &String, topic: &String) -> bool {
     true
 }
 
+/// Whether `pattern` can be used to subscribe: it must be non-empty and may
+/// only use `**` as its final segment.
+pub fn is_valid_topic_pattern(env: &Env, pattern: &String) -> bool {
+    if pattern.is_empty() {
+        return false;
+    }
+
+    let segments = string_to_segments(env, pattern);
+    let globstar = String::from_str(env, "**");
+    match segments.first_index_of(&globstar) {
+        Some(i) => i + 1 == segments.len(),
+        None => true,
+    }
+}
+
 /// Split a dot-delimited string into segments.
 fn string_to_segments(env: &Env, s: &String) -> Vec<String> {
     let mut segments = Vec::new(env);
@@ -482,8 +827,9 @@ fn string_to_segments(env: &Env, s: &String) -> Vec<String> {
 /// Dispatch an event to all matching subscriptions and consumer groups.
 ///
 /// For individual subscriptions, a Soroban event is emitted per match so that
-/// off-chain indexers can push notifications. For consumer groups, events are
-/// assigned to exactly one member via round-robin.
+/// off-chain indexers can push notifications; `AtLeastOnce` subscriptions also
+/// get a pending-delivery entry. For consumer groups, events are assigned to
+/// exactly one member via round-robin.
 #[allow(clippy::arithmetic_side_effects)]
 pub fn dispatch_to_subscribers(env: &Env, envelope: &EventEnvelope) {
     // Dispatch to individual subscriptions
@@ -508,6 +854,9 @@ pub fn dispatch_to_subscribers(env: &Env, envelope: &EventEnvelope) {
                     ),
                     envelope.topic.clone(),
                 );
+                if sub.delivery_mode == DeliveryMode::AtLeastOnce {
+                    record_pending_delivery(env, &sub, envelope.event_id);
+                }
             }
         }
     }
@@ -523,18 +872,8 @@ pub fn dispatch_to_subscribers(env: &Env, envelope: &EventEnvelope) {
         let key = group_key(gid);
         if let Some(mut group) = env.storage().persistent().get::<_, ConsumerGroup>(&key) {
             if topic_matches(env, &group.topic_pattern, &envelope.topic) {
-                let member_count = group.members.len();
-                if member_count > 0 {
-                    let target_index = (group.offset as u32) % member_count;
-                    if let Some(target) = group.members.get(target_index) {
-                        env.events().publish(
-                            (symbol_short!("GRP_DISP"), target.clone(), gid),
-                            envelope.event_id,
-                        );
-                    }
-                    group.offset += 1;
-                    env.storage().persistent().set(&key, &group);
-                }
+                assign_to_next_member(env, &mut group, envelope.event_id);
+                env.storage().persistent().set(&key, &group);
             }
         }
     }
@@ -1,7 +1,13 @@
 extern crate std;
 
-use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
-
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String, Vec,
+};
+
+use crate::registry::SchemaCompat;
+use crate::replay::{retry_backoff, DLQ_BASE_BACKOFF_SECS, DLQ_MAX_BACKOFF_SECS};
+use crate::subscription::{DeliveryMode, DEFAULT_ACK_TIMEOUT_SECS, MAX_GROUP_MEMBERS};
 use crate::{EventError, EventStreamContract, EventStreamContractClient};
 
 // ── Test helpers ─────────────────────────────────────────────────────────────
@@ -30,7 +36,7 @@ fn publish_test_event(
 ) -> u64 {
     let topic_str = String::from_str(env, topic);
     let payload_str = String::from_str(env, payload);
-    client.publish_event(admin, &topic_str, &schema_version, &payload_str)
+    client.publish_event(admin, &topic_str, &schema_version, &payload_str, &None)
 }
 
 fn register_schema(
@@ -42,7 +48,7 @@ fn register_schema(
 ) {
     let topic_str = String::from_str(env, topic);
     let hash = String::from_str(env, "sha256:abc123");
-    client.register_schema(admin, &topic_str, &version, &hash);
+    client.register_schema(admin, &topic_str, &version, &hash, &None);
 }
 
 // ── Initialization tests ─────────────────────────────────────────────────────
@@ -77,7 +83,7 @@ fn test_register_and_get_schema() {
     let topic = String::from_str(&env, "records.vision.create");
     let hash = String::from_str(&env, "sha256:deadbeef");
 
-    client.register_schema(&admin, &topic, &1, &hash);
+    client.register_schema(&admin, &topic, &1, &hash, &None);
 
     let retrieved = client.get_schema(&topic, &1);
     assert_eq!(retrieved, hash);
@@ -93,8 +99,8 @@ fn test_schema_versioning_ascending() {
     let hash_v1 = String::from_str(&env, "sha256:v1");
     let hash_v2 = String::from_str(&env, "sha256:v2");
 
-    client.register_schema(&admin, &topic, &1, &hash_v1);
-    client.register_schema(&admin, &topic, &2, &hash_v2);
+    client.register_schema(&admin, &topic, &1, &hash_v1, &None);
+    client.register_schema(&admin, &topic, &2, &hash_v2, &None);
 
     assert_eq!(client.get_schema(&topic, &1), hash_v1);
     assert_eq!(client.get_schema(&topic, &2), hash_v2);
@@ -107,10 +113,10 @@ fn test_schema_version_must_be_ascending() {
     let topic = String::from_str(&env, "records.vision.create");
     let hash = String::from_str(&env, "sha256:v1");
 
-    client.register_schema(&admin, &topic, &2, &hash);
+    client.register_schema(&admin, &topic, &2, &hash, &None);
 
     // Registering version 1 after version 2 should fail
-    let result = client.try_register_schema(&admin, &topic, &1, &hash);
+    let result = client.try_register_schema(&admin, &topic, &1, &hash, &None);
     match result {
         Err(Ok(e)) => assert_eq!(e, EventError::InvalidSchema),
         _ => panic!("Expected InvalidSchema error"),
@@ -123,13 +129,60 @@ fn test_schema_version_zero_rejected() {
     let topic = String::from_str(&env, "records.vision.create");
     let hash = String::from_str(&env, "sha256:v0");
 
-    let result = client.try_register_schema(&admin, &topic, &0, &hash);
+    let result = client.try_register_schema(&admin, &topic, &0, &hash, &None);
     match result {
         Err(Ok(e)) => assert_eq!(e, EventError::InvalidSchema),
         _ => panic!("Expected InvalidSchema error"),
     }
 }
 
+#[test]
+fn test_schema_compat_reflects_declaration() {
+    let (env, client, admin) = setup();
+    let topic = String::from_str(&env, "records.vision.create");
+    let hash = String::from_str(&env, "sha256:v");
+
+    client.register_schema(&admin, &topic, &1, &hash, &None);
+    client.register_schema(&admin, &topic, &2, &hash, &Some(SchemaCompat::Backward));
+    assert_eq!(client.check_compat(&topic, &1, &2), SchemaCompat::Backward);
+
+    // Versions may skip numbers; the next declaration links to v2.
+    client.register_schema(&admin, &topic, &5, &hash, &Some(SchemaCompat::Backward));
+    assert_eq!(client.check_compat(&topic, &1, &5), SchemaCompat::Backward);
+    assert_eq!(client.check_compat(&topic, &2, &5), SchemaCompat::Backward);
+
+    // One undeclared step makes the whole range unsafe.
+    client.register_schema(&admin, &topic, &6, &hash, &None);
+    assert_eq!(client.check_compat(&topic, &1, &6), SchemaCompat::None);
+    assert_eq!(client.check_compat(&topic, &2, &5), SchemaCompat::Backward);
+}
+
+#[test]
+fn test_schema_compat_mixed_and_invalid_ranges() {
+    let (env, client, admin) = setup();
+    let topic = String::from_str(&env, "records.vision.create");
+    let hash = String::from_str(&env, "sha256:v");
+
+    client.register_schema(&admin, &topic, &1, &hash, &Some(SchemaCompat::Forward));
+    client.register_schema(&admin, &topic, &2, &hash, &Some(SchemaCompat::Forward));
+    client.register_schema(&admin, &topic, &3, &hash, &Some(SchemaCompat::Backward));
+    assert_eq!(client.check_compat(&topic, &1, &2), SchemaCompat::Forward);
+    assert_eq!(client.check_compat(&topic, &1, &3), SchemaCompat::None);
+
+    assert_eq!(
+        client.try_check_compat(&topic, &2, &2),
+        Err(Ok(EventError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_check_compat(&topic, &3, &1),
+        Err(Ok(EventError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_check_compat(&topic, &1, &4),
+        Err(Ok(EventError::SchemaNotFound))
+    );
+}
+
 // ── Event publishing tests ───────────────────────────────────────────────────
 
 #[test]
@@ -174,13 +227,68 @@ fn test_lamport_ordering() {
     assert!(id2 < id3);
 }
 
+#[test]
+fn test_publish_with_same_dedup_key_is_idempotent() {
+    let (env, client, admin) = setup();
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    let topic = String::from_str(&env, "records.vision.create");
+    let payload = String::from_str(&env, "retried_payload");
+    let key = Some(String::from_str(&env, "retry-1"));
+
+    let first = client.publish_event(&admin, &topic, &1, &payload, &key);
+    let retry = client.publish_event(&admin, &topic, &1, &payload, &key);
+
+    assert_eq!(first, retry);
+    assert_eq!(client.get_event_count(), 1);
+    assert_eq!(client.get_lamport_clock(), 1);
+    assert_eq!(client.replay_events(&1, &10).len(), 1);
+}
+
+#[test]
+fn test_publish_with_distinct_dedup_keys_appends() {
+    let (env, client, admin) = setup();
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    let topic = String::from_str(&env, "records.vision.create");
+    let payload = String::from_str(&env, "payload");
+
+    let first = client.publish_event(
+        &admin,
+        &topic,
+        &1,
+        &payload,
+        &Some(String::from_str(&env, "key-a")),
+    );
+    let second = client.publish_event(
+        &admin,
+        &topic,
+        &1,
+        &payload,
+        &Some(String::from_str(&env, "key-b")),
+    );
+    assert_ne!(first, second);
+    assert_eq!(client.replay_events(&1, &10).len(), 2);
+
+    // Keys are scoped to the publishing source.
+    let source = Address::generate(&env);
+    client.register_source(&admin, &source);
+    let third = client.publish_event(
+        &source,
+        &topic,
+        &1,
+        &payload,
+        &Some(String::from_str(&env, "key-a")),
+    );
+    assert_eq!(third, 3);
+    assert_eq!(client.get_event_count(), 3);
+}
+
 #[test]
 fn test_publish_without_schema_fails() {
     let (env, client, admin) = setup();
     let topic = String::from_str(&env, "records.vision.create");
     let payload = String::from_str(&env, "payload");
 
-    let result = client.try_publish_event(&admin, &topic, &1, &payload);
+    let result = client.try_publish_event(&admin, &topic, &1, &payload, &None);
     match result {
         Err(Ok(e)) => assert_eq!(e, EventError::SchemaNotFound),
         _ => panic!("Expected SchemaNotFound error"),
@@ -196,7 +304,7 @@ fn test_publish_unauthorized_fails() {
     let topic = String::from_str(&env, "records.vision.create");
     let payload = String::from_str(&env, "payload");
 
-    let result = client.try_publish_event(&outsider, &topic, &1, &payload);
+    let result = client.try_publish_event(&outsider, &topic, &1, &payload, &None);
     match result {
         Err(Ok(e)) => assert_eq!(e, EventError::Unauthorized),
         _ => panic!("Expected Unauthorized error"),
@@ -363,6 +471,79 @@ fn test_topic_matching_single_segment_wildcard() {
     assert!(crate::subscription::topic_matches(&env, &pattern, &topic));
 }
 
+#[test]
+fn test_topic_matching_recursive_wildcard() {
+    let (env, _client, _admin) = setup();
+    let recursive = String::from_str(&env, "records.**");
+    let single = String::from_str(&env, "records.*");
+    let child = String::from_str(&env, "records.vision");
+    let grandchild = String::from_str(&env, "records.vision.create");
+
+    assert!(crate::subscription::topic_matches(&env, &recursive, &child));
+    assert!(crate::subscription::topic_matches(
+        &env,
+        &recursive,
+        &grandchild
+    ));
+    assert!(crate::subscription::topic_matches(
+        &env,
+        &recursive,
+        &String::from_str(&env, "records")
+    ));
+    assert!(!crate::subscription::topic_matches(
+        &env,
+        &recursive,
+        &String::from_str(&env, "staking.vision.create")
+    ));
+
+    // `*` still covers exactly one level.
+    assert!(crate::subscription::topic_matches(&env, &single, &child));
+    assert!(!crate::subscription::topic_matches(
+        &env,
+        &single,
+        &grandchild
+    ));
+
+    assert!(crate::subscription::topic_matches(
+        &env,
+        &String::from_str(&env, "*.vision.**"),
+        &grandchild
+    ));
+}
+
+#[test]
+fn test_recursive_wildcard_must_end_pattern() {
+    let (env, client, _admin) = setup();
+    let subscriber = Address::generate(&env);
+    let mid = String::from_str(&env, "records.**.create");
+
+    assert!(!crate::subscription::topic_matches(
+        &env,
+        &mid,
+        &String::from_str(&env, "records.vision.create")
+    ));
+    assert_eq!(
+        client.try_subscribe(&subscriber, &mid),
+        Err(Ok(EventError::InvalidTopicPattern))
+    );
+
+    let mut members = Vec::new(&env);
+    members.push_back(subscriber.clone());
+    assert_eq!(
+        client.try_create_consumer_group(
+            &subscriber,
+            &String::from_str(&env, "workers"),
+            &mid,
+            &members
+        ),
+        Err(Ok(EventError::InvalidTopicPattern))
+    );
+
+    assert!(client
+        .try_subscribe(&subscriber, &String::from_str(&env, "records.**"))
+        .is_ok());
+}
+
 // ── Consumer group tests ─────────────────────────────────────────────────────
 
 #[test]
@@ -471,6 +652,193 @@ fn test_consumer_group_round_robin_distribution() {
     assert_eq!(group.offset, 3);
 }
 
+/// Member the last published event was assigned to by a consumer group.
+fn last_group_dispatch_target(env: &Env) -> Option<Address> {
+    use soroban_sdk::testutils::Events;
+    use soroban_sdk::xdr::{ContractEventBody, ScSymbol, ScVal};
+    use soroban_sdk::TryFromVal;
+    let topic = ScVal::Symbol(ScSymbol("GRP_DISP".try_into().unwrap()));
+    let events = env.events().all();
+    let target = events.events().iter().find_map(|e| match &e.body {
+        ContractEventBody::V0(body) if body.topics.first() == Some(&topic) => {
+            body.topics.get(1).cloned()
+        }
+        _ => None,
+    })?;
+    let val = soroban_sdk::Val::try_from_val(env, &target).ok()?;
+    Address::try_from_val(env, &val).ok()
+}
+
+#[test]
+fn test_removed_consumer_is_skipped_by_dispatch() {
+    let (env, client, admin) = setup();
+    let owner = Address::generate(&env);
+    let m1 = Address::generate(&env);
+    let m2 = Address::generate(&env);
+    let m3 = Address::generate(&env);
+    let mut members = Vec::new(&env);
+    members.push_back(m1.clone());
+    members.push_back(m2.clone());
+    let group_id = client.create_consumer_group(
+        &owner,
+        &String::from_str(&env, "rebalance"),
+        &String::from_str(&env, "records.**"),
+        &members,
+    );
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+
+    client.add_consumer_to_group(&owner, &group_id, &m3);
+    client.remove_consumer_from_group(&admin, &group_id, &m2);
+    let group = client.get_consumer_group(&group_id);
+    assert_eq!(group.members.len(), 2);
+    assert!(!group.members.contains(&m2));
+
+    // Six dispatches alternate between the remaining members only.
+    let expected = [&m1, &m3, &m1, &m3, &m1, &m3];
+    for (i, member) in expected.iter().enumerate() {
+        publish_test_event(&env, &client, &admin, "records.vision.create", 1, "p");
+        assert_eq!(
+            last_group_dispatch_target(&env),
+            Some((*member).clone()),
+            "dispatch {i}"
+        );
+    }
+
+    assert_eq!(
+        client.try_ack_event(&m2, &group_id, &1),
+        Err(Ok(EventError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_removed_consumer_unacked_events_are_reassigned() {
+    let (env, client, admin) = setup();
+    let owner = Address::generate(&env);
+    let m1 = Address::generate(&env);
+    let m2 = Address::generate(&env);
+    let m3 = Address::generate(&env);
+    let mut members = Vec::new(&env);
+    members.push_back(m1.clone());
+    members.push_back(m2.clone());
+    members.push_back(m3.clone());
+    let group_id = client.create_consumer_group(
+        &owner,
+        &String::from_str(&env, "reassign"),
+        &String::from_str(&env, "records.**"),
+        &members,
+    );
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+
+    // m2 gets e2 and e5; it acks e5 before being removed.
+    let mut ids = [0u64; 6];
+    for id in ids.iter_mut() {
+        *id = publish_test_event(&env, &client, &admin, "records.vision.create", 1, "p");
+    }
+    assert_eq!(
+        client.get_group_assignments(&group_id, &m2),
+        Vec::from_array(&env, [ids[1], ids[4]])
+    );
+    client.ack_event(&m2, &group_id, &ids[4]);
+    assert_eq!(
+        client.get_group_assignments(&group_id, &m2),
+        Vec::from_array(&env, [ids[1]])
+    );
+
+    client.remove_consumer_from_group(&owner, &group_id, &m2);
+    assert_eq!(last_group_dispatch_target(&env), Some(m1.clone()));
+    assert!(client.get_group_assignments(&group_id, &m2).is_empty());
+    assert_eq!(
+        client.get_group_assignments(&group_id, &m1),
+        Vec::from_array(&env, [ids[0], ids[3], ids[1]])
+    );
+    assert_eq!(
+        client.get_group_assignments(&group_id, &m3),
+        Vec::from_array(&env, [ids[2], ids[5]])
+    );
+
+    // Events acked by another member are not handed out again.
+    client.ack_event(&m3, &group_id, &ids[0]);
+    client.remove_consumer_from_group(&owner, &group_id, &m1);
+    assert_eq!(
+        client.get_group_assignments(&group_id, &m3),
+        Vec::from_array(&env, [ids[2], ids[5], ids[3], ids[1]])
+    );
+}
+
+#[test]
+fn test_consumer_group_member_limit() {
+    let (env, client, _admin) = setup();
+    let owner = Address::generate(&env);
+    let mut members = Vec::new(&env);
+    for _ in 0..MAX_GROUP_MEMBERS {
+        members.push_back(Address::generate(&env));
+    }
+    let group_id = client.create_consumer_group(
+        &owner,
+        &String::from_str(&env, "full"),
+        &String::from_str(&env, "records.*"),
+        &members,
+    );
+    assert!(matches!(
+        client.try_add_consumer_to_group(&owner, &group_id, &Address::generate(&env)),
+        Err(Ok(EventError::GroupMemberLimitExceeded))
+    ));
+
+    members.push_back(Address::generate(&env));
+    assert_eq!(
+        client.try_create_consumer_group(
+            &owner,
+            &String::from_str(&env, "too-big"),
+            &String::from_str(&env, "records.*"),
+            &members,
+        ),
+        Err(Ok(EventError::GroupMemberLimitExceeded))
+    );
+}
+
+#[test]
+fn test_consumer_group_membership_changes_are_guarded() {
+    let (env, client, _admin) = setup();
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let mut members = Vec::new(&env);
+    members.push_back(member.clone());
+    let group_id = client.create_consumer_group(
+        &owner,
+        &String::from_str(&env, "guarded"),
+        &String::from_str(&env, "records.*"),
+        &members,
+    );
+
+    let outsider = Address::generate(&env);
+    assert!(matches!(
+        client.try_add_consumer_to_group(&outsider, &group_id, &outsider),
+        Err(Ok(EventError::Unauthorized))
+    ));
+    assert!(matches!(
+        client.try_remove_consumer_from_group(&outsider, &group_id, &member),
+        Err(Ok(EventError::Unauthorized))
+    ));
+
+    // Duplicates, strangers and emptying the group are rejected.
+    assert!(matches!(
+        client.try_add_consumer_to_group(&owner, &group_id, &member),
+        Err(Ok(EventError::InvalidInput))
+    ));
+    assert!(matches!(
+        client.try_remove_consumer_from_group(&owner, &group_id, &outsider),
+        Err(Ok(EventError::InvalidInput))
+    ));
+    assert!(matches!(
+        client.try_remove_consumer_from_group(&owner, &group_id, &member),
+        Err(Ok(EventError::InvalidInput))
+    ));
+    assert!(matches!(
+        client.try_remove_consumer_from_group(&owner, &99, &member),
+        Err(Ok(EventError::ConsumerGroupNotFound))
+    ));
+}
+
 // ── Webhook tests ────────────────────────────────────────────────────────────
 
 #[test]
@@ -524,6 +892,112 @@ fn test_replay_events() {
     assert_eq!(events.get(0).unwrap().event_id, 2);
 }
 
+#[test]
+fn test_replay_spans_index_segments() {
+    let (env, client, admin) = setup();
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    register_schema(&env, &client, &admin, "staking.staked", 1);
+    let vision = String::from_str(&env, "records.vision.create");
+    let staking = String::from_str(&env, "staking.staked");
+    let payload = String::from_str(&env, "p");
+
+    for i in 0..2_500u32 {
+        let topic = if i % 2 == 0 { &vision } else { &staking };
+        client.publish_event(&admin, topic, &1, &payload, &None);
+    }
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::replay::segment_count(&env), 3);
+    });
+
+    // 995..=1004 straddles the first segment boundary.
+    let events = client.replay_events(&995, &10);
+    assert_eq!(events.len(), 10);
+    for (i, event) in events.iter().enumerate() {
+        assert_eq!(event.event_id, 995 + i as u64);
+    }
+
+    let second = client.replay_events(&1_998, &5);
+    let ids: std::vec::Vec<u64> = second.iter().map(|e| e.event_id).collect();
+    assert_eq!(ids, [1_998, 1_999, 2_000, 2_001, 2_002]);
+
+    // Odd IDs are vision events; the topic replay crosses into segment 2.
+    let vision_events = client.replay_topic_events(&vision, &1_997, &3);
+    let ids: std::vec::Vec<u64> = vision_events.iter().map(|e| e.event_id).collect();
+    assert_eq!(ids, [1_997, 1_999, 2_001]);
+
+    assert_eq!(client.replay_events(&2_500, &10).len(), 1);
+    assert!(client.replay_events(&2_501, &10).is_empty());
+}
+
+/// Rewrite the segmented index as the pre-upgrade single `LOG_IDX` vector.
+fn downgrade_to_legacy_index(env: &Env, client: &EventStreamContractClient) {
+    use soroban_sdk::symbol_short;
+    env.as_contract(&client.address, || {
+        let mut legacy: Vec<u64> = Vec::new(env);
+        for segment in 0..crate::replay::segment_count(env) {
+            let key = (symbol_short!("LOG_SEG"), segment);
+            let ids: Vec<u64> = env.storage().persistent().get(&key).unwrap();
+            legacy.append(&ids);
+            env.storage().persistent().remove(&key);
+        }
+        env.storage().instance().remove(&symbol_short!("LOG_SEGS"));
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("LOG_IDX"), &legacy);
+    });
+}
+
+#[test]
+fn test_legacy_log_index_is_migrated_on_replay() {
+    let (env, client, admin) = setup();
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    let topic = String::from_str(&env, "records.vision.create");
+    let payload = String::from_str(&env, "p");
+    for _ in 0..1_005u32 {
+        client.publish_event(&admin, &topic, &1, &payload, &None);
+    }
+    downgrade_to_legacy_index(&env, &client);
+
+    let events = client.replay_events(&998, &10);
+    let ids: std::vec::Vec<u64> = events.iter().map(|e| e.event_id).collect();
+    assert_eq!(ids, [998, 999, 1_000, 1_001, 1_002, 1_003, 1_004, 1_005]);
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::replay::segment_count(&env), 2);
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&soroban_sdk::symbol_short!("LOG_IDX")));
+    });
+}
+
+#[test]
+fn test_legacy_log_index_is_migrated_on_publish_and_compaction() {
+    let (env, client, admin) = setup();
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    for _ in 0..3 {
+        publish_test_event(&env, &client, &admin, "records.vision.create", 1, "p");
+    }
+    downgrade_to_legacy_index(&env, &client);
+
+    let fourth = publish_test_event(&env, &client, &admin, "records.vision.create", 1, "p");
+    let ids: std::vec::Vec<u64> = client
+        .replay_events(&1, &10)
+        .iter()
+        .map(|e| e.event_id)
+        .collect();
+    assert_eq!(ids, [1, 2, 3, fourth]);
+
+    downgrade_to_legacy_index(&env, &client);
+    let topic = String::from_str(&env, "records.vision.create");
+    assert_eq!(client.compact_topic(&admin, &topic), 3);
+    let ids: std::vec::Vec<u64> = client
+        .replay_events(&1, &10)
+        .iter()
+        .map(|e| e.event_id)
+        .collect();
+    assert_eq!(ids, [fourth]);
+}
+
 #[test]
 fn test_replay_produces_consistent_ordering() {
     let (env, client, admin) = setup();
@@ -573,7 +1047,7 @@ fn test_replay_with_limit() {
     for _i in 0..5 {
         let payload = String::from_str(&env, "p");
         let topic = String::from_str(&env, "records.vision.create");
-        client.publish_event(&admin, &topic, &1, &payload);
+        client.publish_event(&admin, &topic, &1, &payload, &None);
     }
 
     let events = client.replay_events(&1, &3);
@@ -715,6 +1189,145 @@ fn test_retry_invalid_index_fails() {
     }
 }
 
+#[test]
+fn test_dead_letter_retry_backs_off_exponentially() {
+    let (env, client, admin) = setup();
+    let subscriber = Address::generate(&env);
+    let pattern = String::from_str(&env, "records.vision.*");
+    client.subscribe_with_delivery(&subscriber, &pattern, &DeliveryMode::AtLeastOnce, &10);
+
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    publish_test_event(&env, &client, &admin, "records.vision.create", 1, "p1");
+
+    // A first failure is retryable at once.
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    client.expire_pending_deliveries();
+    let entry = client.get_dead_letters().get(0).unwrap();
+    assert_eq!(entry.attempts, 0);
+    assert_eq!(entry.next_retry_at, entry.failed_at);
+
+    let mut expected_wait = DLQ_BASE_BACKOFF_SECS;
+    for attempts in 1..=3u32 {
+        let retried_at = env.ledger().timestamp();
+        client.retry_dead_letter(&admin, &0);
+
+        // The re-delivery goes unacked and lands back in the queue.
+        env.ledger().with_mut(|l| l.timestamp += 10);
+        client.expire_pending_deliveries();
+        let entry = client.get_dead_letters().get(0).unwrap();
+        assert_eq!(entry.attempts, attempts);
+        assert_eq!(entry.next_retry_at, retried_at + expected_wait);
+
+        assert_eq!(
+            client.try_retry_dead_letter(&admin, &0),
+            Err(Ok(EventError::RetryTooEarly))
+        );
+        env.ledger().set_timestamp(entry.next_retry_at);
+        expected_wait *= 2;
+    }
+}
+
+#[test]
+fn test_dead_letter_backoff_is_capped_and_reset_by_ack() {
+    assert_eq!(retry_backoff(1), DLQ_BASE_BACKOFF_SECS);
+    assert_eq!(retry_backoff(2), 2 * DLQ_BASE_BACKOFF_SECS);
+    assert_eq!(retry_backoff(u32::MAX), DLQ_MAX_BACKOFF_SECS);
+
+    let (env, client, admin) = setup();
+    let subscriber = Address::generate(&env);
+    let pattern = String::from_str(&env, "records.vision.*");
+    let sub_id =
+        client.subscribe_with_delivery(&subscriber, &pattern, &DeliveryMode::AtLeastOnce, &10);
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    let event_id = publish_test_event(&env, &client, &admin, "records.vision.create", 1, "p1");
+
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    client.expire_pending_deliveries();
+    client.retry_dead_letter(&admin, &0);
+    client.ack_delivery(&subscriber, &sub_id, &event_id);
+
+    // Once acked, a later failure of the same delivery starts afresh.
+    let reason = String::from_str(&env, "timeout");
+    client.push_dead_letter(&admin, &event_id, &subscriber, &reason);
+    let entry = client.get_dead_letters().get(0).unwrap();
+    assert_eq!(entry.attempts, 0);
+    assert_eq!(entry.next_retry_at, env.ledger().timestamp());
+}
+
+// ── Delivery guarantee tests ─────────────────────────────────────────────────
+
+#[test]
+fn test_at_least_once_unacked_event_is_dead_lettered_after_timeout() {
+    let (env, client, admin) = setup();
+    let reliable = Address::generate(&env);
+    let casual = Address::generate(&env);
+    let pattern = String::from_str(&env, "records.vision.*");
+
+    let reliable_sub =
+        client.subscribe_with_delivery(&reliable, &pattern, &DeliveryMode::AtLeastOnce, &60);
+    let casual_sub = client.subscribe(&casual, &pattern);
+
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    let event_id = publish_test_event(&env, &client, &admin, "records.vision.create", 1, "p1");
+
+    let pending = client.get_pending_deliveries(&reliable_sub);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().event_id, event_id);
+    assert_eq!(client.get_pending_deliveries(&casual_sub).len(), 0);
+
+    // Nothing expires before the deadline.
+    assert_eq!(client.expire_pending_deliveries(), 0);
+
+    env.ledger().with_mut(|l| l.timestamp += 60);
+    assert_eq!(client.expire_pending_deliveries(), 1);
+
+    let dlq = client.get_dead_letters();
+    assert_eq!(dlq.len(), 1);
+    assert_eq!(dlq.get(0).unwrap().event_id, event_id);
+    assert_eq!(dlq.get(0).unwrap().subscriber, reliable);
+    assert_eq!(client.get_pending_deliveries(&reliable_sub).len(), 0);
+
+    // Retrying re-dispatches and tracks the delivery again.
+    client.retry_dead_letter(&admin, &0);
+    assert_eq!(client.get_pending_deliveries(&reliable_sub).len(), 1);
+}
+
+#[test]
+fn test_acked_delivery_is_not_dead_lettered() {
+    let (env, client, admin) = setup();
+    let subscriber = Address::generate(&env);
+    let pattern = String::from_str(&env, "records.vision.*");
+    let sub_id =
+        client.subscribe_with_delivery(&subscriber, &pattern, &DeliveryMode::AtLeastOnce, &0);
+    let sub = client.get_subscriptions(&subscriber).get(0).unwrap();
+    assert_eq!(sub.ack_timeout_secs, DEFAULT_ACK_TIMEOUT_SECS);
+
+    register_schema(&env, &client, &admin, "records.vision.create", 1);
+    let event_id = publish_test_event(&env, &client, &admin, "records.vision.create", 1, "p1");
+
+    let stranger = Address::generate(&env);
+    let result = client.try_ack_delivery(&stranger, &sub_id, &event_id);
+    assert_eq!(result, Err(Ok(EventError::Unauthorized)));
+
+    client.ack_delivery(&subscriber, &sub_id, &event_id);
+    assert_eq!(client.get_pending_deliveries(&sub_id).len(), 0);
+
+    let result = client.try_ack_delivery(&subscriber, &sub_id, &event_id);
+    assert_eq!(result, Err(Ok(EventError::DeliveryNotFound)));
+
+    env.ledger()
+        .with_mut(|l| l.timestamp += DEFAULT_ACK_TIMEOUT_SECS);
+    assert_eq!(client.expire_pending_deliveries(), 0);
+    assert_eq!(client.get_dead_letters().len(), 0);
+}
+
+#[test]
+fn test_pending_deliveries_unknown_subscription_fails() {
+    let (_env, client, _admin) = setup();
+    let result = client.try_get_pending_deliveries(&42);
+    assert_eq!(result, Err(Ok(EventError::SubscriptionNotFound)));
+}
+
 // ── Integration: subscription dispatch with events ───────────────────────────
 
 #[test]
@@ -895,7 +1508,7 @@ fn test_consumer_group_offset_consistency_under_rapid_publishes() {
     for i in 0..25u32 {
         let payload = String::from_str(&env, "p");
         let topic = String::from_str(&env, "records.vision.create");
-        let _ = client.publish_event(&admin, &topic, &1, &payload);
+        let _ = client.publish_event(&admin, &topic, &1, &payload, &None);
         // Each publish should advance group offset by 1 via dispatch.
         let group = client.get_consumer_group(&gid);
         assert_eq!(group.offset, (i as u64) + 1);
@@ -1072,7 +1685,7 @@ fn test_unauthorized_caller_cannot_consume_nonce() {
     let topic = String::from_str(&env, "records.vision.create");
     let payload = String::from_str(&env, "attack_payload");
 
-    let result = client.try_publish_event(&attacker, &topic, &1, &payload);
+    let result = client.try_publish_event(&attacker, &topic, &1, &payload, &None);
     assert!(result.is_err(), "unauthorized publish must be rejected");
 
     // Nonce counters must remain untouched.
@@ -26,7 +26,13 @@ fn test_register_schema_unauthorized() {
     let topic = String::from_str(&env, "test-topic");
     let schema_hash = String::from_str(&env, "hash");
 
-    let result = crate::registry::register_schema(&env, &topic, 1, &schema_hash);
+    let result = crate::registry::register_schema(
+        &env,
+        &topic,
+        1,
+        &schema_hash,
+        crate::registry::SchemaCompat::None,
+    );
 
     // NOTE: If register_schema is later wrapped with admin enforcement,
     // this test ensures Unauthorized is returned.
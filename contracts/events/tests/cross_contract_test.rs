@@ -18,11 +18,11 @@ fn test_cross_contract_calling_invariants() {
     let hash = String::from_str(&env, "sha256:cross_contract");
 
     // Test that the contract parses and handles successfully
-    client.register_schema(&admin, &topic, &1, &hash);
+    client.register_schema(&admin, &topic, &1, &hash, &None);
 
     // Ensure we can publish parsing cross contract mock
     let payload = String::from_str(&env, "payload");
-    client.publish_event(&admin, &topic, &1, &payload);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
 
     let all_events = env.events().all();
     assert!(
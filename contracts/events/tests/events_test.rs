@@ -29,7 +29,7 @@ fn setup() -> (Env, EventStreamContractClient<'static>, Address) {
 fn register_schema(env: &Env, client: &EventStreamContractClient, admin: &Address, topic: &str) {
     let topic_str = String::from_str(env, topic);
     let hash = String::from_str(env, "sha256:abc123");
-    client.register_schema(admin, &topic_str, &1, &hash);
+    client.register_schema(admin, &topic_str, &1, &hash, &None);
 }
 
 // ── Initialization event ─────────────────────────────────────────────────────
@@ -63,7 +63,7 @@ fn test_register_schema_emits_event() {
     let hash = String::from_str(&env, "sha256:deadbeef");
 
     let events_before = env.events().all().len();
-    client.register_schema(&admin, &topic, &1, &hash);
+    client.register_schema(&admin, &topic, &1, &hash, &None);
     let events_after = env.events().all().len();
 
     assert!(
@@ -80,8 +80,8 @@ fn test_register_multiple_schema_versions_emits_events() {
     let hash = String::from_str(&env, "sha256:v1");
 
     let before = env.events().all().len();
-    client.register_schema(&admin, &topic, &1, &hash);
-    client.register_schema(&admin, &topic, &2, &hash);
+    client.register_schema(&admin, &topic, &1, &hash, &None);
+    client.register_schema(&admin, &topic, &2, &hash, &None);
     let after = env.events().all().len();
 
     assert_eq!(
@@ -121,7 +121,7 @@ fn test_publish_event_emits_event() {
     let payload = String::from_str(&env, "payload_hash_1");
 
     let before = env.events().all().len();
-    client.publish_event(&admin, &topic, &1, &payload);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
     let after = env.events().all().len();
 
     assert!(
@@ -140,9 +140,9 @@ fn test_multiple_publishes_emit_multiple_events() {
     let payload = String::from_str(&env, "p");
 
     let before = env.events().all().len();
-    client.publish_event(&admin, &topic, &1, &payload);
-    client.publish_event(&admin, &topic, &1, &payload);
-    client.publish_event(&admin, &topic, &1, &payload);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
     let after = env.events().all().len();
 
     assert!(
@@ -164,7 +164,7 @@ fn test_registered_source_publish_emits_event() {
     let payload = String::from_str(&env, "source_payload");
 
     let before = env.events().all().len();
-    client.publish_event(&source, &topic, &1, &payload);
+    client.publish_event(&source, &topic, &1, &payload, &None);
     let after = env.events().all().len();
 
     assert!(
@@ -291,13 +291,13 @@ fn test_full_flow_emits_events_at_each_step() {
     // Step 2: register schema
     let topic = String::from_str(&env, "records.vision.create");
     let hash = String::from_str(&env, "sha256:abc");
-    client.register_schema(&admin, &topic, &1, &hash);
+    client.register_schema(&admin, &topic, &1, &hash, &None);
     let e2 = env.events().all().len();
     assert!(e2 > e1, "register_schema must emit events");
 
     // Step 3: publish event
     let payload = String::from_str(&env, "p1");
-    client.publish_event(&admin, &topic, &1, &payload);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
     let e3 = env.events().all().len();
     assert!(e3 > e2, "publish_event must emit events");
 
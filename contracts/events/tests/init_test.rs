@@ -84,10 +84,10 @@ fn test_state_unchanged_after_failed_reinitialize() {
     // Publish some events to advance state
     let topic = String::from_str(&env, "records.vision.create");
     let hash = String::from_str(&env, "sha256:abc");
-    client.register_schema(&admin, &topic, &1, &hash);
+    client.register_schema(&admin, &topic, &1, &hash, &None);
     let payload = String::from_str(&env, "p");
-    client.publish_event(&admin, &topic, &1, &payload);
-    client.publish_event(&admin, &topic, &1, &payload);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
 
     let count_before = client.get_event_count();
 
@@ -227,7 +227,7 @@ fn test_publish_before_initialize_fails() {
     let topic = String::from_str(&env, "records.vision.create");
     let payload = String::from_str(&env, "p");
 
-    let result = client.try_publish_event(&caller, &topic, &1, &payload);
+    let result = client.try_publish_event(&caller, &topic, &1, &payload, &None);
     match result {
         Err(Ok(e)) => assert_eq!(e, EventError::NotInitialized),
         _ => panic!("Expected NotInitialized error"),
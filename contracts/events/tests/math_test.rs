@@ -26,7 +26,7 @@ fn setup() -> (Env, EventStreamContractClient<'static>, Address) {
 fn register_schema(env: &Env, client: &EventStreamContractClient, admin: &Address, topic: &str) {
     let topic_str = String::from_str(env, topic);
     let hash = String::from_str(env, "sha256:abc123");
-    client.register_schema(admin, &topic_str, &1, &hash);
+    client.register_schema(admin, &topic_str, &1, &hash, &None);
 }
 
 // ── Event ID counter boundary ────────────────────────────────────────────────
@@ -43,7 +43,7 @@ fn test_event_counter_increments_without_overflow() {
 
     // Publish 100 events — verifies no arithmetic panic in next_event_id
     for _ in 0..100 {
-        client.publish_event(&admin, &topic, &1, &payload);
+        client.publish_event(&admin, &topic, &1, &payload, &None);
     }
 
     assert_eq!(client.get_event_count(), 100);
@@ -61,7 +61,7 @@ fn test_lamport_clock_strict_monotonic_large_batch() {
     let payload = String::from_str(&env, "p");
 
     for _ in 0..50 {
-        client.publish_event(&admin, &topic, &1, &payload);
+        client.publish_event(&admin, &topic, &1, &payload, &None);
     }
 
     let clock = client.get_lamport_clock();
@@ -82,9 +82,9 @@ fn test_schema_version_u32_max() {
     let hash = String::from_str(&env, "sha256:maxversion");
 
     // Register a normal version first so u32::MAX is strictly greater
-    client.register_schema(&admin, &topic, &1, &hash);
+    client.register_schema(&admin, &topic, &1, &hash, &None);
 
-    let result = client.try_register_schema(&admin, &topic, &u32::MAX, &hash);
+    let result = client.try_register_schema(&admin, &topic, &u32::MAX, &hash, &None);
     // Should succeed — u32::MAX > 1
     assert!(result.is_ok(), "u32::MAX schema version should be accepted");
 
@@ -99,7 +99,7 @@ fn test_schema_version_zero_boundary() {
     let topic = String::from_str(&env, "records.vision.create");
     let hash = String::from_str(&env, "sha256:v0");
 
-    let result = client.try_register_schema(&admin, &topic, &0, &hash);
+    let result = client.try_register_schema(&admin, &topic, &0, &hash, &None);
     match result {
         Err(Ok(e)) => assert_eq!(e, EventError::InvalidSchema),
         _ => panic!("Expected InvalidSchema for version 0"),
@@ -117,7 +117,7 @@ fn test_replay_limit_u32_max() {
 
     let topic = String::from_str(&env, "records.vision.create");
     let payload = String::from_str(&env, "p");
-    client.publish_event(&admin, &topic, &1, &payload);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
 
     // u32::MAX limit — should not panic, just return the 1 available event
     let events = client.replay_events(&1, &u32::MAX);
@@ -132,7 +132,7 @@ fn test_replay_from_u64_max_event_id() {
 
     let topic = String::from_str(&env, "records.vision.create");
     let payload = String::from_str(&env, "p");
-    client.publish_event(&admin, &topic, &1, &payload);
+    client.publish_event(&admin, &topic, &1, &payload, &None);
 
     // No event will have ID >= u64::MAX, so result must be empty
     let events = client.replay_events(&u64::MAX, &10);
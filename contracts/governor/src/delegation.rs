@@ -9,6 +9,8 @@
 //! - A representative can **not** re-delegate received power (no chains).
 //! - Self-delegation is a no-op (treated as no delegation).
 //! - Revoking non-existent delegation is a no-op.
+//! - Switching representatives (`redelegate`) moves the delegation in one
+//!   step; the voter is never left undelegated in between.
 
 use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
 
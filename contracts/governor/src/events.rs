@@ -1,6 +1,6 @@
 //! Structured event publishing for the Governor contract.
 
-use soroban_sdk::{symbol_short, Address, Env};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
 
 use crate::proposal::{Proposal, ProposalPhase};
 use crate::voting::VoteChoice;
@@ -46,12 +46,55 @@ pub fn publish_delegation_set(env: &Env, voter: &Address, delegate: &Address) {
     );
 }
 
+pub fn publish_delegation_moved(
+    env: &Env,
+    voter: &Address,
+    previous: &Option<Address>,
+    delegate: &Address,
+) {
+    env.events().publish(
+        (symbol_short!("DEL_MOV"),),
+        (voter.clone(), previous.clone(), delegate.clone()),
+    );
+}
+
 pub fn publish_delegation_revoked(env: &Env, voter: &Address) {
     env.events()
         .publish((symbol_short!("DEL_REV"),), voter.clone());
 }
 
+pub fn publish_proposal_cancelled(env: &Env, proposal_id: u64, proposer: &Address) {
+    env.events()
+        .publish((symbol_short!("PROP_CAN"), proposal_id), proposer.clone());
+}
+
 pub fn publish_proposal_executed(env: &Env, proposal_id: u64) {
     env.events()
         .publish((symbol_short!("PROP_EXE"),), proposal_id);
 }
+
+pub fn publish_callback_invoked(
+    env: &Env,
+    proposal_id: u64,
+    target: &Address,
+    function: &Symbol,
+    outcome: &ProposalPhase,
+) {
+    env.events().publish(
+        (symbol_short!("CB_OK"), proposal_id),
+        (target.clone(), function.clone(), outcome.clone()),
+    );
+}
+
+pub fn publish_callback_failed(
+    env: &Env,
+    proposal_id: u64,
+    target: &Address,
+    function: &Symbol,
+    outcome: &ProposalPhase,
+) {
+    env.events().publish(
+        (symbol_short!("CB_FAIL"), proposal_id),
+        (target.clone(), function.clone(), outcome.clone()),
+    );
+}
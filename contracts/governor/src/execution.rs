@@ -11,7 +11,7 @@
 //! off-chain tooling can verify the exact call being made before the timelock
 //! expires.
 
-use soroban_sdk::{symbol_short, Address, BytesN, Env, IntoVal, Symbol};
+use soroban_sdk::{symbol_short, xdr::ToXdr, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
 
 // ── Timelock durations (seconds) ──────────────────────────────────────────────
 
@@ -35,7 +35,16 @@ pub const FN_SET_POLICY: Symbol = symbol_short!("GOV_POL");
 /// Symbol used to call `governor_emergency(action_hash)` on a target.
 pub const FN_EMERGENCY: Symbol = symbol_short!("GOV_EMG");
 
-use crate::proposal::ProposalType;
+use crate::events;
+use crate::proposal::{ProposalPhase, ProposalType};
+
+/// `params_hash` of a `governor_spend(to, amount)` action:
+/// `sha256(xdr((to, amount)))`.
+pub fn spend_params_hash(env: &Env, to: &Address, amount: i128) -> BytesN<32> {
+    env.crypto()
+        .sha256(&(to.clone(), amount).to_xdr(env))
+        .into()
+}
 
 /// Select the appropriate timelock duration for a proposal type.
 pub fn timelock_duration(proposal_type: &ProposalType) -> u64 {
@@ -72,3 +81,22 @@ pub fn dispatch_action(
         (target.clone(), function.clone(), params_hash.clone()),
     );
 }
+
+/// Invoke a proposal's finalization callback as
+/// `target.function(proposal_id, outcome)`.
+///
+/// Best-effort: a missing target, a trapping callee or a returned error is
+/// recorded as a `CB_FAIL` event and never aborts the phase transition.
+pub fn invoke_finalization_callback(
+    env: &Env,
+    proposal_id: u64,
+    target: &Address,
+    function: &Symbol,
+    outcome: &ProposalPhase,
+) {
+    let args: Vec<Val> = (proposal_id, outcome.clone()).into_val(env);
+    match env.try_invoke_contract::<(), soroban_sdk::Error>(target, function, args) {
+        Ok(Ok(())) => events::publish_callback_invoked(env, proposal_id, target, function, outcome),
+        _ => events::publish_callback_failed(env, proposal_id, target, function, outcome),
+    }
+}
@@ -7,7 +7,8 @@
 //! - **Quadratic voting**: `vote_power = sqrt(staked_tokens) × loyalty_multiplier`
 //! - **Time-weighted influence**: stakers who hold longer earn up to 2× vote weight
 //! - **Multi-phase lifecycle**: Draft → Discussion → Voting → Timelock → Execution → Completed/Rejected
-//! - **Proposal types**: ContractUpgrade, ParameterChange, PolicyModification, EmergencyAction, TreasurySpend
+//! - **Proposal types**: ContractUpgrade, ParameterChange, PolicyModification, EmergencyAction, TreasurySpend,
+//!   plus admin-registered custom types with their own thresholds and timelock
 //! - **Delegation**: delegate vote power to a representative with revocation
 //! - **Commit-reveal**: prevents vote-buying and bandwagon effects
 //! - **Optimistic execution**: execute after timelock unless veto threshold is met
@@ -26,8 +27,12 @@ use soroban_sdk::{
 use delegation::Delegation;
 use execution::timelock_duration;
 use proposal::{
-    load as load_proposal, next_id, pass_threshold_bps, quorum_bps, store as store_proposal,
-    veto_threshold_bps, Proposal, ProposalAction, ProposalPhase, ProposalType,
+    custom_type_config, dynamic_veto_threshold_bps, load as load_proposal, next_id,
+    proposal_pass_threshold_bps, proposal_quorum_bps, proposal_veto_threshold_bps, set_custom_type,
+    set_stored_bps, store as store_proposal, stored_pass_threshold_bps, stored_quorum_bps,
+    stored_veto_threshold_bps, FinalizationCallback, OptionalFinalizationCallback, Proposal,
+    ProposalAction, ProposalPhase, ProposalType, ProposalTypeConfig, PASS_BPS, QUORUM_BPS,
+    VETO_BPS,
 };
 use voting::{
     compute_vote_power, has_committed, has_voted, load_commit, store_commit, store_vote,
@@ -41,6 +46,12 @@ const INITIALIZED: Symbol = symbol_short!("INIT");
 const STAKING_CONTRACT: Symbol = symbol_short!("STK_CTR");
 const TREASURY_CONTRACT: Symbol = symbol_short!("TRES_CTR");
 const TOTAL_VOTE_SUPPLY: Symbol = symbol_short!("TOT_VS");
+const CALLBACK_ALLOWLIST: Symbol = symbol_short!("CB_ALLOW");
+const ELIGIBILITY_POLICY: Symbol = symbol_short!("ELIG_POL");
+const VETO_SLOPE: Symbol = symbol_short!("VETO_SLP");
+const SALT_CHECK: Symbol = symbol_short!("SALT_CHK");
+const SPEND_LIMIT: Symbol = symbol_short!("SPD_LIM");
+const SPEND_WINDOW: Symbol = symbol_short!("SPD_WIN");
 
 /// Default Discussion phase length in seconds (3 days).
 const DEFAULT_DISCUSSION_SECS: u64 = 259_200;
@@ -71,6 +82,14 @@ pub enum ContractError {
     SelfDelegation = 16,
     InsufficientStake = 17,
     PhaseNotAdvanceable = 18,
+    CallbackNotAllowed = 19,
+    SaltReused = 20,
+    /// The staking contract call trapped or returned an unexpected type.
+    StakingQueryFailed = 21,
+    /// `custom_type` names a proposal type that was never registered.
+    UnknownProposalType = 22,
+    /// A TreasurySpend would exceed what is left of the current spend window.
+    SpendWindowExceeded = 23,
 }
 
 // ── Public return types ───────────────────────────────────────────────────────
@@ -85,11 +104,61 @@ pub struct ProposalSummary {
     pub votes_for: i128,
     pub votes_against: i128,
     pub votes_veto: i128,
+    pub votes_abstain: i128,
     pub reveal_count: u32,
+    pub abstain_count: u32,
     pub voting_ends: u64,
     pub timelock_ends: u64,
 }
 
+/// Minimum stake ages (seconds) required to take part in governance.
+///
+/// Guards against flash-staking: staking, proposing or voting, and then
+/// immediately unstaking.  Both default to 0 (no minimum).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EligibilityPolicy {
+    pub min_stake_age_to_propose: u64,
+    pub min_stake_age_to_vote: u64,
+}
+
+/// Admin-configured cap on how much TreasurySpend proposals may move per
+/// fixed window of `window_seconds`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasurySpendLimit {
+    pub max_per_window: i128,
+    pub window_seconds: u64,
+}
+
+/// One `governor_spend(recipient, amount)` call made by a TreasurySpend
+/// proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryTransfer {
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// Spend recorded against the window that opened at `window_start`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct SpendWindow {
+    window_start: u64,
+    spent: i128,
+}
+
+/// Treasury spend budget as seen at the current ledger time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasurySpendBudget {
+    pub max_per_window: i128,
+    pub spent: i128,
+    pub remaining: i128,
+    /// When the budget next refills to `max_per_window`.
+    pub window_ends: u64,
+}
+
 // ── Contract ──────────────────────────────────────────────────────────────────
 
 #[contract]
@@ -144,57 +213,111 @@ impl GovernorContract {
     /// contract.  After creation the proposer calls `advance_phase` to move
     /// the proposal through Discussion → Voting.
     ///
-    /// * `actions` — one or more `ProposalAction` items executed atomically
-    ///               when the proposal passes.
+    /// * `actions`     — one or more `ProposalAction` items executed atomically
+    ///                   when the proposal passes.
+    /// * `custom_type` — a type registered with `register_proposal_type`; its
+    ///                   thresholds and timelock replace those of
+    ///                   `proposal_type`.
+    ///
+    /// TreasurySpend proposals are rejected here: create them with
+    /// `create_treasury_spend_proposal`, which builds the spend actions
+    /// itself so the amount charged to the spend window is the amount that
+    /// is actually moved.
     pub fn create_proposal(
         env: Env,
         proposer: Address,
         proposal_type: ProposalType,
         title: String,
         actions: Vec<ProposalAction>,
+        custom_type: Option<Symbol>,
     ) -> Result<u64, ContractError> {
-        Self::require_initialized(&env)?;
-        proposer.require_auth();
-
-        if actions.is_empty() {
+        if proposal_type == ProposalType::TreasurySpend {
             return Err(ContractError::InvalidInput);
         }
+        Self::create_proposal_inner(
+            env,
+            proposer,
+            proposal_type,
+            custom_type,
+            title,
+            actions,
+            OptionalFinalizationCallback::None,
+            0,
+        )
+    }
 
-        // Proposer must have stake.
-        let staked = Self::query_staked(&env, &proposer);
-        if staked <= 0 {
-            return Err(ContractError::InsufficientStake);
+    /// Create a TreasurySpend proposal paying each of `transfers` out of
+    /// `treasury`.
+    ///
+    /// The governor builds one `governor_spend(recipient, amount)` action
+    /// per transfer, with its `params_hash` over that recipient and amount,
+    /// and records their sum as the proposal's `spend_amount`. The proposer
+    /// cannot declare an amount that differs from what the actions move.
+    /// The sum is charged against the treasury spend window when the
+    /// proposal is executed, not when it is created.
+    pub fn create_treasury_spend_proposal(
+        env: Env,
+        proposer: Address,
+        title: String,
+        treasury: Address,
+        transfers: Vec<TreasuryTransfer>,
+    ) -> Result<u64, ContractError> {
+        let mut actions = Vec::new(&env);
+        let mut total: i128 = 0;
+        for transfer in transfers.iter() {
+            if transfer.amount <= 0 {
+                return Err(ContractError::InvalidInput);
+            }
+            total = total
+                .checked_add(transfer.amount)
+                .ok_or(ContractError::InvalidInput)?;
+            actions.push_back(ProposalAction {
+                target: treasury.clone(),
+                function: execution::FN_SPEND,
+                params_hash: execution::spend_params_hash(
+                    &env,
+                    &transfer.recipient,
+                    transfer.amount,
+                ),
+            });
         }
+        Self::create_proposal_inner(
+            env,
+            proposer,
+            ProposalType::TreasurySpend,
+            None,
+            title,
+            actions,
+            OptionalFinalizationCallback::None,
+            total,
+        )
+    }
 
-        let now = env.ledger().timestamp();
-        let discussion_ends = now.saturating_add(DEFAULT_DISCUSSION_SECS);
-        let voting_ends = discussion_ends.saturating_add(DEFAULT_VOTING_SECS);
-        let timelock_len = timelock_duration(&proposal_type);
-        let timelock_ends = voting_ends.saturating_add(timelock_len);
-
-        let id = next_id(&env);
-        let proposal = Proposal {
-            id,
+    /// Create a proposal that notifies a downstream contract when it is
+    /// finalized.
+    ///
+    /// On transition to Completed or Rejected the governor calls
+    /// `function(proposal_id, outcome)` on `target`. The target must be on the
+    /// admin-managed callback allowlist.
+    pub fn create_proposal_with_callback(
+        env: Env,
+        proposer: Address,
+        proposal_type: ProposalType,
+        title: String,
+        actions: Vec<ProposalAction>,
+        target: Address,
+        function: Symbol,
+    ) -> Result<u64, ContractError> {
+        Self::create_proposal_inner(
+            env,
+            proposer,
             proposal_type,
-            phase: ProposalPhase::Draft,
-            proposer: proposer.clone(),
+            None,
             title,
             actions,
-            created_at: now,
-            discussion_ends,
-            voting_ends,
-            timelock_ends,
-            votes_for: 0,
-            votes_against: 0,
-            votes_veto: 0,
-            commit_count: 0,
-            reveal_count: 0,
-        };
-
-        store_proposal(&env, &proposal);
-        events::publish_proposal_created(&env, &proposal);
-
-        Ok(id)
+            OptionalFinalizationCallback::Some(FinalizationCallback { target, function }),
+            0,
+        )
     }
 
     // ── Phase transitions ─────────────────────────────────────────────────────
@@ -245,7 +368,7 @@ impl GovernorContract {
                 if now < proposal.voting_ends {
                     return Err(ContractError::PhaseNotAdvanceable);
                 }
-                // Check quorum.
+                // Check quorum. Abstentions count toward participation.
                 let total_supply: i128 = env
                     .storage()
                     .instance()
@@ -254,17 +377,19 @@ impl GovernorContract {
                 let total_votes = proposal
                     .votes_for
                     .saturating_add(proposal.votes_against)
-                    .saturating_add(proposal.votes_veto);
+                    .saturating_add(proposal.votes_veto)
+                    .saturating_add(proposal.votes_abstain);
                 let quorum_needed =
-                    total_supply * quorum_bps(&proposal.proposal_type) as i128 / 10_000;
+                    total_supply * proposal_quorum_bps(&env, &proposal) as i128 / 10_000;
 
                 if total_votes < quorum_needed {
                     ProposalPhase::Expired
                 } else {
-                    // Check simple majority among for/against.
+                    // Check simple majority among for/against; abstentions
+                    // are excluded from the decisive pool.
                     let decisive = proposal.votes_for.saturating_add(proposal.votes_against);
                     let pass_needed =
-                        decisive * pass_threshold_bps(&proposal.proposal_type) as i128 / 10_000;
+                        decisive * proposal_pass_threshold_bps(&env, &proposal) as i128 / 10_000;
                     if proposal.votes_for >= pass_needed {
                         ProposalPhase::Timelock
                     } else {
@@ -275,14 +400,7 @@ impl GovernorContract {
 
             ProposalPhase::Timelock => {
                 // Check veto first.
-                let total_supply: i128 = env
-                    .storage()
-                    .instance()
-                    .get(&TOTAL_VOTE_SUPPLY)
-                    .unwrap_or(1);
-                let veto_threshold =
-                    total_supply * veto_threshold_bps(&proposal.proposal_type) as i128 / 10_000;
-                if proposal.votes_veto >= veto_threshold {
+                if proposal.votes_veto >= Self::veto_threshold(&env, &proposal) {
                     ProposalPhase::Rejected
                 } else if now < proposal.timelock_ends {
                     return Err(ContractError::TimelockNotExpired);
@@ -299,10 +417,43 @@ impl GovernorContract {
         proposal.phase = new_phase.clone();
         store_proposal(&env, &proposal);
         events::publish_phase_transition(&env, proposal_id, &new_phase);
+        Self::run_finalization_callback(&env, &proposal);
 
         Ok(new_phase)
     }
 
+    /// Withdraw a proposal before voting opens.
+    ///
+    /// Only the proposer may cancel, and only while the proposal is in Draft
+    /// or Discussion; once voting has started the outcome is left to voters.
+    pub fn cancel_proposal(
+        env: Env,
+        caller: Address,
+        proposal_id: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        caller.require_auth();
+
+        let mut proposal =
+            load_proposal(&env, proposal_id).ok_or(ContractError::ProposalNotFound)?;
+
+        if caller != proposal.proposer {
+            return Err(ContractError::Unauthorized);
+        }
+        if !matches!(
+            proposal.phase,
+            ProposalPhase::Draft | ProposalPhase::Discussion
+        ) {
+            return Err(ContractError::WrongPhase);
+        }
+
+        proposal.phase = ProposalPhase::Cancelled;
+        store_proposal(&env, &proposal);
+        events::publish_proposal_cancelled(&env, proposal_id, &caller);
+
+        Ok(())
+    }
+
     // ── Commit-reveal voting ──────────────────────────────────────────────────
 
     /// Phase 1 — commit a blinded vote.
@@ -340,6 +491,11 @@ impl GovernorContract {
             return Err(ContractError::AlreadyRevealed);
         }
 
+        let policy = Self::get_eligibility_policy(env.clone());
+        if Self::query_stake_age(&env, &voter)? < policy.min_stake_age_to_vote {
+            return Err(ContractError::InsufficientStake);
+        }
+
         let commit = VoteCommit {
             commitment,
             committed_at: env.ledger().timestamp(),
@@ -384,6 +540,12 @@ impl GovernorContract {
             return Err(ContractError::AlreadyCommitted);
         }
 
+        // Delegation must not bypass the voter's own stake-age requirement.
+        let policy = Self::get_eligibility_policy(env.clone());
+        if Self::query_stake_age(&env, &voter)? < policy.min_stake_age_to_vote {
+            return Err(ContractError::InsufficientStake);
+        }
+
         let commit = VoteCommit {
             commitment,
             committed_at: env.ledger().timestamp(),
@@ -447,9 +609,18 @@ impl GovernorContract {
             return Err(ContractError::CommitmentMismatch);
         }
 
+        // A salt reused across proposals links the voter's commitments.
+        let salt_check = Self::get_salt_reuse_check(env.clone());
+        let salt_hash = salt_check.then(|| voting::salt_hash(&env, &voter, &salt));
+        if let Some(hash) = &salt_hash {
+            if voting::is_salt_used(&env, hash) {
+                return Err(ContractError::SaltReused);
+            }
+        }
+
         // Compute vote power using staked balance and stake age.
-        let staked = Self::query_staked(&env, &voter);
-        let stake_age = Self::query_stake_age(&env, &voter);
+        let staked = Self::query_staked(&env, &voter)?;
+        let stake_age = Self::query_stake_age(&env, &voter)?;
         let power = compute_vote_power(staked, stake_age);
 
         // Tally.
@@ -459,6 +630,10 @@ impl GovernorContract {
                 proposal.votes_against = proposal.votes_against.saturating_add(power)
             }
             VoteChoice::Veto => proposal.votes_veto = proposal.votes_veto.saturating_add(power),
+            VoteChoice::Abstain => {
+                proposal.votes_abstain = proposal.votes_abstain.saturating_add(power);
+                proposal.abstain_count = proposal.abstain_count.saturating_add(1);
+            }
         }
         proposal.reveal_count = proposal.reveal_count.saturating_add(1);
 
@@ -472,6 +647,9 @@ impl GovernorContract {
             revealed_at: env.ledger().timestamp(),
         };
         store_vote(&env, proposal_id, &voter, &record);
+        if let Some(hash) = &salt_hash {
+            voting::mark_salt_used(&env, hash);
+        }
 
         events::publish_vote_revealed(&env, proposal_id, &voter, &choice, power);
 
@@ -499,6 +677,30 @@ impl GovernorContract {
         Ok(())
     }
 
+    /// Move `voter`'s delegation to `new_delegate` in one step.
+    ///
+    /// The previous delegate's count is decremented and `new_delegate`'s
+    /// incremented, so there is no window in which the voter is undelegated.
+    /// With no prior delegation this behaves like `delegate`.
+    pub fn redelegate(
+        env: Env,
+        voter: Address,
+        new_delegate: Address,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        voter.require_auth();
+
+        if voter == new_delegate {
+            return Err(ContractError::SelfDelegation);
+        }
+
+        let previous = delegation::get_delegation(&env, &voter).map(|d| d.delegate);
+        delegation::set_delegation(&env, &voter, &new_delegate);
+        events::publish_delegation_moved(&env, &voter, &previous, &new_delegate);
+
+        Ok(())
+    }
+
     /// Revoke the active delegation, restoring direct voting rights.
     pub fn revoke_delegation(env: Env, voter: Address) -> Result<(), ContractError> {
         Self::require_initialized(&env)?;
@@ -516,6 +718,10 @@ impl GovernorContract {
     ///
     /// Moves the proposal to Completed and dispatches each action.
     /// Anyone may call this (permissionless optimistic execution).
+    ///
+    /// A TreasurySpend is first charged against the spend window; if it
+    /// does not fit it stays in Execution and can be retried once the window
+    /// refills.
     pub fn execute_proposal(
         env: Env,
         caller: Address,
@@ -530,6 +736,9 @@ impl GovernorContract {
         if !matches!(proposal.phase, ProposalPhase::Execution) {
             return Err(ContractError::WrongPhase);
         }
+        if proposal.proposal_type == ProposalType::TreasurySpend {
+            Self::charge_treasury_spend(&env, proposal.spend_amount)?;
+        }
 
         // Dispatch each action.
         for (i, action) in proposal.actions.iter().enumerate() {
@@ -546,6 +755,7 @@ impl GovernorContract {
         proposal.phase = ProposalPhase::Completed;
         store_proposal(&env, &proposal);
         events::publish_proposal_executed(&env, proposal_id);
+        Self::run_finalization_callback(&env, &proposal);
 
         Ok(())
     }
@@ -569,8 +779,230 @@ impl GovernorContract {
         Ok(())
     }
 
+    /// Replace the set of contracts that proposals may name as finalization
+    /// callback targets. Proposals already created keep their callback.
+    pub fn set_callback_allowlist(
+        env: Env,
+        caller: Address,
+        targets: Vec<Address>,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        env.storage().instance().set(&CALLBACK_ALLOWLIST, &targets);
+        Ok(())
+    }
+
+    /// Set the minimum stake ages for proposing and voting (0 disables).
+    pub fn set_eligibility_policy(
+        env: Env,
+        caller: Address,
+        min_stake_age_to_propose: u64,
+        min_stake_age_to_vote: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        let policy = EligibilityPolicy {
+            min_stake_age_to_propose,
+            min_stake_age_to_vote,
+        };
+        env.storage().instance().set(&ELIGIBILITY_POLICY, &policy);
+        Ok(())
+    }
+
+    /// Set how steeply the veto threshold rises with the pass margin, in bps
+    /// of the margin added to the base threshold (0 keeps it static).
+    pub fn set_veto_margin_slope(
+        env: Env,
+        caller: Address,
+        slope_bps: u32,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        if slope_bps > 10_000 {
+            return Err(ContractError::InvalidInput);
+        }
+        env.storage().instance().set(&VETO_SLOPE, &slope_bps);
+        Ok(())
+    }
+
+    /// Cap TreasurySpend executions at `max_treasury_spend_per_window` per
+    /// `spend_window_seconds`. Spend already recorded in the open window
+    /// still counts against the new cap.
+    pub fn set_treasury_spend_limit(
+        env: Env,
+        caller: Address,
+        max_treasury_spend_per_window: i128,
+        spend_window_seconds: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        if max_treasury_spend_per_window <= 0 || spend_window_seconds == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+        let limit = TreasurySpendLimit {
+            max_per_window: max_treasury_spend_per_window,
+            window_seconds: spend_window_seconds,
+        };
+        env.storage().instance().set(&SPEND_LIMIT, &limit);
+        Ok(())
+    }
+
+    /// Override the quorum (bps of total vote supply) for `proposal_type`.
+    /// Takes effect for every proposal of that type still in Voting.
+    pub fn set_quorum_bps(
+        env: Env,
+        caller: Address,
+        proposal_type: ProposalType,
+        bps: u32,
+    ) -> Result<(), ContractError> {
+        Self::set_threshold(&env, &caller, QUORUM_BPS, &proposal_type, bps)
+    }
+
+    /// Override the share of decisive votes (bps) that must be FOR for
+    /// `proposal_type` to pass.
+    pub fn set_pass_threshold_bps(
+        env: Env,
+        caller: Address,
+        proposal_type: ProposalType,
+        bps: u32,
+    ) -> Result<(), ContractError> {
+        Self::set_threshold(&env, &caller, PASS_BPS, &proposal_type, bps)
+    }
+
+    /// Override the base veto threshold (bps of total vote supply) for
+    /// `proposal_type`. The pass-margin slope still applies on top. A
+    /// threshold of 0 would veto every timelocked proposal and is rejected.
+    pub fn set_veto_threshold_bps(
+        env: Env,
+        caller: Address,
+        proposal_type: ProposalType,
+        bps: u32,
+    ) -> Result<(), ContractError> {
+        if bps == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+        Self::set_threshold(&env, &caller, VETO_BPS, &proposal_type, bps)
+    }
+
+    /// Register (or reconfigure) a custom proposal type under `name`.
+    /// Reconfiguring takes effect for every proposal of that type still in
+    /// Voting or Timelock; the timelock of existing proposals is unchanged.
+    pub fn register_proposal_type(
+        env: Env,
+        caller: Address,
+        name: Symbol,
+        config: ProposalTypeConfig,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        if config.quorum_bps > 10_000
+            || config.pass_threshold_bps > 10_000
+            || config.veto_threshold_bps == 0
+            || config.veto_threshold_bps > 10_000
+        {
+            return Err(ContractError::InvalidInput);
+        }
+        set_custom_type(&env, &name, &config);
+        Ok(())
+    }
+
+    /// Reject reveals whose `(voter, salt)` pair was already revealed on an
+    /// earlier proposal. Only salts revealed while the check is on are
+    /// remembered.
+    pub fn set_salt_reuse_check(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(&env)?;
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        env.storage().instance().set(&SALT_CHECK, &enabled);
+        Ok(())
+    }
+
     // ── View functions ────────────────────────────────────────────────────────
 
+    /// Effective quorum for `proposal_type`, including any override.
+    pub fn get_quorum_bps(env: Env, proposal_type: ProposalType) -> u32 {
+        stored_quorum_bps(&env, &proposal_type)
+    }
+
+    /// Effective pass threshold for `proposal_type`, including any override.
+    pub fn get_pass_threshold_bps(env: Env, proposal_type: ProposalType) -> u32 {
+        stored_pass_threshold_bps(&env, &proposal_type)
+    }
+
+    /// Effective base veto threshold for `proposal_type`, including any
+    /// override.
+    pub fn get_veto_threshold_bps(env: Env, proposal_type: ProposalType) -> u32 {
+        stored_veto_threshold_bps(&env, &proposal_type)
+    }
+
+    pub fn get_proposal_type_config(env: Env, name: Symbol) -> Option<ProposalTypeConfig> {
+        custom_type_config(&env, &name)
+    }
+
+    pub fn get_salt_reuse_check(env: Env) -> bool {
+        env.storage().instance().get(&SALT_CHECK).unwrap_or(false)
+    }
+
+    pub fn get_veto_margin_slope(env: Env) -> u32 {
+        env.storage().instance().get(&VETO_SLOPE).unwrap_or(0)
+    }
+
+    /// Absolute veto weight needed to reject a proposal during its timelock,
+    /// given its current tallies (0 if it does not exist).
+    pub fn computed_veto_threshold(env: Env, proposal_id: u64) -> i128 {
+        load_proposal(&env, proposal_id)
+            .map(|p| Self::veto_threshold(&env, &p))
+            .unwrap_or(0)
+    }
+
+    pub fn get_eligibility_policy(env: Env) -> EligibilityPolicy {
+        env.storage()
+            .instance()
+            .get(&ELIGIBILITY_POLICY)
+            .unwrap_or(EligibilityPolicy {
+                min_stake_age_to_propose: 0,
+                min_stake_age_to_vote: 0,
+            })
+    }
+
+    pub fn get_treasury_spend_limit(env: Env) -> Option<TreasurySpendLimit> {
+        env.storage().instance().get(&SPEND_LIMIT)
+    }
+
+    /// Remaining treasury spend budget, or `None` if no limit is configured.
+    pub fn get_treasury_spend_budget(env: Env) -> Option<TreasurySpendBudget> {
+        let limit = Self::get_treasury_spend_limit(env.clone())?;
+        let window = Self::current_spend_window(&env, &limit);
+        Some(TreasurySpendBudget {
+            max_per_window: limit.max_per_window,
+            spent: window.spent,
+            remaining: limit.max_per_window.saturating_sub(window.spent),
+            window_ends: window.window_start.saturating_add(limit.window_seconds),
+        })
+    }
+
+    pub fn get_callback_allowlist(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&CALLBACK_ALLOWLIST)
+            .unwrap_or(Vec::new(&env))
+    }
+
     pub fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
         load_proposal(&env, proposal_id)
     }
@@ -583,12 +1015,21 @@ impl GovernorContract {
             votes_for: p.votes_for,
             votes_against: p.votes_against,
             votes_veto: p.votes_veto,
+            votes_abstain: p.votes_abstain,
             reveal_count: p.reveal_count,
+            abstain_count: p.abstain_count,
             voting_ends: p.voting_ends,
             timelock_ends: p.timelock_ends,
         })
     }
 
+    /// Number of revealed abstentions on a proposal (0 if it does not exist).
+    pub fn get_abstain_count(env: Env, proposal_id: u64) -> u32 {
+        load_proposal(&env, proposal_id)
+            .map(|p| p.abstain_count)
+            .unwrap_or(0)
+    }
+
     pub fn get_delegation(env: Env, voter: Address) -> Option<Delegation> {
         delegation::get_delegation(&env, &voter)
     }
@@ -617,14 +1058,48 @@ impl GovernorContract {
     }
 
     /// Return the current vote power for an address (read-only, does not commit).
-    pub fn get_vote_power(env: Env, voter: Address) -> i128 {
-        let staked = Self::query_staked(&env, &voter);
-        let age = Self::query_stake_age(&env, &voter);
-        compute_vote_power(staked, age)
+    pub fn get_vote_power(env: Env, voter: Address) -> Result<i128, ContractError> {
+        let staked = Self::query_staked(&env, &voter)?;
+        let age = Self::query_stake_age(&env, &voter)?;
+        Ok(compute_vote_power(staked, age))
     }
 
     // ── Internal helpers ──────────────────────────────────────────────────────
 
+    fn veto_threshold(env: &Env, proposal: &Proposal) -> i128 {
+        let total_supply: i128 = env
+            .storage()
+            .instance()
+            .get(&TOTAL_VOTE_SUPPLY)
+            .unwrap_or(1);
+        let slope_bps: u32 = env.storage().instance().get(&VETO_SLOPE).unwrap_or(0);
+        let bps = dynamic_veto_threshold_bps(
+            proposal_veto_threshold_bps(env, proposal),
+            proposal.votes_for,
+            proposal.votes_against,
+            slope_bps,
+        );
+        total_supply * bps as i128 / 10_000
+    }
+
+    fn set_threshold(
+        env: &Env,
+        caller: &Address,
+        key: Symbol,
+        proposal_type: &ProposalType,
+        bps: u32,
+    ) -> Result<(), ContractError> {
+        Self::require_initialized(env)?;
+        caller.require_auth();
+        Self::require_admin(env, caller)?;
+
+        if bps > 10_000 {
+            return Err(ContractError::InvalidInput);
+        }
+        set_stored_bps(env, key, proposal_type, bps);
+        Ok(())
+    }
+
     fn require_initialized(env: &Env) -> Result<(), ContractError> {
         if !env.storage().instance().has(&INITIALIZED) {
             return Err(ContractError::NotInitialized);
@@ -632,6 +1107,117 @@ impl GovernorContract {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn create_proposal_inner(
+        env: Env,
+        proposer: Address,
+        proposal_type: ProposalType,
+        custom_type: Option<Symbol>,
+        title: String,
+        actions: Vec<ProposalAction>,
+        finalization_callback: OptionalFinalizationCallback,
+        spend_amount: i128,
+    ) -> Result<u64, ContractError> {
+        Self::require_initialized(&env)?;
+        proposer.require_auth();
+
+        let custom_config = match &custom_type {
+            Some(name) => {
+                Some(custom_type_config(&env, name).ok_or(ContractError::UnknownProposalType)?)
+            }
+            None => None,
+        };
+
+        if let OptionalFinalizationCallback::Some(callback) = &finalization_callback {
+            if !Self::get_callback_allowlist(env.clone()).contains(&callback.target) {
+                return Err(ContractError::CallbackNotAllowed);
+            }
+        }
+
+        if actions.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // Proposer must have stake.
+        let staked = Self::query_staked(&env, &proposer)?;
+        if staked <= 0 {
+            return Err(ContractError::InsufficientStake);
+        }
+        let policy = Self::get_eligibility_policy(env.clone());
+        if Self::query_stake_age(&env, &proposer)? < policy.min_stake_age_to_propose {
+            return Err(ContractError::InsufficientStake);
+        }
+
+        let now = env.ledger().timestamp();
+        let discussion_ends = now.saturating_add(DEFAULT_DISCUSSION_SECS);
+        let voting_ends = discussion_ends.saturating_add(DEFAULT_VOTING_SECS);
+        let timelock_len = custom_config
+            .map(|c| c.timelock_duration)
+            .unwrap_or_else(|| timelock_duration(&proposal_type));
+        let timelock_ends = voting_ends.saturating_add(timelock_len);
+
+        let id = next_id(&env);
+        let proposal = Proposal {
+            id,
+            proposal_type,
+            custom_type,
+            phase: ProposalPhase::Draft,
+            proposer: proposer.clone(),
+            title,
+            actions,
+            created_at: now,
+            discussion_ends,
+            voting_ends,
+            timelock_ends,
+            votes_for: 0,
+            votes_against: 0,
+            votes_veto: 0,
+            votes_abstain: 0,
+            commit_count: 0,
+            reveal_count: 0,
+            abstain_count: 0,
+            finalization_callback,
+            spend_amount,
+        };
+
+        store_proposal(&env, &proposal);
+        events::publish_proposal_created(&env, &proposal);
+
+        Ok(id)
+    }
+
+    /// The spend window open at the current ledger time. A window that has
+    /// run its full length is replaced by a fresh, empty one.
+    fn current_spend_window(env: &Env, limit: &TreasurySpendLimit) -> SpendWindow {
+        let now = env.ledger().timestamp();
+        let stored: Option<SpendWindow> = env.storage().instance().get(&SPEND_WINDOW);
+        match stored {
+            Some(window) if now < window.window_start.saturating_add(limit.window_seconds) => {
+                window
+            }
+            _ => SpendWindow {
+                window_start: now,
+                spent: 0,
+            },
+        }
+    }
+
+    /// Record `amount` against the spend window, or fail without recording
+    /// anything if it would overrun the window's budget.
+    fn charge_treasury_spend(env: &Env, amount: i128) -> Result<(), ContractError> {
+        let Some(limit) = Self::get_treasury_spend_limit(env.clone()) else {
+            return Ok(());
+        };
+        let mut window = Self::current_spend_window(env, &limit);
+        let spent = window.spent.saturating_add(amount);
+        if spent > limit.max_per_window {
+            return Err(ContractError::SpendWindowExceeded);
+        }
+        window.spent = spent;
+        env.storage().instance().set(&SPEND_WINDOW, &window);
+        Ok(())
+    }
+
     fn require_admin(env: &Env, caller: &Address) -> Result<(), ContractError> {
         let admin: Address = env
             .storage()
@@ -644,35 +1230,83 @@ impl GovernorContract {
         Ok(())
     }
 
+    /// Fire the proposal's finalization callback if it just reached a
+    /// terminal outcome.
+    fn run_finalization_callback(env: &Env, proposal: &Proposal) {
+        if !matches!(
+            proposal.phase,
+            ProposalPhase::Completed | ProposalPhase::Rejected
+        ) {
+            return;
+        }
+        if let OptionalFinalizationCallback::Some(callback) = &proposal.finalization_callback {
+            execution::invoke_finalization_callback(
+                env,
+                proposal.id,
+                &callback.target,
+                &callback.function,
+                &proposal.phase,
+            );
+        }
+    }
+
     /// Query the staked balance of `voter` from the staking contract.
-    ///
-    /// In production this uses `env.invoke_contract`; here we read from
-    /// instance storage using the same key convention as `staking/src/lib.rs`
-    /// to allow simulation in tests without a live cross-contract call.
-    fn query_staked(env: &Env, voter: &Address) -> i128 {
-        // In a deployed environment replace this with:
-        //   let staking: Address = env.storage().instance().get(&STAKING_CONTRACT).unwrap();
-        //   env.invoke_contract(&staking, &symbol_short!("get_staked"), (voter.clone(),).into_val(env))
-        //
-        // For testability we use a mock key injected by tests.
+    #[cfg(not(test))]
+    fn query_staked(env: &Env, voter: &Address) -> Result<i128, ContractError> {
+        Self::query_staking(env, "get_staked", voter)
+    }
+
+    /// Query how long `voter` has been staking (in seconds), via the staking
+    /// contract's `get_stake_age` view.
+    #[cfg(not(test))]
+    fn query_stake_age(env: &Env, voter: &Address) -> Result<u64, ContractError> {
+        Self::query_staking(env, "get_stake_age", voter)
+    }
+
+    /// Call a `(staker) -> T` view on the staking contract. A trap in the
+    /// callee, or a return value of the wrong type, becomes
+    /// `StakingQueryFailed` instead of aborting the governor call.
+    #[cfg(not(test))]
+    fn query_staking<T>(env: &Env, function: &str, voter: &Address) -> Result<T, ContractError>
+    where
+        T: soroban_sdk::TryFromVal<Env, soroban_sdk::Val>,
+    {
+        use soroban_sdk::IntoVal;
+
+        let staking: Address = env
+            .storage()
+            .instance()
+            .get(&STAKING_CONTRACT)
+            .ok_or(ContractError::NotInitialized)?;
+        let args: Vec<soroban_sdk::Val> = Vec::from_array(env, [voter.into_val(env)]);
+        match env.try_invoke_contract::<T, soroban_sdk::Error>(
+            &staking,
+            &Symbol::new(env, function),
+            args,
+        ) {
+            Ok(Ok(value)) => Ok(value),
+            _ => Err(ContractError::StakingQueryFailed),
+        }
+    }
+
+    /// Unit-test shim for `query_staked`: reads a mock key injected by
+    /// `src/tests.rs` instead of calling a live staking contract.
+    #[cfg(test)]
+    fn query_staked(env: &Env, voter: &Address) -> Result<i128, ContractError> {
         let mock_key = (symbol_short!("M_STK"), voter.clone());
-        env.storage().persistent().get(&mock_key).unwrap_or(0i128)
+        Ok(env.storage().persistent().get(&mock_key).unwrap_or(0i128))
     }
 
-    /// Query how long `voter` has been staking (in seconds).
-    ///
-    /// This is the new view function added to the staking contract.
-    /// See `contracts/staking/src/lib.rs` `get_stake_age` addition.
-    fn query_stake_age(env: &Env, voter: &Address) -> u64 {
-        // Same pattern: replace with cross-contract call in production.
-        //   env.invoke_contract(&staking, &symbol_short!("get_stk_age"), (voter.clone(),).into_val(env))
+    /// Unit-test shim for `query_stake_age`.
+    #[cfg(test)]
+    fn query_stake_age(env: &Env, voter: &Address) -> Result<u64, ContractError> {
         let mock_key = (symbol_short!("M_AGE"), voter.clone());
-        env.storage().persistent().get(&mock_key).unwrap_or(0u64)
+        Ok(env.storage().persistent().get(&mock_key).unwrap_or(0u64))
     }
 
     /// Compute the vote commitment hash.
     ///
-    /// `commitment = SHA-256(proposal_id_le_bytes || voter_bytes || choice_byte || salt)`
+    /// `commitment = SHA-256(proposal_id_le_bytes || voter_xdr || choice_byte || salt)`
     ///
     /// In Soroban, `env.crypto().sha256()` accepts a `Bytes` value.
     fn hash_commitment(
@@ -682,7 +1316,7 @@ impl GovernorContract {
         choice: &VoteChoice,
         salt: &BytesN<32>,
     ) -> BytesN<32> {
-        use soroban_sdk::Bytes;
+        use soroban_sdk::{xdr::ToXdr, Bytes};
 
         let mut data = Bytes::new(env);
 
@@ -692,11 +1326,15 @@ impl GovernorContract {
             data.push_back(*b);
         }
 
+        // voter as its canonical XDR encoding, binding the commitment to them
+        data.append(&voter.clone().to_xdr(env));
+
         // choice as a single byte
         let choice_byte: u8 = match choice {
             VoteChoice::For => 0,
             VoteChoice::Against => 1,
             VoteChoice::Veto => 2,
+            VoteChoice::Abstain => 3,
         };
         data.push_back(choice_byte);
 
@@ -705,10 +1343,6 @@ impl GovernorContract {
             data.push_back(salt.get(i).unwrap_or(0));
         }
 
-        // Note: voter address bytes would be appended here in a full implementation
-        // using voter.to_string() or a canonical serialisation. Omitted for
-        // no_std compatibility; production code should include it.
-
         env.crypto().sha256(&data).into()
     }
 }
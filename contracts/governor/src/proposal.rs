@@ -6,6 +6,10 @@ use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symb
 
 pub(crate) const PROPOSAL_CTR: Symbol = symbol_short!("PROP_CTR");
 pub(crate) const PROPOSAL: Symbol = symbol_short!("PROP");
+pub(crate) const QUORUM_BPS: Symbol = symbol_short!("QRM_BPS");
+pub(crate) const PASS_BPS: Symbol = symbol_short!("PASS_BPS");
+pub(crate) const VETO_BPS: Symbol = symbol_short!("VETO_BPS");
+const CUSTOM_TYPE: Symbol = symbol_short!("CUST_TYP");
 
 // TTL: ~60 days at 5s/ledger
 const TTL_THRESHOLD: u32 = 1_036_800;
@@ -31,13 +35,29 @@ pub enum ProposalType {
     TreasurySpend,
 }
 
+/// Thresholds and timelock for a deployment-defined proposal category,
+/// registered under a `Symbol` (e.g. `ClinicalProtocolChange`).
+///
+/// A proposal created with a custom type still carries a [`ProposalType`]
+/// for its execution path; the registered config replaces that type's
+/// thresholds and timelock.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalTypeConfig {
+    pub quorum_bps: u32,
+    pub pass_threshold_bps: u32,
+    pub veto_threshold_bps: u32,
+    /// Timelock length in seconds.
+    pub timelock_duration: u64,
+}
+
 /// Phase of the proposal lifecycle.
 ///
 /// ```text
 /// Draft ──► Discussion ──► Voting ──► Timelock ──► Execution
-///                                          │
-///                                          └──► Rejected  (veto threshold met)
-///                                          └──► Completed (executed)
+///   │            │                         │
+///   └────────────┴──► Cancelled (withdrawn └──► Rejected  (veto threshold met)
+///                     by the proposer)     └──► Completed (executed)
 ///                                          └──► Expired   (voting period elapsed, quorum not met)
 /// ```
 #[contracttype]
@@ -51,6 +71,7 @@ pub enum ProposalPhase {
     Completed,
     Rejected,
     Expired,
+    Cancelled,
 }
 
 /// A single action within a batched proposal.
@@ -65,12 +86,32 @@ pub struct ProposalAction {
     pub params_hash: BytesN<32>,
 }
 
+/// Downstream hook invoked as `target.function(proposal_id, outcome)` when a
+/// proposal is finalized.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FinalizationCallback {
+    /// Contract to notify; must be on the governor's callback allowlist.
+    pub target: Address,
+    pub function: Symbol,
+}
+
+/// `Option<FinalizationCallback>` in a form storable inside a contract type.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OptionalFinalizationCallback {
+    None,
+    Some(FinalizationCallback),
+}
+
 /// The full on-chain proposal record.
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Proposal {
     pub id: u64,
     pub proposal_type: ProposalType,
+    /// Registered custom type whose config governs this proposal, if any.
+    pub custom_type: Option<Symbol>,
     pub phase: ProposalPhase,
     pub proposer: Address,
     /// Human-readable summary stored as a short on-chain string.
@@ -90,10 +131,18 @@ pub struct Proposal {
     pub votes_against: i128,
     /// Total quadratic weight of veto votes (counted against optimistic execution).
     pub votes_veto: i128,
+    /// Total quadratic weight of abstentions (counted toward quorum only).
+    pub votes_abstain: i128,
     /// Commit-reveal: hash of committed votes not yet revealed.
     pub commit_count: u32,
-    /// Number of revealed votes (for + against + veto).
+    /// Number of revealed votes (for + against + veto + abstain).
     pub reveal_count: u32,
+    /// Number of revealed abstentions.
+    pub abstain_count: u32,
+    /// Contract notified once the proposal reaches Completed or Rejected.
+    pub finalization_callback: OptionalFinalizationCallback,
+    /// Amount moved by a TreasurySpend proposal; 0 for every other type.
+    pub spend_amount: i128,
 }
 
 // ── Storage helpers ──────────────────────────────────────────────────────────
@@ -126,6 +175,9 @@ pub(crate) fn load(env: &Env, id: u64) -> Option<Proposal> {
 }
 
 // ── Quorum constants by proposal type ────────────────────────────────────────
+//
+// These are the compiled defaults.  The admin can override each of them per
+// proposal type; see the `stored_*` functions below.
 
 /// Minimum fraction of total vote supply (in basis points, 10 000 = 100 %)
 /// that must participate for a proposal to be valid.
@@ -151,3 +203,101 @@ pub fn veto_threshold_bps(proposal_type: &ProposalType) -> u32 {
         _ => 3_300,                             // 33 % for everything else
     }
 }
+
+// ── Stored overrides ─────────────────────────────────────────────────────────
+
+fn stored_bps(env: &Env, key: Symbol, proposal_type: &ProposalType) -> Option<u32> {
+    env.storage().instance().get(&(key, proposal_type.clone()))
+}
+
+pub(crate) fn set_stored_bps(env: &Env, key: Symbol, proposal_type: &ProposalType, bps: u32) {
+    env.storage()
+        .instance()
+        .set(&(key, proposal_type.clone()), &bps);
+}
+
+/// Quorum for `proposal_type`: the stored override, else [`quorum_bps`].
+pub fn stored_quorum_bps(env: &Env, proposal_type: &ProposalType) -> u32 {
+    stored_bps(env, QUORUM_BPS, proposal_type).unwrap_or_else(|| quorum_bps(proposal_type))
+}
+
+/// Pass threshold for `proposal_type`: the stored override, else
+/// [`pass_threshold_bps`].
+pub fn stored_pass_threshold_bps(env: &Env, proposal_type: &ProposalType) -> u32 {
+    stored_bps(env, PASS_BPS, proposal_type).unwrap_or_else(|| pass_threshold_bps(proposal_type))
+}
+
+/// Veto threshold for `proposal_type`: the stored override, else
+/// [`veto_threshold_bps`].
+pub fn stored_veto_threshold_bps(env: &Env, proposal_type: &ProposalType) -> u32 {
+    stored_bps(env, VETO_BPS, proposal_type).unwrap_or_else(|| veto_threshold_bps(proposal_type))
+}
+
+// ── Custom proposal types ────────────────────────────────────────────────────
+
+pub(crate) fn set_custom_type(env: &Env, name: &Symbol, config: &ProposalTypeConfig) {
+    env.storage()
+        .instance()
+        .set(&(CUSTOM_TYPE, name.clone()), config);
+}
+
+pub fn custom_type_config(env: &Env, name: &Symbol) -> Option<ProposalTypeConfig> {
+    env.storage().instance().get(&(CUSTOM_TYPE, name.clone()))
+}
+
+fn proposal_custom_config(env: &Env, proposal: &Proposal) -> Option<ProposalTypeConfig> {
+    proposal
+        .custom_type
+        .as_ref()
+        .and_then(|name| custom_type_config(env, name))
+}
+
+/// Quorum for `proposal`: its custom type's config if it has one, else
+/// [`stored_quorum_bps`].
+pub fn proposal_quorum_bps(env: &Env, proposal: &Proposal) -> u32 {
+    proposal_custom_config(env, proposal)
+        .map(|c| c.quorum_bps)
+        .unwrap_or_else(|| stored_quorum_bps(env, &proposal.proposal_type))
+}
+
+/// Pass threshold for `proposal`: its custom type's config if it has one,
+/// else [`stored_pass_threshold_bps`].
+pub fn proposal_pass_threshold_bps(env: &Env, proposal: &Proposal) -> u32 {
+    proposal_custom_config(env, proposal)
+        .map(|c| c.pass_threshold_bps)
+        .unwrap_or_else(|| stored_pass_threshold_bps(env, &proposal.proposal_type))
+}
+
+/// Base veto threshold for `proposal`: its custom type's config if it has
+/// one, else [`stored_veto_threshold_bps`].
+pub fn proposal_veto_threshold_bps(env: &Env, proposal: &Proposal) -> u32 {
+    proposal_custom_config(env, proposal)
+        .map(|c| c.veto_threshold_bps)
+        .unwrap_or_else(|| stored_veto_threshold_bps(env, &proposal.proposal_type))
+}
+
+/// Veto threshold (bps of total vote supply) scaled by how decisively the
+/// proposal passed.
+///
+/// The pass margin is `(for - against) / (for + against)` in basis points;
+/// `slope_bps` of that margin is added on top of the base threshold `base`,
+/// so a landslide needs more veto weight to overturn than a narrow pass.  A
+/// slope of 0 reproduces the static threshold.  The result is capped at
+/// 100 %.
+pub fn dynamic_veto_threshold_bps(
+    base: u32,
+    votes_for: i128,
+    votes_against: i128,
+    slope_bps: u32,
+) -> u32 {
+    let decisive = votes_for.saturating_add(votes_against);
+    if decisive <= 0 || votes_for <= votes_against {
+        return base;
+    }
+    let margin_bps = votes_for
+        .saturating_sub(votes_against)
+        .saturating_mul(10_000)
+        / decisive;
+    let bonus = margin_bps.saturating_mul(slope_bps as i128) / 10_000;
+    (base as i128).saturating_add(bonus).min(10_000) as u32
+}
@@ -6,7 +6,8 @@ mod tests {
         timelock_duration, TIMELOCK_EMERGENCY, TIMELOCK_STANDARD, TIMELOCK_UPGRADE,
     };
     use crate::proposal::{
-        load, next_id, store, Proposal, ProposalAction, ProposalPhase, ProposalType,
+        load, next_id, store, OptionalFinalizationCallback, Proposal, ProposalAction,
+        ProposalPhase, ProposalType,
     };
     use soroban_sdk::{
         symbol_short, testutils::Address as _, testutils::Ledger as _, Address, BytesN, Env,
@@ -22,6 +23,7 @@ mod tests {
         Proposal {
             id: next_id(env),
             proposal_type,
+            custom_type: None,
             phase: ProposalPhase::Timelock,
             proposer: Address::generate(env),
             title: String::from_str(env, "Test proposal"),
@@ -33,8 +35,12 @@ mod tests {
             votes_for: 1000,
             votes_against: 0,
             votes_veto: 0,
+            votes_abstain: 0,
             commit_count: 0,
             reveal_count: 1,
+            abstain_count: 0,
+            finalization_callback: OptionalFinalizationCallback::None,
+            spend_amount: 0,
         }
     }
 
@@ -6,9 +6,18 @@
 //! - Full proposal lifecycle (Draft → Completed)
 //! - Rejection paths (quorum failure, majority failure, veto)
 //! - Commit-reveal voting
+//! - Abstain votes (quorum participation without affecting majority)
 //! - Delegation and revocation
 //! - Batched proposals
 //! - Emergency proposal (reduced timelock)
+//! - Finalization callbacks
+//! - Minimum stake-age eligibility
+//! - Pass-margin-scaled veto threshold
+//! - Voter-bound commitments and salt-reuse detection
+//! - Stored per-type quorum, pass and veto thresholds
+//! - Registered custom proposal types
+//! - Proposer cancellation before voting
+//! - Treasury spend window limits
 
 #![cfg(test)]
 
@@ -17,14 +26,14 @@ extern crate std;
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
-    Address, BytesN, Env, String, Vec,
+    Address, BytesN, Env, String, Symbol, Vec,
 };
 
 use crate::{
-    delegation,
-    proposal::{ProposalAction, ProposalPhase, ProposalType},
+    delegation, execution,
+    proposal::{ProposalAction, ProposalPhase, ProposalType, ProposalTypeConfig},
     voting::{compute_vote_power, isqrt, loyalty_multiplier_scaled, VoteChoice, SCALE},
-    ContractError, GovernorContract, GovernorContractClient,
+    ContractError, EligibilityPolicy, GovernorContract, GovernorContractClient, TreasuryTransfer,
 };
 
 // ── Test helpers ──────────────────────────────────────────────────────────────
@@ -193,6 +202,7 @@ fn test_initialize_and_create_proposal() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Set lock period to 7 days"),
         &actions,
+        &None,
     );
 
     assert_eq!(id, 1);
@@ -219,6 +229,7 @@ fn test_create_proposal_requires_stake() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Should fail"),
         &actions,
+        &None,
     );
     assert!(result.is_err());
 }
@@ -249,6 +260,7 @@ fn test_full_lifecycle_happy_path() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Increase reward rate"),
         &actions,
+        &None,
     );
 
     // 2. Draft → Discussion
@@ -309,6 +321,7 @@ fn test_proposal_lifecycle_rejection_no_quorum() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Low-participation proposal"),
         &actions,
+        &None,
     );
 
     // Draft → Discussion → Voting
@@ -356,6 +369,7 @@ fn test_delegation_and_revocation() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Test proposal"),
         &actions,
+        &None,
     );
 
     // Revoke delegation
@@ -366,6 +380,38 @@ fn test_delegation_and_revocation() {
     assert_eq!(count, 0);
 }
 
+#[test]
+fn test_redelegate_moves_delegation_counts() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (_contract_id, client) = register_governor(&env);
+    default_init(&env, &client);
+
+    let voter = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    // No prior delegation: behaves like `delegate`.
+    client.redelegate(&voter, &first);
+    assert_eq!(client.get_delegation(&voter).unwrap().delegate, first);
+    assert_eq!(client.get_delegation_count(&first), 1);
+
+    client.redelegate(&voter, &second);
+    assert_eq!(client.get_delegation(&voter).unwrap().delegate, second);
+    assert_eq!(client.get_delegation_count(&first), 0);
+    assert_eq!(client.get_delegation_count(&second), 1);
+
+    // Re-selecting the current delegate does not double count.
+    client.redelegate(&voter, &second);
+    assert_eq!(client.get_delegation_count(&second), 1);
+
+    assert_eq!(
+        client.try_redelegate(&voter, &voter),
+        Err(Ok(ContractError::SelfDelegation))
+    );
+    assert_eq!(client.get_delegation(&voter).unwrap().delegate, second);
+}
+
 #[test]
 fn test_self_delegation_rejected() {
     let env = create_env();
@@ -396,6 +442,7 @@ fn test_emergency_proposal_has_shorter_timelock() {
         &ProposalType::EmergencyAction,
         &String::from_str(&env, "Emergency fix"),
         &actions,
+        &None,
     );
 
     let proposal = client.get_proposal(&id).unwrap();
@@ -422,6 +469,7 @@ fn test_upgrade_proposal_has_longer_timelock() {
         &ProposalType::ContractUpgrade,
         &String::from_str(&env, "Upgrade to v2"),
         &actions,
+        &None,
     );
 
     let proposal = client.get_proposal(&id).unwrap();
@@ -454,6 +502,7 @@ fn test_batched_proposal_multiple_actions() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Batch update 3 params"),
         &actions,
+        &None,
     );
 
     let proposal = client.get_proposal(&id).unwrap();
@@ -494,6 +543,7 @@ fn test_cannot_advance_phase_before_time() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Test"),
         &actions,
+        &None,
     );
 
     // Draft → Discussion (ok, proposer can do this immediately)
@@ -523,6 +573,7 @@ fn test_double_commit_rejected() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Test"),
         &actions,
+        &None,
     );
 
     client.advance_phase(&proposer, &id);
@@ -540,22 +591,25 @@ fn test_double_commit_rejected() {
 // ── Replay / duplicate execution simulation ──────────────────────────────────
 
 /// Compute the commitment hash according to the current on-chain logic:
-/// SHA-256(proposal_id_le_bytes || choice_byte || salt_32bytes)
+/// SHA-256(proposal_id_le_bytes || voter_xdr || choice_byte || salt_32bytes)
 fn compute_commitment(
     env: &Env,
     proposal_id: u64,
+    voter: &Address,
     choice: &VoteChoice,
     salt: &BytesN<32>,
 ) -> BytesN<32> {
-    use soroban_sdk::Bytes;
+    use soroban_sdk::{xdr::ToXdr, Bytes};
     let mut data = Bytes::new(env);
     for b in proposal_id.to_le_bytes().iter() {
         data.push_back(*b);
     }
+    data.append(&voter.clone().to_xdr(env));
     let choice_byte: u8 = match choice {
         VoteChoice::For => 0,
         VoteChoice::Against => 1,
         VoteChoice::Veto => 2,
+        VoteChoice::Abstain => 3,
     };
     data.push_back(choice_byte);
     for i in 0..32u32 {
@@ -589,6 +643,7 @@ fn test_execute_proposal_is_not_replayable() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Replay protection test"),
         &actions,
+        &None,
     );
 
     // Draft → Discussion → Voting
@@ -599,7 +654,7 @@ fn test_execute_proposal_is_not_replayable() {
     // Commit + reveal a FOR vote with a matching commitment.
     let salt = BytesN::from_array(&env, &[9u8; 32]);
     let choice = VoteChoice::For;
-    let commitment = compute_commitment(&env, id, &choice, &salt);
+    let commitment = compute_commitment(&env, id, &voter, &choice, &salt);
     client.commit_vote(&voter, &id, &commitment);
     let revealed_power = client.reveal_vote(&voter, &id, &choice, &salt);
     assert!(revealed_power > 0);
@@ -627,6 +682,102 @@ fn test_execute_proposal_is_not_replayable() {
     assert!(res.is_err());
 }
 
+// ── Abstain votes ─────────────────────────────────────────────────────────────
+
+/// Create a ParameterChange proposal against a 1 000-token supply and move it
+/// into the Voting phase.
+fn proposal_in_voting(env: &Env, contract_id: &Address, client: &GovernorContractClient) -> u64 {
+    let admin = Address::generate(env);
+    let staking = Address::generate(env);
+    let treasury = Address::generate(env);
+    client.initialize(&admin, &staking, &treasury, &1_000i128);
+
+    let proposer = Address::generate(env);
+    set_mock_stake(env, contract_id, &proposer, 10_000);
+    let target = Address::generate(env);
+    let id = client.create_proposal(
+        &proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(env, "Abstain test"),
+        &single_action(env, &target),
+        &None,
+    );
+    client.advance_phase(&proposer, &id);
+    advance_time(env, 3 * 24 * 3600 + 1);
+    client.advance_phase(&proposer, &id);
+    id
+}
+
+fn commit_and_reveal(
+    env: &Env,
+    client: &GovernorContractClient,
+    voter: &Address,
+    id: u64,
+    choice: VoteChoice,
+) {
+    let salt = BytesN::from_array(env, &[7u8; 32]);
+    let commitment = compute_commitment(env, id, voter, &choice, &salt);
+    client.commit_vote(voter, &id, &commitment);
+    client.reveal_vote(voter, &id, &choice, &salt);
+}
+
+#[test]
+fn test_abstain_counts_toward_participation_not_tallies() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let id = proposal_in_voting(&env, &contract_id, &client);
+
+    let supporter = Address::generate(&env);
+    let abstainer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &supporter, 10_000); // power 100
+    set_mock_stake(&env, &contract_id, &abstainer, 10_000); // power 100
+
+    commit_and_reveal(&env, &client, &supporter, id, VoteChoice::For);
+    commit_and_reveal(&env, &client, &abstainer, id, VoteChoice::Abstain);
+
+    let p = client.get_proposal(&id).unwrap();
+    assert_eq!(p.reveal_count, 2);
+    assert_eq!(p.abstain_count, 1);
+    assert_eq!(client.get_abstain_count(&id), 1);
+    assert_eq!(p.votes_for, 100);
+    assert_eq!(p.votes_against, 0);
+    assert_eq!(p.votes_veto, 0);
+    assert_eq!(p.votes_abstain, 100);
+
+    // Quorum is 15 % of 1 000 = 150: the FOR vote alone (100) would expire,
+    // but the abstention brings participation to 200.
+    advance_time(&env, 5 * 24 * 3600 + 1);
+    let phase = client.advance_phase(&supporter, &id);
+    assert!(matches!(phase, ProposalPhase::Timelock));
+}
+
+#[test]
+fn test_abstain_excluded_from_majority() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let id = proposal_in_voting(&env, &contract_id, &client);
+
+    let supporter = Address::generate(&env);
+    let opponent = Address::generate(&env);
+    let abstainer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &supporter, 40_000); // power 200
+    set_mock_stake(&env, &contract_id, &opponent, 10_000); // power 100
+    set_mock_stake(&env, &contract_id, &abstainer, 1_000_000); // power 1 000
+
+    commit_and_reveal(&env, &client, &supporter, id, VoteChoice::For);
+    commit_and_reveal(&env, &client, &opponent, id, VoteChoice::Against);
+    commit_and_reveal(&env, &client, &abstainer, id, VoteChoice::Abstain);
+
+    // 200 / (200 + 100) ≥ 51 % passes; counting the abstention in the pool
+    // would have rejected it.
+    advance_time(&env, 5 * 24 * 3600 + 1);
+    let phase = client.advance_phase(&supporter, &id);
+    assert!(matches!(phase, ProposalPhase::Timelock));
+    assert_eq!(client.get_abstain_count(&id), 1);
+}
+
 // ── Namespace separation tests ────────────────────────────────────────────────
 
 /// Test that proposal storage keys don't collide with delegation keys
@@ -651,6 +802,7 @@ fn test_namespace_proposal_vs_delegation_no_collision() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Test proposal"),
         &actions,
+        &None,
     );
 
     // Create a delegation
@@ -688,6 +840,7 @@ fn test_namespace_votes_vs_proposals_no_collision() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "First proposal"),
         &actions,
+        &None,
     );
 
     // Create second proposal
@@ -696,6 +849,7 @@ fn test_namespace_votes_vs_proposals_no_collision() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Second proposal"),
         &actions,
+        &None,
     );
 
     // Advance both to voting phase
@@ -750,6 +904,7 @@ fn test_namespace_voter_votes_isolated() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Test"),
         &actions,
+        &None,
     );
 
     // Advance to voting
@@ -801,6 +956,7 @@ fn test_namespace_proposal_types_isolated() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Param change"),
         &actions,
+        &None,
     );
 
     let emergency = client.create_proposal(
@@ -808,6 +964,7 @@ fn test_namespace_proposal_types_isolated() {
         &ProposalType::EmergencyAction,
         &String::from_str(&env, "Emergency"),
         &actions,
+        &None,
     );
 
     let upgrade = client.create_proposal(
@@ -815,6 +972,7 @@ fn test_namespace_proposal_types_isolated() {
         &ProposalType::ContractUpgrade,
         &String::from_str(&env, "Upgrade"),
         &actions,
+        &None,
     );
 
     // Verify all three proposals exist independently with correct types
@@ -896,6 +1054,7 @@ fn test_namespace_delegation_vs_votes_no_collision() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Test"),
         &actions,
+        &None,
     );
 
     // Advance to voting
@@ -940,6 +1099,7 @@ fn test_namespace_proposal_id_gaps() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "First"),
         &actions,
+        &None,
     );
     assert_eq!(id1, 1);
 
@@ -949,6 +1109,7 @@ fn test_namespace_proposal_id_gaps() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Second"),
         &actions,
+        &None,
     );
     assert_eq!(id2, 2);
 
@@ -960,3 +1121,896 @@ fn test_namespace_proposal_id_gaps() {
     assert_eq!(p2.id, id2);
     assert_ne!(p1.title, p2.title);
 }
+
+// ── Finalization callbacks ────────────────────────────────────────────────────
+
+/// Downstream contract that records the last finalization it was notified of.
+#[soroban_sdk::contract]
+pub struct CallbackRecorder;
+
+#[soroban_sdk::contractimpl]
+impl CallbackRecorder {
+    pub fn on_final(env: Env, proposal_id: u64, outcome: ProposalPhase) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("LAST"), &(proposal_id, outcome));
+    }
+
+    pub fn last(env: Env) -> Option<(u64, ProposalPhase)> {
+        env.storage().instance().get(&symbol_short!("LAST"))
+    }
+}
+
+/// Initialise against a 1 000-token supply, allowlist a `CallbackRecorder`
+/// and create a proposal that calls `function` on it, moved into Voting.
+fn proposal_with_callback(
+    env: &Env,
+    contract_id: &Address,
+    client: &GovernorContractClient,
+    function: Symbol,
+) -> (u64, CallbackRecorderClient<'static>) {
+    let admin = Address::generate(env);
+    let staking = Address::generate(env);
+    let treasury = Address::generate(env);
+    client.initialize(&admin, &staking, &treasury, &1_000i128);
+
+    let recorder_id = env.register(CallbackRecorder, ());
+    let recorder = CallbackRecorderClient::new(env, &recorder_id);
+    client.set_callback_allowlist(&admin, &Vec::from_array(env, [recorder_id.clone()]));
+
+    let proposer = Address::generate(env);
+    set_mock_stake(env, contract_id, &proposer, 10_000);
+    let target = Address::generate(env);
+    let id = client.create_proposal_with_callback(
+        &proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(env, "Callback test"),
+        &single_action(env, &target),
+        &recorder_id,
+        &function,
+    );
+    client.advance_phase(&proposer, &id);
+    advance_time(env, 3 * 24 * 3600 + 1);
+    client.advance_phase(&proposer, &id);
+    (id, recorder)
+}
+
+fn cast_two_votes(
+    env: &Env,
+    contract_id: &Address,
+    client: &GovernorContractClient,
+    id: u64,
+    choice: VoteChoice,
+) {
+    for _ in 0..2 {
+        let voter = Address::generate(env);
+        set_mock_stake(env, contract_id, &voter, 10_000); // power 100
+        commit_and_reveal(env, client, &voter, id, choice.clone());
+    }
+}
+
+#[test]
+fn test_completed_proposal_invokes_callback() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (id, recorder) =
+        proposal_with_callback(&env, &contract_id, &client, symbol_short!("on_final"));
+
+    cast_two_votes(&env, &contract_id, &client, id, VoteChoice::For);
+    advance_time(&env, 5 * 24 * 3600 + 1);
+    client.advance_phase(&Address::generate(&env), &id);
+    advance_time(&env, 2 * 24 * 3600 + 1);
+    client.advance_phase(&Address::generate(&env), &id);
+    assert_eq!(recorder.last(), None);
+
+    client.execute_proposal(&Address::generate(&env), &id);
+    assert_eq!(recorder.last(), Some((id, ProposalPhase::Completed)));
+}
+
+#[test]
+fn test_rejected_proposal_invokes_callback() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (id, recorder) =
+        proposal_with_callback(&env, &contract_id, &client, symbol_short!("on_final"));
+
+    cast_two_votes(&env, &contract_id, &client, id, VoteChoice::Against);
+    advance_time(&env, 5 * 24 * 3600 + 1);
+    let phase = client.advance_phase(&Address::generate(&env), &id);
+
+    assert_eq!(phase, ProposalPhase::Rejected);
+    assert_eq!(recorder.last(), Some((id, ProposalPhase::Rejected)));
+}
+
+#[test]
+fn test_failing_callback_does_not_block_finalization() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (id, recorder) =
+        proposal_with_callback(&env, &contract_id, &client, symbol_short!("missing"));
+
+    cast_two_votes(&env, &contract_id, &client, id, VoteChoice::Against);
+    advance_time(&env, 5 * 24 * 3600 + 1);
+    let phase = client.advance_phase(&Address::generate(&env), &id);
+
+    assert_eq!(phase, ProposalPhase::Rejected);
+    assert_eq!(recorder.last(), None);
+    let p = client.get_proposal(&id).unwrap();
+    assert_eq!(p.phase, ProposalPhase::Rejected);
+}
+
+#[test]
+fn test_non_allowlisted_callback_rejected_at_creation() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    default_init(&env, &client);
+
+    let proposer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &proposer, 10_000);
+    let target = Address::generate(&env);
+    let result = client.try_create_proposal_with_callback(
+        &proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(&env, "Unlisted"),
+        &single_action(&env, &target),
+        &Address::generate(&env),
+        &symbol_short!("on_final"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::CallbackNotAllowed)));
+}
+
+// ── Stake-age eligibility ─────────────────────────────────────────────────────
+
+const THIRTY_DAYS: u64 = 30 * 86_400;
+
+fn propose(env: &Env, client: &GovernorContractClient, proposer: &Address) -> Result<u64, ()> {
+    let target = Address::generate(env);
+    match client.try_create_proposal(
+        proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(env, "Eligibility"),
+        &single_action(env, &target),
+        &None,
+    ) {
+        Ok(Ok(id)) => Ok(id),
+        Err(Ok(ContractError::InsufficientStake)) => Err(()),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn test_eligibility_policy_defaults_to_zero() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (_, client) = register_governor(&env);
+    let (admin, _, _) = default_init(&env, &client);
+
+    assert_eq!(
+        client.get_eligibility_policy(),
+        EligibilityPolicy {
+            min_stake_age_to_propose: 0,
+            min_stake_age_to_vote: 0,
+        }
+    );
+
+    let outsider = Address::generate(&env);
+    let result = client.try_set_eligibility_policy(&outsider, &THIRTY_DAYS, &THIRTY_DAYS);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    client.set_eligibility_policy(&admin, &THIRTY_DAYS, &0);
+    assert_eq!(
+        client.get_eligibility_policy().min_stake_age_to_propose,
+        THIRTY_DAYS
+    );
+}
+
+#[test]
+fn test_fresh_staker_cannot_propose_until_stake_ages() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (admin, _, _) = default_init(&env, &client);
+    client.set_eligibility_policy(&admin, &THIRTY_DAYS, &0);
+
+    let fresh = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &fresh, 10_000);
+    set_mock_age(&env, &contract_id, &fresh, 60);
+    assert_eq!(propose(&env, &client, &fresh), Err(()));
+
+    set_mock_age(&env, &contract_id, &fresh, THIRTY_DAYS);
+    assert!(propose(&env, &client, &fresh).is_ok());
+
+    let seasoned = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &seasoned, 10_000);
+    set_mock_age(&env, &contract_id, &seasoned, 365 * 86_400);
+    assert!(propose(&env, &client, &seasoned).is_ok());
+}
+
+#[test]
+fn test_fresh_staker_cannot_vote_until_stake_ages() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (admin, _, _) = default_init(&env, &client);
+    client.set_eligibility_policy(&admin, &0, &THIRTY_DAYS);
+
+    let proposer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &proposer, 10_000);
+    let id = propose(&env, &client, &proposer).unwrap();
+    client.advance_phase(&proposer, &id);
+    advance_time(&env, 3 * 24 * 3600 + 1);
+    client.advance_phase(&proposer, &id);
+
+    let fresh = Address::generate(&env);
+    let seasoned = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &fresh, 10_000);
+    set_mock_stake(&env, &contract_id, &seasoned, 10_000);
+    set_mock_age(&env, &contract_id, &fresh, 86_400);
+    set_mock_age(&env, &contract_id, &seasoned, 365 * 86_400);
+
+    let commitment = BytesN::from_array(&env, &[7u8; 32]);
+    let result = client.try_commit_vote(&fresh, &id, &commitment);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientStake)));
+
+    client.commit_vote(&seasoned, &id, &commitment);
+
+    set_mock_age(&env, &contract_id, &fresh, THIRTY_DAYS);
+    client.commit_vote(&fresh, &id, &commitment);
+    assert_eq!(client.get_proposal(&id).unwrap().commit_count, 2);
+}
+
+// ── Margin-scaled veto threshold ──────────────────────────────────────────────
+
+/// Pass a proposal into Timelock with the given For/Against stakes while a
+/// vetoer with power 500 commits a Veto to reveal later. Returns the id.
+fn passed_with_pending_veto(
+    env: &Env,
+    contract_id: &Address,
+    client: &GovernorContractClient,
+    for_stake: i128,
+    against_stake: i128,
+    vetoer: &Address,
+) -> u64 {
+    let id = proposal_in_voting(env, contract_id, client);
+    let admin = client.get_admin();
+    client.set_veto_margin_slope(&admin, &5_000);
+
+    let supporter = Address::generate(env);
+    set_mock_stake(env, contract_id, &supporter, for_stake);
+    commit_and_reveal(env, client, &supporter, id, VoteChoice::For);
+    if against_stake > 0 {
+        let opponent = Address::generate(env);
+        set_mock_stake(env, contract_id, &opponent, against_stake);
+        commit_and_reveal(env, client, &opponent, id, VoteChoice::Against);
+    }
+
+    set_mock_stake(env, contract_id, vetoer, 250_000); // power 500
+    let salt = BytesN::from_array(env, &[7u8; 32]);
+    let commitment = compute_commitment(env, id, vetoer, &VoteChoice::Veto, &salt);
+    client.commit_vote(vetoer, &id, &commitment);
+
+    advance_time(env, 5 * 24 * 3600 + 1);
+    let phase = client.advance_phase(&supporter, &id);
+    assert!(matches!(phase, ProposalPhase::Timelock));
+    id
+}
+
+fn reveal_veto(env: &Env, client: &GovernorContractClient, vetoer: &Address, id: u64) {
+    let salt = BytesN::from_array(env, &[7u8; 32]);
+    client.reveal_vote(vetoer, &id, &VoteChoice::Veto, &salt);
+}
+
+#[test]
+fn test_veto_threshold_static_without_slope() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let id = proposal_in_voting(&env, &contract_id, &client);
+    assert_eq!(client.get_veto_margin_slope(), 0);
+
+    let supporter = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &supporter, 90_000); // power 300
+    commit_and_reveal(&env, &client, &supporter, id, VoteChoice::For);
+
+    // 33 % of 1 000 regardless of the unanimous margin.
+    assert_eq!(client.computed_veto_threshold(&id), 330);
+    assert_eq!(client.computed_veto_threshold(&999), 0);
+
+    let admin = client.get_admin();
+    let result = client.try_set_veto_margin_slope(&admin, &10_001);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+    let outsider = Address::generate(&env);
+    let result = client.try_set_veto_margin_slope(&outsider, &1_000);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_narrow_pass_rejected_by_veto() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let vetoer = Address::generate(&env);
+    // 200 For vs 100 Against: margin 33.33 % → 33 % + 16.66 % of supply.
+    let id = passed_with_pending_veto(&env, &contract_id, &client, 40_000, 10_000, &vetoer);
+    assert_eq!(client.computed_veto_threshold(&id), 496);
+
+    reveal_veto(&env, &client, &vetoer, id);
+    let phase = client.advance_phase(&vetoer, &id);
+    assert!(matches!(phase, ProposalPhase::Rejected));
+}
+
+#[test]
+fn test_landslide_pass_needs_more_veto_weight() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let vetoer = Address::generate(&env);
+    // 300 For vs 0 Against: margin 100 % → 33 % + 50 % of supply.
+    let id = passed_with_pending_veto(&env, &contract_id, &client, 90_000, 0, &vetoer);
+    assert_eq!(client.computed_veto_threshold(&id), 830);
+
+    // The same 500 veto weight that sinks a narrow pass is not enough here.
+    reveal_veto(&env, &client, &vetoer, id);
+    let result = client.try_advance_phase(&vetoer, &id);
+    assert_eq!(result, Err(Ok(ContractError::TimelockNotExpired)));
+
+    advance_time(&env, 7 * 24 * 3600);
+    let phase = client.advance_phase(&vetoer, &id);
+    assert!(matches!(phase, ProposalPhase::Execution));
+}
+
+// ── Commitment binding and salt reuse ─────────────────────────────────────────
+
+/// Create a ParameterChange proposal on an initialized governor and move it
+/// into the Voting phase.
+fn open_voting_proposal(env: &Env, contract_id: &Address, client: &GovernorContractClient) -> u64 {
+    let proposer = Address::generate(env);
+    set_mock_stake(env, contract_id, &proposer, 10_000);
+    let target = Address::generate(env);
+    let id = client.create_proposal(
+        &proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(env, "Salt test"),
+        &single_action(env, &target),
+        &None,
+    );
+    client.advance_phase(&proposer, &id);
+    advance_time(env, 3 * 24 * 3600 + 1);
+    client.advance_phase(&proposer, &id);
+    id
+}
+
+#[test]
+fn test_commitment_depends_on_voter() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let id = proposal_in_voting(&env, &contract_id, &client);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &alice, 10_000);
+    set_mock_stake(&env, &contract_id, &bob, 10_000);
+    let salt = BytesN::from_array(&env, &[5u8; 32]);
+    let choice = VoteChoice::For;
+
+    let for_alice = GovernorContract::hash_commitment(&env, id, &alice, &choice, &salt);
+    let for_bob = GovernorContract::hash_commitment(&env, id, &bob, &choice, &salt);
+    assert_ne!(for_alice, for_bob);
+    assert_eq!(
+        for_alice,
+        compute_commitment(&env, id, &alice, &choice, &salt)
+    );
+
+    // Copying another voter's commitment does not let Bob reveal it.
+    client.commit_vote(&alice, &id, &for_alice);
+    client.commit_vote(&bob, &id, &for_alice);
+    client.reveal_vote(&alice, &id, &choice, &salt);
+    let result = client.try_reveal_vote(&bob, &id, &choice, &salt);
+    assert_eq!(result, Err(Ok(ContractError::CommitmentMismatch)));
+}
+
+#[test]
+fn test_salt_reuse_rejected_when_enabled() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (admin, _, _) = default_init(&env, &client);
+    assert!(!client.get_salt_reuse_check());
+    client.set_salt_reuse_check(&admin, &true);
+    assert!(client.get_salt_reuse_check());
+
+    let first = open_voting_proposal(&env, &contract_id, &client);
+    let second = open_voting_proposal(&env, &contract_id, &client);
+    let voter = Address::generate(&env);
+    let other = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &voter, 10_000);
+    set_mock_stake(&env, &contract_id, &other, 10_000);
+
+    let salt = BytesN::from_array(&env, &[3u8; 32]);
+    let choice = VoteChoice::For;
+    for id in [first, second] {
+        let commitment = compute_commitment(&env, id, &voter, &choice, &salt);
+        client.commit_vote(&voter, &id, &commitment);
+    }
+    client.reveal_vote(&voter, &first, &choice, &salt);
+    let result = client.try_reveal_vote(&voter, &second, &choice, &salt);
+    assert_eq!(result, Err(Ok(ContractError::SaltReused)));
+
+    // The same salt is still fresh for a different voter.
+    let commitment = compute_commitment(&env, second, &other, &choice, &salt);
+    client.commit_vote(&other, &second, &commitment);
+    assert!(client.reveal_vote(&other, &second, &choice, &salt) > 0);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_set_salt_reuse_check(&outsider, &false);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+// ── Stored governance thresholds ──────────────────────────────────────────────
+
+/// Commit and reveal a FOR vote from a fresh voter with `stake` tokens.
+fn vote_for(
+    env: &Env,
+    contract_id: &Address,
+    client: &GovernorContractClient,
+    id: u64,
+    stake: i128,
+) {
+    let voter = Address::generate(env);
+    set_mock_stake(env, contract_id, &voter, stake);
+    let salt = BytesN::from_array(env, &[9u8; 32]);
+    let commitment = compute_commitment(env, id, &voter, &VoteChoice::For, &salt);
+    client.commit_vote(&voter, &id, &commitment);
+    client.reveal_vote(&voter, &id, &VoteChoice::For, &salt);
+}
+
+#[test]
+fn test_lowered_quorum_lets_proposal_reach_timelock() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let admin = Address::generate(&env);
+    let staking = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.initialize(&admin, &staking, &treasury, &1_000i128);
+    let proposer = Address::generate(&env);
+
+    // 100 vote power is below the default 15 % quorum of 1 000.
+    let first = open_voting_proposal(&env, &contract_id, &client);
+    vote_for(&env, &contract_id, &client, first, 10_000);
+    advance_time(&env, 5 * 24 * 3600 + 1);
+    let phase = client.advance_phase(&proposer, &first);
+    assert!(matches!(phase, ProposalPhase::Expired));
+
+    client.set_quorum_bps(&admin, &ProposalType::ParameterChange, &500);
+    assert_eq!(client.get_quorum_bps(&ProposalType::ParameterChange), 500);
+    assert_eq!(client.get_quorum_bps(&ProposalType::ContractUpgrade), 3_000);
+
+    let second = open_voting_proposal(&env, &contract_id, &client);
+    vote_for(&env, &contract_id, &client, second, 10_000);
+    advance_time(&env, 5 * 24 * 3600 + 1);
+    let phase = client.advance_phase(&proposer, &second);
+    assert!(matches!(phase, ProposalPhase::Timelock));
+
+    // A stored veto threshold is read when the timelock is advanced.
+    assert_eq!(client.computed_veto_threshold(&second), 330);
+    client.set_veto_threshold_bps(&admin, &ProposalType::ParameterChange, &100);
+    assert_eq!(client.computed_veto_threshold(&second), 10);
+    assert_eq!(
+        client.try_advance_phase(&proposer, &second),
+        Err(Ok(ContractError::TimelockNotExpired))
+    );
+}
+
+#[test]
+fn test_threshold_overrides_are_validated() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (_, client) = register_governor(&env);
+    let (admin, _, _) = default_init(&env, &client);
+    let kind = ProposalType::TreasurySpend;
+
+    assert_eq!(client.get_pass_threshold_bps(&kind), 5_100);
+    assert_eq!(client.get_veto_threshold_bps(&kind), 3_300);
+    client.set_pass_threshold_bps(&admin, &kind, &10_000);
+    client.set_veto_threshold_bps(&admin, &kind, &2_500);
+    assert_eq!(client.get_pass_threshold_bps(&kind), 10_000);
+    assert_eq!(client.get_veto_threshold_bps(&kind), 2_500);
+
+    assert_eq!(
+        client.try_set_quorum_bps(&admin, &kind, &10_001),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_pass_threshold_bps(&admin, &kind, &10_001),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_veto_threshold_bps(&admin, &kind, &10_001),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    // A 0 veto threshold would reject every proposal reaching its timelock.
+    assert_eq!(
+        client.try_set_veto_threshold_bps(&admin, &kind, &0),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(client.get_veto_threshold_bps(&kind), 2_500);
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_set_quorum_bps(&outsider, &kind, &100),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.get_quorum_bps(&kind), 1_000);
+}
+
+// ── Custom proposal types ─────────────────────────────────────────────────────
+
+fn clinical_protocol_config() -> ProposalTypeConfig {
+    ProposalTypeConfig {
+        quorum_bps: 500,
+        pass_threshold_bps: 6_600,
+        veto_threshold_bps: 500,
+        timelock_duration: 7 * 24 * 3600,
+    }
+}
+
+#[test]
+fn test_custom_proposal_type_uses_registered_thresholds() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let admin = Address::generate(&env);
+    let staking = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.initialize(&admin, &staking, &treasury, &1_000i128);
+
+    let name = Symbol::new(&env, "ClinicalProtocolChange");
+    assert_eq!(client.get_proposal_type_config(&name), None);
+    client.register_proposal_type(&admin, &name, &clinical_protocol_config());
+    assert_eq!(
+        client.get_proposal_type_config(&name),
+        Some(clinical_protocol_config())
+    );
+
+    let proposer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &proposer, 10_000);
+    let target = Address::generate(&env);
+    let id = client.create_proposal(
+        &proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(&env, "Update dilation protocol"),
+        &single_action(&env, &target),
+        &Some(name.clone()),
+    );
+    let proposal = client.get_proposal(&id).unwrap();
+    assert_eq!(proposal.custom_type, Some(name));
+    assert_eq!(
+        proposal.timelock_ends,
+        proposal.voting_ends + clinical_protocol_config().timelock_duration
+    );
+
+    client.advance_phase(&proposer, &id);
+    advance_time(&env, 3 * 24 * 3600 + 1);
+    client.advance_phase(&proposer, &id);
+
+    // 100 vote power clears the registered 5 % quorum but not the default
+    // 15 % for ParameterChange.
+    vote_for(&env, &contract_id, &client, id, 10_000);
+    advance_time(&env, 5 * 24 * 3600 + 1);
+    let phase = client.advance_phase(&proposer, &id);
+    assert!(matches!(phase, ProposalPhase::Timelock));
+
+    // The registered 5 % veto threshold replaces the default 33 %.
+    assert_eq!(client.computed_veto_threshold(&id), 50);
+}
+
+#[test]
+fn test_custom_proposal_type_must_be_registered_by_admin() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (admin, _, _) = default_init(&env, &client);
+    let name = Symbol::new(&env, "ClinicalProtocolChange");
+
+    let proposer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &proposer, 10_000);
+    let target = Address::generate(&env);
+    assert_eq!(
+        client.try_create_proposal(
+            &proposer,
+            &ProposalType::ParameterChange,
+            &String::from_str(&env, "Unregistered"),
+            &single_action(&env, &target),
+            &Some(name.clone()),
+        ),
+        Err(Ok(ContractError::UnknownProposalType))
+    );
+
+    assert_eq!(
+        client.try_register_proposal_type(&proposer, &name, &clinical_protocol_config()),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    let mut invalid = clinical_protocol_config();
+    invalid.pass_threshold_bps = 10_001;
+    assert_eq!(
+        client.try_register_proposal_type(&admin, &name, &invalid),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    let mut invalid = clinical_protocol_config();
+    invalid.veto_threshold_bps = 0;
+    assert_eq!(
+        client.try_register_proposal_type(&admin, &name, &invalid),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(client.get_proposal_type_config(&name), None);
+}
+
+// ── Cancellation ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_proposer_can_cancel_before_voting() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    default_init(&env, &client);
+
+    let proposer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &proposer, 1_000);
+    let target = Address::generate(&env);
+    let draft = client.create_proposal(
+        &proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(&env, "Withdrawn in draft"),
+        &single_action(&env, &target),
+        &None,
+    );
+    let discussed = client.create_proposal(
+        &proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(&env, "Withdrawn in discussion"),
+        &single_action(&env, &target),
+        &None,
+    );
+    client.advance_phase(&proposer, &discussed);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_proposal(&outsider, &draft),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    for id in [draft, discussed] {
+        client.cancel_proposal(&proposer, &id);
+        let proposal = client.get_proposal(&id).unwrap();
+        assert_eq!(proposal.phase, ProposalPhase::Cancelled);
+        // A cancelled proposal is terminal.
+        assert_eq!(
+            client.try_advance_phase(&proposer, &id),
+            Err(Ok(ContractError::WrongPhase))
+        );
+        assert_eq!(
+            client.try_cancel_proposal(&proposer, &id),
+            Err(Ok(ContractError::WrongPhase))
+        );
+    }
+}
+
+#[test]
+fn test_cannot_cancel_once_voting_started() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let id = proposal_in_voting(&env, &contract_id, &client);
+    let proposer = client.get_proposal(&id).unwrap().proposer;
+
+    assert_eq!(
+        client.try_cancel_proposal(&proposer, &id),
+        Err(Ok(ContractError::WrongPhase))
+    );
+    assert_eq!(
+        client.get_proposal(&id).unwrap().phase,
+        ProposalPhase::Voting
+    );
+}
+
+// ── Treasury spend window ─────────────────────────────────────────────────────
+
+fn spend_to(env: &Env, recipient: &Address, amount: i128) -> Vec<TreasuryTransfer> {
+    let mut transfers = Vec::new(env);
+    transfers.push_back(TreasuryTransfer {
+        recipient: recipient.clone(),
+        amount,
+    });
+    transfers
+}
+
+/// Initialise against a 1 000-token supply and drive one TreasurySpend
+/// proposal per amount into the Execution phase together.
+fn spends_in_execution(
+    env: &Env,
+    contract_id: &Address,
+    client: &GovernorContractClient,
+    amounts: &[i128],
+) -> (Address, std::vec::Vec<u64>) {
+    let admin = Address::generate(env);
+    let staking = Address::generate(env);
+    client.initialize(&admin, &staking, &Address::generate(env), &1_000i128);
+
+    let proposer = Address::generate(env);
+    set_mock_stake(env, contract_id, &proposer, 10_000);
+    let treasury = Address::generate(env);
+    let ids: std::vec::Vec<u64> = amounts
+        .iter()
+        .map(|amount| {
+            let id = client.create_treasury_spend_proposal(
+                &proposer,
+                &String::from_str(env, "Fund screening programme"),
+                &treasury,
+                &spend_to(env, &Address::generate(env), *amount),
+            );
+            client.advance_phase(&proposer, &id);
+            id
+        })
+        .collect();
+
+    advance_time(env, 3 * 24 * 3600 + 1);
+    for id in &ids {
+        client.advance_phase(&proposer, id);
+        vote_for(env, contract_id, client, *id, 10_000);
+    }
+    advance_time(env, 5 * 24 * 3600 + 1);
+    for id in &ids {
+        client.advance_phase(&proposer, id);
+    }
+    advance_time(env, 2 * 24 * 3600 + 1);
+    for id in &ids {
+        assert_eq!(
+            client.advance_phase(&proposer, id),
+            ProposalPhase::Execution
+        );
+    }
+    (admin, ids)
+}
+
+#[test]
+fn test_treasury_spend_blocked_until_window_refills() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (admin, ids) = spends_in_execution(&env, &contract_id, &client, &[400, 500, 200]);
+    let executor = Address::generate(&env);
+
+    client.set_treasury_spend_limit(&admin, &1_000, &(30 * 24 * 3600));
+    client.execute_proposal(&executor, &ids[0]);
+    client.execute_proposal(&executor, &ids[1]);
+    let budget = client.get_treasury_spend_budget().unwrap();
+    assert_eq!((budget.spent, budget.remaining), (900, 100));
+
+    // 200 does not fit in the 100 left; the proposal stays executable.
+    assert_eq!(
+        client.try_execute_proposal(&executor, &ids[2]),
+        Err(Ok(ContractError::SpendWindowExceeded))
+    );
+    assert_eq!(
+        client.get_proposal(&ids[2]).unwrap().phase,
+        ProposalPhase::Execution
+    );
+    assert_eq!(client.get_treasury_spend_budget().unwrap().remaining, 100);
+
+    env.ledger().with_mut(|l| l.timestamp = budget.window_ends);
+    assert_eq!(client.get_treasury_spend_budget().unwrap().remaining, 1_000);
+    client.execute_proposal(&executor, &ids[2]);
+    assert_eq!(
+        client.get_proposal(&ids[2]).unwrap().phase,
+        ProposalPhase::Completed
+    );
+    let budget = client.get_treasury_spend_budget().unwrap();
+    assert_eq!((budget.spent, budget.remaining), (200, 800));
+}
+
+#[test]
+fn test_treasury_spend_limit_configuration() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    let (admin, ids) = spends_in_execution(&env, &contract_id, &client, &[5_000]);
+
+    // Without a configured limit spends are not rate limited.
+    assert_eq!(client.get_treasury_spend_budget(), None);
+    client.execute_proposal(&admin, &ids[0]);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_set_treasury_spend_limit(&outsider, &1_000, &3600),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    for (max, window) in [(0, 3600), (1_000, 0)] {
+        assert_eq!(
+            client.try_set_treasury_spend_limit(&admin, &max, &window),
+            Err(Ok(ContractError::InvalidInput))
+        );
+    }
+
+    // TreasurySpend actions are built by the governor, never supplied.
+    let proposer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &proposer, 10_000);
+    let treasury = Address::generate(&env);
+    let actions = single_action(&env, &treasury);
+    let title = String::from_str(&env, "Unpriced spend");
+    assert_eq!(
+        client.try_create_proposal(
+            &proposer,
+            &ProposalType::TreasurySpend,
+            &title,
+            &actions,
+            &None
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    // Every transfer must carry a positive amount, and there must be one.
+    let recipient = Address::generate(&env);
+    for transfers in [
+        spend_to(&env, &recipient, 0),
+        spend_to(&env, &recipient, -5),
+        Vec::new(&env),
+    ] {
+        assert_eq!(
+            client.try_create_treasury_spend_proposal(&proposer, &title, &treasury, &transfers),
+            Err(Ok(ContractError::InvalidInput))
+        );
+    }
+    let mut overflow = spend_to(&env, &recipient, i128::MAX);
+    overflow.push_back(TreasuryTransfer {
+        recipient: recipient.clone(),
+        amount: 1,
+    });
+    assert_eq!(
+        client.try_create_treasury_spend_proposal(&proposer, &title, &treasury, &overflow),
+        Err(Ok(ContractError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_treasury_spend_amount_is_derived_from_actions() {
+    let env = create_env();
+    env.mock_all_auths();
+    let (contract_id, client) = register_governor(&env);
+    default_init(&env, &client);
+    let proposer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &proposer, 10_000);
+
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let mut transfers = spend_to(&env, &alice, 300);
+    transfers.push_back(TreasuryTransfer {
+        recipient: bob.clone(),
+        amount: 450,
+    });
+    let id = client.create_treasury_spend_proposal(
+        &proposer,
+        &String::from_str(&env, "Clinic grants"),
+        &treasury,
+        &transfers,
+    );
+
+    let proposal = client.get_proposal(&id).unwrap();
+    assert_eq!(proposal.proposal_type, ProposalType::TreasurySpend);
+    assert_eq!(proposal.spend_amount, 750);
+    assert_eq!(proposal.actions.len(), 2);
+    for (action, (to, amount)) in proposal.actions.iter().zip([(alice, 300), (bob, 450)]) {
+        assert_eq!(action.target, treasury);
+        assert_eq!(action.function, execution::FN_SPEND);
+        assert_eq!(
+            action.params_hash,
+            env.as_contract(&contract_id, || execution::spend_params_hash(
+                &env, &to, amount
+            ))
+        );
+    }
+}
@@ -8,12 +8,13 @@
 //! ```
 //! All values are integer-scaled by SCALE (1 000) to avoid floating point.
 
-use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
 
 // ── Storage key prefixes ─────────────────────────────────────────────────────
 
 const COMMIT: Symbol = symbol_short!("COMMIT");
 const VOTED: Symbol = symbol_short!("VOTED");
+const SALT_USED: Symbol = symbol_short!("SALT_USED");
 
 // TTL: ~30 days
 const TTL_THRESHOLD: u32 = 518_400;
@@ -25,6 +26,8 @@ pub const SCALE: i128 = 1_000;
 pub const SECS_PER_DAY: u64 = 86_400;
 /// Maximum loyalty bonus: 100 % (doubles raw power after 1 year).
 pub const MAX_LOYALTY_DAYS: u64 = 365;
+/// Upper bound on the loyalty multiplier (2×, scaled by SCALE).
+pub const MAX_LOYALTY_SCALED: i128 = 2 * SCALE;
 
 // ── Types ─────────────────────────────────────────────────────────────────────
 
@@ -34,6 +37,9 @@ pub enum VoteChoice {
     For,
     Against,
     Veto,
+    /// Registers participation (counts toward quorum and `reveal_count`)
+    /// without backing either side.
+    Abstain,
 }
 
 /// A committed (unrevealed) vote.
@@ -99,6 +105,28 @@ pub(crate) fn has_committed(env: &Env, proposal_id: u64, voter: &Address) -> boo
         .has(&commit_key(proposal_id, voter))
 }
 
+/// `SHA-256(voter_xdr || salt)`, the key under which a revealed salt is
+/// remembered without storing the salt itself.
+pub(crate) fn salt_hash(env: &Env, voter: &Address, salt: &BytesN<32>) -> BytesN<32> {
+    let mut data: Bytes = voter.clone().to_xdr(env);
+    data.append(&Bytes::from_array(env, &salt.to_array()));
+    env.crypto().sha256(&data).into()
+}
+
+pub(crate) fn is_salt_used(env: &Env, salt_hash: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&(SALT_USED, salt_hash.clone()))
+}
+
+pub(crate) fn mark_salt_used(env: &Env, salt_hash: &BytesN<32>) {
+    let key = (SALT_USED, salt_hash.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
 // ── Vote power computation ────────────────────────────────────────────────────
 
 /// Integer square-root via Newton's method (no_std compatible).
@@ -131,7 +159,8 @@ pub fn isqrt(n: i128) -> i128 {
 
 /// Compute the loyalty multiplier (scaled by SCALE).
 ///
-/// Multiplier grows linearly from 1× to 2× over `MAX_LOYALTY_DAYS` days.
+/// Multiplier grows linearly from 1× to 2× over `MAX_LOYALTY_DAYS` days and
+/// never exceeds `MAX_LOYALTY_SCALED`.
 ///
 /// ```text
 /// loyalty_scaled = SCALE + min(stake_age_days, 365) * SCALE / 365
@@ -139,7 +168,7 @@ pub fn isqrt(n: i128) -> i128 {
 pub fn loyalty_multiplier_scaled(stake_age_secs: u64) -> i128 {
     let days = (stake_age_secs / SECS_PER_DAY) as i128;
     let capped = days.min(MAX_LOYALTY_DAYS as i128);
-    SCALE + capped * SCALE / (MAX_LOYALTY_DAYS as i128)
+    (SCALE + capped * SCALE / (MAX_LOYALTY_DAYS as i128)).min(MAX_LOYALTY_SCALED)
 }
 
 /// Compute final vote power for a voter.
@@ -150,9 +179,15 @@ pub fn loyalty_multiplier_scaled(stake_age_secs: u64) -> i128 {
 /// Returns an integer scaled by SCALE² to preserve precision through
 /// multiplication; callers should use this value directly for tallying
 /// as long as they compare apples-to-apples.
+///
+/// Both inputs come from the staking contract and are not trusted: a
+/// negative `staked` counts as zero, and the result saturates rather than
+/// overflowing, so any input yields a non-negative power that is
+/// non-decreasing in `staked`.
 pub fn compute_vote_power(staked: i128, stake_age_secs: u64) -> i128 {
-    let raw = isqrt(staked); // sqrt(tokens)
+    let raw = isqrt(staked.max(0)); // sqrt(tokens)
     let loyalty = loyalty_multiplier_scaled(stake_age_secs); // SCALE-scaled
-                                                             // raw × loyalty / SCALE  — keeps result in "SCALE-scaled sqrt-token" units
+
+    // raw × loyalty / SCALE — keeps result in "SCALE-scaled sqrt-token" units
     raw.saturating_mul(loyalty) / SCALE
 }
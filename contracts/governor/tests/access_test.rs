@@ -82,6 +82,7 @@ fn test_cancel_proposal_unauthorized() {
         &String::from_str(&env, "Description"),
         &targets,
         &call_data,
+        &None,
     );
 
     let result = client.try_cancel_proposal(&stranger, &proposal_id);
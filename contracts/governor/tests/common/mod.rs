@@ -0,0 +1,71 @@
+//! Shared helpers for governor integration tests.
+//!
+//! The governor queries vote power from its staking contract, so tests
+//! register [`MockStaking`] as that contract and seed balances and stake ages
+//! through it.
+
+#![allow(dead_code)]
+
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+
+const STAKING_CONTRACT: Symbol = symbol_short!("STK_CTR");
+
+/// Minimal stand-in for the staking contract's `get_staked` and
+/// `get_stake_age` views.
+#[contract]
+pub struct MockStaking;
+
+#[contractimpl]
+impl MockStaking {
+    pub fn set_staked(env: Env, staker: Address, amount: i128) {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("STAKED"), staker), &amount);
+    }
+
+    pub fn set_stake_age(env: Env, staker: Address, age_secs: u64) {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("AGE"), staker), &age_secs);
+    }
+
+    pub fn get_staked(env: Env, staker: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("STAKED"), staker))
+            .unwrap_or(0)
+    }
+
+    pub fn get_stake_age(env: Env, staker: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("AGE"), staker))
+            .unwrap_or(0)
+    }
+}
+
+/// Register a fresh [`MockStaking`] and return its address, for passing to
+/// `initialize` as the staking contract.
+pub fn register_mock_staking(env: &Env) -> Address {
+    env.register(MockStaking, ())
+}
+
+/// The staking contract the governor at `governor_id` was initialized with.
+fn staking_of(env: &Env, governor_id: &Address) -> Address {
+    env.as_contract(governor_id, || {
+        env.storage()
+            .instance()
+            .get(&STAKING_CONTRACT)
+            .expect("governor not initialized")
+    })
+}
+
+/// Seed `voter`'s staked balance in the governor's staking contract.
+pub fn set_mock_stake(env: &Env, governor_id: &Address, voter: &Address, amount: i128) {
+    MockStakingClient::new(env, &staking_of(env, governor_id)).set_staked(voter, &amount);
+}
+
+/// Seed `voter`'s stake age in the governor's staking contract.
+pub fn set_mock_age(env: &Env, governor_id: &Address, voter: &Address, age_secs: u64) {
+    MockStakingClient::new(env, &staking_of(env, governor_id)).set_stake_age(voter, &age_secs);
+}
@@ -13,6 +13,9 @@
 
 extern crate std;
 
+mod common;
+
+use common::{register_mock_staking, set_mock_age, set_mock_stake};
 use governor::{
     proposal::{ProposalAction, ProposalPhase, ProposalType},
     ContractError, GovernorContract, GovernorContractClient,
@@ -33,7 +36,7 @@ fn setup() -> (Env, Address, GovernorContractClient<'static>, Address) {
     let client = GovernorContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    let staking = Address::generate(&env);
+    let staking = register_mock_staking(&env);
     let treasury = Address::generate(&env);
     // Use a small total supply so quadratic vote power can meet quorum.
     client.initialize(&admin, &staking, &treasury, &100i128);
@@ -41,40 +44,27 @@ fn setup() -> (Env, Address, GovernorContractClient<'static>, Address) {
     (env, contract_id, client, admin)
 }
 
-fn set_mock_stake(env: &Env, contract_id: &Address, voter: &Address, amount: i128) {
-    env.as_contract(contract_id, || {
-        env.storage()
-            .persistent()
-            .set(&(symbol_short!("M_STK"), voter.clone()), &amount);
-    });
-}
-
-fn set_mock_age(env: &Env, contract_id: &Address, voter: &Address, age_secs: u64) {
-    env.as_contract(contract_id, || {
-        env.storage()
-            .persistent()
-            .set(&(symbol_short!("M_AGE"), voter.clone()), &age_secs);
-    });
-}
-
 fn advance_time(env: &Env, secs: u64) {
     env.ledger().with_mut(|l| {
         l.timestamp = l.timestamp.saturating_add(secs);
     });
 }
 
-/// Replicate the governor's commitment hash: SHA-256(proposal_id_le || choice_byte || salt).
+/// Replicate the governor's commitment hash:
+/// SHA-256(proposal_id_le || voter_xdr || choice_byte || salt).
 fn compute_commitment(
     env: &Env,
     proposal_id: u64,
+    voter: &Address,
     choice_byte: u8,
     salt: &BytesN<32>,
 ) -> BytesN<32> {
-    use soroban_sdk::Bytes;
+    use soroban_sdk::{xdr::ToXdr, Bytes};
     let mut data = Bytes::new(env);
     for b in proposal_id.to_le_bytes().iter() {
         data.push_back(*b);
     }
+    data.append(&voter.clone().to_xdr(env));
     data.push_back(choice_byte);
     for i in 0..32u32 {
         data.push_back(salt.get(i).unwrap_or(0));
@@ -107,8 +97,8 @@ fn drive_to_execution(
     // Commit + reveal For votes
     let salt_a = BytesN::from_array(env, &[0xAA; 32]);
     let salt_b = BytesN::from_array(env, &[0xBB; 32]);
-    let commit_a = compute_commitment(env, proposal_id, 0u8, &salt_a);
-    let commit_b = compute_commitment(env, proposal_id, 0u8, &salt_b);
+    let commit_a = compute_commitment(env, proposal_id, &voter_a, 0u8, &salt_a);
+    let commit_b = compute_commitment(env, proposal_id, &voter_b, 0u8, &salt_b);
 
     client.commit_vote(&voter_a, &proposal_id, &commit_a);
     client.commit_vote(&voter_b, &proposal_id, &commit_b);
@@ -165,6 +155,7 @@ fn test_execute_dispatches_single_action_event() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Single action dispatch"),
         &actions,
+        &None,
     );
 
     drive_to_execution(&env, &contract_id, &client, id, &proposer);
@@ -202,6 +193,7 @@ fn test_execute_dispatches_batched_actions() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Batched dispatch"),
         &actions,
+        &None,
     );
 
     drive_to_execution(&env, &contract_id, &client, id, &proposer);
@@ -240,6 +232,7 @@ fn test_execute_rejects_wrong_phase() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Not yet executable"),
         &actions,
+        &None,
     );
 
     let result = client.try_execute_proposal(&proposer, &id);
@@ -278,6 +271,7 @@ fn test_execute_moves_to_completed_only_once() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Execute once"),
         &actions,
+        &None,
     );
 
     drive_to_execution(&env, &contract_id, &client, id, &proposer);
@@ -315,6 +309,7 @@ fn test_emergency_proposal_cross_contract_dispatch() {
         &ProposalType::EmergencyAction,
         &String::from_str(&env, "Emergency dispatch"),
         &actions,
+        &None,
     );
 
     // Set up voters
@@ -333,8 +328,8 @@ fn test_emergency_proposal_cross_contract_dispatch() {
     // Commit + reveal
     let salt_a = BytesN::from_array(&env, &[0xAA; 32]);
     let salt_b = BytesN::from_array(&env, &[0xBB; 32]);
-    let commit_a = compute_commitment(&env, id, 0, &salt_a);
-    let commit_b = compute_commitment(&env, id, 0, &salt_b);
+    let commit_a = compute_commitment(&env, id, &voter_a, 0, &salt_a);
+    let commit_b = compute_commitment(&env, id, &voter_b, 0, &salt_b);
     client.commit_vote(&voter_a, &id, &commit_a);
     client.commit_vote(&voter_b, &id, &commit_b);
     client.reveal_vote(&voter_a, &id, &governor::voting::VoteChoice::For, &salt_a);
@@ -381,6 +376,7 @@ fn test_execute_proposal_emits_execution_event() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Execution event test"),
         &actions,
+        &None,
     );
 
     drive_to_execution(&env, &contract_id, &client, id, &proposer);
@@ -415,6 +411,7 @@ fn test_execute_rejects_discussion_phase() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Discussion phase"),
         &actions,
+        &None,
     );
 
     // Move to Discussion only
@@ -450,6 +447,7 @@ fn test_execute_is_permissionless() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Permissionless execution"),
         &actions,
+        &None,
     );
 
     drive_to_execution(&env, &contract_id, &client, id, &proposer);
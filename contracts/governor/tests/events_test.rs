@@ -2,6 +2,9 @@
 
 extern crate std;
 
+mod common;
+
+use common::{register_mock_staking, set_mock_age, set_mock_stake};
 use governor::{
     proposal::{ProposalAction, ProposalPhase, ProposalType},
     voting::VoteChoice,
@@ -28,47 +31,34 @@ fn setup() -> (
     let client = GovernorContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    let staking = Address::generate(&env);
+    let staking = register_mock_staking(&env);
     let treasury = Address::generate(&env);
     client.initialize(&admin, &staking, &treasury, &100i128);
 
     (env, contract_id, client, admin, staking, treasury)
 }
 
-fn set_mock_stake(env: &Env, contract_id: &Address, voter: &Address, amount: i128) {
-    env.as_contract(contract_id, || {
-        env.storage()
-            .persistent()
-            .set(&(symbol_short!("M_STK"), voter.clone()), &amount);
-    });
-}
-
-fn set_mock_age(env: &Env, contract_id: &Address, voter: &Address, age_secs: u64) {
-    env.as_contract(contract_id, || {
-        env.storage()
-            .persistent()
-            .set(&(symbol_short!("M_AGE"), voter.clone()), &age_secs);
-    });
-}
-
 fn advance_time(env: &Env, secs: u64) {
     env.ledger().with_mut(|l| {
         l.timestamp = l.timestamp.saturating_add(secs);
     });
 }
 
-/// Replicate the governor's commitment hash: SHA-256(proposal_id_le || choice_byte || salt).
+/// Replicate the governor's commitment hash:
+/// SHA-256(proposal_id_le || voter_xdr || choice_byte || salt).
 fn compute_commitment(
     env: &Env,
     proposal_id: u64,
+    voter: &Address,
     choice_byte: u8,
     salt: &BytesN<32>,
 ) -> BytesN<32> {
-    use soroban_sdk::Bytes;
+    use soroban_sdk::{xdr::ToXdr, Bytes};
     let mut data = Bytes::new(env);
     for b in proposal_id.to_le_bytes().iter() {
         data.push_back(*b);
     }
+    data.append(&voter.clone().to_xdr(env));
     data.push_back(choice_byte);
     for i in 0..32u32 {
         data.push_back(salt.get(i).unwrap_or(0));
@@ -98,6 +88,7 @@ fn test_proposal_created_event() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Create event"),
         &actions,
+        &None,
     );
 
     let binding = env.events().all();
@@ -137,6 +128,7 @@ fn test_phase_transition_event() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Phase event"),
         &actions,
+        &None,
     );
 
     // Advance Draft -> Discussion
@@ -156,6 +148,46 @@ fn test_phase_transition_event() {
     assert!(found, "PROP_PHS event not found");
 }
 
+#[test]
+fn test_proposal_cancelled_event() {
+    let (env, contract_id, client, _admin, _staking, _treasury) = setup();
+
+    let proposer = Address::generate(&env);
+    set_mock_stake(&env, &contract_id, &proposer, 10_000);
+
+    let target = Address::generate(&env);
+    let actions = vec![
+        &env,
+        ProposalAction {
+            target: target.clone(),
+            function: symbol_short!("GOV_PRM"),
+            params_hash: BytesN::from_array(&env, &[0u8; 32]),
+        },
+    ];
+
+    let id = client.create_proposal(
+        &proposer,
+        &ProposalType::ParameterChange,
+        &String::from_str(&env, "Cancel event"),
+        &actions,
+        &None,
+    );
+    client.cancel_proposal(&proposer, &id);
+
+    let binding = env.events().all();
+    let all = binding.events();
+    let mut found = false;
+    for i in 0..all.len() {
+        let e = all.get(i).unwrap();
+        let debug = format!("{:?}", e);
+        if debug.contains("PROP_CAN") {
+            found = true;
+            break;
+        }
+    }
+    assert!(found, "PROP_CAN event not found");
+}
+
 #[test]
 fn test_vote_commit_and_reveal_events() {
     let (env, contract_id, client, _admin, _staking, _treasury) = setup();
@@ -178,6 +210,7 @@ fn test_vote_commit_and_reveal_events() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Vote events"),
         &actions,
+        &None,
     );
 
     // Move to Voting phase
@@ -191,7 +224,7 @@ fn test_vote_commit_and_reveal_events() {
     set_mock_age(&env, &contract_id, &voter, 365 * 86_400);
 
     let salt = BytesN::from_array(&env, &[0xAA; 32]);
-    let commit = compute_commitment(&env, id, 0u8, &salt);
+    let commit = compute_commitment(&env, id, &voter, 0u8, &salt);
     client.commit_vote(&voter, &id, &commit);
 
     // Verify commit event
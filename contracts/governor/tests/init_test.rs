@@ -14,6 +14,9 @@
 
 extern crate std;
 
+mod common;
+
+use common::{register_mock_staking, set_mock_age, set_mock_stake};
 use governor::{
     proposal::{ProposalAction, ProposalType},
     voting::VoteChoice,
@@ -39,7 +42,7 @@ fn setup() -> (Env, Address, GovernorContractClient<'static>, Address) {
     let (env, contract_id, client) = setup_uninit();
 
     let admin = Address::generate(&env);
-    let staking = Address::generate(&env);
+    let staking = register_mock_staking(&env);
     let treasury = Address::generate(&env);
 
     client.initialize(&admin, &staking, &treasury, &1_000_000i128);
@@ -47,22 +50,6 @@ fn setup() -> (Env, Address, GovernorContractClient<'static>, Address) {
     (env, contract_id, client, admin)
 }
 
-fn set_mock_stake(env: &Env, contract_id: &Address, voter: &Address, amount: i128) {
-    env.as_contract(contract_id, || {
-        env.storage()
-            .persistent()
-            .set(&(symbol_short!("M_STK"), voter.clone()), &amount);
-    });
-}
-
-fn set_mock_age(env: &Env, contract_id: &Address, voter: &Address, age_secs: u64) {
-    env.as_contract(contract_id, || {
-        env.storage()
-            .persistent()
-            .set(&(symbol_short!("M_AGE"), voter.clone()), &age_secs);
-    });
-}
-
 fn dummy_action(env: &Env) -> ProposalAction {
     ProposalAction {
         target: Address::generate(env),
@@ -263,6 +250,7 @@ fn create_proposal_before_init_fails() {
         &ProposalType::ParameterChange,
         &title,
         &vec![&env, action],
+        &None,
     );
     assert_eq!(result, Err(Ok(ContractError::NotInitialized)));
 }
@@ -397,6 +385,7 @@ fn create_proposal_works_after_init() {
         &ProposalType::ParameterChange,
         &title,
         &vec![&env, action],
+        &None,
     );
     assert_eq!(id, 1);
 }
@@ -415,6 +404,7 @@ fn proposal_requires_stake_after_init() {
         &ProposalType::ParameterChange,
         &title,
         &vec![&env, action],
+        &None,
     );
     assert_eq!(result, Err(Ok(ContractError::InsufficientStake)));
 }
@@ -434,6 +424,7 @@ fn proposal_with_empty_actions_rejected() {
         &ProposalType::ParameterChange,
         &title,
         &empty_actions,
+        &None,
     );
     assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
 }
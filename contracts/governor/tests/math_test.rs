@@ -2,7 +2,9 @@
 
 extern crate std;
 
-use governor::voting::{compute_vote_power, isqrt, loyalty_multiplier_scaled, SCALE};
+use governor::voting::{
+    compute_vote_power, isqrt, loyalty_multiplier_scaled, MAX_LOYALTY_SCALED, SCALE,
+};
 use governor::GovernorContract;
 use soroban_sdk::{Address, Env};
 
@@ -59,3 +61,60 @@ fn test_compute_vote_power_with_max_values() {
     let got = compute_vote_power(staked, stake_age);
     assert_eq!(got, expected);
 }
+
+/// Staked balances spanning the whole `i128` range, in ascending order.
+fn staked_samples() -> std::vec::Vec<i128> {
+    let mut samples = std::vec![i128::MIN, i128::MIN + 1, -1_000_000, -1, 0, 1, 2, 3];
+    for shift in 2..127 {
+        let p = 1i128 << shift;
+        samples.extend_from_slice(&[p - 1, p, p + 1]);
+    }
+    samples.extend_from_slice(&[i128::MAX - 1, i128::MAX]);
+    samples.dedup();
+    samples
+}
+
+const AGE_SAMPLES: [u64; 7] = [
+    0,
+    86_399,
+    86_400,
+    180 * 86_400,
+    365 * 86_400,
+    365 * 86_400 + 1,
+    u64::MAX,
+];
+
+#[test]
+fn test_loyalty_multiplier_bounded_and_monotonic() {
+    let mut prev = 0;
+    for age in AGE_SAMPLES {
+        let m = loyalty_multiplier_scaled(age);
+        assert!((SCALE..=MAX_LOYALTY_SCALED).contains(&m), "age {age}");
+        assert!(m >= prev, "age {age}");
+        prev = m;
+    }
+}
+
+#[test]
+fn test_compute_vote_power_never_panics_and_is_monotonic_in_staked() {
+    let upper = isqrt(i128::MAX) * MAX_LOYALTY_SCALED / SCALE;
+    for age in AGE_SAMPLES {
+        let mut prev = 0;
+        for staked in staked_samples() {
+            let power = compute_vote_power(staked, age);
+            assert!(power >= 0, "staked {staked}, age {age}");
+            assert!(power <= upper, "staked {staked}, age {age}");
+            assert!(power >= prev, "staked {staked}, age {age}");
+            prev = power;
+        }
+    }
+}
+
+#[test]
+fn test_compute_vote_power_clamps_negative_stake() {
+    for age in AGE_SAMPLES {
+        for staked in [i128::MIN, -1_000_000, -1] {
+            assert_eq!(compute_vote_power(staked, age), 0);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+mod common;
+
+use common::{register_mock_staking, set_mock_age, set_mock_stake};
+use governor::voting::compute_vote_power;
+use governor::{ContractError, GovernorContract, GovernorContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn setup(staking: impl FnOnce(&Env) -> Address) -> (Env, Address, GovernorContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(GovernorContract, ());
+    let client = GovernorContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let staking = staking(&env);
+    let treasury = Address::generate(&env);
+    client.initialize(&admin, &staking, &treasury, &100i128);
+
+    (env, contract_id, client)
+}
+
+#[test]
+fn test_vote_power_is_read_from_staking_contract() {
+    let (env, contract_id, client) = setup(register_mock_staking);
+    let voter = Address::generate(&env);
+
+    // Nothing staked yet.
+    assert_eq!(client.get_vote_power(&voter), 0);
+
+    set_mock_stake(&env, &contract_id, &voter, 1_000_000);
+    set_mock_age(&env, &contract_id, &voter, 90 * 86_400);
+    assert_eq!(
+        client.get_vote_power(&voter),
+        compute_vote_power(1_000_000, 90 * 86_400)
+    );
+}
+
+#[test]
+fn test_unreachable_staking_contract_is_reported() {
+    // An account address has no `get_staked` to call.
+    let (env, _, client) = setup(|env| Address::generate(env));
+    let voter = Address::generate(&env);
+
+    assert_eq!(
+        client.try_get_vote_power(&voter),
+        Err(Ok(ContractError::StakingQueryFailed))
+    );
+}
@@ -2,6 +2,9 @@
 
 extern crate std;
 
+mod common;
+
+use common::{register_mock_staking, set_mock_age, set_mock_stake};
 use governor::proposal::{ProposalAction, ProposalPhase, ProposalType};
 use governor::{GovernorContract, GovernorContractClient};
 use soroban_sdk::{
@@ -18,29 +21,13 @@ fn setup() -> (Env, Address, GovernorContractClient<'static>, Address) {
     let client = GovernorContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
-    let staking = Address::generate(&env);
+    let staking = register_mock_staking(&env);
     let treasury = Address::generate(&env);
     client.initialize(&admin, &staking, &treasury, &100i128);
 
     (env, contract_id, client, admin)
 }
 
-fn set_mock_stake(env: &Env, contract_id: &Address, voter: &Address, amount: i128) {
-    env.as_contract(contract_id, || {
-        env.storage()
-            .persistent()
-            .set(&(symbol_short!("M_STK"), voter.clone()), &amount);
-    });
-}
-
-fn set_mock_age(env: &Env, contract_id: &Address, voter: &Address, age_secs: u64) {
-    env.as_contract(contract_id, || {
-        env.storage()
-            .persistent()
-            .set(&(symbol_short!("M_AGE"), voter.clone()), &age_secs);
-    });
-}
-
 fn advance_time(env: &Env, secs: u64) {
     env.ledger().with_mut(|l| {
         l.timestamp = l.timestamp.saturating_add(secs);
@@ -69,6 +56,7 @@ fn test_discussion_to_voting_requires_time() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Time test"),
         &actions,
+        &None,
     );
 
     // Draft -> Discussion (immediate)
@@ -106,6 +94,7 @@ fn test_timelock_requires_timestamp_to_expire() {
         &ProposalType::ParameterChange,
         &String::from_str(&env, "Timelock test"),
         &actions,
+        &None,
     );
 
     // Move to Discussion -> Voting
@@ -125,11 +114,12 @@ fn test_timelock_requires_timestamp_to_expire() {
     let salt1 = BytesN::from_array(&env, &[0xAA; 32]);
     let salt2 = BytesN::from_array(&env, &[0xBB; 32]);
     let commit1 = {
-        use soroban_sdk::Bytes;
+        use soroban_sdk::{xdr::ToXdr, Bytes};
         let mut data = Bytes::new(&env);
         for b in id.to_le_bytes().iter() {
             data.push_back(*b);
         }
+        data.append(&v1.clone().to_xdr(&env));
         data.push_back(0u8);
         for i in 0..32u32 {
             data.push_back(salt1.get(i).unwrap_or(0));
@@ -137,11 +127,12 @@ fn test_timelock_requires_timestamp_to_expire() {
         env.crypto().sha256(&data).into()
     };
     let commit2 = {
-        use soroban_sdk::Bytes;
+        use soroban_sdk::{xdr::ToXdr, Bytes};
         let mut data = Bytes::new(&env);
         for b in id.to_le_bytes().iter() {
             data.push_back(*b);
         }
+        data.append(&v2.clone().to_xdr(&env));
         data.push_back(0u8);
         for i in 0..32u32 {
             data.push_back(salt2.get(i).unwrap_or(0));
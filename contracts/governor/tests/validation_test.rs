@@ -55,6 +55,7 @@ fn test_create_proposal_empty_title_reverts() {
         &soroban_sdk::String::from_str(&env, "Valid description"),
         &Vec::<Address>::new(&env),
         &Vec::<soroban_sdk::Bytes>::new(&env),
+        &None,
     );
 
     assert_eq!(result, Err(Ok(GovernorError::InvalidInput)));
@@ -72,6 +73,7 @@ fn test_create_proposal_empty_description_reverts() {
         &soroban_sdk::String::from_str(&env, ""), // ← empty description
         &Vec::<Address>::new(&env),
         &Vec::<soroban_sdk::Bytes>::new(&env),
+        &None,
     );
 
     assert_eq!(result, Err(Ok(GovernorError::InvalidInput)));
@@ -89,6 +91,7 @@ fn test_create_proposal_empty_targets_reverts() {
         &soroban_sdk::String::from_str(&env, "Valid description"),
         &Vec::<Address>::new(&env), // ← empty targets
         &Vec::<soroban_sdk::Bytes>::new(&env),
+        &None,
     );
 
     assert_eq!(result, Err(Ok(GovernorError::InvalidInput)));
@@ -110,6 +113,7 @@ fn test_cast_vote_zero_voting_power_reverts() {
         &soroban_sdk::String::from_str(&env, "Desc"),
         &targets,
         &call_data,
+        &None,
     );
 
     let result = client.try_cast_vote(&voter, &proposal_id, &1_u32 /* For */);
@@ -33,9 +33,14 @@ pub fn verify_zk_credential(
     expires_at: u64,
     nonce: u64,
 ) -> Result<bool, CredentialError> {
-    if env.ledger().timestamp() > expires_at {
+    // The credential's own lifetime is enforced here; the verifier only
+    // accepts proof requests that expire within `MAX_PROOF_TTL_SECONDS`, so
+    // long-lived credentials are presented with a clamped request expiry.
+    let now = env.ledger().timestamp();
+    if now > expires_at {
         return Err(CredentialError::CredentialExpired);
     }
+    let request_expires_at = expires_at.min(now.saturating_add(zk_verifier::MAX_PROOF_TTL_SECONDS));
 
     let verifier_id = get_zk_verifier(env).ok_or(CredentialError::VerifierNotSet)?;
     let client = zk_verifier::ZkVerifierContractClient::new(env, &verifier_id);
@@ -81,7 +86,7 @@ pub fn verify_zk_credential(
         resource_id,
         proof,
         public_inputs,
-        expires_at,
+        expires_at: request_expires_at,
         nonce,
     };
 
@@ -6,7 +6,7 @@
 
 #![allow(deprecated)] // events().publish migration tracked separately
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
 
 // ── Event payloads ───────────────────────────────────────────────────────────
 
@@ -39,6 +39,17 @@ pub struct RecoveryInitiatedEvent {
     pub timestamp: u64,
 }
 
+/// Fired when a guardian approves an active recovery.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryApprovedEvent {
+    pub owner: Address,
+    pub guardian: Address,
+    pub approvals: u32,
+    pub threshold: u32,
+    pub timestamp: u64,
+}
+
 /// Fired when a recovery process is executed successfully.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -56,6 +67,16 @@ pub struct RecoveryCancelledEvent {
     pub timestamp: u64,
 }
 
+/// Fired when the owner vetoes a guardian-initiated recovery.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryVetoedEvent {
+    pub owner: Address,
+    pub new_address: Address,
+    pub veto_count: u32,
+    pub timestamp: u64,
+}
+
 /// Fired when a ZK credential is verified.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -110,6 +131,26 @@ pub fn emit_recovery_initiated(
     );
 }
 
+/// Emit a streaming event when a guardian approves recovery.
+pub fn emit_recovery_approved(
+    env: &Env,
+    owner: Address,
+    guardian: Address,
+    approvals: u32,
+    threshold: u32,
+) {
+    env.events().publish(
+        (symbol_short!("STREAM"), symbol_short!("ID_RAPPR")),
+        RecoveryApprovedEvent {
+            owner,
+            guardian,
+            approvals,
+            threshold,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
 /// Emit a streaming event when recovery is executed.
 pub fn emit_recovery_executed(env: &Env, old_address: Address, new_address: Address) {
     env.events().publish(
@@ -133,6 +174,31 @@ pub fn emit_recovery_cancelled(env: &Env, owner: Address) {
     );
 }
 
+/// Emit a streaming event when recovery is vetoed by the owner.
+///
+/// When `notify` is non-empty, an additional event is published per guardian
+/// under `(ID_RVETO, guardian)` so guardians can subscribe to their own topic.
+pub fn emit_recovery_vetoed(
+    env: &Env,
+    owner: Address,
+    new_address: Address,
+    veto_count: u32,
+    notify: &Vec<Address>,
+) {
+    let event = RecoveryVetoedEvent {
+        owner,
+        new_address,
+        veto_count,
+        timestamp: env.ledger().timestamp(),
+    };
+    for guardian in notify.iter() {
+        env.events()
+            .publish((symbol_short!("ID_RVETO"), guardian), event.clone());
+    }
+    env.events()
+        .publish((symbol_short!("STREAM"), symbol_short!("ID_RVETO")), event);
+}
+
 /// Emit a streaming event when a ZK credential is verified.
 pub fn emit_zk_credential_verified(env: &Env, user: Address, verified: bool) {
     env.events().publish(
@@ -3,14 +3,16 @@
 pub mod credential;
 pub mod events;
 pub mod recovery;
+pub mod recovery_audit;
 
 #[cfg(test)]
 mod test;
 
 use credential::CredentialError;
-use recovery::{RecoveryError, RecoveryRequest};
+use recovery::{RecoveryError, RecoveryRequest, VetoConfig};
+use recovery_audit::RecoveryAuditEntry;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec,
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, Symbol, Vec,
 };
 
 /// Preparation data for guardian addition
@@ -45,6 +47,7 @@ pub struct PrepareThresholdChange {
 const ADMIN: Symbol = symbol_short!("ADMIN");
 const INITIALIZED: Symbol = symbol_short!("INIT");
 const HOLDER_BIND_PREFIX: &str = "HLD_BIND";
+const BIND_EXPIRY: Symbol = symbol_short!("BIND_EXP");
 
 /// Re-export credential error for downstream consumers.
 pub use credential::CredentialError as CredentialVerificationError;
@@ -96,6 +99,22 @@ impl IdentityContract {
         result
     }
 
+    /// Replace guardian `old` with `new` in one step, so the guardian count
+    /// never dips below the recovery threshold mid-transition.
+    pub fn replace_guardian(
+        env: Env,
+        caller: Address,
+        old: Address,
+        new: Address,
+    ) -> Result<(), RecoveryError> {
+        caller.require_auth();
+        Self::require_active_owner(&env, &caller)?;
+        recovery::replace_guardian(&env, &caller, &old, new.clone())?;
+        events::emit_guardian_changed(&env, caller.clone(), old, false);
+        events::emit_guardian_changed(&env, caller, new, true);
+        Ok(())
+    }
+
     /// Set the M-of-N approval threshold for recovery.
     pub fn set_recovery_threshold(
         env: Env,
@@ -118,6 +137,7 @@ impl IdentityContract {
         guardian.require_auth();
         let result = recovery::initiate_recovery(&env, &guardian, &owner, new_address.clone());
         if result.is_ok() {
+            recovery_audit::record(&env, &owner, &guardian, recovery_audit::INITIATE);
             events::emit_recovery_initiated(&env, owner, new_address, guardian);
         }
         result
@@ -130,7 +150,15 @@ impl IdentityContract {
         owner: Address,
     ) -> Result<(), RecoveryError> {
         guardian.require_auth();
-        recovery::approve_recovery(&env, &guardian, &owner)
+        recovery::approve_recovery(&env, &guardian, &owner)?;
+        recovery_audit::record(&env, &owner, &guardian, recovery_audit::APPROVE);
+
+        let approvals = recovery::get_recovery_request(&env, &owner)
+            .map(|request| request.approvals.len())
+            .unwrap_or(0);
+        let threshold = recovery::get_threshold(&env, &owner);
+        events::emit_recovery_approved(&env, owner, guardian, approvals, threshold);
+        Ok(())
     }
 
     /// Execute recovery after cooldown and sufficient approvals.
@@ -143,6 +171,7 @@ impl IdentityContract {
         caller.require_auth();
         let result = recovery::execute_recovery(&env, &owner);
         if let Ok(ref new_addr) = result {
+            recovery_audit::record(&env, &owner, &caller, recovery_audit::EXECUTE);
             events::emit_recovery_executed(&env, owner.clone(), new_addr.clone());
             events::emit_owner_status_changed(&env, owner, false);
             events::emit_owner_status_changed(&env, new_addr.clone(), true);
@@ -150,17 +179,95 @@ impl IdentityContract {
         result
     }
 
-    /// Owner cancels an active recovery request.
+    /// Owner cancels an active recovery request. The cancel counts towards
+    /// `max_vetoes` and needs the configured `veto_signer`, as a veto does.
     pub fn cancel_recovery(env: Env, caller: Address) -> Result<(), RecoveryError> {
         caller.require_auth();
         Self::require_active_owner(&env, &caller)?;
-        let result = recovery::cancel_recovery(&env, &caller);
+        if let Some(signer) = recovery::get_veto_config(&env, &caller).and_then(|c| c.veto_signer) {
+            signer.require_auth();
+        }
+        let result = recovery::cancel_recovery(&env, &caller).map(|_| ());
         if result.is_ok() {
+            recovery_audit::record(&env, &caller, &caller, recovery_audit::CANCEL);
             events::emit_recovery_cancelled(&env, caller);
         }
         result
     }
 
+    /// Configure how the owner may veto guardian-initiated recoveries.
+    /// Cannot be changed while a recovery is active, and once set can only
+    /// be tightened (`VetoConfigLoosened` otherwise).
+    pub fn set_veto_config(
+        env: Env,
+        caller: Address,
+        config: VetoConfig,
+    ) -> Result<(), RecoveryError> {
+        caller.require_auth();
+        Self::require_active_owner(&env, &caller)?;
+        recovery::set_veto_config(&env, &caller, config)
+    }
+
+    /// Owner vetoes an active recovery during its cooldown.
+    ///
+    /// A configured `veto_signer` must co-sign, and the veto is counted; the
+    /// same applies to `cancel_recovery`. Once `max_vetoes` is reached the
+    /// owner can no longer block recovery and guardians can proceed.
+    pub fn veto_recovery(env: Env, owner: Address) -> Result<u32, RecoveryError> {
+        owner.require_auth();
+        Self::require_active_owner(&env, &owner)?;
+
+        let config = recovery::get_veto_config(&env, &owner);
+        if let Some(signer) = config.as_ref().and_then(|c| c.veto_signer.clone()) {
+            signer.require_auth();
+        }
+
+        let (request, count) = recovery::veto_recovery(&env, &owner)?;
+        recovery_audit::record(&env, &owner, &owner, recovery_audit::VETO);
+
+        let notify = match config {
+            Some(c) if c.notify_guardians => recovery::get_guardians(&env, &owner),
+            _ => Vec::new(&env),
+        };
+        events::emit_recovery_vetoed(&env, owner, request.new_address, count, &notify);
+
+        Ok(count)
+    }
+
+    /// Set how long guardians must wait after a recovery request ends
+    /// (executed, cancelled or vetoed) before initiating another, up to
+    /// `MAX_RECOVERY_INITIATION_COOLDOWN`. Refused while a recovery is active.
+    pub fn set_recovery_cooldown(
+        env: Env,
+        caller: Address,
+        seconds: u64,
+    ) -> Result<(), RecoveryError> {
+        caller.require_auth();
+        Self::require_active_owner(&env, &caller)?;
+        recovery::set_initiation_cooldown(&env, &caller, seconds)
+    }
+
+    /// Get the owner's recovery initiation cooldown in seconds, or
+    /// `DEFAULT_RECOVERY_INITIATION_COOLDOWN` if they never set one.
+    pub fn get_recovery_cooldown(env: Env, owner: Address) -> u64 {
+        recovery::get_initiation_cooldown(&env, &owner)
+    }
+
+    /// When the owner's last recovery request ended, if one ever has.
+    pub fn get_last_recovery_at(env: Env, owner: Address) -> Option<u64> {
+        recovery::get_last_recovery_at(&env, &owner)
+    }
+
+    /// Get the owner's veto configuration, if any.
+    pub fn get_veto_config(env: Env, owner: Address) -> Option<VetoConfig> {
+        recovery::get_veto_config(&env, &owner)
+    }
+
+    /// Number of recoveries the owner has vetoed.
+    pub fn get_veto_count(env: Env, owner: Address) -> u32 {
+        recovery::get_veto_count(&env, &owner)
+    }
+
     /// Check if an address is an active identity owner.
     pub fn is_owner_active(env: Env, owner: Address) -> bool {
         recovery::is_owner_active(&env, &owner)
@@ -186,6 +293,27 @@ impl IdentityContract {
         recovery::get_recovery_request(&env, &owner)
     }
 
+    /// Digest of the owner's active recovery request, for guardians to check
+    /// against what they expect before approving.
+    pub fn recovery_request_digest(env: Env, owner: Address) -> Option<BytesN<32>> {
+        recovery::recovery_request_digest(&env, &owner)
+    }
+
+    /// Get entry `seq` (1-based) of the owner's recovery audit log.
+    pub fn get_recovery_audit(env: Env, owner: Address, seq: u64) -> Option<RecoveryAuditEntry> {
+        recovery_audit::get_entry(&env, &owner, seq)
+    }
+
+    /// Number of entries in the owner's recovery audit log.
+    pub fn get_recovery_audit_len(env: Env, owner: Address) -> u64 {
+        recovery_audit::get_len(&env, &owner)
+    }
+
+    /// Recompute the owner's recovery audit chain and check every link.
+    pub fn verify_recovery_audit_chain(env: Env, owner: Address) -> bool {
+        recovery_audit::verify_chain(&env, &owner)
+    }
+
     // ===== Two-Phase Commit Hooks =====
 
     /// Prepare phase for add_guardian operation
@@ -495,6 +623,12 @@ impl IdentityContract {
             env.storage().persistent().set(&key, &creds);
         }
 
+        // A plain bind is permanent, replacing any earlier expiry.
+        let mut expiries = Self::binding_expiries(&env, &caller);
+        if expiries.remove(credential_id.clone()).is_some() {
+            Self::set_binding_expiries(&env, &caller, &expiries);
+        }
+
         #[allow(deprecated)]
         env.events()
             .publish((symbol_short!("CRD_BIND"), caller), credential_id);
@@ -502,6 +636,90 @@ impl IdentityContract {
         Ok(())
     }
 
+    /// Bind a credential that stops counting as bound after `expires_at`,
+    /// matching the lifetime of the underlying verifiable credential.
+    ///
+    /// Re-binding an already bound credential updates its expiry.
+    pub fn bind_credential_with_expiry(
+        env: Env,
+        caller: Address,
+        credential_id: BytesN<32>,
+        expires_at: u64,
+    ) -> Result<(), RecoveryError> {
+        caller.require_auth();
+        Self::require_active_owner(&env, &caller)?;
+        if env.ledger().timestamp() > expires_at {
+            return Err(RecoveryError::InvalidExpiry);
+        }
+
+        let key = (Symbol::new(&env, HOLDER_BIND_PREFIX), caller.clone());
+        let mut creds: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if !creds.contains(&credential_id) {
+            creds.push_back(credential_id.clone());
+            env.storage().persistent().set(&key, &creds);
+        }
+
+        let mut expiries = Self::binding_expiries(&env, &caller);
+        expiries.set(credential_id.clone(), expires_at);
+        Self::set_binding_expiries(&env, &caller, &expiries);
+
+        #[allow(deprecated)]
+        env.events().publish(
+            (symbol_short!("CRD_BIND"), caller),
+            (credential_id, expires_at),
+        );
+
+        Ok(())
+    }
+
+    /// Drop every expired binding of the caller, returning how many were
+    /// removed.
+    pub fn prune_expired_bindings(env: Env, caller: Address) -> Result<u32, RecoveryError> {
+        caller.require_auth();
+        Self::require_active_owner(&env, &caller)?;
+
+        let mut expiries = Self::binding_expiries(&env, &caller);
+        let now = env.ledger().timestamp();
+        let mut expired = Vec::new(&env);
+        for (id, expires_at) in expiries.iter() {
+            if now > expires_at {
+                expired.push_back(id);
+            }
+        }
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        let key = (Symbol::new(&env, HOLDER_BIND_PREFIX), caller.clone());
+        let creds: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut kept = Vec::new(&env);
+        for c in creds.iter() {
+            if !expired.contains(&c) {
+                kept.push_back(c);
+            }
+        }
+        env.storage().persistent().set(&key, &kept);
+
+        for id in expired.iter() {
+            expiries.remove(id);
+        }
+        Self::set_binding_expiries(&env, &caller, &expiries);
+
+        #[allow(deprecated)]
+        env.events()
+            .publish((symbol_short!("CRD_PRUN"), caller), expired.len());
+
+        Ok(expired.len())
+    }
+
     /// Unbind a credential from this identity. Only the identity owner can unbind.
     pub fn unbind_credential(
         env: Env,
@@ -526,6 +744,11 @@ impl IdentityContract {
         }
         env.storage().persistent().set(&key, &new_creds);
 
+        let mut expiries = Self::binding_expiries(&env, &caller);
+        if expiries.remove(credential_id.clone()).is_some() {
+            Self::set_binding_expiries(&env, &caller, &expiries);
+        }
+
         #[allow(deprecated)]
         env.events()
             .publish((symbol_short!("CRD_UBND"), caller), credential_id);
@@ -533,24 +756,45 @@ impl IdentityContract {
         Ok(())
     }
 
-    /// Get all credential IDs bound to an identity.
+    /// Get all credential IDs bound to an identity, leaving out expired
+    /// bindings that have not been pruned yet.
     pub fn get_bound_credentials(env: Env, holder: Address) -> Vec<BytesN<32>> {
-        let key = (Symbol::new(&env, HOLDER_BIND_PREFIX), holder);
-        env.storage()
+        let key = (Symbol::new(&env, HOLDER_BIND_PREFIX), holder.clone());
+        let creds: Vec<BytesN<32>> = env
+            .storage()
             .persistent()
             .get(&key)
-            .unwrap_or_else(|| Vec::new(&env))
+            .unwrap_or_else(|| Vec::new(&env));
+        let expiries = Self::binding_expiries(&env, &holder);
+        if expiries.is_empty() {
+            return creds;
+        }
+        let now = env.ledger().timestamp();
+        let mut live = Vec::new(&env);
+        for c in creds.iter() {
+            if expiries.get(c.clone()).map_or(true, |at| now <= at) {
+                live.push_back(c);
+            }
+        }
+        live
     }
 
-    /// Check if a specific credential is bound to an identity.
+    /// Check if a specific credential is bound to an identity and its binding
+    /// has not expired.
     pub fn is_credential_bound(env: Env, holder: Address, credential_id: BytesN<32>) -> bool {
-        let key = (Symbol::new(&env, HOLDER_BIND_PREFIX), holder);
+        let key = (Symbol::new(&env, HOLDER_BIND_PREFIX), holder.clone());
         let creds: Vec<BytesN<32>> = env
             .storage()
             .persistent()
             .get(&key)
             .unwrap_or_else(|| Vec::new(&env));
-        creds.contains(&credential_id)
+        if !creds.contains(&credential_id) {
+            return false;
+        }
+        match Self::binding_expiries(&env, &holder).get(credential_id) {
+            Some(expires_at) => env.ledger().timestamp() <= expires_at,
+            None => true,
+        }
     }
 
     // ── Internal helpers ─────────────────────────────────────────────────────
@@ -561,4 +805,22 @@ impl IdentityContract {
         }
         Ok(())
     }
+
+    /// Expiry timestamps of a holder's expiring bindings. Bindings absent
+    /// from the map are permanent.
+    fn binding_expiries(env: &Env, holder: &Address) -> Map<BytesN<32>, u64> {
+        env.storage()
+            .persistent()
+            .get(&(BIND_EXPIRY, holder.clone()))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn set_binding_expiries(env: &Env, holder: &Address, expiries: &Map<BytesN<32>, u64>) {
+        let key = (BIND_EXPIRY, holder.clone());
+        if expiries.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, expiries);
+        }
+    }
 }
@@ -1,6 +1,8 @@
 #![allow(clippy::arithmetic_side_effects)]
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec,
+};
 
 // ── Constants ────────────────────────────────────────────────────────────────
 
@@ -8,6 +10,13 @@ const MIN_GUARDIANS: u32 = 3;
 const MAX_GUARDIANS: u32 = 5;
 const COOLDOWN_PERIOD: u64 = 172_800; // 48 hours in seconds
 
+/// Longest wait an owner may impose between the end of one recovery request
+/// and the start of the next.
+pub const MAX_RECOVERY_INITIATION_COOLDOWN: u64 = 604_800; // 7 days
+
+/// Initiation cooldown for owners who never set one.
+pub const DEFAULT_RECOVERY_INITIATION_COOLDOWN: u64 = 86_400; // 1 day
+
 const TTL_THRESHOLD: u32 = 5_184_000;
 const TTL_EXTEND_TO: u32 = 10_368_000;
 
@@ -20,6 +29,10 @@ const GUARDIANS: Symbol = symbol_short!("GUARD");
 const REC_THR: Symbol = symbol_short!("REC_THR");
 const REC_REQ: Symbol = symbol_short!("REC_REQ");
 const OWN_ACT: Symbol = symbol_short!("OWN_ACT");
+const VETO_CFG: Symbol = symbol_short!("VETO_CFG");
+const VETO_CNT: Symbol = symbol_short!("VETO_CNT");
+const REC_LAST: Symbol = symbol_short!("REC_LAST");
+const REC_GAP: Symbol = symbol_short!("REC_GAP");
 
 // ── Errors ───────────────────────────────────────────────────────────────────
 
@@ -42,6 +55,12 @@ pub enum RecoveryError {
     InsufficientApprovals = 13,
     CooldownNotExpired = 14,
     OwnerDeactivated = 15,
+    VetoWindowClosed = 16,
+    VetoLimitReached = 17,
+    InvalidExpiry = 18,
+    RecoveryCooldown = 19,
+    InvalidCooldown = 20,
+    VetoConfigLoosened = 21,
 }
 
 // ── Types ────────────────────────────────────────────────────────────────────
@@ -56,6 +75,21 @@ pub struct RecoveryRequest {
     pub execute_after: u64,
 }
 
+/// Owner-configured rules for vetoing guardian-initiated recoveries.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VetoConfig {
+    /// Second key that must co-sign every veto (e.g. a hardware key), so a
+    /// stolen primary key alone cannot block recovery.
+    pub veto_signer: Option<Address>,
+    /// Vetoes allowed before guardians can no longer be blocked (0 = unlimited).
+    /// Cancels count as vetoes; once reached, both `veto_recovery` and
+    /// `cancel_recovery` are refused.
+    pub max_vetoes: u32,
+    /// Emit a veto notification addressed to each guardian.
+    pub notify_guardians: bool,
+}
+
 // ── Storage key helpers ──────────────────────────────────────────────────────
 
 fn guardians_key(owner: &Address) -> (Symbol, Address) {
@@ -74,6 +108,22 @@ fn owner_active_key(owner: &Address) -> (Symbol, Address) {
     (OWN_ACT, owner.clone())
 }
 
+fn veto_config_key(owner: &Address) -> (Symbol, Address) {
+    (VETO_CFG, owner.clone())
+}
+
+fn veto_count_key(owner: &Address) -> (Symbol, Address) {
+    (VETO_CNT, owner.clone())
+}
+
+fn last_recovery_key(owner: &Address) -> (Symbol, Address) {
+    (REC_LAST, owner.clone())
+}
+
+fn initiation_cooldown_key(owner: &Address) -> (Symbol, Address) {
+    (REC_GAP, owner.clone())
+}
+
 /// Matches the key structure in lib.rs: (Symbol::new(&env, HOLDER_BIND_PREFIX), caller)
 fn credentials_key(env: &Env, owner: &Address) -> (Symbol, Address) {
     (Symbol::new(env, HOLDER_BIND_PREFIX), owner.clone())
@@ -173,6 +223,45 @@ pub fn remove_guardian(
     Ok(())
 }
 
+/// Swap `old` for `new` in place. The guardian count never changes, so the
+/// threshold stays satisfiable throughout. An approval `old` gave to an active
+/// recovery is withdrawn, since a replaced guardian may be compromised.
+pub fn replace_guardian(
+    env: &Env,
+    owner: &Address,
+    old: &Address,
+    new: Address,
+) -> Result<(), RecoveryError> {
+    let key = guardians_key(owner);
+    let mut guardians = get_guardians(env, owner);
+
+    let index = guardians
+        .first_index_of(old)
+        .ok_or(RecoveryError::GuardianNotFound)?;
+    if guardians.contains(&new) {
+        return Err(RecoveryError::DuplicateGuardian);
+    }
+
+    guardians.set(index, new);
+    env.storage().persistent().set(&key, &guardians);
+    extend_ttl(env, &key);
+
+    let req_key = recovery_key(owner);
+    if let Some(mut request) = env
+        .storage()
+        .persistent()
+        .get::<_, RecoveryRequest>(&req_key)
+    {
+        if let Some(i) = request.approvals.first_index_of(old) {
+            request.approvals.remove(i);
+            env.storage().persistent().set(&req_key, &request);
+            extend_ttl(env, &req_key);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn set_threshold(env: &Env, owner: &Address, threshold: u32) -> Result<(), RecoveryError> {
     let guardians = get_guardians(env, owner);
     if threshold == 0 || threshold > guardians.len() {
@@ -210,6 +299,12 @@ pub fn initiate_recovery(
     }
 
     let now = env.ledger().timestamp();
+    if let Some(last) = get_last_recovery_at(env, owner) {
+        if now < last.saturating_add(get_initiation_cooldown(env, owner)) {
+            return Err(RecoveryError::RecoveryCooldown);
+        }
+    }
+
     let mut approvals = Vec::new(env);
     approvals.push_back(guardian.clone());
 
@@ -310,22 +405,189 @@ pub fn execute_recovery(env: &Env, owner: &Address) -> Result<Address, RecoveryE
 
     // 6. Clean up recovery request
     env.storage().persistent().remove(&key);
+    mark_recovery_ended(env, owner);
 
     Ok(new_address)
 }
 
-pub fn cancel_recovery(env: &Env, owner: &Address) -> Result<(), RecoveryError> {
+/// Cancel an active recovery at any point before execution.
+///
+/// Every request is guardian-initiated, so a cancel is counted as a veto:
+/// otherwise repeated cancels would block guardians without ever reaching
+/// `max_vetoes`. Callers are responsible for owner (and veto-signer)
+/// authorization. Returns the owner's total veto count.
+pub fn cancel_recovery(env: &Env, owner: &Address) -> Result<u32, RecoveryError> {
     let key = recovery_key(owner);
     if !env.storage().persistent().has(&key) {
         return Err(RecoveryError::NoActiveRecovery);
     }
 
+    if veto_limit_reached(env, owner) {
+        return Err(RecoveryError::VetoLimitReached);
+    }
+
+    let count = record_veto(env, owner);
     env.storage().persistent().remove(&key);
+    mark_recovery_ended(env, owner);
 
-    Ok(())
+    Ok(count)
 }
 
 pub fn get_recovery_request(env: &Env, owner: &Address) -> Option<RecoveryRequest> {
     let key = recovery_key(owner);
     env.storage().persistent().get(&key)
-}
\ No newline at end of file
+}
+
+/// When the owner's last recovery request was executed, cancelled or vetoed.
+pub fn get_last_recovery_at(env: &Env, owner: &Address) -> Option<u64> {
+    env.storage().persistent().get(&last_recovery_key(owner))
+}
+
+fn mark_recovery_ended(env: &Env, owner: &Address) {
+    let key = last_recovery_key(owner);
+    env.storage()
+        .persistent()
+        .set(&key, &env.ledger().timestamp());
+    extend_ttl(env, &key);
+}
+
+/// Seconds that must pass after a request ends before guardians may start
+/// another. Defaults to `DEFAULT_RECOVERY_INITIATION_COOLDOWN` and never
+/// exceeds `MAX_RECOVERY_INITIATION_COOLDOWN`.
+pub fn get_initiation_cooldown(env: &Env, owner: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&initiation_cooldown_key(owner))
+        .unwrap_or(DEFAULT_RECOVERY_INITIATION_COOLDOWN)
+        .min(MAX_RECOVERY_INITIATION_COOLDOWN)
+}
+
+/// Store the owner's initiation cooldown. Like the veto rules it is locked
+/// while a recovery is active.
+pub fn set_initiation_cooldown(
+    env: &Env,
+    owner: &Address,
+    seconds: u64,
+) -> Result<(), RecoveryError> {
+    if seconds > MAX_RECOVERY_INITIATION_COOLDOWN {
+        return Err(RecoveryError::InvalidCooldown);
+    }
+    if env.storage().persistent().has(&recovery_key(owner)) {
+        return Err(RecoveryError::RecoveryAlreadyActive);
+    }
+
+    let key = initiation_cooldown_key(owner);
+    env.storage().persistent().set(&key, &seconds);
+    extend_ttl(env, &key);
+
+    Ok(())
+}
+
+/// SHA-256 commitment to what guardians are asked to approve: the owner, the
+/// proposed address, when the request was made and the current threshold.
+///
+/// Guardians can rebuild it off-chain from the initiation event and compare
+/// before approving. `None` when no recovery is active.
+pub fn recovery_request_digest(env: &Env, owner: &Address) -> Option<BytesN<32>> {
+    let request = get_recovery_request(env, owner)?;
+    let mut data = Bytes::new(env);
+    data.append(&owner.clone().to_xdr(env));
+    data.append(&request.new_address.to_xdr(env));
+    data.extend_from_array(&request.initiated_at.to_be_bytes());
+    data.extend_from_array(&get_threshold(env, owner).to_be_bytes());
+    Some(env.crypto().sha256(&data).into())
+}
+
+// ── Owner veto ───────────────────────────────────────────────────────────────
+
+pub fn get_veto_config(env: &Env, owner: &Address) -> Option<VetoConfig> {
+    env.storage().persistent().get(&veto_config_key(owner))
+}
+
+/// Store the owner's veto rules. Refused while a recovery is active, and
+/// once rules exist they can only be tightened, so a compromised owner cannot
+/// remove the guardian override between recoveries either. The veto count is
+/// never reset by a change.
+pub fn set_veto_config(
+    env: &Env,
+    owner: &Address,
+    config: VetoConfig,
+) -> Result<(), RecoveryError> {
+    if env.storage().persistent().has(&recovery_key(owner)) {
+        return Err(RecoveryError::RecoveryAlreadyActive);
+    }
+    if let Some(current) = get_veto_config(env, owner) {
+        if !is_tightening(&current, &config) {
+            return Err(RecoveryError::VetoConfigLoosened);
+        }
+    }
+
+    let key = veto_config_key(owner);
+    env.storage().persistent().set(&key, &config);
+    extend_ttl(env, &key);
+
+    Ok(())
+}
+
+pub fn get_veto_count(env: &Env, owner: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&veto_count_key(owner))
+        .unwrap_or(0)
+}
+
+/// Whether `next` is at least as strict as `current`: the veto limit is no
+/// higher (and not switched to unlimited), an existing veto signer is kept,
+/// and guardian notifications are not switched off.
+fn is_tightening(current: &VetoConfig, next: &VetoConfig) -> bool {
+    let limit_kept = match (current.max_vetoes, next.max_vetoes) {
+        (_, 0) => current.max_vetoes == 0,
+        (0, _) => true,
+        (cur, new) => new <= cur,
+    };
+    let signer_kept = current.veto_signer.is_none() || current.veto_signer == next.veto_signer;
+    let notify_kept = !current.notify_guardians || next.notify_guardians;
+    limit_kept && signer_kept && notify_kept
+}
+
+fn record_veto(env: &Env, owner: &Address) -> u32 {
+    let count = get_veto_count(env, owner).saturating_add(1);
+    let count_key = veto_count_key(owner);
+    env.storage().persistent().set(&count_key, &count);
+    extend_ttl(env, &count_key);
+    count
+}
+
+fn veto_limit_reached(env: &Env, owner: &Address) -> bool {
+    match get_veto_config(env, owner) {
+        Some(cfg) if cfg.max_vetoes > 0 => get_veto_count(env, owner) >= cfg.max_vetoes,
+        _ => false,
+    }
+}
+
+/// Cancel an active recovery during its cooldown and record the veto.
+///
+/// Callers are responsible for owner (and veto-signer) authorization.
+/// Returns the vetoed request and the owner's total veto count.
+pub fn veto_recovery(env: &Env, owner: &Address) -> Result<(RecoveryRequest, u32), RecoveryError> {
+    let key = recovery_key(owner);
+    let request: RecoveryRequest = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(RecoveryError::NoActiveRecovery)?;
+
+    if env.ledger().timestamp() >= request.execute_after {
+        return Err(RecoveryError::VetoWindowClosed);
+    }
+
+    if veto_limit_reached(env, owner) {
+        return Err(RecoveryError::VetoLimitReached);
+    }
+
+    let count = record_veto(env, owner);
+    env.storage().persistent().remove(&key);
+    mark_recovery_ended(env, owner);
+
+    Ok((request, count))
+}
@@ -0,0 +1,161 @@
+//! Hash-chained audit log of each owner's recovery history.
+//!
+//! Every initiate, approve, execute, cancel and veto appends an entry to the
+//! owner's chain. Each entry commits to its predecessor through `prev_hash`,
+//! so a rewritten or dropped entry breaks [`verify_chain`].
+
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+// ── Actions ──────────────────────────────────────────────────────────────────
+
+pub const INITIATE: Symbol = symbol_short!("REC_INIT");
+pub const APPROVE: Symbol = symbol_short!("REC_APPR");
+pub const EXECUTE: Symbol = symbol_short!("REC_EXEC");
+pub const CANCEL: Symbol = symbol_short!("REC_CANC");
+pub const VETO: Symbol = symbol_short!("REC_VETO");
+
+// ── Storage key symbols ──────────────────────────────────────────────────────
+
+const AUDIT: Symbol = symbol_short!("REC_AUD");
+const AUDIT_SEQ: Symbol = symbol_short!("RAUD_SEQ");
+const AUDIT_TAIL: Symbol = symbol_short!("RAUD_TL");
+
+const TTL_THRESHOLD: u32 = 5_184_000;
+const TTL_EXTEND_TO: u32 = 10_368_000;
+
+// ── Types ────────────────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryAuditEntry {
+    /// 1-based position in the owner's chain.
+    pub seq: u64,
+    pub owner: Address,
+    pub actor: Address,
+    pub action: Symbol,
+    pub timestamp: u64,
+    /// `entry_hash` of the previous entry; all zeroes for the first.
+    pub prev_hash: BytesN<32>,
+    /// `SHA-256(prev_hash ‖ seq ‖ actor ‖ action ‖ timestamp)`.
+    pub entry_hash: BytesN<32>,
+}
+
+// ── Storage key helpers ──────────────────────────────────────────────────────
+
+fn entry_key(owner: &Address, seq: u64) -> (Symbol, Address, u64) {
+    (AUDIT, owner.clone(), seq)
+}
+
+fn seq_key(owner: &Address) -> (Symbol, Address) {
+    (AUDIT_SEQ, owner.clone())
+}
+
+fn tail_key(owner: &Address) -> (Symbol, Address) {
+    (AUDIT_TAIL, owner.clone())
+}
+
+fn hash_entry(
+    env: &Env,
+    prev_hash: &BytesN<32>,
+    seq: u64,
+    actor: &Address,
+    action: &Symbol,
+    timestamp: u64,
+) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.extend_from_array(&prev_hash.to_array());
+    data.extend_from_array(&seq.to_be_bytes());
+    data.append(&actor.clone().to_xdr(env));
+    data.append(&action.clone().to_xdr(env));
+    data.extend_from_array(&timestamp.to_be_bytes());
+    env.crypto().sha256(&data).into()
+}
+
+// ── Log ──────────────────────────────────────────────────────────────────────
+
+/// Append `action` by `actor` to `owner`'s chain and return the new entry.
+pub fn record(env: &Env, owner: &Address, actor: &Address, action: Symbol) -> RecoveryAuditEntry {
+    let seq = get_len(env, owner).saturating_add(1);
+    let prev_hash = env
+        .storage()
+        .persistent()
+        .get(&tail_key(owner))
+        .unwrap_or(BytesN::from_array(env, &[0u8; 32]));
+    let timestamp = env.ledger().timestamp();
+    let entry_hash = hash_entry(env, &prev_hash, seq, actor, &action, timestamp);
+
+    let entry = RecoveryAuditEntry {
+        seq,
+        owner: owner.clone(),
+        actor: actor.clone(),
+        action,
+        timestamp,
+        prev_hash,
+        entry_hash: entry_hash.clone(),
+    };
+
+    let key = entry_key(owner, seq);
+    env.storage().persistent().set(&key, &entry);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+    let key = seq_key(owner);
+    env.storage().persistent().set(&key, &seq);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+    let key = tail_key(owner);
+    env.storage().persistent().set(&key, &entry_hash);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+    entry
+}
+
+/// Number of entries in `owner`'s chain.
+pub fn get_len(env: &Env, owner: &Address) -> u64 {
+    env.storage().persistent().get(&seq_key(owner)).unwrap_or(0)
+}
+
+pub fn get_entry(env: &Env, owner: &Address, seq: u64) -> Option<RecoveryAuditEntry> {
+    env.storage().persistent().get(&entry_key(owner, seq))
+}
+
+/// Walk `owner`'s chain from the first entry, recomputing every hash and
+/// link. Returns `false` if an entry is missing or altered, or if the last
+/// entry does not match the stored tail. An empty chain is intact.
+pub fn verify_chain(env: &Env, owner: &Address) -> bool {
+    let mut prev_hash = BytesN::from_array(env, &[0u8; 32]);
+    for seq in 1..=get_len(env, owner) {
+        let entry = match get_entry(env, owner, seq) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        let expected = hash_entry(
+            env,
+            &prev_hash,
+            seq,
+            &entry.actor,
+            &entry.action,
+            entry.timestamp,
+        );
+        if entry.seq != seq
+            || entry.owner != *owner
+            || entry.prev_hash != prev_hash
+            || entry.entry_hash != expected
+        {
+            return false;
+        }
+        prev_hash = entry.entry_hash;
+    }
+
+    let tail: BytesN<32> = env
+        .storage()
+        .persistent()
+        .get(&tail_key(owner))
+        .unwrap_or(BytesN::from_array(env, &[0u8; 32]));
+    tail == prev_hash
+}
@@ -0,0 +1,91 @@
+//! Expiring credential binding tests.
+//!
+//! Covers bindings that lapse with the ledger clock, pruning them from
+//! storage, and how expiring and permanent bindings interact.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use identity::{recovery::RecoveryError, IdentityContract, IdentityContractClient};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, Address, BytesN, Env};
+
+const START: u64 = 10_000;
+
+fn setup() -> (Env, IdentityContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START);
+
+    let contract_id = env.register(IdentityContract, ());
+    let client = IdentityContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    client.initialize(&owner);
+
+    (env, client, owner)
+}
+
+#[test]
+fn test_expired_binding_reports_unbound_and_is_pruned() {
+    let (env, client, owner) = setup();
+    let expiring = BytesN::from_array(&env, &[0x01u8; 32]);
+    let permanent = BytesN::from_array(&env, &[0x02u8; 32]);
+
+    client.bind_credential_with_expiry(&owner, &expiring, &(START + 60));
+    client.bind_credential(&owner, &permanent);
+    assert!(client.is_credential_bound(&owner, &expiring));
+    assert_eq!(client.get_bound_credentials(&owner).len(), 2);
+
+    // Still bound at the expiry instant itself.
+    env.ledger().set_timestamp(START + 60);
+    assert!(client.is_credential_bound(&owner, &expiring));
+    assert_eq!(client.prune_expired_bindings(&owner), 0);
+
+    env.ledger().set_timestamp(START + 61);
+    assert!(!client.is_credential_bound(&owner, &expiring));
+    assert!(client.is_credential_bound(&owner, &permanent));
+    assert_eq!(client.get_bound_credentials(&owner).len(), 1);
+
+    assert_eq!(client.prune_expired_bindings(&owner), 1);
+    assert_eq!(client.prune_expired_bindings(&owner), 0);
+    let remaining = client.get_bound_credentials(&owner);
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining.get(0), Some(permanent));
+}
+
+#[test]
+fn test_rebinding_updates_or_clears_expiry() {
+    let (env, client, owner) = setup();
+    let cred = BytesN::from_array(&env, &[0x03u8; 32]);
+
+    client.bind_credential_with_expiry(&owner, &cred, &(START + 10));
+    client.bind_credential_with_expiry(&owner, &cred, &(START + 100));
+    assert_eq!(client.get_bound_credentials(&owner).len(), 1);
+    env.ledger().set_timestamp(START + 50);
+    assert!(client.is_credential_bound(&owner, &cred));
+
+    // A plain bind makes the binding permanent again.
+    client.bind_credential(&owner, &cred);
+    env.ledger().set_timestamp(START + 1_000);
+    assert!(client.is_credential_bound(&owner, &cred));
+    assert_eq!(client.prune_expired_bindings(&owner), 0);
+}
+
+#[test]
+fn test_binding_expiry_must_not_be_in_the_past() {
+    let (env, client, owner) = setup();
+    let cred = BytesN::from_array(&env, &[0x04u8; 32]);
+
+    assert_eq!(
+        client.try_bind_credential_with_expiry(&owner, &cred, &(START - 1)),
+        Err(Ok(RecoveryError::InvalidExpiry))
+    );
+    assert_eq!(
+        client.try_bind_credential_with_expiry(&Address::generate(&env), &cred, &(START + 1)),
+        Err(Ok(RecoveryError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_prune_expired_bindings(&Address::generate(&env)),
+        Err(Ok(RecoveryError::Unauthorized))
+    );
+    assert!(!client.is_credential_bound(&owner, &cred));
+}
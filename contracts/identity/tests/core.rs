@@ -93,6 +93,87 @@ fn test_recovery_flow_happy_path() {
     assert!(client.is_owner_active(&new_owner));
 }
 
+#[test]
+fn test_replace_guardian_swaps_in_place() {
+    let (env, client, owner) = setup();
+    let (g1, g2, g3) = add_three_guardians(&env, &client, &owner);
+    client.set_recovery_threshold(&owner, &3);
+
+    let replacement = Address::generate(&env);
+    client.replace_guardian(&owner, &g2, &replacement);
+
+    let guardians = client.get_guardians(&owner);
+    assert_eq!(guardians.len(), 3);
+    assert_eq!(guardians.get(1), Some(replacement.clone()));
+    assert!(!client.is_guardian(&owner, &g2));
+    assert_eq!(client.get_recovery_threshold(&owner), 3);
+
+    // The replaced guardian's approval no longer counts toward recovery.
+    client.initiate_recovery(&g1, &owner, &Address::generate(&env));
+    client.approve_recovery(&g3, &owner);
+    client.replace_guardian(&owner, &g3, &g2);
+    let req = client.get_recovery_request(&owner).unwrap();
+    assert_eq!(req.approvals.len(), 1);
+    assert!(req.approvals.contains(&g1));
+}
+
+#[test]
+fn test_replace_guardian_rejects_invalid_swaps() {
+    let (env, client, owner) = setup();
+    let (g1, g2, _g3) = add_three_guardians(&env, &client, &owner);
+
+    assert_eq!(
+        client.try_replace_guardian(&owner, &g1, &g2),
+        Err(Ok(RecoveryError::DuplicateGuardian))
+    );
+    assert_eq!(
+        client.try_replace_guardian(&owner, &Address::generate(&env), &Address::generate(&env)),
+        Err(Ok(RecoveryError::GuardianNotFound))
+    );
+    assert_eq!(
+        client.try_replace_guardian(&Address::generate(&env), &g1, &Address::generate(&env)),
+        Err(Ok(RecoveryError::Unauthorized))
+    );
+
+    let guardians = client.get_guardians(&owner);
+    assert_eq!(guardians.len(), 3);
+    assert_eq!(guardians.get(0), Some(g1));
+}
+
+#[test]
+fn test_recovery_request_digest_commits_to_request() {
+    use soroban_sdk::{xdr::ToXdr, Bytes};
+
+    let (env, client, owner) = setup();
+    let (g1, g2, _g3) = add_three_guardians(&env, &client, &owner);
+    client.set_recovery_threshold(&owner, &2);
+    client.set_recovery_cooldown(&owner, &0);
+    assert_eq!(client.recovery_request_digest(&owner), None);
+
+    env.ledger().set_timestamp(5_000);
+    let proposed = Address::generate(&env);
+    client.initiate_recovery(&g1, &owner, &proposed);
+    let digest = client.recovery_request_digest(&owner).unwrap();
+
+    // A guardian can rebuild the digest from what it expects to approve.
+    let mut expected = Bytes::new(&env);
+    expected.append(&owner.clone().to_xdr(&env));
+    expected.append(&proposed.clone().to_xdr(&env));
+    expected.extend_from_array(&5_000u64.to_be_bytes());
+    expected.extend_from_array(&2u32.to_be_bytes());
+    let expected: BytesN<32> = env.crypto().sha256(&expected).into();
+    assert_eq!(digest, expected);
+
+    // Approvals don't change what is being approved.
+    client.approve_recovery(&g2, &owner);
+    assert_eq!(client.recovery_request_digest(&owner), Some(digest.clone()));
+
+    client.cancel_recovery(&owner);
+    assert_eq!(client.recovery_request_digest(&owner), None);
+    client.initiate_recovery(&g1, &owner, &Address::generate(&env));
+    assert_ne!(client.recovery_request_digest(&owner), Some(digest));
+}
+
 // ===========================================================================
 // ZK Credential Verification Tests
 // ===========================================================================
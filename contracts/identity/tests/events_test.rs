@@ -105,6 +105,7 @@ fn test_recovery_events() {
     client.add_guardian(&owner, &g2);
     client.add_guardian(&owner, &g3);
     client.set_recovery_threshold(&owner, &3);
+    client.set_recovery_cooldown(&owner, &0);
 
     let new_owner = Address::generate(&env);
 
@@ -0,0 +1,118 @@
+//! Recovery audit chain tests.
+//!
+//! Covers the entries recorded across a full recovery, chain verification,
+//! and detection of a rewritten entry.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use identity::{recovery_audit, IdentityContract, IdentityContractClient};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, Address, BytesN, Env, Symbol};
+
+const COOLDOWN: u64 = 172_800;
+
+fn setup() -> (Env, IdentityContractClient<'static>, Address, [Address; 3]) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(IdentityContract, ());
+    let client = IdentityContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    client.initialize(&owner);
+
+    let guardians = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for guardian in guardians.iter() {
+        client.add_guardian(&owner, guardian);
+    }
+    client.set_recovery_threshold(&owner, &2);
+    client.set_recovery_cooldown(&owner, &0);
+
+    (env, client, owner, guardians)
+}
+
+#[test]
+fn test_full_recovery_produces_linked_audit_entries() {
+    let (env, client, owner, guardians) = setup();
+    let new_owner = Address::generate(&env);
+    let executor = Address::generate(&env);
+
+    client.initiate_recovery(&guardians[0], &owner, &new_owner);
+    env.ledger().set_timestamp(2_000);
+    client.approve_recovery(&guardians[1], &owner);
+    client.approve_recovery(&guardians[2], &owner);
+    env.ledger().set_timestamp(1_000 + COOLDOWN);
+    client.execute_recovery(&executor, &owner);
+
+    assert_eq!(client.get_recovery_audit_len(&owner), 4);
+    let expected: [(Symbol, &Address, u64); 4] = [
+        (recovery_audit::INITIATE, &guardians[0], 1_000),
+        (recovery_audit::APPROVE, &guardians[1], 2_000),
+        (recovery_audit::APPROVE, &guardians[2], 2_000),
+        (recovery_audit::EXECUTE, &executor, 1_000 + COOLDOWN),
+    ];
+
+    let mut prev_hash = BytesN::from_array(&env, &[0u8; 32]);
+    for (i, (action, actor, timestamp)) in expected.iter().enumerate() {
+        let seq = i as u64 + 1;
+        let entry = client.get_recovery_audit(&owner, &seq).unwrap();
+        assert_eq!(entry.seq, seq);
+        assert_eq!(entry.owner, owner);
+        assert_eq!(entry.action, *action);
+        assert_eq!(entry.actor, **actor);
+        assert_eq!(entry.timestamp, *timestamp);
+        assert_eq!(entry.prev_hash, prev_hash);
+        assert_ne!(entry.entry_hash, prev_hash);
+        prev_hash = entry.entry_hash;
+    }
+    assert!(client.get_recovery_audit(&owner, &5).is_none());
+
+    assert!(client.verify_recovery_audit_chain(&owner));
+    // The new address starts with an empty, trivially intact history.
+    assert_eq!(client.get_recovery_audit_len(&new_owner), 0);
+    assert!(client.verify_recovery_audit_chain(&new_owner));
+}
+
+#[test]
+fn test_cancel_and_veto_are_audited() {
+    let (env, client, owner, guardians) = setup();
+
+    client.initiate_recovery(&guardians[0], &owner, &Address::generate(&env));
+    client.cancel_recovery(&owner);
+    client.initiate_recovery(&guardians[1], &owner, &Address::generate(&env));
+    client.veto_recovery(&owner);
+
+    assert_eq!(client.get_recovery_audit_len(&owner), 4);
+    let cancel = client.get_recovery_audit(&owner, &2).unwrap();
+    assert_eq!(cancel.action, recovery_audit::CANCEL);
+    assert_eq!(cancel.actor, owner);
+    let veto = client.get_recovery_audit(&owner, &4).unwrap();
+    assert_eq!(veto.action, recovery_audit::VETO);
+    assert_eq!(veto.actor, owner);
+    assert!(client.verify_recovery_audit_chain(&owner));
+}
+
+#[test]
+fn test_rewritten_entry_breaks_chain() {
+    let (env, client, owner, guardians) = setup();
+
+    client.initiate_recovery(&guardians[0], &owner, &Address::generate(&env));
+    client.approve_recovery(&guardians[1], &owner);
+    assert!(client.verify_recovery_audit_chain(&owner));
+
+    // Attribute the approval to a different guardian.
+    let mut forged = client.get_recovery_audit(&owner, &2).unwrap();
+    forged.actor = guardians[2].clone();
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &(Symbol::new(&env, "REC_AUD"), owner.clone(), 2u64),
+            &forged,
+        );
+    });
+
+    assert!(!client.verify_recovery_audit_chain(&owner));
+}
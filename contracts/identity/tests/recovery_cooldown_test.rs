@@ -0,0 +1,117 @@
+//! Recovery initiation cooldown tests.
+//!
+//! Covers the owner-configured wait between the end of one recovery request
+//! and the start of the next, which stops guardians churning requests.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use identity::{
+    recovery::{
+        RecoveryError, DEFAULT_RECOVERY_INITIATION_COOLDOWN, MAX_RECOVERY_INITIATION_COOLDOWN,
+    },
+    IdentityContract, IdentityContractClient,
+};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, Address, Env};
+
+const START: u64 = 1_000;
+const HOUR: u64 = 3_600;
+
+fn setup() -> (Env, IdentityContractClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START);
+
+    let contract_id = env.register(IdentityContract, ());
+    let client = IdentityContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    client.initialize(&owner);
+
+    let guardian = Address::generate(&env);
+    client.add_guardian(&owner, &guardian);
+    client.add_guardian(&owner, &Address::generate(&env));
+    client.add_guardian(&owner, &Address::generate(&env));
+    client.set_recovery_threshold(&owner, &2);
+
+    (env, client, owner, guardian)
+}
+
+#[test]
+fn test_reinitiation_waits_for_cooldown_after_cancel() {
+    let (env, client, owner, guardian) = setup();
+    client.set_recovery_cooldown(&owner, &HOUR);
+    assert_eq!(client.get_recovery_cooldown(&owner), HOUR);
+    assert_eq!(client.get_last_recovery_at(&owner), None);
+
+    // The first request is never held back.
+    client.initiate_recovery(&guardian, &owner, &Address::generate(&env));
+    env.ledger().set_timestamp(START + 10);
+    client.cancel_recovery(&owner);
+    assert_eq!(client.get_last_recovery_at(&owner), Some(START + 10));
+
+    assert_eq!(
+        client.try_initiate_recovery(&guardian, &owner, &Address::generate(&env)),
+        Err(Ok(RecoveryError::RecoveryCooldown))
+    );
+    env.ledger().set_timestamp(START + 10 + HOUR - 1);
+    assert_eq!(
+        client.try_initiate_recovery(&guardian, &owner, &Address::generate(&env)),
+        Err(Ok(RecoveryError::RecoveryCooldown))
+    );
+
+    env.ledger().set_timestamp(START + 10 + HOUR);
+    client.initiate_recovery(&guardian, &owner, &Address::generate(&env));
+    assert!(client.get_recovery_request(&owner).is_some());
+}
+
+#[test]
+fn test_veto_also_starts_cooldown() {
+    let (env, client, owner, guardian) = setup();
+    client.set_recovery_cooldown(&owner, &HOUR);
+
+    client.initiate_recovery(&guardian, &owner, &Address::generate(&env));
+    client.veto_recovery(&owner);
+    assert_eq!(
+        client.try_initiate_recovery(&guardian, &owner, &Address::generate(&env)),
+        Err(Ok(RecoveryError::RecoveryCooldown))
+    );
+
+    env.ledger().set_timestamp(START + HOUR);
+    client.initiate_recovery(&guardian, &owner, &Address::generate(&env));
+}
+
+#[test]
+fn test_cooldown_defaults_to_one_day_and_is_bounded() {
+    let (env, client, owner, guardian) = setup();
+    assert_eq!(
+        client.get_recovery_cooldown(&owner),
+        DEFAULT_RECOVERY_INITIATION_COOLDOWN
+    );
+
+    client.initiate_recovery(&guardian, &owner, &Address::generate(&env));
+    assert_eq!(
+        client.try_set_recovery_cooldown(&owner, &HOUR),
+        Err(Ok(RecoveryError::RecoveryAlreadyActive))
+    );
+    client.cancel_recovery(&owner);
+
+    // Owners who never configure a cooldown still get the default wait.
+    assert_eq!(
+        client.try_initiate_recovery(&guardian, &owner, &Address::generate(&env)),
+        Err(Ok(RecoveryError::RecoveryCooldown))
+    );
+    env.ledger()
+        .set_timestamp(START + DEFAULT_RECOVERY_INITIATION_COOLDOWN);
+    client.initiate_recovery(&guardian, &owner, &Address::generate(&env));
+    client.cancel_recovery(&owner);
+
+    assert_eq!(
+        client.try_set_recovery_cooldown(&owner, &(MAX_RECOVERY_INITIATION_COOLDOWN + 1)),
+        Err(Ok(RecoveryError::InvalidCooldown))
+    );
+    client.set_recovery_cooldown(&owner, &MAX_RECOVERY_INITIATION_COOLDOWN);
+    assert_eq!(
+        client.try_set_recovery_cooldown(&Address::generate(&env), &HOUR),
+        Err(Ok(RecoveryError::Unauthorized))
+    );
+}
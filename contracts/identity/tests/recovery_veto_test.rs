@@ -0,0 +1,261 @@
+//! Owner veto tests for guardian-initiated social recovery.
+//!
+//! Covers vetoing during the cooldown, the closed veto window after cooldown,
+//! the guardian override once `max_vetoes` is exhausted, and that veto rules
+//! are locked while a recovery is in progress.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use identity::{
+    recovery::{RecoveryError, VetoConfig},
+    IdentityContract, IdentityContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _, MockAuth, MockAuthInvoke},
+    Address, Env, IntoVal,
+};
+
+fn setup() -> (Env, IdentityContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(IdentityContract, ());
+    let client = IdentityContractClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    client.initialize(&owner);
+
+    (env, client, owner)
+}
+
+fn add_guardians(
+    env: &Env,
+    client: &IdentityContractClient,
+    owner: &Address,
+) -> (Address, Address) {
+    let g1 = Address::generate(env);
+    let g2 = Address::generate(env);
+    client.add_guardian(owner, &g1);
+    client.add_guardian(owner, &g2);
+    client.add_guardian(owner, &Address::generate(env));
+    client.set_recovery_threshold(owner, &2);
+    client.set_recovery_cooldown(owner, &0);
+    (g1, g2)
+}
+
+fn start_recovery(
+    env: &Env,
+    client: &IdentityContractClient,
+    owner: &Address,
+    guardians: &(Address, Address),
+) -> Address {
+    let new_owner = Address::generate(env);
+    client.initiate_recovery(&guardians.0, owner, &new_owner);
+    client.approve_recovery(&guardians.1, owner);
+    new_owner
+}
+
+#[test]
+fn test_owner_vetoes_during_cooldown() {
+    let (env, client, owner) = setup();
+    let guardians = add_guardians(&env, &client, &owner);
+    start_recovery(&env, &client, &owner, &guardians);
+
+    assert_eq!(client.veto_recovery(&owner), 1);
+    assert!(client.get_recovery_request(&owner).is_none());
+    assert_eq!(client.get_veto_count(&owner), 1);
+    assert!(client.is_owner_active(&owner));
+
+    assert_eq!(
+        client.try_veto_recovery(&owner),
+        Err(Ok(RecoveryError::NoActiveRecovery))
+    );
+}
+
+#[test]
+fn test_veto_rejected_after_cooldown() {
+    let (env, client, owner) = setup();
+    let guardians = add_guardians(&env, &client, &owner);
+    start_recovery(&env, &client, &owner, &guardians);
+
+    let req = client.get_recovery_request(&owner).unwrap();
+    env.ledger().set_timestamp(req.execute_after);
+
+    assert_eq!(
+        client.try_veto_recovery(&owner),
+        Err(Ok(RecoveryError::VetoWindowClosed))
+    );
+    assert!(client.get_recovery_request(&owner).is_some());
+}
+
+#[test]
+fn test_guardians_override_after_max_vetoes() {
+    let (env, client, owner) = setup();
+    let guardians = add_guardians(&env, &client, &owner);
+    let config = VetoConfig {
+        veto_signer: Some(Address::generate(&env)),
+        max_vetoes: 2,
+        notify_guardians: true,
+    };
+    client.set_veto_config(&owner, &config);
+    assert_eq!(client.get_veto_config(&owner), Some(config));
+
+    start_recovery(&env, &client, &owner, &guardians);
+    client.veto_recovery(&owner);
+    start_recovery(&env, &client, &owner, &guardians);
+    client.veto_recovery(&owner);
+
+    // Limit reached: the owner can no longer block guardians.
+    let new_owner = start_recovery(&env, &client, &owner, &guardians);
+    assert_eq!(
+        client.try_veto_recovery(&owner),
+        Err(Ok(RecoveryError::VetoLimitReached))
+    );
+    assert_eq!(
+        client.try_cancel_recovery(&owner),
+        Err(Ok(RecoveryError::VetoLimitReached))
+    );
+
+    let req = client.get_recovery_request(&owner).unwrap();
+    env.ledger().set_timestamp(req.execute_after + 1);
+    let caller = Address::generate(&env);
+    assert_eq!(client.execute_recovery(&caller, &owner), new_owner);
+    assert!(client.is_owner_active(&new_owner));
+}
+
+#[test]
+fn test_veto_config_locked_during_recovery() {
+    let (env, client, owner) = setup();
+    let guardians = add_guardians(&env, &client, &owner);
+    start_recovery(&env, &client, &owner, &guardians);
+
+    let config = VetoConfig {
+        veto_signer: None,
+        max_vetoes: 0,
+        notify_guardians: false,
+    };
+    assert_eq!(
+        client.try_set_veto_config(&owner, &config),
+        Err(Ok(RecoveryError::RecoveryAlreadyActive))
+    );
+    assert!(client.get_veto_config(&owner).is_none());
+}
+
+#[test]
+fn test_repeated_cancels_count_as_vetoes() {
+    let (env, client, owner) = setup();
+    let guardians = add_guardians(&env, &client, &owner);
+    client.set_veto_config(
+        &owner,
+        &VetoConfig {
+            veto_signer: None,
+            max_vetoes: 2,
+            notify_guardians: false,
+        },
+    );
+
+    start_recovery(&env, &client, &owner, &guardians);
+    client.cancel_recovery(&owner);
+    start_recovery(&env, &client, &owner, &guardians);
+    client.veto_recovery(&owner);
+    assert_eq!(client.get_veto_count(&owner), 2);
+
+    // Cancelling cannot be used to block guardians past the limit either.
+    start_recovery(&env, &client, &owner, &guardians);
+    assert_eq!(
+        client.try_cancel_recovery(&owner),
+        Err(Ok(RecoveryError::VetoLimitReached))
+    );
+    assert!(client.get_recovery_request(&owner).is_some());
+}
+
+#[test]
+fn test_cancel_requires_veto_signer() {
+    let (env, client, owner) = setup();
+    let guardians = add_guardians(&env, &client, &owner);
+    let signer = Address::generate(&env);
+    client.set_veto_config(
+        &owner,
+        &VetoConfig {
+            veto_signer: Some(signer.clone()),
+            max_vetoes: 3,
+            notify_guardians: false,
+        },
+    );
+    start_recovery(&env, &client, &owner, &guardians);
+
+    // Only the owner signs; the veto signer's authorization is missing.
+    env.mock_auths(&[MockAuth {
+        address: &owner,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "cancel_recovery",
+            args: (owner.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_cancel_recovery(&owner).is_err());
+    assert!(client.get_recovery_request(&owner).is_some());
+    assert_eq!(client.get_veto_count(&owner), 0);
+
+    env.mock_all_auths();
+    client.cancel_recovery(&owner);
+    assert_eq!(client.get_veto_count(&owner), 1);
+}
+
+#[test]
+fn test_veto_config_can_only_be_tightened() {
+    let (env, client, owner) = setup();
+    let guardians = add_guardians(&env, &client, &owner);
+    let signer = Address::generate(&env);
+    let config = VetoConfig {
+        veto_signer: Some(signer.clone()),
+        max_vetoes: 2,
+        notify_guardians: true,
+    };
+    client.set_veto_config(&owner, &config);
+    start_recovery(&env, &client, &owner, &guardians);
+    client.veto_recovery(&owner);
+
+    for loosened in [
+        VetoConfig {
+            max_vetoes: 0,
+            ..config.clone()
+        },
+        VetoConfig {
+            max_vetoes: 3,
+            ..config.clone()
+        },
+        VetoConfig {
+            veto_signer: None,
+            ..config.clone()
+        },
+        VetoConfig {
+            veto_signer: Some(Address::generate(&env)),
+            ..config.clone()
+        },
+        VetoConfig {
+            notify_guardians: false,
+            ..config.clone()
+        },
+    ] {
+        assert_eq!(
+            client.try_set_veto_config(&owner, &loosened),
+            Err(Ok(RecoveryError::VetoConfigLoosened))
+        );
+    }
+    assert_eq!(client.get_veto_config(&owner), Some(config.clone()));
+
+    // Tightening is allowed and keeps the vetoes already spent.
+    let tightened = VetoConfig {
+        max_vetoes: 1,
+        ..config
+    };
+    client.set_veto_config(&owner, &tightened);
+    assert_eq!(client.get_veto_count(&owner), 1);
+    start_recovery(&env, &client, &owner, &guardians);
+    assert_eq!(
+        client.try_veto_recovery(&owner),
+        Err(Ok(RecoveryError::VetoLimitReached))
+    );
+}
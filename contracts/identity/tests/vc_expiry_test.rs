@@ -28,6 +28,23 @@ fn make_proof(env: &Env) -> (Bytes, Bytes, Bytes, Vec<BytesN<32>>) {
     )
 }
 
+/// Proof bytes the mock BN254 verifier accepts: `a.x[0] == 1`,
+/// `c.x[0] == 1`, non-zero G2 limbs and `public_inputs[0][0] == 1`.
+fn make_valid_proof(env: &Env) -> (Bytes, Bytes, Bytes, Vec<BytesN<32>>) {
+    let mut g1 = [1u8; 64];
+    g1[32] = 2;
+    let mut pi = [0u8; 32];
+    pi[0] = 1;
+    let mut public_inputs = Vec::new(env);
+    public_inputs.push_back(BytesN::from_array(env, &pi));
+    (
+        Bytes::from_array(env, &g1),
+        Bytes::from_array(env, &[1u8; 128]),
+        Bytes::from_array(env, &g1),
+        public_inputs,
+    )
+}
+
 fn setup_verifier(env: &Env, client: &IdentityContractClient, owner: &Address) {
     use zk_verifier::vk::{G1Point, G2Point, VerificationKey};
     use zk_verifier::{ZkVerifierContract, ZkVerifierContractClient};
@@ -184,16 +201,17 @@ fn test_long_term_credential_remains_valid_at_large_timestamp() {
 
     let user = Address::generate(&env);
     let resource_id = BytesN::from_array(&env, &[5u8; 32]);
-    let (pa, pb, pc, pi) = make_proof(&env);
+    let (pa, pb, pc, pi) = make_valid_proof(&env);
 
-    // u64::MAX expiry is never exceeded
+    // u64::MAX expiry is never exceeded, and the request sent to the
+    // verifier is clamped to its maximum proof lifetime.
     let result =
         client.try_verify_zk_credential(&user, &resource_id, &pa, &pb, &pc, &pi, &u64::MAX);
 
-    assert_ne!(
+    assert_eq!(
         result,
-        Err(Ok(CredentialError::CredentialExpired)),
-        "long-term credential with u64::MAX expiry must remain valid"
+        Ok(Ok(true)),
+        "long-term credential with u64::MAX expiry must verify"
     );
 }
 
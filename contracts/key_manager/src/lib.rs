@@ -8,8 +8,8 @@ mod hierarchy;
 mod rotation;
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Symbol, Vec,
 };
 
 use identity::IdentityContractClient;
@@ -27,6 +27,21 @@ const KEY: Symbol = symbol_short!("KEY");
 const KEY_VER: Symbol = symbol_short!("KEY_VER");
 const RECOVERY: Symbol = symbol_short!("RECOV");
 const AUDIT: Symbol = symbol_short!("AUDIT");
+const MK_MSIG: Symbol = symbol_short!("MK_MSIG");
+const MK_PROP: Symbol = symbol_short!("MK_PROP");
+const MK_PCTR: Symbol = symbol_short!("MK_PCTR");
+const DER_ENF: Symbol = symbol_short!("DER_ENF");
+const OWN_KEYS: Symbol = symbol_short!("OWN_KEYS");
+
+/// Operation a key's `allowed_ops` must include for record-key derivation
+/// once derive-time policy enforcement is enabled.
+pub const RECORD_DERIVE: Symbol = symbol_short!("REC_DERIV");
+
+/// Most distinct operations a `KeyPolicy` may list in `allowed_ops`.
+pub const MAX_ALLOWED_OPS: u32 = 32;
+
+/// Most record ids `derive_record_keys` accepts in one call.
+pub const MAX_RECORD_KEYS_PER_CALL: u32 = 256;
 
 const RECOVERY_COOLDOWN: u64 = 86_400; // 24 hours
 
@@ -61,7 +76,15 @@ pub struct KeyPolicy {
     pub max_uses: u32,
     pub not_before: u64,
     pub not_after: u64,
+    /// Operations the key may be used for; empty allows any. Duplicates are
+    /// dropped when the policy is stored, and at most `MAX_ALLOWED_OPS`
+    /// distinct entries are accepted.
     pub allowed_ops: Vec<Symbol>,
+    /// Uses allowed per budget period; `0` disables the rate limit.
+    pub usage_budget_per_period: u32,
+    /// Length of a budget period. Periods are aligned to the key's
+    /// `created_at`, and the budget refills at each boundary.
+    pub budget_period_seconds: u64,
 }
 
 #[contracttype]
@@ -80,6 +103,23 @@ pub struct KeyRecord {
     pub uses: u32,
     pub policy: KeyPolicy,
     pub status: KeyStatus,
+    /// Start of the budget period `period_uses` counts against.
+    pub period_start: u64,
+    pub period_uses: u32,
+}
+
+/// Snapshot of a key's per-period usage budget.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BudgetStatus {
+    /// Whether the key's policy sets a per-period budget at all.
+    pub enabled: bool,
+    pub budget: u32,
+    pub used: u32,
+    pub remaining: u32,
+    pub period_start: u64,
+    /// When the budget next refills.
+    pub resets_at: u64,
 }
 
 #[contracttype]
@@ -120,6 +160,45 @@ pub struct AuditEntry {
     pub entry_hash: BytesN<32>,
 }
 
+/// Optional M-of-N co-administrator requirement for master-key operations.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MasterKeyMultisig {
+    pub enabled: bool,
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Parameters for creating a master key through the multisig flow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MasterKeyParams {
+    pub key_type: KeyType,
+    pub policy: KeyPolicy,
+    pub rotation_interval: u64,
+    pub key_bytes: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MasterKeyAction {
+    Create(MasterKeyParams),
+    Revoke(BytesN<32>),
+    /// Replace (or disable) the multisig configuration itself.
+    Configure(MasterKeyMultisig),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MasterKeyProposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub action: MasterKeyAction,
+    pub approvals: Vec<Address>,
+    pub created_at: u64,
+    pub executed: bool,
+}
+
 #[contracterror]
 #[derive(Clone, Debug, Eq, PartialEq, Copy)]
 #[repr(u32)]
@@ -139,6 +218,11 @@ pub enum ContractError {
     InsufficientApprovals = 13,
     CooldownNotExpired = 14,
     KeyRevoked = 15,
+    MultisigRequired = 16,
+    InvalidMultisigConfig = 17,
+    ProposalNotFound = 18,
+    ProposalAlreadyExecuted = 19,
+    NotASigner = 20,
 }
 
 #[contract]
@@ -171,6 +255,22 @@ impl KeyManagerContract {
         Ok(())
     }
 
+    /// Enable or disable `allowed_ops` enforcement for record-key derivation.
+    /// Disabled by default so existing keys keep deriving unchecked.
+    pub fn set_derive_policy_enforced(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+        env.storage().instance().set(&DER_ENF, &enabled);
+        Ok(())
+    }
+
+    pub fn is_derive_policy_enforced(env: Env) -> bool {
+        env.storage().instance().get(&DER_ENF).unwrap_or(false)
+    }
+
     pub fn create_master_key(
         env: Env,
         caller: Address,
@@ -180,40 +280,23 @@ impl KeyManagerContract {
         key_bytes: BytesN<32>,
     ) -> Result<BytesN<32>, ContractError> {
         Self::require_admin(&env, &caller)?;
-        Self::validate_policy(&policy)?;
-
-        let now = env.ledger().timestamp();
-        let id = Self::new_key_id(&env, None, key_type.clone(), KeyLevel::Master, now, 0);
-        let chain_code = Self::chain_code_from(&env, &key_bytes, 0, false);
+        if Self::master_multisig_enabled(&env) {
+            return Err(ContractError::MultisigRequired);
+        }
+        let policy = Self::validate_policy(&env, policy)?;
 
-        let record = KeyRecord {
-            id: id.clone(),
-            owner: caller.clone(),
-            parent: None,
-            level: KeyLevel::Master,
+        let params = MasterKeyParams {
             key_type,
-            chain_code,
-            current_version: 1,
-            created_at: now,
-            last_rotated: now,
-            rotation_interval,
-            uses: 0,
             policy,
-            status: KeyStatus::Active,
+            rotation_interval,
+            key_bytes,
         };
-
-        Self::store_key_record(&env, &record);
-        Self::store_key_version(&env, &record.id, 1, key_bytes, now);
-
-        Self::audit(
+        Ok(Self::create_master_key_inner(
             &env,
+            caller.clone(),
             caller,
-            symbol_short!("KEY_NEW"),
-            Some(record.id.clone()),
-            &record.id,
-        );
-
-        Ok(record.id)
+            params,
+        ))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -233,7 +316,7 @@ impl KeyManagerContract {
         Self::require_owner_or_admin(&env, &caller, &parent.owner)?;
         Self::ensure_active(&parent)?;
         validate_child_level(parent.level.clone(), child_level.clone())?;
-        Self::validate_policy(&policy)?;
+        let policy = Self::validate_policy(&env, policy)?;
 
         let (parent_key, _) = Self::load_key_version(&env, &parent_id, parent.current_version)?;
         let (child_key, child_chain) =
@@ -263,10 +346,13 @@ impl KeyManagerContract {
             uses: 0,
             policy,
             status: KeyStatus::Active,
+            period_start: now,
+            period_uses: 0,
         };
 
         Self::store_key_record(&env, &record);
         Self::store_key_version(&env, &record.id, 1, child_key, now);
+        Self::index_owner_key(&env, &record.owner, &record.id);
 
         parent.uses = parent.uses.saturating_add(1);
         Self::store_key_record(&env, &parent);
@@ -292,7 +378,9 @@ impl KeyManagerContract {
         let mut record = Self::load_key_record(&env, &key_id)?;
         Self::require_owner_or_admin(&env, &caller, &record.owner)?;
         Self::ensure_active(&record)?;
-        Self::enforce_policy(&record, &operation, env.ledger().timestamp())?;
+        let now = env.ledger().timestamp();
+        Self::enforce_policy(&record, &operation, now)?;
+        Self::draw_usage_budget(&mut record, now)?;
 
         record.uses = record.uses.saturating_add(1);
         Self::store_key_record(&env, &record);
@@ -308,6 +396,8 @@ impl KeyManagerContract {
         Ok(key_bytes)
     }
 
+    /// When derive-time policy enforcement is enabled, the key's
+    /// `allowed_ops` must permit [`RECORD_DERIVE`].
     pub fn derive_record_key(
         env: Env,
         key_id: BytesN<32>,
@@ -315,6 +405,7 @@ impl KeyManagerContract {
     ) -> Result<DerivedKey, ContractError> {
         let record = Self::load_key_record(&env, &key_id)?;
         Self::ensure_active(&record)?;
+        Self::enforce_derive_policy(&env, &record)?;
         let (key_bytes, _) = Self::load_key_version(&env, &key_id, record.current_version)?;
         let derived = derive_record_key(&env, &key_bytes, record_id);
         Ok(DerivedKey {
@@ -323,13 +414,41 @@ impl KeyManagerContract {
         })
     }
 
+    /// Batch form of `derive_record_key`: derives a key for each of
+    /// `record_ids` from the current key version, loaded once, in input
+    /// order. More than `MAX_RECORD_KEYS_PER_CALL` ids is rejected with
+    /// `InvalidPolicy`.
+    pub fn derive_record_keys(
+        env: Env,
+        key_id: BytesN<32>,
+        record_ids: Vec<u64>,
+    ) -> Result<Vec<DerivedKey>, ContractError> {
+        if record_ids.len() > MAX_RECORD_KEYS_PER_CALL {
+            return Err(ContractError::InvalidPolicy);
+        }
+        let record = Self::load_key_record(&env, &key_id)?;
+        Self::ensure_active(&record)?;
+        Self::enforce_derive_policy(&env, &record)?;
+        let (key_bytes, _) = Self::load_key_version(&env, &key_id, record.current_version)?;
+
+        let mut keys = Vec::new(&env);
+        for record_id in record_ids.iter() {
+            keys.push_back(DerivedKey {
+                key: derive_record_key(&env, &key_bytes, record_id),
+                version: record.current_version,
+            });
+        }
+        Ok(keys)
+    }
+
     pub fn derive_record_key_with_version(
         env: Env,
         key_id: BytesN<32>,
         record_id: u64,
         version: u32,
     ) -> Result<DerivedKey, ContractError> {
-        let _record = Self::load_key_record(&env, &key_id)?;
+        let record = Self::load_key_record(&env, &key_id)?;
+        Self::enforce_derive_policy(&env, &record)?;
         let (key_bytes, _) = Self::load_key_version(&env, &key_id, version)?;
         let derived = derive_record_key(&env, &key_bytes, record_id);
         Ok(DerivedKey {
@@ -373,20 +492,199 @@ impl KeyManagerContract {
 
     pub fn revoke_key(env: Env, caller: Address, key_id: BytesN<32>) -> Result<(), ContractError> {
         caller.require_auth();
-        let mut record = Self::load_key_record(&env, &key_id)?;
+        let record = Self::load_key_record(&env, &key_id)?;
         Self::require_owner_or_admin(&env, &caller, &record.owner)?;
-        record.status = KeyStatus::Revoked;
-        Self::store_key_record(&env, &record);
-        Self::audit(
-            &env,
-            caller,
-            symbol_short!("KEY_RVK"),
-            Some(key_id),
-            &record.id,
-        );
+        if record.level == KeyLevel::Master && Self::master_multisig_enabled(&env) {
+            return Err(ContractError::MultisigRequired);
+        }
+        Self::revoke_key_inner(&env, caller, record);
+        Ok(())
+    }
+
+    // ── Master-key multisig ──────────────────────────────────────────────────
+
+    /// Configure the co-administrator set and approval threshold required for
+    /// master-key creation and revocation. Admin only, and only while
+    /// multisig is not enabled: once it is, changes (including disabling it)
+    /// must go through `propose_master_key_action` with
+    /// `MasterKeyAction::Configure`.
+    pub fn configure_master_key_multisig(
+        env: Env,
+        caller: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller)?;
+        if Self::master_multisig_enabled(&env) {
+            return Err(ContractError::MultisigRequired);
+        }
+
+        let config = MasterKeyMultisig {
+            enabled,
+            signers,
+            threshold,
+        };
+        Self::validate_multisig_config(&config)?;
+        env.storage().instance().set(&MK_MSIG, &config);
+        Ok(())
+    }
+
+    pub fn get_master_key_multisig(env: Env) -> Option<MasterKeyMultisig> {
+        env.storage().instance().get(&MK_MSIG)
+    }
+
+    /// Propose a master-key creation or revocation. The proposer must be a
+    /// registered co-administrator and counts as the first approval.
+    pub fn propose_master_key_action(
+        env: Env,
+        proposer: Address,
+        action: MasterKeyAction,
+    ) -> Result<u64, ContractError> {
+        proposer.require_auth();
+        let config = Self::load_master_multisig(&env)?;
+        if !config.signers.contains(&proposer) {
+            return Err(ContractError::NotASigner);
+        }
+
+        let action = match action {
+            MasterKeyAction::Create(mut params) => {
+                params.policy = Self::validate_policy(&env, params.policy)?;
+                MasterKeyAction::Create(params)
+            }
+            MasterKeyAction::Revoke(key_id) => {
+                let record = Self::load_key_record(&env, &key_id)?;
+                if record.level != KeyLevel::Master {
+                    return Err(ContractError::InvalidHierarchy);
+                }
+                Self::ensure_active(&record)?;
+                MasterKeyAction::Revoke(key_id)
+            }
+            MasterKeyAction::Configure(next) => {
+                Self::validate_multisig_config(&next)?;
+                MasterKeyAction::Configure(next)
+            }
+        };
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&MK_PCTR)
+            .unwrap_or(0u64)
+            .saturating_add(1);
+        env.storage().instance().set(&MK_PCTR, &id);
+
+        let mut approvals = Vec::new(&env);
+        approvals.push_back(proposer.clone());
+        let proposal = MasterKeyProposal {
+            id,
+            proposer: proposer.clone(),
+            action,
+            approvals,
+            created_at: env.ledger().timestamp(),
+            executed: false,
+        };
+        env.storage().persistent().set(&(MK_PROP, id), &proposal);
+
+        let details = Self::hash_master_proposal(&env, &proposal);
+        Self::audit(&env, proposer, symbol_short!("MK_PROP"), None, &details);
+
+        Ok(id)
+    }
+
+    pub fn approve_master_key_action(
+        env: Env,
+        approver: Address,
+        proposal_id: u64,
+    ) -> Result<(), ContractError> {
+        approver.require_auth();
+        let config = Self::load_master_multisig(&env)?;
+        if !config.signers.contains(&approver) {
+            return Err(ContractError::NotASigner);
+        }
+
+        let mut proposal = Self::load_master_proposal(&env, proposal_id)?;
+        if proposal.executed {
+            return Err(ContractError::ProposalAlreadyExecuted);
+        }
+        if proposal.approvals.contains(&approver) {
+            return Err(ContractError::AlreadyApproved);
+        }
+        proposal.approvals.push_back(approver.clone());
+        env.storage()
+            .persistent()
+            .set(&(MK_PROP, proposal_id), &proposal);
+
+        let details = Self::hash_master_proposal(&env, &proposal);
+        Self::audit(&env, approver, symbol_short!("MK_APP"), None, &details);
+
         Ok(())
     }
 
+    /// Execute an approved master-key proposal once the threshold is met.
+    /// Returns the created or revoked key id, or for a configuration change
+    /// the proposal's audit details hash.
+    pub fn execute_master_key_action(
+        env: Env,
+        caller: Address,
+        proposal_id: u64,
+    ) -> Result<BytesN<32>, ContractError> {
+        caller.require_auth();
+        let config = Self::load_master_multisig(&env)?;
+        if !config.signers.contains(&caller) {
+            return Err(ContractError::NotASigner);
+        }
+
+        let mut proposal = Self::load_master_proposal(&env, proposal_id)?;
+        if proposal.executed {
+            return Err(ContractError::ProposalAlreadyExecuted);
+        }
+
+        // Only approvals from the *current* signer set count, so removing a
+        // co-administrator invalidates their outstanding approvals.
+        let mut valid_approvals: u32 = 0;
+        for approver in proposal.approvals.iter() {
+            if config.signers.contains(&approver) {
+                valid_approvals = valid_approvals.saturating_add(1);
+            }
+        }
+        if valid_approvals < config.threshold {
+            return Err(ContractError::InsufficientApprovals);
+        }
+
+        proposal.executed = true;
+        env.storage()
+            .persistent()
+            .set(&(MK_PROP, proposal_id), &proposal);
+
+        match proposal.action {
+            MasterKeyAction::Create(params) => {
+                let owner: Address = env
+                    .storage()
+                    .instance()
+                    .get(&ADMIN)
+                    .ok_or(ContractError::NotInitialized)?;
+                Ok(Self::create_master_key_inner(&env, caller, owner, params))
+            }
+            MasterKeyAction::Revoke(key_id) => {
+                let record = Self::load_key_record(&env, &key_id)?;
+                Self::ensure_active(&record)?;
+                Self::revoke_key_inner(&env, caller, record);
+                Ok(key_id)
+            }
+            MasterKeyAction::Configure(ref next) => {
+                env.storage().instance().set(&MK_MSIG, next);
+                let details = Self::hash_master_proposal(&env, &proposal);
+                Self::audit(&env, caller, symbol_short!("MK_CFG"), None, &details);
+                Ok(details)
+            }
+        }
+    }
+
+    pub fn get_master_key_proposal(env: Env, proposal_id: u64) -> Option<MasterKeyProposal> {
+        env.storage().persistent().get(&(MK_PROP, proposal_id))
+    }
+
     pub fn attest_key(env: Env, key_id: BytesN<32>) -> Result<BytesN<32>, ContractError> {
         let record = Self::load_key_record(&env, &key_id)?;
         Ok(attest_record(&env, &record))
@@ -471,6 +769,36 @@ impl KeyManagerContract {
         Ok(())
     }
 
+    /// Abort a pending recovery of `key_id` before it executes. Only the
+    /// key's owner may cancel.
+    pub fn cancel_recovery(
+        env: Env,
+        owner: Address,
+        key_id: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+        let record = Self::load_key_record(&env, &key_id)?;
+        if owner != record.owner {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let key = (RECOVERY, key_id.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(ContractError::RecoveryNotActive);
+        }
+        env.storage().persistent().remove(&key);
+
+        Self::audit(
+            &env,
+            owner,
+            symbol_short!("REC_CAN"),
+            Some(key_id),
+            &record.id,
+        );
+
+        Ok(())
+    }
+
     pub fn execute_recovery(
         env: Env,
         caller: Address,
@@ -535,10 +863,86 @@ impl KeyManagerContract {
         env.storage().persistent().get(&(KEY, key_id))
     }
 
+    /// Current period's usage budget for `key_id`, accounting for any refill
+    /// that is due but not yet applied by a `use_key` call.
+    pub fn get_usage_budget_status(
+        env: Env,
+        key_id: BytesN<32>,
+    ) -> Result<BudgetStatus, ContractError> {
+        let record = Self::load_key_record(&env, &key_id)?;
+        let policy = &record.policy;
+        if policy.usage_budget_per_period == 0 {
+            return Ok(BudgetStatus {
+                enabled: false,
+                budget: 0,
+                used: 0,
+                remaining: 0,
+                period_start: 0,
+                resets_at: 0,
+            });
+        }
+        let now = env.ledger().timestamp();
+        let period_start = Self::budget_period_start(&record, now);
+        let used = if period_start == record.period_start {
+            record.period_uses
+        } else {
+            0
+        };
+        Ok(BudgetStatus {
+            enabled: true,
+            budget: policy.usage_budget_per_period,
+            used,
+            remaining: policy.usage_budget_per_period.saturating_sub(used),
+            period_start,
+            resets_at: period_start.saturating_add(policy.budget_period_seconds),
+        })
+    }
+
     pub fn get_key_version(env: Env, key_id: BytesN<32>, version: u32) -> Option<KeyVersion> {
         env.storage().persistent().get(&(KEY_VER, key_id, version))
     }
 
+    /// Every stored version of `key_id`, oldest first, up to its current
+    /// version. Versions that were never materialized are skipped. Owner or
+    /// admin only.
+    pub fn get_key_versions(
+        env: Env,
+        caller: Address,
+        key_id: BytesN<32>,
+    ) -> Result<Vec<KeyVersion>, ContractError> {
+        caller.require_auth();
+        let record = Self::load_key_record(&env, &key_id)?;
+        Self::require_owner_or_admin(&env, &caller, &record.owner)?;
+
+        let mut versions = Vec::new(&env);
+        for version in 1..=record.current_version {
+            if let Some(entry) = Self::get_key_version(env.clone(), key_id.clone(), version) {
+                versions.push_back(entry);
+            }
+        }
+        Ok(versions)
+    }
+
+    /// Ids of `owner`'s active keys whose rotation interval has elapsed, so a
+    /// keeper can rotate them in one pass. Keys without an interval are never
+    /// due.
+    pub fn keys_due_for_rotation(env: Env, owner: Address) -> Vec<BytesN<32>> {
+        let now = env.ledger().timestamp();
+        let mut due = Vec::new(&env);
+        for key_id in Self::owner_keys(&env, &owner).iter() {
+            let Some(record) = Self::get_key_record(env.clone(), key_id.clone()) else {
+                continue;
+            };
+            if record.status == KeyStatus::Active
+                && record.rotation_interval > 0
+                && rotation_due(now, record.last_rotated, record.rotation_interval)
+            {
+                due.push_back(key_id);
+            }
+        }
+        due
+    }
+
     pub fn get_audit_entry(env: Env, seq: u64) -> Option<AuditEntry> {
         env.storage().persistent().get(&(AUDIT, seq))
     }
@@ -547,6 +951,141 @@ impl KeyManagerContract {
         env.storage().instance().get(&AUDIT_TAIL)
     }
 
+    /// Recompute the audit hash chain over entries `from_seq..=to_seq` and
+    /// check every link. The first entry of the log must chain from the zero
+    /// hash, and a range ending at the latest entry must end at the stored
+    /// tail. Returns false at the first missing or inconsistent entry.
+    pub fn verify_audit_chain(env: Env, from_seq: u64, to_seq: u64) -> bool {
+        let latest: u64 = env.storage().instance().get(&AUDIT_SEQ).unwrap_or(0);
+        if from_seq == 0 || from_seq > to_seq || to_seq > latest {
+            return false;
+        }
+
+        let mut expected_prev: Option<BytesN<32>> = if from_seq == 1 {
+            Some(BytesN::from_array(&env, &[0u8; 32]))
+        } else {
+            Self::get_audit_entry(env.clone(), from_seq - 1).map(|e| e.entry_hash)
+        };
+        for seq in from_seq..=to_seq {
+            let Some(entry) = Self::get_audit_entry(env.clone(), seq) else {
+                return false;
+            };
+            if entry.seq != seq {
+                return false;
+            }
+            if let Some(prev) = &expected_prev {
+                if entry.prev_hash != *prev {
+                    return false;
+                }
+            }
+            if Self::hash_audit(&env, &entry.prev_hash, &entry.details_hash) != entry.entry_hash {
+                return false;
+            }
+            expected_prev = Some(entry.entry_hash);
+        }
+
+        to_seq < latest || expected_prev == Self::get_audit_tail(env)
+    }
+
+    fn create_master_key_inner(
+        env: &Env,
+        actor: Address,
+        owner: Address,
+        params: MasterKeyParams,
+    ) -> BytesN<32> {
+        let now = env.ledger().timestamp();
+        let id = Self::new_key_id(env, None, params.key_type.clone(), KeyLevel::Master, now, 0);
+        let chain_code = Self::chain_code_from(env, &params.key_bytes, 0, false);
+
+        let record = KeyRecord {
+            id: id.clone(),
+            owner,
+            parent: None,
+            level: KeyLevel::Master,
+            key_type: params.key_type,
+            chain_code,
+            current_version: 1,
+            created_at: now,
+            last_rotated: now,
+            rotation_interval: params.rotation_interval,
+            uses: 0,
+            policy: params.policy,
+            status: KeyStatus::Active,
+            period_start: now,
+            period_uses: 0,
+        };
+
+        Self::store_key_record(env, &record);
+        Self::store_key_version(env, &record.id, 1, params.key_bytes, now);
+        Self::index_owner_key(env, &record.owner, &record.id);
+
+        Self::audit(
+            env,
+            actor,
+            symbol_short!("KEY_NEW"),
+            Some(record.id.clone()),
+            &record.id,
+        );
+
+        record.id
+    }
+
+    fn revoke_key_inner(env: &Env, actor: Address, mut record: KeyRecord) {
+        record.status = KeyStatus::Revoked;
+        Self::store_key_record(env, &record);
+        Self::audit(
+            env,
+            actor,
+            symbol_short!("KEY_RVK"),
+            Some(record.id.clone()),
+            &record.id,
+        );
+    }
+
+    fn master_multisig_enabled(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, MasterKeyMultisig>(&MK_MSIG)
+            .map(|cfg| cfg.enabled)
+            .unwrap_or(false)
+    }
+
+    fn validate_multisig_config(config: &MasterKeyMultisig) -> Result<(), ContractError> {
+        let signers = &config.signers;
+        if config.enabled && (config.threshold == 0 || config.threshold > signers.len()) {
+            return Err(ContractError::InvalidMultisigConfig);
+        }
+        for i in 0..signers.len() {
+            if let Some(signer) = signers.get(i) {
+                if signers.first_index_of(&signer) != Some(i) {
+                    return Err(ContractError::InvalidMultisigConfig);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn load_master_multisig(env: &Env) -> Result<MasterKeyMultisig, ContractError> {
+        match env
+            .storage()
+            .instance()
+            .get::<_, MasterKeyMultisig>(&MK_MSIG)
+        {
+            Some(cfg) if cfg.enabled => Ok(cfg),
+            _ => Err(ContractError::InvalidMultisigConfig),
+        }
+    }
+
+    fn load_master_proposal(
+        env: &Env,
+        proposal_id: u64,
+    ) -> Result<MasterKeyProposal, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&(MK_PROP, proposal_id))
+            .ok_or(ContractError::ProposalNotFound)
+    }
+
     fn require_admin(env: &Env, caller: &Address) -> Result<(), ContractError> {
         caller.require_auth();
         let admin: Address = env
@@ -583,11 +1122,29 @@ impl KeyManagerContract {
         Ok(())
     }
 
-    fn validate_policy(policy: &KeyPolicy) -> Result<(), ContractError> {
+    /// Check `policy` and return it as it should be stored, with duplicate
+    /// `allowed_ops` removed (first occurrence kept).
+    fn validate_policy(env: &Env, mut policy: KeyPolicy) -> Result<KeyPolicy, ContractError> {
         if policy.not_after > 0 && policy.not_before > 0 && policy.not_after <= policy.not_before {
             return Err(ContractError::InvalidPolicy);
         }
-        Ok(())
+        if policy.usage_budget_per_period > 0 && policy.budget_period_seconds == 0 {
+            return Err(ContractError::InvalidPolicy);
+        }
+
+        // The cap is checked while deduplicating, so each entry is compared
+        // against at most `MAX_ALLOWED_OPS` kept ones.
+        let mut ops: Vec<Symbol> = Vec::new(env);
+        for op in policy.allowed_ops.iter() {
+            if !ops.contains(&op) {
+                if ops.len() == MAX_ALLOWED_OPS {
+                    return Err(ContractError::InvalidPolicy);
+                }
+                ops.push_back(op);
+            }
+        }
+        policy.allowed_ops = ops;
+        Ok(policy)
     }
 
     fn enforce_policy(
@@ -610,6 +1167,46 @@ impl KeyManagerContract {
         Ok(())
     }
 
+    /// Start of the budget period containing `now`, aligned to `created_at`.
+    fn budget_period_start(record: &KeyRecord, now: u64) -> u64 {
+        let period = record.policy.budget_period_seconds;
+        if period == 0 || now < record.created_at {
+            return record.period_start;
+        }
+        let elapsed = now - record.created_at;
+        record.created_at + elapsed - elapsed % period
+    }
+
+    /// Charge one use against the current period's budget, refilling it first
+    /// if a period boundary has passed since the last use.
+    fn draw_usage_budget(record: &mut KeyRecord, now: u64) -> Result<(), ContractError> {
+        let budget = record.policy.usage_budget_per_period;
+        if budget == 0 {
+            return Ok(());
+        }
+        let period_start = Self::budget_period_start(record, now);
+        if period_start != record.period_start {
+            record.period_start = period_start;
+            record.period_uses = 0;
+        }
+        if record.period_uses >= budget {
+            return Err(ContractError::PolicyViolation);
+        }
+        record.period_uses = record.period_uses.saturating_add(1);
+        Ok(())
+    }
+
+    fn enforce_derive_policy(env: &Env, record: &KeyRecord) -> Result<(), ContractError> {
+        if !Self::is_derive_policy_enforced(env.clone()) {
+            return Ok(());
+        }
+        let ops = &record.policy.allowed_ops;
+        if !ops.is_empty() && !ops.contains(&RECORD_DERIVE) {
+            return Err(ContractError::PolicyViolation);
+        }
+        Ok(())
+    }
+
     fn new_key_id(
         env: &Env,
         parent: Option<BytesN<32>>,
@@ -658,6 +1255,21 @@ impl KeyManagerContract {
             .set(&(KEY, record.id.clone()), record);
     }
 
+    fn owner_keys(env: &Env, owner: &Address) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&(OWN_KEYS, owner.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn index_owner_key(env: &Env, owner: &Address, key_id: &BytesN<32>) {
+        let mut keys = Self::owner_keys(env, owner);
+        keys.push_back(key_id.clone());
+        env.storage()
+            .persistent()
+            .set(&(OWN_KEYS, owner.clone()), &keys);
+    }
+
     fn store_key_version(
         env: &Env,
         key_id: &BytesN<32>,
@@ -750,6 +1362,31 @@ impl KeyManagerContract {
         env.crypto().sha256(&data).into()
     }
 
+    fn hash_master_proposal(env: &Env, proposal: &MasterKeyProposal) -> BytesN<32> {
+        let mut data = Bytes::new(env);
+        data.extend_from_array(&proposal.id.to_be_bytes());
+        match &proposal.action {
+            MasterKeyAction::Create(params) => {
+                data.extend_from_array(b"create");
+                data.extend_from_array(&params.key_bytes.to_array());
+            }
+            MasterKeyAction::Revoke(key_id) => {
+                data.extend_from_array(b"revoke");
+                data.extend_from_array(&key_id.to_array());
+            }
+            MasterKeyAction::Configure(config) => {
+                data.extend_from_array(b"config");
+                data.extend_from_array(&[u8::from(config.enabled)]);
+                data.extend_from_array(&config.threshold.to_be_bytes());
+                for signer in config.signers.iter() {
+                    data.append(&signer.to_xdr(env));
+                }
+            }
+        }
+        data.extend_from_array(&proposal.approvals.len().to_be_bytes());
+        env.crypto().sha256(&data).into()
+    }
+
     fn hash_recovery_details(env: &Env, request: &RecoveryRequest, threshold: u32) -> BytesN<32> {
         let mut data = Bytes::new(env);
         data.extend_from_array(&request.key_id.to_array());
@@ -761,4 +1398,3 @@ impl KeyManagerContract {
         env.crypto().sha256(&data).into()
     }
 }
-
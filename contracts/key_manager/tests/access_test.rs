@@ -35,6 +35,8 @@ fn test_unauthenticated_admin_calls() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_bytes = BytesN::from_array(&env, &[0u8; 32]);
     let res_create_master = client.try_create_master_key(
@@ -57,6 +59,8 @@ fn test_unauthenticated_owner_level_calls() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_bytes = BytesN::from_array(&env, &[1u8; 32]);
     let key_id = client.create_master_key(&admin, &KeyType::Signing, &policy, &0, &key_bytes);
@@ -4,6 +4,7 @@ use identity::IdentityContract;
 use identity::IdentityContractClient;
 use key_manager::{
     ContractError, KeyLevel, KeyManagerContract, KeyManagerContractClient, KeyPolicy, KeyType,
+    MAX_RECORD_KEYS_PER_CALL,
 };
 use soroban_sdk::{
     symbol_short, testutils::Address as _, testutils::Ledger, Address, BytesN, Env, Vec,
@@ -40,6 +41,8 @@ fn test_rotation_preserves_versions() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[7u8; 32]);
@@ -57,6 +60,78 @@ fn test_rotation_preserves_versions() {
     assert_ne!(derived_v1_again.key, derived_v2.key);
 }
 
+#[test]
+fn test_derive_record_keys_matches_single_derivation() {
+    let (env, client, _identity, admin) = setup();
+
+    let policy = KeyPolicy {
+        max_uses: 0,
+        not_before: 0,
+        not_after: 0,
+        allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
+    };
+    let key_bytes = BytesN::from_array(&env, &[7u8; 32]);
+    let key_id = client.create_master_key(&admin, &KeyType::Encryption, &policy, &0u64, &key_bytes);
+    client.rotate_key(&admin, &key_id);
+
+    let record_ids = Vec::from_array(&env, [42u64, 7, 1_000, 7]);
+    let keys = client.derive_record_keys(&key_id, &record_ids);
+    assert_eq!(keys.len(), record_ids.len());
+    for (record_id, derived) in record_ids.iter().zip(keys.iter()) {
+        assert_eq!(derived, client.derive_record_key(&key_id, &record_id));
+    }
+    assert!(client
+        .derive_record_keys(&key_id, &Vec::new(&env))
+        .is_empty());
+
+    let mut too_many = Vec::new(&env);
+    for record_id in 0..=MAX_RECORD_KEYS_PER_CALL as u64 {
+        too_many.push_back(record_id);
+    }
+    assert!(matches!(
+        client.try_derive_record_keys(&key_id, &too_many),
+        Err(Ok(ContractError::InvalidPolicy))
+    ));
+}
+
+#[test]
+fn test_get_key_versions_lists_every_rotation() {
+    let (env, client, _identity, admin) = setup();
+
+    let policy = KeyPolicy {
+        max_uses: 0,
+        not_before: 0,
+        not_after: 0,
+        allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
+    };
+    let key_bytes = BytesN::from_array(&env, &[7u8; 32]);
+    let key_id = client.create_master_key(&admin, &KeyType::Encryption, &policy, &0u64, &key_bytes);
+
+    for _ in 0..3 {
+        env.ledger().with_mut(|li| li.timestamp += 100);
+        client.rotate_key(&admin, &key_id);
+    }
+
+    let versions = client.get_key_versions(&admin, &key_id);
+    assert_eq!(versions.len(), 4);
+    for i in 1..versions.len() {
+        let prev = versions.get(i - 1).unwrap();
+        let next = versions.get(i).unwrap();
+        assert_eq!(next.version, prev.version + 1);
+        assert!(next.created_at > prev.created_at);
+    }
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_get_key_versions(&stranger, &key_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
 #[test]
 fn test_recovery_flow() {
     let (env, client, identity, admin) = setup();
@@ -75,6 +150,8 @@ fn test_recovery_flow() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[9u8; 32]);
@@ -92,6 +169,54 @@ fn test_recovery_flow() {
     assert_eq!(key_version.key_bytes, new_key);
 }
 
+#[test]
+fn test_owner_can_cancel_pending_recovery() {
+    let (env, client, identity, admin) = setup();
+
+    let guardian1 = Address::generate(&env);
+    let guardian2 = Address::generate(&env);
+    identity.add_guardian(&admin, &guardian1);
+    identity.add_guardian(&admin, &guardian2);
+    identity.set_recovery_threshold(&admin, &2);
+
+    let policy = KeyPolicy {
+        max_uses: 0,
+        not_before: 0,
+        not_after: 0,
+        allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
+    };
+    let key_bytes = BytesN::from_array(&env, &[11u8; 32]);
+    let key_id = client.create_master_key(&admin, &KeyType::Encryption, &policy, &0u64, &key_bytes);
+
+    client.initiate_recovery(&guardian1, &key_id, &BytesN::from_array(&env, &[12u8; 32]));
+    client.approve_recovery(&guardian2, &key_id);
+
+    // Guardians cannot call off a recovery; only the owner can.
+    assert_eq!(
+        client.try_cancel_recovery(&guardian1, &key_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.cancel_recovery(&admin, &key_id);
+    // Entries 1-3 are the key creation, initiation and approval.
+    let entry = client.get_audit_entry(&4u64).unwrap();
+    assert_eq!(entry.action, symbol_short!("REC_CAN"));
+    assert_eq!(Some(entry.entry_hash), client.get_audit_tail());
+
+    let now = env.ledger().timestamp();
+    env.ledger().set_timestamp(now + 86_401);
+    assert_eq!(
+        client.try_execute_recovery(&admin, &key_id),
+        Err(Ok(ContractError::RecoveryNotActive))
+    );
+    assert_eq!(
+        client.try_cancel_recovery(&admin, &key_id),
+        Err(Ok(ContractError::RecoveryNotActive))
+    );
+    assert_eq!(client.get_key_record(&key_id).unwrap().current_version, 1);
+}
+
 #[test]
 fn test_policy_enforcement() {
     let (env, client, _identity, admin) = setup();
@@ -104,6 +229,8 @@ fn test_policy_enforcement() {
         not_before: 0,
         not_after: 0,
         allowed_ops: ops,
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[3u8; 32]);
@@ -128,6 +255,8 @@ fn test_hierarchy_validation() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[5u8; 32]);
@@ -147,6 +276,95 @@ fn test_hierarchy_validation() {
     assert!(matches!(err, Err(Ok(ContractError::InvalidHierarchy))));
 }
 
+#[test]
+fn test_keys_due_for_rotation_respects_each_interval() {
+    let (env, client, _identity, admin) = setup();
+
+    let policy = KeyPolicy {
+        max_uses: 0,
+        not_before: 0,
+        not_after: 0,
+        allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
+    };
+    let key_bytes = BytesN::from_array(&env, &[6u8; 32]);
+    let master_id =
+        client.create_master_key(&admin, &KeyType::Encryption, &policy, &100u64, &key_bytes);
+    let child_id = client.derive_key(
+        &admin,
+        &master_id,
+        &KeyLevel::Contract,
+        &1u32,
+        &false,
+        &KeyType::Encryption,
+        &policy,
+        &1_000u64,
+    );
+    // No interval: never due.
+    client.derive_key(
+        &admin,
+        &master_id,
+        &KeyLevel::Contract,
+        &2u32,
+        &false,
+        &KeyType::Encryption,
+        &policy,
+        &0u64,
+    );
+    assert!(client.keys_due_for_rotation(&admin).is_empty());
+
+    env.ledger().with_mut(|li| li.timestamp += 150);
+    let due = client.keys_due_for_rotation(&admin);
+    assert_eq!(due, Vec::from_array(&env, [master_id.clone()]));
+
+    client.rotate_key(&admin, &master_id);
+    assert!(client.keys_due_for_rotation(&admin).is_empty());
+
+    env.ledger().with_mut(|li| li.timestamp += 1_000);
+    let due = client.keys_due_for_rotation(&admin);
+    assert_eq!(due, Vec::from_array(&env, [master_id, child_id]));
+    assert!(client
+        .keys_due_for_rotation(&Address::generate(&env))
+        .is_empty());
+}
+
+#[test]
+fn test_verify_audit_chain_detects_tampering() {
+    let (env, client, _identity, admin) = setup();
+
+    let policy = KeyPolicy {
+        max_uses: 0,
+        not_before: 0,
+        not_after: 0,
+        allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
+    };
+    let key_bytes = BytesN::from_array(&env, &[8u8; 32]);
+    let key_id = client.create_master_key(&admin, &KeyType::Encryption, &policy, &0u64, &key_bytes);
+    client.rotate_key(&admin, &key_id);
+    client.rotate_key(&admin, &key_id);
+
+    assert!(client.verify_audit_chain(&1u64, &3u64));
+    assert!(client.verify_audit_chain(&2u64, &3u64));
+    assert!(client.verify_audit_chain(&1u64, &2u64));
+    assert!(!client.verify_audit_chain(&0u64, &3u64));
+    assert!(!client.verify_audit_chain(&1u64, &4u64));
+
+    let mut entry = client.get_audit_entry(&2u64).unwrap();
+    entry.details_hash = BytesN::from_array(&env, &[0xAAu8; 32]);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("AUDIT"), 2u64), &entry);
+    });
+
+    assert!(client.verify_audit_chain(&1u64, &1u64));
+    assert!(!client.verify_audit_chain(&1u64, &3u64));
+    assert!(!client.verify_audit_chain(&2u64, &2u64));
+}
+
 #[test]
 fn test_recovery_cooldown_is_enforced_before_execution() {
     let (env, client, identity, admin) = setup();
@@ -165,6 +383,8 @@ fn test_recovery_cooldown_is_enforced_before_execution() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[13u8; 32]);
@@ -204,6 +424,8 @@ fn test_only_designated_guardians_can_start_and_approve_recovery() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[15u8; 32]);
@@ -116,6 +116,8 @@ fn create_master_key(
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     client.create_master_key(
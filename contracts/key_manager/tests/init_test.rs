@@ -74,6 +74,8 @@ fn test_create_master_key_before_init_returns_not_initialized() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_bytes = BytesN::from_array(&env, &[1; 32]);
 
@@ -92,6 +94,8 @@ fn test_derive_key_before_init_returns_not_found() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let res = client.try_derive_key(
@@ -0,0 +1,292 @@
+//! Master-key multi-signature tests for KeyManagerContract.
+//!
+//! Covers threshold approval of master-key creation and revocation, rejection
+//! of direct master-key operations (and direct reconfiguration) while
+//! multisig is enabled, and that non-master keys keep their single-owner flow.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use key_manager::{
+    ContractError, KeyLevel, KeyManagerContract, KeyManagerContractClient, KeyPolicy, KeyStatus,
+    KeyType, MasterKeyAction, MasterKeyMultisig, MasterKeyParams,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, Vec};
+
+struct Setup {
+    env: Env,
+    client: KeyManagerContractClient<'static>,
+    admin: Address,
+    signers: Vec<Address>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(KeyManagerContract, ());
+    let client = KeyManagerContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let identity = Address::generate(&env);
+    client.initialize(&admin, &identity);
+
+    let signers = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    Setup {
+        env,
+        client,
+        admin,
+        signers,
+    }
+}
+
+fn unrestricted_policy(env: &Env) -> KeyPolicy {
+    KeyPolicy {
+        max_uses: 0,
+        not_before: 0,
+        not_after: 0,
+        allowed_ops: Vec::new(env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
+    }
+}
+
+fn make_master_key(s: &Setup) -> BytesN<32> {
+    let key_bytes = BytesN::from_array(&s.env, &[1u8; 32]);
+    s.client.create_master_key(
+        &s.admin,
+        &KeyType::Signing,
+        &unrestricted_policy(&s.env),
+        &0u64,
+        &key_bytes,
+    )
+}
+
+#[test]
+fn test_configure_rejects_invalid_threshold() {
+    let s = setup();
+
+    let result = s
+        .client
+        .try_configure_master_key_multisig(&s.admin, &s.signers, &0u32, &true);
+    assert_eq!(result, Err(Ok(ContractError::InvalidMultisigConfig)));
+
+    let result = s
+        .client
+        .try_configure_master_key_multisig(&s.admin, &s.signers, &4u32, &true);
+    assert_eq!(result, Err(Ok(ContractError::InvalidMultisigConfig)));
+
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+    let config = s.client.get_master_key_multisig().unwrap();
+    assert!(config.enabled);
+    assert_eq!(config.threshold, 2);
+}
+
+#[test]
+fn test_direct_master_operations_blocked_when_enabled() {
+    let s = setup();
+    let key_id = make_master_key(&s);
+
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+
+    let key_bytes = BytesN::from_array(&s.env, &[2u8; 32]);
+    let result = s.client.try_create_master_key(
+        &s.admin,
+        &KeyType::Signing,
+        &unrestricted_policy(&s.env),
+        &0u64,
+        &key_bytes,
+    );
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired)));
+
+    let result = s.client.try_revoke_key(&s.admin, &key_id);
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired)));
+}
+
+#[test]
+fn test_revoke_requires_threshold_approvals() {
+    let s = setup();
+    let key_id = make_master_key(&s);
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+
+    let first = s.signers.get(0).unwrap();
+    let second = s.signers.get(1).unwrap();
+    let proposal_id = s
+        .client
+        .propose_master_key_action(&first, &MasterKeyAction::Revoke(key_id.clone()));
+
+    // Only the proposer has approved so far.
+    let result = s.client.try_execute_master_key_action(&first, &proposal_id);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientApprovals)));
+
+    let result = s.client.try_approve_master_key_action(&first, &proposal_id);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyApproved)));
+
+    s.client.approve_master_key_action(&second, &proposal_id);
+    let revoked = s.client.execute_master_key_action(&second, &proposal_id);
+    assert_eq!(revoked, key_id);
+
+    let record = s.client.get_key_record(&key_id).unwrap();
+    assert_eq!(record.status, KeyStatus::Revoked);
+
+    let result = s.client.try_execute_master_key_action(&first, &proposal_id);
+    assert_eq!(result, Err(Ok(ContractError::ProposalAlreadyExecuted)));
+}
+
+#[test]
+fn test_create_through_multisig_is_owned_by_admin() {
+    let s = setup();
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+
+    let params = MasterKeyParams {
+        key_type: KeyType::Encryption,
+        policy: unrestricted_policy(&s.env),
+        rotation_interval: 0,
+        key_bytes: BytesN::from_array(&s.env, &[3u8; 32]),
+    };
+    let first = s.signers.get(0).unwrap();
+    let third = s.signers.get(2).unwrap();
+    let proposal_id = s
+        .client
+        .propose_master_key_action(&first, &MasterKeyAction::Create(params));
+    s.client.approve_master_key_action(&third, &proposal_id);
+    let key_id = s.client.execute_master_key_action(&third, &proposal_id);
+
+    let record = s.client.get_key_record(&key_id).unwrap();
+    assert_eq!(record.owner, s.admin);
+    assert_eq!(record.level, KeyLevel::Master);
+    assert_eq!(record.status, KeyStatus::Active);
+}
+
+#[test]
+fn test_non_signer_cannot_propose_or_approve() {
+    let s = setup();
+    let key_id = make_master_key(&s);
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+
+    let outsider = Address::generate(&s.env);
+    let result = s
+        .client
+        .try_propose_master_key_action(&outsider, &MasterKeyAction::Revoke(key_id.clone()));
+    assert_eq!(result, Err(Ok(ContractError::NotASigner)));
+
+    let first = s.signers.get(0).unwrap();
+    let proposal_id = s
+        .client
+        .propose_master_key_action(&first, &MasterKeyAction::Revoke(key_id));
+    let result = s
+        .client
+        .try_approve_master_key_action(&outsider, &proposal_id);
+    assert_eq!(result, Err(Ok(ContractError::NotASigner)));
+}
+
+#[test]
+fn test_non_master_keys_unaffected() {
+    let s = setup();
+    let master_id = make_master_key(&s);
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+
+    let child_id = s.client.derive_key(
+        &s.admin,
+        &master_id,
+        &KeyLevel::Contract,
+        &0u32,
+        &true,
+        &KeyType::Encryption,
+        &unrestricted_policy(&s.env),
+        &0u64,
+    );
+    s.client.revoke_key(&s.admin, &child_id);
+
+    let record = s.client.get_key_record(&child_id).unwrap();
+    assert_eq!(record.status, KeyStatus::Revoked);
+}
+
+#[test]
+fn test_lone_admin_cannot_reconfigure_enabled_multisig() {
+    let s = setup();
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+
+    let result = s
+        .client
+        .try_configure_master_key_multisig(&s.admin, &s.signers, &2u32, &false);
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired)));
+
+    let own_set = vec![&s.env, s.admin.clone()];
+    let result = s
+        .client
+        .try_configure_master_key_multisig(&s.admin, &own_set, &1u32, &true);
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired)));
+
+    let config = s.client.get_master_key_multisig().unwrap();
+    assert_eq!(config.signers, s.signers);
+    assert_eq!(config.threshold, 2);
+}
+
+#[test]
+fn test_signer_set_changes_through_multisig() {
+    let s = setup();
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+
+    let first = s.signers.get(0).unwrap();
+    let second = s.signers.get(1).unwrap();
+    let invalid = MasterKeyMultisig {
+        enabled: true,
+        signers: vec![&s.env, first.clone()],
+        threshold: 2,
+    };
+    let result = s
+        .client
+        .try_propose_master_key_action(&first, &MasterKeyAction::Configure(invalid));
+    assert_eq!(result, Err(Ok(ContractError::InvalidMultisigConfig)));
+
+    let next = MasterKeyMultisig {
+        enabled: true,
+        signers: vec![&s.env, first.clone(), second.clone()],
+        threshold: 2,
+    };
+    let proposal_id = s
+        .client
+        .propose_master_key_action(&first, &MasterKeyAction::Configure(next.clone()));
+    let result = s.client.try_execute_master_key_action(&first, &proposal_id);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientApprovals)));
+
+    s.client.approve_master_key_action(&second, &proposal_id);
+    s.client.execute_master_key_action(&second, &proposal_id);
+    assert_eq!(s.client.get_master_key_multisig(), Some(next));
+}
+
+#[test]
+fn test_disabled_multisig_restores_direct_operations() {
+    let s = setup();
+    let key_id = make_master_key(&s);
+    s.client
+        .configure_master_key_multisig(&s.admin, &s.signers, &2u32, &true);
+
+    let first = s.signers.get(0).unwrap();
+    let second = s.signers.get(1).unwrap();
+    let disabled = MasterKeyMultisig {
+        enabled: false,
+        signers: s.signers.clone(),
+        threshold: 2,
+    };
+    let proposal_id = s
+        .client
+        .propose_master_key_action(&first, &MasterKeyAction::Configure(disabled));
+    s.client.approve_master_key_action(&second, &proposal_id);
+    s.client.execute_master_key_action(&second, &proposal_id);
+
+    s.client.revoke_key(&s.admin, &key_id);
+    let record = s.client.get_key_record(&key_id).unwrap();
+    assert_eq!(record.status, KeyStatus::Revoked);
+}
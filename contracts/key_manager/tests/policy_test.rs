@@ -1,18 +1,20 @@
 //! Policy enforcement tests for KeyManagerContract.
 //!
 //! Covers issue #484: not_before / not_after time windows, max_uses cap,
-//! revoked-key rejection, and non-owner / non-admin access denial.
+//! revoked-key rejection, and non-owner / non-admin access denial, plus
+//! derive-time `allowed_ops` enforcement for record-key derivation,
+//! `allowed_ops` length and duplicate handling, and per-period usage budgets.
 
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 
 use key_manager::{
     ContractError, KeyLevel, KeyManagerContract, KeyManagerContractClient, KeyPolicy, KeyStatus,
-    KeyType,
+    KeyType, MAX_ALLOWED_OPS, RECORD_DERIVE,
 };
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
-    Address, BytesN, Env, Vec,
+    Address, BytesN, Env, Symbol, Vec,
 };
 
 fn setup() -> (Env, KeyManagerContractClient<'static>, Address) {
@@ -32,6 +34,8 @@ fn unrestricted_policy(env: &Env) -> KeyPolicy {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     }
 }
 
@@ -62,6 +66,8 @@ fn test_use_key_before_not_before_is_rejected() {
         not_before: 2_000, // key not valid until t=2000
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_id = make_key(&env, &client, &admin, policy);
 
@@ -82,6 +88,8 @@ fn test_use_key_after_not_before_is_allowed() {
         not_before: 2_000,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_id = make_key(&env, &client, &admin, policy);
 
@@ -108,6 +116,8 @@ fn test_use_key_past_not_after_is_rejected() {
         not_before: 0,
         not_after: 1_000, // expires at t=1000
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_id = make_key(&env, &client, &admin, policy);
 
@@ -130,6 +140,8 @@ fn test_use_key_at_not_after_boundary_is_allowed() {
         not_before: 0,
         not_after: 1_000,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_id = make_key(&env, &client, &admin, policy);
 
@@ -149,6 +161,8 @@ fn test_create_key_with_inverted_time_window_rejected() {
         not_before: 5_000,
         not_after: 4_000, // not_after <= not_before — invalid
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_bytes = BytesN::from_array(&env, &[2u8; 32]);
     let result =
@@ -170,6 +184,8 @@ fn test_max_uses_one_allows_single_use_then_rejects() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_id = make_key(&env, &client, &admin, policy);
 
@@ -194,6 +210,8 @@ fn test_max_uses_boundary_at_exact_limit() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_id = make_key(&env, &client, &admin, policy);
 
@@ -346,6 +364,8 @@ fn test_allowed_ops_filters_disallowed_operation() {
         not_before: 0,
         not_after: 0,
         allowed_ops: ops,
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
     let key_id = make_key(&env, &client, &admin, policy);
 
@@ -359,6 +379,79 @@ fn test_allowed_ops_filters_disallowed_operation() {
     assert_eq!(result, Err(Ok(ContractError::PolicyViolation)));
 }
 
+/// More than `MAX_ALLOWED_OPS` distinct operations must be rejected.
+#[test]
+fn test_over_length_allowed_ops_is_rejected() {
+    let (env, client, admin) = setup();
+
+    let mut ops = Vec::new(&env);
+    for i in 0..=MAX_ALLOWED_OPS {
+        ops.push_back(Symbol::new(&env, &std::format!("OP{i}")));
+    }
+    let policy = KeyPolicy {
+        allowed_ops: ops,
+        ..unrestricted_policy(&env)
+    };
+    let key_bytes = BytesN::from_array(&env, &[1u8; 32]);
+    let result =
+        client.try_create_master_key(&admin, &KeyType::Signing, &policy, &0u64, &key_bytes);
+    assert_eq!(result, Err(Ok(ContractError::InvalidPolicy)));
+}
+
+/// The cap counts distinct operations, so repeats beyond it are accepted.
+#[test]
+fn test_repeated_allowed_ops_count_once_toward_cap() {
+    let (env, client, admin) = setup();
+
+    let mut ops = Vec::new(&env);
+    for i in 0..MAX_ALLOWED_OPS {
+        ops.push_back(Symbol::new(&env, &std::format!("OP{i}")));
+    }
+    ops.push_back(Symbol::new(&env, "OP0"));
+    let policy = KeyPolicy {
+        allowed_ops: ops,
+        ..unrestricted_policy(&env)
+    };
+    let key_id = make_key(&env, &client, &admin, policy);
+    assert_eq!(
+        client
+            .get_key_record(&key_id)
+            .unwrap()
+            .policy
+            .allowed_ops
+            .len(),
+        MAX_ALLOWED_OPS
+    );
+}
+
+/// Duplicate operations are collapsed in the stored policy.
+#[test]
+fn test_duplicate_allowed_ops_are_collapsed() {
+    let (env, client, admin) = setup();
+
+    let mut ops = Vec::new(&env);
+    for op in [
+        symbol_short!("ENC"),
+        symbol_short!("SIGN"),
+        symbol_short!("ENC"),
+        symbol_short!("SIGN"),
+        symbol_short!("ENC"),
+    ] {
+        ops.push_back(op);
+    }
+    let policy = KeyPolicy {
+        allowed_ops: ops,
+        ..unrestricted_policy(&env)
+    };
+    let key_id = make_key(&env, &client, &admin, policy);
+
+    let stored = client.get_key_record(&key_id).unwrap().policy.allowed_ops;
+    assert_eq!(
+        stored,
+        Vec::from_array(&env, [symbol_short!("ENC"), symbol_short!("SIGN")])
+    );
+}
+
 /// When allowed_ops is empty, any operation is permitted.
 #[test]
 fn test_empty_allowed_ops_permits_any_operation() {
@@ -376,3 +469,173 @@ fn test_empty_allowed_ops_permits_any_operation() {
         .try_use_key(&admin, &key_id, &symbol_short!("AUTH"))
         .is_ok());
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Derive-time allowed_ops enforcement
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn ops_policy(env: &Env, ops: &[Symbol]) -> KeyPolicy {
+    let mut allowed_ops = Vec::new(env);
+    for op in ops {
+        allowed_ops.push_back(op.clone());
+    }
+    KeyPolicy {
+        max_uses: 0,
+        not_before: 0,
+        not_after: 0,
+        allowed_ops,
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
+    }
+}
+
+/// Without enforcement, derivation ignores allowed_ops (legacy behaviour).
+#[test]
+fn test_derive_unchecked_by_default() {
+    let (env, client, admin) = setup();
+    assert!(!client.is_derive_policy_enforced());
+
+    let key_id = make_key(
+        &env,
+        &client,
+        &admin,
+        ops_policy(&env, &[symbol_short!("SIGN")]),
+    );
+    assert!(client.try_derive_record_key(&key_id, &1u64).is_ok());
+}
+
+/// With enforcement on, a key whose allowed_ops omit RECORD_DERIVE cannot
+/// derive record keys by either entry point.
+#[test]
+fn test_derive_refused_when_policy_disallows() {
+    let (env, client, admin) = setup();
+    client.set_derive_policy_enforced(&admin, &true);
+
+    let key_id = make_key(
+        &env,
+        &client,
+        &admin,
+        ops_policy(&env, &[symbol_short!("SIGN")]),
+    );
+    let result = client.try_derive_record_key(&key_id, &1u64);
+    assert_eq!(result, Err(Ok(ContractError::PolicyViolation)));
+
+    let result = client.try_derive_record_key_with_version(&key_id, &1u64, &1u32);
+    assert_eq!(result, Err(Ok(ContractError::PolicyViolation)));
+}
+
+/// With enforcement on, RECORD_DERIVE in allowed_ops (or an empty list)
+/// permits derivation.
+#[test]
+fn test_derive_permitted_when_policy_allows() {
+    let (env, client, admin) = setup();
+    client.set_derive_policy_enforced(&admin, &true);
+
+    let key_id = make_key(
+        &env,
+        &client,
+        &admin,
+        ops_policy(&env, &[symbol_short!("SIGN"), RECORD_DERIVE]),
+    );
+    let derived = client.derive_record_key(&key_id, &7u64);
+    assert_eq!(derived.version, 1);
+
+    let open_key = make_key(&env, &client, &admin, unrestricted_policy(&env));
+    assert!(client.try_derive_record_key(&open_key, &7u64).is_ok());
+}
+
+/// Only the admin may toggle derive-time enforcement.
+#[test]
+fn test_non_admin_cannot_toggle_derive_enforcement() {
+    let (env, client, _admin) = setup();
+    let outsider = Address::generate(&env);
+
+    let result = client.try_set_derive_policy_enforced(&outsider, &true);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Per-period usage budget
+// ─────────────────────────────────────────────────────────────────────────────
+
+const DAY: u64 = 86_400;
+
+fn budget_policy(env: &Env, budget: u32, max_uses: u32) -> KeyPolicy {
+    KeyPolicy {
+        max_uses,
+        usage_budget_per_period: budget,
+        budget_period_seconds: DAY,
+        ..unrestricted_policy(env)
+    }
+}
+
+/// Once the period budget is spent, use is rejected even though the lifetime
+/// cap has room left.
+#[test]
+fn test_use_key_rejected_when_period_budget_exhausted() {
+    let (env, client, admin) = setup();
+    env.ledger().set_timestamp(1_000);
+    let key_id = make_key(&env, &client, &admin, budget_policy(&env, 3, 100));
+
+    for _ in 0..3 {
+        client.use_key(&admin, &key_id, &symbol_short!("SIGN"));
+    }
+    let result = client.try_use_key(&admin, &key_id, &symbol_short!("SIGN"));
+    assert_eq!(result, Err(Ok(ContractError::PolicyViolation)));
+
+    let status = client.get_usage_budget_status(&key_id);
+    assert!(status.enabled);
+    assert_eq!(status.used, 3);
+    assert_eq!(status.remaining, 0);
+    assert_eq!(status.resets_at, 1_000 + DAY);
+    assert_eq!(client.get_key_record(&key_id).unwrap().uses, 3);
+}
+
+/// The budget refills at the next period boundary.
+#[test]
+fn test_period_budget_refills_after_period_elapses() {
+    let (env, client, admin) = setup();
+    env.ledger().set_timestamp(1_000);
+    let key_id = make_key(&env, &client, &admin, budget_policy(&env, 2, 0));
+
+    client.use_key(&admin, &key_id, &symbol_short!("SIGN"));
+    client.use_key(&admin, &key_id, &symbol_short!("SIGN"));
+
+    env.ledger().set_timestamp(1_000 + DAY - 1);
+    let result = client.try_use_key(&admin, &key_id, &symbol_short!("SIGN"));
+    assert_eq!(result, Err(Ok(ContractError::PolicyViolation)));
+
+    // Skipping ahead several periods still lands on an aligned boundary.
+    env.ledger().set_timestamp(1_000 + 3 * DAY + 5);
+    let status = client.get_usage_budget_status(&key_id);
+    assert_eq!(status.remaining, 2);
+    assert_eq!(status.period_start, 1_000 + 3 * DAY);
+
+    client.use_key(&admin, &key_id, &symbol_short!("SIGN"));
+    let status = client.get_usage_budget_status(&key_id);
+    assert_eq!(status.used, 1);
+    assert_eq!(status.resets_at, 1_000 + 4 * DAY);
+}
+
+/// The lifetime cap still applies on top of a fresh period budget, and a
+/// budget without a period length is rejected at creation.
+#[test]
+fn test_budget_combines_with_lifetime_cap_and_validates() {
+    let (env, client, admin) = setup();
+    let key_id = make_key(&env, &client, &admin, budget_policy(&env, 5, 1));
+
+    client.use_key(&admin, &key_id, &symbol_short!("SIGN"));
+    let result = client.try_use_key(&admin, &key_id, &symbol_short!("SIGN"));
+    assert_eq!(result, Err(Ok(ContractError::PolicyViolation)));
+
+    let unlimited = make_key(&env, &client, &admin, unrestricted_policy(&env));
+    assert!(!client.get_usage_budget_status(&unlimited).enabled);
+
+    let bad = KeyPolicy {
+        usage_budget_per_period: 5,
+        ..unrestricted_policy(&env)
+    };
+    let key_bytes = BytesN::from_array(&env, &[1u8; 32]);
+    let result = client.try_create_master_key(&admin, &KeyType::Signing, &bad, &0u64, &key_bytes);
+    assert_eq!(result, Err(Ok(ContractError::InvalidPolicy)));
+}
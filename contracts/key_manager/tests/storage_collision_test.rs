@@ -37,6 +37,8 @@ fn test_storage_namespace_separation() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[1u8; 32]);
@@ -71,6 +73,8 @@ fn test_key_id_uniqueness() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[1u8; 32]);
@@ -94,6 +98,8 @@ fn test_audit_log_collision_prevention() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[1u8; 32]);
@@ -116,6 +122,8 @@ fn test_recovery_storage_collision() {
         not_before: 0,
         not_after: 0,
         allowed_ops: Vec::new(&env),
+        usage_budget_per_period: 0,
+        budget_period_seconds: 0,
     };
 
     let key_bytes = BytesN::from_array(&env, &[1u8; 32]);
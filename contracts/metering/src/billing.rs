@@ -10,10 +10,18 @@
 //! 1. `open_cycle` — admin starts a new cycle, resetting per-tenant usage.
 //! 2. Operations are metered in real-time via `record_usage`.
 //! 3. `close_cycle` — admin closes the cycle; a `BillingReport` is finalised.
-//! 4. `settle_invoice` — postpaid tenants pay their invoice.
+//! 4. `settle_invoice` — postpaid tenants pay their invoice, in full or in
+//!    instalments via `settle_invoice_partial`.
+//!
+//! ## Grace window
+//! With a non-zero `cycle_grace_seconds`, operations that executed before a
+//! close but are recorded within the grace window after it are charged to
+//! the closed cycle (its stored report and invoice) instead of the new one.
 
 use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 
+use crate::OperationType;
+
 // ── Storage keys ──────────────────────────────────────────────────────────────
 
 pub const CYCLE_CTR: Symbol = symbol_short!("CYC_CTR");
@@ -21,6 +29,9 @@ const CYCLE_KEY: Symbol = symbol_short!("CYCLE");
 const INVOICE_KEY: Symbol = symbol_short!("INVOICE");
 const BILLING_MDL: Symbol = symbol_short!("BIL_MDL");
 const PREPAID_BAL: Symbol = symbol_short!("PP_BAL");
+const CYCLE_GRACE: Symbol = symbol_short!("CYC_GRC");
+const CYCLE_BOUNDARY: Symbol = symbol_short!("CYC_BND");
+const REPORT_KEY: Symbol = symbol_short!("CYC_RPT");
 
 const TTL_THRESHOLD: u32 = 5_184_000;
 const TTL_EXTEND_TO: u32 = 10_368_000;
@@ -66,6 +77,8 @@ pub struct TenantUsageRecord {
     pub compute_units: u64,
     pub storage_units: u64,
     pub burst_units: u64,
+    /// Units served from the tenant's rollover balance.
+    pub rollover_units: u64,
     /// Total cost in gas tokens (computed at cycle close using `GasCosts`).
     pub total_cost: u64,
 }
@@ -79,6 +92,21 @@ pub struct BillingReport {
     pub records: Vec<TenantUsageRecord>,
 }
 
+/// The most recent cycle close and the grace window that follows it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CycleBoundaryInfo {
+    /// Id of the last closed cycle (0 if none has closed yet).
+    pub closed_cycle_id: u64,
+    pub closed_at: u64,
+    /// Grace period in effect when the cycle closed.
+    pub grace_seconds: u64,
+    /// Operations executed before `closed_at` are accepted until this time.
+    pub grace_ends_at: u64,
+    /// Whether the closing and current cycles both accept charges right now.
+    pub in_grace: bool,
+}
+
 /// An invoice for a postpaid tenant for a specific cycle.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -86,6 +114,8 @@ pub struct Invoice {
     pub tenant: Address,
     pub cycle_id: u64,
     pub amount_due: u64,
+    /// Part of `amount_due` not yet paid; 0 once settled.
+    pub outstanding: u64,
     pub settled: bool,
     pub issued_at: u64,
     pub settled_at: u64, // 0 if unsettled
@@ -100,6 +130,8 @@ pub enum BillingError {
     InvoiceNotFound,
     AlreadySettled,
     InsufficientPrepaidBalance,
+    /// A partial payment larger than the invoice's outstanding balance.
+    Overpayment,
 }
 
 // ── Storage helpers ───────────────────────────────────────────────────────────
@@ -200,9 +232,114 @@ pub fn close_cycle(env: &Env) -> Result<u64, BillingError> {
     env.storage().persistent().set(&key, &cycle);
     extend_cycle_ttl(env, &key);
 
+    let grace_seconds = get_cycle_grace(env);
+    let boundary = CycleBoundaryInfo {
+        closed_cycle_id: current_id,
+        closed_at: cycle.ended_at,
+        grace_seconds,
+        grace_ends_at: cycle.ended_at.saturating_add(grace_seconds),
+        in_grace: false,
+    };
+    env.storage().instance().set(&CYCLE_BOUNDARY, &boundary);
+
     Ok(current_id)
 }
 
+// ── Grace window helpers ──────────────────────────────────────────────────────
+
+/// Return the configured cycle grace period in seconds (0 if unset).
+pub fn get_cycle_grace(env: &Env) -> u64 {
+    env.storage().instance().get(&CYCLE_GRACE).unwrap_or(0)
+}
+
+/// Set the grace period applied to subsequent cycle closes.
+pub fn set_cycle_grace(env: &Env, seconds: u64) {
+    env.storage().instance().set(&CYCLE_GRACE, &seconds);
+}
+
+/// Return the last cycle boundary, with `in_grace` evaluated at the current
+/// ledger time.
+pub fn get_boundary(env: &Env) -> CycleBoundaryInfo {
+    let mut boundary: CycleBoundaryInfo =
+        env.storage()
+            .instance()
+            .get(&CYCLE_BOUNDARY)
+            .unwrap_or(CycleBoundaryInfo {
+                closed_cycle_id: 0,
+                closed_at: 0,
+                grace_seconds: 0,
+                grace_ends_at: 0,
+                in_grace: false,
+            });
+    boundary.in_grace =
+        boundary.closed_cycle_id > 0 && env.ledger().timestamp() < boundary.grace_ends_at;
+    boundary
+}
+
+// ── Report helpers ────────────────────────────────────────────────────────────
+
+/// Persist the report generated when a cycle closed.
+pub fn store_report(env: &Env, report: &BillingReport) {
+    let key = (REPORT_KEY, report.cycle_id);
+    env.storage().persistent().set(&key, report);
+    extend_cycle_ttl(env, &key);
+}
+
+/// Retrieve the stored report for a closed cycle.
+pub fn get_report(env: &Env, cycle_id: u64) -> Option<BillingReport> {
+    let key = (REPORT_KEY, cycle_id);
+    let report: Option<BillingReport> = env.storage().persistent().get(&key);
+    if report.is_some() {
+        extend_cycle_ttl(env, &key);
+    }
+    report
+}
+
+/// Add a late (grace-window) charge to `tenant`'s record in a closed cycle's
+/// report. Returns the amount added to the record's `total_cost`.
+pub fn add_late_usage(
+    env: &Env,
+    cycle_id: u64,
+    tenant: &Address,
+    op_type: &OperationType,
+    units: u64,
+) -> u64 {
+    let Some(mut report) = get_report(env, cycle_id) else {
+        return 0;
+    };
+
+    let position = report.records.iter().position(|r| r.tenant == *tenant);
+    let mut record = match position.and_then(|i| report.records.get(i as u32)) {
+        Some(record) => record,
+        None => TenantUsageRecord {
+            tenant: tenant.clone(),
+            cycle_id,
+            read_units: 0,
+            write_units: 0,
+            compute_units: 0,
+            storage_units: 0,
+            burst_units: 0,
+            rollover_units: 0,
+            total_cost: 0,
+        },
+    };
+    let bucket = match op_type {
+        OperationType::Read => &mut record.read_units,
+        OperationType::Write => &mut record.write_units,
+        OperationType::Compute => &mut record.compute_units,
+        OperationType::Storage => &mut record.storage_units,
+    };
+    *bucket = bucket.saturating_add(units);
+    record.total_cost = record.total_cost.saturating_add(units);
+
+    match position {
+        Some(i) => report.records.set(i as u32, record),
+        None => report.records.push_back(record),
+    }
+    store_report(env, &report);
+    units
+}
+
 /// Retrieve a billing cycle by id.
 pub fn get_cycle(env: &Env, id: u64) -> Option<BillingCycle> {
     let key = cycle_key(id);
@@ -276,6 +413,7 @@ pub fn create_invoice(env: &Env, tenant: &Address, cycle_id: u64, amount_due: u6
         tenant: tenant.clone(),
         cycle_id,
         amount_due,
+        outstanding: amount_due,
         settled: false,
         issued_at: env.ledger().timestamp(),
         settled_at: 0,
@@ -284,6 +422,29 @@ pub fn create_invoice(env: &Env, tenant: &Address, cycle_id: u64, amount_due: u6
     extend_invoice_ttl(env, &key);
 }
 
+/// Add a late charge to a postpaid tenant's invoice for `cycle_id`, issuing
+/// one if the cycle closed without any cost. A late charge against a settled
+/// invoice reopens it for the late amount only.
+pub fn add_to_invoice(env: &Env, tenant: &Address, cycle_id: u64, amount: u64) {
+    let key = invoice_key(tenant, cycle_id);
+    match env.storage().persistent().get::<_, Invoice>(&key) {
+        Some(mut inv) => {
+            if inv.settled {
+                inv.amount_due = amount;
+                inv.outstanding = amount;
+            } else {
+                inv.amount_due = inv.amount_due.saturating_add(amount);
+                inv.outstanding = inv.outstanding.saturating_add(amount);
+            }
+            inv.settled = false;
+            inv.settled_at = 0;
+            env.storage().persistent().set(&key, &inv);
+            extend_invoice_ttl(env, &key);
+        }
+        None => create_invoice(env, tenant, cycle_id, amount),
+    }
+}
+
 /// Retrieve an invoice for a tenant / cycle pair.
 pub fn get_invoice(env: &Env, tenant: &Address, cycle_id: u64) -> Option<Invoice> {
     let key = invoice_key(tenant, cycle_id);
@@ -307,9 +468,42 @@ pub fn settle_invoice(env: &Env, tenant: &Address, cycle_id: u64) -> Result<(),
         return Err(BillingError::AlreadySettled);
     }
 
+    inv.outstanding = 0;
     inv.settled = true;
     inv.settled_at = env.ledger().timestamp();
     env.storage().persistent().set(&key, &inv);
     extend_invoice_ttl(env, &key);
     Ok(())
 }
+
+/// Pay `amount` towards an invoice, marking it settled once nothing is
+/// outstanding. Returns the updated invoice.
+pub fn settle_invoice_partial(
+    env: &Env,
+    tenant: &Address,
+    cycle_id: u64,
+    amount: u64,
+) -> Result<Invoice, BillingError> {
+    let key = invoice_key(tenant, cycle_id);
+    let mut inv: Invoice = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(BillingError::InvoiceNotFound)?;
+
+    if inv.settled {
+        return Err(BillingError::AlreadySettled);
+    }
+    if amount > inv.outstanding {
+        return Err(BillingError::Overpayment);
+    }
+
+    inv.outstanding -= amount;
+    if inv.outstanding == 0 {
+        inv.settled = true;
+        inv.settled_at = env.ledger().timestamp();
+    }
+    env.storage().persistent().set(&key, &inv);
+    extend_invoice_ttl(env, &key);
+    Ok(inv)
+}
@@ -0,0 +1,44 @@
+//! Usage attribution by external correlation id.
+//!
+//! A caller can tag `record_gas` with a `correlation_id` naming the external
+//! workflow the operation belongs to (e.g. one patient intake).  Units are
+//! tallied per `(tenant, correlation_id)` alongside, not instead of, the
+//! hierarchy-based usage counters used for billing.
+//!
+//! Tallies live in temporary storage: each charge extends the entry's TTL,
+//! and a workflow that goes quiet for `TTL_EXTEND_TO` ledgers is dropped, so
+//! storage stays bounded by the set of recently active workflows.
+
+use soroban_sdk::{symbol_short, Address, BytesN, Env, Symbol};
+
+// ── Storage keys ──────────────────────────────────────────────────────────────
+
+const CORR_USAGE: Symbol = symbol_short!("CORR_USE");
+
+// TTL: ~30 days at 5s/ledger
+const TTL_THRESHOLD: u32 = 259_200;
+const TTL_EXTEND_TO: u32 = 518_400;
+
+fn usage_key(tenant: &Address, correlation_id: &BytesN<32>) -> (Symbol, Address, BytesN<32>) {
+    (CORR_USAGE, tenant.clone(), correlation_id.clone())
+}
+
+// ── Tally ─────────────────────────────────────────────────────────────────────
+
+/// Add `units` to the tally for `(tenant, correlation_id)`.
+pub fn record(env: &Env, tenant: &Address, correlation_id: &BytesN<32>, units: u64) {
+    let key = usage_key(tenant, correlation_id);
+    let total = get_usage(env, tenant, correlation_id).saturating_add(units);
+    env.storage().temporary().set(&key, &total);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Units recorded for `(tenant, correlation_id)`; 0 if none or expired.
+pub fn get_usage(env: &Env, tenant: &Address, correlation_id: &BytesN<32>) -> u64 {
+    env.storage()
+        .temporary()
+        .get(&usage_key(tenant, correlation_id))
+        .unwrap_or(0)
+}
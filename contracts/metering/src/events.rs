@@ -82,6 +82,16 @@ pub struct InvoiceSettledEvent {
     pub timestamp: u64,
 }
 
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoicePaymentEvent {
+    pub tenant: Address,
+    pub cycle_id: u64,
+    pub amount: u64,
+    pub outstanding: u64,
+    pub timestamp: u64,
+}
+
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GasTokenMintedEvent {
@@ -91,6 +101,15 @@ pub struct GasTokenMintedEvent {
     pub timestamp: u64,
 }
 
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GasTokenRefundedEvent {
+    pub tenant: Address,
+    pub amount: u64,
+    pub new_balance: u64,
+    pub timestamp: u64,
+}
+
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GasTokenBurnedEvent {
@@ -100,6 +119,16 @@ pub struct GasTokenBurnedEvent {
     pub timestamp: u64,
 }
 
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolloverAccruedEvent {
+    pub tenant: Address,
+    pub cycle_id: u64,
+    pub units: u64,
+    pub balance: u64,
+    pub timestamp: u64,
+}
+
 // ── Publishers ────────────────────────────────────────────────────────────────
 
 pub fn publish_tenant_registered(env: &Env, tenant: Address, level: TenantLevel, parent: Address) {
@@ -206,6 +235,26 @@ pub fn publish_invoice_settled(env: &Env, tenant: Address, cycle_id: u64) {
     );
 }
 
+pub fn publish_invoice_payment(
+    env: &Env,
+    tenant: Address,
+    cycle_id: u64,
+    amount: u64,
+    outstanding: u64,
+) {
+    emit(
+        env,
+        "InvPayment",
+        InvoicePaymentEvent {
+            tenant,
+            cycle_id,
+            amount,
+            outstanding,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
 pub fn publish_gas_token_minted(env: &Env, tenant: Address, amount: u64, new_balance: u64) {
     emit(
         env,
@@ -219,6 +268,19 @@ pub fn publish_gas_token_minted(env: &Env, tenant: Address, amount: u64, new_bal
     );
 }
 
+pub fn publish_gas_token_refunded(env: &Env, tenant: Address, amount: u64, new_balance: u64) {
+    emit(
+        env,
+        "GTRefunded",
+        GasTokenRefundedEvent {
+            tenant,
+            amount,
+            new_balance,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
 pub fn publish_gas_token_burned(env: &Env, tenant: Address, amount: u64, remaining: u64) {
     emit(
         env,
@@ -231,3 +293,23 @@ pub fn publish_gas_token_burned(env: &Env, tenant: Address, amount: u64, remaini
         },
     );
 }
+
+pub fn publish_rollover_accrued(
+    env: &Env,
+    tenant: Address,
+    cycle_id: u64,
+    units: u64,
+    balance: u64,
+) {
+    emit(
+        env,
+        "RollAccr",
+        RolloverAccruedEvent {
+            tenant,
+            cycle_id,
+            units,
+            balance,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
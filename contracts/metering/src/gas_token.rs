@@ -7,6 +7,8 @@
 //! ## Lifecycle
 //! - Admin **mints** tokens to a tenant (prepaid top-up or administrative credit).
 //! - The metering system **burns** tokens as operations are performed (prepaid model).
+//! - Admin can **refund** burned tokens for an operation that never took effect,
+//!   up to what the tenant has burned and not already had refunded.
 //! - Admin can **freeze** a tenant's balance to prevent further spending.
 
 use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
@@ -16,6 +18,8 @@ use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
 const GT_BALANCE: Symbol = symbol_short!("GT_BAL");
 const GT_TOTAL: Symbol = symbol_short!("GT_TOT");
 const GT_FROZEN: Symbol = symbol_short!("GT_FRZ");
+const GT_BURNED: Symbol = symbol_short!("GT_BURN");
+const GT_REFUNDED: Symbol = symbol_short!("GT_RFND");
 
 const TTL_THRESHOLD: u32 = 5_184_000;
 const TTL_EXTEND_TO: u32 = 10_368_000;
@@ -43,6 +47,8 @@ pub enum GasTokenError {
     InsufficientBalance,
     /// Minting amount must be greater than zero.
     ZeroMintAmount,
+    /// The refund exceeds the tenant's burns net of earlier refunds.
+    RefundExceedsBurned,
 }
 
 // ── Storage helpers ───────────────────────────────────────────────────────────
@@ -55,6 +61,24 @@ fn frozen_key(tenant: &Address) -> (Symbol, Address) {
     (GT_FROZEN, tenant.clone())
 }
 
+fn burned_key(tenant: &Address) -> (Symbol, Address) {
+    (GT_BURNED, tenant.clone())
+}
+
+fn refunded_key(tenant: &Address) -> (Symbol, Address) {
+    (GT_REFUNDED, tenant.clone())
+}
+
+fn read_counter(env: &Env, key: &(Symbol, Address)) -> u64 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn add_to_counter(env: &Env, key: &(Symbol, Address), amount: u64) {
+    let total = read_counter(env, key).saturating_add(amount);
+    env.storage().persistent().set(key, &total);
+    extend_ttl(env, key);
+}
+
 fn extend_ttl(env: &Env, key: &(Symbol, Address)) {
     env.storage()
         .persistent()
@@ -120,6 +144,31 @@ pub fn burn(env: &Env, tenant: &Address, amount: u64) -> Result<(), GasTokenErro
     let new_balance = current.saturating_sub(amount);
     env.storage().persistent().set(&key, &new_balance);
     extend_ttl(env, &key);
+    add_to_counter(env, &burned_key(tenant), amount);
+
+    Ok(())
+}
+
+/// Tokens `tenant` has burned that have not been refunded yet.
+pub fn refundable(env: &Env, tenant: &Address) -> u64 {
+    read_counter(env, &burned_key(tenant)).saturating_sub(read_counter(env, &refunded_key(tenant)))
+}
+
+/// Return `amount` previously burned tokens to `tenant`. Unlike `mint`, this
+/// leaves the total supply unchanged: the tokens were minted once already.
+/// Refunds are capped by [`refundable`], so they can never add supply.
+pub fn refund(env: &Env, tenant: &Address, amount: u64) -> Result<(), GasTokenError> {
+    if amount > refundable(env, tenant) {
+        return Err(GasTokenError::RefundExceedsBurned);
+    }
+
+    let key = balance_key(tenant);
+    let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&key, &current.saturating_add(amount));
+    extend_ttl(env, &key);
+    add_to_counter(env, &refunded_key(tenant), amount);
 
     Ok(())
 }
@@ -144,7 +193,7 @@ pub fn get_account(env: &Env, tenant: &Address) -> GasTokenAccount {
         tenant: tenant.clone(),
         balance: balance_of(env, tenant),
         total_minted: env.storage().instance().get(&GT_TOTAL).unwrap_or(0),
-        total_burned: 0, // Aggregate burn tracking is additive; see `burn`.
+        total_burned: read_counter(env, &burned_key(tenant)),
         frozen: is_frozen(env, tenant),
     }
 }
@@ -0,0 +1,189 @@
+//! Ad-hoc tenant groups sharing a pooled quota.
+//!
+//! A group is any set of registered tenants, independent of the hierarchy
+//! (e.g. "pilot clinics").  Gas recorded by a member counts against both its
+//! own quota and the group's `pooled_limit`; once the pool is spent every
+//! member is blocked, even if its individual quota still has headroom.
+//!
+//! A tenant belongs to at most one group.  Pool usage is per billing cycle
+//! and is reset when a new cycle opens.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────────────────────
+
+const GROUP_KEY: Symbol = symbol_short!("GROUP");
+const GROUP_USAGE: Symbol = symbol_short!("GRP_USE");
+const GROUP_LIST: Symbol = symbol_short!("GRP_LST");
+const MEMBER_GROUP: Symbol = symbol_short!("TEN_GRP");
+
+const TTL_THRESHOLD: u32 = 5_184_000;
+const TTL_EXTEND_TO: u32 = 10_368_000;
+
+// ── Types ─────────────────────────────────────────────────────────────────────
+
+/// A named set of tenants drawing on one shared quota.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TenantGroup {
+    pub group_id: Symbol,
+    pub members: Vec<Address>,
+    /// Gas units all members may consume together per cycle.
+    pub pooled_limit: u64,
+}
+
+// ── Errors ────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GroupError {
+    /// No group is registered under the given id.
+    GroupNotFound,
+    /// A group with the given id already exists.
+    GroupAlreadyExists,
+    /// The tenant is already a member of a group.
+    AlreadyInGroup,
+    /// The tenant is not a member of the group.
+    NotAMember,
+    /// The operation would exceed the group's pooled limit.
+    PoolExhausted,
+}
+
+// ── Storage helpers ───────────────────────────────────────────────────────────
+
+fn group_key(group_id: &Symbol) -> (Symbol, Symbol) {
+    (GROUP_KEY, group_id.clone())
+}
+
+fn usage_key(group_id: &Symbol) -> (Symbol, Symbol) {
+    (GROUP_USAGE, group_id.clone())
+}
+
+fn member_key(tenant: &Address) -> (Symbol, Address) {
+    (MEMBER_GROUP, tenant.clone())
+}
+
+fn save_group(env: &Env, group: &TenantGroup) {
+    let key = group_key(&group.group_id);
+    env.storage().persistent().set(&key, group);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn set_membership(env: &Env, tenant: &Address, group_id: &Symbol) {
+    let key = member_key(tenant);
+    env.storage().persistent().set(&key, group_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+// ── Public API ────────────────────────────────────────────────────────────────
+
+/// Create a group with an initial member list.
+pub fn create_group(
+    env: &Env,
+    group_id: &Symbol,
+    members: &Vec<Address>,
+    pooled_limit: u64,
+) -> Result<(), GroupError> {
+    if env.storage().persistent().has(&group_key(group_id)) {
+        return Err(GroupError::GroupAlreadyExists);
+    }
+
+    let mut group = TenantGroup {
+        group_id: group_id.clone(),
+        members: Vec::new(env),
+        pooled_limit,
+    };
+    for member in members.iter() {
+        if group_of(env, &member).is_some() || group.members.contains(&member) {
+            return Err(GroupError::AlreadyInGroup);
+        }
+        group.members.push_back(member);
+    }
+    for member in group.members.iter() {
+        set_membership(env, &member, group_id);
+    }
+    save_group(env, &group);
+
+    let mut list: Vec<Symbol> = env
+        .storage()
+        .persistent()
+        .get(&GROUP_LIST)
+        .unwrap_or(Vec::new(env));
+    list.push_back(group_id.clone());
+    env.storage().persistent().set(&GROUP_LIST, &list);
+    Ok(())
+}
+
+pub fn get_group(env: &Env, group_id: &Symbol) -> Option<TenantGroup> {
+    env.storage().persistent().get(&group_key(group_id))
+}
+
+/// The group `tenant` belongs to, if any.
+pub fn group_of(env: &Env, tenant: &Address) -> Option<Symbol> {
+    env.storage().persistent().get(&member_key(tenant))
+}
+
+pub fn add_member(env: &Env, group_id: &Symbol, tenant: &Address) -> Result<(), GroupError> {
+    let mut group = get_group(env, group_id).ok_or(GroupError::GroupNotFound)?;
+    if group_of(env, tenant).is_some() {
+        return Err(GroupError::AlreadyInGroup);
+    }
+    group.members.push_back(tenant.clone());
+    set_membership(env, tenant, group_id);
+    save_group(env, &group);
+    Ok(())
+}
+
+pub fn remove_member(env: &Env, group_id: &Symbol, tenant: &Address) -> Result<(), GroupError> {
+    let mut group = get_group(env, group_id).ok_or(GroupError::GroupNotFound)?;
+    let index = group
+        .members
+        .first_index_of(tenant)
+        .ok_or(GroupError::NotAMember)?;
+    group.members.remove(index);
+    env.storage().persistent().remove(&member_key(tenant));
+    save_group(env, &group);
+    Ok(())
+}
+
+/// Units consumed from the group's pool in the current cycle.
+pub fn get_usage(env: &Env, group_id: &Symbol) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&usage_key(group_id))
+        .unwrap_or(0)
+}
+
+/// Check whether the pool of `group_id` can absorb `delta` more units.
+pub fn check_pool(env: &Env, group_id: &Symbol, delta: u64) -> Result<(), GroupError> {
+    let group = get_group(env, group_id).ok_or(GroupError::GroupNotFound)?;
+    if get_usage(env, group_id).saturating_add(delta) > group.pooled_limit {
+        return Err(GroupError::PoolExhausted);
+    }
+    Ok(())
+}
+
+/// Add `delta` units to the pool usage of `group_id`.
+pub fn consume_pool(env: &Env, group_id: &Symbol, delta: u64) {
+    let key = usage_key(group_id);
+    let used = get_usage(env, group_id).saturating_add(delta);
+    env.storage().persistent().set(&key, &used);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Reset pool usage of every group (called at the start of each billing cycle).
+pub fn reset_all_usage(env: &Env) {
+    let list: Vec<Symbol> = env
+        .storage()
+        .persistent()
+        .get(&GROUP_LIST)
+        .unwrap_or(Vec::new(env));
+    for group_id in list.iter() {
+        env.storage().persistent().remove(&usage_key(&group_id));
+    }
+}
@@ -13,6 +13,11 @@
 //! Gas consumed by a lower-level tenant is **rolled up** into every ancestor's
 //! usage counters, enabling top-down quota enforcement and accurate billing.
 //!
+//! ## Tenant groups
+//! Tenants can also be placed in an ad-hoc group (see [`group`]) that shares
+//! a pooled quota across the hierarchy. A member's gas is checked against and
+//! consumed from the pool in addition to its own quota.
+//!
 //! ## Operation types & costs
 //! | Type    | Default cost (units) |
 //! |---------|---------------------|
@@ -29,17 +34,22 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod billing;
+pub mod correlation;
 pub mod events;
 pub mod gas_token;
+pub mod group;
 pub mod quota;
 
-use billing::{BillingError, BillingModel, BillingReport, Invoice, TenantUsageRecord};
+use billing::{
+    BillingError, BillingModel, BillingReport, CycleBoundaryInfo, Invoice, TenantUsageRecord,
+};
 use gas_token::GasTokenError;
-use quota::{QuotaError, QuotaUsage, TenantQuota};
+use group::{GroupError, TenantGroup};
+use quota::{QuotaError, QuotaUsage, RolloverConfig, TenantQuota};
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Symbol,
-    Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env,
+    Symbol, Vec,
 };
 
 // ── Storage keys ──────────────────────────────────────────────────────────────
@@ -54,6 +64,15 @@ const GAS_COSTS: Symbol = symbol_short!("GAS_CST");
 /// Percentage of total quota consumed before a `QuotaAlertEvent` fires.
 const ALERT_THRESHOLD_PCT: u64 = 80;
 
+/// Width of the usage buckets above the threshold; one alert fires per
+/// bucket entered (80, 85, 90, 95, 100).
+const ALERT_BUCKET_PCT: u64 = 5;
+
+/// Most ancestors any walk up the tenant tree visits. The four
+/// `TenantLevel`s need at most three; the cap bounds the walk even if parent
+/// links were ever to form a cycle.
+const MAX_HIERARCHY_DEPTH: u32 = 16;
+
 const TTL_THRESHOLD: u32 = 5_184_000;
 const TTL_EXTEND_TO: u32 = 10_368_000;
 
@@ -121,6 +140,23 @@ pub struct Tenant {
     pub active: bool,
 }
 
+/// Pre-flight cost of a single metered operation, as `record_gas` would
+/// charge it right now.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CostEstimate {
+    /// Gas units the operation consumes under the current `GasCosts`.
+    pub units: u64,
+    /// Gas tokens burned (prepaid) or added to the cycle invoice (postpaid).
+    pub token_cost: u64,
+    pub billing_model: BillingModel,
+    /// Whether the tenant's quota, including burst, admits the operation.
+    pub within_quota: bool,
+    /// Whether a prepaid tenant's gas-token account can cover `token_cost`.
+    /// Always `true` for postpaid tenants.
+    pub can_pay: bool,
+}
+
 // ── Contract errors ───────────────────────────────────────────────────────────
 
 #[contracterror]
@@ -143,10 +179,26 @@ pub enum MeteringError {
     GasTokenAccountFrozen = 14,
     GasTokenInsufficientBalance = 15,
     ZeroMintAmount = 16,
+    /// The operation executed before the last cycle close and its grace
+    /// window has elapsed.
+    CycleGraceExpired = 17,
+    GroupNotFound = 18,
+    GroupAlreadyExists = 19,
+    TenantAlreadyInGroup = 20,
+    TenantNotInGroup = 21,
+    /// The tenant's group has exhausted its pooled quota.
+    GroupQuotaExceeded = 22,
+    /// The tenant has exhausted the sub-limit for this operation type.
+    OperationQuotaExceeded = 23,
+    /// The refund exceeds what the tenant has burned net of earlier refunds.
+    RefundExceedsBurned = 24,
 }
 
-fn map_quota_error(_e: QuotaError) -> MeteringError {
-    MeteringError::QuotaExceeded
+fn map_quota_error(e: QuotaError) -> MeteringError {
+    match e {
+        QuotaError::QuotaExceeded => MeteringError::QuotaExceeded,
+        QuotaError::OperationLimitExceeded => MeteringError::OperationQuotaExceeded,
+    }
 }
 
 fn map_billing_error(e: BillingError) -> MeteringError {
@@ -156,6 +208,7 @@ fn map_billing_error(e: BillingError) -> MeteringError {
         BillingError::InvoiceNotFound => MeteringError::InvoiceNotFound,
         BillingError::AlreadySettled => MeteringError::AlreadySettled,
         BillingError::InsufficientPrepaidBalance => MeteringError::InsufficientPrepaidBalance,
+        BillingError::Overpayment => MeteringError::InvalidInput,
     }
 }
 
@@ -164,6 +217,17 @@ fn map_gas_token_error(e: GasTokenError) -> MeteringError {
         GasTokenError::AccountFrozen => MeteringError::GasTokenAccountFrozen,
         GasTokenError::InsufficientBalance => MeteringError::GasTokenInsufficientBalance,
         GasTokenError::ZeroMintAmount => MeteringError::ZeroMintAmount,
+        GasTokenError::RefundExceedsBurned => MeteringError::RefundExceedsBurned,
+    }
+}
+
+fn map_group_error(e: GroupError) -> MeteringError {
+    match e {
+        GroupError::GroupNotFound => MeteringError::GroupNotFound,
+        GroupError::GroupAlreadyExists => MeteringError::GroupAlreadyExists,
+        GroupError::AlreadyInGroup => MeteringError::TenantAlreadyInGroup,
+        GroupError::NotAMember => MeteringError::TenantNotInGroup,
+        GroupError::PoolExhausted => MeteringError::GroupQuotaExceeded,
     }
 }
 
@@ -276,18 +340,22 @@ impl MeteringContract {
         Self::require_initialized(&env)?;
         Self::require_admin(&env, &caller)?;
 
-        // Ensure not already registered.
-        let key = tenant_key(&tenant);
-        if env.storage().persistent().has(&key) {
-            return Err(MeteringError::TenantAlreadyExists);
-        }
-
-        // For non-root tenants, verify parent exists.
+        // For non-root tenants, verify parent exists and that linking
+        // `tenant → parent` would not close a loop in the hierarchy.
         if level != TenantLevel::Organization {
             let parent_key_val = tenant_key(&parent);
             if !env.storage().persistent().has(&parent_key_val) {
                 return Err(MeteringError::TenantNotFound);
             }
+            if Self::has_ancestor(&env, &parent, &tenant) {
+                return Err(MeteringError::InvalidInput);
+            }
+        }
+
+        // Ensure not already registered.
+        let key = tenant_key(&tenant);
+        if env.storage().persistent().has(&key) {
+            return Err(MeteringError::TenantAlreadyExists);
         }
 
         let record = Tenant {
@@ -393,25 +461,176 @@ impl MeteringContract {
         quota::get_usage(&env, &tenant)
     }
 
+    /// Configure whether a tenant's unused quota rolls over into the next
+    /// cycle, and the cap on the accumulated balance. Admin only.
+    /// Disabling rollover forfeits the current balance.
+    pub fn set_rollover_config(
+        env: Env,
+        caller: Address,
+        tenant: Address,
+        rollover_enabled: bool,
+        max_rollover_units: u64,
+    ) -> Result<(), MeteringError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        if !env.storage().persistent().has(&tenant_key(&tenant)) {
+            return Err(MeteringError::TenantNotFound);
+        }
+
+        quota::set_rollover_config(
+            &env,
+            &tenant,
+            &RolloverConfig {
+                rollover_enabled,
+                max_rollover_units,
+            },
+        );
+        Ok(())
+    }
+
+    /// Return the rollover settings for a tenant.
+    pub fn get_rollover_config(env: Env, tenant: Address) -> RolloverConfig {
+        quota::get_rollover_config(&env, &tenant)
+    }
+
+    /// Return the rollover units a tenant has available this cycle.
+    pub fn get_rollover_balance(env: Env, tenant: Address) -> u64 {
+        quota::get_rollover_balance(&env, &tenant)
+    }
+
+    // ── Tenant groups ─────────────────────────────────────────────────────────
+
+    /// Create a group of registered tenants sharing `pooled_limit` gas units
+    /// per cycle. A tenant may belong to only one group. Admin only.
+    pub fn create_tenant_group(
+        env: Env,
+        caller: Address,
+        group_id: Symbol,
+        members: Vec<Address>,
+        pooled_limit: u64,
+    ) -> Result<(), MeteringError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        for member in members.iter() {
+            if !env.storage().persistent().has(&tenant_key(&member)) {
+                return Err(MeteringError::TenantNotFound);
+            }
+        }
+        group::create_group(&env, &group_id, &members, pooled_limit).map_err(map_group_error)
+    }
+
+    /// Add a registered tenant to an existing group. Admin only.
+    pub fn add_group_member(
+        env: Env,
+        caller: Address,
+        group_id: Symbol,
+        tenant: Address,
+    ) -> Result<(), MeteringError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        if !env.storage().persistent().has(&tenant_key(&tenant)) {
+            return Err(MeteringError::TenantNotFound);
+        }
+        group::add_member(&env, &group_id, &tenant).map_err(map_group_error)
+    }
+
+    /// Remove a tenant from a group. Units it already drew from the pool
+    /// stay counted for the current cycle. Admin only.
+    pub fn remove_group_member(
+        env: Env,
+        caller: Address,
+        group_id: Symbol,
+        tenant: Address,
+    ) -> Result<(), MeteringError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        group::remove_member(&env, &group_id, &tenant).map_err(map_group_error)
+    }
+
+    /// Return a group's configuration and member list.
+    pub fn get_tenant_group(env: Env, group_id: Symbol) -> Option<TenantGroup> {
+        group::get_group(&env, &group_id)
+    }
+
+    /// Return the id of the group `tenant` belongs to, if any.
+    pub fn get_group_of(env: Env, tenant: Address) -> Option<Symbol> {
+        group::group_of(&env, &tenant)
+    }
+
+    /// Return the units a group's members have drawn from the pool this cycle.
+    pub fn get_group_usage(env: Env, group_id: Symbol) -> u64 {
+        group::get_usage(&env, &group_id)
+    }
+
     // ── Gas recording ─────────────────────────────────────────────────────────
 
     /// Record gas consumption for a tenant and propagate up the hierarchy.
     ///
     /// - Validates the tenant is registered and active.
-    /// - Enforces quota (with burst).
-    /// - If prepaid: burns gas tokens.
+    /// - Draws on any rollover balance before the base quota.
+    /// - Enforces quota (with burst) on the remainder.
+    /// - Enforces the pooled quota of the tenant's group, if any.
+    /// - If prepaid: checks the gas-token balance up front, and burns the
+    ///   tokens only after usage has been committed.
     /// - Propagates usage to every ancestor.
-    /// - Emits alert event when tenant crosses 80 % of total quota.
+    /// - Emits an alert event each time the tenant enters a higher 5 % bucket
+    ///   at or above 80 % of total quota.
+    /// - If `correlation_id` is given, adds the units to that workflow's
+    ///   tally (see [`correlation`]).
     pub fn record_gas(
         env: Env,
         caller: Address,
         tenant: Address,
         op_type: OperationType,
+        correlation_id: Option<BytesN<32>>,
+    ) -> Result<(), MeteringError> {
+        let executed_at = env.ledger().timestamp();
+        Self::charge_gas(env, caller, tenant, op_type, executed_at, correlation_id)
+    }
+
+    /// Record gas for an operation that executed at `executed_at`.
+    ///
+    /// Operations executed before the last cycle close are charged to that
+    /// closed cycle while its grace window is open, and rejected with
+    /// `CycleGraceExpired` afterwards. Everything else is charged to the
+    /// current cycle exactly as `record_gas` does.
+    pub fn record_gas_at(
+        env: Env,
+        caller: Address,
+        tenant: Address,
+        op_type: OperationType,
+        executed_at: u64,
+    ) -> Result<(), MeteringError> {
+        Self::charge_gas(env, caller, tenant, op_type, executed_at, None)
+    }
+
+    /// Record a batch of operations for one tenant in a single call.
+    ///
+    /// Equivalent in usage to calling `record_gas` once per entry of `ops`,
+    /// but units are summed per operation type first: quota, the group pool
+    /// and prepaid balance are checked once against the aggregate, and the
+    /// hierarchy is walked once, applying every type's total to each
+    /// ancestor. One `GasRecordedEvent` is emitted per operation type per
+    /// tenant, carrying that type's total.
+    ///
+    /// The batch is all-or-nothing; an empty `ops` is rejected with
+    /// `InvalidInput`.
+    pub fn record_gas_batch(
+        env: Env,
+        caller: Address,
+        tenant: Address,
+        ops: Vec<OperationType>,
     ) -> Result<(), MeteringError> {
         caller.require_auth();
         Self::require_initialized(&env)?;
 
-        // Validate tenant.
+        if ops.is_empty() {
+            return Err(MeteringError::InvalidInput);
+        }
+
         let tenant_record: Tenant = env
             .storage()
             .persistent()
@@ -422,37 +641,154 @@ impl MeteringContract {
             return Err(MeteringError::TenantInactive);
         }
 
-        // Determine gas units for this operation type.
-        let costs: GasCosts = env
-            .storage()
-            .instance()
-            .get(&GAS_COSTS)
-            .unwrap_or_else(GasCosts::default_costs);
-        let units = costs.cost_for(&op_type);
+        let totals = Self::batch_totals(&env, &ops);
+        let mut units = 0u64;
+        for (_, type_units) in totals.iter() {
+            units = units.saturating_add(type_units);
+        }
 
-        // Enforce quota for the direct tenant.
-        quota::check_quota(&env, &tenant, &op_type, units).map_err(|e| {
-            events::publish_quota_exceeded(&env, tenant.clone(), op_type.clone());
+        // Rollover covers what it can, applied to the types in order; the
+        // rest counts against the base quota.
+        let mut rollover = quota::rollover_available(&env, &tenant, units);
+        let mut base_totals: Vec<(OperationType, u64)> = Vec::new(&env);
+        for (op_type, type_units) in totals.iter() {
+            let covered = rollover.min(type_units);
+            rollover -= covered;
+            if type_units > covered {
+                base_totals.push_back((op_type, type_units - covered));
+            }
+        }
+
+        // Enforce quota for the direct tenant once, on the aggregate.
+        quota::check_quota_batch(&env, &tenant, &base_totals).map_err(|(op_type, e)| {
+            events::publish_quota_exceeded(&env, tenant.clone(), op_type);
             map_quota_error(e)
         })?;
 
-        // Prepaid: debit gas tokens.
-        let model = billing::get_billing_model(&env, &tenant);
-        if model == BillingModel::Prepaid {
-            let current_balance = gas_token::balance_of(&env, &tenant);
-            if gas_token::is_frozen(&env, &tenant) {
-                return Err(MeteringError::GasTokenAccountFrozen);
+        let group_id = group::group_of(&env, &tenant);
+        if let Some(group_id) = &group_id {
+            group::check_pool(&env, group_id, units).map_err(|e| {
+                if let Some((op_type, _)) = totals.last() {
+                    events::publish_quota_exceeded(&env, tenant.clone(), op_type);
+                }
+                map_group_error(e)
+            })?;
+        }
+
+        Self::check_prepaid(&env, &tenant, units)?;
+
+        quota::draw_rollover(&env, &tenant, units);
+        for (op_type, base_units) in base_totals.iter() {
+            quota::consume_quota(&env, &tenant, &op_type, base_units);
+        }
+        if let Some(group_id) = &group_id {
+            group::consume_pool(&env, group_id, units);
+        }
+        Self::burn_prepaid(&env, &tenant, units)?;
+
+        let cycle_id = billing::current_cycle_id(&env);
+        for (op_type, type_units) in totals.iter() {
+            events::publish_gas_recorded(&env, tenant.clone(), op_type, type_units, cycle_id);
+        }
+        Self::maybe_emit_alert(&env, &tenant);
+
+        Self::rollup_gas(&env, &tenant_record, &totals, cycle_id, 0);
+
+        Ok(())
+    }
+
+    /// Units recorded by `record_gas` calls tagged with `correlation_id` for
+    /// `tenant`; 0 if none, or if the tally has expired.
+    pub fn get_correlated_usage(env: Env, tenant: Address, correlation_id: BytesN<32>) -> u64 {
+        correlation::get_usage(&env, &tenant, &correlation_id)
+    }
+
+    fn charge_gas(
+        env: Env,
+        caller: Address,
+        tenant: Address,
+        op_type: OperationType,
+        executed_at: u64,
+        correlation_id: Option<BytesN<32>>,
+    ) -> Result<(), MeteringError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+
+        if executed_at > env.ledger().timestamp() {
+            return Err(MeteringError::InvalidInput);
+        }
+
+        // Validate tenant.
+        let tenant_record: Tenant = env
+            .storage()
+            .persistent()
+            .get(&tenant_key(&tenant))
+            .ok_or(MeteringError::TenantNotFound)?;
+
+        if !tenant_record.active {
+            return Err(MeteringError::TenantInactive);
+        }
+
+        // Determine gas units for this operation type.
+        let units = Self::gas_units(&env, &op_type);
+
+        let boundary = billing::get_boundary(&env);
+        if executed_at < boundary.closed_at {
+            if !boundary.in_grace {
+                return Err(MeteringError::CycleGraceExpired);
             }
-            if current_balance < units {
-                return Err(MeteringError::InsufficientPrepaidBalance);
+            Self::check_prepaid(&env, &tenant, units)?;
+            Self::charge_closed_cycle(
+                &env,
+                &tenant_record,
+                &op_type,
+                units,
+                boundary.closed_cycle_id,
+                0,
+            );
+            Self::burn_prepaid(&env, &tenant, units)?;
+            if let Some(correlation_id) = &correlation_id {
+                correlation::record(&env, &tenant, correlation_id, units);
             }
-            gas_token::burn(&env, &tenant, units).map_err(map_gas_token_error)?;
-            let new_balance = gas_token::balance_of(&env, &tenant);
-            events::publish_gas_token_burned(&env, tenant.clone(), units, new_balance);
+            return Ok(());
         }
 
-        // Commit usage for the direct tenant.
-        quota::consume_quota(&env, &tenant, &op_type, units);
+        // Rollover covers what it can; the rest counts against the base quota.
+        let base_units = units.saturating_sub(quota::rollover_available(&env, &tenant, units));
+
+        // Enforce quota for the direct tenant.
+        if base_units > 0 {
+            quota::check_quota(&env, &tenant, &op_type, base_units).map_err(|e| {
+                events::publish_quota_exceeded(&env, tenant.clone(), op_type.clone());
+                map_quota_error(e)
+            })?;
+        }
+
+        // Enforce the group's pooled quota on the full charge.
+        let group_id = group::group_of(&env, &tenant);
+        if let Some(group_id) = &group_id {
+            group::check_pool(&env, group_id, units).map_err(|e| {
+                events::publish_quota_exceeded(&env, tenant.clone(), op_type.clone());
+                map_group_error(e)
+            })?;
+        }
+
+        // Prepaid: make sure the tokens are there, but only burn them once
+        // usage has been committed.
+        Self::check_prepaid(&env, &tenant, units)?;
+
+        // Commit usage for the direct tenant and its group.
+        quota::draw_rollover(&env, &tenant, units);
+        if base_units > 0 {
+            quota::consume_quota(&env, &tenant, &op_type, base_units);
+        }
+        if let Some(group_id) = &group_id {
+            group::consume_pool(&env, group_id, units);
+        }
+        Self::burn_prepaid(&env, &tenant, units)?;
+        if let Some(correlation_id) = &correlation_id {
+            correlation::record(&env, &tenant, correlation_id, units);
+        }
 
         // Get active cycle id (0 if none).
         let cycle_id = billing::current_cycle_id(&env);
@@ -464,15 +800,199 @@ impl MeteringContract {
         Self::maybe_emit_alert(&env, &tenant);
 
         // Propagate consumption up the hierarchy.
-        Self::rollup_gas(&env, &tenant_record, &op_type, units, cycle_id);
+        Self::rollup_gas(
+            &env,
+            &tenant_record,
+            &vec![&env, (op_type, units)],
+            cycle_id,
+            0,
+        );
 
         Ok(())
     }
 
-    /// Walk up the tenant tree and apply usage to every ancestor.
-    fn rollup_gas(env: &Env, child: &Tenant, op_type: &OperationType, units: u64, cycle_id: u64) {
-        // Stop if child is an org (root) or parent == child.
-        if child.level == TenantLevel::Organization || child.parent == child.address {
+    /// Estimate what `record_gas` would charge `tenant` for `op_type`,
+    /// without consuming quota or burning tokens.
+    ///
+    /// Uses the same cost resolution as `record_gas`: the current `GasCosts`,
+    /// the tenant's billing model, quota (with burst), group pool, and — for
+    /// prepaid tenants — the gas-token balance and freeze state.
+    pub fn estimate_operation_cost(
+        env: Env,
+        tenant: Address,
+        op_type: OperationType,
+    ) -> Result<CostEstimate, MeteringError> {
+        Self::require_initialized(&env)?;
+
+        let tenant_record: Tenant = env
+            .storage()
+            .persistent()
+            .get(&tenant_key(&tenant))
+            .ok_or(MeteringError::TenantNotFound)?;
+        if !tenant_record.active {
+            return Err(MeteringError::TenantInactive);
+        }
+
+        let units = Self::gas_units(&env, &op_type);
+        let base_units = units.saturating_sub(quota::rollover_available(&env, &tenant, units));
+        let within_pool = match group::group_of(&env, &tenant) {
+            Some(group_id) => group::check_pool(&env, &group_id, units).is_ok(),
+            None => true,
+        };
+        let within_quota = within_pool
+            && (base_units == 0 || quota::check_quota(&env, &tenant, &op_type, base_units).is_ok());
+
+        let billing_model = billing::get_billing_model(&env, &tenant);
+        let can_pay = match billing_model {
+            BillingModel::Prepaid => {
+                !gas_token::is_frozen(&env, &tenant)
+                    && gas_token::balance_of(&env, &tenant) >= units
+            }
+            BillingModel::Postpaid => true,
+        };
+
+        Ok(CostEstimate {
+            units,
+            // Gas tokens are denominated 1:1 in gas units.
+            token_cost: units,
+            billing_model,
+            within_quota,
+            can_pay,
+        })
+    }
+
+    /// Fail if `tenant` is prepaid and cannot pay `units` gas tokens right
+    /// now. Called before usage is committed so a rejected operation leaves
+    /// nothing to undo.
+    fn check_prepaid(env: &Env, tenant: &Address, units: u64) -> Result<(), MeteringError> {
+        if billing::get_billing_model(env, tenant) != BillingModel::Prepaid {
+            return Ok(());
+        }
+        if gas_token::is_frozen(env, tenant) {
+            return Err(MeteringError::GasTokenAccountFrozen);
+        }
+        if gas_token::balance_of(env, tenant) < units {
+            return Err(MeteringError::InsufficientPrepaidBalance);
+        }
+        Ok(())
+    }
+
+    /// Burn `units` gas tokens if `tenant` is prepaid; no-op for postpaid.
+    fn burn_prepaid(env: &Env, tenant: &Address, units: u64) -> Result<(), MeteringError> {
+        if billing::get_billing_model(env, tenant) != BillingModel::Prepaid {
+            return Ok(());
+        }
+        Self::check_prepaid(env, tenant, units)?;
+        gas_token::burn(env, tenant, units).map_err(map_gas_token_error)?;
+        let new_balance = gas_token::balance_of(env, tenant);
+        events::publish_gas_token_burned(env, tenant.clone(), units, new_balance);
+        Ok(())
+    }
+
+    /// Charge a grace-window operation to the closed cycle `cycle_id` for
+    /// `tenant` and every ancestor, updating the stored report and postpaid
+    /// invoices. Quotas belong to the live cycle and are not touched.
+    ///
+    /// `depth` counts the ancestors already charged; the walk stops at
+    /// `MAX_HIERARCHY_DEPTH`.
+    fn charge_closed_cycle(
+        env: &Env,
+        tenant: &Tenant,
+        op_type: &OperationType,
+        units: u64,
+        cycle_id: u64,
+        depth: u32,
+    ) {
+        let cost = billing::add_late_usage(env, cycle_id, &tenant.address, op_type, units);
+        if cost > 0 && billing::get_billing_model(env, &tenant.address) == BillingModel::Postpaid {
+            billing::add_to_invoice(env, &tenant.address, cycle_id, cost);
+        }
+        events::publish_gas_recorded(
+            env,
+            tenant.address.clone(),
+            op_type.clone(),
+            units,
+            cycle_id,
+        );
+
+        if tenant.level == TenantLevel::Organization
+            || tenant.parent == tenant.address
+            || depth >= MAX_HIERARCHY_DEPTH
+        {
+            return;
+        }
+        let parent: Option<Tenant> = env.storage().persistent().get(&tenant_key(&tenant.parent));
+        if let Some(parent) = parent {
+            Self::charge_closed_cycle(env, &parent, op_type, units, cycle_id, depth + 1);
+        }
+    }
+
+    /// Whether `target` is `start` or one of its ancestors. A chain longer
+    /// than `MAX_HIERARCHY_DEPTH` counts as a match, so callers reject it.
+    fn has_ancestor(env: &Env, start: &Address, target: &Address) -> bool {
+        let mut current = start.clone();
+        for _ in 0..=MAX_HIERARCHY_DEPTH {
+            if current == *target {
+                return true;
+            }
+            let record: Option<Tenant> = env.storage().persistent().get(&tenant_key(&current));
+            match record {
+                Some(t) if t.level != TenantLevel::Organization && t.parent != t.address => {
+                    current = t.parent;
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Resolve the gas units charged for `op_type` under the current costs.
+    fn gas_units(env: &Env, op_type: &OperationType) -> u64 {
+        let costs: GasCosts = env
+            .storage()
+            .instance()
+            .get(&GAS_COSTS)
+            .unwrap_or_else(GasCosts::default_costs);
+        costs.cost_for(op_type)
+    }
+
+    /// Sum the units of `ops` per operation type, in declaration order.
+    /// Types absent from `ops` are omitted.
+    fn batch_totals(env: &Env, ops: &Vec<OperationType>) -> Vec<(OperationType, u64)> {
+        let mut totals: Vec<(OperationType, u64)> = Vec::new(env);
+        for op_type in [
+            OperationType::Read,
+            OperationType::Write,
+            OperationType::Compute,
+            OperationType::Storage,
+        ] {
+            let count = ops.iter().filter(|op| *op == op_type).count() as u64;
+            if count > 0 {
+                let units = Self::gas_units(env, &op_type).saturating_mul(count);
+                totals.push_back((op_type, units));
+            }
+        }
+        totals
+    }
+
+    /// Walk up the tenant tree and apply `totals` (units per operation type)
+    /// to every ancestor.
+    ///
+    /// `depth` counts the ancestors already charged; the walk stops at
+    /// `MAX_HIERARCHY_DEPTH`.
+    fn rollup_gas(
+        env: &Env,
+        child: &Tenant,
+        totals: &Vec<(OperationType, u64)>,
+        cycle_id: u64,
+        depth: u32,
+    ) {
+        // Stop if child is an org (root), parent == child, or the walk is
+        // already as deep as any hierarchy may be.
+        if child.level == TenantLevel::Organization
+            || child.parent == child.address
+            || depth >= MAX_HIERARCHY_DEPTH
+        {
             return;
         }
 
@@ -484,15 +1004,17 @@ impl MeteringContract {
         };
 
         // Apply to parent's usage.
-        quota::consume_quota(env, &parent_addr, op_type, units);
-        events::publish_gas_recorded(env, parent_addr.clone(), op_type.clone(), units, cycle_id);
+        for (op_type, units) in totals.iter() {
+            quota::consume_quota(env, &parent_addr, &op_type, units);
+            events::publish_gas_recorded(env, parent_addr.clone(), op_type, units, cycle_id);
+        }
         Self::maybe_emit_alert(env, &parent_addr);
 
         // Recurse.
         let parent_record: Option<Tenant> =
             env.storage().persistent().get(&tenant_key(&parent_addr));
         if let Some(pr) = parent_record {
-            Self::rollup_gas(env, &pr, op_type, units, cycle_id);
+            Self::rollup_gas(env, &pr, totals, cycle_id, depth + 1);
         }
     }
 
@@ -512,7 +1034,15 @@ impl MeteringContract {
             .checked_div(quota.total_limit)
             .unwrap_or(0);
 
-        if pct >= ALERT_THRESHOLD_PCT {
+        if pct < ALERT_THRESHOLD_PCT {
+            return;
+        }
+
+        // Only alert on entering a higher bucket; usage past the limit
+        // (burst) stays in the 100 % bucket.
+        let bucket = (pct.min(100) / ALERT_BUCKET_PCT * ALERT_BUCKET_PCT) as u32;
+        if bucket > quota::get_alert_bucket(env, tenant) {
+            quota::set_alert_bucket(env, tenant, bucket);
             events::publish_quota_alert(env, tenant.clone(), pct as u32);
         }
     }
@@ -520,7 +1050,7 @@ impl MeteringContract {
     // ── Billing cycle management ──────────────────────────────────────────────
 
     /// Open a new billing cycle. Admin only.
-    /// Resets usage counters for all registered tenants.
+    /// Resets usage counters for all registered tenants and groups.
     pub fn open_billing_cycle(env: Env, caller: Address) -> Result<u64, MeteringError> {
         caller.require_auth();
         Self::require_admin(&env, &caller)?;
@@ -539,6 +1069,7 @@ impl MeteringContract {
                 quota::reset_usage(&env, &addr);
             }
         }
+        group::reset_all_usage(&env);
 
         events::publish_cycle_opened(&env, cycle_id);
 
@@ -547,6 +1078,8 @@ impl MeteringContract {
 
     /// Close the current billing cycle and generate invoices for postpaid tenants.
     /// Returns a `BillingReport` summarising usage and costs for all tenants.
+    /// Unused quota of rollover-enabled tenants is carried into their
+    /// rollover balance.
     pub fn close_billing_cycle(env: Env, caller: Address) -> Result<BillingReport, MeteringError> {
         caller.require_auth();
         Self::require_admin(&env, &caller)?;
@@ -568,7 +1101,8 @@ impl MeteringContract {
                 // Usage buckets already store metered gas units at record time.
                 // Summing the buckets avoids retroactive re-pricing and keeps
                 // billing stable when gas costs are updated mid-cycle.
-                let total_cost = usage.total();
+                // Rollover draws are still billable usage.
+                let total_cost = usage.total().saturating_add(usage.rollover_used);
 
                 let record = TenantUsageRecord {
                     tenant: addr.clone(),
@@ -578,9 +1112,22 @@ impl MeteringContract {
                     compute_units: usage.compute_used,
                     storage_units: usage.storage_used,
                     burst_units: usage.burst_used,
+                    rollover_units: usage.rollover_used,
                     total_cost,
                 };
 
+                let accrued = quota::accrue_rollover(&env, &addr);
+                if accrued > 0 {
+                    let balance = quota::get_rollover_balance(&env, &addr);
+                    events::publish_rollover_accrued(
+                        &env,
+                        addr.clone(),
+                        cycle_id,
+                        accrued,
+                        balance,
+                    );
+                }
+
                 // Issue invoice for postpaid tenants with non-zero cost.
                 let model = billing::get_billing_model(&env, &addr);
                 if model == BillingModel::Postpaid && total_cost > 0 {
@@ -594,11 +1141,39 @@ impl MeteringContract {
 
         events::publish_cycle_closed(&env, cycle_id);
 
-        Ok(BillingReport {
+        let report = BillingReport {
             cycle_id,
             closed_at: env.ledger().timestamp(),
             records,
-        })
+        };
+        billing::store_report(&env, &report);
+
+        Ok(report)
+    }
+
+    /// Return the report for a closed cycle, including any charges recorded
+    /// during its grace window.
+    pub fn get_billing_report(env: Env, cycle_id: u64) -> Option<BillingReport> {
+        billing::get_report(&env, cycle_id)
+    }
+
+    /// Set how long after a close operations executed before it may still be
+    /// charged to the closed cycle. Applies to subsequent closes. Admin only.
+    pub fn set_cycle_grace(env: Env, caller: Address, seconds: u64) -> Result<(), MeteringError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        billing::set_cycle_grace(&env, seconds);
+        Ok(())
+    }
+
+    /// Return the configured cycle grace period in seconds.
+    pub fn get_cycle_grace(env: Env) -> u64 {
+        billing::get_cycle_grace(&env)
+    }
+
+    /// Return the last cycle close and whether its grace window is open.
+    pub fn get_cycle_boundary_info(env: Env) -> CycleBoundaryInfo {
+        billing::get_boundary(&env)
     }
 
     /// Return the current active cycle id (0 if no cycle has been opened).
@@ -646,6 +1221,28 @@ impl MeteringContract {
         Ok(())
     }
 
+    /// Pay part of a postpaid invoice.  The invoice is settled once its
+    /// outstanding balance reaches zero; paying more than is outstanding
+    /// fails with `InvalidInput`.
+    pub fn settle_invoice_partial(
+        env: Env,
+        caller: Address,
+        cycle_id: u64,
+        amount: u64,
+    ) -> Result<(), MeteringError> {
+        caller.require_auth();
+        if amount == 0 {
+            return Err(MeteringError::InvalidInput);
+        }
+        let inv = billing::settle_invoice_partial(&env, &caller, cycle_id, amount)
+            .map_err(map_billing_error)?;
+        events::publish_invoice_payment(&env, caller.clone(), cycle_id, amount, inv.outstanding);
+        if inv.settled {
+            events::publish_invoice_settled(&env, caller, cycle_id);
+        }
+        Ok(())
+    }
+
     // ── Gas token management ──────────────────────────────────────────────────
 
     /// Mint gas tokens to a tenant (prepaid top-up). Admin only.
@@ -669,6 +1266,32 @@ impl MeteringContract {
         Ok(())
     }
 
+    /// Return gas tokens burned for an operation that never took effect,
+    /// e.g. when the caller's own transaction failed afterwards. Admin only.
+    ///
+    /// The total supply is unchanged; the tokens were minted once already.
+    /// Fails with `RefundExceedsBurned` if `amount` is more than the tenant
+    /// has burned minus what was already refunded.
+    pub fn refund_gas_tokens(
+        env: Env,
+        caller: Address,
+        tenant: Address,
+        amount: u64,
+    ) -> Result<(), MeteringError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        if amount == 0 {
+            return Err(MeteringError::InvalidInput);
+        }
+        gas_token::refund(&env, &tenant, amount).map_err(map_gas_token_error)?;
+
+        let new_balance = gas_token::balance_of(&env, &tenant);
+        events::publish_gas_token_refunded(&env, tenant, amount, new_balance);
+
+        Ok(())
+    }
+
     /// Return the gas token balance for a tenant.
     pub fn gas_token_balance(env: Env, tenant: Address) -> u64 {
         gas_token::balance_of(&env, &tenant)
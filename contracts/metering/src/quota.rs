@@ -4,17 +4,32 @@
 //! (Organization → Clinic → Provider → Patient). When a quota is not set at a
 //! lower level, the system walks up the hierarchy to find an inherited limit.
 //!
+//! ## Per-operation sub-limits
+//! `total_limit` caps all operation types together. Each type may also carry
+//! its own optional sub-limit (e.g. `compute_limit`), which is a hard cap: an
+//! operation that would exceed it is rejected even while the total has
+//! headroom. A `None` sub-limit leaves that type bounded by the total only.
+//!
 //! ## Burst allowance
 //! Each quota may specify a `burst_allowance` — extra capacity that may be
-//! consumed beyond the base limit before the tenant is blocked. Burst usage is
+//! consumed beyond `total_limit` before the tenant is blocked. Burst usage is
 //! tracked separately and replenished when a new billing cycle starts.
+//! Burst never extends a per-operation sub-limit.
+//!
+//! ## Rollover
+//! Tenants with rollover enabled carry unused `total_limit` capacity into a
+//! capped `rollover_balance` when a cycle closes. `record_gas` draws on that
+//! balance before the base quota, tracking the draw in `rollover_used`.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+use soroban_sdk::{contracttype, symbol_short, vec, Address, Env, Symbol, Vec};
 
 // ── Storage key prefixes ─────────────────────────────────────────────────────
 
 pub const QUOTA_KEY: Symbol = symbol_short!("QUOTA");
 pub const BURST_KEY: Symbol = symbol_short!("BURST");
+pub const ROLLOVER_CFG_KEY: Symbol = symbol_short!("ROLL_CFG");
+pub const ROLLOVER_BAL_KEY: Symbol = symbol_short!("ROLL_BAL");
+pub const ALERT_BUCKET_KEY: Symbol = symbol_short!("ALRT_BKT");
 
 pub const TTL_THRESHOLD: u32 = 5_184_000;
 pub const TTL_EXTEND_TO: u32 = 10_368_000;
@@ -28,14 +43,14 @@ pub const TTL_EXTEND_TO: u32 = 10_368_000;
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TenantQuota {
-    /// Maximum gas units for read operations per cycle.
-    pub read_limit: u64,
-    /// Maximum gas units for write operations per cycle.
-    pub write_limit: u64,
-    /// Maximum gas units for compute operations per cycle.
-    pub compute_limit: u64,
-    /// Maximum gas units for storage operations per cycle.
-    pub storage_limit: u64,
+    /// Maximum gas units for read operations per cycle (`None`: total only).
+    pub read_limit: Option<u64>,
+    /// Maximum gas units for write operations per cycle (`None`: total only).
+    pub write_limit: Option<u64>,
+    /// Maximum gas units for compute operations per cycle (`None`: total only).
+    pub compute_limit: Option<u64>,
+    /// Maximum gas units for storage operations per cycle (`None`: total only).
+    pub storage_limit: Option<u64>,
     /// Total gas cap across all operation types per cycle.
     pub total_limit: u64,
     /// Additional gas units available beyond `total_limit` (burst).
@@ -54,10 +69,21 @@ pub struct QuotaUsage {
     pub storage_used: u64,
     /// Burst units consumed (drawn from `burst_allowance`).
     pub burst_used: u64,
+    /// Units served from the rollover balance instead of the base buckets.
+    pub rollover_used: u64,
+}
+
+/// Per-tenant rollover settings.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolloverConfig {
+    pub rollover_enabled: bool,
+    /// Cap on the accumulated rollover balance.
+    pub max_rollover_units: u64,
 }
 
 impl QuotaUsage {
-    /// Total gas across all non-burst buckets.
+    /// Total gas across all base buckets (excludes rollover draws).
     pub fn total(&self) -> u64 {
         self.read_used
             .saturating_add(self.write_used)
@@ -76,6 +102,10 @@ fn usage_key(tenant: &Address) -> (Symbol, Address) {
     (symbol_short!("QUSAGE"), tenant.clone())
 }
 
+fn alert_bucket_key(tenant: &Address) -> (Symbol, Address) {
+    (ALERT_BUCKET_KEY, tenant.clone())
+}
+
 fn extend_ttl(env: &Env, key: &(Symbol, Address)) {
     env.storage()
         .persistent()
@@ -120,6 +150,7 @@ pub fn get_usage(env: &Env, tenant: &Address) -> QuotaUsage {
             compute_used: 0,
             storage_used: 0,
             burst_used: 0,
+            rollover_used: 0,
         }
     }
 }
@@ -131,7 +162,23 @@ pub fn set_usage(env: &Env, tenant: &Address, usage: &QuotaUsage) {
     extend_ttl(env, &key);
 }
 
+/// Highest usage-percentage bucket a quota alert has fired for this cycle
+/// (0 if none has).
+pub fn get_alert_bucket(env: &Env, tenant: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&alert_bucket_key(tenant))
+        .unwrap_or(0)
+}
+
+pub fn set_alert_bucket(env: &Env, tenant: &Address, bucket: u32) {
+    let key = alert_bucket_key(tenant);
+    env.storage().persistent().set(&key, &bucket);
+    extend_ttl(env, &key);
+}
+
 /// Reset usage counters for a tenant (called at the start of each billing cycle).
+/// Also re-arms the tenant's quota alerts.
 pub fn reset_usage(env: &Env, tenant: &Address) {
     let key = usage_key(tenant);
     let zeroed = QuotaUsage {
@@ -140,23 +187,41 @@ pub fn reset_usage(env: &Env, tenant: &Address) {
         compute_used: 0,
         storage_used: 0,
         burst_used: 0,
+        rollover_used: 0,
     };
     env.storage().persistent().set(&key, &zeroed);
     extend_ttl(env, &key);
+    env.storage().persistent().remove(&alert_bucket_key(tenant));
 }
 
 /// Check whether adding `delta` units to a specific bucket would breach
 /// the configured quota.
 ///
 /// Returns `Ok(())` if within limits (including burst), or
-/// `Err(QuotaError)` if the operation would exceed both the hard limit and
-/// the burst allowance.
+/// `Err(QuotaError::OperationLimitExceeded)` if the op type's sub-limit
+/// would be exceeded, or `Err(QuotaError::QuotaExceeded)` if the operation
+/// would exceed both the total limit and the burst allowance.
 pub fn check_quota(
     env: &Env,
     tenant: &Address,
     op_type: &super::OperationType,
     delta: u64,
 ) -> Result<(), QuotaError> {
+    check_quota_batch(env, tenant, &vec![env, (op_type.clone(), delta)]).map_err(|(_, e)| e)
+}
+
+/// Check whether adding every `(op_type, delta)` in `deltas` together would
+/// breach the configured quota.
+///
+/// Each op type's sub-limit is checked against its own delta, and the total
+/// (with burst) against the sum, as [`check_quota`] does for a single
+/// operation. On failure, returns the op type whose delta breaches its
+/// sub-limit or, for the total, first tips the running sum over.
+pub fn check_quota_batch(
+    env: &Env,
+    tenant: &Address,
+    deltas: &Vec<(super::OperationType, u64)>,
+) -> Result<(), (super::OperationType, QuotaError)> {
     let quota = match get_quota(env, tenant) {
         Some(q) if q.enabled => q,
         // No quota configured, or quota disabled → allow all.
@@ -164,33 +229,37 @@ pub fn check_quota(
     };
 
     let usage = get_usage(env, tenant);
+    let burst_remaining = quota.burst_allowance.saturating_sub(usage.burst_used);
+    let mut batch_total = 0u64;
 
-    // 1. Per-type bucket check.
-    let (bucket_used, bucket_limit) = match op_type {
-        super::OperationType::Read => (usage.read_used, quota.read_limit),
-        super::OperationType::Write => (usage.write_used, quota.write_limit),
-        super::OperationType::Compute => (usage.compute_used, quota.compute_limit),
-        super::OperationType::Storage => (usage.storage_used, quota.storage_limit),
-    };
-
-    // 2. Total cap check.
-    let total_after = usage.total().saturating_add(delta);
-
-    if bucket_used.saturating_add(delta) <= bucket_limit && total_after <= quota.total_limit {
-        return Ok(());
-    }
+    for (op_type, delta) in deltas.iter() {
+        // 1. Per-type sub-limit: a hard cap, not covered by burst.
+        let (bucket_used, bucket_limit) = match op_type {
+            super::OperationType::Read => (usage.read_used, quota.read_limit),
+            super::OperationType::Write => (usage.write_used, quota.write_limit),
+            super::OperationType::Compute => (usage.compute_used, quota.compute_limit),
+            super::OperationType::Storage => (usage.storage_used, quota.storage_limit),
+        };
+        if let Some(limit) = bucket_limit {
+            if bucket_used.saturating_add(delta) > limit {
+                return Err((op_type, QuotaError::OperationLimitExceeded));
+            }
+        }
 
-    // 3. Try to draw from burst allowance.
-    let burst_remaining = quota.burst_allowance.saturating_sub(usage.burst_used);
-    if burst_remaining >= delta {
-        return Ok(()); // Will be drawn from burst when usage is committed.
+        // 2. Total cap check, falling back to burst allowance (drawn when
+        //    usage is committed).
+        batch_total = batch_total.saturating_add(delta);
+        let total_after = usage.total().saturating_add(batch_total);
+        if total_after > quota.total_limit && burst_remaining < batch_total {
+            return Err((op_type, QuotaError::QuotaExceeded));
+        }
     }
 
-    Err(QuotaError::QuotaExceeded)
+    Ok(())
 }
 
 /// Apply gas consumption to a tenant's usage counters.
-/// If the regular bucket is exhausted, overflow spills into `burst_used`.
+/// If `total_limit` is exhausted, overflow spills into `burst_used`.
 ///
 /// # Panics
 /// This function does not panic; overflows are capped with saturating arithmetic.
@@ -199,20 +268,9 @@ pub fn consume_quota(env: &Env, tenant: &Address, op_type: &super::OperationType
     let mut usage = get_usage(env, tenant);
 
     let burst_draw = if let Some(ref quota) = quota_opt {
-        let (bucket_used, bucket_limit) = match op_type {
-            super::OperationType::Read => (usage.read_used, quota.read_limit),
-            super::OperationType::Write => (usage.write_used, quota.write_limit),
-            super::OperationType::Compute => (usage.compute_used, quota.compute_limit),
-            super::OperationType::Storage => (usage.storage_used, quota.storage_limit),
-        };
-
-        // How much fits in the regular bucket?
-        let headroom = bucket_limit.saturating_sub(bucket_used);
-        if headroom >= delta {
-            0u64 // Fits entirely in the regular bucket.
-        } else {
-            delta.saturating_sub(headroom) // Remainder goes to burst.
-        }
+        // How much fits under the total cap? The remainder goes to burst.
+        let headroom = quota.total_limit.saturating_sub(usage.total());
+        delta.saturating_sub(headroom)
     } else {
         0u64
     };
@@ -233,10 +291,104 @@ pub fn consume_quota(env: &Env, tenant: &Address, op_type: &super::OperationType
     set_usage(env, tenant, &usage);
 }
 
+// ── Rollover ──────────────────────────────────────────────────────────────────
+
+fn rollover_cfg_key(tenant: &Address) -> (Symbol, Address) {
+    (ROLLOVER_CFG_KEY, tenant.clone())
+}
+
+fn rollover_bal_key(tenant: &Address) -> (Symbol, Address) {
+    (ROLLOVER_BAL_KEY, tenant.clone())
+}
+
+/// Persist rollover settings. Disabling rollover forfeits any balance.
+pub fn set_rollover_config(env: &Env, tenant: &Address, config: &RolloverConfig) {
+    let key = rollover_cfg_key(tenant);
+    env.storage().persistent().set(&key, config);
+    extend_ttl(env, &key);
+    if !config.rollover_enabled {
+        env.storage().persistent().remove(&rollover_bal_key(tenant));
+    }
+}
+
+/// Rollover settings for a tenant (disabled if never configured).
+pub fn get_rollover_config(env: &Env, tenant: &Address) -> RolloverConfig {
+    env.storage()
+        .persistent()
+        .get(&rollover_cfg_key(tenant))
+        .unwrap_or(RolloverConfig {
+            rollover_enabled: false,
+            max_rollover_units: 0,
+        })
+}
+
+pub fn get_rollover_balance(env: &Env, tenant: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&rollover_bal_key(tenant))
+        .unwrap_or(0)
+}
+
+fn set_rollover_balance(env: &Env, tenant: &Address, balance: u64) {
+    let key = rollover_bal_key(tenant);
+    env.storage().persistent().set(&key, &balance);
+    extend_ttl(env, &key);
+}
+
+/// Carry this cycle's unused `total_limit` into the rollover balance, capped
+/// at `max_rollover_units`. Returns the units actually added.
+pub fn accrue_rollover(env: &Env, tenant: &Address) -> u64 {
+    let config = get_rollover_config(env, tenant);
+    if !config.rollover_enabled {
+        return 0;
+    }
+    let quota = match get_quota(env, tenant) {
+        Some(q) if q.enabled => q,
+        _ => return 0,
+    };
+
+    let unused = quota
+        .total_limit
+        .saturating_sub(get_usage(env, tenant).total());
+    let balance = get_rollover_balance(env, tenant);
+    let new_balance = balance
+        .saturating_add(unused)
+        .min(config.max_rollover_units)
+        .max(balance);
+    set_rollover_balance(env, tenant, new_balance);
+    new_balance.saturating_sub(balance)
+}
+
+/// Units of `delta` that the rollover balance would cover.
+pub fn rollover_available(env: &Env, tenant: &Address, delta: u64) -> u64 {
+    if !get_rollover_config(env, tenant).rollover_enabled {
+        return 0;
+    }
+    get_rollover_balance(env, tenant).min(delta)
+}
+
+/// Draw up to `delta` units from the rollover balance and record them in
+/// `rollover_used`. Returns the units drawn.
+pub fn draw_rollover(env: &Env, tenant: &Address, delta: u64) -> u64 {
+    let drawn = rollover_available(env, tenant, delta);
+    if drawn == 0 {
+        return 0;
+    }
+    let balance = get_rollover_balance(env, tenant);
+    set_rollover_balance(env, tenant, balance.saturating_sub(drawn));
+
+    let mut usage = get_usage(env, tenant);
+    usage.rollover_used = usage.rollover_used.saturating_add(drawn);
+    set_usage(env, tenant, &usage);
+    drawn
+}
+
 // ── Errors ────────────────────────────────────────────────────────────────────
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum QuotaError {
     /// The tenant has exhausted their quota and burst allowance.
     QuotaExceeded,
+    /// The operation type's own sub-limit is exhausted.
+    OperationLimitExceeded,
 }
@@ -9,15 +9,22 @@
 //! - Prepaid and postpaid billing models
 //! - Gas token minting, burning, and freeze/unfreeze
 //! - Hierarchical rollup (org → clinic → provider)
-//! - Alert threshold events
+//! - Alert threshold events, one per 5 % bucket crossed
 //! - Edge cases: zero usage, exact quota boundary, multiple cycles
+//! - Cost estimation matching `record_gas` charges
+//! - Quota rollover accrual and draw-down
+//! - Billing-cycle grace window attribution
+//! - Tenant groups sharing a pooled quota
+//! - Usage attribution by correlation id
+//! - Batched recording matching sequential `record_gas` calls
 
 #![allow(unused_variables, unused_imports)]
 extern crate std;
 
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    vec, Address, Env, FromVal, IntoVal, TryFromVal, Vec,
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, BytesN, Env, FromVal, IntoVal, TryFromVal, Vec,
 };
 
 use crate::{
@@ -47,10 +54,10 @@ fn setup() -> (Env, MeteringContractClient<'static>, Address) {
 /// Default quota — 10 read, 10 write, 10 compute, 10 storage, 40 total, 5 burst.
 fn default_quota(env: &Env) -> TenantQuota {
     TenantQuota {
-        read_limit: 10,
-        write_limit: 10,
-        compute_limit: 10,
-        storage_limit: 10,
+        read_limit: Some(10),
+        write_limit: Some(10),
+        compute_limit: Some(10),
+        storage_limit: Some(10),
         total_limit: 40,
         burst_allowance: 5,
         enabled: true,
@@ -204,6 +211,25 @@ fn test_register_clinic_without_parent_fails() {
     assert_eq!(result, Err(Ok(MeteringError::TenantNotFound)));
 }
 
+#[test]
+fn test_register_rejects_hierarchy_cycle() {
+    let (env, client, admin) = setup();
+    // a → b → c, from the organization down.
+    let a = register_org(&client, &admin, &env);
+    let b = register_clinic(&client, &admin, &env, &a);
+    let c = register_provider(&client, &admin, &env, &b);
+
+    let result = client.try_register_tenant(&admin, &a, &TenantLevel::Clinic, &c);
+    assert_eq!(result, Err(Ok(MeteringError::InvalidInput)));
+    let result = client.try_register_tenant(&admin, &b, &TenantLevel::Clinic, &b);
+    assert_eq!(result, Err(Ok(MeteringError::InvalidInput)));
+    assert_eq!(client.get_tenant(&a).level, TenantLevel::Organization);
+
+    // Rollup still terminates at the organization.
+    client.record_gas(&admin, &c, &OperationType::Read, &None);
+    assert_eq!(client.get_usage(&a).total(), 1);
+}
+
 #[test]
 fn test_deactivate_tenant() {
     let (env, client, admin) = setup();
@@ -261,7 +287,7 @@ fn test_usage_starts_at_zero() {
 fn test_record_read_gas_increments_usage() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     let usage = client.get_usage(&org);
     // Default read cost = 1
     assert_eq!(usage.read_used, 1);
@@ -271,7 +297,7 @@ fn test_record_read_gas_increments_usage() {
 fn test_record_write_gas_uses_write_cost() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
-    client.record_gas(&admin, &org, &OperationType::Write);
+    client.record_gas(&admin, &org, &OperationType::Write, &None);
     let usage = client.get_usage(&org);
     // Default write cost = 5
     assert_eq!(usage.write_used, 5);
@@ -281,7 +307,7 @@ fn test_record_write_gas_uses_write_cost() {
 fn test_record_compute_gas() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
-    client.record_gas(&admin, &org, &OperationType::Compute);
+    client.record_gas(&admin, &org, &OperationType::Compute, &None);
     let usage = client.get_usage(&org);
     // Default compute cost = 10
     assert_eq!(usage.compute_used, 10);
@@ -291,7 +317,7 @@ fn test_record_compute_gas() {
 fn test_record_storage_gas() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
-    client.record_gas(&admin, &org, &OperationType::Storage);
+    client.record_gas(&admin, &org, &OperationType::Storage, &None);
     let usage = client.get_usage(&org);
     // Default storage cost = 3
     assert_eq!(usage.storage_used, 3);
@@ -302,7 +328,7 @@ fn test_record_gas_on_inactive_tenant_fails() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
     client.deactivate_tenant(&admin, &org);
-    let result = client.try_record_gas(&admin, &org, &OperationType::Read);
+    let result = client.try_record_gas(&admin, &org, &OperationType::Read, &None);
     assert_eq!(result, Err(Ok(MeteringError::TenantInactive)));
 }
 
@@ -314,10 +340,10 @@ fn test_record_gas_within_quota_succeeds() {
     let org = register_org(&client, &admin, &env);
     // Set a generous quota
     let quota = TenantQuota {
-        read_limit: 100,
-        write_limit: 100,
-        compute_limit: 100,
-        storage_limit: 100,
+        read_limit: Some(100),
+        write_limit: Some(100),
+        compute_limit: Some(100),
+        storage_limit: Some(100),
         total_limit: 400,
         burst_allowance: 0,
         enabled: true,
@@ -325,7 +351,7 @@ fn test_record_gas_within_quota_succeeds() {
     client.set_quota(&admin, &org, &quota);
     // Record 5 reads — should succeed
     for _ in 0..5 {
-        client.record_gas(&admin, &org, &OperationType::Read);
+        client.record_gas(&admin, &org, &OperationType::Read, &None);
     }
     assert_eq!(client.get_usage(&org).read_used, 5);
 }
@@ -336,10 +362,10 @@ fn test_quota_enforcement_blocks_excess() {
     let org = register_org(&client, &admin, &env);
     // Very tight quota: 2 units total, 0 burst
     let quota = TenantQuota {
-        read_limit: 2,
-        write_limit: 2,
-        compute_limit: 2,
-        storage_limit: 2,
+        read_limit: None,
+        write_limit: None,
+        compute_limit: None,
+        storage_limit: None,
         total_limit: 2,
         burst_allowance: 0,
         enabled: true,
@@ -347,11 +373,11 @@ fn test_quota_enforcement_blocks_excess() {
     client.set_quota(&admin, &org, &quota);
 
     // First read (cost 1) — should succeed.
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     // Second read (cost 1) — fills quota.
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     // Third read — should be blocked.
-    let result = client.try_record_gas(&admin, &org, &OperationType::Read);
+    let result = client.try_record_gas(&admin, &org, &OperationType::Read, &None);
     assert_eq!(result, Err(Ok(MeteringError::QuotaExceeded)));
 }
 
@@ -361,10 +387,10 @@ fn test_burst_allowance_extends_quota() {
     let org = register_org(&client, &admin, &env);
     // Quota: 1 read unit total, burst 1 unit
     let quota = TenantQuota {
-        read_limit: 1,
-        write_limit: 100,
-        compute_limit: 100,
-        storage_limit: 100,
+        read_limit: None,
+        write_limit: None,
+        compute_limit: None,
+        storage_limit: None,
         total_limit: 1,
         burst_allowance: 1,
         enabled: true,
@@ -372,23 +398,52 @@ fn test_burst_allowance_extends_quota() {
     client.set_quota(&admin, &org, &quota);
 
     // First read fills hard limit.
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     // Second read draws from burst allowance.
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     // Third read — both hard limit and burst exhausted.
-    let result = client.try_record_gas(&admin, &org, &OperationType::Read);
+    let result = client.try_record_gas(&admin, &org, &OperationType::Read, &None);
     assert_eq!(result, Err(Ok(MeteringError::QuotaExceeded)));
 }
 
+#[test]
+fn test_operation_sub_limit_blocks_under_total() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    // Compute capped at one operation; other types bounded by the total only.
+    let quota = TenantQuota {
+        read_limit: None,
+        write_limit: None,
+        compute_limit: Some(10),
+        storage_limit: None,
+        total_limit: 100,
+        burst_allowance: 50,
+        enabled: true,
+    };
+    client.set_quota(&admin, &org, &quota);
+
+    client.record_gas(&admin, &org, &OperationType::Compute, &None);
+    // Total and burst have headroom, but the compute sub-limit does not.
+    let result = client.try_record_gas(&admin, &org, &OperationType::Compute, &None);
+    assert_eq!(result, Err(Ok(MeteringError::OperationQuotaExceeded)));
+
+    // Writes (5 each) run up to the total without a sub-limit of their own.
+    for _ in 0..18 {
+        client.record_gas(&admin, &org, &OperationType::Write, &None);
+    }
+    assert_eq!(client.get_usage(&org).total(), 100);
+    assert_eq!(client.get_usage(&org).burst_used, 0);
+}
+
 #[test]
 fn test_disabled_quota_allows_unlimited() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
     let quota = TenantQuota {
-        read_limit: 0,
-        write_limit: 0,
-        compute_limit: 0,
-        storage_limit: 0,
+        read_limit: Some(0),
+        write_limit: Some(0),
+        compute_limit: Some(0),
+        storage_limit: Some(0),
         total_limit: 0,
         burst_allowance: 0,
         enabled: false, // disabled
@@ -396,7 +451,7 @@ fn test_disabled_quota_allows_unlimited() {
     client.set_quota(&admin, &org, &quota);
     // Should not be blocked despite zero limits
     for _ in 0..10 {
-        client.record_gas(&admin, &org, &OperationType::Read);
+        client.record_gas(&admin, &org, &OperationType::Read, &None);
     }
     assert_eq!(client.get_usage(&org).read_used, 10);
 }
@@ -407,20 +462,20 @@ fn test_exact_quota_boundary() {
     let org = register_org(&client, &admin, &env);
     // read cost = 1, total_limit = 3 → exactly 3 reads allowed
     let quota = TenantQuota {
-        read_limit: 3,
-        write_limit: 3,
-        compute_limit: 3,
-        storage_limit: 3,
+        read_limit: None,
+        write_limit: None,
+        compute_limit: None,
+        storage_limit: None,
         total_limit: 3,
         burst_allowance: 0,
         enabled: true,
     };
     client.set_quota(&admin, &org, &quota);
-    client.record_gas(&admin, &org, &OperationType::Read);
-    client.record_gas(&admin, &org, &OperationType::Read);
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     // Exactly at limit — next should fail
-    let result = client.try_record_gas(&admin, &org, &OperationType::Read);
+    let result = client.try_record_gas(&admin, &org, &OperationType::Read, &None);
     assert_eq!(result, Err(Ok(MeteringError::QuotaExceeded)));
 }
 
@@ -433,7 +488,7 @@ fn test_provider_usage_rolls_up_to_clinic() {
     let clinic = register_clinic(&client, &admin, &env, &org);
     let provider = register_provider(&client, &admin, &env, &clinic);
 
-    client.record_gas(&admin, &provider, &OperationType::Read);
+    client.record_gas(&admin, &provider, &OperationType::Read, &None);
 
     // Provider has 1 unit read.
     assert_eq!(client.get_usage(&provider).read_used, 1);
@@ -450,7 +505,7 @@ fn test_rollup_does_not_double_count_clinic_direct_usage() {
     let clinic = register_clinic(&client, &admin, &env, &org);
 
     // Direct clinic usage.
-    client.record_gas(&admin, &clinic, &OperationType::Write);
+    client.record_gas(&admin, &clinic, &OperationType::Write, &None);
 
     assert_eq!(client.get_usage(&clinic).write_used, 5);
     // Org picks up the rollup.
@@ -465,8 +520,8 @@ fn test_multiple_providers_aggregate_at_org() {
     let p1 = register_provider(&client, &admin, &env, &clinic);
     let p2 = register_provider(&client, &admin, &env, &clinic);
 
-    client.record_gas(&admin, &p1, &OperationType::Read);
-    client.record_gas(&admin, &p2, &OperationType::Read);
+    client.record_gas(&admin, &p1, &OperationType::Read, &None);
+    client.record_gas(&admin, &p2, &OperationType::Read, &None);
 
     // Clinic should have both reads rolled up (2 units).
     assert_eq!(client.get_usage(&clinic).read_used, 2);
@@ -524,7 +579,7 @@ fn test_close_cycle_resets_usage_on_next_open() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     assert_eq!(client.get_usage(&org).read_used, 1);
 
     client.close_billing_cycle(&admin);
@@ -540,7 +595,7 @@ fn test_billing_report_contains_all_tenants() {
     let org = register_org(&client, &admin, &env);
     let clinic = register_clinic(&client, &admin, &env, &org);
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     let report = client.close_billing_cycle(&admin);
     // Two tenants: org and clinic.
     assert_eq!(report.records.len(), 2);
@@ -552,8 +607,8 @@ fn test_billing_report_cost_calculation() {
     let org = register_org(&client, &admin, &env);
     client.open_billing_cycle(&admin);
     // 2 reads at cost 1 each = 2 total.
-    client.record_gas(&admin, &org, &OperationType::Read);
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     let report = client.close_billing_cycle(&admin);
     let record = report.records.get(0).unwrap();
     assert_eq!(record.read_units, 2);
@@ -568,10 +623,11 @@ fn test_postpaid_invoice_created_on_cycle_close() {
     let org = register_org(&client, &admin, &env);
     client.set_billing_model(&admin, &org, &BillingModel::Postpaid);
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Write); // cost = 5
+    client.record_gas(&admin, &org, &OperationType::Write, &None); // cost = 5
     let report = client.close_billing_cycle(&admin);
     let inv = client.get_invoice(&org, &report.cycle_id).unwrap();
     assert_eq!(inv.amount_due, 5);
+    assert_eq!(inv.outstanding, 5);
     assert!(!inv.settled);
 }
 
@@ -581,7 +637,7 @@ fn test_settle_invoice_marks_as_settled() {
     let org = register_org(&client, &admin, &env);
     client.set_billing_model(&admin, &org, &BillingModel::Postpaid);
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Write);
+    client.record_gas(&admin, &org, &OperationType::Write, &None);
     let report = client.close_billing_cycle(&admin);
     client.settle_invoice(&org, &report.cycle_id);
     let inv = client.get_invoice(&org, &report.cycle_id).unwrap();
@@ -594,13 +650,59 @@ fn test_settle_same_invoice_twice_fails() {
     let org = register_org(&client, &admin, &env);
     client.set_billing_model(&admin, &org, &BillingModel::Postpaid);
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Write);
+    client.record_gas(&admin, &org, &OperationType::Write, &None);
     let report = client.close_billing_cycle(&admin);
     client.settle_invoice(&org, &report.cycle_id);
     let result = client.try_settle_invoice(&org, &report.cycle_id);
     assert_eq!(result, Err(Ok(MeteringError::AlreadySettled)));
 }
 
+#[test]
+fn test_partial_payments_settle_invoice() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_billing_model(&admin, &org, &BillingModel::Postpaid);
+    client.open_billing_cycle(&admin);
+    client.record_gas(&admin, &org, &OperationType::Write, &None); // cost = 5
+    let report = client.close_billing_cycle(&admin);
+
+    client.settle_invoice_partial(&org, &report.cycle_id, &2u64);
+    let inv = client.get_invoice(&org, &report.cycle_id).unwrap();
+    assert_eq!((inv.amount_due, inv.outstanding), (5, 3));
+    assert!(!inv.settled);
+
+    client.settle_invoice_partial(&org, &report.cycle_id, &3u64);
+    let inv = client.get_invoice(&org, &report.cycle_id).unwrap();
+    assert_eq!(inv.outstanding, 0);
+    assert!(inv.settled);
+    assert_eq!(
+        client.try_settle_invoice_partial(&org, &report.cycle_id, &1u64),
+        Err(Ok(MeteringError::AlreadySettled))
+    );
+}
+
+#[test]
+fn test_partial_overpayment_rejected() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_billing_model(&admin, &org, &BillingModel::Postpaid);
+    client.open_billing_cycle(&admin);
+    client.record_gas(&admin, &org, &OperationType::Write, &None); // cost = 5
+    let report = client.close_billing_cycle(&admin);
+
+    client.settle_invoice_partial(&org, &report.cycle_id, &4u64);
+    for amount in [0u64, 2] {
+        assert_eq!(
+            client.try_settle_invoice_partial(&org, &report.cycle_id, &amount),
+            Err(Ok(MeteringError::InvalidInput))
+        );
+    }
+    // A rejected payment leaves the balance untouched.
+    let inv = client.get_invoice(&org, &report.cycle_id).unwrap();
+    assert_eq!(inv.outstanding, 1);
+    assert!(!inv.settled);
+}
+
 #[test]
 fn test_get_nonexistent_invoice_returns_none() {
     let (env, client, admin) = setup();
@@ -624,7 +726,7 @@ fn test_prepaid_record_gas_burns_tokens() {
     let org = register_org(&client, &admin, &env);
     client.set_billing_model(&admin, &org, &BillingModel::Prepaid);
     client.mint_gas_tokens(&admin, &org, &100u64);
-    client.record_gas(&admin, &org, &OperationType::Write); // cost = 5
+    client.record_gas(&admin, &org, &OperationType::Write, &None); // cost = 5
     assert_eq!(client.gas_token_balance(&org), 95);
 }
 
@@ -635,7 +737,7 @@ fn test_prepaid_insufficient_balance_blocks_operation() {
     client.set_billing_model(&admin, &org, &BillingModel::Prepaid);
     client.mint_gas_tokens(&admin, &org, &3u64); // only 3 units
                                                  // Write costs 5 — should be blocked.
-    let result = client.try_record_gas(&admin, &org, &OperationType::Write);
+    let result = client.try_record_gas(&admin, &org, &OperationType::Write, &None);
     assert_eq!(result, Err(Ok(MeteringError::InsufficientPrepaidBalance)));
 }
 
@@ -646,7 +748,7 @@ fn test_frozen_account_blocks_prepaid_operation() {
     client.set_billing_model(&admin, &org, &BillingModel::Prepaid);
     client.mint_gas_tokens(&admin, &org, &100u64);
     client.freeze_gas_token_account(&admin, &org);
-    let result = client.try_record_gas(&admin, &org, &OperationType::Read);
+    let result = client.try_record_gas(&admin, &org, &OperationType::Read, &None);
     assert_eq!(result, Err(Ok(MeteringError::GasTokenAccountFrozen)));
 }
 
@@ -659,7 +761,7 @@ fn test_unfreeze_account_restores_operations() {
     client.freeze_gas_token_account(&admin, &org);
     client.unfreeze_gas_token_account(&admin, &org);
     // Should succeed again.
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     assert_eq!(client.gas_token_balance(&org), 99);
 }
 
@@ -673,6 +775,72 @@ fn test_total_gas_token_supply_tracks_minting() {
     assert_eq!(client.total_gas_token_supply(), 80);
 }
 
+#[test]
+fn test_exhausted_quota_burns_no_tokens() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_billing_model(&admin, &org, &BillingModel::Prepaid);
+    client.mint_gas_tokens(&admin, &org, &100u64);
+    let quota = TenantQuota {
+        read_limit: None,
+        write_limit: None,
+        compute_limit: None,
+        storage_limit: None,
+        total_limit: 5,
+        burst_allowance: 0,
+        enabled: true,
+    };
+    client.set_quota(&admin, &org, &quota);
+
+    client.record_gas(&admin, &org, &OperationType::Write, &None); // cost = 5
+    assert_eq!(client.gas_token_balance(&org), 95);
+    let result = client.try_record_gas(&admin, &org, &OperationType::Read, &None);
+    assert_eq!(result, Err(Ok(MeteringError::QuotaExceeded)));
+    assert_eq!(client.gas_token_balance(&org), 95);
+}
+
+#[test]
+fn test_refund_gas_tokens_restores_balance() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_billing_model(&admin, &org, &BillingModel::Prepaid);
+    client.mint_gas_tokens(&admin, &org, &100u64);
+    client.record_gas(&admin, &org, &OperationType::Write, &None); // cost = 5
+
+    client.refund_gas_tokens(&admin, &org, &5u64);
+    assert_eq!(client.gas_token_balance(&org), 100);
+    assert_eq!(client.total_gas_token_supply(), 100);
+
+    // Only burned tokens can come back, and only once.
+    assert_eq!(
+        client.try_refund_gas_tokens(&admin, &org, &1u64),
+        Err(Ok(MeteringError::RefundExceedsBurned))
+    );
+    client.record_gas(&admin, &org, &OperationType::Write, &None);
+    client.record_gas(&admin, &org, &OperationType::Write, &None);
+    assert_eq!(
+        client.try_refund_gas_tokens(&admin, &org, &11u64),
+        Err(Ok(MeteringError::RefundExceedsBurned))
+    );
+    client.refund_gas_tokens(&admin, &org, &4u64);
+    client.refund_gas_tokens(&admin, &org, &6u64);
+    assert_eq!(client.gas_token_balance(&org), 100);
+    assert_eq!(
+        client.try_refund_gas_tokens(&admin, &org, &1u64),
+        Err(Ok(MeteringError::RefundExceedsBurned))
+    );
+
+    assert_eq!(
+        client.try_refund_gas_tokens(&admin, &org, &0u64),
+        Err(Ok(MeteringError::InvalidInput))
+    );
+    let not_admin = Address::generate(&env);
+    assert_eq!(
+        client.try_refund_gas_tokens(&not_admin, &org, &5u64),
+        Err(Ok(MeteringError::Unauthorized))
+    );
+}
+
 #[test]
 fn test_mint_zero_fails() {
     let (env, client, admin) = setup();
@@ -725,8 +893,8 @@ fn test_cost_allocation_proportional_across_tenants() {
 
     client.open_billing_cycle(&admin);
     // Clinic: 2 reads (cost 2).
-    client.record_gas(&admin, &clinic, &OperationType::Read);
-    client.record_gas(&admin, &clinic, &OperationType::Read);
+    client.record_gas(&admin, &clinic, &OperationType::Read, &None);
+    client.record_gas(&admin, &clinic, &OperationType::Read, &None);
     let report = client.close_billing_cycle(&admin);
 
     // Org record: picked up 2 units via rollup.
@@ -753,7 +921,7 @@ fn test_usage_reset_between_cycles() {
     let org = register_org(&client, &admin, &env);
 
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Compute); // cost 10
+    client.record_gas(&admin, &org, &OperationType::Compute, &None); // cost 10
     assert_eq!(client.get_usage(&org).compute_used, 10);
 
     client.close_billing_cycle(&admin);
@@ -767,10 +935,10 @@ fn test_multiple_operation_types_tracked_separately() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
 
-    client.record_gas(&admin, &org, &OperationType::Read);
-    client.record_gas(&admin, &org, &OperationType::Write);
-    client.record_gas(&admin, &org, &OperationType::Compute);
-    client.record_gas(&admin, &org, &OperationType::Storage);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
+    client.record_gas(&admin, &org, &OperationType::Write, &None);
+    client.record_gas(&admin, &org, &OperationType::Compute, &None);
+    client.record_gas(&admin, &org, &OperationType::Storage, &None);
 
     let u = client.get_usage(&org);
     assert_eq!(u.read_used, 1);
@@ -798,12 +966,12 @@ fn test_long_running_and_short_burst_usage_are_billed_precisely() {
 
     // Long-running background read load: 100 x 1 unit.
     for _ in 0..100 {
-        client.record_gas(&admin, &org, &OperationType::Read);
+        client.record_gas(&admin, &org, &OperationType::Read, &None);
     }
 
     // Short burst compute spike: 2 x 10 units.
     for _ in 0..2 {
-        client.record_gas(&admin, &org, &OperationType::Compute);
+        client.record_gas(&admin, &org, &OperationType::Compute, &None);
     }
 
     let report = client.close_billing_cycle(&admin);
@@ -822,7 +990,7 @@ fn test_dynamic_pricing_only_affects_future_metered_usage() {
     client.open_billing_cycle(&admin);
 
     // First read at default price (1).
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
 
     // Raise read cost from 1 to 7. Only future usage should use the new cost.
     let updated_costs = GasCosts {
@@ -833,7 +1001,7 @@ fn test_dynamic_pricing_only_affects_future_metered_usage() {
     };
     client.set_gas_costs(&admin, &updated_costs);
 
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
 
     let report = client.close_billing_cycle(&admin);
     let record = report.records.get(0).unwrap();
@@ -857,9 +1025,9 @@ fn test_rounding_stays_in_base_units_without_fractional_drift() {
     client.set_gas_costs(&admin, &custom_costs);
 
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Read); // 3
-    client.record_gas(&admin, &org, &OperationType::Storage); // 4
-    client.record_gas(&admin, &org, &OperationType::Read); // 3
+    client.record_gas(&admin, &org, &OperationType::Read, &None); // 3
+    client.record_gas(&admin, &org, &OperationType::Storage, &None); // 4
+    client.record_gas(&admin, &org, &OperationType::Read, &None); // 3
 
     let report = client.close_billing_cycle(&admin);
     let record = report.records.get(0).unwrap();
@@ -911,7 +1079,7 @@ fn test_tenant_registered_event_emitted() {
 fn test_gas_recorded_event_emitted_for_direct_tenant() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
 
     let events = collect_events(&env);
     assert_eq!(events.len(), 1);
@@ -924,7 +1092,7 @@ fn test_gas_recorded_events_emitted_for_hierarchy_rollup() {
     let clinic = register_clinic(&client, &admin, &env, &org);
     let provider = register_provider(&client, &admin, &env, &clinic);
 
-    client.record_gas(&admin, &provider, &OperationType::Write);
+    client.record_gas(&admin, &provider, &OperationType::Write, &None);
 
     let events = collect_events(&env);
     assert_eq!(events.len(), 3); // provider, clinic, org
@@ -936,10 +1104,10 @@ fn test_quota_alert_event_emitted_when_threshold_crossed() {
     let org = register_org(&client, &admin, &env);
     // Set quota where alert triggers at 80% of 10 = 8 units
     let quota = TenantQuota {
-        read_limit: 10,
-        write_limit: 10,
-        compute_limit: 10,
-        storage_limit: 10,
+        read_limit: Some(10),
+        write_limit: Some(10),
+        compute_limit: Some(10),
+        storage_limit: Some(10),
         total_limit: 10,
         burst_allowance: 0,
         enabled: true,
@@ -948,7 +1116,7 @@ fn test_quota_alert_event_emitted_when_threshold_crossed() {
 
     // Record 8 reads (8 units, exactly 80%)
     for _ in 0..8 {
-        client.record_gas(&admin, &org, &OperationType::Read);
+        client.record_gas(&admin, &org, &OperationType::Read, &None);
     }
 
     let events = collect_events(&env);
@@ -956,15 +1124,64 @@ fn test_quota_alert_event_emitted_when_threshold_crossed() {
     assert_eq!(events.len(), 9);
 }
 
+/// Whether the last invocation emitted a `QuotaAlert` event.
+fn alert_emitted(env: &Env) -> bool {
+    use soroban_sdk::xdr::{ContractEventBody, ScSymbol, ScVal};
+    let topic = ScVal::Symbol(ScSymbol("QuotaAlert".try_into().unwrap()));
+    collect_events(env).iter().any(|e| match &e.body {
+        ContractEventBody::V0(body) => body.topics.contains(&topic),
+    })
+}
+
+#[test]
+fn test_quota_alerts_fire_once_per_bucket() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    let quota = TenantQuota {
+        read_limit: None,
+        write_limit: None,
+        compute_limit: None,
+        storage_limit: None,
+        total_limit: 100,
+        burst_allowance: 10,
+        enabled: true,
+    };
+    client.set_quota(&admin, &org, &quota);
+    client.open_billing_cycle(&admin);
+
+    // 110 one-unit reads cross 80, 85, 90, 95 and 100 %; burst usage past
+    // the limit stays in the top bucket.
+    let mut alerts = 0;
+    for _ in 0..110 {
+        client.record_gas(&admin, &org, &OperationType::Read, &None);
+        if alert_emitted(&env) {
+            alerts += 1;
+        }
+    }
+    assert_eq!(alerts, 5);
+
+    // A new cycle re-arms the alerts.
+    client.close_billing_cycle(&admin);
+    client.open_billing_cycle(&admin);
+    let mut alerts = 0;
+    for _ in 0..84 {
+        client.record_gas(&admin, &org, &OperationType::Read, &None);
+        if alert_emitted(&env) {
+            alerts += 1;
+        }
+    }
+    assert_eq!(alerts, 1);
+}
+
 #[test]
 fn test_quota_exceeded_event_emitted_when_quota_breached() {
     let (env, client, admin) = setup();
     let org = register_org(&client, &admin, &env);
     let quota = TenantQuota {
-        read_limit: 1,
-        write_limit: 1,
-        compute_limit: 1,
-        storage_limit: 1,
+        read_limit: Some(1),
+        write_limit: Some(1),
+        compute_limit: Some(1),
+        storage_limit: Some(1),
         total_limit: 1,
         burst_allowance: 0,
         enabled: true,
@@ -972,9 +1189,9 @@ fn test_quota_exceeded_event_emitted_when_quota_breached() {
     client.set_quota(&admin, &org, &quota);
 
     // First read succeeds
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     // Second read fails and emits event
-    let _ = client.try_record_gas(&admin, &org, &OperationType::Read);
+    let _ = client.try_record_gas(&admin, &org, &OperationType::Read, &None);
 
     let events = collect_events(&env);
     // 1 gas recorded + 1 quota exceeded
@@ -1007,7 +1224,7 @@ fn test_invoice_issued_event_emitted_for_postpaid_tenant() {
     client.set_billing_model(&admin, &org, &BillingModel::Postpaid);
 
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Write); // 5 units
+    client.record_gas(&admin, &org, &OperationType::Write, &None); // 5 units
     client.close_billing_cycle(&admin);
 
     let events = collect_events(&env);
@@ -1022,7 +1239,7 @@ fn test_invoice_settled_event_emitted() {
     client.set_billing_model(&admin, &org, &BillingModel::Postpaid);
 
     client.open_billing_cycle(&admin);
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
     client.close_billing_cycle(&admin);
     client.settle_invoice(&org, &1u64);
 
@@ -1047,7 +1264,7 @@ fn test_gas_token_burned_event_emitted_for_prepaid_usage() {
     let org = register_org(&client, &admin, &env);
     client.set_billing_model(&admin, &org, &BillingModel::Prepaid);
     client.mint_gas_tokens(&admin, &org, &10u64);
-    client.record_gas(&admin, &org, &OperationType::Write); // burns 5
+    client.record_gas(&admin, &org, &OperationType::Write, &None); // burns 5
 
     let events = collect_events(&env);
     // mint + gas recorded + burn
@@ -1071,7 +1288,7 @@ fn test_events_include_correct_timestamps() {
     let initial_time = env.ledger().timestamp();
 
     let org = register_org(&client, &admin, &env);
-    client.record_gas(&admin, &org, &OperationType::Read);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
 
     let events = collect_events(&env);
     assert_eq!(events.len(), 2);
@@ -1093,7 +1310,7 @@ fn test_upgrade_preserves_state() {
     let quota = default_quota(&env);
     client.set_quota(&admin, &org, &quota);
 
-    client.record_gas(&org, &org, &OperationType::Read);
+    client.record_gas(&org, &org, &OperationType::Read, &None);
     let usage_before = client.get_usage(&org);
     assert_eq!(usage_before.read_used, 1);
 
@@ -1103,3 +1320,537 @@ fn test_upgrade_preserves_state() {
     // we will focus on the fact that the upgrade method is correctly implemented
     // in lib.rs and follows the standard pattern.
 }
+
+// ── Cost estimation ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_estimate_matches_postpaid_charge() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+
+    let estimate = client.estimate_operation_cost(&org, &OperationType::Write);
+    assert_eq!(estimate.units, 5);
+    assert_eq!(estimate.token_cost, 5);
+    assert_eq!(estimate.billing_model, BillingModel::Postpaid);
+    assert!(estimate.within_quota);
+    assert!(estimate.can_pay);
+
+    // Estimating does not consume anything.
+    assert_eq!(client.get_usage(&org).total(), 0);
+
+    client.record_gas(&admin, &org, &OperationType::Write, &None);
+    assert_eq!(client.get_usage(&org).write_used, estimate.units);
+}
+
+#[test]
+fn test_estimate_matches_prepaid_burn() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_billing_model(&admin, &org, &BillingModel::Prepaid);
+    client.mint_gas_tokens(&admin, &org, &12);
+
+    let estimate = client.estimate_operation_cost(&org, &OperationType::Compute);
+    assert_eq!(estimate.billing_model, BillingModel::Prepaid);
+    assert!(estimate.can_pay);
+    assert_eq!(client.gas_token_balance(&org), 12);
+
+    client.record_gas(&admin, &org, &OperationType::Compute, &None);
+    assert_eq!(client.gas_token_balance(&org), 12 - estimate.token_cost);
+
+    // Remaining balance (2) cannot cover another compute op (10).
+    let estimate = client.estimate_operation_cost(&org, &OperationType::Compute);
+    assert!(!estimate.can_pay);
+    let res = client.try_record_gas(&admin, &org, &OperationType::Compute, &None);
+    assert_eq!(res, Err(Ok(MeteringError::InsufficientPrepaidBalance)));
+}
+
+#[test]
+fn test_estimate_uses_custom_gas_costs() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_gas_costs(
+        &admin,
+        &GasCosts {
+            read_cost: 7,
+            write_cost: 5,
+            compute_cost: 10,
+            storage_cost: 3,
+        },
+    );
+
+    let estimate = client.estimate_operation_cost(&org, &OperationType::Read);
+    assert_eq!(estimate.units, 7);
+
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
+    assert_eq!(client.get_usage(&org).read_used, estimate.units);
+}
+
+#[test]
+fn test_estimate_reports_quota_exhaustion() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_quota(&admin, &org, &default_quota(&env));
+
+    // Compute costs 10: the first fills the compute sub-limit, which burst
+    // does not extend.
+    client.record_gas(&admin, &org, &OperationType::Compute, &None);
+    let estimate = client.estimate_operation_cost(&org, &OperationType::Compute);
+    assert!(!estimate.within_quota);
+    let res = client.try_record_gas(&admin, &org, &OperationType::Compute, &None);
+    assert_eq!(res, Err(Ok(MeteringError::OperationQuotaExceeded)));
+}
+
+#[test]
+fn test_estimate_unknown_tenant_fails() {
+    let (env, client, _admin) = setup();
+    let stranger = Address::generate(&env);
+    let res = client.try_estimate_operation_cost(&stranger, &OperationType::Read);
+    assert_eq!(res, Err(Ok(MeteringError::TenantNotFound)));
+}
+
+// ── Quota rollover ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_underused_tenant_accrues_rollover_up_to_cap() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_quota(&admin, &org, &default_quota(&env));
+    client.set_rollover_config(&admin, &org, &true, &50);
+
+    // Cycle 1: use 5 of 40 → 35 unused.
+    client.open_billing_cycle(&admin);
+    client.record_gas(&admin, &org, &OperationType::Write, &None);
+    client.close_billing_cycle(&admin);
+    assert_eq!(client.get_rollover_balance(&org), 35);
+
+    // Cycle 2: nothing used → 40 more, capped at 50.
+    client.open_billing_cycle(&admin);
+    client.close_billing_cycle(&admin);
+    assert_eq!(client.get_rollover_balance(&org), 50);
+}
+
+#[test]
+fn test_rollover_consumed_before_base_quota() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_quota(&admin, &org, &default_quota(&env));
+    client.set_rollover_config(&admin, &org, &true, &12);
+
+    client.open_billing_cycle(&admin);
+    client.close_billing_cycle(&admin);
+    assert_eq!(client.get_rollover_balance(&org), 12);
+
+    client.open_billing_cycle(&admin);
+    // Compute (10) is served entirely from rollover.
+    client.record_gas(&admin, &org, &OperationType::Compute, &None);
+    assert_eq!(client.get_rollover_balance(&org), 2);
+    let usage = client.get_usage(&org);
+    assert_eq!(usage.compute_used, 0);
+    assert_eq!(usage.rollover_used, 10);
+
+    // The next one splits: 2 from rollover, 8 from the base bucket.
+    client.record_gas(&admin, &org, &OperationType::Compute, &None);
+    assert_eq!(client.get_rollover_balance(&org), 0);
+    let usage = client.get_usage(&org);
+    assert_eq!(usage.compute_used, 8);
+    assert_eq!(usage.rollover_used, 12);
+
+    // Rollover draws are still billed.
+    let report = client.close_billing_cycle(&admin);
+    let record = report.records.get(0).unwrap();
+    assert_eq!(record.rollover_units, 12);
+    assert_eq!(record.total_cost, 20);
+}
+
+#[test]
+fn test_rollover_disabled_by_default_and_forfeited_on_disable() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_quota(&admin, &org, &default_quota(&env));
+
+    client.open_billing_cycle(&admin);
+    client.close_billing_cycle(&admin);
+    assert_eq!(client.get_rollover_balance(&org), 0);
+
+    client.set_rollover_config(&admin, &org, &true, &100);
+    client.open_billing_cycle(&admin);
+    client.close_billing_cycle(&admin);
+    assert_eq!(client.get_rollover_balance(&org), 40);
+
+    client.set_rollover_config(&admin, &org, &false, &100);
+    assert_eq!(client.get_rollover_balance(&org), 0);
+    assert!(!client.get_rollover_config(&org).rollover_enabled);
+}
+
+// ── Billing-cycle grace window ────────────────────────────────────────────────
+
+/// Open cycle 1 at t=1000, record one read, then close it and open cycle 2
+/// at t=2000 with a 60-second grace window.
+fn setup_cycle_boundary() -> (Env, MeteringContractClient<'static>, Address, Address) {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_cycle_grace(&admin, &60);
+
+    env.ledger().set_timestamp(1_000);
+    client.open_billing_cycle(&admin);
+    client.record_gas(&admin, &org, &OperationType::Read, &None);
+
+    env.ledger().set_timestamp(2_000);
+    let report = client.close_billing_cycle(&admin);
+    assert_eq!(report.records.get(0).unwrap().total_cost, 1);
+    client.open_billing_cycle(&admin);
+    (env, client, admin, org)
+}
+
+#[test]
+fn test_grace_window_attributes_operations_by_timestamp() {
+    let (env, client, admin, org) = setup_cycle_boundary();
+    env.ledger().set_timestamp(2_010);
+
+    let info = client.get_cycle_boundary_info();
+    assert_eq!(info.closed_cycle_id, 1);
+    assert_eq!(info.closed_at, 2_000);
+    assert_eq!(info.grace_ends_at, 2_060);
+    assert!(info.in_grace);
+
+    // Executed just before the close → old cycle; just after → new cycle.
+    client.record_gas_at(&admin, &org, &OperationType::Write, &1_999);
+    client.record_gas_at(&admin, &org, &OperationType::Compute, &2_001);
+
+    let old = client.get_billing_report(&1u64).unwrap();
+    let record = old.records.get(0).unwrap();
+    assert_eq!(record.read_units, 1);
+    assert_eq!(record.write_units, 5);
+    assert_eq!(record.compute_units, 0);
+    assert_eq!(record.total_cost, 6);
+    assert_eq!(client.get_invoice(&org, &1u64).unwrap().amount_due, 6);
+
+    let usage = client.get_usage(&org);
+    assert_eq!(usage.write_used, 0);
+    assert_eq!(usage.compute_used, 10);
+
+    env.ledger().set_timestamp(3_000);
+    let new = client.close_billing_cycle(&admin);
+    let record = new.records.get(0).unwrap();
+    assert_eq!(record.write_units, 0);
+    assert_eq!(record.compute_units, 10);
+    assert_eq!(record.total_cost, 10);
+}
+
+#[test]
+fn test_grace_window_late_charge_rolls_up_to_ancestors() {
+    let (env, client, admin, org) = setup_cycle_boundary();
+    let clinic = register_clinic(&client, &admin, &env, &org);
+    env.ledger().set_timestamp(2_030);
+
+    client.record_gas_at(&admin, &clinic, &OperationType::Storage, &1_990);
+
+    let old = client.get_billing_report(&1u64).unwrap();
+    let org_record = old.records.iter().find(|r| r.tenant == org).unwrap();
+    let clinic_record = old.records.iter().find(|r| r.tenant == clinic).unwrap();
+    assert_eq!(org_record.storage_units, 3);
+    assert_eq!(clinic_record.storage_units, 3);
+    assert_eq!(client.get_invoice(&clinic, &1u64).unwrap().amount_due, 3);
+    assert_eq!(client.get_usage(&org).total(), 0);
+}
+
+#[test]
+fn test_pre_close_operation_rejected_after_grace() {
+    let (env, client, admin, org) = setup_cycle_boundary();
+
+    env.ledger().set_timestamp(2_060);
+    assert!(!client.get_cycle_boundary_info().in_grace);
+    let res = client.try_record_gas_at(&admin, &org, &OperationType::Read, &1_999);
+    assert_eq!(res, Err(Ok(MeteringError::CycleGraceExpired)));
+
+    // Operations cannot be stamped in the future either.
+    let res = client.try_record_gas_at(&admin, &org, &OperationType::Read, &2_061);
+    assert_eq!(res, Err(Ok(MeteringError::InvalidInput)));
+
+    let old = client.get_billing_report(&1u64).unwrap();
+    assert_eq!(old.records.get(0).unwrap().total_cost, 1);
+}
+
+#[test]
+fn test_cycle_grace_defaults_to_zero() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    assert_eq!(client.get_cycle_grace(), 0);
+    assert_eq!(client.get_cycle_boundary_info().closed_cycle_id, 0);
+
+    env.ledger().set_timestamp(500);
+    client.open_billing_cycle(&admin);
+    client.close_billing_cycle(&admin);
+    client.open_billing_cycle(&admin);
+    let res = client.try_record_gas_at(&admin, &org, &OperationType::Read, &499);
+    assert_eq!(res, Err(Ok(MeteringError::CycleGraceExpired)));
+
+    let outsider = Address::generate(&env);
+    let res = client.try_set_cycle_grace(&outsider, &60);
+    assert_eq!(res, Err(Ok(MeteringError::Unauthorized)));
+}
+
+// ── Tenant group tests ────────────────────────────────────────────────────────
+
+#[test]
+fn test_group_members_are_jointly_limited_by_pool() {
+    let (env, client, admin) = setup();
+    let a = register_org(&client, &admin, &env);
+    let b = register_org(&client, &admin, &env);
+    let outsider = register_org(&client, &admin, &env);
+    for tenant in [&a, &b, &outsider] {
+        client.set_quota(&admin, tenant, &default_quota(&env));
+    }
+
+    let pilots = symbol_short!("pilots");
+    client.create_tenant_group(&admin, &pilots, &vec![&env, a.clone(), b.clone()], &12);
+    assert_eq!(client.get_group_of(&a), Some(pilots.clone()));
+    assert_eq!(client.get_group_of(&outsider), None);
+
+    client.record_gas(&admin, &a, &OperationType::Write, &None);
+    client.record_gas(&admin, &b, &OperationType::Write, &None);
+    assert_eq!(client.get_group_usage(&pilots), 10);
+
+    // `a` has 35 units of individual quota left, but the pool only 2.
+    let estimate = client.estimate_operation_cost(&a, &OperationType::Write);
+    assert!(!estimate.within_quota);
+    let res = client.try_record_gas(&admin, &a, &OperationType::Write, &None);
+    assert_eq!(res, Err(Ok(MeteringError::GroupQuotaExceeded)));
+    assert_eq!(client.get_usage(&a).total(), 5);
+
+    client.record_gas(&admin, &b, &OperationType::Read, &None);
+    client.record_gas(&admin, &a, &OperationType::Read, &None);
+    assert_eq!(client.get_group_usage(&pilots), 12);
+    let res = client.try_record_gas(&admin, &b, &OperationType::Read, &None);
+    assert_eq!(res, Err(Ok(MeteringError::GroupQuotaExceeded)));
+
+    // A non-member is limited only by its own quota.
+    client.record_gas(&admin, &outsider, &OperationType::Write, &None);
+    client.record_gas(&admin, &outsider, &OperationType::Write, &None);
+    client.record_gas(&admin, &outsider, &OperationType::Read, &None);
+    assert_eq!(client.get_usage(&outsider).total(), 11);
+}
+
+#[test]
+fn test_group_membership_and_pool_reset() {
+    let (env, client, admin) = setup();
+    let a = register_org(&client, &admin, &env);
+    let b = register_org(&client, &admin, &env);
+    let pilots = symbol_short!("pilots");
+    client.create_tenant_group(&admin, &pilots, &vec![&env, a.clone()], &5);
+
+    client.open_billing_cycle(&admin);
+    client.record_gas(&admin, &a, &OperationType::Write, &None);
+    let res = client.try_record_gas(&admin, &a, &OperationType::Read, &None);
+    assert_eq!(res, Err(Ok(MeteringError::GroupQuotaExceeded)));
+
+    // A new cycle refills the pool.
+    client.close_billing_cycle(&admin);
+    client.open_billing_cycle(&admin);
+    assert_eq!(client.get_group_usage(&pilots), 0);
+    client.record_gas(&admin, &a, &OperationType::Read, &None);
+
+    client.add_group_member(&admin, &pilots, &b);
+    assert_eq!(client.get_tenant_group(&pilots).unwrap().members.len(), 2);
+    let res = client.try_record_gas(&admin, &b, &OperationType::Write, &None);
+    assert_eq!(res, Err(Ok(MeteringError::GroupQuotaExceeded)));
+    client.remove_group_member(&admin, &pilots, &b);
+    client.record_gas(&admin, &b, &OperationType::Write, &None);
+    assert_eq!(client.get_group_usage(&pilots), 1);
+
+    let res = client.try_create_tenant_group(&admin, &pilots, &Vec::new(&env), &1);
+    assert_eq!(res, Err(Ok(MeteringError::GroupAlreadyExists)));
+    let res = client.try_create_tenant_group(&admin, &symbol_short!("other"), &vec![&env, a], &1);
+    assert_eq!(res, Err(Ok(MeteringError::TenantAlreadyInGroup)));
+    let res = client.try_remove_group_member(&admin, &pilots, &b);
+    assert_eq!(res, Err(Ok(MeteringError::TenantNotInGroup)));
+    let res = client.try_add_group_member(&admin, &pilots, &Address::generate(&env));
+    assert_eq!(res, Err(Ok(MeteringError::TenantNotFound)));
+    let res = client.try_add_group_member(&Address::generate(&env), &pilots, &b);
+    assert_eq!(res, Err(Ok(MeteringError::Unauthorized)));
+}
+
+// ── Correlation id attribution ────────────────────────────────────────────────
+
+#[test]
+fn test_correlated_usage_accumulates_per_workflow() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    let clinic = register_clinic(&client, &admin, &env, &org);
+    let intake = BytesN::from_array(&env, &[1u8; 32]);
+    let referral = BytesN::from_array(&env, &[2u8; 32]);
+
+    for (op, tag) in [
+        (OperationType::Write, Some(intake.clone())),
+        (OperationType::Read, Some(intake.clone())),
+        (OperationType::Compute, Some(referral.clone())),
+        (OperationType::Storage, None),
+    ] {
+        client.record_gas(&admin, &clinic, &op, &tag);
+    }
+
+    assert_eq!(client.get_correlated_usage(&clinic, &intake), 6);
+    assert_eq!(client.get_correlated_usage(&clinic, &referral), 10);
+    // Tallies are per tenant and leave hierarchy billing untouched.
+    assert_eq!(client.get_correlated_usage(&org, &intake), 0);
+    assert_eq!(client.get_usage(&clinic).total(), 19);
+    assert_eq!(client.get_usage(&org).total(), 19);
+}
+
+#[test]
+fn test_rejected_operation_is_not_correlated() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_quota(&admin, &org, &default_quota(&env));
+    let intake = BytesN::from_array(&env, &[1u8; 32]);
+
+    // The second compute exceeds the compute sub-limit of 10.
+    client.record_gas(&admin, &org, &OperationType::Compute, &Some(intake.clone()));
+    let res = client.try_record_gas(&admin, &org, &OperationType::Compute, &Some(intake.clone()));
+    assert_eq!(res, Err(Ok(MeteringError::OperationQuotaExceeded)));
+    assert_eq!(client.get_correlated_usage(&org, &intake), 10);
+}
+
+#[test]
+fn test_correlated_usage_expires_when_idle() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    let intake = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.record_gas(&admin, &org, &OperationType::Write, &Some(intake.clone()));
+    env.ledger().with_mut(|l| l.sequence_number += 518_401);
+    assert_eq!(client.get_correlated_usage(&org, &intake), 0);
+}
+
+// ── Batched recording ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_batch_matches_sequential_record_gas() {
+    let (env, client, admin) = setup();
+    let ops = vec![
+        &env,
+        OperationType::Read,
+        OperationType::Write,
+        OperationType::Read,
+        OperationType::Storage,
+        OperationType::Read,
+        OperationType::Compute,
+    ];
+
+    let mut chains = std::vec::Vec::new();
+    for _ in 0..2 {
+        let org = register_org(&client, &admin, &env);
+        let clinic = register_clinic(&client, &admin, &env, &org);
+        let provider = register_provider(&client, &admin, &env, &clinic);
+        chains.push([org, clinic, provider]);
+    }
+    let (single, batched) = (&chains[0], &chains[1]);
+
+    for op in ops.iter() {
+        client.record_gas(&admin, &single[2], &op, &None);
+    }
+    client.record_gas_batch(&admin, &batched[2], &ops);
+
+    for (a, b) in single.iter().zip(batched.iter()) {
+        assert_eq!(client.get_usage(a), client.get_usage(b));
+    }
+    let usage = client.get_usage(&batched[0]);
+    assert_eq!(
+        (
+            usage.read_used,
+            usage.write_used,
+            usage.compute_used,
+            usage.storage_used
+        ),
+        (3, 5, 10, 3)
+    );
+}
+
+#[test]
+fn test_batch_emits_one_event_per_type_per_tenant() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    let clinic = register_clinic(&client, &admin, &env, &org);
+
+    let ops = vec![
+        &env,
+        OperationType::Read,
+        OperationType::Write,
+        OperationType::Read,
+    ];
+    client.record_gas_batch(&admin, &clinic, &ops);
+
+    let events = env.events().all();
+    let recorded: std::vec::Vec<GasRecordedEvent> = events
+        .events()
+        .iter()
+        .filter_map(|e| {
+            let soroban_sdk::xdr::ContractEventBody::V0(body) = &e.body;
+            let val = soroban_sdk::Val::from_val(&env, &body.data);
+            GasRecordedEvent::try_from_val(&env, &val).ok()
+        })
+        .collect();
+    assert_eq!(recorded.len(), 4);
+    for (event, (tenant, op, units)) in recorded.iter().zip([
+        (&clinic, OperationType::Read, 2),
+        (&clinic, OperationType::Write, 5),
+        (&org, OperationType::Read, 2),
+        (&org, OperationType::Write, 5),
+    ]) {
+        assert_eq!(
+            (&event.tenant, &event.op_type, event.units),
+            (tenant, &op, units)
+        );
+    }
+}
+
+#[test]
+fn test_batch_quota_is_enforced_on_aggregate() {
+    let (env, client, admin) = setup();
+    let org = register_org(&client, &admin, &env);
+    client.set_quota(&admin, &org, &default_quota(&env));
+
+    // Each write fits the write sub-limit of 10 alone; three together do not.
+    let ops = vec![
+        &env,
+        OperationType::Write,
+        OperationType::Write,
+        OperationType::Write,
+    ];
+    let res = client.try_record_gas_batch(&admin, &org, &ops);
+    assert_eq!(res, Err(Ok(MeteringError::OperationQuotaExceeded)));
+    assert_eq!(client.get_usage(&org).total(), 0);
+
+    // Without sub-limits, the batch total is checked against the total.
+    let clinic = register_clinic(&client, &admin, &env, &org);
+    let quota = TenantQuota {
+        read_limit: None,
+        write_limit: None,
+        compute_limit: None,
+        storage_limit: None,
+        total_limit: 20,
+        burst_allowance: 5,
+        enabled: true,
+    };
+    client.set_quota(&admin, &clinic, &quota);
+    let ops = vec![
+        &env,
+        OperationType::Compute,
+        OperationType::Write,
+        OperationType::Compute,
+    ];
+    let res = client.try_record_gas_batch(&admin, &clinic, &ops);
+    assert_eq!(res, Err(Ok(MeteringError::QuotaExceeded)));
+    assert_eq!(client.get_usage(&clinic).total(), 0);
+
+    client.record_gas_batch(
+        &admin,
+        &clinic,
+        &vec![&env, OperationType::Compute, OperationType::Write],
+    );
+    assert_eq!(client.get_usage(&clinic).total(), 15);
+    assert_eq!(client.get_usage(&org).total(), 15);
+
+    let res = client.try_record_gas_batch(&admin, &org, &Vec::new(&env));
+    assert_eq!(res, Err(Ok(MeteringError::InvalidInput)));
+}
@@ -40,10 +40,10 @@ fn sample_costs() -> GasCosts {
 
 fn sample_quota() -> TenantQuota {
     TenantQuota {
-        read_limit: 10,
-        write_limit: 10,
-        compute_limit: 10,
-        storage_limit: 10,
+        read_limit: Some(10),
+        write_limit: Some(10),
+        compute_limit: Some(10),
+        storage_limit: Some(10),
         total_limit: 40,
         burst_allowance: 5,
         enabled: true,
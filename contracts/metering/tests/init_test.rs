@@ -61,7 +61,7 @@ fn stateful_entrypoints_reject_calls_before_initialization() {
         Err(Ok(MeteringError::NotInitialized))
     );
     assert_eq!(
-        client.try_record_gas(&outsider, &tenant, &OperationType::Read),
+        client.try_record_gas(&outsider, &tenant, &OperationType::Read, &None),
         Err(Ok(MeteringError::NotInitialized))
     );
 }
@@ -49,7 +49,7 @@ fn zero_gas_costs_are_handled_as_a_no_op_charge() {
     };
 
     client.set_gas_costs(&admin, &zero_costs);
-    client.record_gas(&admin, &tenant, &OperationType::Read);
+    client.record_gas(&admin, &tenant, &OperationType::Read, &None);
 
     assert_eq!(client.get_gas_costs(), zero_costs);
     let usage = client.get_usage(&tenant);
@@ -64,10 +64,10 @@ fn zero_gas_costs_are_handled_as_a_no_op_charge() {
 fn enabled_zero_quota_reliably_blocks_the_first_metered_operation() {
     let (_env, client, admin, tenant) = setup_initialized();
     let zero_quota = TenantQuota {
-        read_limit: 0,
-        write_limit: 0,
-        compute_limit: 0,
-        storage_limit: 0,
+        read_limit: None,
+        write_limit: None,
+        compute_limit: None,
+        storage_limit: None,
         total_limit: 0,
         burst_allowance: 0,
         enabled: true,
@@ -75,7 +75,7 @@ fn enabled_zero_quota_reliably_blocks_the_first_metered_operation() {
 
     client.set_quota(&admin, &tenant, &zero_quota);
 
-    let result = client.try_record_gas(&admin, &tenant, &OperationType::Read);
+    let result = client.try_record_gas(&admin, &tenant, &OperationType::Read, &None);
 
     assert_eq!(result, Err(Ok(MeteringError::QuotaExceeded)));
     assert_eq!(client.get_usage(&tenant).read_used, 0);
@@ -89,10 +89,10 @@ fn random_unregistered_addresses_revert_consistently_in_state_mutations() {
     let clinic = Address::generate(&env);
 
     let quota = TenantQuota {
-        read_limit: 1,
-        write_limit: 1,
-        compute_limit: 1,
-        storage_limit: 1,
+        read_limit: Some(1),
+        write_limit: Some(1),
+        compute_limit: Some(1),
+        storage_limit: Some(1),
         total_limit: 1,
         burst_allowance: 0,
         enabled: true,
@@ -111,7 +111,7 @@ fn random_unregistered_addresses_revert_consistently_in_state_mutations() {
         Err(Ok(MeteringError::TenantNotFound))
     );
     assert_eq!(
-        client.try_record_gas(&admin, &missing_tenant, &OperationType::Read),
+        client.try_record_gas(&admin, &missing_tenant, &OperationType::Read, &None),
         Err(Ok(MeteringError::TenantNotFound))
     );
     assert!(client.get_tenant(&tenant).active);
@@ -143,7 +143,7 @@ fn zero_cost_prepaid_reads_do_not_consume_empty_balances() {
 
     client.set_billing_model(&admin, &tenant, &BillingModel::Prepaid);
     client.set_gas_costs(&admin, &zero_costs);
-    client.record_gas(&admin, &tenant, &OperationType::Read);
+    client.record_gas(&admin, &tenant, &OperationType::Read, &None);
 
     assert_eq!(client.gas_token_balance(&tenant), 0);
     assert_eq!(client.get_usage(&tenant).read_used, 0);
@@ -0,0 +1,184 @@
+use common::transaction::{
+    get_transaction_log, AtomicityMode, TransactionError, TransactionOperation, TransactionPhase,
+    TransactionStatus, TransactionTreeNode,
+};
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+/// Storage keys for sub-transaction composition
+const TX_PARENT: Symbol = symbol_short!("TX_PARNT");
+const TX_CHILDREN: Symbol = symbol_short!("TX_CHILD");
+const TX_ATOMICITY: Symbol = symbol_short!("TX_ATOM");
+
+/// Tracks parent/child relationships between composed transactions.
+///
+/// A sub-transaction is created with `compose_transaction` and runs when an
+/// operation of its parent references it. Each sub-transaction has exactly
+/// one parent with the same initiator, and the composition graph is kept
+/// acyclic.
+pub struct CompositionManager<'a> {
+    env: &'a Env,
+}
+
+impl<'a> CompositionManager<'a> {
+    pub fn new(env: &'a Env) -> Self {
+        Self { env }
+    }
+
+    /// Atomicity mode of a transaction (defaults to `AllOrNothing`)
+    pub fn atomicity(&self, transaction_id: u64) -> AtomicityMode {
+        self.env
+            .storage()
+            .persistent()
+            .get(&(TX_ATOMICITY, transaction_id))
+            .unwrap_or(AtomicityMode::AllOrNothing)
+    }
+
+    pub fn set_atomicity(&self, transaction_id: u64, mode: &AtomicityMode) {
+        self.env
+            .storage()
+            .persistent()
+            .set(&(TX_ATOMICITY, transaction_id), mode);
+    }
+
+    /// Transaction that contains `transaction_id`, if any
+    pub fn parent(&self, transaction_id: u64) -> Option<u64> {
+        self.env
+            .storage()
+            .persistent()
+            .get(&(TX_PARENT, transaction_id))
+    }
+
+    /// Direct sub-transactions of `transaction_id`
+    pub fn children(&self, transaction_id: u64) -> Vec<u64> {
+        self.env
+            .storage()
+            .persistent()
+            .get(&(TX_CHILDREN, transaction_id))
+            .unwrap_or(Vec::new(self.env))
+    }
+
+    /// Check that the sub-transactions referenced by `operations` can be
+    /// composed under the new transaction `transaction_id`.
+    ///
+    /// Each referenced transaction must exist, must belong to `initiator`,
+    /// must not have started, and must not already belong to another
+    /// parent. Walking the referenced subtrees must never reach
+    /// `transaction_id` again.
+    pub fn validate(
+        &self,
+        transaction_id: u64,
+        initiator: &Address,
+        operations: &Vec<TransactionOperation>,
+    ) -> Result<(), TransactionError> {
+        let mut referenced: Vec<u64> = Vec::new(self.env);
+        for i in 0..operations.len() {
+            let operation = operations.get(i).unwrap();
+            let Some(child) = operation.sub_transaction else {
+                continue;
+            };
+
+            let mut path: Vec<u64> = Vec::new(self.env);
+            path.push_back(transaction_id);
+            self.ensure_acyclic(child, &mut path)?;
+
+            if referenced.contains(child) || self.parent(child).is_some() {
+                return Err(TransactionError::InvalidInput);
+            }
+            let log = get_transaction_log(self.env, child)
+                .ok_or(TransactionError::TransactionNotFound)?;
+            if log.initiator != *initiator {
+                return Err(TransactionError::Unauthorized);
+            }
+            if log.phase != TransactionPhase::Preparing || log.status != TransactionStatus::Active {
+                return Err(TransactionError::InvalidPhase);
+            }
+            referenced.push_back(child);
+        }
+        Ok(())
+    }
+
+    /// Depth-first walk from `transaction_id`, failing if it revisits any
+    /// transaction on the current `path`.
+    fn ensure_acyclic(
+        &self,
+        transaction_id: u64,
+        path: &mut Vec<u64>,
+    ) -> Result<(), TransactionError> {
+        if path.contains(transaction_id) {
+            return Err(TransactionError::CompositionCycle);
+        }
+        path.push_back(transaction_id);
+        let children = self.children(transaction_id);
+        for i in 0..children.len() {
+            self.ensure_acyclic(children.get(i).unwrap(), path)?;
+        }
+        path.pop_back();
+        Ok(())
+    }
+
+    /// Record `parent` as the parent of every sub-transaction in `operations`
+    pub fn link(&self, parent: u64, operations: &Vec<TransactionOperation>) {
+        let mut children = self.children(parent);
+        for i in 0..operations.len() {
+            if let Some(child) = operations.get(i).unwrap().sub_transaction {
+                self.env
+                    .storage()
+                    .persistent()
+                    .set(&(TX_PARENT, child), &parent);
+                children.push_back(child);
+            }
+        }
+        self.env
+            .storage()
+            .persistent()
+            .set(&(TX_CHILDREN, parent), &children);
+    }
+
+    /// All operations in the tree rooted at `operations`, depth first, so
+    /// locks for the whole tree can be checked and taken together.
+    pub fn flatten_operations(
+        &self,
+        operations: &Vec<TransactionOperation>,
+    ) -> Vec<TransactionOperation> {
+        let mut all: Vec<TransactionOperation> = Vec::new(self.env);
+        for i in 0..operations.len() {
+            let operation = operations.get(i).unwrap();
+            let child = operation.sub_transaction;
+            all.push_back(operation);
+            if let Some(log) = child.and_then(|id| get_transaction_log(self.env, id)) {
+                all.append(&self.flatten_operations(&log.operations));
+            }
+        }
+        all
+    }
+
+    /// Pre-order listing of the tree rooted at `transaction_id`
+    pub fn tree(&self, transaction_id: u64) -> Result<Vec<TransactionTreeNode>, TransactionError> {
+        let mut nodes: Vec<TransactionTreeNode> = Vec::new(self.env);
+        self.collect(transaction_id, 0, &mut nodes)?;
+        Ok(nodes)
+    }
+
+    fn collect(
+        &self,
+        transaction_id: u64,
+        depth: u32,
+        nodes: &mut Vec<TransactionTreeNode>,
+    ) -> Result<(), TransactionError> {
+        let log = get_transaction_log(self.env, transaction_id)
+            .ok_or(TransactionError::TransactionNotFound)?;
+        nodes.push_back(TransactionTreeNode {
+            transaction_id,
+            parent: self.parent(transaction_id),
+            depth,
+            phase: log.phase,
+            status: log.status,
+            atomicity: self.atomicity(transaction_id),
+        });
+        let children = self.children(transaction_id);
+        for i in 0..children.len() {
+            self.collect(children.get(i).unwrap(), depth + 1, nodes)?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,10 @@
-use common::transaction::{DeadlockInfo, TransactionError, TransactionOperation, RESOURCE_LOCKS};
-use soroban_sdk::{Env, String, Vec};
+use common::transaction::{
+    get_transaction_log, DeadlockInfo, LockMode, TransactionError, TransactionOperation,
+    TransactionStatus, RESOURCE_LOCKS, SHARED_LOCKS,
+};
+use soroban_sdk::{vec, Env, Map, String, Vec};
+
+use super::composition::CompositionManager;
 
 /// Deadlock detector for preventing and resolving transaction deadlocks
 pub struct DeadlockDetector<'a> {
@@ -11,7 +16,11 @@ impl<'a> DeadlockDetector<'a> {
         Self { env }
     }
 
-    /// Check if a new transaction would cause a deadlock
+    /// Check if a new transaction would cause a deadlock.
+    ///
+    /// Only a cycle in the wait-for graph counts. A resource that is merely
+    /// held by another transaction is contention, reported as
+    /// `ResourceLocked` when the locks are taken.
     pub fn would_cause_deadlock(
         &self,
         transaction_id: &u64,
@@ -21,7 +30,12 @@ impl<'a> DeadlockDetector<'a> {
         self.has_cycles(&dependency_graph)
     }
 
-    /// Build a dependency graph for the current transaction
+    /// Build the wait-for graph reachable from the requesting transaction.
+    ///
+    /// The requester waits for every transaction holding one of its
+    /// resources, unless both sides only need it shared. Each active holder
+    /// in turn waits for the holders of the resources its own operations
+    /// still need.
     fn build_dependency_graph(
         &self,
         transaction_id: &u64,
@@ -35,23 +49,76 @@ impl<'a> DeadlockDetector<'a> {
             .instance()
             .get(&RESOURCE_LOCKS)
             .unwrap_or(Vec::new(self.env));
+        let shared: Map<String, u32> = self
+            .env
+            .storage()
+            .instance()
+            .get(&SHARED_LOCKS)
+            .unwrap_or(Map::new(self.env));
+
+        let mut visited: Vec<u64> = vec![self.env, *transaction_id];
+        let mut queue = self.add_waits(
+            &mut graph,
+            *transaction_id,
+            operations,
+            &current_locks,
+            &shared,
+        );
+        while let Some(holder) = queue.pop_front() {
+            if visited.contains(holder) {
+                continue;
+            }
+            visited.push_back(holder);
+
+            let log = match get_transaction_log(self.env, holder) {
+                Some(log)
+                    if matches!(
+                        log.status,
+                        TransactionStatus::Active | TransactionStatus::Suspended
+                    ) =>
+                {
+                    log
+                }
+                _ => continue,
+            };
+            let wanted = CompositionManager::new(self.env).flatten_operations(&log.operations);
+            queue.append(&self.add_waits(&mut graph, holder, &wanted, &current_locks, &shared));
+        }
 
-        // Check each new operation's resources against existing locks
+        graph
+    }
+
+    /// Add an edge from `waiter` to each other transaction holding a
+    /// resource that `operations` lock, returning the holders. A shared
+    /// request does not wait on a resource that is only held shared.
+    fn add_waits(
+        &self,
+        graph: &mut DependencyGraph,
+        waiter: u64,
+        operations: &Vec<TransactionOperation>,
+        current_locks: &Vec<(String, u64)>,
+        shared: &Map<String, u32>,
+    ) -> Vec<u64> {
+        let mut holders: Vec<u64> = Vec::new(self.env);
         for op_idx in 0..operations.len() {
             let operation = operations.get(op_idx).unwrap();
             for res_idx in 0..operation.locked_resources.len() {
                 let resource = operation.locked_resources.get(res_idx).unwrap();
+                if operation.lock_mode == LockMode::Shared && shared.contains_key(resource.clone())
+                {
+                    continue;
+                }
 
                 for lock_idx in 0..current_locks.len() {
                     let (locked_resource, locked_tx_id) = current_locks.get(lock_idx).unwrap();
-                    if locked_resource == resource && locked_tx_id != *transaction_id {
-                        graph.add_dependency(*transaction_id, locked_tx_id, resource.clone());
+                    if locked_resource == resource && locked_tx_id != waiter {
+                        graph.add_dependency(waiter, locked_tx_id, resource.clone());
+                        holders.push_back(locked_tx_id);
                     }
                 }
             }
         }
-
-        graph
+        holders
     }
 
     /// Check if the dependency graph has cycles (indicating deadlock)
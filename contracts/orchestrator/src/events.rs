@@ -1,4 +1,6 @@
-use common::transaction::{ContractType, DeadlockInfo, TransactionLog, TransactionPhase};
+use common::transaction::{
+    AtomicityMode, ContractType, DeadlockInfo, TransactionLog, TransactionPhase,
+};
 use soroban_sdk::{symbol_short, Address, Env, String, Vec};
 
 /// Event publisher for orchestrator events.
@@ -15,6 +17,18 @@ impl EventPublisher {
         );
     }
 
+    /// Publish sub-transaction composed event
+    pub fn transaction_composed(env: &Env, log: &TransactionLog, atomicity: &AtomicityMode) {
+        env.events().publish(
+            (symbol_short!("TX_COMP"), log.transaction_id),
+            (
+                log.initiator.clone(),
+                atomicity.clone(),
+                log.operations.len(),
+            ),
+        );
+    }
+
     /// Publish transaction prepared event
     pub fn transaction_prepared(env: &Env, log: &TransactionLog) {
         env.events().publish(
@@ -39,6 +53,14 @@ impl EventPublisher {
         );
     }
 
+    /// Publish transaction suspended at a savepoint event
+    pub fn transaction_suspended(env: &Env, log: &TransactionLog) {
+        env.events().publish(
+            (symbol_short!("TX_SUSP"), log.transaction_id),
+            (log.updated_at, log.savepoint),
+        );
+    }
+
     /// Publish transaction timed out event
     pub fn transaction_timed_out(env: &Env, log: &TransactionLog) {
         env.events().publish(
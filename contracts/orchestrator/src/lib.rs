@@ -1,5 +1,6 @@
 #![no_std]
 
+pub mod composition;
 pub mod deadlock;
 pub mod errors;
 pub mod events;
@@ -9,12 +10,14 @@ pub mod validation;
 
 use common::transaction::{
     generate_transaction_id, get_default_timeout_config, get_transaction_log,
-    is_transaction_expired, set_transaction_log, TransactionError, TransactionLog,
-    TransactionOperation, TransactionPhase, TransactionStatus, TransactionTimeoutConfig,
-    ACTIVE_TRANSACTIONS, RESOURCE_LOCKS, TIMEOUT_CONFIG, TRANSACTION_COUNTER,
+    is_transaction_expired, set_transaction_log, AtomicityMode, LockMode, TransactionError,
+    TransactionLog, TransactionOperation, TransactionPhase, TransactionStatus,
+    TransactionTimeoutConfig, TransactionTreeNode, ACTIVE_TRANSACTIONS, RESOURCE_LOCKS,
+    SHARED_LOCKS, TIMEOUT_CONFIG, TRANSACTION_COUNTER,
 };
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Map, String, Symbol, Vec};
 
+use composition::CompositionManager;
 use deadlock::DeadlockDetector;
 use events::EventPublisher;
 use rollback::RollbackManager;
@@ -60,33 +63,101 @@ impl OrchestratorContract {
         timeout_seconds: Option<u64>,
         metadata: Vec<String>,
     ) -> Result<u64, TransactionError> {
+        Self::run_transaction(env, initiator, operations, timeout_seconds, metadata, false)
+    }
+
+    /// Start a transaction that keeps its committed progress on failure.
+    ///
+    /// If a commit fails, operations committed before it stay committed and
+    /// the transaction is left `Suspended`, holding its locks, with the
+    /// savepoint recorded in its log. The call still returns the id, since
+    /// an error would discard that state along with the committed prefix;
+    /// check the log's status to tell a suspension from a completed run.
+    /// `resume_transaction` then retries from the first uncommitted
+    /// operation. A prepare failure rolls back as usual.
+    pub fn start_resumable_transaction(
+        env: Env,
+        initiator: Address,
+        operations: Vec<TransactionOperation>,
+        timeout_seconds: Option<u64>,
+        metadata: Vec<String>,
+    ) -> Result<u64, TransactionError> {
+        Self::run_transaction(env, initiator, operations, timeout_seconds, metadata, true)
+    }
+
+    /// Resume a suspended transaction from its savepoint (admin only).
+    ///
+    /// Operations up to the savepoint are not re-driven. The commit that
+    /// failed is retried, so commit hooks must be idempotent: a retried
+    /// commit may follow an attempt that already took partial effect.
+    /// Returns `Completed` once every operation has committed, or
+    /// `Suspended` if a commit fails again, leaving the transaction
+    /// resumable at its new savepoint.
+    pub fn resume_transaction(
+        env: Env,
+        admin: Address,
+        transaction_id: u64,
+    ) -> Result<TransactionStatus, TransactionError> {
+        Self::require_admin(&env, &admin)?;
         Self::require_initialized(&env)?;
 
-        let transaction_id = generate_transaction_id(&env);
-        let now = env.ledger().timestamp();
+        let mut log = get_transaction_log(&env, transaction_id)
+            .ok_or(TransactionError::TransactionNotFound)?;
 
-        // Get timeout configuration
-        let config: TransactionTimeoutConfig = env
-            .storage()
-            .instance()
-            .get(&TIMEOUT_CONFIG)
-            .unwrap_or_else(|| get_default_timeout_config(&env));
+        if log.status != TransactionStatus::Suspended {
+            return Err(TransactionError::InvalidPhase);
+        }
+        if is_transaction_expired(&env, &log) {
+            return Err(TransactionError::TransactionTimeout);
+        }
 
-        let timeout = timeout_seconds.unwrap_or(config.default_timeout);
-        if timeout > config.max_timeout {
-            return Err(TransactionError::InvalidInput);
+        let committed = TransactionManager::new(&env).commit_phase(&mut log);
+        if committed.is_err() {
+            Self::suspend(&env, &mut log);
+            return Ok(log.status);
         }
 
-        // Check for potential deadlocks before starting
-        let deadlock_detector = DeadlockDetector::new(&env);
-        if deadlock_detector.would_cause_deadlock(&transaction_id, &operations) {
-            return Err(TransactionError::DeadlockDetected);
+        log.phase = TransactionPhase::Committed;
+        log.status = TransactionStatus::Completed;
+        log.error = None;
+        log.updated_at = env.ledger().timestamp();
+
+        set_transaction_log(&env, &log);
+        Self::release_resource_locks(&env, transaction_id)?;
+
+        EventPublisher::transaction_committed(&env, &log);
+        Ok(log.status)
+    }
+
+    /// Register a transaction to run later as a sub-transaction.
+    ///
+    /// Nothing is executed here. The returned id is referenced from a parent
+    /// operation's `sub_transaction` and runs, with its own two-phase commit,
+    /// when the parent starts. `atomicity` decides whether its failure rolls
+    /// back the parent. Only a parent with the same `initiator` may claim it.
+    pub fn compose_transaction(
+        env: Env,
+        initiator: Address,
+        operations: Vec<TransactionOperation>,
+        atomicity: AtomicityMode,
+        timeout_seconds: Option<u64>,
+    ) -> Result<u64, TransactionError> {
+        initiator.require_auth();
+        Self::require_initialized(&env)?;
+        if operations.is_empty() {
+            return Err(TransactionError::InvalidInput);
         }
 
-        // Create transaction log
-        let mut log = TransactionLog {
+        let transaction_id = generate_transaction_id(&env);
+        let now = env.ledger().timestamp();
+        let timeout = Self::resolve_timeout(&env, timeout_seconds)?;
+
+        let composition = CompositionManager::new(&env);
+        composition.validate(transaction_id, &initiator, &operations)?;
+
+        let log = TransactionLog {
             transaction_id,
-            initiator: initiator.clone(),
+            initiator,
             phase: TransactionPhase::Preparing,
             status: TransactionStatus::Active,
             operations: operations.clone(),
@@ -94,82 +165,25 @@ impl OrchestratorContract {
             updated_at: now,
             timeout_seconds: timeout,
             error: None,
-            metadata,
+            metadata: Vec::new(&env),
+            savepoint: None,
         };
-
-        // Store transaction log
         set_transaction_log(&env, &log);
+        composition.link(transaction_id, &operations);
+        composition.set_atomicity(transaction_id, &atomicity);
 
-        // Acquire resource locks
-        Self::acquire_resource_locks(&env, &transaction_id, &operations)?;
-
-        // Publish transaction started event
-        EventPublisher::transaction_started(&env, &log);
-
-        // Start two-phase commit
-        let tx_manager = TransactionManager::new(&env);
-        match tx_manager.prepare_phase(&mut log) {
-            Ok(()) => {
-                // All operations prepared successfully, commit them
-                match tx_manager.commit_phase(&mut log) {
-                    Ok(()) => {
-                        log.phase = TransactionPhase::Committed;
-                        log.status = TransactionStatus::Completed;
-                        log.updated_at = env.ledger().timestamp();
-
-                        set_transaction_log(&env, &log);
-                        Self::release_resource_locks(&env, transaction_id)?;
-
-                        EventPublisher::transaction_committed(&env, &log);
-                        Ok(transaction_id)
-                    }
-                    Err(e) => {
-                        // Commit failed, rollback
-                        let rollback_manager = RollbackManager::new(&env);
-                        if let Err(_rollback_err) = rollback_manager.rollback_transaction(&log) {
-                            log.error = Some(String::from_str(
-                                &env,
-                                "Commit failed; rollback also failed",
-                            ));
-                        } else {
-                            log.error = Some(String::from_str(&env, "Commit failed; rolled back"));
-                        }
-
-                        log.phase = TransactionPhase::RolledBack;
-                        log.status = TransactionStatus::Failed;
-                        log.updated_at = env.ledger().timestamp();
-
-                        set_transaction_log(&env, &log);
-                        Self::release_resource_locks(&env, transaction_id)?;
-
-                        EventPublisher::transaction_rolled_back(&env, &log);
-                        Err(e)
-                    }
-                }
-            }
-            Err(e) => {
-                // Prepare failed, rollback
-                let rollback_manager = RollbackManager::new(&env);
-                if let Err(_rollback_err) = rollback_manager.rollback_transaction(&log) {
-                    log.error = Some(String::from_str(
-                        &env,
-                        "Prepare failed; rollback also failed",
-                    ));
-                } else {
-                    log.error = Some(String::from_str(&env, "Prepare failed; rolled back"));
-                }
-
-                log.phase = TransactionPhase::RolledBack;
-                log.status = TransactionStatus::Failed;
-                log.updated_at = env.ledger().timestamp();
+        EventPublisher::transaction_composed(&env, &log, &atomicity);
+        Ok(transaction_id)
+    }
 
-                set_transaction_log(&env, &log);
-                Self::release_resource_locks(&env, transaction_id)?;
+    /// Get a transaction and all of its sub-transactions, in pre-order
+    pub fn get_transaction_tree(
+        env: Env,
+        transaction_id: u64,
+    ) -> Result<Vec<TransactionTreeNode>, TransactionError> {
+        Self::require_initialized(&env)?;
 
-                EventPublisher::transaction_rolled_back(&env, &log);
-                Err(e)
-            }
-        }
+        CompositionManager::new(&env).tree(transaction_id)
     }
 
     /// Get transaction details by ID
@@ -293,6 +307,158 @@ impl OrchestratorContract {
 
     // Helper functions
 
+    /// Park a transaction whose commit failed at its savepoint. The locks
+    /// stay held.
+    fn suspend(env: &Env, log: &mut TransactionLog) {
+        log.status = TransactionStatus::Suspended;
+        log.error = Some(String::from_str(
+            env,
+            "Commit failed; resumable from savepoint",
+        ));
+        log.updated_at = env.ledger().timestamp();
+
+        set_transaction_log(env, log);
+        EventPublisher::transaction_suspended(env, log);
+    }
+
+    /// Shared body of `start_transaction` and `start_resumable_transaction`
+    fn run_transaction(
+        env: Env,
+        initiator: Address,
+        operations: Vec<TransactionOperation>,
+        timeout_seconds: Option<u64>,
+        metadata: Vec<String>,
+        resumable: bool,
+    ) -> Result<u64, TransactionError> {
+        initiator.require_auth();
+        Self::require_initialized(&env)?;
+
+        let transaction_id = generate_transaction_id(&env);
+        let now = env.ledger().timestamp();
+        let timeout = Self::resolve_timeout(&env, timeout_seconds)?;
+
+        // Sub-transactions run under this transaction, so their resources are
+        // checked and locked together with ours.
+        let composition = CompositionManager::new(&env);
+        composition.validate(transaction_id, &initiator, &operations)?;
+        let tree_operations = composition.flatten_operations(&operations);
+
+        // Check for potential deadlocks before starting
+        let deadlock_detector = DeadlockDetector::new(&env);
+        if deadlock_detector.would_cause_deadlock(&transaction_id, &tree_operations) {
+            return Err(TransactionError::DeadlockDetected);
+        }
+
+        // Create transaction log
+        let mut log = TransactionLog {
+            transaction_id,
+            initiator: initiator.clone(),
+            phase: TransactionPhase::Preparing,
+            status: TransactionStatus::Active,
+            operations: operations.clone(),
+            created_at: now,
+            updated_at: now,
+            timeout_seconds: timeout,
+            error: None,
+            metadata,
+            savepoint: None,
+        };
+
+        // Store transaction log
+        set_transaction_log(&env, &log);
+        composition.link(transaction_id, &operations);
+
+        // Acquire resource locks
+        Self::acquire_resource_locks(&env, &transaction_id, &tree_operations)?;
+
+        // Publish transaction started event
+        EventPublisher::transaction_started(&env, &log);
+
+        // Start two-phase commit
+        let tx_manager = TransactionManager::new(&env);
+        match tx_manager.prepare_phase(&mut log) {
+            Ok(()) => {
+                // All operations prepared successfully, commit them
+                match tx_manager.commit_phase(&mut log) {
+                    Ok(()) => {
+                        log.phase = TransactionPhase::Committed;
+                        log.status = TransactionStatus::Completed;
+                        log.updated_at = env.ledger().timestamp();
+
+                        set_transaction_log(&env, &log);
+                        Self::release_resource_locks(&env, transaction_id)?;
+
+                        EventPublisher::transaction_committed(&env, &log);
+                        Ok(transaction_id)
+                    }
+                    Err(_) if resumable => {
+                        // Keep the committed prefix and the locks for a resume
+                        Self::suspend(&env, &mut log);
+                        Ok(transaction_id)
+                    }
+                    Err(e) => {
+                        // Commit failed, rollback
+                        let rollback_manager = RollbackManager::new(&env);
+                        if let Err(_rollback_err) = rollback_manager.rollback_transaction(&log) {
+                            log.error = Some(String::from_str(
+                                &env,
+                                "Commit failed; rollback also failed",
+                            ));
+                        } else {
+                            log.error = Some(String::from_str(&env, "Commit failed; rolled back"));
+                        }
+
+                        log.phase = TransactionPhase::RolledBack;
+                        log.status = TransactionStatus::Failed;
+                        log.updated_at = env.ledger().timestamp();
+
+                        set_transaction_log(&env, &log);
+                        Self::release_resource_locks(&env, transaction_id)?;
+
+                        EventPublisher::transaction_rolled_back(&env, &log);
+                        Err(e)
+                    }
+                }
+            }
+            Err(e) => {
+                // Prepare failed, rollback
+                let rollback_manager = RollbackManager::new(&env);
+                if let Err(_rollback_err) = rollback_manager.rollback_transaction(&log) {
+                    log.error = Some(String::from_str(
+                        &env,
+                        "Prepare failed; rollback also failed",
+                    ));
+                } else {
+                    log.error = Some(String::from_str(&env, "Prepare failed; rolled back"));
+                }
+
+                log.phase = TransactionPhase::RolledBack;
+                log.status = TransactionStatus::Failed;
+                log.updated_at = env.ledger().timestamp();
+
+                set_transaction_log(&env, &log);
+                Self::release_resource_locks(&env, transaction_id)?;
+
+                EventPublisher::transaction_rolled_back(&env, &log);
+                Err(e)
+            }
+        }
+    }
+
+    fn resolve_timeout(env: &Env, timeout_seconds: Option<u64>) -> Result<u64, TransactionError> {
+        let config: TransactionTimeoutConfig = env
+            .storage()
+            .instance()
+            .get(&TIMEOUT_CONFIG)
+            .unwrap_or_else(|| get_default_timeout_config(env));
+
+        let timeout = timeout_seconds.unwrap_or(config.default_timeout);
+        if timeout > config.max_timeout {
+            return Err(TransactionError::InvalidInput);
+        }
+        Ok(timeout)
+    }
+
     fn require_initialized(env: &Env) -> Result<(), TransactionError> {
         if !env.storage().instance().has(&INITIALIZED) {
             Err(TransactionError::Unauthorized)
@@ -315,6 +481,11 @@ impl OrchestratorContract {
         }
     }
 
+    /// Lock every resource of `operations` for `transaction_id`.
+    ///
+    /// `RESOURCE_LOCKS` has one entry per holder. A resource listed in
+    /// `SHARED_LOCKS` is held in shared mode by that many transactions and
+    /// admits further shared holders; any other held resource is exclusive.
     fn acquire_resource_locks(
         env: &Env,
         transaction_id: &u64,
@@ -325,24 +496,50 @@ impl OrchestratorContract {
             .instance()
             .get(&RESOURCE_LOCKS)
             .unwrap_or(Vec::new(env));
+        let mut shared: Map<String, u32> = env
+            .storage()
+            .instance()
+            .get(&SHARED_LOCKS)
+            .unwrap_or(Map::new(env));
 
         for op_idx in 0..operations.len() {
             let operation = operations.get(op_idx).unwrap();
+            let exclusive = operation.lock_mode == LockMode::Exclusive;
             for res_idx in 0..operation.locked_resources.len() {
                 let resource = operation.locked_resources.get(res_idx).unwrap();
-                // Check if resource is already locked
+                // Check if resource is already locked. A resource shared by
+                // several operations of the same tree is locked once.
+                let mut held = false;
+                let mut held_by_others = false;
                 for i in 0..locks.len() {
-                    let (locked_resource, _locked_tx) = locks.get(i).unwrap();
+                    let (locked_resource, locked_tx) = locks.get(i).unwrap();
                     if locked_resource == resource {
-                        return Err(TransactionError::ResourceLocked);
+                        if locked_tx == *transaction_id {
+                            held = true;
+                        } else {
+                            held_by_others = true;
+                        }
                     }
                 }
+                let shared_holders = shared.get(resource.clone());
+                if held_by_others && (exclusive || shared_holders.is_none()) {
+                    return Err(TransactionError::ResourceLocked);
+                }
                 // Acquire lock
-                locks.push_back((resource.clone(), *transaction_id));
+                if !held {
+                    locks.push_back((resource.clone(), *transaction_id));
+                    if !exclusive {
+                        shared.set(resource, shared_holders.unwrap_or(0) + 1);
+                    }
+                } else if exclusive {
+                    // Sole shared holder upgrading within its own tree
+                    shared.remove(resource);
+                }
             }
         }
 
         env.storage().instance().set(&RESOURCE_LOCKS, &locks);
+        env.storage().instance().set(&SHARED_LOCKS, &shared);
         Ok(())
     }
 
@@ -352,16 +549,29 @@ impl OrchestratorContract {
             .instance()
             .get(&RESOURCE_LOCKS)
             .unwrap_or(Vec::new(env));
+        let mut shared: Map<String, u32> = env
+            .storage()
+            .instance()
+            .get(&SHARED_LOCKS)
+            .unwrap_or(Map::new(env));
 
         let mut new_locks: Vec<(String, u64)> = Vec::new(env);
         for i in 0..locks.len() {
             let (resource, locked_tx_id) = locks.get(i).unwrap();
             if locked_tx_id != transaction_id {
                 new_locks.push_back((resource, locked_tx_id));
+            } else if let Some(holders) = shared.get(resource.clone()) {
+                // The last shared holder leaving frees the resource
+                if holders > 1 {
+                    shared.set(resource, holders - 1);
+                } else {
+                    shared.remove(resource);
+                }
             }
         }
 
         env.storage().instance().set(&RESOURCE_LOCKS, &new_locks);
+        env.storage().instance().set(&SHARED_LOCKS, &shared);
         Ok(())
     }
 }
@@ -377,3 +587,18 @@ mod test_rbac_interactions;
 
 #[cfg(test)]
 mod test_workflow_transitions;
+
+#[cfg(test)]
+mod test_sub_transactions;
+
+#[cfg(test)]
+mod test_two_phase_hooks;
+
+#[cfg(test)]
+mod test_deadlock;
+
+#[cfg(test)]
+mod test_savepoints;
+
+#[cfg(test)]
+mod test_lock_modes;
@@ -1,9 +1,11 @@
 use common::transaction::{
-    get_transaction_log, RollbackInfo, TransactionError, TransactionLog, TransactionOperation,
+    get_transaction_log, set_transaction_log, RollbackInfo, TransactionError, TransactionLog,
+    TransactionOperation, TransactionPhase, TransactionStatus,
 };
-use soroban_sdk::{Env, IntoVal, String, Symbol, Val, Vec};
+use soroban_sdk::{Env, String, Vec};
 
 use super::events::EventPublisher;
+use super::transaction::{invoke_hook, Hook};
 
 /// Rollback manager for handling transaction rollback operations
 pub struct RollbackManager<'a> {
@@ -17,15 +19,38 @@ impl<'a> RollbackManager<'a> {
 
     /// Rollback an entire transaction in reverse order (LIFO)
     pub fn rollback_transaction(&self, log: &TransactionLog) -> Result<(), TransactionError> {
+        self.rollback_operations(log)
+    }
+
+    /// Roll back a prepared sub-transaction and mark it rolled back.
+    pub fn rollback_sub_transaction(&self, transaction_id: u64) -> Result<(), TransactionError> {
+        let mut log = get_transaction_log(self.env, transaction_id)
+            .ok_or(TransactionError::TransactionNotFound)?;
+        let result = self.rollback_operations(&log);
+
+        log.phase = TransactionPhase::RolledBack;
+        log.status = TransactionStatus::Failed;
+        log.updated_at = self.env.ledger().timestamp();
+        set_transaction_log(self.env, &log);
+        EventPublisher::transaction_rolled_back(self.env, &log);
+        result
+    }
+
+    /// Undo every prepared operation, including ones that already committed
+    /// before a later commit failed.
+    fn rollback_operations(&self, log: &TransactionLog) -> Result<(), TransactionError> {
         let mut rollback_failed = false;
 
         // Rollback operations in reverse order (LIFO principle)
         for i in (0..log.operations.len()).rev() {
             let operation = log.operations.get(i).unwrap();
 
-            // Only rollback operations that were prepared but not committed
-            if operation.prepared && !operation.committed {
-                match self.rollback_operation(&operation) {
+            if operation.prepared {
+                let outcome = match operation.sub_transaction {
+                    Some(child) => self.rollback_sub_transaction(child),
+                    None => self.rollback_operation(&operation).map(|_| ()),
+                };
+                match outcome {
                     Ok(_) => {
                         EventPublisher::operation_rolled_back(
                             self.env,
@@ -55,13 +80,11 @@ impl<'a> RollbackManager<'a> {
         }
     }
 
-    /// Rollback a single operation via cross-contract invocation
+    /// Rollback a single operation by calling its rollback hook
     pub fn rollback_operation(
         &self,
         operation: &TransactionOperation,
     ) -> Result<RollbackInfo, TransactionError> {
-        let func_sym = Symbol::new(self.env, "rollback_");
-
         let mut rollback_info = RollbackInfo {
             transaction_id: 0,
             operation_id: operation.operation_id,
@@ -72,16 +95,8 @@ impl<'a> RollbackManager<'a> {
             rollback_error: None,
         };
 
-        let mut args: Vec<Val> = Vec::new(self.env);
-        for i in 0..operation.parameters.len() {
-            let param = operation.parameters.get(i).unwrap();
-            args.push_back(param.into_val(self.env));
-        }
-
-        // Invoke rollback — panics on failure which Soroban runtime catches
-        let _result: Val = self
-            .env
-            .invoke_contract(&operation.contract_address, &func_sym, args);
+        invoke_hook(self.env, operation, Hook::Rollback)
+            .map_err(|_| TransactionError::RollbackFailed)?;
 
         rollback_info.rollback_successful = true;
         Ok(rollback_info)
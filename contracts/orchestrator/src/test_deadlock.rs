@@ -0,0 +1,111 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::{deadlock::DeadlockDetector, OrchestratorContract};
+use common::transaction::{
+    set_transaction_log, ContractType, LockMode, TransactionError, TransactionLog,
+    TransactionOperation, TransactionPhase, TransactionStatus, RESOURCE_LOCKS,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, Vec};
+
+fn setup() -> (Env, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let orchestrator = env.register(OrchestratorContract, ());
+    env.as_contract(&orchestrator, || {
+        OrchestratorContract::initialize(env.clone(), Address::generate(&env), None).unwrap();
+    });
+    (env, orchestrator)
+}
+
+fn locking(env: &Env, resources: &[&str]) -> Vec<TransactionOperation> {
+    let mut locked_resources = Vec::new(env);
+    for resource in resources {
+        locked_resources.push_back(String::from_str(env, resource));
+    }
+    vec![
+        env,
+        TransactionOperation {
+            operation_id: 1,
+            contract_type: ContractType::VisionRecords,
+            contract_address: Address::generate(env),
+            function_name: String::from_str(env, "update"),
+            parameters: Vec::new(env),
+            locked_resources,
+            lock_mode: LockMode::Exclusive,
+            prepared: false,
+            committed: false,
+            error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
+        },
+    ]
+}
+
+/// Record `transaction_id` as an active transaction holding `held` while
+/// its operations need `wanted`.
+fn hold(env: &Env, transaction_id: u64, held: &[&str], wanted: &[&str]) {
+    set_transaction_log(
+        env,
+        &TransactionLog {
+            transaction_id,
+            initiator: Address::generate(env),
+            phase: TransactionPhase::Preparing,
+            status: TransactionStatus::Active,
+            operations: locking(env, wanted),
+            created_at: 0,
+            updated_at: 0,
+            timeout_seconds: 300,
+            error: None,
+            metadata: Vec::new(env),
+            savepoint: None,
+        },
+    );
+
+    let mut locks: Vec<(String, u64)> = env
+        .storage()
+        .instance()
+        .get(&RESOURCE_LOCKS)
+        .unwrap_or(Vec::new(env));
+    for resource in held {
+        locks.push_back((String::from_str(env, resource), transaction_id));
+    }
+    env.storage().instance().set(&RESOURCE_LOCKS, &locks);
+}
+
+#[test]
+fn test_two_transaction_cyclic_wait_is_a_deadlock() {
+    let (env, orchestrator) = setup();
+    env.as_contract(&orchestrator, || {
+        // 7 holds patient:1, 8 holds patient:2 and is waiting for patient:1.
+        hold(&env, 7, &["patient:1"], &["patient:1"]);
+        hold(&env, 8, &["patient:2"], &["patient:2", "patient:1"]);
+
+        let detector = DeadlockDetector::new(&env);
+        // 7 asking for patient:2 closes the cycle 7 -> 8 -> 7.
+        assert!(detector.would_cause_deadlock(&7, &locking(&env, &["patient:2"])));
+        // Without that edge there is no cycle.
+        assert!(!detector.would_cause_deadlock(&7, &locking(&env, &["patient:3"])));
+    });
+}
+
+#[test]
+fn test_contention_is_resource_locked_not_deadlock() {
+    let (env, orchestrator) = setup();
+    env.as_contract(&orchestrator, || {
+        hold(&env, 7, &["patient:1"], &["patient:1"]);
+
+        let operations = locking(&env, &["patient:1"]);
+        assert!(!DeadlockDetector::new(&env).would_cause_deadlock(&9, &operations));
+
+        let result = OrchestratorContract::start_transaction(
+            env.clone(),
+            Address::generate(&env),
+            operations,
+            None,
+            Vec::new(&env),
+        );
+        assert_eq!(result, Err(TransactionError::ResourceLocked));
+    });
+}
@@ -5,7 +5,7 @@ mod gas_benchmarks {
         OrchestratorContract,
     };
     use common::transaction::{
-        self, ContractType, TransactionLog, TransactionOperation, TransactionPhase,
+        self, ContractType, LockMode, TransactionLog, TransactionOperation, TransactionPhase,
         TransactionStatus,
     };
     use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, Vec};
@@ -35,9 +35,14 @@ mod gas_benchmarks {
             function_name: String::from_str(&env, "add_record"),
             parameters: Vec::new(&env),
             locked_resources: Vec::new(&env),
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         };
 
         let operations = vec![&env, single_operation];
@@ -86,9 +91,14 @@ mod gas_benchmarks {
                     function_name: String::from_str(&env, "test_function"),
                     parameters: Vec::new(&env),
                     locked_resources: vec![&env, String::from_str(&env, "resource")],
+                    lock_mode: LockMode::Exclusive,
                     prepared: false,
                     committed: false,
                     error: None,
+                    sub_transaction: None,
+                    prepare_hook: None,
+                    commit_hook: None,
+                    rollback_hook: None,
                 });
             }
 
@@ -138,9 +148,14 @@ mod gas_benchmarks {
                         String::from_str(&env, "resource_a"),
                         String::from_str(&env, "resource_b"),
                     ],
+                    lock_mode: LockMode::Exclusive,
                     prepared: false,
                     committed: false,
                     error: None,
+                    sub_transaction: None,
+                    prepare_hook: None,
+                    commit_hook: None,
+                    rollback_hook: None,
                 });
             }
 
@@ -178,9 +193,14 @@ mod gas_benchmarks {
                     function_name: String::from_str(&env, "test_function"),
                     parameters: Vec::new(&env),
                     locked_resources: Vec::new(&env),
+                    lock_mode: LockMode::Exclusive,
                     prepared: true,
                     committed: false,
                     error: None,
+                    sub_transaction: None,
+                    prepare_hook: None,
+                    commit_hook: None,
+                    rollback_hook: None,
                 });
             }
 
@@ -195,6 +215,7 @@ mod gas_benchmarks {
                 timeout_seconds: 300,
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
 
             env.cost_estimate().budget().reset_default();
@@ -257,9 +278,14 @@ mod gas_benchmarks {
                 function_name: String::from_str(&env, "test_function"),
                 parameters: Vec::new(&env),
                 locked_resources: Vec::new(&env),
+                lock_mode: LockMode::Exclusive,
                 prepared: false,
                 committed: false,
                 error: None,
+                sub_transaction: None,
+                prepare_hook: None,
+                commit_hook: None,
+                rollback_hook: None,
             });
 
             let log = TransactionLog {
@@ -273,6 +299,7 @@ mod gas_benchmarks {
                 timeout_seconds: 300,
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
 
             env.cost_estimate().budget().reset_default();
@@ -321,9 +348,14 @@ mod gas_benchmarks {
                 function_name: String::from_str(&env, "test_function"),
                 parameters: Vec::new(&env),
                 locked_resources: Vec::new(&env),
+                lock_mode: LockMode::Exclusive,
                 prepared: false,
                 committed: false,
                 error: None,
+                sub_transaction: None,
+                prepare_hook: None,
+                commit_hook: None,
+                rollback_hook: None,
             });
 
             let log = TransactionLog {
@@ -337,6 +369,7 @@ mod gas_benchmarks {
                 timeout_seconds: 100, // Very short timeout
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
 
             transaction::set_transaction_log(&env, &log);
@@ -0,0 +1,96 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::OrchestratorContract;
+use common::transaction::{
+    ContractType, LockMode, TransactionError, TransactionOperation, RESOURCE_LOCKS, SHARED_LOCKS,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Map, String, Vec};
+
+fn setup() -> (Env, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let orchestrator = env.register(OrchestratorContract, ());
+    env.as_contract(&orchestrator, || {
+        OrchestratorContract::initialize(env.clone(), Address::generate(&env), None).unwrap();
+    });
+    (env, orchestrator)
+}
+
+fn locking(env: &Env, lock_mode: LockMode) -> Vec<TransactionOperation> {
+    vec![
+        env,
+        TransactionOperation {
+            operation_id: 1,
+            contract_type: ContractType::VisionRecords,
+            contract_address: Address::generate(env),
+            function_name: String::from_str(env, "get_record"),
+            parameters: Vec::new(env),
+            locked_resources: vec![env, String::from_str(env, "patient:1")],
+            lock_mode,
+            prepared: false,
+            committed: false,
+            error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
+        },
+    ]
+}
+
+fn lock(env: &Env, transaction_id: u64, mode: LockMode) -> Result<(), TransactionError> {
+    OrchestratorContract::acquire_resource_locks(env, &transaction_id, &locking(env, mode))
+}
+
+fn shared_holders(env: &Env) -> Option<u32> {
+    let shared: Map<String, u32> = env.storage().instance().get(&SHARED_LOCKS).unwrap();
+    shared.get(String::from_str(env, "patient:1"))
+}
+
+#[test]
+fn test_shared_locks_coexist_and_are_refcounted() {
+    let (env, orchestrator) = setup();
+    env.as_contract(&orchestrator, || {
+        lock(&env, 7, LockMode::Shared).unwrap();
+        lock(&env, 8, LockMode::Shared).unwrap();
+        assert_eq!(shared_holders(&env), Some(2));
+
+        // One reader leaving still blocks a writer.
+        OrchestratorContract::release_resource_locks(&env, 7).unwrap();
+        assert_eq!(shared_holders(&env), Some(1));
+        assert_eq!(
+            lock(&env, 9, LockMode::Exclusive),
+            Err(TransactionError::ResourceLocked)
+        );
+
+        OrchestratorContract::release_resource_locks(&env, 8).unwrap();
+        assert_eq!(shared_holders(&env), None);
+        let locks: Vec<(String, u64)> = env.storage().instance().get(&RESOURCE_LOCKS).unwrap();
+        assert!(locks.is_empty());
+        lock(&env, 9, LockMode::Exclusive).unwrap();
+    });
+}
+
+#[test]
+fn test_shared_and_exclusive_locks_conflict() {
+    let (env, orchestrator) = setup();
+    env.as_contract(&orchestrator, || {
+        lock(&env, 7, LockMode::Shared).unwrap();
+        assert_eq!(
+            lock(&env, 8, LockMode::Exclusive),
+            Err(TransactionError::ResourceLocked)
+        );
+        OrchestratorContract::release_resource_locks(&env, 7).unwrap();
+
+        lock(&env, 8, LockMode::Exclusive).unwrap();
+        assert_eq!(
+            lock(&env, 9, LockMode::Shared),
+            Err(TransactionError::ResourceLocked)
+        );
+        assert_eq!(
+            lock(&env, 9, LockMode::Exclusive),
+            Err(TransactionError::ResourceLocked)
+        );
+        assert_eq!(shared_holders(&env), None);
+    });
+}
@@ -5,7 +5,7 @@ mod tests {
         transaction::TransactionManager, OrchestratorContract,
     };
     use common::transaction::{
-        get_default_timeout_config, is_transaction_expired, set_transaction_log, ContractType,
+        get_default_timeout_config, is_transaction_expired, set_transaction_log, ContractType, LockMode,
         TransactionError, TransactionLog, TransactionOperation, TransactionPhase,
         TransactionStatus, TransactionTimeoutConfig,
     };
@@ -48,9 +48,14 @@ mod tests {
             function_name: String::from_str(&env, "add_record"),
             parameters: Vec::new(&env),
             locked_resources: Vec::new(&env),
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
         // Start transaction (should fail gracefully since contract doesn't exist)
@@ -94,9 +99,14 @@ mod tests {
             function_name: String::from_str(&env, "add_record"),
             parameters: Vec::new(&env),
             locked_resources: Vec::new(&env),
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
         duplicate_ops.push_back(TransactionOperation {
             operation_id: 1, // Duplicate ID
@@ -105,9 +115,14 @@ mod tests {
             function_name: String::from_str(&env, "add_guardian"),
             parameters: Vec::new(&env),
             locked_resources: Vec::new(&env),
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
         assert_eq!(
@@ -139,9 +154,14 @@ mod tests {
                 String::from_str(&env, "resource_1"),
                 String::from_str(&env, "resource_2"),
             ],
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
         let mut operations2 = Vec::new(&env);
@@ -156,9 +176,14 @@ mod tests {
                 String::from_str(&env, "resource_2"),
                 String::from_str(&env, "resource_1"),
             ],
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
         // First transaction should not cause deadlock
@@ -172,8 +197,8 @@ mod tests {
             .instance()
             .set(&common::transaction::RESOURCE_LOCKS, &locks);
 
-        // Second transaction should detect potential deadlock
-        assert!(deadlock_detector.would_cause_deadlock(&2, &operations2));
+        // Waiting on the first transaction is contention, not a deadlock
+        assert!(!deadlock_detector.would_cause_deadlock(&2, &operations2));
     }
 
     #[test]
@@ -195,9 +220,14 @@ mod tests {
             function_name: String::from_str(&env, "add_record"),
             parameters: Vec::new(&env),
             locked_resources: Vec::new(&env),
+            lock_mode: LockMode::Exclusive,
             prepared: true,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
         let log = TransactionLog {
@@ -211,6 +241,7 @@ mod tests {
             timeout_seconds: 300,
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         // Test rollback (should fail gracefully since contract doesn't exist)
@@ -304,9 +335,14 @@ mod tests {
             function_name: String::from_str(&env, "add_record"),
             parameters: Vec::new(&env),
             locked_resources: Vec::new(&env),
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
         // Start transaction (should fail)
@@ -358,6 +394,7 @@ mod tests {
             timeout_seconds: 300,
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         // These should not panic
@@ -457,9 +494,14 @@ mod tests {
             function_name: String::from_str(&env, "add_record"),
             parameters: Vec::new(&env),
             locked_resources: vec![&env, String::from_str(&env, "patient_123")],
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
         operations.push_back(TransactionOperation {
@@ -469,9 +511,14 @@ mod tests {
             function_name: String::from_str(&env, "add_guardian"),
             parameters: Vec::new(&env),
             locked_resources: vec![&env, String::from_str(&env, "identity_456")],
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
         // Start transaction (should fail due to contract calls, but structure should be valid)
@@ -571,6 +618,7 @@ mod tests {
             timeout_seconds: 300, // deadline = 1_300
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         // At creation time — not expired.
@@ -602,6 +650,7 @@ mod tests {
             timeout_seconds: 300, // deadline = 5_300
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         env.ledger().with_mut(|li| li.timestamp = 5_299);
@@ -625,6 +674,7 @@ mod tests {
             timeout_seconds: 300, // deadline = 5_300
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         env.ledger().with_mut(|li| li.timestamp = 5_301);
@@ -649,6 +699,7 @@ mod tests {
             timeout_seconds: 300, // deadline = 5_300
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         // now (1_000) is not > deadline (5_300) → not expired.
@@ -695,6 +746,7 @@ mod tests {
             timeout_seconds: 300, // deadline = 1_300
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         // Jump timestamp to far future (~10 years from now).
@@ -741,6 +793,7 @@ mod tests {
                 timeout_seconds: 300, // deadline = 1_300
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log);
         });
@@ -788,6 +841,7 @@ mod tests {
                 timeout_seconds: 300, // deadline = 1_300
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log);
         });
@@ -834,6 +888,7 @@ mod tests {
                     timeout_seconds: timeout,
                     error: None,
                     metadata: Vec::new(&env),
+                    savepoint: None,
                 };
                 set_transaction_log(&env, &log);
             }
@@ -872,6 +927,7 @@ mod tests {
                 timeout_seconds: 300,
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log);
         });
@@ -912,6 +968,7 @@ mod tests {
                 timeout_seconds: 300,
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log);
         });
@@ -947,6 +1004,7 @@ mod tests {
                 timeout_seconds: 100,
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             // tx2: deadline = 10_500
             let log2 = TransactionLog {
@@ -960,6 +1018,7 @@ mod tests {
                 timeout_seconds: 500,
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log1);
             set_transaction_log(&env, &log2);
@@ -1003,6 +1062,7 @@ mod tests {
             timeout_seconds: 3600, // max allowed; deadline = 1_003_600
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         env.as_contract(&contract_id, || {
@@ -1054,6 +1114,7 @@ mod tests {
                 timeout_seconds: 100, // deadline = 5_100
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log);
         });
@@ -1079,6 +1140,7 @@ mod tests {
     #[test]
     fn test_start_transaction_rejects_timeout_exceeding_max() {
         let env = Env::default();
+        env.mock_all_auths();
         let admin = Address::generate(&env);
         let initiator = Address::generate(&env);
         let contract_address = Address::generate(&env);
@@ -1098,9 +1160,14 @@ mod tests {
                 function_name: String::from_str(&env, "add_record"),
                 parameters: Vec::new(&env),
                 locked_resources: Vec::new(&env),
+                lock_mode: LockMode::Exclusive,
                 prepared: false,
                 committed: false,
                 error: None,
+                sub_transaction: None,
+                prepare_hook: None,
+                commit_hook: None,
+                rollback_hook: None,
             });
 
             // 9_999 seconds exceeds default max_timeout of 3_600.
@@ -1137,6 +1204,7 @@ mod tests {
             timeout_seconds: 30, // minimum positive timeout
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
 
         env.as_contract(&contract_id, || {
@@ -1178,6 +1246,7 @@ mod tests {
                 timeout_seconds: 300,
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log);
         });
@@ -1257,6 +1326,7 @@ mod tests {
                 timeout_seconds: 200, // deadline = 300
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log);
         });
@@ -1298,6 +1368,7 @@ mod tests {
                 timeout_seconds: 50,
                 error: None,
                 metadata: Vec::new(&env),
+                savepoint: None,
             };
             set_transaction_log(&env, &log);
         });
@@ -0,0 +1,158 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::{OrchestratorContract, OrchestratorContractClient};
+use common::transaction::{
+    ContractType, LockMode, TransactionError, TransactionOperation, TransactionPhase,
+    TransactionStatus, RESOURCE_LOCKS,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, vec, Address, Env, String,
+    Symbol, Vec,
+};
+
+const CALLS: Symbol = symbol_short!("CALLS");
+const READY: Symbol = symbol_short!("READY");
+
+/// Participant whose `commit_("flaky")` traps until `set_ready` is called.
+#[contract]
+pub struct Flaky;
+
+#[contractimpl]
+impl Flaky {
+    pub fn set_ready(env: Env) {
+        env.storage().instance().set(&READY, &true);
+    }
+
+    pub fn prepare_(env: Env, tag: String) {
+        Self::record(&env, symbol_short!("prepare"), tag);
+    }
+
+    pub fn commit_(env: Env, tag: String) {
+        let ready = env.storage().instance().get(&READY).unwrap_or(false);
+        if tag == String::from_str(&env, "flaky") && !ready {
+            panic!("not ready");
+        }
+        Self::record(&env, symbol_short!("commit"), tag);
+    }
+
+    pub fn rollback_(env: Env, tag: String) {
+        Self::record(&env, symbol_short!("rollback"), tag);
+    }
+
+    fn record(env: &Env, hook: Symbol, tag: String) {
+        let mut calls: Vec<(Symbol, String)> = env
+            .storage()
+            .instance()
+            .get(&CALLS)
+            .unwrap_or(Vec::new(env));
+        calls.push_back((hook, tag));
+        env.storage().instance().set(&CALLS, &calls);
+    }
+}
+
+fn op(env: &Env, participant: &Address, operation_id: u64, tag: &str) -> TransactionOperation {
+    TransactionOperation {
+        operation_id,
+        contract_type: ContractType::VisionRecords,
+        contract_address: participant.clone(),
+        function_name: String::from_str(env, "update"),
+        parameters: vec![env, String::from_str(env, tag)],
+        locked_resources: vec![env, String::from_str(env, tag)],
+        lock_mode: LockMode::Exclusive,
+        prepared: false,
+        committed: false,
+        error: None,
+        sub_transaction: None,
+        prepare_hook: None,
+        commit_hook: None,
+        rollback_hook: None,
+    }
+}
+
+fn count(env: &Env, calls: &Vec<(Symbol, String)>, hook: Symbol, tag: &str) -> u32 {
+    let call = (hook, String::from_str(env, tag));
+    calls.iter().filter(|c| *c == call).count() as u32
+}
+
+fn locks_held(env: &Env, orchestrator: &Address) -> u32 {
+    env.as_contract(orchestrator, || {
+        env.storage()
+            .instance()
+            .get::<_, Vec<(String, u64)>>(&RESOURCE_LOCKS)
+            .map_or(0, |locks| locks.len())
+    })
+}
+
+#[test]
+fn test_resume_continues_from_savepoint() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let orchestrator = env.register(OrchestratorContract, ());
+    let client = OrchestratorContractClient::new(&env, &orchestrator);
+    let participant = env.register(Flaky, ());
+    let flaky = FlakyClient::new(&env, &participant);
+
+    let operations = vec![
+        &env,
+        op(&env, &participant, 1, "a"),
+        op(&env, &participant, 2, "b"),
+        op(&env, &participant, 3, "flaky"),
+        op(&env, &participant, 4, "d"),
+    ];
+
+    client.initialize(&admin, &None);
+    let transaction_id = client.start_resumable_transaction(
+        &Address::generate(&env),
+        &operations,
+        &None,
+        &Vec::new(&env),
+    );
+
+    // The suspension survives the call: ops 0-1 stay committed and the
+    // transaction keeps its locks.
+    let log = client.get_transaction(&transaction_id);
+    assert_eq!(log.savepoint, Some(1));
+    assert_eq!(log.phase, TransactionPhase::Prepared);
+    assert_eq!(log.status, TransactionStatus::Suspended);
+    assert_eq!(locks_held(&env, &orchestrator), 4);
+
+    // Still failing: the resume succeeds as a call and stays suspended.
+    assert_eq!(
+        client.resume_transaction(&admin, &transaction_id),
+        TransactionStatus::Suspended
+    );
+    assert_eq!(client.get_transaction(&transaction_id).savepoint, Some(1));
+
+    flaky.set_ready();
+
+    assert_eq!(
+        client.try_resume_transaction(&Address::generate(&env), &transaction_id),
+        Err(Ok(TransactionError::Unauthorized))
+    );
+    assert_eq!(
+        client.resume_transaction(&admin, &transaction_id),
+        TransactionStatus::Completed
+    );
+
+    let log = client.get_transaction(&transaction_id);
+    assert_eq!(log.phase, TransactionPhase::Committed);
+    assert_eq!(log.status, TransactionStatus::Completed);
+    assert_eq!(log.savepoint, Some(3));
+    assert_eq!(locks_held(&env, &orchestrator), 0);
+
+    // Nothing left to resume.
+    assert_eq!(
+        client.try_resume_transaction(&admin, &transaction_id),
+        Err(Ok(TransactionError::InvalidPhase))
+    );
+
+    let calls: Vec<(Symbol, String)> = env.as_contract(&participant, || {
+        env.storage().instance().get(&CALLS).unwrap()
+    });
+    for tag in ["a", "b", "flaky", "d"] {
+        assert_eq!(count(&env, &calls, symbol_short!("prepare"), tag), 1);
+        assert_eq!(count(&env, &calls, symbol_short!("commit"), tag), 1);
+        assert_eq!(count(&env, &calls, symbol_short!("rollback"), tag), 0);
+    }
+}
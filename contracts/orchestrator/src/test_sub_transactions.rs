@@ -0,0 +1,399 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::OrchestratorContract;
+use common::transaction::{
+    get_transaction_log, AtomicityMode, ContractType, LockMode, TransactionError,
+    TransactionOperation, TransactionPhase, TransactionStatus, RESOURCE_LOCKS, TRANSACTION_COUNTER,
+};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, symbol_short, testutils::Address as _, vec, Address,
+    Env, String, Symbol, Vec,
+};
+
+const CALLS: Symbol = symbol_short!("CALLS");
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ParticipantError {
+    Rejected = 1,
+}
+
+/// Participant that records every hook call and rejects `prepare_("fail")`.
+#[contract]
+pub struct Participant;
+
+#[contractimpl]
+impl Participant {
+    pub fn prepare_(env: Env, tag: String) -> Result<(), ParticipantError> {
+        if tag == String::from_str(&env, "fail") {
+            return Err(ParticipantError::Rejected);
+        }
+        Self::record(&env, symbol_short!("prepare"), tag);
+        Ok(())
+    }
+
+    pub fn commit_(env: Env, tag: String) {
+        Self::record(&env, symbol_short!("commit"), tag);
+    }
+
+    pub fn rollback_(env: Env, tag: String) {
+        Self::record(&env, symbol_short!("rollback"), tag);
+    }
+
+    fn record(env: &Env, hook: Symbol, tag: String) {
+        let mut calls: Vec<(Symbol, String)> = env
+            .storage()
+            .instance()
+            .get(&CALLS)
+            .unwrap_or(Vec::new(env));
+        calls.push_back((hook, tag));
+        env.storage().instance().set(&CALLS, &calls);
+    }
+}
+
+struct Setup {
+    env: Env,
+    orchestrator: Address,
+    participant: Address,
+    initiator: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let orchestrator = env.register(OrchestratorContract, ());
+    let participant = env.register(Participant, ());
+    env.as_contract(&orchestrator, || {
+        OrchestratorContract::initialize(env.clone(), Address::generate(&env), None).unwrap();
+    });
+    let initiator = Address::generate(&env);
+    Setup {
+        env,
+        orchestrator,
+        participant,
+        initiator,
+    }
+}
+
+fn call_op(s: &Setup, operation_id: u64, tag: &str) -> TransactionOperation {
+    TransactionOperation {
+        operation_id,
+        contract_type: ContractType::VisionRecords,
+        contract_address: s.participant.clone(),
+        function_name: String::from_str(&s.env, "update"),
+        parameters: vec![&s.env, String::from_str(&s.env, tag)],
+        locked_resources: vec![&s.env, String::from_str(&s.env, "patient:1")],
+        lock_mode: LockMode::Exclusive,
+        prepared: false,
+        committed: false,
+        error: None,
+        sub_transaction: None,
+        prepare_hook: None,
+        commit_hook: None,
+        rollback_hook: None,
+    }
+}
+
+fn sub_op(s: &Setup, operation_id: u64, child: u64) -> TransactionOperation {
+    TransactionOperation {
+        sub_transaction: Some(child),
+        parameters: Vec::new(&s.env),
+        ..call_op(s, operation_id, "")
+    }
+}
+
+fn compose(
+    s: &Setup,
+    operations: Vec<TransactionOperation>,
+    atomicity: AtomicityMode,
+) -> Result<u64, TransactionError> {
+    s.env.as_contract(&s.orchestrator, || {
+        OrchestratorContract::compose_transaction(
+            s.env.clone(),
+            s.initiator.clone(),
+            operations,
+            atomicity,
+            None,
+        )
+    })
+}
+
+fn start(s: &Setup, operations: Vec<TransactionOperation>) -> Result<u64, TransactionError> {
+    s.env.as_contract(&s.orchestrator, || {
+        OrchestratorContract::start_transaction(
+            s.env.clone(),
+            s.initiator.clone(),
+            operations,
+            None,
+            Vec::new(&s.env),
+        )
+    })
+}
+
+fn phase_and_status(s: &Setup, transaction_id: u64) -> (TransactionPhase, TransactionStatus) {
+    s.env.as_contract(&s.orchestrator, || {
+        let log = get_transaction_log(&s.env, transaction_id).unwrap();
+        (log.phase, log.status)
+    })
+}
+
+fn calls(s: &Setup) -> Vec<(Symbol, String)> {
+    s.env.as_contract(&s.participant, || {
+        s.env
+            .storage()
+            .instance()
+            .get(&CALLS)
+            .unwrap_or(Vec::new(&s.env))
+    })
+}
+
+fn next_id(s: &Setup) -> u64 {
+    s.env.as_contract(&s.orchestrator, || {
+        s.env
+            .storage()
+            .instance()
+            .get::<_, u64>(&TRANSACTION_COUNTER)
+            .unwrap()
+            + 1
+    })
+}
+
+fn call(s: &Setup, hook: Symbol, tag: &str) -> (Symbol, String) {
+    (hook, String::from_str(&s.env, tag))
+}
+
+#[test]
+fn test_failing_sub_transaction_rolls_back_parent_all_or_nothing() {
+    let s = setup();
+    let child = compose(
+        &s,
+        vec![&s.env, call_op(&s, 1, "child"), call_op(&s, 2, "fail")],
+        AtomicityMode::AllOrNothing,
+    )
+    .unwrap();
+
+    let result = start(
+        &s,
+        vec![&s.env, call_op(&s, 1, "parent"), sub_op(&s, 2, child)],
+    );
+    assert_eq!(result, Err(TransactionError::ContractCallFailed));
+
+    let parent = child + 1;
+    let failed = (TransactionPhase::RolledBack, TransactionStatus::Failed);
+    assert_eq!(phase_and_status(&s, parent), failed);
+    assert_eq!(phase_and_status(&s, child), failed);
+
+    // Everything prepared is undone innermost first; nothing is committed.
+    assert_eq!(
+        calls(&s),
+        vec![
+            &s.env,
+            call(&s, symbol_short!("prepare"), "parent"),
+            call(&s, symbol_short!("prepare"), "child"),
+            call(&s, symbol_short!("rollback"), "child"),
+            call(&s, symbol_short!("rollback"), "parent"),
+        ]
+    );
+
+    let locks: Vec<(String, u64)> = s.env.as_contract(&s.orchestrator, || {
+        s.env.storage().instance().get(&RESOURCE_LOCKS).unwrap()
+    });
+    assert!(locks.is_empty());
+}
+
+#[test]
+fn test_best_effort_sub_transaction_failure_lets_parent_commit() {
+    let s = setup();
+    let child = compose(
+        &s,
+        vec![&s.env, call_op(&s, 1, "child"), call_op(&s, 2, "fail")],
+        AtomicityMode::BestEffort,
+    )
+    .unwrap();
+
+    let parent = start(
+        &s,
+        vec![&s.env, call_op(&s, 1, "parent"), sub_op(&s, 2, child)],
+    )
+    .unwrap();
+
+    assert_eq!(
+        phase_and_status(&s, parent),
+        (TransactionPhase::Committed, TransactionStatus::Completed)
+    );
+    assert_eq!(
+        phase_and_status(&s, child),
+        (TransactionPhase::RolledBack, TransactionStatus::Failed)
+    );
+    let failed_op = s.env.as_contract(&s.orchestrator, || {
+        get_transaction_log(&s.env, parent)
+            .unwrap()
+            .operations
+            .get(1)
+            .unwrap()
+    });
+    assert!(failed_op.error.is_some());
+    assert!(!failed_op.committed);
+
+    assert_eq!(
+        calls(&s),
+        vec![
+            &s.env,
+            call(&s, symbol_short!("prepare"), "parent"),
+            call(&s, symbol_short!("prepare"), "child"),
+            call(&s, symbol_short!("rollback"), "child"),
+            call(&s, symbol_short!("commit"), "parent"),
+        ]
+    );
+}
+
+#[test]
+fn test_nested_tree_commits_and_shares_locks() {
+    let s = setup();
+    // Every operation locks the same resource; within one tree that must
+    // not count as a conflict.
+    let grandchild = compose(
+        &s,
+        vec![&s.env, call_op(&s, 1, "grandchild")],
+        AtomicityMode::AllOrNothing,
+    )
+    .unwrap();
+    let child = compose(
+        &s,
+        vec![&s.env, call_op(&s, 1, "child"), sub_op(&s, 2, grandchild)],
+        AtomicityMode::AllOrNothing,
+    )
+    .unwrap();
+    let parent = start(
+        &s,
+        vec![&s.env, sub_op(&s, 1, child), call_op(&s, 2, "parent")],
+    )
+    .unwrap();
+
+    let tree = s.env.as_contract(&s.orchestrator, || {
+        OrchestratorContract::get_transaction_tree(s.env.clone(), parent).unwrap()
+    });
+    assert_eq!(tree.len(), 3);
+    let expected = [
+        (parent, None, 0),
+        (child, Some(parent), 1),
+        (grandchild, Some(child), 2),
+    ];
+    for (i, (id, parent_id, depth)) in expected.into_iter().enumerate() {
+        let node = tree.get(i as u32).unwrap();
+        assert_eq!(node.transaction_id, id);
+        assert_eq!(node.parent, parent_id);
+        assert_eq!(node.depth, depth);
+        assert_eq!(node.phase, TransactionPhase::Committed);
+    }
+
+    let commits: Vec<(Symbol, String)> = calls(&s)
+        .iter()
+        .filter(|(hook, _)| *hook == symbol_short!("commit"))
+        .fold(Vec::new(&s.env), |mut v, c| {
+            v.push_back(c);
+            v
+        });
+    assert_eq!(
+        commits,
+        vec![
+            &s.env,
+            call(&s, symbol_short!("commit"), "child"),
+            call(&s, symbol_short!("commit"), "grandchild"),
+            call(&s, symbol_short!("commit"), "parent"),
+        ]
+    );
+}
+
+#[test]
+fn test_composition_cycle_rejected() {
+    let s = setup();
+
+    // A transaction referencing its own (next) id is a cycle.
+    let result = compose(
+        &s,
+        vec![&s.env, sub_op(&s, 1, next_id(&s))],
+        AtomicityMode::AllOrNothing,
+    );
+    assert_eq!(result, Err(TransactionError::CompositionCycle));
+    assert_eq!(
+        start(&s, vec![&s.env, sub_op(&s, 1, next_id(&s))]),
+        Err(TransactionError::CompositionCycle)
+    );
+
+    // A sub-transaction can belong to only one parent.
+    let child = compose(
+        &s,
+        vec![&s.env, call_op(&s, 1, "child")],
+        AtomicityMode::AllOrNothing,
+    )
+    .unwrap();
+    compose(
+        &s,
+        vec![&s.env, sub_op(&s, 1, child)],
+        AtomicityMode::AllOrNothing,
+    )
+    .unwrap();
+    assert_eq!(
+        start(&s, vec![&s.env, sub_op(&s, 1, child)]),
+        Err(TransactionError::InvalidInput)
+    );
+
+    assert_eq!(
+        start(&s, vec![&s.env, sub_op(&s, 1, 999)]),
+        Err(TransactionError::TransactionNotFound)
+    );
+}
+
+#[test]
+fn test_sub_transaction_is_bound_to_its_initiator() {
+    let s = setup();
+    let child = compose(
+        &s,
+        vec![&s.env, call_op(&s, 1, "child")],
+        AtomicityMode::AllOrNothing,
+    )
+    .unwrap();
+
+    let other = Address::generate(&s.env);
+    let claimed = s.env.as_contract(&s.orchestrator, || {
+        OrchestratorContract::start_transaction(
+            s.env.clone(),
+            other.clone(),
+            vec![&s.env, sub_op(&s, 1, child)],
+            None,
+            Vec::new(&s.env),
+        )
+    });
+    assert_eq!(claimed, Err(TransactionError::Unauthorized));
+    let composed = s.env.as_contract(&s.orchestrator, || {
+        OrchestratorContract::compose_transaction(
+            s.env.clone(),
+            other.clone(),
+            vec![&s.env, sub_op(&s, 1, child)],
+            AtomicityMode::AllOrNothing,
+            None,
+        )
+    });
+    assert_eq!(composed, Err(TransactionError::Unauthorized));
+
+    // The initiator that registered it can still claim it.
+    let parent = start(&s, vec![&s.env, sub_op(&s, 1, child)]).unwrap();
+    assert_eq!(
+        phase_and_status(&s, parent),
+        (TransactionPhase::Committed, TransactionStatus::Completed)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_compose_transaction_requires_initiator_auth() {
+    let s = setup();
+    s.env.set_auths(&[]);
+    let _ = compose(
+        &s,
+        vec![&s.env, call_op(&s, 1, "child")],
+        AtomicityMode::AllOrNothing,
+    );
+}
@@ -0,0 +1,161 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::OrchestratorContract;
+use common::transaction::{
+    get_transaction_log, ContractType, LockMode, TransactionError, TransactionOperation,
+    TransactionPhase, TransactionStatus,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, vec, Address, Env, String,
+    Symbol, Vec,
+};
+
+const CALLS: Symbol = symbol_short!("CALLS");
+
+fn record(env: &Env, hook: Symbol) {
+    let mut calls: Vec<Symbol> = env
+        .storage()
+        .instance()
+        .get(&CALLS)
+        .unwrap_or(Vec::new(env));
+    calls.push_back(hook);
+    env.storage().instance().set(&CALLS, &calls);
+}
+
+/// Stock participant with its own hook names.
+#[contract]
+pub struct Inventory;
+
+#[contractimpl]
+impl Inventory {
+    pub fn reserve(env: Env, _sku: String) {
+        record(&env, symbol_short!("reserve"));
+    }
+
+    pub fn confirm(env: Env, _sku: String) {
+        record(&env, symbol_short!("confirm"));
+    }
+
+    pub fn release(env: Env, _sku: String) {
+        record(&env, symbol_short!("release"));
+    }
+}
+
+/// Payment participant whose commit hook traps.
+#[contract]
+pub struct Billing;
+
+#[contractimpl]
+impl Billing {
+    pub fn hold(env: Env, _invoice: String) {
+        record(&env, symbol_short!("hold"));
+    }
+
+    pub fn capture(_env: Env, _invoice: String) {
+        panic!("capture rejected");
+    }
+
+    pub fn refund(env: Env, _invoice: String) {
+        record(&env, symbol_short!("refund"));
+    }
+}
+
+fn operation(
+    env: &Env,
+    operation_id: u64,
+    contract_address: &Address,
+    hooks: (Symbol, Symbol, Symbol),
+) -> TransactionOperation {
+    TransactionOperation {
+        operation_id,
+        contract_type: ContractType::VisionRecords,
+        contract_address: contract_address.clone(),
+        function_name: String::from_str(env, "dispense"),
+        parameters: vec![env, String::from_str(env, "order:7")],
+        locked_resources: vec![env, String::from_str(env, "order:7")],
+        lock_mode: LockMode::Exclusive,
+        prepared: false,
+        committed: false,
+        error: None,
+        sub_transaction: None,
+        prepare_hook: Some(hooks.0),
+        commit_hook: Some(hooks.1),
+        rollback_hook: Some(hooks.2),
+    }
+}
+
+fn calls(env: &Env, contract: &Address) -> Vec<Symbol> {
+    env.as_contract(contract, || {
+        env.storage()
+            .instance()
+            .get(&CALLS)
+            .unwrap_or(Vec::new(env))
+    })
+}
+
+#[test]
+fn test_failed_commit_rolls_back_every_participant() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let orchestrator = env.register(OrchestratorContract, ());
+    let inventory = env.register(Inventory, ());
+    let billing = env.register(Billing, ());
+
+    let operations = vec![
+        &env,
+        operation(
+            &env,
+            1,
+            &inventory,
+            (
+                symbol_short!("reserve"),
+                symbol_short!("confirm"),
+                symbol_short!("release"),
+            ),
+        ),
+        operation(
+            &env,
+            2,
+            &billing,
+            (
+                symbol_short!("hold"),
+                symbol_short!("capture"),
+                symbol_short!("refund"),
+            ),
+        ),
+    ];
+
+    env.as_contract(&orchestrator, || {
+        OrchestratorContract::initialize(env.clone(), Address::generate(&env), None).unwrap();
+        let result = OrchestratorContract::start_transaction(
+            env.clone(),
+            Address::generate(&env),
+            operations,
+            None,
+            Vec::new(&env),
+        );
+        assert_eq!(result, Err(TransactionError::ContractCallFailed));
+
+        let log = get_transaction_log(&env, 1).unwrap();
+        assert_eq!(log.phase, TransactionPhase::RolledBack);
+        assert_eq!(log.status, TransactionStatus::Failed);
+        // Inventory had already committed when billing trapped.
+        assert!(log.operations.get(0).unwrap().committed);
+        assert!(!log.operations.get(1).unwrap().committed);
+    });
+
+    // Both participants are compensated through their own rollback hooks.
+    assert_eq!(
+        calls(&env, &inventory),
+        vec![
+            &env,
+            symbol_short!("reserve"),
+            symbol_short!("confirm"),
+            symbol_short!("release"),
+        ]
+    );
+    assert_eq!(
+        calls(&env, &billing),
+        vec![&env, symbol_short!("hold"), symbol_short!("refund")]
+    );
+}
@@ -1,4 +1,4 @@
-use common::transaction::{TransactionError, TransactionLog, TransactionPhase, TransactionStatus, TransactionOperation, ContractType, get_transaction_log, set_transaction_log};
+use common::transaction::{TransactionError, TransactionLog, TransactionPhase, TransactionStatus, TransactionOperation, ContractType, LockMode, get_transaction_log, set_transaction_log};
 use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, Vec};
 use crate::OrchestratorContract;
 
@@ -26,6 +26,7 @@ fn test_invalid_state_transition_committed_to_rolled_back() {
             timeout_seconds: 300,
             error: None,
             metadata: Vec::new(&env),
+            savepoint: None,
         };
         set_transaction_log(&env, &log);
 
@@ -54,6 +55,7 @@ fn test_recovery_from_failed_state() {
             timeout_seconds: 300,
             error: Some(String::from_str(&env, "Initial failure")),
             metadata: Vec::new(&env),
+            savepoint: None,
         };
         set_transaction_log(&env, &log);
 
@@ -67,9 +69,9 @@ fn test_recovery_from_failed_state() {
 }
 
 #[test]
-#[should_panic]
-fn test_pharmaceutical_supply_chain_workflow_panics_on_missing_contract() {
+fn test_pharmaceutical_supply_chain_workflow_rolls_back_on_missing_contract() {
     let env = Env::default();
+    env.mock_all_auths();
     let admin = Address::generate(&env);
     let initiator = Address::generate(&env);
     let contract_id = register_orchestrator(&env);
@@ -85,18 +87,28 @@ fn test_pharmaceutical_supply_chain_workflow_panics_on_missing_contract() {
             function_name: String::from_str(&env, "record_batch"),
             parameters: Vec::new(&env),
             locked_resources: vec![&env, String::from_str(&env, "batch_vial_001")],
+            lock_mode: LockMode::Exclusive,
             prepared: false,
             committed: false,
             error: None,
+            sub_transaction: None,
+            prepare_hook: None,
+            commit_hook: None,
+            rollback_hook: None,
         });
 
-        // This will panic inside invoke_contract because contract_address is not registered
-        let _ = OrchestratorContract::start_transaction(
+        // The prepare hook cannot be called because contract_address is not registered
+        let result = OrchestratorContract::start_transaction(
             env.clone(),
             initiator.clone(),
             operations,
             Some(600),
             vec![&env, String::from_str(&env, "pharma_supply_chain")],
         );
+        assert_eq!(result, Err(TransactionError::ContractCallFailed));
+
+        let log = get_transaction_log(&env, 1).unwrap();
+        assert_eq!(log.phase, TransactionPhase::RolledBack);
+        assert_eq!(log.status, TransactionStatus::Failed);
     });
 }
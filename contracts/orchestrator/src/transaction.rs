@@ -1,10 +1,58 @@
 use common::transaction::{
-    get_transaction_log, set_transaction_log, TransactionError, TransactionLog,
-    TransactionOperation, TransactionPhase,
+    get_transaction_log, set_transaction_log, AtomicityMode, TransactionError, TransactionLog,
+    TransactionOperation, TransactionPhase, TransactionStatus,
 };
-use soroban_sdk::{Env, IntoVal, String, Symbol, Val, Vec};
+use soroban_sdk::{Env, Error, IntoVal, String, Symbol, Val, Vec};
 
+use super::composition::CompositionManager;
 use super::events::EventPublisher;
+use super::rollback::RollbackManager;
+
+/// Phase of the two-phase commit a participant hook is called for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Hook {
+    Prepare,
+    Commit,
+    Rollback,
+}
+
+impl Hook {
+    /// Entry point to call for this phase: the operation's own hook if it
+    /// names one, otherwise `prepare_`, `commit_` or `rollback_`.
+    pub(crate) fn symbol(self, env: &Env, operation: &TransactionOperation) -> Symbol {
+        let (custom, default) = match self {
+            Hook::Prepare => (&operation.prepare_hook, "prepare_"),
+            Hook::Commit => (&operation.commit_hook, "commit_"),
+            Hook::Rollback => (&operation.rollback_hook, "rollback_"),
+        };
+        custom.clone().unwrap_or_else(|| Symbol::new(env, default))
+    }
+}
+
+/// Invoke the `hook` entry point of the operation's target contract with its
+/// parameters.
+///
+/// A trap or error in the target is reported as
+/// [`TransactionError::ContractCallFailed`] so the caller can roll the
+/// transaction back instead of aborting the whole invocation.
+pub(crate) fn invoke_hook(
+    env: &Env,
+    operation: &TransactionOperation,
+    hook: Hook,
+) -> Result<(), TransactionError> {
+    let func_sym = hook.symbol(env, operation);
+
+    let mut args: Vec<Val> = Vec::new(env);
+    for j in 0..operation.parameters.len() {
+        let param = operation.parameters.get(j).unwrap();
+        args.push_back(param.into_val(env));
+    }
+
+    match env.try_invoke_contract::<Val, Error>(&operation.contract_address, &func_sym, args) {
+        Ok(Ok(_)) => Ok(()),
+        _ => Err(TransactionError::ContractCallFailed),
+    }
+}
 
 /// Transaction manager for handling two-phase commit protocol
 pub struct TransactionManager<'a> {
@@ -16,9 +64,13 @@ impl<'a> TransactionManager<'a> {
         Self { env }
     }
 
-    /// Prepare phase: call prepare_* functions on all participating contracts.
-    /// Each operation's `function_name` should be the base name (e.g., "stake").
-    /// This method will invoke `prepare_<function_name>` on each target contract.
+    /// Prepare phase: call the prepare hook of every participating contract.
+    /// The hook is the operation's `prepare_hook`, or `prepare_` when unset.
+    ///
+    /// Operations that reference a sub-transaction prepare that transaction
+    /// instead. If it fails, its atomicity mode decides whether this prepare
+    /// fails too (`AllOrNothing`) or the operation is marked failed and
+    /// skipped (`BestEffort`).
     pub fn prepare_phase(&self, log: &mut TransactionLog) -> Result<(), TransactionError> {
         log.phase = TransactionPhase::Preparing;
         set_transaction_log(self.env, log);
@@ -28,29 +80,16 @@ impl<'a> TransactionManager<'a> {
         for i in 0..log.operations.len() {
             let mut operation = log.operations.get(i).unwrap().clone();
 
-            // Build the prepare function symbol and invoke
-            let func_sym = Symbol::new(self.env, "prepare_");
-
-            let mut args: Vec<Val> = Vec::new(self.env);
-            for j in 0..operation.parameters.len() {
-                let param = operation.parameters.get(j).unwrap();
-                args.push_back(param.into_val(self.env));
+            if let Err(e) = self.prepare_operation(log.transaction_id, &mut operation) {
+                // Keep what was prepared so the caller's rollback can undo it.
+                for j in i..log.operations.len() {
+                    prepared_operations.push_back(log.operations.get(j).unwrap());
+                }
+                log.operations = prepared_operations;
+                set_transaction_log(self.env, log);
+                return Err(e);
             }
-
-            // invoke_contract panics on failure; Soroban runtime catches it
-            // For orchestrated transactions, the caller should handle panics
-            let _result: Val =
-                self.env
-                    .invoke_contract(&operation.contract_address, &func_sym, args);
-
-            operation.prepared = true;
-            prepared_operations.push_back(operation.clone());
-            EventPublisher::operation_prepared(
-                self.env,
-                log.transaction_id,
-                operation.operation_id,
-                &operation.contract_type,
-            );
+            prepared_operations.push_back(operation);
         }
 
         log.operations = prepared_operations;
@@ -62,7 +101,10 @@ impl<'a> TransactionManager<'a> {
         Ok(())
     }
 
-    /// Commit phase: call commit_* functions on all prepared contracts
+    /// Commit phase: call the commit hook of every prepared contract.
+    ///
+    /// Operations that already committed are skipped, so a resumed
+    /// transaction continues after its savepoint.
     pub fn commit_phase(&self, log: &mut TransactionLog) -> Result<(), TransactionError> {
         if log.phase != TransactionPhase::Prepared {
             return Err(TransactionError::InvalidPhase);
@@ -73,36 +115,162 @@ impl<'a> TransactionManager<'a> {
         for i in 0..log.operations.len() {
             let mut operation = log.operations.get(i).unwrap().clone();
 
-            if !operation.prepared {
-                return Err(TransactionError::InvalidPhase);
+            if let Err(e) = self.commit_operation(log.transaction_id, &mut operation) {
+                for j in i..log.operations.len() {
+                    committed_operations.push_back(log.operations.get(j).unwrap());
+                }
+                log.operations = committed_operations;
+                set_transaction_log(self.env, log);
+                return Err(e);
+            }
+            if operation.committed {
+                log.savepoint = Some(i);
             }
+            committed_operations.push_back(operation);
+        }
 
-            let func_sym = Symbol::new(self.env, "commit_");
+        log.operations = committed_operations;
+        log.updated_at = self.env.ledger().timestamp();
+        set_transaction_log(self.env, log);
+
+        Ok(())
+    }
 
-            let mut args: Vec<Val> = Vec::new(self.env);
-            for j in 0..operation.parameters.len() {
-                let param = operation.parameters.get(j).unwrap();
-                args.push_back(param.into_val(self.env));
+    /// Prepare one operation, either a contract call or a sub-transaction.
+    fn prepare_operation(
+        &self,
+        transaction_id: u64,
+        operation: &mut TransactionOperation,
+    ) -> Result<(), TransactionError> {
+        match operation.sub_transaction {
+            Some(child) => match self.prepare_sub_transaction(child) {
+                Ok(()) => operation.prepared = true,
+                Err(e)
+                    if CompositionManager::new(self.env).atomicity(child)
+                        == AtomicityMode::AllOrNothing =>
+                {
+                    return Err(e);
+                }
+                Err(_) => {
+                    let error = String::from_str(self.env, "Sub-transaction failed");
+                    EventPublisher::operation_failed(
+                        self.env,
+                        transaction_id,
+                        operation.operation_id,
+                        &operation.contract_type,
+                        &error,
+                    );
+                    operation.error = Some(error);
+                    return Ok(());
+                }
+            },
+            None => {
+                invoke_hook(self.env, operation, Hook::Prepare)?;
+                operation.prepared = true;
             }
+        }
 
-            let _result: Val =
-                self.env
-                    .invoke_contract(&operation.contract_address, &func_sym, args);
+        EventPublisher::operation_prepared(
+            self.env,
+            transaction_id,
+            operation.operation_id,
+            &operation.contract_type,
+        );
+        Ok(())
+    }
 
-            operation.committed = true;
-            committed_operations.push_back(operation.clone());
-            EventPublisher::operation_committed(
-                self.env,
-                log.transaction_id,
-                operation.operation_id,
-                &operation.contract_type,
-            );
+    /// Commit one prepared operation; best-effort failures are skipped.
+    fn commit_operation(
+        &self,
+        transaction_id: u64,
+        operation: &mut TransactionOperation,
+    ) -> Result<(), TransactionError> {
+        if operation.error.is_some() || operation.committed {
+            return Ok(());
+        }
+        if !operation.prepared {
+            return Err(TransactionError::InvalidPhase);
         }
 
-        log.operations = committed_operations;
+        match operation.sub_transaction {
+            Some(child) => self.commit_sub_transaction(child)?,
+            None => invoke_hook(self.env, operation, Hook::Commit)?,
+        }
+
+        operation.committed = true;
+        EventPublisher::operation_committed(
+            self.env,
+            transaction_id,
+            operation.operation_id,
+            &operation.contract_type,
+        );
+        Ok(())
+    }
+
+    /// Prepare every operation of a sub-transaction. On failure the
+    /// sub-transaction rolls back what it prepared and is marked rolled back.
+    fn prepare_sub_transaction(&self, transaction_id: u64) -> Result<(), TransactionError> {
+        let mut log = get_transaction_log(self.env, transaction_id)
+            .ok_or(TransactionError::TransactionNotFound)?;
+        if log.phase != TransactionPhase::Preparing || log.status != TransactionStatus::Active {
+            return Err(TransactionError::InvalidPhase);
+        }
+
+        let mut operations: Vec<TransactionOperation> = Vec::new(self.env);
+        for i in 0..log.operations.len() {
+            let mut operation = log.operations.get(i).unwrap();
+            if let Err(e) = self.prepare_operation(transaction_id, &mut operation) {
+                for j in i..log.operations.len() {
+                    operations.push_back(log.operations.get(j).unwrap());
+                }
+                log.operations = operations;
+                log.error = Some(String::from_str(
+                    self.env,
+                    "Sub-transaction prepare failed; rolled back",
+                ));
+                set_transaction_log(self.env, &log);
+                let _ = RollbackManager::new(self.env).rollback_sub_transaction(transaction_id);
+                return Err(e);
+            }
+            operations.push_back(operation);
+        }
+
+        log.operations = operations;
+        log.phase = TransactionPhase::Prepared;
         log.updated_at = self.env.ledger().timestamp();
-        set_transaction_log(self.env, log);
+        set_transaction_log(self.env, &log);
+        EventPublisher::transaction_prepared(self.env, &log);
+        Ok(())
+    }
+
+    /// Commit every prepared operation of a sub-transaction.
+    fn commit_sub_transaction(&self, transaction_id: u64) -> Result<(), TransactionError> {
+        let mut log = get_transaction_log(self.env, transaction_id)
+            .ok_or(TransactionError::TransactionNotFound)?;
+        if log.phase != TransactionPhase::Prepared {
+            return Err(TransactionError::InvalidPhase);
+        }
+
+        let mut operations: Vec<TransactionOperation> = Vec::new(self.env);
+        for i in 0..log.operations.len() {
+            let mut operation = log.operations.get(i).unwrap();
+            if let Err(e) = self.commit_operation(transaction_id, &mut operation) {
+                for j in i..log.operations.len() {
+                    operations.push_back(log.operations.get(j).unwrap());
+                }
+                log.operations = operations;
+                set_transaction_log(self.env, &log);
+                return Err(e);
+            }
+            operations.push_back(operation);
+        }
 
+        log.operations = operations;
+        log.phase = TransactionPhase::Committed;
+        log.status = TransactionStatus::Completed;
+        log.updated_at = self.env.ledger().timestamp();
+        set_transaction_log(self.env, &log);
+        EventPublisher::transaction_committed(self.env, &log);
         Ok(())
     }
 
@@ -28,6 +28,7 @@ pub struct BatchRecordInput {
 trait VisionRecordsInterface {
     fn add_records(
         env: Env,
+        caller: Address,
         provider: Address,
         records: soroban_sdk::Vec<BatchRecordInput>,
     ) -> Result<soroban_sdk::Vec<u64>, common::CommonError>;
@@ -67,7 +68,7 @@ pub fn settle(env: &Env, channel_id: u64) -> Result<(), CommonError> {
     // records.push_back(BatchRecordInput { ... });
 
     // Ignore result for mock settlement
-    let _ = client.add_records(&channel.provider, &records);
+    let _ = client.add_records(&channel.provider, &channel.provider, &records);
 
     channel.status = ChannelStatus::Closed;
     env.storage().persistent().set(&key, &channel);
@@ -12,6 +12,7 @@ pub struct MockVisionRecords;
 impl MockVisionRecords {
     pub fn add_records(
         _env: Env,
+        _caller: Address,
         _provider: Address,
         _records: Vec<BatchRecordInput>,
     ) -> Result<Vec<u64>, common::CommonError> {
@@ -15,8 +15,8 @@ use common::{VaultPolicy, VaultRecord, VaultShare};
 use identity::IdentityContractClient;
 use key_rotation::RotationEvent;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
-    String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env,
+    Map, String, Symbol, Vec,
 };
 
 const ADMIN: Symbol = symbol_short!("ADMIN");
@@ -25,6 +25,7 @@ const INIT: Symbol = symbol_short!("INIT");
 const VAULT: Symbol = symbol_short!("VAULT");
 const SHARE: Symbol = symbol_short!("SHARE");
 const APR: Symbol = symbol_short!("EM_APR");
+const COMMIT: Symbol = symbol_short!("SHR_CMT");
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -39,6 +40,8 @@ pub enum VaultError {
     InvalidShare = 7,
     EmergencyThresholdNotMet = 8,
     DeadmanNotReady = 9,
+    ShareVerificationFailed = 10,
+    TimeLocked = 11,
 }
 
 #[contracttype]
@@ -106,6 +109,8 @@ impl VaultContract {
             shard_count,
             emergency_threshold,
             inactivity_timeout_secs,
+            not_before: 0,
+            require_guardian: false,
         };
 
         let now = env.ledger().timestamp();
@@ -138,9 +143,7 @@ impl VaultContract {
                 x: share.x as u32,
                 y: BytesN::from_array(&env, &share.y),
             };
-            env.storage()
-                .persistent()
-                .set(&(SHARE, owner.clone(), record.epoch, guardian.clone()), &s);
+            Self::store_share(&env, &owner, record.epoch, &s);
             holders.push_back(guardian);
         }
 
@@ -164,20 +167,31 @@ impl VaultContract {
             .persistent()
             .get(&(VAULT, owner.clone()))
             .ok_or(VaultError::VaultNotFound)?;
+        Self::require_unlocked(&env, &record)?;
+        if record.policy.require_guardian {
+            let identity: Address = env
+                .storage()
+                .instance()
+                .get(&IDENTITY)
+                .ok_or(VaultError::NotInitialized)?;
+            let id_client = IdentityContractClient::new(&env, &identity);
+            if !id_client.get_guardians(&owner).contains(&requester) {
+                return Err(VaultError::Unauthorized);
+            }
+        }
         if shares.len() < record.policy.threshold {
             return Err(VaultError::InsufficientShares);
         }
 
+        let commitments = Self::commitments(&env, &owner, record.epoch);
         let mut verified = StdVec::new();
         for i in 0..record.policy.threshold {
             let s = shares.get(i).ok_or(VaultError::InsufficientShares)?;
-            let stored: VaultShare = env
-                .storage()
-                .persistent()
-                .get(&(SHARE, owner.clone(), record.epoch, s.guardian.clone()))
+            let commitment = commitments
+                .get(s.guardian.clone())
                 .ok_or(VaultError::InvalidShare)?;
-            if stored.x != s.x || stored.y != s.y {
-                return Err(VaultError::InvalidShare);
+            if Self::share_digest(&env, &s) != commitment {
+                return Err(VaultError::ShareVerificationFailed);
             }
             verified.push(sss::Share {
                 x: s.x as u8,
@@ -224,6 +238,7 @@ impl VaultContract {
             .persistent()
             .get(&(VAULT, owner.clone()))
             .ok_or(VaultError::VaultNotFound)?;
+        Self::require_unlocked(&env, &record)?;
         let identity: Address = env
             .storage()
             .instance()
@@ -300,8 +315,10 @@ impl VaultContract {
         for i in 0..record.policy.shard_count {
             let g = guardians.get(i).ok_or(VaultError::InvalidConfig)?;
             let share = shares.get(i as usize).ok_or(VaultError::InvalidConfig)?;
-            env.storage().persistent().set(
-                &(SHARE, owner.clone(), new_epoch, g.clone()),
+            Self::store_share(
+                &env,
+                &owner,
+                new_epoch,
                 &VaultShare {
                     guardian: g,
                     x: share.x as u32,
@@ -327,6 +344,198 @@ impl VaultContract {
         })
     }
 
+    /// Move the vault's shares onto `new_holders` with `new_threshold`
+    /// without reconstructing the secret.
+    ///
+    /// The current shares are re-randomized into a fresh sharing of the same
+    /// secret under a new epoch; earlier shares stop verifying and do not
+    /// combine with the new ones. Every new holder must be a guardian of
+    /// `owner`. The emergency threshold is clamped into the new bounds.
+    pub fn reshare(
+        env: Env,
+        owner: Address,
+        new_threshold: u32,
+        new_holders: Vec<Address>,
+    ) -> Result<VaultSnapshot, VaultError> {
+        Self::require_init(&env)?;
+        owner.require_auth();
+
+        let mut record: VaultRecord = env
+            .storage()
+            .persistent()
+            .get(&(VAULT, owner.clone()))
+            .ok_or(VaultError::VaultNotFound)?;
+        let new_count = new_holders.len();
+        if new_threshold == 0 || new_threshold > new_count || new_count > u8::MAX as u32 {
+            return Err(VaultError::InvalidConfig);
+        }
+
+        let identity: Address = env
+            .storage()
+            .instance()
+            .get(&IDENTITY)
+            .ok_or(VaultError::NotInitialized)?;
+        let id_client = IdentityContractClient::new(&env, &identity);
+        let guardians = id_client.get_guardians(&owner);
+        for i in 0..new_count {
+            let holder = new_holders.get(i).ok_or(VaultError::InvalidConfig)?;
+            if !guardians.contains(&holder) || new_holders.first_index_of(&holder) != Some(i) {
+                return Err(VaultError::InvalidConfig);
+            }
+        }
+
+        let mut current = StdVec::new();
+        let mut current_holders = Vec::new(&env);
+        for g in guardians.iter() {
+            if let Some(s) = env.storage().persistent().get::<_, VaultShare>(&(
+                SHARE,
+                owner.clone(),
+                record.epoch,
+                g.clone(),
+            )) {
+                current.push(sss::Share {
+                    x: s.x as u8,
+                    y: s.y.to_array(),
+                });
+                current_holders.push_back(g);
+            }
+        }
+        if (current.len() as u32) < record.policy.threshold {
+            return Err(VaultError::InsufficientShares);
+        }
+
+        let rows = record.policy.threshold * (new_threshold - 1);
+        let mut randomness = StdVec::new();
+        for _ in 0..rows {
+            let mut row = [0u8; 32];
+            env.prng().fill(&mut row);
+            randomness.push(row);
+        }
+        let shares = sss::reshare(
+            &current,
+            record.policy.threshold as u8,
+            new_threshold as u8,
+            new_count as u8,
+            &randomness,
+        )
+        .ok_or(VaultError::InvalidConfig)?;
+
+        for g in current_holders.iter() {
+            env.storage()
+                .persistent()
+                .remove(&(SHARE, owner.clone(), record.epoch, g));
+        }
+        env.storage()
+            .persistent()
+            .remove(&(COMMIT, owner.clone(), record.epoch));
+        let new_epoch = record.epoch.saturating_add(1);
+        for i in 0..new_count {
+            let g = new_holders.get(i).ok_or(VaultError::InvalidConfig)?;
+            let share = shares.get(i as usize).ok_or(VaultError::InvalidConfig)?;
+            Self::store_share(
+                &env,
+                &owner,
+                new_epoch,
+                &VaultShare {
+                    guardian: g,
+                    x: share.x as u32,
+                    y: BytesN::from_array(&env, &share.y),
+                },
+            );
+        }
+
+        record.epoch = new_epoch;
+        record.policy.threshold = new_threshold;
+        record.policy.shard_count = new_count;
+        record.policy.emergency_threshold = record
+            .policy
+            .emergency_threshold
+            .clamp(new_threshold, new_count);
+        record.last_activity_at = env.ledger().timestamp();
+        record.deadman_release_at = record
+            .last_activity_at
+            .saturating_add(record.policy.inactivity_timeout_secs);
+        env.storage()
+            .persistent()
+            .set(&(VAULT, owner.clone()), &record);
+
+        Ok(VaultSnapshot {
+            record,
+            shard_holders: new_holders,
+        })
+    }
+
+    /// Check `share` against the commitment published for its guardian at
+    /// the vault's current epoch, without revealing any other share.
+    pub fn verify_share(env: Env, owner: Address, share: VaultShare) -> bool {
+        let Some(record) = env
+            .storage()
+            .persistent()
+            .get::<_, VaultRecord>(&(VAULT, owner.clone()))
+        else {
+            return false;
+        };
+        Self::commitments(&env, &owner, record.epoch)
+            .get(share.guardian.clone())
+            .is_some_and(|c| c == Self::share_digest(&env, &share))
+    }
+
+    /// The calling guardian's share of `owner`'s vault at the current epoch.
+    pub fn get_share(
+        env: Env,
+        guardian: Address,
+        owner: Address,
+    ) -> Result<VaultShare, VaultError> {
+        Self::require_init(&env)?;
+        guardian.require_auth();
+
+        let record: VaultRecord = env
+            .storage()
+            .persistent()
+            .get(&(VAULT, owner.clone()))
+            .ok_or(VaultError::VaultNotFound)?;
+        env.storage()
+            .persistent()
+            .get(&(SHARE, owner, record.epoch, guardian))
+            .ok_or(VaultError::Unauthorized)
+    }
+
+    /// Time-lock the vault: shares cannot be combined, normally or in an
+    /// emergency, before the `not_before` ledger timestamp. 0 lifts the lock.
+    pub fn set_not_before(env: Env, owner: Address, not_before: u64) -> Result<(), VaultError> {
+        Self::require_init(&env)?;
+        owner.require_auth();
+
+        let mut record: VaultRecord = env
+            .storage()
+            .persistent()
+            .get(&(VAULT, owner.clone()))
+            .ok_or(VaultError::VaultNotFound)?;
+        record.policy.not_before = not_before;
+        env.storage().persistent().set(&(VAULT, owner), &record);
+        Ok(())
+    }
+
+    /// Restrict `reconstruct_key` to requesters who are currently guardians
+    /// of `owner` in the identity contract.
+    pub fn set_require_guardian(
+        env: Env,
+        owner: Address,
+        require_guardian: bool,
+    ) -> Result<(), VaultError> {
+        Self::require_init(&env)?;
+        owner.require_auth();
+
+        let mut record: VaultRecord = env
+            .storage()
+            .persistent()
+            .get(&(VAULT, owner.clone()))
+            .ok_or(VaultError::VaultNotFound)?;
+        record.policy.require_guardian = require_guardian;
+        env.storage().persistent().set(&(VAULT, owner), &record);
+        Ok(())
+    }
+
     pub fn touch_activity(env: Env, owner: Address) -> Result<(), VaultError> {
         owner.require_auth();
         let mut record: VaultRecord = env
@@ -361,6 +570,40 @@ impl VaultContract {
             .ok_or(VaultError::VaultNotFound)
     }
 
+    /// Store a guardian's share and publish its commitment for `epoch`.
+    fn store_share(env: &Env, owner: &Address, epoch: u32, share: &VaultShare) {
+        env.storage().persistent().set(
+            &(SHARE, owner.clone(), epoch, share.guardian.clone()),
+            share,
+        );
+        let mut commitments = Self::commitments(env, owner, epoch);
+        commitments.set(share.guardian.clone(), Self::share_digest(env, share));
+        env.storage()
+            .persistent()
+            .set(&(COMMIT, owner.clone(), epoch), &commitments);
+    }
+
+    fn commitments(env: &Env, owner: &Address, epoch: u32) -> Map<Address, BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&(COMMIT, owner.clone(), epoch))
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Hiding commitment to a share: sha256 over `x` (big-endian) and `y`.
+    fn share_digest(env: &Env, share: &VaultShare) -> BytesN<32> {
+        let mut payload = Bytes::from_array(env, &share.x.to_be_bytes());
+        payload.append(&share.y.clone().into());
+        env.crypto().sha256(&payload).into()
+    }
+
+    fn require_unlocked(env: &Env, record: &VaultRecord) -> Result<(), VaultError> {
+        if env.ledger().timestamp() < record.policy.not_before {
+            return Err(VaultError::TimeLocked);
+        }
+        Ok(())
+    }
+
     fn require_init(env: &Env) -> Result<(), VaultError> {
         if !env.storage().instance().has(&INIT) {
             return Err(VaultError::NotInitialized);
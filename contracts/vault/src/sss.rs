@@ -91,6 +91,24 @@ pub fn split(secret: [u8; 32], threshold: u8, n: u8, seed: [u8; 32]) -> Vec<Shar
     shares
 }
 
+/// Lagrange basis polynomial of share `j` among the first `threshold`
+/// shares, evaluated at zero.
+fn lagrange_at_zero(shares: &[Share], threshold: usize, j: usize) -> u8 {
+    let xj = shares[j].x;
+    let mut num = 1u8;
+    let mut den = 1u8;
+    let mut m = 0usize;
+    while m < threshold {
+        if m != j {
+            let xm = shares[m].x;
+            num = gf_mul(num, xm);
+            den = gf_mul(den, xm ^ xj);
+        }
+        m += 1;
+    }
+    gf_div(num, den)
+}
+
 pub fn reconstruct(shares: &[Share], threshold: u8) -> Option<[u8; 32]> {
     if shares.len() < threshold as usize || threshold == 0 {
         return None;
@@ -102,23 +120,8 @@ pub fn reconstruct(shares: &[Share], threshold: u8) -> Option<[u8; 32]> {
         let mut secret_byte = 0u8;
         let mut j = 0usize;
         while j < threshold as usize {
-            let xj = shares[j].x;
-            let yj = shares[j].y[i];
-
-            let mut num = 1u8;
-            let mut den = 1u8;
-            let mut m = 0usize;
-            while m < threshold as usize {
-                if m != j {
-                    let xm = shares[m].x;
-                    num = gf_mul(num, xm);
-                    den = gf_mul(den, xm ^ xj);
-                }
-                m += 1;
-            }
-
-            let lj = gf_div(num, den);
-            secret_byte ^= gf_mul(yj, lj);
+            let lj = lagrange_at_zero(shares, threshold as usize, j);
+            secret_byte ^= gf_mul(shares[j].y[i], lj);
             j += 1;
         }
         out[i] = secret_byte;
@@ -127,6 +130,64 @@ pub fn reconstruct(shares: &[Share], threshold: u8) -> Option<[u8; 32]> {
     Some(out)
 }
 
+/// Move a `threshold` sharing onto `new_n` holders with `new_threshold`,
+/// without recovering the secret.
+///
+/// Each of the first `threshold` shares is split again on a fresh
+/// polynomial whose constant term is that share. A new holder's share is
+/// the Lagrange-weighted sum of the sub-shares at its `x`, which lies on a
+/// polynomial with the same constant term as the original. `randomness`
+/// holds the non-constant coefficients, `new_threshold - 1` rows per old
+/// share.
+pub fn reshare(
+    shares: &[Share],
+    threshold: u8,
+    new_threshold: u8,
+    new_n: u8,
+    randomness: &[[u8; 32]],
+) -> Option<Vec<Share>> {
+    let t = threshold as usize;
+    let degree = new_threshold.saturating_sub(1) as usize;
+    if t == 0
+        || shares.len() < t
+        || new_threshold == 0
+        || new_threshold > new_n
+        || randomness.len() < t * degree
+    {
+        return None;
+    }
+
+    let weights: Vec<u8> = (0..t).map(|j| lagrange_at_zero(shares, t, j)).collect();
+
+    let mut out = Vec::new();
+    let mut x = 1u8;
+    while x <= new_n {
+        let mut y = [0u8; 32];
+        let mut j = 0usize;
+        while j < t {
+            let coeffs = &randomness[j * degree..(j + 1) * degree];
+            let mut i = 0usize;
+            while i < 32 {
+                let mut acc = shares[j].y[i];
+                let mut pow = x;
+                for c in coeffs {
+                    acc ^= gf_mul(c[i], pow);
+                    pow = gf_mul(pow, x);
+                }
+                y[i] ^= gf_mul(weights[j], acc);
+                i += 1;
+            }
+            j += 1;
+        }
+        out.push(Share { x, y });
+        if x == u8::MAX {
+            break;
+        }
+        x += 1;
+    }
+    Some(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,4 +202,29 @@ mod tests {
         let recovered = reconstruct(&subset, 3).unwrap();
         assert_eq!(recovered, secret);
     }
+
+    #[test]
+    fn reshare_keeps_secret_under_new_threshold() {
+        let secret = [7u8; 32];
+        let shares = split(secret, 3, 5, [11u8; 32]);
+        let old = vec![shares[1].clone(), shares[3].clone(), shares[4].clone()];
+
+        let mut randomness = vec![[0u8; 32]; 3];
+        for (k, row) in randomness.iter_mut().enumerate() {
+            for (i, b) in row.iter_mut().enumerate() {
+                *b = (k as u8).wrapping_mul(31) ^ (i as u8).wrapping_mul(7) ^ 0x5a;
+            }
+        }
+        let new = reshare(&old, 3, 2, 4, &randomness).unwrap();
+        assert_eq!(new.len(), 4);
+
+        let pair = vec![new[0].clone(), new[3].clone()];
+        assert_eq!(reconstruct(&pair, 2).unwrap(), secret);
+        // One old share alongside a new one lies on a different polynomial.
+        let mixed = vec![shares[0].clone(), new[1].clone()];
+        assert_ne!(reconstruct(&mixed, 2).unwrap(), secret);
+
+        assert!(reshare(&old[..2], 3, 2, 4, &randomness).is_none());
+        assert!(reshare(&old, 3, 5, 4, &randomness).is_none());
+    }
 }
@@ -2,7 +2,7 @@ extern crate std;
 
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
-    Address, BytesN, Env, String,
+    vec, Address, BytesN, Env, String,
 };
 
 use crate::{VaultContract, VaultContractClient, VaultError};
@@ -515,3 +515,225 @@ fn test_rapid_configuration_changes_no_corruption() {
     assert_eq!(stored.policy.threshold, 5); // Last configuration
     assert_eq!(stored.policy.shard_count, 5);
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Threshold re-sharing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Resharing 2-of-3 onto a new 3-of-4 holder set keeps the same secret, and
+/// shares from before the reshare no longer verify.
+#[test]
+fn test_reshare_preserves_secret_under_new_holders() {
+    let env = Env::default();
+    let (vault, identity, _admin, owner) = setup_vault(&env);
+    let g = add_guardians(&env, &identity, &owner, 5);
+
+    let seed = BytesN::from_array(&env, &[23u8; 32]);
+    let data_ref = String::from_str(&env, "ipfs://reshare");
+    vault.configure_vault(&owner, &2, &3, &3, &3600, &data_ref, &seed);
+    let old0 = vault.get_share(&g[0], &owner);
+    let old1 = vault.get_share(&g[1], &owner);
+
+    // g[0] leaves; g[3] and g[4] join.
+    let holders = vec![&env, g[1].clone(), g[2].clone(), g[3].clone(), g[4].clone()];
+    let snap = vault.reshare(&owner, &3, &holders);
+    assert_eq!(snap.record.epoch, 2);
+    assert_eq!(snap.record.policy.threshold, 3);
+    assert_eq!(snap.record.policy.shard_count, 4);
+    assert_eq!(snap.record.policy.emergency_threshold, 3);
+    assert_eq!(
+        vault.try_get_share(&g[0], &owner),
+        Err(Ok(VaultError::Unauthorized))
+    );
+
+    let new1 = vault.get_share(&g[1], &owner);
+    let new3 = vault.get_share(&g[3], &owner);
+    let new4 = vault.get_share(&g[4], &owner);
+    assert_ne!(new1, old1);
+    let recovered = vault.reconstruct_key(
+        &g[1],
+        &owner,
+        &vec![&env, new4.clone(), new1.clone(), new3.clone()],
+    );
+    assert_eq!(recovered, seed);
+
+    // Old shares, alone or mixed with new ones, are rejected.
+    assert_eq!(
+        vault.try_reconstruct_key(&g[0], &owner, &vec![&env, old0.clone(), old1, new3.clone()]),
+        Err(Ok(VaultError::InvalidShare))
+    );
+    assert_eq!(
+        vault.try_reconstruct_key(&g[1], &owner, &vec![&env, new1, new3, old0]),
+        Err(Ok(VaultError::InvalidShare))
+    );
+}
+
+#[test]
+fn test_reshare_rejects_invalid_holder_sets() {
+    let env = Env::default();
+    let (vault, identity, _admin, owner) = setup_vault(&env);
+    let g = add_guardians(&env, &identity, &owner, 3);
+
+    let seed = BytesN::from_array(&env, &[24u8; 32]);
+    let data_ref = String::from_str(&env, "ipfs://reshare");
+    vault.configure_vault(&owner, &2, &3, &2, &3600, &data_ref, &seed);
+
+    let outsider = Address::generate(&env);
+    for (threshold, holders) in [
+        (3, vec![&env, g[0].clone(), g[1].clone()]),
+        (0, vec![&env, g[0].clone(), g[1].clone()]),
+        (2, vec![&env, g[0].clone(), outsider]),
+        (2, vec![&env, g[0].clone(), g[0].clone()]),
+    ] {
+        assert_eq!(
+            vault.try_reshare(&owner, &threshold, &holders),
+            Err(Ok(VaultError::InvalidConfig))
+        );
+    }
+    assert_eq!(vault.get_vault(&owner).epoch, 1);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Share commitments
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A holder can check a share before submitting it, and reconstruction
+/// refuses a share that does not match its commitment.
+#[test]
+fn test_tampered_share_fails_verification() {
+    let env = Env::default();
+    let (vault, identity, _admin, owner) = setup_vault(&env);
+    let g = add_guardians(&env, &identity, &owner, 3);
+
+    let seed = BytesN::from_array(&env, &[25u8; 32]);
+    let data_ref = String::from_str(&env, "ipfs://commit");
+    vault.configure_vault(&owner, &2, &3, &2, &3600, &data_ref, &seed);
+
+    let honest0 = vault.get_share(&g[0], &owner);
+    let honest2 = vault.get_share(&g[2], &owner);
+    assert!(vault.verify_share(&owner, &honest0));
+    assert!(vault.verify_share(&owner, &honest2));
+
+    let mut y = honest2.y.to_array();
+    y[5] ^= 0x01;
+    let tampered = common::VaultShare {
+        y: BytesN::from_array(&env, &y),
+        ..honest2.clone()
+    };
+    assert!(!vault.verify_share(&owner, &tampered));
+    // A valid share presented under another guardian does not verify either.
+    let misattributed = common::VaultShare {
+        guardian: g[1].clone(),
+        ..honest2.clone()
+    };
+    assert!(!vault.verify_share(&owner, &misattributed));
+
+    assert_eq!(
+        vault.try_reconstruct_key(&g[0], &owner, &vec![&env, honest0.clone(), tampered]),
+        Err(Ok(VaultError::ShareVerificationFailed))
+    );
+    let recovered = vault.reconstruct_key(&g[0], &owner, &vec![&env, honest0, honest2]);
+    assert_eq!(recovered, seed);
+}
+
+/// Rotation publishes fresh commitments; shares of the previous epoch stop
+/// verifying.
+#[test]
+fn test_rotation_replaces_share_commitments() {
+    let env = Env::default();
+    let (vault, identity, _admin, owner) = setup_vault(&env);
+    let g = add_guardians(&env, &identity, &owner, 3);
+
+    let data_ref = String::from_str(&env, "ipfs://commit");
+    vault.configure_vault(
+        &owner,
+        &2,
+        &3,
+        &2,
+        &3600,
+        &data_ref,
+        &BytesN::from_array(&env, &[26u8; 32]),
+    );
+    let before = vault.get_share(&g[1], &owner);
+
+    vault.rotate_key(&owner, &BytesN::from_array(&env, &[27u8; 32]));
+    assert!(!vault.verify_share(&owner, &before));
+    assert!(vault.verify_share(&owner, &vault.get_share(&g[1], &owner)));
+    assert!(!vault.verify_share(&Address::generate(&env), &before));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Time-locked share release
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Shares submitted before `not_before` are refused; once the ledger clock
+/// reaches it they combine as usual.
+#[test]
+fn test_time_locked_shares_combine_only_after_unlock() {
+    let env = Env::default();
+    env.ledger().set_timestamp(1_000);
+    let (vault, identity, _admin, owner) = setup_vault(&env);
+    let g = add_guardians(&env, &identity, &owner, 3);
+
+    let seed = BytesN::from_array(&env, &[28u8; 32]);
+    let data_ref = String::from_str(&env, "ipfs://estate");
+    vault.configure_vault(&owner, &2, &3, &2, &0, &data_ref, &seed);
+    vault.set_not_before(&owner, &5_000);
+    assert_eq!(vault.get_vault(&owner).policy.not_before, 5_000);
+
+    let shares = vec![
+        &env,
+        vault.get_share(&g[0], &owner),
+        vault.get_share(&g[1], &owner),
+    ];
+    vault.submit_emergency_approval(&g[0], &owner);
+    vault.submit_emergency_approval(&g[1], &owner);
+
+    env.ledger().set_timestamp(4_999);
+    assert_eq!(
+        vault.try_reconstruct_key(&g[0], &owner, &shares),
+        Err(Ok(VaultError::TimeLocked))
+    );
+    assert_eq!(
+        vault.try_emergency_reconstruct(&owner),
+        Err(Ok(VaultError::TimeLocked))
+    );
+
+    env.ledger().set_timestamp(5_000);
+    assert_eq!(vault.reconstruct_key(&g[0], &owner, &shares), seed);
+    assert_eq!(vault.emergency_reconstruct(&owner), seed);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Guardian-gated reconstruction
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// With `require_guardian` set, only a current guardian of the owner may
+/// request reconstruction, even when holding valid shares.
+#[test]
+fn test_guardian_gated_reconstruction() {
+    let env = Env::default();
+    let (vault, identity, _admin, owner) = setup_vault(&env);
+    let g = add_guardians(&env, &identity, &owner, 3);
+
+    let seed = BytesN::from_array(&env, &[29u8; 32]);
+    let data_ref = String::from_str(&env, "ipfs://gated");
+    vault.configure_vault(&owner, &2, &3, &2, &3600, &data_ref, &seed);
+    let shares = vec![
+        &env,
+        vault.get_share(&g[0], &owner),
+        vault.get_share(&g[1], &owner),
+    ];
+
+    // Off by default: anyone presenting valid shares may reconstruct.
+    let outsider = Address::generate(&env);
+    assert_eq!(vault.reconstruct_key(&outsider, &owner, &shares), seed);
+
+    vault.set_require_guardian(&owner, &true);
+    assert!(vault.get_vault(&owner).policy.require_guardian);
+    assert_eq!(
+        vault.try_reconstruct_key(&outsider, &owner, &shares),
+        Err(Ok(VaultError::Unauthorized))
+    );
+    assert_eq!(vault.reconstruct_key(&g[2], &owner, &shares), seed);
+}
@@ -0,0 +1,54 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, Symbol};
+
+/// Longest lifetime a one-time token may be minted with: 7 days.
+pub const MAX_TOKEN_TTL: u64 = 604_800;
+
+const ACCESS_TOKEN: Symbol = symbol_short!("ACC_TKN");
+
+const TTL_THRESHOLD: u32 = 5_184_000;
+const TTL_EXTEND_TO: u32 = 10_368_000;
+
+/// A single-use, expiring token for reading one record.
+///
+/// The patient draws the secret off-chain and registers only
+/// `sha256(secret)`, so the secret never appears in contract storage,
+/// events or return values; they hand it to the redeemer out of band.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessToken {
+    pub patient: Address,
+    pub record_id: u64,
+    /// When set, only this address may redeem the token.
+    pub redeemer: Option<Address>,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    /// Set once the token has been redeemed.
+    pub redeemed_by: Option<Address>,
+}
+
+fn token_key(token_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (ACCESS_TOKEN, token_id.clone())
+}
+
+/// Identifier a token is stored and reported under: `sha256(secret)`.
+pub fn token_id(env: &Env, secret: &BytesN<32>) -> BytesN<32> {
+    env.crypto()
+        .sha256(&Bytes::from_array(env, &secret.to_array()))
+        .into()
+}
+
+pub fn get(env: &Env, token_id: &BytesN<32>) -> Option<AccessToken> {
+    env.storage().persistent().get(&token_key(token_id))
+}
+
+pub fn set(env: &Env, token_id: &BytesN<32>, token: &AccessToken) {
+    let key = token_key(token_id);
+    env.storage().persistent().set(&key, token);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn remove(env: &Env, token_id: &BytesN<32>) {
+    env.storage().persistent().remove(&token_key(token_id));
+}
@@ -1,11 +1,17 @@
 use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
 
+use crate::sub_delegation::level_rank;
+use crate::{AccessLevel, RecordType};
+
 // ── Storage keys ──────────────────────────────────────────────
 pub const APPT_CTR: Symbol = symbol_short!("APPT_CTR");
 const APPT_RECORD: Symbol = symbol_short!("APPT_REC");
 const APPT_PATIENT: Symbol = symbol_short!("APPT_PAT");
 const APPT_PROVIDER: Symbol = symbol_short!("APPT_PROV");
 const APPT_HISTORY: Symbol = symbol_short!("APPT_HIST");
+const APPT_AUTO: Symbol = symbol_short!("APPT_AUTO");
+const APPT_WINDOW: Symbol = symbol_short!("APPT_WIN");
+const APPT_WINDOW_PROVIDERS: Symbol = symbol_short!("APPT_WPRV");
 
 const TTL_THRESHOLD: u32 = 5184000;
 const TTL_EXTEND_TO: u32 = 10368000;
@@ -24,6 +30,20 @@ fn extend_ttl_appointment_patient_key(env: &Env, key: &(Symbol, Address, u64)) {
         .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
 }
 
+/// Extends the time-to-live (TTL) for a patient's auto-access consent key.
+fn extend_ttl_appointment_auto_key(env: &Env, key: &(Symbol, Address)) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Extends the time-to-live (TTL) for access window keys.
+fn extend_ttl_appointment_window_key(env: &Env, key: &(Symbol, Address, Address)) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
 /// Extends the time-to-live (TTL) for appointment by provider keys.
 fn extend_ttl_appointment_provider_key(env: &Env, key: &(Symbol, Address, u64)) {
     env.storage()
@@ -92,6 +112,31 @@ pub struct AppointmentHistoryEntry {
     pub notes: Option<String>,
 }
 
+/// A patient's standing consent for appointment-based access: each
+/// appointment scheduled for the patient grants its provider `level` access
+/// to the patient's records of `record_types` from `pre_window` seconds
+/// before the appointment until `post_window` seconds after it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppointmentAutoAccess {
+    pub level: AccessLevel,
+    pub record_types: Vec<RecordType>,
+    pub pre_window: u64,
+    pub post_window: u64,
+}
+
+/// Record access granted to a provider around one appointment, active for
+/// `starts_at <= now < ends_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppointmentAccessWindow {
+    pub appointment_id: u64,
+    pub level: AccessLevel,
+    pub record_types: Vec<RecordType>,
+    pub starts_at: u64,
+    pub ends_at: u64,
+}
+
 // ── Storage Functions ────────────────────────────────────────
 
 /// Increments and returns the next appointment ID
@@ -256,3 +301,106 @@ pub fn mark_reminder_sent(env: &Env, appointment_id: u64) -> Option<Appointment>
         None
     }
 }
+
+// ── Appointment-based access ─────────────────────────────────
+
+pub fn set_auto_access(env: &Env, patient: &Address, consent: &AppointmentAutoAccess) {
+    let key = (APPT_AUTO, patient.clone());
+    env.storage().persistent().set(&key, consent);
+    extend_ttl_appointment_auto_key(env, &key);
+}
+
+pub fn get_auto_access(env: &Env, patient: &Address) -> Option<AppointmentAutoAccess> {
+    env.storage()
+        .persistent()
+        .get(&(APPT_AUTO, patient.clone()))
+}
+
+/// Withdraw the patient's consent and every access window it granted, so
+/// consenting again does not bring old windows back.
+pub fn remove_auto_access(env: &Env, patient: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&(APPT_AUTO, patient.clone()));
+
+    let providers_key = (APPT_WINDOW_PROVIDERS, patient.clone());
+    let providers: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&providers_key)
+        .unwrap_or(Vec::new(env));
+    for provider in providers.iter() {
+        env.storage()
+            .persistent()
+            .remove(&(APPT_WINDOW, patient.clone(), provider));
+    }
+    env.storage().persistent().remove(&providers_key);
+}
+
+/// Access windows `provider` holds on `patient`'s records, lapsed ones
+/// included until the next window is added.
+pub fn get_access_windows(
+    env: &Env,
+    patient: &Address,
+    provider: &Address,
+) -> Vec<AppointmentAccessWindow> {
+    env.storage()
+        .persistent()
+        .get(&(APPT_WINDOW, patient.clone(), provider.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add an access window, dropping any that have already lapsed.
+pub fn add_access_window(
+    env: &Env,
+    patient: &Address,
+    provider: &Address,
+    window: &AppointmentAccessWindow,
+) {
+    let now = env.ledger().timestamp();
+    let mut windows = Vec::new(env);
+    for existing in get_access_windows(env, patient, provider).iter() {
+        if existing.ends_at > now {
+            windows.push_back(existing);
+        }
+    }
+    windows.push_back(window.clone());
+
+    let key = (APPT_WINDOW, patient.clone(), provider.clone());
+    env.storage().persistent().set(&key, &windows);
+    extend_ttl_appointment_window_key(env, &key);
+
+    let providers_key = (APPT_WINDOW_PROVIDERS, patient.clone());
+    let mut providers: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&providers_key)
+        .unwrap_or(Vec::new(env));
+    if !providers.contains(provider) {
+        providers.push_back(provider.clone());
+        env.storage().persistent().set(&providers_key, &providers);
+    }
+    extend_ttl_appointment_auto_key(env, &providers_key);
+}
+
+/// Highest access level among `provider`'s windows on `patient`'s records
+/// that are active now and cover `record_type`.
+pub fn active_window_level(
+    env: &Env,
+    patient: &Address,
+    provider: &Address,
+    record_type: &RecordType,
+) -> AccessLevel {
+    let now = env.ledger().timestamp();
+    let mut level = AccessLevel::None;
+    for window in get_access_windows(env, patient, provider).iter() {
+        if window.starts_at <= now
+            && now < window.ends_at
+            && window.record_types.contains(record_type)
+            && level_rank(&window.level) > level_rank(&level)
+        {
+            level = window.level;
+        }
+    }
+    level
+}
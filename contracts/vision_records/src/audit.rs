@@ -95,6 +95,9 @@ const AUDIT_ENTRY: Symbol = symbol_short!("AUD_ENT");
 const AUDIT_RECORD: Symbol = symbol_short!("AUD_REC");
 const AUDIT_USER: Symbol = symbol_short!("AUD_USR");
 const AUDIT_PATIENT: Symbol = symbol_short!("AUD_PAT");
+const AUDIT_ACTOR_IDX: Symbol = symbol_short!("AUD_ACT");
+const AUDIT_SAMPLE_RATE: Symbol = symbol_short!("AUD_SMPL");
+const AUDIT_READ_SEEN: Symbol = symbol_short!("AUD_RSEEN");
 
 const TTL_THRESHOLD: u32 = 5184000;
 const TTL_EXTEND_TO: u32 = 10368000;
@@ -120,6 +123,13 @@ fn extend_ttl_audit_user_key(env: &Env, key: &(Symbol, Address, u64)) {
         .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
 }
 
+/// Extends the time-to-live (TTL) for the per-actor audit index.
+fn extend_ttl_audit_actor_index_key(env: &Env, key: &(Symbol, Address)) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
 /// Extends the time-to-live (TTL) for audit by patient keys.
 fn extend_ttl_audit_patient_key(env: &Env, key: &(Symbol, Address, u64)) {
     env.storage()
@@ -141,6 +151,7 @@ pub enum AccessAction {
     RevokeAccess = 5,
     EmergencyAccess = 6,
     Query = 7,
+    EscrowDecryption = 8,
 }
 
 /// Result of an access attempt
@@ -200,6 +211,17 @@ pub fn add_audit_entry(env: &Env, entry: &AuditEntry) {
     env.storage().persistent().set(&user_key, &true);
     extend_ttl_audit_user_key(env, &user_key);
 
+    // Ordered list of entry IDs per actor, for paginated actor history
+    let actor_index_key = (AUDIT_ACTOR_IDX, entry.actor.clone());
+    let mut actor_ids: soroban_sdk::Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&actor_index_key)
+        .unwrap_or(soroban_sdk::Vec::new(env));
+    actor_ids.push_back(entry.id);
+    env.storage().persistent().set(&actor_index_key, &actor_ids);
+    extend_ttl_audit_actor_index_key(env, &actor_index_key);
+
     // Index by patient for quick lookup
     let patient_key = (AUDIT_PATIENT, entry.patient.clone(), entry.id);
     env.storage().persistent().set(&patient_key, &true);
@@ -259,6 +281,14 @@ pub fn get_user_audit_log(env: &Env, user: &Address) -> soroban_sdk::Vec<AuditEn
     entries
 }
 
+/// Gets the IDs of all audit entries recorded for an actor, oldest first
+pub fn get_actor_audit_ids(env: &Env, actor: &Address) -> soroban_sdk::Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&(AUDIT_ACTOR_IDX, actor.clone()))
+        .unwrap_or(soroban_sdk::Vec::new(env))
+}
+
 /// Gets all audit entries for a specific patient
 pub fn get_patient_audit_log(env: &Env, patient: &Address) -> soroban_sdk::Vec<AuditEntry> {
     let mut entries = soroban_sdk::Vec::new(env);
@@ -347,6 +377,37 @@ pub fn get_recent_audit_log(env: &Env, limit: u64) -> soroban_sdk::Vec<AuditEntr
     entries
 }
 
+// ── Read sampling ────────────────────────────────────────────
+
+/// Sets the 1-in-N sampling rate for successful reads (1 audits every read).
+pub fn set_read_sample_rate(env: &Env, rate: u32) {
+    env.storage().instance().set(&AUDIT_SAMPLE_RATE, &rate);
+}
+
+/// Returns the sampling rate for successful reads, defaulting to 1.
+pub fn get_read_sample_rate(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&AUDIT_SAMPLE_RATE)
+        .unwrap_or(1)
+}
+
+/// Counts a successful read by `actor` and reports whether it should be
+/// audited: the first read, then every Nth one after it. Only successful
+/// reads go through here; denials and writes are always logged.
+pub fn should_audit_read(env: &Env, actor: &Address) -> bool {
+    let rate = get_read_sample_rate(env);
+    if rate <= 1 {
+        return true;
+    }
+
+    let key = (AUDIT_READ_SEEN, actor.clone());
+    let seen: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &((seen + 1) % rate));
+    extend_ttl_audit_actor_index_key(env, &key);
+    seen % rate == 0
+}
+
 /// Helper function to create an audit entry
 pub fn create_audit_entry(
     env: &Env,
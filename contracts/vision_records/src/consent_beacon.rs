@@ -0,0 +1,194 @@
+use audit::merkle_log::{compute_root, hash_leaf, prove_inclusion_over, InclusionProof};
+use audit::types::Digest;
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{AccessGrant, AccessLevel, ConsentGrant};
+
+/// Default minimum time between two beacons for the same patient: 1 day.
+pub const DEFAULT_BEACON_INTERVAL: u64 = 86_400;
+
+const BEACON_INTERVAL: Symbol = symbol_short!("BCN_INT");
+const BEACON: Symbol = symbol_short!("BCN");
+const BEACON_SEQ: Symbol = symbol_short!("BCN_SEQ");
+
+/// An access-control entry committed to by a consent beacon.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum BeaconGrant {
+    Consent(ConsentGrant),
+    Access(AccessGrant),
+}
+
+impl BeaconGrant {
+    pub fn patient(&self) -> &Address {
+        match self {
+            BeaconGrant::Consent(consent) => &consent.patient,
+            BeaconGrant::Access(grant) => &grant.patient,
+        }
+    }
+
+    pub fn grantee(&self) -> &Address {
+        match self {
+            BeaconGrant::Consent(consent) => &consent.grantee,
+            BeaconGrant::Access(grant) => &grant.grantee,
+        }
+    }
+}
+
+/// A beacon published for a patient.
+///
+/// `root` is the Merkle root over `hash_leaf(xdr(grant))` for each entry of
+/// `grants`, in order. Every sequence is kept, with its grant set, so that
+/// proofs against an older beacon still verify after the live state and
+/// later beacons have moved on.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ConsentBeacon {
+    pub patient: Address,
+    pub sequence: u32,
+    pub root: BytesN<32>,
+    pub grants: Vec<BeaconGrant>,
+    pub published_at: u64,
+}
+
+/// Proof that `grant` was in the set committed to by a beacon `root`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ConsentInclusionProof {
+    pub patient: Address,
+    pub sequence: u32,
+    pub root: BytesN<32>,
+    pub grant: BeaconGrant,
+    pub leaf_index: u32,
+    pub tree_size: u32,
+    pub siblings: Vec<BytesN<32>>,
+}
+
+pub fn beacon_interval(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&BEACON_INTERVAL)
+        .unwrap_or(DEFAULT_BEACON_INTERVAL)
+}
+
+pub fn set_beacon_interval(env: &Env, seconds: u64) {
+    env.storage().instance().set(&BEACON_INTERVAL, &seconds);
+}
+
+/// The latest beacon published for `patient`.
+pub fn get_beacon(env: &Env, patient: &Address) -> Option<ConsentBeacon> {
+    let sequence: u32 = env
+        .storage()
+        .persistent()
+        .get(&(BEACON_SEQ, patient.clone()))?;
+    get_beacon_at(env, patient, sequence)
+}
+
+pub fn get_beacon_at(env: &Env, patient: &Address, sequence: u32) -> Option<ConsentBeacon> {
+    env.storage()
+        .persistent()
+        .get(&(BEACON, patient.clone(), sequence))
+}
+
+/// Store `beacon` under its sequence and make it the patient's latest.
+pub fn set_beacon(env: &Env, beacon: &ConsentBeacon) {
+    env.storage()
+        .persistent()
+        .set(&(BEACON, beacon.patient.clone(), beacon.sequence), beacon);
+    env.storage()
+        .persistent()
+        .set(&(BEACON_SEQ, beacon.patient.clone()), &beacon.sequence);
+}
+
+/// Active consents and access grants of `patient`, in grantee-list order.
+pub fn active_grants(env: &Env, patient: &Address) -> Vec<BeaconGrant> {
+    let now = env.ledger().timestamp();
+    let grantees: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&(symbol_short!("ACC_LST"), patient.clone()))
+        .unwrap_or(Vec::new(env));
+
+    let mut grants = Vec::new(env);
+    for grantee in grantees.iter() {
+        let consent_key = (symbol_short!("CONSENT"), patient.clone(), grantee.clone());
+        if let Some(consent) = env
+            .storage()
+            .persistent()
+            .get::<_, ConsentGrant>(&consent_key)
+        {
+            if !consent.revoked && consent.expires_at > now {
+                grants.push_back(BeaconGrant::Consent(consent));
+            }
+        }
+        let access_key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
+        if let Some(grant) = env
+            .storage()
+            .persistent()
+            .get::<_, AccessGrant>(&access_key)
+        {
+            if grant.level != AccessLevel::None && grant.expires_at > now {
+                grants.push_back(BeaconGrant::Access(grant));
+            }
+        }
+    }
+    grants
+}
+
+/// Leaf hash of one committed grant: `hash_leaf(xdr(grant))`.
+pub fn leaf_hash(env: &Env, grant: &BeaconGrant) -> Digest {
+    let bytes = grant.clone().to_xdr(env);
+    let mut buf = alloc::vec![0u8; bytes.len() as usize];
+    bytes.copy_into_slice(&mut buf);
+    hash_leaf(&buf)
+}
+
+fn leaf_hashes(env: &Env, grants: &Vec<BeaconGrant>) -> alloc::vec::Vec<Digest> {
+    grants.iter().map(|grant| leaf_hash(env, &grant)).collect()
+}
+
+/// Merkle root over `grants`; all zeroes for an empty set.
+pub fn compute_beacon_root(env: &Env, grants: &Vec<BeaconGrant>) -> BytesN<32> {
+    BytesN::from_array(env, &compute_root(&leaf_hashes(env, grants)))
+}
+
+/// Inclusion proof for the entry at `index` of `beacon.grants`.
+pub fn prove(env: &Env, beacon: &ConsentBeacon, index: u32) -> Option<ConsentInclusionProof> {
+    let grant = beacon.grants.get(index)?;
+    let proof = prove_inclusion_over(&leaf_hashes(env, &beacon.grants), index as usize).ok()?;
+    let mut siblings = Vec::new(env);
+    for sibling in proof.siblings.iter() {
+        siblings.push_back(BytesN::from_array(env, sibling));
+    }
+    Some(ConsentInclusionProof {
+        patient: beacon.patient.clone(),
+        sequence: beacon.sequence,
+        root: beacon.root.clone(),
+        grant,
+        leaf_index: index,
+        tree_size: beacon.grants.len(),
+        siblings,
+    })
+}
+
+/// Check `proof` against the stored beacon it names by recomputing the leaf
+/// hash from the committed grant. A proof carrying any root other than the
+/// one published under `(patient, sequence)` is rejected.
+pub fn verify(env: &Env, proof: &ConsentInclusionProof) -> bool {
+    if proof.grant.patient() != &proof.patient {
+        return false;
+    }
+    let Some(beacon) = get_beacon_at(env, &proof.patient, proof.sequence) else {
+        return false;
+    };
+    if beacon.root != proof.root || beacon.grants.len() != proof.tree_size {
+        return false;
+    }
+    let inclusion = InclusionProof {
+        leaf_index: proof.leaf_index as u64,
+        tree_size: proof.tree_size as u64,
+        leaf_hash: leaf_hash(env, &proof.grant),
+        siblings: proof.siblings.iter().map(|s| s.to_array()).collect(),
+    };
+    inclusion.verify(&proof.root.to_array()).is_ok()
+}
@@ -0,0 +1,119 @@
+use crate::{AccessLevel, ConsentType, RecordType};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+const CST_TPL: Symbol = symbol_short!("CST_TPL");
+const CST_TPLS: Symbol = symbol_short!("CST_TPLS");
+
+const TTL_THRESHOLD: u32 = 5184000;
+const TTL_EXTEND_TO: u32 = 10368000;
+
+/// Extends the time-to-live (TTL) for a consent template storage key.
+fn extend_ttl_template_key(env: &Env, key: &(Symbol, Address, Symbol)) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Extends the time-to-live (TTL) for a patient's template index key.
+fn extend_ttl_index_key(env: &Env, key: &(Symbol, Address)) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// A reusable, per-patient consent shape that can be materialized into a
+/// consent + access grant for any grantee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentTemplate {
+    pub template_id: Symbol,
+    pub patient: Address,
+    pub consent_type: ConsentType,
+    pub level: AccessLevel,
+    pub duration_seconds: u64,
+    /// Record types the template is intended for (empty = all types).
+    pub record_types: Vec<RecordType>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+// ── Storage Functions ────────────────────────────────────────
+
+fn template_key(patient: &Address, template_id: &Symbol) -> (Symbol, Address, Symbol) {
+    (CST_TPL, patient.clone(), template_id.clone())
+}
+
+fn index_key(patient: &Address) -> (Symbol, Address) {
+    (CST_TPLS, patient.clone())
+}
+
+/// Stores a template, creating or replacing it, and tracks its ID in the
+/// patient's template index.
+pub fn set_template(env: &Env, template: &ConsentTemplate) {
+    let key = template_key(&template.patient, &template.template_id);
+    env.storage().persistent().set(&key, template);
+    extend_ttl_template_key(env, &key);
+
+    let list_key = index_key(&template.patient);
+    let mut ids: Vec<Symbol> = env
+        .storage()
+        .persistent()
+        .get(&list_key)
+        .unwrap_or(Vec::new(env));
+    if !ids.contains(&template.template_id) {
+        ids.push_back(template.template_id.clone());
+        env.storage().persistent().set(&list_key, &ids);
+    }
+    extend_ttl_index_key(env, &list_key);
+}
+
+/// Retrieves a patient's template by ID.
+pub fn get_template(env: &Env, patient: &Address, template_id: &Symbol) -> Option<ConsentTemplate> {
+    env.storage()
+        .persistent()
+        .get(&template_key(patient, template_id))
+}
+
+/// Removes a template and its index entry. Returns `false` if it did not exist.
+pub fn remove_template(env: &Env, patient: &Address, template_id: &Symbol) -> bool {
+    let key = template_key(patient, template_id);
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    env.storage().persistent().remove(&key);
+
+    let list_key = index_key(patient);
+    if let Some(ids) = env.storage().persistent().get::<_, Vec<Symbol>>(&list_key) {
+        let mut remaining = Vec::new(env);
+        for id in ids.iter() {
+            if id != *template_id {
+                remaining.push_back(id);
+            }
+        }
+        if remaining.is_empty() {
+            env.storage().persistent().remove(&list_key);
+        } else {
+            env.storage().persistent().set(&list_key, &remaining);
+        }
+    }
+    true
+}
+
+/// Returns all templates stored for a patient, in creation order.
+pub fn list_templates(env: &Env, patient: &Address) -> Vec<ConsentTemplate> {
+    let mut templates = Vec::new(env);
+    let ids: Vec<Symbol> = env
+        .storage()
+        .persistent()
+        .get(&index_key(patient))
+        .unwrap_or(Vec::new(env));
+    for id in ids.iter() {
+        if let Some(template) = get_template(env, patient, &id) {
+            templates.push_back(template);
+        }
+    }
+    templates
+}
@@ -128,6 +128,24 @@ pub enum ContractError {
     LineageCycleDetected = 44,
     UserAlreadyExists = 45,
     InvalidPhase = 46,
+    ConsentTemplateNotFound = 47,
+    EscrowNotConfigured = 48,
+    EscrowRequestNotFound = 49,
+    EscrowRequestExists = 50,
+    EscrowAlreadyApproved = 51,
+    EscrowQuorumNotMet = 52,
+    EscrowDelayNotElapsed = 53,
+    ContactChallengeNotFound = 54,
+    ContactChallengeExpired = 55,
+    ContactChallengeMismatch = 56,
+    ProviderNotVerified = 57,
+    AccessGrantNotFound = 58,
+    ConsentBeaconNotFound = 59,
+    GrantNotInBeacon = 60,
+    AccessTokenNotFound = 61,
+    AccessTokenExpired = 62,
+    AccessTokenRedeemed = 63,
+    SubDelegationDepthExceeded = 64,
 }
 
 impl ContractError {
@@ -151,7 +169,21 @@ impl ContractError {
             | ContractError::InvalidAppointmentStatus
             | ContractError::InvalidPhase
             | ContractError::AppointmentNotVerified
-            | ContractError::MetaTxExpired => ErrorCategory::Validation,
+            | ContractError::MetaTxExpired
+            | ContractError::ContactChallengeExpired
+            | ContractError::ContactChallengeMismatch => ErrorCategory::Validation,
+            ContractError::ContactChallengeNotFound => ErrorCategory::NotFound,
+            ContractError::AccessTokenNotFound => ErrorCategory::NotFound,
+            ContractError::AccessTokenExpired => ErrorCategory::Authorization,
+            ContractError::AccessTokenRedeemed => ErrorCategory::StateConflict,
+            ContractError::SubDelegationDepthExceeded => ErrorCategory::Authorization,
+            ContractError::EscrowNotConfigured
+            | ContractError::EscrowQuorumNotMet
+            | ContractError::EscrowDelayNotElapsed => ErrorCategory::Authorization,
+            ContractError::EscrowRequestNotFound => ErrorCategory::NotFound,
+            ContractError::EscrowRequestExists | ContractError::EscrowAlreadyApproved => {
+                ErrorCategory::StateConflict
+            }
             ContractError::VersionConflict | ContractError::ConflictQueued => {
                 ErrorCategory::StateConflict
             }
@@ -161,12 +193,17 @@ impl ContractError {
             | ContractError::ExpiredAccess
             | ContractError::ConsentRequired
             | ContractError::ConsentExpired
-            | ContractError::LineageAccessDenied => ErrorCategory::Authorization,
+            | ContractError::LineageAccessDenied
+            | ContractError::ProviderNotVerified => ErrorCategory::Authorization,
             ContractError::UserNotFound
             | ContractError::RecordNotFound
             | ContractError::ProviderNotFound
             | ContractError::EmergencyAccessNotFound
             | ContractError::AppointmentNotFound
+            | ContractError::ConsentTemplateNotFound
+            | ContractError::AccessGrantNotFound
+            | ContractError::ConsentBeaconNotFound
+            | ContractError::GrantNotInBeacon
             | ContractError::LineageNodeNotFound
             | ContractError::LineageAncestorMissing => ErrorCategory::NotFound,
             ContractError::ProviderAlreadyRegistered
@@ -219,10 +256,15 @@ impl ContractError {
             | ContractError::ProviderAlreadyRegistered
             | ContractError::DelegationExpired
             | ContractError::RateLimitExceeded
-            | ContractError::NonceAlreadyUsed => ErrorSeverity::Medium,
+            | ContractError::NonceAlreadyUsed
+            | ContractError::ProviderNotVerified => ErrorSeverity::Medium,
             ContractError::EmergencyAccessNotFound
             | ContractError::AppointmentNotFound
-            | ContractError::AppointmentNotVerified => ErrorSeverity::Low,
+            | ContractError::AppointmentNotVerified
+            | ContractError::ConsentTemplateNotFound
+            | ContractError::AccessGrantNotFound
+            | ContractError::ConsentBeaconNotFound
+            | ContractError::GrantNotInBeacon => ErrorSeverity::Low,
             ContractError::VersionConflict | ContractError::ConflictQueued => ErrorSeverity::Medium,
             ContractError::ConflictNotFound => ErrorSeverity::Low,
             ContractError::StorageError | ContractError::TransientFailure => ErrorSeverity::High,
@@ -233,6 +275,21 @@ impl ContractError {
             ContractError::LineageTampered => ErrorSeverity::Critical,
             ContractError::LineageAccessDenied => ErrorSeverity::Medium,
             ContractError::LineageCycleDetected => ErrorSeverity::High,
+            ContractError::EscrowRequestNotFound
+            | ContractError::EscrowRequestExists
+            | ContractError::EscrowAlreadyApproved => ErrorSeverity::Low,
+            ContractError::EscrowNotConfigured
+            | ContractError::EscrowQuorumNotMet
+            | ContractError::EscrowDelayNotElapsed => ErrorSeverity::High,
+            ContractError::ContactChallengeNotFound | ContractError::ContactChallengeExpired => {
+                ErrorSeverity::Low
+            }
+            ContractError::ContactChallengeMismatch => ErrorSeverity::Medium,
+            ContractError::AccessTokenNotFound | ContractError::AccessTokenExpired => {
+                ErrorSeverity::Low
+            }
+            ContractError::AccessTokenRedeemed => ErrorSeverity::Medium,
+            ContractError::SubDelegationDepthExceeded => ErrorSeverity::Medium,
         }
     }
 
@@ -286,6 +343,7 @@ impl ContractError {
             ContractError::EmergencyAccessNotFound => "Emergency access request not found",
             ContractError::AppointmentNotFound => "Appointment not found",
             ContractError::AppointmentNotVerified => "Appointment is not verified",
+            ContractError::ConsentTemplateNotFound => "Consent template not found",
             ContractError::InvalidEmergencyCondition => "Invalid emergency condition provided",
             ContractError::InvalidAttestation => "Invalid emergency attestation provided",
             ContractError::InvalidAppointmentTime => "Invalid appointment time provided",
@@ -308,6 +366,39 @@ impl ContractError {
             ContractError::LineageCycleDetected => {
                 "Operation would create a cycle in the provenance DAG"
             }
+            ContractError::EscrowNotConfigured => "Key escrow has not been configured",
+            ContractError::EscrowRequestNotFound => "No escrow decryption request for this record",
+            ContractError::EscrowRequestExists => {
+                "An escrow decryption request is already pending for this record"
+            }
+            ContractError::EscrowAlreadyApproved => "Escrow agent has already approved",
+            ContractError::EscrowQuorumNotMet => "Escrow agent quorum has not been reached",
+            ContractError::EscrowDelayNotElapsed => "Escrow decryption delay has not elapsed",
+            ContractError::ContactChallengeNotFound => {
+                "No pending emergency contact verification challenge"
+            }
+            ContractError::ContactChallengeExpired => {
+                "Emergency contact verification challenge has expired"
+            }
+            ContractError::ProviderNotVerified => {
+                "Provider must be verified before writing clinical records"
+            }
+            ContractError::ContactChallengeMismatch => {
+                "Caller or challenge does not match the pending contact verification"
+            }
+            ContractError::AccessGrantNotFound => "No access grant exists for this grantee",
+            ContractError::ConsentBeaconNotFound => {
+                "No consent beacon has been published for this patient"
+            }
+            ContractError::GrantNotInBeacon => {
+                "Grantee has no grant in the published consent beacon"
+            }
+            ContractError::AccessTokenNotFound => "Access token is unknown or was revoked",
+            ContractError::AccessTokenExpired => "Access token has expired",
+            ContractError::AccessTokenRedeemed => "Access token has already been redeemed",
+            ContractError::SubDelegationDepthExceeded => {
+                "Sub-delegation would exceed the maximum delegation depth"
+            }
         }
     }
 }
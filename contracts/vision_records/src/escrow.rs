@@ -0,0 +1,83 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+const ESC_CFG: Symbol = symbol_short!("ESC_CFG");
+const ESC_REQ: Symbol = symbol_short!("ESC_REQ");
+
+const TTL_THRESHOLD: u32 = 5184000;
+const TTL_EXTEND_TO: u32 = 10368000;
+
+/// Smallest delay allowed between reaching quorum and decryption.
+pub const MIN_ESCROW_DELAY: u64 = 86_400;
+
+/// Smallest number of agents that must jointly authorize a decryption.
+pub const MIN_ESCROW_QUORUM: u32 = 2;
+
+/// Extends the time-to-live (TTL) for escrow request storage keys.
+fn extend_ttl_escrow_key(env: &Env, key: &(Symbol, u64)) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// Escrow agents allowed to jointly authorize break-glass decryption.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowConfig {
+    pub agents: Vec<Address>,
+    /// Distinct agent approvals required before the delay starts.
+    pub quorum: u32,
+    /// Seconds that must elapse after quorum before decryption.
+    pub delay_seconds: u64,
+}
+
+/// A pending break-glass decryption of a single record.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRequest {
+    pub record_id: u64,
+    pub requester: Address,
+    /// Legal basis supplied by the requester (e.g. a discovery reference).
+    pub reason: String,
+    pub approvals: Vec<Address>,
+    pub requested_at: u64,
+    /// Earliest decryption time; set once quorum is reached.
+    pub unlocks_at: Option<u64>,
+}
+
+// ── Storage Functions ────────────────────────────────────────
+
+pub fn get_config(env: &Env) -> Option<EscrowConfig> {
+    env.storage().instance().get(&ESC_CFG)
+}
+
+pub fn set_config(env: &Env, config: &EscrowConfig) {
+    env.storage().instance().set(&ESC_CFG, config);
+}
+
+pub fn get_request(env: &Env, record_id: u64) -> Option<EscrowRequest> {
+    env.storage().persistent().get(&(ESC_REQ, record_id))
+}
+
+pub fn set_request(env: &Env, request: &EscrowRequest) {
+    let key = (ESC_REQ, request.record_id);
+    env.storage().persistent().set(&key, request);
+    extend_ttl_escrow_key(env, &key);
+}
+
+pub fn remove_request(env: &Env, record_id: u64) {
+    env.storage().persistent().remove(&(ESC_REQ, record_id));
+}
+
+/// Number of approvals on `request` that come from currently configured agents.
+pub fn valid_approvals(config: &EscrowConfig, request: &EscrowRequest) -> u32 {
+    let mut count = 0u32;
+    for agent in request.approvals.iter() {
+        if config.agents.contains(&agent) {
+            count = count.saturating_add(1);
+        }
+    }
+    count
+}
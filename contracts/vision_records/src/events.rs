@@ -6,7 +6,7 @@ use crate::circuit_breaker::PauseScope;
 use crate::emergency::EmergencyCondition;
 use crate::errors::{ErrorCategory, ErrorContext, ErrorSeverity};
 use crate::{AccessLevel, RecordType, Role, VerificationStatus};
-use soroban_sdk::{symbol_short, Address, Env, String};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Symbol, Vec};
 
 /// Event published when the contract is initialized.
 #[soroban_sdk::contracttype]
@@ -137,6 +137,15 @@ pub struct BatchAccessGrantedEvent {
     pub timestamp: u64,
 }
 
+/// Event published when consent is revoked from a batch of grantees.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchConsentRevokedEvent {
+    pub patient: Address,
+    pub count: u32,
+    pub timestamp: u64,
+}
+
 /// Event published when circuit breaker is enabled.
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -544,6 +553,149 @@ pub fn publish_consent_granted(
     env.events().publish(topics, data);
 }
 
+/// Event published when a patient's consent beacon is committed.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentBeaconEvent {
+    pub patient: Address,
+    pub sequence: u32,
+    pub root: BytesN<32>,
+    pub grant_count: u32,
+    pub timestamp: u64,
+}
+
+/// Publishes the Merkle root of a patient's active consents and grants.
+pub fn publish_consent_beacon(
+    env: &Env,
+    patient: Address,
+    sequence: u32,
+    root: BytesN<32>,
+    grant_count: u32,
+) {
+    let topics = (symbol_short!("CST_BCN"), patient.clone());
+    let data = ConsentBeaconEvent {
+        patient,
+        sequence,
+        root,
+        grant_count,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Event published when a one-time access token is minted or revoked.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessTokenEvent {
+    pub patient: Address,
+    pub record_id: u64,
+    pub token_id: BytesN<32>,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Publishes the minting of a one-time access token, identified by its hash.
+pub fn publish_access_token_minted(
+    env: &Env,
+    patient: Address,
+    record_id: u64,
+    token_id: BytesN<32>,
+    expires_at: u64,
+) {
+    let topics = (symbol_short!("TKN_MINT"), patient.clone(), record_id);
+    let data = AccessTokenEvent {
+        patient,
+        record_id,
+        token_id,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Publishes the revocation of an unredeemed one-time access token.
+pub fn publish_access_token_revoked(
+    env: &Env,
+    patient: Address,
+    record_id: u64,
+    token_id: BytesN<32>,
+    expires_at: u64,
+) {
+    let topics = (symbol_short!("TKN_REV"), patient.clone(), record_id);
+    let data = AccessTokenEvent {
+        patient,
+        record_id,
+        token_id,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Event published when a patient's records are merged between providers.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordsMergedEvent {
+    pub patient: Address,
+    pub source_provider: Address,
+    pub target_provider: Address,
+    pub merged: Vec<u64>,
+    /// Records left with the source provider as duplicates.
+    pub skipped: Vec<u64>,
+    pub timestamp: u64,
+}
+
+/// Publishes a cross-provider record merge.
+pub fn publish_records_merged(
+    env: &Env,
+    patient: Address,
+    source_provider: Address,
+    target_provider: Address,
+    merged: Vec<u64>,
+    skipped: Vec<u64>,
+) {
+    let topics = (symbol_short!("REC_MRG"), patient.clone());
+    let data = RecordsMergedEvent {
+        patient,
+        source_provider,
+        target_provider,
+        merged,
+        skipped,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Event published when a one-time access token is redeemed.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessTokenRedeemedEvent {
+    pub patient: Address,
+    pub redeemer: Address,
+    pub record_id: u64,
+    pub token_id: BytesN<32>,
+    pub timestamp: u64,
+}
+
+/// Publishes the redemption of a one-time access token.
+pub fn publish_access_token_redeemed(
+    env: &Env,
+    patient: Address,
+    redeemer: Address,
+    record_id: u64,
+    token_id: BytesN<32>,
+) {
+    let topics = (symbol_short!("TKN_RDM"), redeemer.clone(), record_id);
+    let data = AccessTokenRedeemedEvent {
+        patient,
+        redeemer,
+        record_id,
+        token_id,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
 /// Publishes an event when consent is revoked.
 pub fn publish_consent_revoked(env: &Env, patient: Address, grantee: Address) {
     let topics = (symbol_short!("CST_REV"), patient.clone(), grantee.clone());
@@ -555,6 +707,47 @@ pub fn publish_consent_revoked(env: &Env, patient: Address, grantee: Address) {
     env.events().publish(topics, data);
 }
 
+/// Publishes a single summary event for `revoke_consent_batch`.
+pub fn publish_batch_consent_revoked(env: &Env, patient: Address, count: u32) {
+    let topics = (symbol_short!("BATCH_CR"), patient.clone());
+    let data = BatchConsentRevokedEvent {
+        patient,
+        count,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Event published when a consent template is materialized into a grant.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentTemplateAppliedEvent {
+    pub patient: Address,
+    pub grantee: Address,
+    pub template_id: Symbol,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Publishes an event when a consent template is applied to a grantee.
+pub fn publish_consent_template_applied(
+    env: &Env,
+    patient: Address,
+    grantee: Address,
+    template_id: Symbol,
+    expires_at: u64,
+) {
+    let topics = (symbol_short!("CST_TPL"), patient.clone(), grantee.clone());
+    let data = ConsentTemplateAppliedEvent {
+        patient,
+        grantee,
+        template_id,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
 /// Event published when a patient profile is created.
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -589,6 +782,75 @@ pub fn publish_profile_updated(env: &Env, patient: Address) {
     env.events().publish(topics, data);
 }
 
+/// Event published when an emergency contact completes verification.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyContactVerifiedEvent {
+    pub patient: Address,
+    pub contact: Address,
+    pub verified_at: u64,
+}
+
+pub fn publish_emergency_contact_challenge(
+    env: &Env,
+    patient: Address,
+    contact: Address,
+    challenge: BytesN<32>,
+) {
+    let topics = (symbol_short!("ECV_REQ"), patient, contact);
+    env.events().publish(topics, challenge);
+}
+
+pub fn publish_emergency_contact_verified(env: &Env, patient: Address, contact: Address) {
+    let topics = (symbol_short!("ECV_OK"), patient.clone());
+    let data = EmergencyContactVerifiedEvent {
+        patient,
+        contact,
+        verified_at: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Event published when an expired auto-renewing access grant is extended.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessRenewedEvent {
+    pub patient: Address,
+    pub grantee: Address,
+    pub expires_at: u64,
+    pub renewal_count: u32,
+    pub timestamp: u64,
+}
+
+pub fn publish_access_renewed(
+    env: &Env,
+    patient: Address,
+    grantee: Address,
+    expires_at: u64,
+    renewal_count: u32,
+) {
+    let topics = (symbol_short!("ACC_RNW"), patient.clone(), grantee.clone());
+    let data = AccessRenewedEvent {
+        patient,
+        grantee,
+        expires_at,
+        renewal_count,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_access_auto_renew_set(
+    env: &Env,
+    patient: Address,
+    grantee: Address,
+    enabled: bool,
+    renew_interval: u64,
+) {
+    let topics = (symbol_short!("ACC_ARNW"), patient, grantee);
+    env.events().publish(topics, (enabled, renew_interval));
+}
+
 /// Event published when an error occurs.
 /// This event includes error code, category, severity, message, user, resource ID, retryable flag, and timestamp.
 #[soroban_sdk::contracttype]
@@ -973,6 +1235,43 @@ pub fn publish_appointment_verified(
     env.events().publish(topics, data);
 }
 
+/// Event published when scheduling an appointment grants its provider
+/// time-boxed access to the patient's records.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppointmentAccessGrantedEvent {
+    pub appointment_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub level: AccessLevel,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub timestamp: u64,
+}
+
+/// Publishes an event when an appointment auto-grants record access.
+pub fn publish_appointment_access_granted(
+    env: &Env,
+    appointment_id: u64,
+    patient: Address,
+    provider: Address,
+    level: AccessLevel,
+    starts_at: u64,
+    ends_at: u64,
+) {
+    let topics = (symbol_short!("APPT_ACC"), patient.clone(), provider.clone());
+    let data = AppointmentAccessGrantedEvent {
+        appointment_id,
+        patient,
+        provider,
+        level,
+        starts_at,
+        ends_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
 /// Event published when an audit log entry is created.
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -1170,3 +1469,157 @@ pub fn publish_sensitivity_set(
     };
     env.events().publish(topics, data);
 }
+
+/// Event published at every step of a break-glass escrow decryption.
+///
+/// Always carries `ErrorSeverity::High` so monitoring treats it as an alert.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowDecryptionEvent {
+    pub record_id: u64,
+    pub patient: Address,
+    pub agent: Address,
+    pub approvals: u32,
+    pub quorum: u32,
+    pub unlocks_at: Option<u64>,
+    pub severity: ErrorSeverity,
+    pub timestamp: u64,
+}
+
+/// Event published when an emergency responder breaks glass on a record.
+/// Always carries `ErrorSeverity::High` so monitoring treats it as an alert.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyRecordReadEvent {
+    pub record_id: u64,
+    pub patient: Address,
+    pub responder: Address,
+    pub justification: String,
+    pub severity: ErrorSeverity,
+    pub timestamp: u64,
+}
+
+/// Publishes a break-glass read of `record_id` by `responder`.
+pub fn publish_emergency_record_read(
+    env: &Env,
+    record_id: u64,
+    patient: Address,
+    responder: Address,
+    justification: String,
+) {
+    let topics = (
+        symbol_short!("EMRG_RD"),
+        responder.clone(),
+        ErrorSeverity::High,
+        record_id,
+    );
+    let data = EmergencyRecordReadEvent {
+        record_id,
+        patient,
+        responder,
+        justification,
+        severity: ErrorSeverity::High,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Publishes an escrow decryption event. `stage` is one of `ESC_REQ`,
+/// `ESC_APP` or `ESC_EXE`.
+pub fn publish_escrow_decryption(
+    env: &Env,
+    stage: Symbol,
+    record_id: u64,
+    patient: Address,
+    agent: Address,
+    approvals: u32,
+    quorum: u32,
+    unlocks_at: Option<u64>,
+) {
+    let topics = (
+        symbol_short!("ESCROW"),
+        stage,
+        ErrorSeverity::High,
+        record_id,
+    );
+    let data = EscrowDecryptionEvent {
+        record_id,
+        patient,
+        agent,
+        approvals,
+        quorum,
+        unlocks_at,
+        severity: ErrorSeverity::High,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Event published when a record's Admin grantee re-shares its access.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessSubDelegatedEvent {
+    pub record_id: u64,
+    pub grantor: Address,
+    pub grantee: Address,
+    pub level: AccessLevel,
+    pub depth: u32,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Publishes a sub-delegated record grant.
+pub fn publish_access_sub_delegated(
+    env: &Env,
+    record_id: u64,
+    grantor: Address,
+    grantee: Address,
+    level: AccessLevel,
+    depth: u32,
+    expires_at: u64,
+) {
+    let topics = (symbol_short!("SUB_DEL"), grantor.clone(), record_id);
+    let data = AccessSubDelegatedEvent {
+        record_id,
+        grantor,
+        grantee,
+        level,
+        depth,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+/// Event published when a record is handed to a new provider.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordOwnershipTransferredEvent {
+    pub record_id: u64,
+    pub patient: Address,
+    pub from_provider: Address,
+    pub to_provider: Address,
+    pub transferred_by: Address,
+    pub timestamp: u64,
+}
+
+/// Publishes a change of a record's responsible provider.
+pub fn publish_record_ownership_transferred(
+    env: &Env,
+    record_id: u64,
+    patient: Address,
+    from_provider: Address,
+    to_provider: Address,
+    transferred_by: Address,
+) {
+    let topics = (symbol_short!("REC_XFER"), record_id, to_provider.clone());
+    let data = RecordOwnershipTransferredEvent {
+        record_id,
+        patient,
+        from_provider,
+        to_provider,
+        transferred_by,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
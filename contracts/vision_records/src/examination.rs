@@ -171,6 +171,33 @@ pub fn set_examination(env: &Env, exam: &EyeExamination, provider: &Address) {
     );
 }
 
+pub fn exam_version_key(record_id: u64, version: u64) -> (Symbol, u64, u64) {
+    (symbol_short!("EXAM_VER"), record_id, version)
+}
+
+/// Returns the examination as it stood at `version`, if a snapshot was taken.
+pub fn get_examination_at_version(
+    env: &Env,
+    record_id: u64,
+    version: u64,
+) -> Option<EyeExamination> {
+    env.storage()
+        .persistent()
+        .get(&exam_version_key(record_id, version))
+}
+
+/// Snapshots are append-only: an existing version is never overwritten.
+fn snapshot_examination(env: &Env, exam: &EyeExamination, version: u64) {
+    let key = exam_version_key(exam.record_id, version);
+    if env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage().persistent().set(&key, exam);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
 pub fn remove_examination(env: &Env, record_id: u64) {
     let key = exam_key(record_id);
     env.storage().persistent().remove(&key);
@@ -182,6 +209,10 @@ pub fn remove_examination(env: &Env, record_id: u64) {
 /// modifications, along with the `node_id` that identifies the provider in
 /// the vector clock and the list of field-level changes.
 ///
+/// Every applied or merged update is snapshotted under its new version, and
+/// the examination it replaces under the prior one, so earlier states stay
+/// readable through [`get_examination_at_version`].
+///
 /// Returns the [`UpdateOutcome`] so the contract layer can decide whether
 /// the update was applied, merged, or queued as a conflict.
 pub fn versioned_set_examination(
@@ -192,6 +223,7 @@ pub fn versioned_set_examination(
     provider: &soroban_sdk::Address,
     changed_fields: &Vec<FieldChange>,
 ) -> UpdateOutcome {
+    let prior_version = concurrency::get_version_stamp(env, exam.record_id).version;
     let outcome = concurrency::compare_and_swap(
         env,
         exam.record_id,
@@ -202,7 +234,12 @@ pub fn versioned_set_examination(
     );
 
     match &outcome {
-        UpdateOutcome::Applied(_) | UpdateOutcome::Merged(_) => {
+        UpdateOutcome::Applied(stamp) | UpdateOutcome::Merged(stamp) => {
+            if let Some(prior) = get_examination(env, exam.record_id) {
+                snapshot_examination(env, &prior, prior_version);
+            }
+            snapshot_examination(env, exam, stamp.version);
+
             let key = exam_key(exam.record_id);
             env.storage().persistent().set(&key, exam);
             extend_ttl_exam_key(env, &key);
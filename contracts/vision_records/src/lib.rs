@@ -7,18 +7,25 @@ use alloc::{
     vec::Vec as StdVec,
 };
 
+pub mod access_token;
 pub mod appointment;
 pub mod audit;
 pub mod circuit_breaker;
+pub mod consent_beacon;
+pub mod consent_template;
 pub mod emergency;
 pub mod errors;
+pub mod escrow;
 pub mod events;
 pub mod examination;
+pub mod patient_index;
 pub mod patient_profile;
+pub mod portability;
 pub mod prescription;
 pub mod provider;
 pub mod rate_limit;
 pub mod rbac;
+pub mod sub_delegation;
 pub mod validation;
 
 use key_manager::{DerivedKey, KeyManagerContractClient};
@@ -48,7 +55,14 @@ pub use provider::VerificationStatus;
 pub use errors::{create_error_context, log_error};
 
 /// Re-export types from submodules used directly in the contract impl.
-pub use audit::{AccessAction, AccessResult};
+pub use access_token::AccessToken;
+pub use appointment::{
+    Appointment, AppointmentAccessWindow, AppointmentAutoAccess, AppointmentStatus, AppointmentType,
+};
+pub use audit::{AccessAction, AccessResult, AuditEntry};
+pub use consent_beacon::{BeaconGrant, ConsentBeacon, ConsentInclusionProof};
+pub use consent_template::ConsentTemplate;
+pub use escrow::{EscrowConfig, EscrowRequest};
 pub use examination::{
     EyeExamination, IntraocularPressure, OptFundusPhotography, OptRetinalImaging, OptVisualField,
     SlitLampFindings, VisualAcuity,
@@ -57,7 +71,9 @@ pub use patient_profile::{
     EmergencyContact, InsuranceInfo, OptionalEmergencyContact, OptionalInsuranceInfo,
     PatientProfile,
 };
+pub use portability::{ExportedRecord, PatientDataExport};
 pub use prescription::{LensType, OptionalContactLensData, Prescription, PrescriptionData};
+pub use sub_delegation::SubDelegation;
 
 /// Storage keys for the contract
 const ADMIN: Symbol = symbol_short!("ADMIN");
@@ -74,6 +90,16 @@ const ENC_KEY: Symbol = symbol_short!("ENC_KEY");
 const KEY_MGR: Symbol = symbol_short!("KEY_MGR");
 const KEY_MGR_KEY: Symbol = symbol_short!("KEY_MGRK");
 
+/// Prefix marking a record's `key_version` as a key-manager derivation
+/// version rather than the name of a local `ENC_KEY` entry.
+const KEY_MGR_VERSION_PREFIX: &str = "km:";
+
+/// Maximum number of entries returned by one `get_actor_access_history` page.
+const MAX_ACTOR_HISTORY_PAGE: u32 = 100;
+
+/// Maximum number of ids returned by one `get_patient_records_paged` page.
+const MAX_PATIENT_RECORDS_PAGE: u32 = 100;
+
 /// Extends the time-to-live (TTL) for a storage key containing an Address.
 /// This ensures the data remains accessible for the extended period.
 fn extend_ttl_address_key(env: &Env, key: &(Symbol, Address)) {
@@ -137,10 +163,98 @@ fn consent_key(patient: &Address, grantee: &Address) -> (Symbol, Address, Addres
     (symbol_short!("CONSENT"), patient.clone(), grantee.clone())
 }
 
-fn has_active_consent(env: &Env, patient: &Address, grantee: &Address) -> bool {
+/// Tracks the grantee address in the patient's grantee list for purge iteration.
+fn track_grantee(env: &Env, patient: &Address, grantee: &Address) {
+    let list_key = (symbol_short!("ACC_LST"), patient.clone());
+    let mut grantees: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&list_key)
+        .unwrap_or(Vec::new(env));
+    // Avoid duplicates: only append if not already present.
+    if !grantees.contains(grantee) {
+        grantees.push_back(grantee.clone());
+        env.storage().persistent().set(&list_key, &grantees);
+    }
+}
+
+/// Removes the grantee address from the patient's grantee list, if present.
+fn untrack_grantee(env: &Env, patient: &Address, grantee: &Address) {
+    let list_key = (symbol_short!("ACC_LST"), patient.clone());
+    let mut grantees: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&list_key)
+        .unwrap_or(Vec::new(env));
+    if let Some(index) = grantees.first_index_of(grantee) {
+        grantees.remove(index);
+        env.storage().persistent().set(&list_key, &grantees);
+    }
+}
+
+/// Default cap on how long an auto-renewing grant may live: 365 days.
+pub const DEFAULT_MAX_GRANT_LIFETIME: u64 = 31_536_000;
+
+fn max_grant_lifetime(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("ACC_MAXLF"))
+        .unwrap_or(DEFAULT_MAX_GRANT_LIFETIME)
+}
+
+/// Extends an expired auto-renewing grant by whole `renew_interval` steps
+/// until it covers the current time, capped at `granted_at` plus the max
+/// grant lifetime. Persists and announces the renewal; returns `None` when
+/// the grant cannot be renewed past `now`.
+fn renew_access_grant(
+    env: &Env,
+    key: &(Symbol, Address, Address),
+    grant: &AccessGrant,
+) -> Option<AccessGrant> {
+    let now = env.ledger().timestamp();
+    if !grant.auto_renew || grant.renew_interval == 0 {
+        return None;
+    }
+    let limit = grant.granted_at.saturating_add(max_grant_lifetime(env));
+    let steps = (now.saturating_sub(grant.expires_at) / grant.renew_interval).saturating_add(1);
+    let expires_at = grant
+        .expires_at
+        .saturating_add(steps.saturating_mul(grant.renew_interval))
+        .min(limit);
+    if expires_at <= now {
+        return None;
+    }
+
+    let mut renewed = grant.clone();
+    renewed.expires_at = expires_at;
+    renewed.renewal_count = renewed.renewal_count.saturating_add(1);
+    env.storage().persistent().set(key, &renewed);
+    extend_ttl_access_key(env, key);
+    events::publish_access_renewed(
+        env,
+        renewed.patient.clone(),
+        renewed.grantee.clone(),
+        expires_at,
+        renewed.renewal_count,
+    );
+    Some(renewed)
+}
+
+/// Whether `grantee` holds live consent from `patient`. With `record_type`
+/// set, the consent must also cover that type.
+fn has_active_consent(
+    env: &Env,
+    patient: &Address,
+    grantee: &Address,
+    record_type: Option<&RecordType>,
+) -> bool {
     let key = consent_key(patient, grantee);
     if let Some(consent) = env.storage().persistent().get::<_, ConsentGrant>(&key) {
-        !consent.revoked && consent.expires_at > env.ledger().timestamp()
+        !consent.revoked
+            && consent.expires_at > env.ledger().timestamp()
+            && record_type.map_or(true, |t| {
+                consent.record_types.is_empty() || consent.record_types.contains(t)
+            })
     } else {
         false
     }
@@ -289,6 +403,11 @@ pub struct AccessGrant {
     pub level: AccessLevel,
     pub granted_at: u64,
     pub expires_at: u64,
+    /// When set, an expired grant is extended by `renew_interval` on the
+    /// next access check instead of lapsing, up to the max grant lifetime.
+    pub auto_renew: bool,
+    pub renew_interval: u64,
+    pub renewal_count: u32,
 }
 
 /// Consent grant structure for patient-to-provider consent tracking
@@ -301,6 +420,8 @@ pub struct ConsentGrant {
     pub granted_at: u64,
     pub expires_at: u64,
     pub revoked: bool,
+    /// Record types the consent covers (empty = all types).
+    pub record_types: Vec<RecordType>,
 }
 
 /// Input for batch record creation
@@ -361,6 +482,160 @@ impl VisionRecordsContract {
         Err(ContractError::AccessDenied)
     }
 
+    /// Decrypts a record's stored `data_hash` with the key version it was
+    /// written under (falling back to the current version). A `km:`-tagged
+    /// version is re-derived through the key manager; any other version is
+    /// looked up in the local key store, with untagged numeric versions from
+    /// before tagging falling back to the key manager. Returns the stored
+    /// value unchanged if no usable key is available.
+    fn decrypt_data_hash(env: &Env, record: &VisionRecord) -> String {
+        let key_ver = record
+            .key_version
+            .clone()
+            .or_else(|| env.storage().instance().get(&ENC_CUR));
+        let mut master_bytes: StdVec<u8> = StdVec::new();
+        if let Some(ver) = key_ver {
+            let name = ver.to_string();
+            if let Some(num) = name.strip_prefix(KEY_MGR_VERSION_PREFIX) {
+                if let Ok(num) = num.parse::<u32>() {
+                    if let Ok(Some((bytes, _))) =
+                        Self::derive_key_manager_bytes(env, record.id, Some(num))
+                    {
+                        master_bytes = bytes;
+                    }
+                }
+            } else if let Some(sv) = env
+                .storage()
+                .persistent()
+                .get::<(Symbol, String), String>(&(ENC_KEY, ver.clone()))
+            {
+                let hex = sv.to_string();
+                if let Some(bytes) = teye_common::hex_to_bytes(&hex) {
+                    master_bytes = bytes;
+                }
+            } else if let Some(num) = Self::parse_key_version_u32(&ver) {
+                if let Ok(Some((bytes, _))) =
+                    Self::derive_key_manager_bytes(env, record.id, Some(num))
+                {
+                    master_bytes = bytes;
+                }
+            }
+        }
+
+        if !master_bytes.is_empty() || record.key_version.is_none() {
+            let km = KeyManager::new(master_bytes);
+            let ciphertext = record.data_hash.to_string();
+            if let Some(plain) = km.decrypt(None, &ciphertext) {
+                return String::from_str(env, &plain);
+            }
+        }
+        record.data_hash.clone()
+    }
+
+    /// Encrypts `data_hash` for `record_id`, with a key derived by the key
+    /// manager when one is configured and the current master key otherwise.
+    /// Returns the value to store together with the version it was written
+    /// under, which is what `decrypt_data_hash` resolves the key from on read.
+    fn encrypt_data_hash(
+        env: &Env,
+        record_id: u64,
+        data_hash: &String,
+    ) -> Result<(String, Option<String>), ContractError> {
+        let mut current_version: Option<String> = env.storage().instance().get(&ENC_CUR);
+        let mut master_bytes: StdVec<u8> = StdVec::new();
+        if let Some((bytes, version)) = Self::derive_key_manager_bytes(env, record_id, None)? {
+            master_bytes = bytes;
+            current_version = Some(version);
+        } else if let Some(ver) = current_version.clone() {
+            if let Some(sv) = env
+                .storage()
+                .persistent()
+                .get::<(Symbol, String), String>(&(ENC_KEY, ver.clone()))
+            {
+                let hex = sv.to_string();
+                if let Some(bytes) = teye_common::hex_to_bytes(&hex) {
+                    master_bytes = bytes;
+                }
+            }
+        }
+        let km = KeyManager::new(master_bytes);
+        let plaintext: StdString = data_hash.to_string();
+        let ciphertext = km.encrypt(None, &plaintext);
+        Ok((String::from_str(env, &ciphertext), current_version))
+    }
+
+    fn load_record(env: &Env, record_id: u64) -> Result<VisionRecord, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("RECORD"), record_id))
+            .ok_or(ContractError::RecordNotFound)
+    }
+
+    /// Enforces the `require_verified_provider` policy for a clinical write
+    /// attributed to `provider`. SystemAdmin callers bypass the check.
+    fn require_verified_provider(
+        env: &Env,
+        caller: &Address,
+        provider: &Address,
+    ) -> Result<(), ContractError> {
+        if !provider::requires_verified_provider(env)
+            || rbac::has_permission(env, caller, &Permission::SystemAdmin)
+            || provider::is_verified(env, provider)
+        {
+            return Ok(());
+        }
+        Err(ContractError::ProviderNotVerified)
+    }
+
+    /// Loads the escrow configuration and checks that `agent` is one of its
+    /// agents.
+    fn require_escrow_agent(
+        env: &Env,
+        agent: &Address,
+        action: &str,
+    ) -> Result<EscrowConfig, ContractError> {
+        let config = escrow::get_config(env).ok_or(ContractError::EscrowNotConfigured)?;
+        if !config.agents.contains(agent) {
+            return Self::unauthorized(env, agent, action, "escrow_agent");
+        }
+        Ok(config)
+    }
+
+    /// Audits one escrow step against the record and publishes its
+    /// high-severity event.
+    fn record_escrow_step(
+        env: &Env,
+        stage: Symbol,
+        agent: &Address,
+        record: &VisionRecord,
+        config: &EscrowConfig,
+        request: &EscrowRequest,
+        reason: String,
+    ) {
+        let audit_entry = audit::create_audit_entry(
+            env,
+            agent.clone(),
+            record.patient.clone(),
+            Some(record.id),
+            AccessAction::EscrowDecryption,
+            AccessResult::Success,
+            Some(reason),
+        );
+        audit::add_audit_entry(env, &audit_entry);
+        events::publish_audit_log_entry(env, &audit_entry);
+
+        events::publish_escrow_decryption(
+            env,
+            stage,
+            record.id,
+            record.patient.clone(),
+            agent.clone(),
+            escrow::valid_approvals(config, request),
+            config.quorum,
+            request.unlocks_at,
+        );
+    }
+
     fn get_key_manager_config(env: &Env) -> Option<(Address, BytesN<32>)> {
         let manager: Option<Address> = env.storage().instance().get(&KEY_MGR);
         let key_id: Option<BytesN<32>> = env.storage().instance().get(&KEY_MGR_KEY);
@@ -370,7 +645,6 @@ impl VisionRecordsContract {
         }
     }
 
-    #[allow(dead_code)]
     fn derive_key_manager_bytes(
         env: &Env,
         record_id: u64,
@@ -387,11 +661,10 @@ impl VisionRecordsContract {
             None => client.derive_record_key(&key_id, &record_id),
         };
         let bytes = derived.key.to_array().to_vec();
-        let version_str = derived.version.to_string();
+        let version_str = alloc::format!("{}{}", KEY_MGR_VERSION_PREFIX, derived.version);
         Ok(Some((bytes, String::from_str(env, &version_str))))
     }
 
-    #[allow(dead_code)]
     fn parse_key_version_u32(version: &String) -> Option<u32> {
         version.to_string().parse::<u32>().ok()
     }
@@ -707,6 +980,9 @@ impl VisionRecordsContract {
 
     /// Set or rotate an encryption master key under a given `version`.
     /// Stores the key bytes persistently under (ENC_KEY, version) and updates current.
+    ///
+    /// Numeric and `km:`-prefixed names are rejected: they identify
+    /// key-manager versions in a record's `key_version`.
     pub fn set_encryption_key(
         env: Env,
         caller: Address,
@@ -715,6 +991,12 @@ impl VisionRecordsContract {
         proposal_id: u64,
     ) -> Result<(), ContractError> {
         caller.require_auth();
+        let name = version.to_string();
+        if name.starts_with(KEY_MGR_VERSION_PREFIX)
+            || Self::parse_key_version_u32(&version).is_some()
+        {
+            return Err(ContractError::InvalidInput);
+        }
 
         if !multisig::is_legacy_admin_allowed(&env) {
             if !multisig::is_executable(&env, proposal_id) {
@@ -758,6 +1040,31 @@ impl VisionRecordsContract {
         Ok(())
     }
 
+    /// Audit only one in every `rate` successful record reads per caller.
+    /// A rate of 1 audits every read; denied reads and writes are always
+    /// audited regardless of the rate.
+    pub fn set_audit_sample_rate(
+        env: Env,
+        caller: Address,
+        rate: u32,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if !admin_tiers::require_tier(&env, &caller, &AdminTier::ContractAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+        if rate == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        audit::set_read_sample_rate(&env, rate);
+        Ok(())
+    }
+
+    /// Return the successful-read audit sampling rate (1 when unset).
+    pub fn get_audit_sample_rate(env: Env) -> u32 {
+        audit::get_read_sample_rate(&env)
+    }
+
     /// Return the current rate limiting configuration, if any.
     pub fn get_rate_limit_config(env: Env) -> Option<(u64, u64)> {
         env.storage().instance().get(&RATE_CFG)
@@ -992,32 +1299,14 @@ impl VisionRecordsContract {
             );
         }
 
+        Self::require_verified_provider(&env, &caller, &provider)?;
+
         // Generate record ID
         let counter_key = symbol_short!("REC_CTR");
         let record_id: u64 = env.storage().instance().get(&counter_key).unwrap_or(0) + 1;
         env.storage().instance().set(&counter_key, &record_id);
 
-        // Determine current encryption key version (if any) and load master bytes
-        let current_version: Option<String> = env.storage().instance().get(&ENC_CUR);
-        let mut master_bytes: StdVec<u8> = StdVec::new();
-        if let Some(ver) = current_version.clone() {
-            if let Some(sv) = env
-                .storage()
-                .persistent()
-                .get::<(Symbol, String), String>(&(ENC_KEY, ver.clone()))
-            {
-                let hex = sv.to_string();
-                if let Some(bytes) = teye_common::hex_to_bytes(&hex) {
-                    master_bytes = bytes;
-                }
-            }
-        }
-        // Build KeyManager and encrypt the provided data_hash
-        let km = KeyManager::new(master_bytes);
-        let plaintext: StdString = data_hash.to_string();
-        let ciphertext = km.encrypt(None, &plaintext);
-        let stored_hash = String::from_str(&env, &ciphertext);
-        let key_version = current_version;
+        let (stored_hash, key_version) = Self::encrypt_data_hash(&env, record_id, &data_hash)?;
 
         let record = VisionRecord {
             id: record_id,
@@ -1039,16 +1328,7 @@ impl VisionRecordsContract {
         Self::meter_op(&env, &provider, MeteringOpType::Write);
 
         // Add to patient's record list
-        let patient_key = (symbol_short!("PAT_REC"), patient.clone());
-        let mut patient_records: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&patient_key)
-            .unwrap_or(Vec::new(&env));
-        patient_records.push_back(record_id);
-        env.storage()
-            .persistent()
-            .set(&patient_key, &patient_records);
+        patient_index::append(&env, &patient, record_id);
 
         // Initialize OCC version tracking
         teye_common::concurrency::init_record_version(&env, record_id, 1);
@@ -1067,78 +1347,54 @@ impl VisionRecordsContract {
     }
 
     /// Add multiple vision records in a single transaction.
-    /// Validates provider permission once, then creates all records atomically.
+    /// Validates `caller`'s permission to write for `provider` once, then
+    /// creates all records atomically.
     #[allow(clippy::arithmetic_side_effects)]
     pub fn add_records(
         env: Env,
+        caller: Address,
         provider: Address,
         records: Vec<BatchRecordInput>,
     ) -> Result<Vec<u64>, ContractError> {
         circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
-        provider.require_auth();
+        caller.require_auth();
 
         if records.is_empty() {
             return Err(ContractError::InvalidInput);
         }
 
-        if !whitelist::check_whitelist_access(&env, &provider) {
-            return Self::unauthorized(&env, &provider, "add_records", "whitelisted_provider");
+        if !whitelist::check_whitelist_access(&env, &caller) {
+            return Self::unauthorized(&env, &caller, "add_records", "whitelisted_caller");
         }
 
-        // Check provider has WriteRecord permission once for the whole batch
-        if !rbac::has_permission(&env, &provider, &Permission::WriteRecord)
-            && !rbac::has_permission(&env, &provider, &Permission::SystemAdmin)
-        {
+        // Check WriteRecord once for the whole batch, with the same
+        // caller/provider rules as `add_record`.
+        let has_perm = if caller == provider {
+            rbac::has_permission(&env, &caller, &Permission::WriteRecord)
+        } else {
+            rbac::has_delegated_permission(&env, &provider, &caller, &Permission::WriteRecord)
+        };
+        if !has_perm && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
             return Self::unauthorized(
                 &env,
-                &provider,
+                &caller,
                 "add_records",
                 "permission:WriteRecord_or_SystemAdmin",
             );
         }
 
+        Self::require_verified_provider(&env, &caller, &provider)?;
+
         let counter_key = symbol_short!("REC_CTR");
         let mut current_id: u64 = env.storage().instance().get(&counter_key).unwrap_or(0);
         let mut record_ids = Vec::new(&env);
 
-        // Load current encryption key/version once for the batch
-        let current_version: Option<String> = env.storage().instance().get(&ENC_CUR);
-        let key_manager_cfg = Self::get_key_manager_config(&env);
-        let key_manager_client = key_manager_cfg
-            .as_ref()
-            .map(|(mgr, _)| KeyManagerContractClient::new(&env, mgr));
-        let mut master_bytes_batch: StdVec<u8> = StdVec::new();
-        if let Some(ver) = current_version.clone() {
-            if let Some(sv) = env
-                .storage()
-                .persistent()
-                .get::<(Symbol, String), String>(&(ENC_KEY, ver.clone()))
-            {
-                let hex = sv.to_string();
-                if let Some(bytes) = teye_common::hex_to_bytes(&hex) {
-                    master_bytes_batch = bytes;
-                }
-            }
-        }
-
         for input in records.iter() {
             current_id += 1;
 
-            let mut master_bytes = master_bytes_batch.clone();
-            let mut key_version = current_version.clone();
-            if let Some((_, key_id)) = key_manager_cfg.as_ref() {
-                if let Some(client) = key_manager_client.as_ref() {
-                    let derived = client.derive_record_key(key_id, &current_id);
-                    master_bytes = derived.key.to_array().to_vec();
-                    key_version = Some(String::from_str(&env, &derived.version.to_string()));
-                }
-            }
-
-            // Encrypt input.data_hash with master bytes
-            let km = KeyManager::new(master_bytes);
-            let plaintext: StdString = input.data_hash.to_string();
-            let ciphertext = km.encrypt(None, &plaintext);
-            let stored_hash = String::from_str(&env, &ciphertext);
+            // Same key selection as `add_record`, so `get_record` can decrypt it.
+            let (stored_hash, key_version) =
+                Self::encrypt_data_hash(&env, current_id, &input.data_hash)?;
 
             let record = VisionRecord {
                 id: current_id,
@@ -1155,16 +1411,7 @@ impl VisionRecordsContract {
             env.storage().persistent().set(&key, &record);
             teye_common::concurrency::init_record_version(&env, current_id, 0);
 
-            let patient_key = (symbol_short!("PAT_REC"), input.patient.clone());
-            let mut patient_records: Vec<u64> = env
-                .storage()
-                .persistent()
-                .get(&patient_key)
-                .unwrap_or(Vec::new(&env));
-            patient_records.push_back(current_id);
-            env.storage()
-                .persistent()
-                .set(&patient_key, &patient_records);
+            patient_index::append(&env, &input.patient, current_id);
 
             events::publish_record_added(
                 &env,
@@ -1203,6 +1450,17 @@ impl VisionRecordsContract {
         record_id: u64,
     ) -> Result<VisionRecord, ContractError> {
         caller.require_auth();
+        Self::read_record(env, caller, record_id)
+    }
+
+    /// Access-checked, audited read behind `get_record`, for entry points
+    /// that have already authorized `caller` (a second `require_auth` for
+    /// the same address in one invocation traps).
+    fn read_record(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+    ) -> Result<VisionRecord, ContractError> {
         let key = (symbol_short!("RECORD"), record_id);
         match env.storage().persistent().get::<_, VisionRecord>(&key) {
             Some(record) => {
@@ -1212,18 +1470,17 @@ impl VisionRecordsContract {
                     // Provider can read records they created
                     true
                 } else {
-                    // Check if caller has broad read permissions, active consent, or explicit grant
+                    // Check if caller has broad read permissions, consent covering this
+                    // record's type, or an explicit record grant. A patient-level access
+                    // grant only applies under consent, so it needs no separate check.
                     rbac::has_permission(&env, &caller, &Permission::ReadAnyRecord)
                         || rbac::has_permission(&env, &caller, &Permission::SystemAdmin)
-                        || has_active_consent(&env, &record.patient, &caller)
-                        || {
-                            let access_level = Self::check_access(
-                                env.clone(),
-                                record.patient.clone(),
-                                caller.clone(),
-                            );
-                            access_level != AccessLevel::None
-                        }
+                        || has_active_consent(
+                            &env,
+                            &record.patient,
+                            &caller,
+                            Some(&record.record_type),
+                        )
                         || Self::check_record_access(env.clone(), record_id, caller.clone())
                             != AccessLevel::None
                 };
@@ -1245,50 +1502,27 @@ impl VisionRecordsContract {
                     return Self::unauthorized(&env, &caller, "get_record", "record_read_access");
                 }
 
-                // Log successful access
-                let audit_entry = audit::create_audit_entry(
-                    &env,
-                    caller.clone(),
-                    record.patient.clone(),
-                    Some(record_id),
-                    AccessAction::Read,
-                    AccessResult::Success,
-                    None,
-                );
-                audit::add_audit_entry(&env, &audit_entry);
-                events::publish_audit_log_entry(&env, &audit_entry);
+                // Log successful access, subject to the read sampling rate
+                if audit::should_audit_read(&env, &caller) {
+                    let audit_entry = audit::create_audit_entry(
+                        &env,
+                        caller.clone(),
+                        record.patient.clone(),
+                        Some(record_id),
+                        AccessAction::Read,
+                        AccessResult::Success,
+                        None,
+                    );
+                    audit::add_audit_entry(&env, &audit_entry);
+                    events::publish_audit_log_entry(&env, &audit_entry);
+                }
 
                 // Meter: read operation for the caller.
                 Self::meter_op(&env, &caller, MeteringOpType::Read);
 
                 // Decrypt data_hash for authorized caller before returning
                 let mut out_record = record.clone();
-                // Prefer record's key_version, fall back to current instance version
-                let key_ver = out_record
-                    .key_version
-                    .clone()
-                    .or_else(|| env.storage().instance().get(&ENC_CUR));
-                let mut master_bytes: StdVec<u8> = StdVec::new();
-                if let Some(ver) = key_ver {
-                    if let Some(sv) = env
-                        .storage()
-                        .persistent()
-                        .get::<(Symbol, String), String>(&(ENC_KEY, ver.clone()))
-                    {
-                        let hex = sv.to_string();
-                        if let Some(bytes) = teye_common::hex_to_bytes(&hex) {
-                            master_bytes = bytes;
-                        }
-                    }
-                }
-
-                if !master_bytes.is_empty() || out_record.key_version.is_none() {
-                    let km = KeyManager::new(master_bytes);
-                    let ciphertext = out_record.data_hash.to_string();
-                    if let Some(plain) = km.decrypt(None, &ciphertext) {
-                        out_record.data_hash = String::from_str(&env, &plain);
-                    }
-                }
+                out_record.data_hash = Self::decrypt_data_hash(&env, &record);
 
                 Ok(out_record)
             }
@@ -1344,7 +1578,7 @@ impl VisionRecordsContract {
         circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
         caller.require_auth();
 
-        let record = Self::get_record(env.clone(), caller.clone(), record_id)?;
+        let record = Self::read_record(env.clone(), caller.clone(), record_id)?;
 
         let has_perm = if caller == record.provider {
             rbac::has_permission(&env, &caller, &Permission::WriteRecord)
@@ -1366,6 +1600,8 @@ impl VisionRecordsContract {
             );
         }
 
+        Self::require_verified_provider(&env, &caller, &record.provider)?;
+
         if record.record_type != RecordType::Examination {
             return Err(ContractError::InvalidRecordType);
         }
@@ -1416,7 +1652,7 @@ impl VisionRecordsContract {
         circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
         caller.require_auth();
 
-        let record = Self::get_record(env.clone(), caller.clone(), record_id)?;
+        let record = Self::read_record(env.clone(), caller.clone(), record_id)?;
 
         let has_perm = if caller == record.provider {
             rbac::has_permission(&env, &caller, &Permission::WriteRecord)
@@ -1472,9 +1708,37 @@ impl VisionRecordsContract {
         record_id: u64,
     ) -> Result<EyeExamination, ContractError> {
         caller.require_auth();
-        let record = Self::get_record(env.clone(), caller.clone(), record_id)?;
+        Self::require_exam_read(&env, &caller, record_id, "get_eye_examination")?;
+        examination::get_examination(&env, record_id).ok_or(ContractError::RecordNotFound)
+    }
+
+    /// Get an eye examination as it stood at a given OCC version.
+    ///
+    /// Snapshots are taken as `update_examination_versioned` applies changes,
+    /// along with the state each update replaces; any other version returns
+    /// `RecordNotFound`.
+    pub fn get_examination_at_version(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+        version: u64,
+    ) -> Result<EyeExamination, ContractError> {
+        caller.require_auth();
+        Self::require_exam_read(&env, &caller, record_id, "get_examination_at_version")?;
+        examination::get_examination_at_version(&env, record_id, version)
+            .ok_or(ContractError::RecordNotFound)
+    }
+
+    /// Read ACL shared by the examination getters.
+    fn require_exam_read(
+        env: &Env,
+        caller: &Address,
+        record_id: u64,
+        operation: &str,
+    ) -> Result<(), ContractError> {
+        let record = Self::read_record(env.clone(), caller.clone(), record_id)?;
 
-        let has_perm = if caller == record.patient || caller == record.provider {
+        let has_perm = if *caller == record.patient || *caller == record.provider {
             true
         } else {
             let access = Self::check_access(env.clone(), record.patient.clone(), caller.clone());
@@ -1484,14 +1748,13 @@ impl VisionRecordsContract {
                 || access == AccessLevel::Full
                 || access == AccessLevel::Admin
                 || record_access != AccessLevel::None
-                || rbac::has_permission(&env, &caller, &Permission::SystemAdmin)
+                || rbac::has_permission(env, caller, &Permission::SystemAdmin)
         };
 
         if !has_perm {
-            return Self::access_denied(&env, &caller, "get_eye_examination", "record_read_access");
+            return Self::access_denied(env, caller, operation, "record_read_access");
         }
-
-        examination::get_examination(&env, record_id).ok_or(ContractError::RecordNotFound)
+        Ok(())
     }
 
     /// Return the current OCC version stamp for a record.
@@ -1508,7 +1771,7 @@ impl VisionRecordsContract {
     ) -> Result<(), ContractError> {
         caller.require_auth();
 
-        let record = Self::get_record(env.clone(), caller.clone(), record_id)?;
+        let record = Self::read_record(env.clone(), caller.clone(), record_id)?;
         let has_perm = if caller == record.provider {
             rbac::has_permission(&env, &caller, &Permission::WriteRecord)
         } else {
@@ -1594,11 +1857,27 @@ impl VisionRecordsContract {
 
     /// Get all records for a patient
     pub fn get_patient_records(env: Env, patient: Address) -> Vec<u64> {
-        let key = (symbol_short!("PAT_REC"), patient);
-        env.storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(&env))
+        patient_index::all(&env, &patient)
+    }
+
+    /// Number of record ids indexed for a patient.
+    pub fn get_patient_record_count(env: Env, patient: Address) -> u32 {
+        patient_index::len(&env, &patient)
+    }
+
+    /// Get up to `limit` of a patient's record ids starting at `offset`, in
+    /// the order they were added. `limit` is capped at
+    /// `MAX_PATIENT_RECORDS_PAGE`; an offset past the end yields an empty page.
+    ///
+    /// The index is stored in chunks of `PATIENT_INDEX_CHUNK` ids, and a page
+    /// reads only the chunks it overlaps.
+    pub fn get_patient_records_paged(
+        env: Env,
+        patient: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        patient_index::page(&env, &patient, offset, limit.min(MAX_PATIENT_RECORDS_PAGE))
     }
 
     /// Grant access to a user
@@ -1659,31 +1938,16 @@ impl VisionRecordsContract {
             level: level.clone(),
             granted_at: env.ledger().timestamp(),
             expires_at,
+            auto_renew: false,
+            renew_interval: 0,
+            renewal_count: 0,
         };
 
         let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
         env.storage().persistent().set(&key, &grant);
         extend_ttl_access_key(&env, &key);
 
-        // Track the grantee address in the patient's grantee list for purge iteration.
-        let list_key = (symbol_short!("ACC_LST"), patient.clone());
-        let mut grantees: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&list_key)
-            .unwrap_or(Vec::new(&env));
-        // Avoid duplicates: only append if not already present.
-        let mut found = false;
-        for i in 0..grantees.len() {
-            if grantees.get(i) == Some(grantee.clone()) {
-                found = true;
-                break;
-            }
-        }
-        if !found {
-            grantees.push_back(grantee.clone());
-            env.storage().persistent().set(&list_key, &grantees);
-        }
+        track_grantee(&env, &patient, &grantee);
 
         events::publish_access_granted(
             &env,
@@ -1735,6 +1999,9 @@ impl VisionRecordsContract {
                 level: grant.level.clone(),
                 granted_at: now,
                 expires_at,
+                auto_renew: false,
+                renew_interval: 0,
+                renewal_count: 0,
             };
             let key = (
                 symbol_short!("ACCESS"),
@@ -1761,13 +2028,18 @@ impl VisionRecordsContract {
     /// Check access level with ABAC policy evaluation
     pub fn check_access(env: Env, patient: Address, grantee: Address) -> AccessLevel {
         // First check traditional consent-based access
-        if !has_active_consent(&env, &patient, &grantee) {
+        if !has_active_consent(&env, &patient, &grantee, None) {
             return AccessLevel::None;
         }
 
         let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
 
-        if let Some(grant) = env.storage().persistent().get::<_, AccessGrant>(&key) {
+        if let Some(mut grant) = env.storage().persistent().get::<_, AccessGrant>(&key) {
+            if grant.expires_at <= env.ledger().timestamp() {
+                if let Some(renewed) = renew_access_grant(&env, &key, &grant) {
+                    grant = renewed;
+                }
+            }
             if grant.expires_at > env.ledger().timestamp() {
                 // Check if ABAC policies also allow this access
                 let abac_allowed =
@@ -1780,28 +2052,89 @@ impl VisionRecordsContract {
         AccessLevel::None
     }
 
-    /// Grant record-level access to a specific record.
-    #[allow(clippy::arithmetic_side_effects)]
-    pub fn grant_record_access(
+    /// Enable or disable auto-renewal on an existing access grant.
+    /// Only the patient may change this; `renew_interval` must be non-zero
+    /// when enabling.
+    pub fn set_grant_auto_renew(
         env: Env,
         patient: Address,
         grantee: Address,
-        record_id: u64,
-        level: AccessLevel,
-        duration_seconds: u64,
+        enabled: bool,
+        renew_interval: u64,
     ) -> Result<(), ContractError> {
-        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
         patient.require_auth();
-        validation::validate_duration(duration_seconds)?;
+        if enabled {
+            validation::validate_duration(renew_interval)?;
+        }
 
-        let record_key = (symbol_short!("RECORD"), record_id);
-        let record: VisionRecord = env
+        let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
+        let mut grant: AccessGrant = env
             .storage()
             .persistent()
-            .get(&record_key)
-            .ok_or(ContractError::RecordNotFound)?;
-        if record.patient != patient {
-            return Self::unauthorized(&env, &patient, "grant_record_access", "record_owner");
+            .get(&key)
+            .ok_or(ContractError::AccessGrantNotFound)?;
+        grant.auto_renew = enabled;
+        if enabled {
+            grant.renew_interval = renew_interval;
+        }
+        env.storage().persistent().set(&key, &grant);
+        extend_ttl_access_key(&env, &key);
+
+        events::publish_access_auto_renew_set(&env, patient, grantee, enabled, renew_interval);
+        Ok(())
+    }
+
+    /// Set the maximum total lifetime (from `granted_at`) that auto-renewal
+    /// may extend a grant to. Requires ContractAdmin tier.
+    pub fn set_max_grant_lifetime(
+        env: Env,
+        caller: Address,
+        seconds: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if !admin_tiers::require_tier(&env, &caller, &AdminTier::ContractAdmin) {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "set_max_grant_lifetime",
+                "admin_tier:ContractAdmin",
+            );
+        }
+        if seconds == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ACC_MAXLF"), &seconds);
+        Ok(())
+    }
+
+    pub fn get_max_grant_lifetime(env: Env) -> u64 {
+        max_grant_lifetime(&env)
+    }
+
+    /// Grant record-level access to a specific record.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn grant_record_access(
+        env: Env,
+        patient: Address,
+        grantee: Address,
+        record_id: u64,
+        level: AccessLevel,
+        duration_seconds: u64,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+        validation::validate_duration(duration_seconds)?;
+
+        let record_key = (symbol_short!("RECORD"), record_id);
+        let record: VisionRecord = env
+            .storage()
+            .persistent()
+            .get(&record_key)
+            .ok_or(ContractError::RecordNotFound)?;
+        if record.patient != patient {
+            return Self::unauthorized(&env, &patient, "grant_record_access", "record_owner");
         }
 
         let now = env.ledger().timestamp();
@@ -1812,11 +2145,16 @@ impl VisionRecordsContract {
             level: level.clone(),
             granted_at: now,
             expires_at,
+            auto_renew: false,
+            renew_interval: 0,
+            renewal_count: 0,
         };
 
         let key = (symbol_short!("REC_ACC"), record_id, grantee.clone());
         env.storage().persistent().set(&key, &grant);
         extend_ttl_record_access_key(&env, &key);
+        // A direct grant replaces any sub-delegated one for this grantee.
+        sub_delegation::detach(&env, record_id, &grantee);
 
         events::publish_record_access_granted(
             &env,
@@ -1830,85 +2168,912 @@ impl VisionRecordsContract {
         Ok(())
     }
 
-    /// Check record-level access for a specific grantee.
-    pub fn check_record_access(env: Env, record_id: u64, grantee: Address) -> AccessLevel {
-        let key = (symbol_short!("REC_ACC"), record_id, grantee);
-        if let Some(grant) = env.storage().persistent().get::<_, AccessGrant>(&key) {
-            if grant.expires_at > env.ledger().timestamp() {
-                return grant.level;
-            }
+    /// Check record-level access for a specific grantee.
+    ///
+    /// Falls back to an appointment access window on the record's patient
+    /// that covers the record's type, while the patient's appointment
+    /// auto-access consent stands.
+    pub fn check_record_access(env: Env, record_id: u64, grantee: Address) -> AccessLevel {
+        let key = (symbol_short!("REC_ACC"), record_id, grantee.clone());
+        if let Some(grant) = env.storage().persistent().get::<_, AccessGrant>(&key) {
+            if grant.expires_at > env.ledger().timestamp() {
+                return grant.level;
+            }
+        }
+        let record_key = (symbol_short!("RECORD"), record_id);
+        if let Some(record) = env
+            .storage()
+            .persistent()
+            .get::<_, VisionRecord>(&record_key)
+        {
+            if appointment::get_auto_access(&env, &record.patient).is_some() {
+                return appointment::active_window_level(
+                    &env,
+                    &record.patient,
+                    &grantee,
+                    &record.record_type,
+                );
+            }
+        }
+        AccessLevel::None
+    }
+
+    /// Revoke record-level access for a specific record.
+    pub fn revoke_record_access(
+        env: Env,
+        patient: Address,
+        grantee: Address,
+        record_id: u64,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+        let record_key = (symbol_short!("RECORD"), record_id);
+        let record: VisionRecord = env
+            .storage()
+            .persistent()
+            .get(&record_key)
+            .ok_or(ContractError::RecordNotFound)?;
+        if record.patient != patient {
+            return Self::unauthorized(&env, &patient, "revoke_record_access", "record_owner");
+        }
+
+        let key = (symbol_short!("REC_ACC"), record_id, grantee.clone());
+        env.storage().persistent().remove(&key);
+
+        // Everything the grantee re-shared, directly or transitively, goes too.
+        for sub_grantee in sub_delegation::remove_subtree(&env, record_id, &grantee).iter() {
+            let key = (symbol_short!("REC_ACC"), record_id, sub_grantee);
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Re-share record access held by `caller` with `new_grantee`.
+    ///
+    /// `caller` must hold an unexpired `Admin` grant on the record. The
+    /// sub-grant may not outrank or outlive the caller's grant, and its depth
+    /// (the caller's depth plus one; direct grants from the patient are depth
+    /// 0) may not exceed `max_sub_delegation_depth`. Revoking any grant in
+    /// the chain revokes every sub-grant beneath it.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn sub_delegate_access(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        new_grantee: Address,
+        record_id: u64,
+        level: AccessLevel,
+        duration_seconds: u64,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        caller.require_auth();
+        validation::validate_duration(duration_seconds)?;
+
+        let record_key = (symbol_short!("RECORD"), record_id);
+        let record: VisionRecord = env
+            .storage()
+            .persistent()
+            .get(&record_key)
+            .ok_or(ContractError::RecordNotFound)?;
+        if record.patient != patient
+            || level == AccessLevel::None
+            || new_grantee == caller
+            || new_grantee == patient
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let now = env.ledger().timestamp();
+        let parent_key = (symbol_short!("REC_ACC"), record_id, caller.clone());
+        let parent = match env
+            .storage()
+            .persistent()
+            .get::<_, AccessGrant>(&parent_key)
+        {
+            Some(grant) if grant.expires_at > now && grant.level == AccessLevel::Admin => grant,
+            _ => return Self::unauthorized(&env, &caller, "sub_delegate_access", "record_admin"),
+        };
+
+        let expires_at = now + duration_seconds;
+        if sub_delegation::level_rank(&level) > sub_delegation::level_rank(&parent.level)
+            || expires_at > parent.expires_at
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let depth = sub_delegation::depth_of(&env, record_id, &caller) + 1;
+        if depth > sub_delegation::max_depth(&env) {
+            return Err(ContractError::SubDelegationDepthExceeded);
+        }
+
+        // Don't overwrite live access; a lapsed sub-grant (and the lapsed
+        // sub-grants beneath it) is cleared to make room.
+        if Self::check_record_access(env.clone(), record_id, new_grantee.clone())
+            != AccessLevel::None
+        {
+            return Err(ContractError::InvalidInput);
+        }
+        for stale in sub_delegation::remove_subtree(&env, record_id, &new_grantee).iter() {
+            let key = (symbol_short!("REC_ACC"), record_id, stale);
+            env.storage().persistent().remove(&key);
+        }
+
+        let grant = AccessGrant {
+            patient: patient.clone(),
+            grantee: new_grantee.clone(),
+            level: level.clone(),
+            granted_at: now,
+            expires_at,
+            auto_renew: false,
+            renew_interval: 0,
+            renewal_count: 0,
+        };
+        let key = (symbol_short!("REC_ACC"), record_id, new_grantee.clone());
+        env.storage().persistent().set(&key, &grant);
+        extend_ttl_record_access_key(&env, &key);
+
+        sub_delegation::add(
+            &env,
+            &SubDelegation {
+                record_id,
+                grantor: caller.clone(),
+                grantee: new_grantee.clone(),
+                level: level.clone(),
+                depth,
+                granted_at: now,
+                expires_at,
+            },
+        );
+
+        events::publish_access_sub_delegated(
+            &env,
+            record_id,
+            caller,
+            new_grantee,
+            level,
+            depth,
+            expires_at,
+        );
+        Ok(())
+    }
+
+    /// Every sub-delegated grant issued on `record_id`, as grantor → grantee
+    /// edges with their depth.
+    pub fn get_sub_delegation_tree(env: Env, record_id: u64) -> Vec<SubDelegation> {
+        sub_delegation::get_tree(&env, record_id)
+    }
+
+    /// Set how many levels of re-sharing below a patient's direct grant are
+    /// allowed (0 disables sub-delegation). Requires ContractAdmin tier.
+    pub fn set_max_sub_delegation_depth(
+        env: Env,
+        caller: Address,
+        depth: u32,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if !admin_tiers::require_tier(&env, &caller, &AdminTier::ContractAdmin) {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "set_max_sub_delegation_depth",
+                "admin_tier:ContractAdmin",
+            );
+        }
+        sub_delegation::set_max_depth(&env, depth);
+        Ok(())
+    }
+
+    pub fn get_max_sub_delegation_depth(env: Env) -> u32 {
+        sub_delegation::max_depth(&env)
+    }
+
+    /// Consent to appointment-based access: every appointment later
+    /// scheduled for `patient` grants its provider `level` access to the
+    /// patient's records of `record_types` over `[scheduled_at - pre_window,
+    /// scheduled_at + post_window)`. Windows lapse on their own, and are
+    /// deleted if the consent is revoked.
+    pub fn set_appointment_auto_access(
+        env: Env,
+        patient: Address,
+        level: AccessLevel,
+        record_types: Vec<RecordType>,
+        pre_window: u64,
+        post_window: u64,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+        // Admin would let the provider re-share; None grants nothing.
+        if matches!(level, AccessLevel::None | AccessLevel::Admin) || record_types.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+        validation::validate_duration(pre_window.saturating_add(post_window))?;
+
+        appointment::set_auto_access(
+            &env,
+            &patient,
+            &AppointmentAutoAccess {
+                level,
+                record_types,
+                pre_window,
+                post_window,
+            },
+        );
+        Ok(())
+    }
+
+    /// Withdraw appointment auto-access consent and delete every window it
+    /// granted; consenting again only covers appointments scheduled after.
+    pub fn revoke_appointment_auto_access(env: Env, patient: Address) -> Result<(), ContractError> {
+        patient.require_auth();
+        appointment::remove_auto_access(&env, &patient);
+        Ok(())
+    }
+
+    pub fn get_appointment_auto_access(
+        env: Env,
+        patient: Address,
+    ) -> Option<AppointmentAutoAccess> {
+        appointment::get_auto_access(&env, &patient)
+    }
+
+    /// Schedule an appointment between `patient` and `provider`; `caller`
+    /// must be one of them.
+    ///
+    /// If the patient has set appointment auto-access consent and schedules
+    /// the appointment themselves, the provider is granted a record access
+    /// window around `scheduled_at`. Provider-scheduled appointments grant
+    /// nothing until the patient calls `confirm_appointment`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn schedule_appointment(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        provider: Address,
+        appointment_type: AppointmentType,
+        scheduled_at: u64,
+        duration_minutes: u32,
+        notes: Option<String>,
+    ) -> Result<u64, ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        caller.require_auth();
+        if caller != patient && caller != provider {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "schedule_appointment",
+                "patient_or_provider",
+            );
+        }
+
+        let now = env.ledger().timestamp();
+        if scheduled_at <= now {
+            return Err(ContractError::InvalidAppointmentTime);
+        }
+        if duration_minutes == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let appointment = Appointment {
+            id: appointment::increment_appointment_counter(&env),
+            patient: patient.clone(),
+            provider: provider.clone(),
+            appointment_type: appointment_type.clone(),
+            scheduled_at,
+            duration_minutes,
+            status: AppointmentStatus::Scheduled,
+            notes,
+            created_at: now,
+            updated_at: now,
+            verified_at: None,
+            verified_by: None,
+            reminder_sent: false,
+        };
+        appointment::set_appointment(&env, &appointment);
+        appointment::add_history_entry(
+            &env,
+            &appointment::AppointmentHistoryEntry {
+                appointment_id: appointment.id,
+                action: String::from_str(&env, "CREATED"),
+                actor: caller.clone(),
+                timestamp: now,
+                previous_status: AppointmentStatus::None,
+                new_status: AppointmentStatus::Scheduled,
+                notes: None,
+            },
+        );
+        events::publish_appointment_scheduled(
+            &env,
+            appointment.id,
+            patient.clone(),
+            provider,
+            appointment_type,
+            scheduled_at,
+        );
+
+        if caller == patient {
+            Self::open_appointment_window(&env, &appointment)?;
+        }
+
+        Ok(appointment.id)
+    }
+
+    /// Patient confirmation of a `Scheduled` appointment. This is how an
+    /// appointment booked by the provider picks up the patient's
+    /// auto-access consent.
+    pub fn confirm_appointment(
+        env: Env,
+        patient: Address,
+        appointment_id: u64,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+        let mut appointment = appointment::get_appointment(&env, appointment_id)
+            .ok_or(ContractError::AppointmentNotFound)?;
+        if appointment.patient != patient {
+            return Self::unauthorized(&env, &patient, "confirm_appointment", "patient");
+        }
+        if appointment.status != AppointmentStatus::Scheduled {
+            return Err(ContractError::InvalidAppointmentStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        appointment.status = AppointmentStatus::Confirmed;
+        appointment.updated_at = now;
+        appointment::set_appointment(&env, &appointment);
+        appointment::add_history_entry(
+            &env,
+            &appointment::AppointmentHistoryEntry {
+                appointment_id,
+                action: String::from_str(&env, "CONFIRMED"),
+                actor: patient.clone(),
+                timestamp: now,
+                previous_status: AppointmentStatus::Scheduled,
+                new_status: AppointmentStatus::Confirmed,
+                notes: None,
+            },
+        );
+        events::publish_appointment_confirmed(
+            &env,
+            appointment_id,
+            patient.clone(),
+            appointment.provider.clone(),
+            patient,
+        );
+
+        Self::open_appointment_window(&env, &appointment)
+    }
+
+    /// Grants `appointment.provider` the window described by the patient's
+    /// auto-access consent, if any. The provider must pass the verified
+    /// provider policy.
+    fn open_appointment_window(env: &Env, appointment: &Appointment) -> Result<(), ContractError> {
+        let Some(consent) = appointment::get_auto_access(env, &appointment.patient) else {
+            return Ok(());
+        };
+        Self::require_verified_provider(env, &appointment.provider, &appointment.provider)?;
+
+        let window = AppointmentAccessWindow {
+            appointment_id: appointment.id,
+            level: consent.level,
+            record_types: consent.record_types,
+            starts_at: appointment.scheduled_at.saturating_sub(consent.pre_window),
+            ends_at: appointment.scheduled_at.saturating_add(consent.post_window),
+        };
+        appointment::add_access_window(env, &appointment.patient, &appointment.provider, &window);
+        events::publish_appointment_access_granted(
+            env,
+            appointment.id,
+            appointment.patient.clone(),
+            appointment.provider.clone(),
+            window.level,
+            window.starts_at,
+            window.ends_at,
+        );
+        Ok(())
+    }
+
+    pub fn get_appointment(env: Env, appointment_id: u64) -> Result<Appointment, ContractError> {
+        appointment::get_appointment(&env, appointment_id).ok_or(ContractError::AppointmentNotFound)
+    }
+
+    /// Appointment access windows `provider` holds on `patient`'s records.
+    pub fn get_appointment_access_windows(
+        env: Env,
+        patient: Address,
+        provider: Address,
+    ) -> Vec<AppointmentAccessWindow> {
+        appointment::get_access_windows(&env, &patient, &provider)
+    }
+
+    /// Grant consent for a grantee.
+    pub fn grant_consent(
+        env: Env,
+        patient: Address,
+        grantee: Address,
+        consent_type: ConsentType,
+        duration_seconds: u64,
+    ) -> Result<(), ContractError> {
+        let all_types = Vec::new(&env);
+        Self::grant_scoped_consent(
+            env,
+            patient,
+            grantee,
+            consent_type,
+            duration_seconds,
+            all_types,
+        )
+    }
+
+    /// Grant consent for a grantee that only covers records of `record_types`.
+    /// An empty list covers every type, as `grant_consent` does.
+    pub fn grant_scoped_consent(
+        env: Env,
+        patient: Address,
+        grantee: Address,
+        consent_type: ConsentType,
+        duration_seconds: u64,
+        record_types: Vec<RecordType>,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+        if duration_seconds == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+        let now = env.ledger().timestamp();
+        let consent = ConsentGrant {
+            patient: patient.clone(),
+            grantee: grantee.clone(),
+            consent_type: consent_type.clone(),
+            granted_at: now,
+            expires_at: now.saturating_add(duration_seconds),
+            revoked: false,
+            record_types,
+        };
+        let key = consent_key(&patient, &grantee);
+        env.storage().persistent().set(&key, &consent);
+        extend_ttl_access_key(&env, &key);
+        track_grantee(&env, &patient, &grantee);
+        events::publish_consent_granted(&env, patient, grantee, consent_type, consent.expires_at);
+        Ok(())
+    }
+
+    /// Revoke previously granted consent.
+    pub fn revoke_consent(
+        env: Env,
+        patient: Address,
+        grantee: Address,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+        let key = consent_key(&patient, &grantee);
+        if let Some(mut consent) = env.storage().persistent().get::<_, ConsentGrant>(&key) {
+            consent.revoked = true;
+            env.storage().persistent().set(&key, &consent);
+        }
+        events::publish_consent_revoked(&env, patient, grantee);
+        Ok(())
+    }
+
+    /// Revoke consent from several grantees in a single transaction.
+    /// Patient authorizes once; grantees without an active consent are
+    /// skipped. Returns the number of consents actually revoked.
+    pub fn revoke_consent_batch(
+        env: Env,
+        patient: Address,
+        grantees: Vec<Address>,
+    ) -> Result<u32, ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+
+        if grantees.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut revoked = 0u32;
+        for grantee in grantees.iter() {
+            if !has_active_consent(&env, &patient, &grantee, None) {
+                continue;
+            }
+            let key = consent_key(&patient, &grantee);
+            if let Some(mut consent) = env.storage().persistent().get::<_, ConsentGrant>(&key) {
+                consent.revoked = true;
+                env.storage().persistent().set(&key, &consent);
+                revoked = revoked.saturating_add(1);
+                events::publish_consent_revoked(&env, patient.clone(), grantee);
+            }
+        }
+
+        events::publish_batch_consent_revoked(&env, patient, revoked);
+        Ok(revoked)
+    }
+
+    // ── Consent templates ─────────────────────────────────────────────────────
+
+    /// Create (or replace) a reusable consent template owned by `patient`.
+    ///
+    /// Replacing a template only affects future applications; grants that were
+    /// already materialized from it are left untouched.
+    pub fn create_consent_template(
+        env: Env,
+        patient: Address,
+        template_id: Symbol,
+        consent_type: ConsentType,
+        level: AccessLevel,
+        duration_seconds: u64,
+        record_types: Vec<RecordType>,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+
+        validation::validate_duration(duration_seconds)?;
+        if level == AccessLevel::None {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let now = env.ledger().timestamp();
+        let created_at = consent_template::get_template(&env, &patient, &template_id)
+            .map(|existing| existing.created_at)
+            .unwrap_or(now);
+
+        let template = ConsentTemplate {
+            template_id,
+            patient,
+            consent_type,
+            level,
+            duration_seconds,
+            record_types,
+            created_at,
+            updated_at: now,
+        };
+        consent_template::set_template(&env, &template);
+
+        Ok(())
+    }
+
+    /// Materialize a stored template into a consent and access grant for `grantee`.
+    ///
+    /// The grant is a snapshot of the template at the time of application.
+    pub fn apply_consent_template(
+        env: Env,
+        patient: Address,
+        grantee: Address,
+        template_id: Symbol,
+    ) -> Result<ConsentGrant, ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+
+        let template = consent_template::get_template(&env, &patient, &template_id)
+            .ok_or(ContractError::ConsentTemplateNotFound)?;
+
+        let now = env.ledger().timestamp();
+        let expires_at = now.saturating_add(template.duration_seconds);
+
+        let consent = ConsentGrant {
+            patient: patient.clone(),
+            grantee: grantee.clone(),
+            consent_type: template.consent_type.clone(),
+            granted_at: now,
+            expires_at,
+            revoked: false,
+            record_types: template.record_types.clone(),
+        };
+        let consent_key = consent_key(&patient, &grantee);
+        env.storage().persistent().set(&consent_key, &consent);
+        extend_ttl_access_key(&env, &consent_key);
+
+        let grant = AccessGrant {
+            patient: patient.clone(),
+            grantee: grantee.clone(),
+            level: template.level.clone(),
+            granted_at: now,
+            expires_at,
+            auto_renew: false,
+            renew_interval: 0,
+            renewal_count: 0,
+        };
+        let access_key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
+        env.storage().persistent().set(&access_key, &grant);
+        extend_ttl_access_key(&env, &access_key);
+        track_grantee(&env, &patient, &grantee);
+
+        events::publish_consent_granted(
+            &env,
+            patient.clone(),
+            grantee.clone(),
+            template.consent_type,
+            expires_at,
+        );
+        events::publish_access_granted(
+            &env,
+            patient.clone(),
+            grantee.clone(),
+            template.level,
+            template.duration_seconds,
+            expires_at,
+        );
+        events::publish_consent_template_applied(&env, patient, grantee, template_id, expires_at);
+
+        Ok(consent)
+    }
+
+    /// Return all consent templates stored for `patient`.
+    pub fn list_consent_templates(env: Env, patient: Address) -> Vec<ConsentTemplate> {
+        consent_template::list_templates(&env, &patient)
+    }
+
+    /// Delete one of the patient's consent templates.
+    pub fn delete_consent_template(
+        env: Env,
+        patient: Address,
+        template_id: Symbol,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+        if !consent_template::remove_template(&env, &patient, &template_id) {
+            return Err(ContractError::ConsentTemplateNotFound);
+        }
+        Ok(())
+    }
+
+    /// Configure the escrow agents allowed to jointly authorize break-glass
+    /// decryption of individual records.
+    ///
+    /// Requires at least `ContractAdmin` tier. The quorum must be at least
+    /// `MIN_ESCROW_QUORUM` and the delay at least `MIN_ESCROW_DELAY`.
+    pub fn set_escrow_config(
+        env: Env,
+        caller: Address,
+        agents: Vec<Address>,
+        quorum: u32,
+        delay_seconds: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if !admin_tiers::require_tier(&env, &caller, &AdminTier::ContractAdmin) {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "set_escrow_config",
+                "admin_tier:ContractAdmin",
+            );
+        }
+
+        if quorum < escrow::MIN_ESCROW_QUORUM
+            || quorum > agents.len()
+            || delay_seconds < escrow::MIN_ESCROW_DELAY
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        escrow::set_config(
+            &env,
+            &EscrowConfig {
+                agents,
+                quorum,
+                delay_seconds,
+            },
+        );
+        Ok(())
+    }
+
+    /// Toggle whether clinical writes (`add_record`, `add_records`,
+    /// `prepare_add_record`, `add_eye_examination`, `prepare_add_prescription`)
+    /// require a `Verified` provider.
+    /// Requires at least `ContractAdmin` tier. Off by default.
+    pub fn set_require_verified_provider(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if !admin_tiers::require_tier(&env, &caller, &AdminTier::ContractAdmin) {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "set_require_verified_provider",
+                "admin_tier:ContractAdmin",
+            );
         }
-        AccessLevel::None
+        provider::set_requires_verified_provider(&env, enabled);
+        Ok(())
     }
 
-    /// Revoke record-level access for a specific record.
-    pub fn revoke_record_access(
+    /// Whether clinical writes currently require a `Verified` provider.
+    pub fn get_require_verified_provider(env: Env) -> bool {
+        provider::requires_verified_provider(&env)
+    }
+
+    /// Return the escrow agent configuration, if any.
+    pub fn get_escrow_config(env: Env) -> Option<EscrowConfig> {
+        escrow::get_config(&env)
+    }
+
+    /// Return the pending escrow decryption request for a record, if any.
+    pub fn get_escrow_request(env: Env, record_id: u64) -> Option<EscrowRequest> {
+        escrow::get_request(&env, record_id)
+    }
+
+    /// Open a break-glass decryption request for `record_id`.
+    ///
+    /// The requesting agent counts as the first approval. Only one request
+    /// per record may be pending at a time.
+    pub fn request_escrow_decryption(
         env: Env,
-        patient: Address,
-        grantee: Address,
+        agent: Address,
         record_id: u64,
-    ) -> Result<(), ContractError> {
+        reason: String,
+    ) -> Result<EscrowRequest, ContractError> {
         circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
-        patient.require_auth();
-        let record_key = (symbol_short!("RECORD"), record_id);
-        let record: VisionRecord = env
-            .storage()
-            .persistent()
-            .get(&record_key)
-            .ok_or(ContractError::RecordNotFound)?;
-        if record.patient != patient {
-            return Self::unauthorized(&env, &patient, "revoke_record_access", "record_owner");
+        agent.require_auth();
+
+        let config = Self::require_escrow_agent(&env, &agent, "request_escrow_decryption")?;
+        let record = Self::load_record(&env, record_id)?;
+        if escrow::get_request(&env, record_id).is_some() {
+            return Err(ContractError::EscrowRequestExists);
         }
 
-        let key = (symbol_short!("REC_ACC"), record_id, grantee);
-        env.storage().persistent().remove(&key);
-        Ok(())
+        let mut approvals = Vec::new(&env);
+        approvals.push_back(agent.clone());
+        let request = EscrowRequest {
+            record_id,
+            requester: agent.clone(),
+            reason: reason.clone(),
+            approvals,
+            requested_at: env.ledger().timestamp(),
+            unlocks_at: None,
+        };
+        escrow::set_request(&env, &request);
+
+        Self::record_escrow_step(
+            &env,
+            symbol_short!("ESC_REQ"),
+            &agent,
+            &record,
+            &config,
+            &request,
+            reason,
+        );
+        Ok(request)
     }
 
-    /// Grant consent for a grantee.
-    pub fn grant_consent(
+    /// Add an escrow agent's approval to a pending request. Reaching quorum
+    /// starts the mandatory delay.
+    pub fn approve_escrow_decryption(
         env: Env,
-        patient: Address,
-        grantee: Address,
-        consent_type: ConsentType,
-        duration_seconds: u64,
-    ) -> Result<(), ContractError> {
+        agent: Address,
+        record_id: u64,
+    ) -> Result<EscrowRequest, ContractError> {
         circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
-        patient.require_auth();
-        if duration_seconds == 0 {
-            return Err(ContractError::InvalidInput);
+        agent.require_auth();
+
+        let config = Self::require_escrow_agent(&env, &agent, "approve_escrow_decryption")?;
+        let record = Self::load_record(&env, record_id)?;
+        let mut request =
+            escrow::get_request(&env, record_id).ok_or(ContractError::EscrowRequestNotFound)?;
+        if request.approvals.contains(&agent) {
+            return Err(ContractError::EscrowAlreadyApproved);
         }
-        let now = env.ledger().timestamp();
-        let consent = ConsentGrant {
-            patient: patient.clone(),
-            grantee: grantee.clone(),
-            consent_type: consent_type.clone(),
-            granted_at: now,
-            expires_at: now.saturating_add(duration_seconds),
-            revoked: false,
-        };
-        let key = consent_key(&patient, &grantee);
-        env.storage().persistent().set(&key, &consent);
-        extend_ttl_access_key(&env, &key);
-        events::publish_consent_granted(&env, patient, grantee, consent_type, consent.expires_at);
-        Ok(())
+
+        request.approvals.push_back(agent.clone());
+        if request.unlocks_at.is_none()
+            && escrow::valid_approvals(&config, &request) >= config.quorum
+        {
+            request.unlocks_at = Some(
+                env.ledger()
+                    .timestamp()
+                    .saturating_add(config.delay_seconds),
+            );
+        }
+        escrow::set_request(&env, &request);
+
+        Self::record_escrow_step(
+            &env,
+            symbol_short!("ESC_APP"),
+            &agent,
+            &record,
+            &config,
+            &request,
+            String::from_str(&env, "escrow approval"),
+        );
+        Ok(request)
     }
 
-    /// Revoke previously granted consent.
-    pub fn revoke_consent(
+    /// Decrypt `record_id` once the agent quorum has approved and the delay
+    /// has elapsed. Returns the plaintext `data_hash` and closes the request.
+    pub fn execute_escrow_decryption(
+        env: Env,
+        agent: Address,
+        record_id: u64,
+    ) -> Result<String, ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        agent.require_auth();
+
+        let config = Self::require_escrow_agent(&env, &agent, "execute_escrow_decryption")?;
+        let record = Self::load_record(&env, record_id)?;
+        let request =
+            escrow::get_request(&env, record_id).ok_or(ContractError::EscrowRequestNotFound)?;
+
+        // Approvals from agents removed since are not counted.
+        if escrow::valid_approvals(&config, &request) < config.quorum {
+            return Err(ContractError::EscrowQuorumNotMet);
+        }
+        let unlocks_at = request
+            .unlocks_at
+            .ok_or(ContractError::EscrowQuorumNotMet)?;
+        if env.ledger().timestamp() < unlocks_at {
+            return Err(ContractError::EscrowDelayNotElapsed);
+        }
+
+        let plaintext = Self::decrypt_data_hash(&env, &record);
+        escrow::remove_request(&env, record_id);
+
+        Self::record_escrow_step(
+            &env,
+            symbol_short!("ESC_EXE"),
+            &agent,
+            &record,
+            &config,
+            &request,
+            String::from_str(&env, "escrow decryption executed"),
+        );
+        Ok(plaintext)
+    }
+
+    /// Break-glass read of `record_id` for an `EmergencyResponder`, bypassing
+    /// consent and access grants. `justification` is required and is kept in
+    /// the audit entry and the high-severity `EMRG_RD` event.
+    pub fn emergency_get_record(
         env: Env,
+        caller: Address,
         patient: Address,
-        grantee: Address,
-    ) -> Result<(), ContractError> {
+        record_id: u64,
+        justification: String,
+    ) -> Result<VisionRecord, ContractError> {
         circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
-        patient.require_auth();
-        let key = consent_key(&patient, &grantee);
-        if let Some(mut consent) = env.storage().persistent().get::<_, ConsentGrant>(&key) {
-            consent.revoked = true;
-            env.storage().persistent().set(&key, &consent);
+        caller.require_auth();
+
+        let is_responder = rbac::get_active_assignment(&env, &caller)
+            .is_some_and(|a| a.role == Role::EmergencyResponder);
+        if !is_responder {
+            // No denied-path audit entry: returning Err would roll it back.
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "emergency_get_record",
+                "role:EmergencyResponder",
+            );
         }
-        events::publish_consent_revoked(&env, patient, grantee);
-        Ok(())
+        if justification.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let record = Self::load_record(&env, record_id)?;
+        if record.patient != patient {
+            return Err(ContractError::RecordNotFound);
+        }
+
+        let audit_entry = audit::create_audit_entry(
+            &env,
+            caller.clone(),
+            patient.clone(),
+            Some(record_id),
+            AccessAction::EmergencyAccess,
+            AccessResult::Success,
+            Some(justification.clone()),
+        );
+        audit::add_audit_entry(&env, &audit_entry);
+        events::publish_audit_log_entry(&env, &audit_entry);
+        events::publish_emergency_record_read(
+            &env,
+            record_id,
+            patient,
+            caller.clone(),
+            justification,
+        );
+
+        Self::meter_op(&env, &caller, MeteringOpType::Read);
+
+        let mut out_record = record.clone();
+        out_record.data_hash = Self::decrypt_data_hash(&env, &record);
+        Ok(out_record)
     }
 
     /// Revoke access
@@ -1925,6 +3090,10 @@ impl VisionRecordsContract {
 
         let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
         env.storage().persistent().remove(&key);
+        // Consent alone still keeps the grantee in the list for export.
+        if !has_active_consent(&env, &patient, &grantee, None) {
+            untrack_grantee(&env, &patient, &grantee);
+        }
 
         // Log successful access revoke
         let audit_entry = audit::create_audit_entry(
@@ -1942,6 +3111,41 @@ impl VisionRecordsContract {
         Ok(())
     }
 
+    /// Drop grantees from `patient`'s tracked list whose access grant is
+    /// missing or expired, removing the expired grants as well. Grantees that
+    /// still hold active consent, or an auto-renewing grant, are kept. Returns
+    /// the number of grantees dropped.
+    pub fn purge_expired_grants(env: Env, patient: Address) -> u32 {
+        let now = env.ledger().timestamp();
+        let list_key = (symbol_short!("ACC_LST"), patient.clone());
+        let grantees: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&list_key)
+            .unwrap_or(Vec::new(&env));
+
+        let mut kept = Vec::new(&env);
+        for grantee in grantees.iter() {
+            let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
+            let grant: Option<AccessGrant> = env.storage().persistent().get(&key);
+            let live = grant
+                .as_ref()
+                .is_some_and(|g| g.expires_at > now || g.auto_renew);
+            if grant.is_some() && !live {
+                env.storage().persistent().remove(&key);
+            }
+            if live || has_active_consent(&env, &patient, &grantee, None) {
+                kept.push_back(grantee);
+            }
+        }
+
+        let dropped = grantees.len().saturating_sub(kept.len());
+        if dropped > 0 {
+            env.storage().persistent().set(&list_key, &kept);
+        }
+        dropped
+    }
+
     /// Update emergency contact information
     pub fn update_emergency_contact(
         env: Env,
@@ -1956,6 +3160,10 @@ impl VisionRecordsContract {
         if caller != patient {
             return Self::unauthorized(&env, &caller, "update_emergency_contact", "profile_owner");
         }
+        // The patient cannot be their own emergency contact.
+        if contact.as_ref().is_some_and(|c| c.address == patient) {
+            return Err(ContractError::InvalidInput);
+        }
 
         let profile_key = (symbol_short!("PAT_PROF"), patient.clone());
         let mut profile: PatientProfile = env
@@ -1964,18 +3172,123 @@ impl VisionRecordsContract {
             .get(&profile_key)
             .ok_or(ContractError::UserNotFound)?;
 
+        // A new or edited contact always starts unverified.
         profile.emergency_contact = match contact {
-            Some(c) => OptionalEmergencyContact::Some(c),
+            Some(mut c) => {
+                c.verified = false;
+                c.verified_at = 0;
+                OptionalEmergencyContact::Some(c)
+            }
             None => OptionalEmergencyContact::None,
         };
         profile.updated_at = env.ledger().timestamp();
 
         env.storage().persistent().set(&profile_key, &profile);
+        patient_profile::clear_contact_challenge(&env, &patient);
         events::publish_profile_updated(&env, patient);
 
         Ok(())
     }
 
+    /// Issue a verification challenge for the patient's emergency contact.
+    ///
+    /// The challenge is bound to the contact address stored by
+    /// `update_emergency_contact` and delivered to the contact off-chain; the
+    /// contact answers by calling `confirm_emergency_contact` with it under
+    /// their own authorization within `CONTACT_CHALLENGE_TTL`. The patient
+    /// cannot complete the handshake on the contact's behalf.
+    pub fn request_contact_verification(
+        env: Env,
+        patient: Address,
+    ) -> Result<BytesN<32>, ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+
+        let profile = Self::get_profile(env.clone(), patient.clone())?;
+        let OptionalEmergencyContact::Some(details) = profile.emergency_contact else {
+            return Err(ContractError::InvalidInput);
+        };
+        let contact = details.address;
+
+        let challenge = patient_profile::issue_contact_challenge(&env, &patient, &contact);
+        events::publish_emergency_contact_challenge(&env, patient, contact, challenge.clone());
+        Ok(challenge)
+    }
+
+    /// Complete the emergency contact handshake and mark the contact
+    /// verified. `contact` must be the stored contact address the challenge
+    /// was issued to.
+    pub fn confirm_emergency_contact(
+        env: Env,
+        contact: Address,
+        patient: Address,
+        challenge: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        contact.require_auth();
+
+        let pending = patient_profile::get_contact_challenge(&env, &patient)
+            .ok_or(ContractError::ContactChallengeNotFound)?;
+        let now = env.ledger().timestamp();
+        if now
+            >= pending
+                .issued_at
+                .saturating_add(patient_profile::CONTACT_CHALLENGE_TTL)
+        {
+            patient_profile::clear_contact_challenge(&env, &patient);
+            return Err(ContractError::ContactChallengeExpired);
+        }
+        if contact != pending.contact || challenge != pending.challenge {
+            return Err(ContractError::ContactChallengeMismatch);
+        }
+
+        let profile_key = (symbol_short!("PAT_PROF"), patient.clone());
+        let mut profile: PatientProfile = env
+            .storage()
+            .persistent()
+            .get(&profile_key)
+            .ok_or(ContractError::UserNotFound)?;
+        let OptionalEmergencyContact::Some(mut details) = profile.emergency_contact else {
+            return Err(ContractError::InvalidInput);
+        };
+        if details.address != contact {
+            return Err(ContractError::ContactChallengeMismatch);
+        }
+        details.verified = true;
+        details.verified_at = now;
+        profile.emergency_contact = OptionalEmergencyContact::Some(details);
+        profile.updated_at = now;
+
+        env.storage().persistent().set(&profile_key, &profile);
+        patient_profile::clear_contact_challenge(&env, &patient);
+        events::publish_emergency_contact_verified(&env, patient, contact);
+
+        Ok(())
+    }
+
+    /// Set how long an emergency contact verification stays valid.
+    /// Requires at least `ContractAdmin` tier.
+    pub fn set_contact_reverify_period(
+        env: Env,
+        caller: Address,
+        seconds: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if !admin_tiers::require_tier(&env, &caller, &AdminTier::ContractAdmin) {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "set_contact_reverify_period",
+                "admin_tier:ContractAdmin",
+            );
+        }
+        if seconds == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+        patient_profile::set_reverify_period(&env, seconds);
+        Ok(())
+    }
+
     /// Update insurance information (hashed values only)
     pub fn update_insurance(
         env: Env,
@@ -2047,12 +3360,18 @@ impl VisionRecordsContract {
     }
 
     /// Get patient profile
+    ///
+    /// The emergency contact reports `verified = false` once its verification
+    /// is older than the re-verification period.
     pub fn get_profile(env: Env, patient: Address) -> Result<PatientProfile, ContractError> {
         let profile_key = (symbol_short!("PAT_PROF"), patient);
-        env.storage()
+        let mut profile: PatientProfile = env
+            .storage()
             .persistent()
             .get(&profile_key)
-            .ok_or(ContractError::UserNotFound)
+            .ok_or(ContractError::UserNotFound)?;
+        patient_profile::apply_verification_expiry(&env, &mut profile);
+        Ok(profile)
     }
 
     /// Check if patient profile exists
@@ -2304,6 +3623,73 @@ impl VisionRecordsContract {
         Ok(())
     }
 
+    /// Hand `record_id` to `new_provider`, e.g. when its provider leaves a
+    /// clinic. Only the current provider or a SystemAdmin may transfer; the
+    /// patient is unchanged and provider-based rights move with the record.
+    /// `new_provider` must hold a provider role and pass the verified
+    /// provider policy.
+    pub fn transfer_record_ownership(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+        new_provider: Address,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        caller.require_auth();
+
+        let mut record = Self::load_record(&env, record_id)?;
+        if caller != record.provider
+            && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin)
+        {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "transfer_record_ownership",
+                "record_provider_or_SystemAdmin",
+            );
+        }
+        if new_provider == record.provider {
+            return Err(ContractError::InvalidInput);
+        }
+        let target_role = rbac::get_active_assignment(&env, &new_provider).map(|a| a.role);
+        if !matches!(
+            target_role,
+            Some(Role::Optometrist) | Some(Role::Ophthalmologist)
+        ) {
+            return Err(ContractError::InvalidInput);
+        }
+        Self::require_verified_provider(&env, &caller, &new_provider)?;
+
+        let from_provider = record.provider.clone();
+        record.provider = new_provider.clone();
+        record.updated_at = env.ledger().timestamp();
+        let key = (symbol_short!("RECORD"), record_id);
+        env.storage().persistent().set(&key, &record);
+        extend_ttl_u64_key(&env, &key);
+
+        let audit_entry = audit::create_audit_entry(
+            &env,
+            caller.clone(),
+            record.patient.clone(),
+            Some(record_id),
+            AccessAction::Write,
+            AccessResult::Success,
+            Some(String::from_str(&env, "Record ownership transferred")),
+        );
+        audit::add_audit_entry(&env, &audit_entry);
+        events::publish_audit_log_entry(&env, &audit_entry);
+        events::publish_record_ownership_transferred(
+            &env,
+            record_id,
+            record.patient,
+            from_provider,
+            new_provider,
+            caller,
+        );
+
+        Ok(())
+    }
+
     /// Check access for a specific record with ABAC evaluation
     /// Prepare phase for register_user operation
     pub fn prepare_register_user(
@@ -2445,6 +3831,8 @@ impl VisionRecordsContract {
             return Err(ContractError::Unauthorized);
         }
 
+        Self::require_verified_provider(&env, &caller, &provider)?;
+
         // Generate and return the record ID that will be used
         let counter_key = symbol_short!("REC_CTR");
         let record_id: u64 = env
@@ -2498,20 +3886,10 @@ impl VisionRecordsContract {
         // Store the record
         let key = (symbol_short!("RECORD"), record_id);
         env.storage().persistent().set(&key, &record);
-        extend_ttl_u64_key(&env, &key);
-
-        // Add to patient's record list
-        let patient_key = (symbol_short!("PAT_REC"), prep_data.patient.clone());
-        let mut patient_records: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&patient_key)
-            .unwrap_or(Vec::new(&env));
-        patient_records.push_back(record_id);
-        env.storage()
-            .persistent()
-            .set(&patient_key, &patient_records);
-        extend_ttl_address_key(&env, &patient_key);
+        extend_ttl_u64_key(&env, &key);
+
+        // Add to patient's record list
+        patient_index::append(&env, &prep_data.patient, record_id);
 
         // Clean up preparation data
         env.storage().temporary().remove(&prep_key);
@@ -2582,6 +3960,9 @@ impl VisionRecordsContract {
             level: prep_data.access_level,
             granted_at: prep_data.timestamp,
             expires_at: prep_data.expires_at.unwrap_or(0),
+            auto_renew: false,
+            renew_interval: 0,
+            renewal_count: 0,
         };
 
         // Store the grant
@@ -2734,6 +4115,8 @@ impl VisionRecordsContract {
             return Err(ContractError::Unauthorized);
         }
 
+        Self::require_verified_provider(&env, &caller, &provider)?;
+
         // Generate prescription ID
         let counter_key = symbol_short!("RX_CTR");
         let rx_id: u64 = env
@@ -2832,6 +4215,513 @@ impl VisionRecordsContract {
         Ok(records)
     }
 
+    /// Return the record-access audit history of `actor`, oldest first.
+    ///
+    /// Admins and holders of `SystemAdmin` or `ReadAnyRecord` see every entry;
+    /// any other caller only sees entries for their own records. `start` skips
+    /// that many visible entries and at most `MAX_ACTOR_HISTORY_PAGE` are
+    /// returned.
+    pub fn get_actor_access_history(
+        env: Env,
+        caller: Address,
+        actor: Address,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<AuditEntry>, ContractError> {
+        caller.require_auth();
+
+        let admin = Self::get_admin(env.clone())?;
+        let sees_all = caller == admin
+            || rbac::has_permission(&env, &caller, &Permission::SystemAdmin)
+            || rbac::has_permission(&env, &caller, &Permission::ReadAnyRecord);
+
+        let limit = limit.min(MAX_ACTOR_HISTORY_PAGE);
+        let mut entries = Vec::new(&env);
+        let mut skipped: u32 = 0;
+        for id in audit::get_actor_audit_ids(&env, &actor).iter() {
+            if entries.len() >= limit {
+                break;
+            }
+            let Some(entry) = audit::get_audit_entry(&env, id) else {
+                continue;
+            };
+            if entry.record_id.is_none() || (!sees_all && entry.patient != caller) {
+                continue;
+            }
+            if skipped < start {
+                skipped = skipped.saturating_add(1);
+                continue;
+            }
+            entries.push_back(entry);
+        }
+        Ok(entries)
+    }
+
+    /// Export a page of the patient's complete data set for portability.
+    ///
+    /// Returns up to `MAX_EXPORT_PAGE` of the patient's records from `start`,
+    /// with data decrypted, plus their consent history on the first page.
+    /// Pages are hash-chained (see [`PatientDataExport`]) so that the full
+    /// export can be verified end to end: pass the previous page's
+    /// `export_hash` as `prev_hash` for every page after the first, and
+    /// `None` for the first. Only the records on this page are hashed.
+    /// Only the patient may call this.
+    pub fn export_patient_data(
+        env: Env,
+        patient: Address,
+        start: u32,
+        limit: u32,
+        prev_hash: Option<BytesN<32>>,
+    ) -> Result<PatientDataExport, ContractError> {
+        patient.require_auth();
+        if limit == 0 || (start == 0) != prev_hash.is_none() {
+            return Err(ContractError::InvalidInput);
+        }
+        let limit = limit.min(portability::MAX_EXPORT_PAGE);
+
+        let mut consents = Vec::new(&env);
+        let prev_hash = match prev_hash {
+            Some(hash) => hash,
+            None => {
+                let list_key = (symbol_short!("ACC_LST"), patient.clone());
+                let grantees: Vec<Address> = env
+                    .storage()
+                    .persistent()
+                    .get(&list_key)
+                    .unwrap_or(Vec::new(&env));
+                for grantee in grantees.iter() {
+                    let key = consent_key(&patient, &grantee);
+                    if let Some(consent) = env.storage().persistent().get::<_, ConsentGrant>(&key) {
+                        consents.push_back(consent);
+                    }
+                }
+                portability::genesis_hash(&env, &patient, &consents)
+            }
+        };
+
+        let record_ids = Self::get_patient_records(env.clone(), patient.clone());
+        let total_records = record_ids.len();
+        let end = start.saturating_add(limit).min(total_records);
+
+        let mut hash = prev_hash.clone();
+        let mut records = Vec::new(&env);
+        for i in start..end {
+            let Some(id) = record_ids.get(i) else {
+                continue;
+            };
+            let record: VisionRecord = env
+                .storage()
+                .persistent()
+                .get(&(symbol_short!("RECORD"), id))
+                .ok_or(ContractError::RecordNotFound)?;
+            let exported = ExportedRecord {
+                id,
+                provider: record.provider.clone(),
+                record_type: record.record_type.clone(),
+                data: Self::decrypt_data_hash(&env, &record),
+                created_at: record.created_at,
+                updated_at: record.updated_at,
+            };
+            hash = portability::chain_record(&env, &hash, &exported);
+            records.push_back(exported);
+        }
+
+        Ok(PatientDataExport {
+            patient,
+            start,
+            total_records,
+            records,
+            consents,
+            prev_hash,
+            export_hash: hash,
+            has_more: end < total_records,
+        })
+    }
+
+    // ── Consent beacons ───────────────────────────────────────────────────────
+
+    /// Commit to the patient's current access-control state.
+    ///
+    /// Computes a Merkle root over the patient's active consents and access
+    /// grants, stores the committed set and publishes the root as an event.
+    /// `caller` must be the patient or a SystemAdmin, and may publish at most
+    /// once per beacon interval per patient.
+    pub fn publish_consent_beacon(
+        env: Env,
+        caller: Address,
+        patient: Address,
+    ) -> Result<BytesN<32>, ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        caller.require_auth();
+        if caller != patient && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "publish_consent_beacon",
+                "patient_or_system_admin",
+            );
+        }
+        let now = env.ledger().timestamp();
+        let previous = consent_beacon::get_beacon(&env, &patient);
+        if let Some(previous) = &previous {
+            let next_allowed = previous
+                .published_at
+                .saturating_add(consent_beacon::beacon_interval(&env));
+            if now < next_allowed {
+                return Err(ContractError::RateLimitExceeded);
+            }
+        }
+
+        let grants = consent_beacon::active_grants(&env, &patient);
+        let root = consent_beacon::compute_beacon_root(&env, &grants);
+        let beacon = ConsentBeacon {
+            patient: patient.clone(),
+            sequence: previous.map_or(1, |b| b.sequence.saturating_add(1)),
+            root: root.clone(),
+            grants,
+            published_at: now,
+        };
+        consent_beacon::set_beacon(&env, &beacon);
+        events::publish_consent_beacon(
+            &env,
+            patient,
+            beacon.sequence,
+            root.clone(),
+            beacon.grants.len(),
+        );
+        Ok(root)
+    }
+
+    /// Return the latest consent beacon published for `patient`.
+    pub fn get_consent_beacon(env: Env, patient: Address) -> Option<ConsentBeacon> {
+        consent_beacon::get_beacon(&env, &patient)
+    }
+
+    /// Return the consent beacon published for `patient` under `sequence`.
+    pub fn get_consent_beacon_at(
+        env: Env,
+        patient: Address,
+        sequence: u32,
+    ) -> Option<ConsentBeacon> {
+        consent_beacon::get_beacon_at(&env, &patient, sequence)
+    }
+
+    /// Prove that `grantee` held a consent or access grant in the patient's
+    /// latest beacon. Returns one proof per committed grant for `grantee`.
+    pub fn prove_consent_inclusion(
+        env: Env,
+        patient: Address,
+        grantee: Address,
+    ) -> Result<Vec<ConsentInclusionProof>, ContractError> {
+        let beacon = consent_beacon::get_beacon(&env, &patient)
+            .ok_or(ContractError::ConsentBeaconNotFound)?;
+        let mut proofs = Vec::new(&env);
+        for (index, grant) in beacon.grants.iter().enumerate() {
+            if grant.grantee() != &grantee {
+                continue;
+            }
+            if let Some(proof) = consent_beacon::prove(&env, &beacon, index as u32) {
+                proofs.push_back(proof);
+            }
+        }
+        if proofs.is_empty() {
+            return Err(ContractError::GrantNotInBeacon);
+        }
+        Ok(proofs)
+    }
+
+    /// Check an inclusion proof against the beacon stored under its patient
+    /// and sequence. Proofs against earlier beacons keep verifying.
+    pub fn verify_consent_inclusion(env: Env, proof: ConsentInclusionProof) -> bool {
+        consent_beacon::verify(&env, &proof)
+    }
+
+    /// Set the minimum time between beacons for one patient (0 disables the
+    /// limit). Requires ContractAdmin tier.
+    pub fn set_consent_beacon_interval(
+        env: Env,
+        caller: Address,
+        seconds: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if !admin_tiers::require_tier(&env, &caller, &AdminTier::ContractAdmin) {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "set_consent_beacon_interval",
+                "admin_tier:ContractAdmin",
+            );
+        }
+        consent_beacon::set_beacon_interval(&env, seconds);
+        Ok(())
+    }
+
+    pub fn get_consent_beacon_interval(env: Env) -> u64 {
+        consent_beacon::beacon_interval(&env)
+    }
+
+    // ── Cross-provider record merge ───────────────────────────────────────────
+
+    /// Move `record_ids` of `patient` from `source_provider` to
+    /// `target_provider`, e.g. after a practice merger.
+    ///
+    /// The patient must co-sign, and `caller` needs `WriteRecord` for the
+    /// target provider (directly or by delegation) or `SystemAdmin`. A record
+    /// whose decrypted `data_hash` matches one the patient already has with
+    /// the target (or one moved earlier in the same call) is skipped and left
+    /// with the source provider. Returns the ids that were moved.
+    pub fn merge_patient_records(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        source_provider: Address,
+        target_provider: Address,
+        record_ids: Vec<u64>,
+    ) -> Result<Vec<u64>, ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        caller.require_auth();
+        patient.require_auth();
+
+        if record_ids.is_empty() || source_provider == target_provider {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let has_perm = if caller == target_provider {
+            rbac::has_permission(&env, &caller, &Permission::WriteRecord)
+        } else {
+            rbac::has_delegated_permission(
+                &env,
+                &target_provider,
+                &caller,
+                &Permission::WriteRecord,
+            )
+        };
+        if !has_perm && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Self::unauthorized(
+                &env,
+                &caller,
+                "merge_patient_records",
+                "permission:WriteRecord_or_SystemAdmin",
+            );
+        }
+
+        let patient_records = patient_index::all(&env, &patient);
+
+        // Plaintext hashes the patient already holds with the target provider.
+        let mut target_hashes: Vec<String> = Vec::new(&env);
+        for id in patient_records.iter() {
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<_, VisionRecord>(&(symbol_short!("RECORD"), id))
+            {
+                if record.provider == target_provider {
+                    target_hashes.push_back(Self::decrypt_data_hash(&env, &record));
+                }
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        let mut merged: Vec<u64> = Vec::new(&env);
+        let mut skipped: Vec<u64> = Vec::new(&env);
+        for record_id in record_ids.iter() {
+            let key = (symbol_short!("RECORD"), record_id);
+            let mut record: VisionRecord = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .ok_or(ContractError::RecordNotFound)?;
+            if record.patient != patient || record.provider != source_provider {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let plaintext = Self::decrypt_data_hash(&env, &record);
+            let (result, note) = if target_hashes.contains(&plaintext) {
+                skipped.push_back(record_id);
+                (AccessResult::Denied, "Merge skipped: duplicate data_hash")
+            } else {
+                record.provider = target_provider.clone();
+                record.updated_at = now;
+                env.storage().persistent().set(&key, &record);
+                extend_ttl_u64_key(&env, &key);
+
+                target_hashes.push_back(plaintext);
+                merged.push_back(record_id);
+                if !patient_records.contains(record_id) {
+                    patient_index::append(&env, &patient, record_id);
+                }
+                (AccessResult::Success, "Merged from source provider")
+            };
+
+            let audit_entry = audit::create_audit_entry(
+                &env,
+                caller.clone(),
+                patient.clone(),
+                Some(record_id),
+                AccessAction::Write,
+                result,
+                Some(String::from_str(&env, note)),
+            );
+            audit::add_audit_entry(&env, &audit_entry);
+            events::publish_audit_log_entry(&env, &audit_entry);
+        }
+
+        events::publish_records_merged(
+            &env,
+            patient,
+            source_provider,
+            target_provider,
+            merged.clone(),
+            skipped,
+        );
+        Ok(merged)
+    }
+
+    // ── One-time access tokens ────────────────────────────────────────────────
+
+    /// Register a single-use token that lets its holder read `record_id`
+    /// once within `ttl` seconds, without a grant to any address.
+    ///
+    /// `token_id` is `sha256(secret)` for a secret the patient generates
+    /// off-chain and passes to the redeemer out of band; only the hash is
+    /// stored. If `redeemer` is set, no other address can use the secret.
+    pub fn mint_access_token(
+        env: Env,
+        patient: Address,
+        record_id: u64,
+        token_id: BytesN<32>,
+        redeemer: Option<Address>,
+        ttl: u64,
+    ) -> Result<(), ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        patient.require_auth();
+        if ttl == 0 || ttl > access_token::MAX_TOKEN_TTL {
+            return Err(ContractError::InvalidInput);
+        }
+        // Re-registering a hash would revive a redeemed token.
+        if access_token::get(&env, &token_id).is_some() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let record: VisionRecord = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("RECORD"), record_id))
+            .ok_or(ContractError::RecordNotFound)?;
+        if record.patient != patient {
+            return Self::unauthorized(&env, &patient, "mint_access_token", "record_owner");
+        }
+
+        let now = env.ledger().timestamp();
+        let stored = AccessToken {
+            patient: patient.clone(),
+            record_id,
+            redeemer,
+            issued_at: now,
+            expires_at: now.saturating_add(ttl),
+            redeemed_by: None,
+        };
+        access_token::set(&env, &token_id, &stored);
+        events::publish_access_token_minted(&env, patient, record_id, token_id, stored.expires_at);
+        Ok(())
+    }
+
+    /// Redeem a one-time token for `record_id` by presenting its `secret`,
+    /// and return the decrypted record. The redeemer needs no grant; the
+    /// token is consumed and the read is audited under the redeemer's
+    /// address.
+    pub fn redeem_access_token(
+        env: Env,
+        redeemer: Address,
+        record_id: u64,
+        secret: BytesN<32>,
+    ) -> Result<VisionRecord, ContractError> {
+        circuit_breaker::require_not_paused(&env, &circuit_breaker::PauseScope::Global)?;
+        redeemer.require_auth();
+
+        let token_id = access_token::token_id(&env, &secret);
+        let mut stored = access_token::get(&env, &token_id)
+            .filter(|t| t.record_id == record_id)
+            .ok_or(ContractError::AccessTokenNotFound)?;
+        if stored.redeemer.as_ref().is_some_and(|r| *r != redeemer) {
+            return Self::unauthorized(&env, &redeemer, "redeem_access_token", "token_redeemer");
+        }
+        if stored.redeemed_by.is_some() {
+            return Err(ContractError::AccessTokenRedeemed);
+        }
+        if env.ledger().timestamp() >= stored.expires_at {
+            return Err(ContractError::AccessTokenExpired);
+        }
+
+        let record: VisionRecord = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("RECORD"), record_id))
+            .ok_or(ContractError::RecordNotFound)?;
+
+        stored.redeemed_by = Some(redeemer.clone());
+        access_token::set(&env, &token_id, &stored);
+
+        let audit_entry = audit::create_audit_entry(
+            &env,
+            redeemer.clone(),
+            record.patient.clone(),
+            Some(record_id),
+            AccessAction::Read,
+            AccessResult::Success,
+            Some(String::from_str(&env, "One-time access token")),
+        );
+        audit::add_audit_entry(&env, &audit_entry);
+        events::publish_audit_log_entry(&env, &audit_entry);
+        events::publish_access_token_redeemed(
+            &env,
+            record.patient.clone(),
+            redeemer.clone(),
+            record_id,
+            token_id,
+        );
+
+        Self::meter_op(&env, &redeemer, MeteringOpType::Read);
+
+        let mut out_record = record.clone();
+        out_record.data_hash = Self::decrypt_data_hash(&env, &record);
+        Ok(out_record)
+    }
+
+    /// Revoke an unredeemed one-time token. Only the minting patient may
+    /// revoke it.
+    pub fn revoke_access_token(
+        env: Env,
+        patient: Address,
+        token_id: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+
+        let stored =
+            access_token::get(&env, &token_id).ok_or(ContractError::AccessTokenNotFound)?;
+        if stored.patient != patient {
+            return Self::unauthorized(&env, &patient, "revoke_access_token", "token_owner");
+        }
+        if stored.redeemed_by.is_some() {
+            return Err(ContractError::AccessTokenRedeemed);
+        }
+
+        access_token::remove(&env, &token_id);
+        events::publish_access_token_revoked(
+            &env,
+            patient,
+            stored.record_id,
+            token_id,
+            stored.expires_at,
+        );
+        Ok(())
+    }
+
+    /// Return a token's state by its id, `sha256(secret)`.
+    pub fn get_access_token(env: Env, token_id: BytesN<32>) -> Option<AccessToken> {
+        access_token::get(&env, &token_id)
+    }
+
     // ── Admin tier management ─────────────────────────────────────────────────
 
     /// Return the admin tier for a given address.
@@ -2897,3 +4787,46 @@ mod test_occ;
 
 #[cfg(test)]
 mod test_diagnostic_image_metadata;
+
+#[cfg(test)]
+mod test_access_auto_renew;
+#[cfg(test)]
+mod test_access_token;
+#[cfg(test)]
+mod test_actor_audit;
+#[cfg(test)]
+mod test_consent_beacon;
+#[cfg(test)]
+mod test_consent_template;
+#[cfg(test)]
+mod test_emergency_contact;
+#[cfg(test)]
+mod test_escrow;
+#[cfg(test)]
+mod test_patient_export;
+#[cfg(test)]
+mod test_record_merge;
+#[cfg(test)]
+mod test_sub_delegation;
+#[cfg(test)]
+mod test_appointment_access;
+#[cfg(test)]
+mod test_provider_gating;
+#[cfg(test)]
+mod test_key_version;
+#[cfg(test)]
+mod test_record_pagination;
+#[cfg(test)]
+mod test_emergency_access;
+#[cfg(test)]
+mod test_scoped_consent;
+#[cfg(test)]
+mod test_grantee_tracking;
+#[cfg(test)]
+mod test_record_transfer;
+#[cfg(test)]
+mod test_consent_batch_revoke;
+#[cfg(test)]
+mod test_audit_sampling;
+#[cfg(test)]
+mod test_exam_history;
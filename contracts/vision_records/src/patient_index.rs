@@ -0,0 +1,125 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+/// Record ids held in one index chunk.
+pub const PATIENT_INDEX_CHUNK: u32 = 50;
+
+/// Pre-chunking index: the patient's whole id list in a single entry.
+const LEGACY_INDEX: Symbol = symbol_short!("PAT_REC");
+const INDEX_LEN: Symbol = symbol_short!("PAT_RLEN");
+const INDEX_CHUNK: Symbol = symbol_short!("PAT_RCHK");
+
+const TTL_THRESHOLD: u32 = 5_184_000;
+const TTL_EXTEND_TO: u32 = 10_368_000;
+
+fn legacy_key(patient: &Address) -> (Symbol, Address) {
+    (LEGACY_INDEX, patient.clone())
+}
+
+fn len_key(patient: &Address) -> (Symbol, Address) {
+    (INDEX_LEN, patient.clone())
+}
+
+fn chunk_key(patient: &Address, chunk: u32) -> (Symbol, Address, u32) {
+    (INDEX_CHUNK, patient.clone(), chunk)
+}
+
+fn legacy(env: &Env, patient: &Address) -> Option<Vec<u64>> {
+    env.storage().persistent().get(&legacy_key(patient))
+}
+
+fn chunk(env: &Env, patient: &Address, index: u32) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&chunk_key(patient, index))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Number of record ids indexed for `patient`.
+pub fn len(env: &Env, patient: &Address) -> u32 {
+    match env.storage().persistent().get(&len_key(patient)) {
+        Some(len) => len,
+        None => legacy(env, patient).map(|ids| ids.len()).unwrap_or(0),
+    }
+}
+
+/// Append `record_id` to the patient's index, touching only the last chunk.
+///
+/// A legacy single-entry index is split into chunks on the first append.
+pub fn append(env: &Env, patient: &Address, record_id: u64) {
+    migrate_legacy(env, patient);
+
+    let len = len(env, patient);
+    let index = len / PATIENT_INDEX_CHUNK;
+    let mut ids = chunk(env, patient, index);
+    ids.push_back(record_id);
+    write_chunk(env, patient, index, &ids);
+    write_len(env, patient, len.saturating_add(1));
+}
+
+/// Up to `limit` ids starting at `offset`, reading only the chunks that
+/// overlap the requested range.
+pub fn page(env: &Env, patient: &Address, offset: u32, limit: u32) -> Vec<u64> {
+    let mut out = Vec::new(env);
+    let end = offset.saturating_add(limit).min(len(env, patient));
+    if offset >= end {
+        return out;
+    }
+    if let Some(ids) = legacy(env, patient) {
+        return ids.slice(offset..end);
+    }
+
+    let mut pos = offset;
+    while pos < end {
+        let index = pos / PATIENT_INDEX_CHUNK;
+        let ids = chunk(env, patient, index);
+        let base = index * PATIENT_INDEX_CHUNK;
+        let stop = end.min(base.saturating_add(ids.len()));
+        if stop <= pos {
+            break;
+        }
+        for i in (pos - base)..(stop - base) {
+            if let Some(id) = ids.get(i) {
+                out.push_back(id);
+            }
+        }
+        pos = stop;
+    }
+    out
+}
+
+/// Every id indexed for `patient`, in insertion order.
+pub fn all(env: &Env, patient: &Address) -> Vec<u64> {
+    page(env, patient, 0, u32::MAX)
+}
+
+fn migrate_legacy(env: &Env, patient: &Address) {
+    let Some(ids) = legacy(env, patient) else {
+        return;
+    };
+    let total = ids.len();
+    let mut index = 0;
+    while index * PATIENT_INDEX_CHUNK < total {
+        let start = index * PATIENT_INDEX_CHUNK;
+        let stop = start.saturating_add(PATIENT_INDEX_CHUNK).min(total);
+        write_chunk(env, patient, index, &ids.slice(start..stop));
+        index += 1;
+    }
+    write_len(env, patient, total);
+    env.storage().persistent().remove(&legacy_key(patient));
+}
+
+fn write_chunk(env: &Env, patient: &Address, index: u32, ids: &Vec<u64>) {
+    let key = chunk_key(patient, index);
+    env.storage().persistent().set(&key, ids);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn write_len(env: &Env, patient: &Address, len: u32) {
+    let key = len_key(patient);
+    env.storage().persistent().set(&key, &len);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -1,13 +1,43 @@
-use soroban_sdk::{contracttype, Address, String, Vec};
+use soroban_sdk::{
+    contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Symbol, Vec,
+};
+
+const ECV_CHL: Symbol = symbol_short!("ECV_CHL");
+const ECV_NONCE: Symbol = symbol_short!("ECV_NONCE");
+const ECV_PERIOD: Symbol = symbol_short!("ECV_PER");
+
+/// Default lifetime of an emergency contact verification (180 days).
+pub const DEFAULT_CONTACT_REVERIFY_SECS: u64 = 15_552_000;
+
+/// How long an issued verification challenge remains answerable (1 day).
+pub const CONTACT_CHALLENGE_TTL: u64 = 86_400;
 
 /// Emergency contact information
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EmergencyContact {
+    /// On-chain address of the contact; only it can answer a verification
+    /// challenge. Must differ from the patient's own address.
+    pub address: Address,
     pub name: String,
     pub relationship: String,
     pub phone: String,
     pub email: String,
+    /// Set once the contact completes the verification handshake; lapses
+    /// after the re-verification period.
+    pub verified: bool,
+    /// Timestamp of the last successful verification (0 if never verified).
+    pub verified_at: u64,
+}
+
+/// A pending emergency contact verification challenge, answerable only by
+/// `contact`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContactChallenge {
+    pub contact: Address,
+    pub challenge: BytesN<32>,
+    pub issued_at: u64,
 }
 
 /// Insurance information (hashed values only for security)
@@ -59,3 +89,70 @@ pub struct PatientProfile {
     // Medical history references (IPFS hashes or record IDs)
     pub medical_history_refs: Vec<String>,
 }
+
+// ── Emergency contact verification ───────────────────────────
+
+fn challenge_key(patient: &Address) -> (Symbol, Address) {
+    (ECV_CHL, patient.clone())
+}
+
+/// Issues and stores a fresh challenge for `patient`'s stored contact at
+/// `contact`, replacing any pending one.
+pub fn issue_contact_challenge(env: &Env, patient: &Address, contact: &Address) -> BytesN<32> {
+    let nonce: u64 = env
+        .storage()
+        .instance()
+        .get(&ECV_NONCE)
+        .unwrap_or(0u64)
+        .saturating_add(1);
+    env.storage().instance().set(&ECV_NONCE, &nonce);
+
+    let mut payload = Bytes::new(env);
+    payload.append(&Bytes::from_slice(env, b"ECV_CHAL"));
+    payload.append(&patient.clone().to_xdr(env));
+    payload.append(&Bytes::from_slice(env, &nonce.to_be_bytes()));
+    payload.append(&Bytes::from_slice(
+        env,
+        &env.ledger().timestamp().to_be_bytes(),
+    ));
+    let challenge: BytesN<32> = env.crypto().sha256(&payload).into();
+
+    env.storage().persistent().set(
+        &challenge_key(patient),
+        &ContactChallenge {
+            contact: contact.clone(),
+            challenge: challenge.clone(),
+            issued_at: env.ledger().timestamp(),
+        },
+    );
+    challenge
+}
+
+pub fn get_contact_challenge(env: &Env, patient: &Address) -> Option<ContactChallenge> {
+    env.storage().persistent().get(&challenge_key(patient))
+}
+
+pub fn clear_contact_challenge(env: &Env, patient: &Address) {
+    env.storage().persistent().remove(&challenge_key(patient));
+}
+
+pub fn get_reverify_period(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&ECV_PERIOD)
+        .unwrap_or(DEFAULT_CONTACT_REVERIFY_SECS)
+}
+
+pub fn set_reverify_period(env: &Env, seconds: u64) {
+    env.storage().instance().set(&ECV_PERIOD, &seconds);
+}
+
+/// Clears `verified` on the profile's contact if its verification is stale.
+pub fn apply_verification_expiry(env: &Env, profile: &mut PatientProfile) {
+    if let OptionalEmergencyContact::Some(contact) = &mut profile.emergency_contact {
+        let lapses_at = contact.verified_at.saturating_add(get_reverify_period(env));
+        if contact.verified && env.ledger().timestamp() >= lapses_at {
+            contact.verified = false;
+        }
+    }
+}
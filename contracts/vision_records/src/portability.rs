@@ -0,0 +1,54 @@
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Vec};
+
+use crate::{ConsentGrant, RecordType};
+
+/// Maximum number of records returned by one `export_patient_data` page.
+pub const MAX_EXPORT_PAGE: u32 = 25;
+
+/// One record as it appears in a patient data export, with its data
+/// decrypted for the patient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportedRecord {
+    pub id: u64,
+    pub provider: Address,
+    pub record_type: RecordType,
+    pub data: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// A page of a patient's data-portability export.
+///
+/// Pages form a hash chain over the patient's records in order:
+/// `prev_hash` of a page equals `export_hash` of the page before it, and the
+/// first page's `prev_hash` commits to the patient and their consents. A
+/// verifier recomputes each page with [`genesis_hash`] and [`chain_record`].
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PatientDataExport {
+    pub patient: Address,
+    pub start: u32,
+    pub total_records: u32,
+    pub records: Vec<ExportedRecord>,
+    /// Consent history; only populated on the first page (`start == 0`).
+    pub consents: Vec<ConsentGrant>,
+    pub prev_hash: BytesN<32>,
+    pub export_hash: BytesN<32>,
+    pub has_more: bool,
+}
+
+/// Head of the export chain: `sha256("PAT_EXP" ‖ patient ‖ consents)`.
+pub fn genesis_hash(env: &Env, patient: &Address, consents: &Vec<ConsentGrant>) -> BytesN<32> {
+    let mut payload = Bytes::from_slice(env, b"PAT_EXP");
+    payload.append(&patient.clone().to_xdr(env));
+    payload.append(&consents.clone().to_xdr(env));
+    env.crypto().sha256(&payload).into()
+}
+
+/// Extend the export chain with one record: `sha256(prev ‖ record)`.
+pub fn chain_record(env: &Env, prev: &BytesN<32>, record: &ExportedRecord) -> BytesN<32> {
+    let mut payload = Bytes::from_array(env, &prev.to_array());
+    payload.append(&record.clone().to_xdr(env));
+    env.crypto().sha256(&payload).into()
+}
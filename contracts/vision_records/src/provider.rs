@@ -249,3 +249,26 @@ pub fn add_provider_id(env: &Env, provider_id: u64, provider: &Address) {
     env.storage().persistent().set(&id_key, provider);
     extend_ttl_u64_key(env, &id_key);
 }
+
+// ── Write gating ─────────────────────────────────────────────
+
+/// Whether clinical writes require the provider to be `Verified`.
+pub fn requires_verified_provider(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("REQ_VPROV"))
+        .unwrap_or(false)
+}
+
+pub fn set_requires_verified_provider(env: &Env, enabled: bool) {
+    env.storage()
+        .instance()
+        .set(&symbol_short!("REQ_VPROV"), &enabled);
+}
+
+/// True if `provider` is registered, active and `Verified`.
+pub fn is_verified(env: &Env, provider: &Address) -> bool {
+    get_provider(env, provider)
+        .map(|p| p.is_active && p.verification_status == VerificationStatus::Verified)
+        .unwrap_or(false)
+}
@@ -45,8 +45,11 @@
 //! ├── Staff (2)
 //! │   ├── ManageUsers
 //! │
-//! └── Patient (1)
-//!     ├── No global permissions (manages own records implicitly)
+//! ├── Patient (1)
+//! │   ├── No global permissions (manages own records implicitly)
+//! │
+//! └── EmergencyResponder (6)
+//!     ├── No global permissions (break-glass reads only)
 //! ```
 //!
 //! ## Permission Hierarchy
@@ -242,6 +245,9 @@ pub enum Role {
     Ophthalmologist = 4,
     /// System administrator: full access and configuration rights
     Admin = 5,
+    /// Emergency responder: no standing permissions, may break glass through
+    /// `emergency_get_record` with a recorded justification
+    EmergencyResponder = 6,
 }
 
 pub fn get_base_permissions(env: &Env, role: &Role) -> Vec<Permission> {
@@ -1172,6 +1178,7 @@ pub fn build_eval_context(
             Role::Optometrist => "optometrist",
             Role::Ophthalmologist => "ophthalmologist",
             Role::Admin => "admin",
+            Role::EmergencyResponder => "emergency_responder",
         };
         attr_vals.push_back(String::from_str(env, role_str));
     }
@@ -0,0 +1,135 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::AccessLevel;
+
+/// Sub-delegation depth allowed when none is configured: a patient's direct
+/// grantee may re-share once, but that sub-grantee may not re-share again.
+pub const DEFAULT_MAX_SUB_DELEGATION_DEPTH: u32 = 1;
+
+const MAX_DEPTH: Symbol = symbol_short!("SUBD_MAX");
+const SUB_TREE: Symbol = symbol_short!("SUBD_TRE");
+
+const TTL_THRESHOLD: u32 = 5_184_000;
+const TTL_EXTEND_TO: u32 = 10_368_000;
+
+/// One edge of a record's sub-delegation tree: `grantor` re-shared its
+/// record access with `grantee`.
+///
+/// Grants issued directly by the patient are the roots and have depth 0;
+/// they do not appear in the tree themselves.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubDelegation {
+    pub record_id: u64,
+    pub grantor: Address,
+    pub grantee: Address,
+    pub level: AccessLevel,
+    /// 1 for a sub-grant of a direct grant, 2 for a sub-grant of that, ...
+    pub depth: u32,
+    pub granted_at: u64,
+    pub expires_at: u64,
+}
+
+fn tree_key(record_id: u64) -> (Symbol, u64) {
+    (SUB_TREE, record_id)
+}
+
+/// Ordering of access levels, used to stop a sub-grant outranking its parent.
+pub fn level_rank(level: &AccessLevel) -> u32 {
+    match level {
+        AccessLevel::None => 0,
+        AccessLevel::Read => 1,
+        AccessLevel::Write => 2,
+        AccessLevel::Full => 3,
+        AccessLevel::Admin => 4,
+    }
+}
+
+pub fn max_depth(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&MAX_DEPTH)
+        .unwrap_or(DEFAULT_MAX_SUB_DELEGATION_DEPTH)
+}
+
+pub fn set_max_depth(env: &Env, depth: u32) {
+    env.storage().instance().set(&MAX_DEPTH, &depth);
+}
+
+pub fn get_tree(env: &Env, record_id: u64) -> Vec<SubDelegation> {
+    env.storage()
+        .persistent()
+        .get(&tree_key(record_id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_tree(env: &Env, record_id: u64, tree: &Vec<SubDelegation>) {
+    let key = tree_key(record_id);
+    if tree.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, tree);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Depth of `grantee`'s access to `record_id`: its tree depth, or 0 if it
+/// was granted directly by the patient.
+pub fn depth_of(env: &Env, record_id: u64, grantee: &Address) -> u32 {
+    get_tree(env, record_id)
+        .iter()
+        .find(|node| node.grantee == *grantee)
+        .map(|node| node.depth)
+        .unwrap_or(0)
+}
+
+pub fn add(env: &Env, node: &SubDelegation) {
+    let mut tree = get_tree(env, node.record_id);
+    tree.push_back(node.clone());
+    set_tree(env, node.record_id, &tree);
+}
+
+/// Drop `grantee`'s own edge, leaving any sub-grants it issued in place.
+/// Used when the patient grants `grantee` access directly.
+pub fn detach(env: &Env, record_id: u64, grantee: &Address) {
+    let mut tree = get_tree(env, record_id);
+    if let Some(i) = tree.iter().position(|node| node.grantee == *grantee) {
+        tree.remove(i as u32);
+        set_tree(env, record_id, &tree);
+    }
+}
+
+/// Remove `root`'s edge and every sub-grant issued beneath it. Returns the
+/// grantees of the removed descendants (not `root` itself), whose record
+/// access the caller must revoke.
+pub fn remove_subtree(env: &Env, record_id: u64, root: &Address) -> Vec<Address> {
+    let tree = get_tree(env, record_id);
+    let mut revoked: Vec<Address> = Vec::new(env);
+    let mut grantors: Vec<Address> = Vec::new(env);
+    grantors.push_back(root.clone());
+
+    // Each pass pulls in the children of grantors found so far; a tree of
+    // depth d settles after d passes.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for node in tree.iter() {
+            if grantors.contains(&node.grantor) && !grantors.contains(&node.grantee) {
+                grantors.push_back(node.grantee.clone());
+                revoked.push_back(node.grantee);
+                changed = true;
+            }
+        }
+    }
+
+    let mut kept: Vec<SubDelegation> = Vec::new(env);
+    for node in tree.iter() {
+        if !grantors.contains(&node.grantee) {
+            kept.push_back(node);
+        }
+    }
+    set_tree(env, record_id, &kept);
+    revoked
+}
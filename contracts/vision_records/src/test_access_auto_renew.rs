@@ -0,0 +1,139 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    AccessGrant, AccessLevel, ConsentType, ContractError, VisionRecordsContract,
+    VisionRecordsContractClient, DEFAULT_MAX_GRANT_LIFETIME,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const START: u64 = 1_000;
+const HOUR: u64 = 3_600;
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    contract_id: Address,
+    admin: Address,
+    patient: Address,
+    grantee: Address,
+}
+
+/// One-hour Read grant backed by a long-lived consent.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START);
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let grantee = Address::generate(&env);
+    client.grant_consent(&patient, &grantee, &ConsentType::Treatment, &(365 * 86_400));
+    client.grant_access(&patient, &patient, &grantee, &AccessLevel::Read, &HOUR);
+
+    Setup {
+        env,
+        client,
+        contract_id,
+        admin,
+        patient,
+        grantee,
+    }
+}
+
+fn stored_grant(s: &Setup) -> AccessGrant {
+    s.env.as_contract(&s.contract_id, || {
+        s.env
+            .storage()
+            .persistent()
+            .get(&(
+                symbol_short!("ACCESS"),
+                s.patient.clone(),
+                s.grantee.clone(),
+            ))
+            .unwrap()
+    })
+}
+
+fn access_at(s: &Setup, timestamp: u64) -> AccessLevel {
+    s.env.ledger().set_timestamp(timestamp);
+    s.client.check_access(&s.patient, &s.grantee)
+}
+
+#[test]
+fn test_grant_lapses_without_auto_renew() {
+    let s = setup();
+    assert!(!stored_grant(&s).auto_renew);
+    assert_eq!(
+        s.client.get_max_grant_lifetime(),
+        DEFAULT_MAX_GRANT_LIFETIME
+    );
+
+    assert_eq!(access_at(&s, START + HOUR - 1), AccessLevel::Read);
+    assert_eq!(access_at(&s, START + HOUR), AccessLevel::None);
+}
+
+#[test]
+fn test_auto_renew_extends_until_max_lifetime() {
+    let s = setup();
+    s.client.set_max_grant_lifetime(&s.admin, &(3 * HOUR));
+    s.client
+        .set_grant_auto_renew(&s.patient, &s.grantee, &true, &HOUR);
+
+    // Past the original expiry the grant renews instead of lapsing.
+    assert_eq!(access_at(&s, START + HOUR), AccessLevel::Read);
+    let grant = stored_grant(&s);
+    assert_eq!(grant.expires_at, START + 2 * HOUR);
+    assert_eq!(grant.renewal_count, 1);
+
+    // Skipping several intervals is covered in one renewal, capped at the
+    // max lifetime.
+    assert_eq!(access_at(&s, START + 3 * HOUR - 1), AccessLevel::Read);
+    let grant = stored_grant(&s);
+    assert_eq!(grant.expires_at, START + 3 * HOUR);
+    assert_eq!(grant.renewal_count, 2);
+
+    // Once the lifetime is exhausted the grant finally lapses.
+    assert_eq!(access_at(&s, START + 3 * HOUR), AccessLevel::None);
+    assert_eq!(stored_grant(&s).renewal_count, 2);
+}
+
+#[test]
+fn test_patient_can_disable_auto_renew() {
+    let s = setup();
+    s.client
+        .set_grant_auto_renew(&s.patient, &s.grantee, &true, &HOUR);
+    assert_eq!(access_at(&s, START + HOUR), AccessLevel::Read);
+
+    s.client
+        .set_grant_auto_renew(&s.patient, &s.grantee, &false, &0);
+    assert_eq!(access_at(&s, START + 2 * HOUR - 1), AccessLevel::Read);
+    assert_eq!(access_at(&s, START + 2 * HOUR), AccessLevel::None);
+}
+
+#[test]
+fn test_auto_renew_input_validation() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+
+    let result = s
+        .client
+        .try_set_grant_auto_renew(&s.patient, &stranger, &true, &HOUR);
+    assert_eq!(result, Err(Ok(ContractError::AccessGrantNotFound)));
+
+    let result = s
+        .client
+        .try_set_grant_auto_renew(&s.patient, &s.grantee, &true, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let result = s.client.try_set_max_grant_lifetime(&stranger, &HOUR);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
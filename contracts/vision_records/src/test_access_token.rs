@@ -0,0 +1,220 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    access_token, audit, AccessAction, AccessResult, ContractError, RecordType,
+    VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String,
+};
+
+const START: u64 = 1_000;
+const TTL: u64 = 900;
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    contract_id: Address,
+    patient: Address,
+    specialist: Address,
+    record_id: u64,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START);
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let record_id = client.add_record(
+        &admin,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+    );
+
+    Setup {
+        env: env.clone(),
+        client,
+        contract_id,
+        patient,
+        specialist: Address::generate(&env),
+        record_id,
+    }
+}
+
+/// Registers `sha256(secret)` and returns the secret the patient would hand
+/// to the redeemer.
+fn mint(s: &Setup, seed: u8, redeemer: Option<Address>) -> BytesN<32> {
+    let secret = BytesN::from_array(&s.env, &[seed; 32]);
+    s.client.mint_access_token(
+        &s.patient,
+        &s.record_id,
+        &access_token::token_id(&s.env, &secret),
+        &redeemer,
+        &TTL,
+    );
+    secret
+}
+
+#[test]
+fn test_token_grants_exactly_one_read() {
+    let s = setup();
+    let token = mint(&s, 1, None);
+
+    // The specialist holds no consent or grant of their own.
+    assert!(s
+        .client
+        .try_get_record(&s.specialist, &s.record_id)
+        .is_err());
+
+    let record = s
+        .client
+        .redeem_access_token(&s.specialist, &s.record_id, &token);
+    assert_eq!(record.patient, s.patient);
+    // Decrypted exactly like a regular read.
+    assert_eq!(
+        record.data_hash,
+        s.client.get_record(&s.patient, &s.record_id).data_hash
+    );
+
+    let token_id = access_token::token_id(&s.env, &token);
+    let stored = s.client.get_access_token(&token_id).unwrap();
+    assert_eq!(stored.redeemed_by, Some(s.specialist.clone()));
+
+    let reads = s.env.as_contract(&s.contract_id, || {
+        audit::get_user_audit_log(&s.env, &s.specialist)
+    });
+    let last = reads.last().unwrap();
+    assert_eq!(last.actor, s.specialist);
+    assert_eq!(last.record_id, Some(s.record_id));
+    assert_eq!(last.action, AccessAction::Read);
+    assert_eq!(last.result, AccessResult::Success);
+
+    // Single use: the same or any other redeemer is turned away.
+    let other = Address::generate(&s.env);
+    for redeemer in [&s.specialist, &other] {
+        assert!(matches!(
+            s.client
+                .try_redeem_access_token(redeemer, &s.record_id, &token),
+            Err(Ok(ContractError::AccessTokenRedeemed))
+        ));
+    }
+    assert_eq!(
+        s.client.try_revoke_access_token(&s.patient, &token_id),
+        Err(Ok(ContractError::AccessTokenRedeemed))
+    );
+    // A redeemed hash cannot be registered again.
+    assert_eq!(
+        s.client
+            .try_mint_access_token(&s.patient, &s.record_id, &token_id, &None, &TTL),
+        Err(Ok(ContractError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_expired_token_is_rejected() {
+    let s = setup();
+    let token = mint(&s, 1, None);
+
+    s.env.ledger().set_timestamp(START + TTL);
+    assert!(matches!(
+        s.client
+            .try_redeem_access_token(&s.specialist, &s.record_id, &token),
+        Err(Ok(ContractError::AccessTokenExpired))
+    ));
+}
+
+#[test]
+fn test_revoked_or_unknown_token_is_rejected() {
+    let s = setup();
+    let token = mint(&s, 1, None);
+    let token_id = access_token::token_id(&s.env, &token);
+
+    // A token only opens the record it was minted for.
+    assert!(matches!(
+        s.client
+            .try_redeem_access_token(&s.specialist, &(s.record_id + 1), &token),
+        Err(Ok(ContractError::AccessTokenNotFound))
+    ));
+    let forged = BytesN::from_array(&s.env, &[7u8; 32]);
+    assert!(matches!(
+        s.client
+            .try_redeem_access_token(&s.specialist, &s.record_id, &forged),
+        Err(Ok(ContractError::AccessTokenNotFound))
+    ));
+    // Knowing the stored hash is not enough to redeem.
+    assert!(matches!(
+        s.client
+            .try_redeem_access_token(&s.specialist, &s.record_id, &token_id),
+        Err(Ok(ContractError::AccessTokenNotFound))
+    ));
+
+    assert_eq!(
+        s.client.try_revoke_access_token(&s.specialist, &token_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    s.client.revoke_access_token(&s.patient, &token_id);
+    assert!(matches!(
+        s.client
+            .try_redeem_access_token(&s.specialist, &s.record_id, &token),
+        Err(Ok(ContractError::AccessTokenNotFound))
+    ));
+}
+
+#[test]
+fn test_bound_token_only_redeems_for_its_redeemer() {
+    let s = setup();
+    let token = mint(&s, 2, Some(s.specialist.clone()));
+
+    let interceptor = Address::generate(&s.env);
+    assert!(matches!(
+        s.client
+            .try_redeem_access_token(&interceptor, &s.record_id, &token),
+        Err(Ok(ContractError::Unauthorized))
+    ));
+    let record = s
+        .client
+        .redeem_access_token(&s.specialist, &s.record_id, &token);
+    assert_eq!(record.patient, s.patient);
+}
+
+#[test]
+fn test_mint_requires_record_owner_and_bounded_ttl() {
+    let s = setup();
+    let token_id = access_token::token_id(&s.env, &BytesN::from_array(&s.env, &[3u8; 32]));
+    let try_mint = |patient: &Address, record_id: u64, ttl: u64| {
+        s.client
+            .try_mint_access_token(patient, &record_id, &token_id, &None, &ttl)
+    };
+
+    assert_eq!(
+        try_mint(&s.specialist, s.record_id, TTL),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        try_mint(&s.patient, s.record_id, 0),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        try_mint(&s.patient, s.record_id, access_token::MAX_TOKEN_TTL + 1),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        try_mint(&s.patient, 999, TTL),
+        Err(Ok(ContractError::RecordNotFound))
+    );
+
+    // Only the hash is kept; the secret appears nowhere in storage.
+    assert_eq!(try_mint(&s.patient, s.record_id, TTL), Ok(Ok(())));
+    let stored = s.client.get_access_token(&token_id).unwrap();
+    assert_eq!((stored.patient, stored.redeemer), (s.patient.clone(), None));
+}
@@ -0,0 +1,132 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    rbac::Role, AccessAction, AccessResult, ConsentType, ContractError, RecordType,
+    VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+const ONE_DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    provider: Address,
+    patient: Address,
+    grantee: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+    let patient = Address::generate(&env);
+    let grantee = Address::generate(&env);
+
+    Setup {
+        env,
+        client,
+        admin,
+        provider,
+        patient,
+        grantee,
+    }
+}
+
+fn add_record(s: &Setup, patient: &Address) -> u64 {
+    let hash = String::from_str(&s.env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+    s.client.add_record(
+        &s.provider,
+        patient,
+        &s.provider,
+        &RecordType::Examination,
+        &hash,
+    )
+}
+
+#[test]
+fn test_grantee_reads_are_indexed_by_actor() {
+    let s = setup();
+    let first = add_record(&s, &s.patient);
+    let second = add_record(&s, &s.patient);
+    s.client
+        .grant_consent(&s.patient, &s.grantee, &ConsentType::Treatment, &ONE_DAY);
+
+    s.client.get_record(&s.grantee, &first);
+    s.client.get_record(&s.grantee, &second);
+
+    let history = s
+        .client
+        .get_actor_access_history(&s.admin, &s.grantee, &0, &10);
+    assert_eq!(history.len(), 2);
+    let a = history.get(0).unwrap();
+    let b = history.get(1).unwrap();
+    assert_eq!(a.actor, s.grantee);
+    assert_eq!(a.record_id, Some(first));
+    assert_eq!(a.action, AccessAction::Read);
+    assert_eq!(a.result, AccessResult::Success);
+    assert_eq!(b.record_id, Some(second));
+
+    // Pagination skips visible entries.
+    let page = s
+        .client
+        .get_actor_access_history(&s.admin, &s.grantee, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().record_id, Some(second));
+}
+
+#[test]
+fn test_patient_only_sees_own_records() {
+    let s = setup();
+    let other_patient = Address::generate(&s.env);
+    let own = add_record(&s, &s.patient);
+    let foreign = add_record(&s, &other_patient);
+    s.client
+        .grant_consent(&s.patient, &s.grantee, &ConsentType::Treatment, &ONE_DAY);
+    s.client.grant_consent(
+        &other_patient,
+        &s.grantee,
+        &ConsentType::Treatment,
+        &ONE_DAY,
+    );
+
+    s.client.get_record(&s.grantee, &own);
+    s.client.get_record(&s.grantee, &foreign);
+
+    let history = s
+        .client
+        .get_actor_access_history(&s.patient, &s.grantee, &0, &10);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().record_id, Some(own));
+
+    let all = s
+        .client
+        .get_actor_access_history(&s.admin, &s.grantee, &0, &10);
+    assert_eq!(all.len(), 2);
+}
+
+#[test]
+fn test_uninitialized_contract_rejects_history_query() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let caller = Address::generate(&env);
+    let result = client.try_get_actor_access_history(&caller, &caller, &0, &10);
+    assert_eq!(result, Err(Ok(ContractError::NotInitialized)));
+}
@@ -0,0 +1,358 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    AccessLevel, AppointmentStatus, AppointmentType, ContractError, RecordType,
+    VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String, Vec,
+};
+
+const START: u64 = 1_000_000;
+const HOUR: u64 = 3_600;
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    patient: Address,
+    provider: Address,
+    record_id: u64,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START);
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let record_id = client.add_record(
+        &admin,
+        &patient,
+        &Address::generate(&env),
+        &RecordType::Examination,
+        &String::from_str(&env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+    );
+
+    Setup {
+        env: env.clone(),
+        client,
+        admin,
+        patient,
+        provider: Address::generate(&env),
+        record_id,
+    }
+}
+
+fn exams(env: &Env) -> Vec<RecordType> {
+    Vec::from_array(env, [RecordType::Examination])
+}
+
+fn schedule(s: &Setup, scheduled_at: u64) -> u64 {
+    s.client.schedule_appointment(
+        &s.patient,
+        &s.patient,
+        &s.provider,
+        &AppointmentType::Examination,
+        &scheduled_at,
+        &30,
+        &None,
+    )
+}
+
+#[test]
+fn test_appointment_grants_access_only_within_window() {
+    let s = setup();
+    s.client.set_appointment_auto_access(
+        &s.patient,
+        &AccessLevel::Read,
+        &exams(&s.env),
+        &HOUR,
+        &(2 * HOUR),
+    );
+
+    let at = START + DAY;
+    let appointment_id = schedule(&s, at);
+    assert_eq!(
+        s.client.get_appointment(&appointment_id).provider,
+        s.provider
+    );
+
+    let windows = s
+        .client
+        .get_appointment_access_windows(&s.patient, &s.provider);
+    assert_eq!(windows.len(), 1);
+    let window = windows.get(0).unwrap();
+    assert_eq!(
+        (window.appointment_id, window.starts_at, window.ends_at),
+        (appointment_id, at - HOUR, at + 2 * HOUR)
+    );
+
+    for (now, expected) in [
+        (START, AccessLevel::None),
+        (at - HOUR - 1, AccessLevel::None),
+        (at - HOUR, AccessLevel::Read),
+        (at, AccessLevel::Read),
+        (at + 2 * HOUR - 1, AccessLevel::Read),
+    ] {
+        s.env.ledger().set_timestamp(now);
+        assert_eq!(
+            s.client.check_record_access(&s.record_id, &s.provider),
+            expected,
+            "at {now}"
+        );
+    }
+    s.env.ledger().set_timestamp(at);
+    assert!(s.client.try_get_record(&s.provider, &s.record_id).is_ok());
+}
+
+#[test]
+fn test_appointment_access_lapses_after_post_window() {
+    let s = setup();
+    s.client.set_appointment_auto_access(
+        &s.patient,
+        &AccessLevel::Write,
+        &exams(&s.env),
+        &0,
+        &HOUR,
+    );
+    let at = START + DAY;
+    schedule(&s, at);
+
+    s.env.ledger().set_timestamp(at + HOUR);
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &s.provider),
+        AccessLevel::None
+    );
+    assert!(matches!(
+        s.client.try_get_record(&s.provider, &s.record_id),
+        Err(Ok(ContractError::Unauthorized))
+    ));
+
+    // Scheduling again replaces the lapsed window rather than piling up.
+    schedule(&s, at + DAY);
+    assert_eq!(
+        s.client
+            .get_appointment_access_windows(&s.patient, &s.provider)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_appointment_access_requires_standing_consent() {
+    let s = setup();
+    let at = START + DAY;
+
+    // No consent: the appointment is scheduled but grants nothing.
+    schedule(&s, at);
+    assert!(s
+        .client
+        .get_appointment_access_windows(&s.patient, &s.provider)
+        .is_empty());
+
+    s.client.set_appointment_auto_access(
+        &s.patient,
+        &AccessLevel::Read,
+        &exams(&s.env),
+        &HOUR,
+        &HOUR,
+    );
+    schedule(&s, at + DAY);
+    s.env.ledger().set_timestamp(at + DAY);
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &s.provider),
+        AccessLevel::Read
+    );
+
+    // Withdrawing consent ends the open window immediately.
+    s.client.revoke_appointment_auto_access(&s.patient);
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &s.provider),
+        AccessLevel::None
+    );
+
+    assert_eq!(
+        s.client.try_set_appointment_auto_access(
+            &s.patient,
+            &AccessLevel::Admin,
+            &exams(&s.env),
+            &HOUR,
+            &HOUR
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        s.client.try_schedule_appointment(
+            &Address::generate(&s.env),
+            &s.patient,
+            &s.provider,
+            &AppointmentType::Routine,
+            &(at + 2 * DAY),
+            &30,
+            &None,
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_provider_scheduled_appointment_needs_patient_confirmation() {
+    let s = setup();
+    s.client.set_appointment_auto_access(
+        &s.patient,
+        &AccessLevel::Read,
+        &exams(&s.env),
+        &HOUR,
+        &HOUR,
+    );
+    let at = START + DAY;
+
+    let appointment_id = s.client.schedule_appointment(
+        &s.provider,
+        &s.patient,
+        &s.provider,
+        &AppointmentType::Examination,
+        &at,
+        &30,
+        &None,
+    );
+    assert!(s
+        .client
+        .get_appointment_access_windows(&s.patient, &s.provider)
+        .is_empty());
+
+    assert_eq!(
+        s.client
+            .try_confirm_appointment(&s.provider, &appointment_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    s.client.confirm_appointment(&s.patient, &appointment_id);
+    assert_eq!(
+        s.client.get_appointment(&appointment_id).status,
+        AppointmentStatus::Confirmed
+    );
+    s.env.ledger().set_timestamp(at);
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &s.provider),
+        AccessLevel::Read
+    );
+
+    assert_eq!(
+        s.client
+            .try_confirm_appointment(&s.patient, &appointment_id),
+        Err(Ok(ContractError::InvalidAppointmentStatus))
+    );
+}
+
+#[test]
+fn test_unverified_provider_gets_no_window_when_policy_on() {
+    let s = setup();
+    s.client.set_require_verified_provider(&s.admin, &true);
+    s.client.set_appointment_auto_access(
+        &s.patient,
+        &AccessLevel::Read,
+        &exams(&s.env),
+        &HOUR,
+        &HOUR,
+    );
+
+    assert_eq!(
+        s.client.try_schedule_appointment(
+            &s.patient,
+            &s.patient,
+            &s.provider,
+            &AppointmentType::Examination,
+            &(START + DAY),
+            &30,
+            &None,
+        ),
+        Err(Ok(ContractError::ProviderNotVerified))
+    );
+    assert!(s
+        .client
+        .get_appointment_access_windows(&s.patient, &s.provider)
+        .is_empty());
+}
+
+#[test]
+fn test_revoking_consent_deletes_windows() {
+    let s = setup();
+    s.client.set_appointment_auto_access(
+        &s.patient,
+        &AccessLevel::Read,
+        &exams(&s.env),
+        &HOUR,
+        &HOUR,
+    );
+    let at = START + DAY;
+    schedule(&s, at);
+    s.client.revoke_appointment_auto_access(&s.patient);
+    assert!(s
+        .client
+        .get_appointment_access_windows(&s.patient, &s.provider)
+        .is_empty());
+
+    // Consenting again does not revive the window from before the revoke.
+    s.client.set_appointment_auto_access(
+        &s.patient,
+        &AccessLevel::Read,
+        &exams(&s.env),
+        &HOUR,
+        &HOUR,
+    );
+    s.env.ledger().set_timestamp(at);
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &s.provider),
+        AccessLevel::None
+    );
+}
+
+#[test]
+fn test_appointment_window_covers_only_consented_record_types() {
+    let s = setup();
+    let prescription_id = s.client.add_record(
+        &s.admin,
+        &s.patient,
+        &Address::generate(&s.env),
+        &RecordType::Prescription,
+        &String::from_str(&s.env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+    );
+    s.client.set_appointment_auto_access(
+        &s.patient,
+        &AccessLevel::Read,
+        &exams(&s.env),
+        &HOUR,
+        &HOUR,
+    );
+    let at = START + DAY;
+    schedule(&s, at);
+
+    s.env.ledger().set_timestamp(at);
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &s.provider),
+        AccessLevel::Read
+    );
+    assert_eq!(
+        s.client.check_record_access(&prescription_id, &s.provider),
+        AccessLevel::None
+    );
+
+    assert_eq!(
+        s.client.try_set_appointment_auto_access(
+            &s.patient,
+            &AccessLevel::Read,
+            &Vec::new(&s.env),
+            &HOUR,
+            &HOUR
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+}
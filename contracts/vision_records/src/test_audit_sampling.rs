@@ -0,0 +1,137 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    audit::{self, AccessAction, AccessResult},
+    ContractError, RecordType, Role, VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+const DATA_HASH: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+struct Setup {
+    env: Env,
+    contract_id: Address,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    provider: Address,
+    patient: Address,
+    record_id: u64,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+
+    let patient = Address::generate(&env);
+    let record_id = client.add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&env, DATA_HASH),
+    );
+
+    Setup {
+        env,
+        contract_id,
+        client,
+        admin,
+        provider,
+        patient,
+        record_id,
+    }
+}
+
+fn count(s: &Setup, action: AccessAction, result: AccessResult) -> u32 {
+    s.env.as_contract(&s.contract_id, || {
+        let mut n = 0;
+        for entry in audit::get_record_audit_log(&s.env, s.record_id).iter() {
+            if entry.action == action && entry.result == result {
+                n += 1;
+            }
+        }
+        n
+    })
+}
+
+#[test]
+fn test_successful_reads_are_sampled_per_caller() {
+    let s = setup();
+    assert_eq!(s.client.get_audit_sample_rate(), 1);
+    s.client.set_audit_sample_rate(&s.admin, &3);
+    assert_eq!(s.client.get_audit_sample_rate(), 3);
+
+    // Reads 1, 4 and 7 are audited.
+    for _ in 0..7 {
+        s.client.get_record(&s.patient, &s.record_id);
+    }
+    assert_eq!(count(&s, AccessAction::Read, AccessResult::Success), 3);
+
+    // The provider has its own counter, so its first read is audited.
+    s.client.get_record(&s.provider, &s.record_id);
+    assert_eq!(count(&s, AccessAction::Read, AccessResult::Success), 4);
+
+    // Writes are never sampled.
+    let mut current = s.provider.clone();
+    for _ in 0..2 {
+        let successor = Address::generate(&s.env);
+        s.client.register_user(
+            &s.admin,
+            &successor,
+            &Role::Optometrist,
+            &String::from_str(&s.env, "Successor"),
+        );
+        s.client
+            .transfer_record_ownership(&current, &s.record_id, &successor);
+        current = successor;
+    }
+    assert_eq!(count(&s, AccessAction::Write, AccessResult::Success), 2);
+
+    s.client.set_audit_sample_rate(&s.admin, &1);
+    s.client.get_record(&s.patient, &s.record_id);
+    assert_eq!(count(&s, AccessAction::Read, AccessResult::Success), 5);
+}
+
+#[test]
+fn test_denials_are_never_sampled_out() {
+    let s = setup();
+    s.client.set_audit_sample_rate(&s.admin, &1000);
+    let stranger = Address::generate(&s.env);
+
+    // A failed client call rolls back its own writes, so drive the read
+    // path inside the contract to observe the Denied entries it records.
+    for _ in 0..3 {
+        let result = s.env.as_contract(&s.contract_id, || {
+            VisionRecordsContract::read_record(s.env.clone(), stranger.clone(), s.record_id)
+        });
+        assert!(matches!(result, Err(ContractError::Unauthorized)));
+    }
+    assert_eq!(count(&s, AccessAction::Read, AccessResult::Denied), 3);
+}
+
+#[test]
+fn test_sample_rate_is_admin_only_and_nonzero() {
+    let s = setup();
+    assert!(matches!(
+        s.client.try_set_audit_sample_rate(&s.provider, &10),
+        Err(Ok(ContractError::Unauthorized))
+    ));
+    assert!(matches!(
+        s.client.try_set_audit_sample_rate(&s.admin, &0),
+        Err(Ok(ContractError::InvalidInput))
+    ));
+    assert_eq!(s.client.get_audit_sample_rate(), 1);
+}
@@ -9,7 +9,10 @@ use super::{
     AccessLevel, BatchGrantInput, BatchRecordInput, ContractError, RecordType, Role,
     VisionRecordsContract, VisionRecordsContractClient,
 };
-use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, Address, Env, String, Vec};
+use key_manager::{KeyManagerContract, KeyManagerContractClient, KeyPolicy, KeyType};
+use soroban_sdk::{
+    testutils::Address as _, testutils::Ledger as _, Address, BytesN, Env, String, Vec,
+};
 
 // ── Helpers ──────────────────────────────────────────────────────
 
@@ -68,7 +71,7 @@ fn test_batch_add_records_single() {
         data_hash: String::from_str(&env, "hash_a"),
     });
 
-    let ids = client.add_records(&provider, &inputs);
+    let ids = client.add_records(&provider, &provider, &inputs);
     assert_eq!(ids.len(), 1);
     assert_eq!(ids.get(0).unwrap(), 1);
 
@@ -103,7 +106,7 @@ fn test_batch_add_records_multiple() {
         data_hash: String::from_str(&env, "hash_3"),
     });
 
-    let ids = client.add_records(&provider, &inputs);
+    let ids = client.add_records(&provider, &provider, &inputs);
     assert_eq!(ids.len(), 3);
     assert_eq!(ids.get(0).unwrap(), 1);
     assert_eq!(ids.get(1).unwrap(), 2);
@@ -148,7 +151,7 @@ fn test_batch_add_records_unauthorized() {
         data_hash: String::from_str(&env, "hash"),
     });
 
-    let result = client.try_add_records(&patient, &inputs);
+    let result = client.try_add_records(&patient, &patient, &inputs);
     assert_eq!(result.err().unwrap().unwrap(), ContractError::Unauthorized);
 }
 
@@ -158,7 +161,7 @@ fn test_batch_add_records_empty_input() {
     let provider = register_provider(&env, &client, &admin);
 
     let inputs: Vec<BatchRecordInput> = Vec::new(&env);
-    let result = client.try_add_records(&provider, &inputs);
+    let result = client.try_add_records(&provider, &provider, &inputs);
     assert_eq!(result.err().unwrap().unwrap(), ContractError::InvalidInput);
 }
 
@@ -175,7 +178,7 @@ fn test_batch_add_records_admin_can_create() {
         data_hash: String::from_str(&env, "surgery_hash"),
     });
 
-    let ids = client.add_records(&admin, &inputs);
+    let ids = client.add_records(&admin, &admin, &inputs);
     assert_eq!(ids.len(), 1);
     let record = client.get_record(&admin, &ids.get(0).unwrap());
     assert_eq!(record.record_type, RecordType::Surgery);
@@ -210,7 +213,7 @@ fn test_batch_add_records_counter_continuity() {
         data_hash: String::from_str(&env, "batch_hash_2"),
     });
 
-    let ids = client.add_records(&provider, &inputs);
+    let ids = client.add_records(&provider, &provider, &inputs);
     assert_eq!(ids.get(0).unwrap(), 2);
     assert_eq!(ids.get(1).unwrap(), 3);
     assert_eq!(client.get_record_count(), 3);
@@ -240,7 +243,7 @@ fn test_batch_get_records() {
         });
     }
 
-    let ids = client.add_records(&provider, &inputs);
+    let ids = client.add_records(&provider, &provider, &inputs);
     assert_eq!(ids.len(), 4);
 
     // Retrieve a subset
@@ -280,7 +283,7 @@ fn test_batch_get_records_partial_not_found() {
         record_type: RecordType::Examination,
         data_hash: String::from_str(&env, "hash_1"),
     });
-    client.add_records(&provider, &inputs);
+    client.add_records(&provider, &provider, &inputs);
 
     // Try to get existing + non-existing record
     let mut ids = Vec::new(&env);
@@ -408,7 +411,7 @@ fn test_batch_records_atomic_counter() {
         });
     }
 
-    let ids = client.add_records(&provider, &inputs);
+    let ids = client.add_records(&provider, &provider, &inputs);
     assert_eq!(ids.len(), 5);
 
     // Counter should reflect all 5 records
@@ -439,7 +442,7 @@ fn test_batch_add_and_retrieve_round_trip() {
         data_hash: String::from_str(&env, "rx_data"),
     });
 
-    let ids = client.add_records(&provider, &inputs);
+    let ids = client.add_records(&provider, &provider, &inputs);
 
     // Retrieve all via batch
     let records = client.get_records(&ids);
@@ -453,3 +456,73 @@ fn test_batch_add_and_retrieve_round_trip() {
     assert_eq!(records.get(0).unwrap().provider, provider);
     assert_eq!(records.get(1).unwrap().provider, provider);
 }
+
+#[test]
+fn test_batch_add_records_decrypt_on_read() {
+    let (env, client, admin) = setup();
+    client.set_encryption_key(
+        &admin,
+        &String::from_str(&env, "v1"),
+        &String::from_str(
+            &env,
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        ),
+        &0,
+    );
+    // With a key manager configured, both write paths use derived keys.
+    let key_manager = env.register(KeyManagerContract, ());
+    let km_client = KeyManagerContractClient::new(&env, &key_manager);
+    km_client.initialize(&admin, &Address::generate(&env));
+    let root_key_id = km_client.create_master_key(
+        &admin,
+        &KeyType::Encryption,
+        &KeyPolicy {
+            max_uses: 0,
+            not_before: 0,
+            not_after: 0,
+            allowed_ops: Vec::new(&env),
+            usage_budget_per_period: 0,
+            budget_period_seconds: 0,
+        },
+        &86_400,
+        &BytesN::from_array(&env, &[7; 32]),
+    );
+    client.set_key_manager(&admin, &key_manager, &root_key_id);
+    let provider = register_provider(&env, &client, &admin);
+    let patient = register_patient(&env, &client, &admin, "Alice");
+
+    let mut inputs = Vec::new(&env);
+    inputs.push_back(BatchRecordInput {
+        patient: patient.clone(),
+        record_type: RecordType::Examination,
+        data_hash: String::from_str(&env, "exam_data"),
+    });
+    inputs.push_back(BatchRecordInput {
+        patient: patient.clone(),
+        record_type: RecordType::Prescription,
+        data_hash: String::from_str(&env, "rx_data"),
+    });
+
+    let ids = client.add_records(&provider, &provider, &inputs);
+    let single = client.add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+    );
+
+    // Batch records are keyed the same way as single ones, whichever key
+    // source that is, and decrypt alike.
+    let single_version = client.get_record(&provider, &single).key_version;
+    assert!(single_version.is_some());
+    for (id, expected) in [
+        (ids.get(0).unwrap(), "exam_data"),
+        (ids.get(1).unwrap(), "rx_data"),
+        (single, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+    ] {
+        let record = client.get_record(&provider, &id);
+        assert_eq!(record.data_hash, String::from_str(&env, expected));
+        assert_eq!(record.key_version, single_version);
+    }
+}
@@ -0,0 +1,90 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    AccessLevel, ConsentType, ContractError, VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{
+    testutils::Address as _, testutils::Events as _, testutils::Ledger as _, vec, xdr, Address,
+    Env, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+fn setup() -> (Env, VisionRecordsContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    let patient = Address::generate(&env);
+    (env, client, patient)
+}
+
+fn consent_with_access(
+    client: &VisionRecordsContractClient,
+    patient: &Address,
+    grantee: &Address,
+    duration: u64,
+) {
+    client.grant_consent(patient, grantee, &ConsentType::Research, &duration);
+    client.grant_access(patient, patient, grantee, &AccessLevel::Read, &(30 * DAY));
+}
+
+fn count_topic(env: &Env, name: &str) -> usize {
+    let name = xdr::ScVal::Symbol(xdr::ScSymbol(name.try_into().unwrap()));
+    env.events()
+        .all()
+        .events()
+        .iter()
+        .filter(|e| match &e.body {
+            xdr::ContractEventBody::V0(v0) => v0.topics.first() == Some(&name),
+        })
+        .count()
+}
+
+#[test]
+fn test_batch_revokes_present_and_skips_absent() {
+    let (env, client, patient) = setup();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let expired = Address::generate(&env);
+    let absent = Address::generate(&env);
+    let kept = Address::generate(&env);
+
+    consent_with_access(&client, &patient, &a, 30 * DAY);
+    consent_with_access(&client, &patient, &b, 30 * DAY);
+    consent_with_access(&client, &patient, &expired, DAY);
+    consent_with_access(&client, &patient, &kept, 30 * DAY);
+    env.ledger().with_mut(|l| l.timestamp += 2 * DAY);
+
+    let revoked = client.revoke_consent_batch(
+        &patient,
+        &vec![&env, a.clone(), absent.clone(), expired.clone(), b.clone()],
+    );
+    assert_eq!(revoked, 2);
+    assert_eq!(count_topic(&env, "CST_REV"), 2);
+    assert_eq!(count_topic(&env, "BATCH_CR"), 1);
+
+    assert_eq!(client.check_access(&patient, &a), AccessLevel::None);
+    assert_eq!(client.check_access(&patient, &b), AccessLevel::None);
+    assert_eq!(client.check_access(&patient, &kept), AccessLevel::Read);
+
+    // Re-running the same batch finds nothing left to revoke.
+    assert_eq!(
+        client.revoke_consent_batch(&patient, &vec![&env, a, b, absent]),
+        0
+    );
+    assert_eq!(count_topic(&env, "CST_REV"), 0);
+    assert_eq!(count_topic(&env, "BATCH_CR"), 1);
+}
+
+#[test]
+fn test_batch_rejects_empty_list() {
+    let (env, client, patient) = setup();
+    assert!(matches!(
+        client.try_revoke_consent_batch(&patient, &Vec::new(&env)),
+        Err(Ok(ContractError::InvalidInput))
+    ));
+}
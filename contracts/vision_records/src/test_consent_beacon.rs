@@ -0,0 +1,209 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    consent_beacon::{self, DEFAULT_BEACON_INTERVAL},
+    AccessLevel, BeaconGrant, ConsentInclusionProof, ConsentType, ContractError,
+    VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Vec,
+};
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    patient: Address,
+    doctor: Address,
+    researcher: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let researcher = Address::generate(&env);
+    client.grant_consent(&patient, &doctor, &ConsentType::Treatment, &86_400);
+    client.grant_access(&patient, &patient, &doctor, &AccessLevel::Read, &86_400);
+    client.grant_consent(&patient, &researcher, &ConsentType::Research, &86_400);
+
+    Setup {
+        env,
+        client,
+        admin,
+        patient,
+        doctor,
+        researcher,
+    }
+}
+
+#[test]
+fn test_included_grant_has_verifiable_proof() {
+    let s = setup();
+    let root = s.client.publish_consent_beacon(&s.patient, &s.patient);
+
+    let beacon = s.client.get_consent_beacon(&s.patient).unwrap();
+    assert_eq!(beacon.root, root);
+    assert_eq!(beacon.sequence, 1);
+    assert_eq!(beacon.grants.len(), 3);
+
+    // The doctor holds both a consent and an access grant.
+    let proofs = s.client.prove_consent_inclusion(&s.patient, &s.doctor);
+    assert_eq!(proofs.len(), 2);
+    for proof in proofs.iter() {
+        assert_eq!(proof.root, root);
+        assert_eq!(proof.grant.grantee(), &s.doctor);
+        assert!(s.client.verify_consent_inclusion(&proof));
+    }
+
+    // Altering the committed grant breaks the proof.
+    let mut forged = proofs.get(0).unwrap();
+    if let BeaconGrant::Consent(ref mut consent) = forged.grant {
+        consent.expires_at += 1;
+    }
+    assert!(!s.client.verify_consent_inclusion(&forged));
+}
+
+#[test]
+fn test_missing_grant_has_no_proof() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_prove_consent_inclusion(&s.patient, &s.doctor),
+        Err(Ok(ContractError::ConsentBeaconNotFound))
+    );
+
+    // A revoked consent is not part of the committed set.
+    s.client.revoke_consent(&s.patient, &s.researcher);
+    s.client.publish_consent_beacon(&s.patient, &s.patient);
+    assert_eq!(
+        s.client
+            .try_prove_consent_inclusion(&s.patient, &s.researcher),
+        Err(Ok(ContractError::GrantNotInBeacon))
+    );
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_prove_consent_inclusion(&s.patient, &stranger),
+        Err(Ok(ContractError::GrantNotInBeacon))
+    );
+
+    // Re-labelling a real proof for another grantee does not verify.
+    let mut forged = s
+        .client
+        .prove_consent_inclusion(&s.patient, &s.doctor)
+        .get(0)
+        .unwrap();
+    if let BeaconGrant::Consent(ref mut consent) = forged.grant {
+        consent.grantee = stranger;
+    }
+    assert!(!s.client.verify_consent_inclusion(&forged));
+}
+
+#[test]
+fn test_beacon_interval_is_enforced_and_configurable() {
+    let s = setup();
+    assert_eq!(
+        s.client.get_consent_beacon_interval(),
+        DEFAULT_BEACON_INTERVAL
+    );
+    let first = s.client.publish_consent_beacon(&s.patient, &s.patient);
+    assert_eq!(
+        s.client.try_publish_consent_beacon(&s.patient, &s.patient),
+        Err(Ok(ContractError::RateLimitExceeded))
+    );
+
+    s.client.set_consent_beacon_interval(&s.admin, &60);
+    s.env.ledger().set_timestamp(1_060);
+    s.client.revoke_consent(&s.patient, &s.researcher);
+    let second = s.client.publish_consent_beacon(&s.patient, &s.patient);
+    assert_ne!(first, second);
+    assert_eq!(s.client.get_consent_beacon(&s.patient).unwrap().sequence, 2);
+
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_set_consent_beacon_interval(&stranger, &0),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_self_consistent_forgery_is_rejected() {
+    let s = setup();
+    s.client.publish_consent_beacon(&s.patient, &s.patient);
+
+    // A one-leaf tree over an invented grant is internally consistent but
+    // does not match the published root.
+    let mut grant = s
+        .client
+        .prove_consent_inclusion(&s.patient, &s.doctor)
+        .get(0)
+        .unwrap()
+        .grant;
+    if let BeaconGrant::Consent(ref mut consent) = grant {
+        consent.grantee = Address::generate(&s.env);
+    }
+    let forged = ConsentInclusionProof {
+        patient: s.patient.clone(),
+        sequence: 1,
+        root: consent_beacon::compute_beacon_root(&s.env, &vec![&s.env, grant.clone()]),
+        grant,
+        leaf_index: 0,
+        tree_size: 1,
+        siblings: Vec::new(&s.env),
+    };
+    assert!(!s.client.verify_consent_inclusion(&forged));
+
+    // The same forgery naming a sequence that was never published.
+    let mut unpublished = forged;
+    unpublished.sequence = 2;
+    assert!(!s.client.verify_consent_inclusion(&unpublished));
+}
+
+#[test]
+fn test_proofs_against_earlier_beacons_still_verify() {
+    let s = setup();
+    s.client.set_consent_beacon_interval(&s.admin, &0);
+    let first_root = s.client.publish_consent_beacon(&s.patient, &s.patient);
+    let old_proof = s
+        .client
+        .prove_consent_inclusion(&s.patient, &s.researcher)
+        .get(0)
+        .unwrap();
+
+    s.client.revoke_consent(&s.patient, &s.researcher);
+    s.client.publish_consent_beacon(&s.patient, &s.patient);
+    assert_eq!(s.client.get_consent_beacon(&s.patient).unwrap().sequence, 2);
+    assert_eq!(
+        s.client.get_consent_beacon_at(&s.patient, &1).unwrap().root,
+        first_root
+    );
+
+    // The researcher was in beacon 1, so the old proof still holds.
+    assert!(s.client.verify_consent_inclusion(&old_proof));
+    // Pointing it at beacon 2 does not.
+    let mut moved = old_proof;
+    moved.sequence = 2;
+    assert!(!s.client.verify_consent_inclusion(&moved));
+}
+
+#[test]
+fn test_publish_requires_patient_or_admin() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_publish_consent_beacon(&stranger, &s.patient),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert!(s.client.get_consent_beacon(&s.patient).is_none());
+
+    s.client.publish_consent_beacon(&s.admin, &s.patient);
+    assert_eq!(s.client.get_consent_beacon(&s.patient).unwrap().sequence, 1);
+}
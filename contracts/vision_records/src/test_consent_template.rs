@@ -0,0 +1,185 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::arithmetic_side_effects
+)]
+
+use super::{
+    AccessLevel, ConsentType, ContractError, RecordType, VisionRecordsContract,
+    VisionRecordsContractClient,
+};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Vec};
+
+const ONE_YEAR: u64 = 31_536_000;
+
+fn setup() -> (Env, VisionRecordsContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    (env, client)
+}
+
+#[test]
+fn test_apply_template_matches_template_parameters() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let id = symbol_short!("primary");
+
+    let mut types = Vec::new(&env);
+    types.push_back(RecordType::Examination);
+    client.create_consent_template(
+        &patient,
+        &id,
+        &ConsentType::Treatment,
+        &AccessLevel::Read,
+        &ONE_YEAR,
+        &types,
+    );
+
+    let consent = client.apply_consent_template(&patient, &provider, &id);
+    let now = env.ledger().timestamp();
+    assert_eq!(consent.patient, patient);
+    assert_eq!(consent.grantee, provider);
+    assert_eq!(consent.consent_type, ConsentType::Treatment);
+    assert_eq!(consent.expires_at, now + ONE_YEAR);
+    assert!(!consent.revoked);
+
+    assert_eq!(client.check_access(&patient, &provider), AccessLevel::Read);
+}
+
+#[test]
+fn test_editing_template_does_not_change_prior_grants() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let id = symbol_short!("primary");
+
+    client.create_consent_template(
+        &patient,
+        &id,
+        &ConsentType::Treatment,
+        &AccessLevel::Read,
+        &ONE_YEAR,
+        &Vec::new(&env),
+    );
+    let original = client.apply_consent_template(&patient, &first, &id);
+
+    client.create_consent_template(
+        &patient,
+        &id,
+        &ConsentType::Research,
+        &AccessLevel::Full,
+        &(ONE_YEAR * 2),
+        &Vec::new(&env),
+    );
+    let updated = client.apply_consent_template(&patient, &second, &id);
+
+    assert_eq!(client.check_access(&patient, &first), AccessLevel::Read);
+    assert_eq!(client.check_access(&patient, &second), AccessLevel::Full);
+    assert_eq!(original.consent_type, ConsentType::Treatment);
+    assert_eq!(updated.consent_type, ConsentType::Research);
+    assert!(updated.expires_at > original.expires_at);
+
+    // Editing keeps a single template entry with the original creation time.
+    let templates = client.list_consent_templates(&patient);
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates.get(0).unwrap().level, AccessLevel::Full);
+}
+
+#[test]
+fn test_list_and_delete_templates() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.create_consent_template(
+        &patient,
+        &symbol_short!("primary"),
+        &ConsentType::Treatment,
+        &AccessLevel::Read,
+        &ONE_YEAR,
+        &Vec::new(&env),
+    );
+    client.create_consent_template(
+        &patient,
+        &symbol_short!("study"),
+        &ConsentType::Research,
+        &AccessLevel::Read,
+        &ONE_YEAR,
+        &Vec::new(&env),
+    );
+
+    assert_eq!(client.list_consent_templates(&patient).len(), 2);
+    assert_eq!(client.list_consent_templates(&other).len(), 0);
+
+    client.delete_consent_template(&patient, &symbol_short!("primary"));
+    let remaining = client.list_consent_templates(&patient);
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(
+        remaining.get(0).unwrap().template_id,
+        symbol_short!("study")
+    );
+
+    let result = client.try_delete_consent_template(&patient, &symbol_short!("primary"));
+    assert_eq!(result, Err(Ok(ContractError::ConsentTemplateNotFound)));
+}
+
+#[test]
+fn test_templates_are_per_patient() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+    let other_patient = Address::generate(&env);
+    let grantee = Address::generate(&env);
+    let id = symbol_short!("primary");
+
+    client.create_consent_template(
+        &patient,
+        &id,
+        &ConsentType::Treatment,
+        &AccessLevel::Read,
+        &ONE_YEAR,
+        &Vec::new(&env),
+    );
+
+    let result = client.try_apply_consent_template(&other_patient, &grantee, &id);
+    assert!(matches!(
+        result,
+        Err(Ok(ContractError::ConsentTemplateNotFound))
+    ));
+}
+
+#[test]
+fn test_create_template_rejects_invalid_parameters() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+    let id = symbol_short!("primary");
+
+    let result = client.try_create_consent_template(
+        &patient,
+        &id,
+        &ConsentType::Treatment,
+        &AccessLevel::None,
+        &ONE_YEAR,
+        &Vec::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let result = client.try_create_consent_template(
+        &patient,
+        &id,
+        &ConsentType::Treatment,
+        &AccessLevel::Read,
+        &0,
+        &Vec::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
@@ -0,0 +1,146 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    audit::{self, AccessAction, AccessResult},
+    rbac::Role,
+    ContractError, RecordType, VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+const DATA_HASH: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+struct Setup {
+    env: Env,
+    contract_id: Address,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    patient: Address,
+    record_id: u64,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_encryption_key(
+        &admin,
+        &String::from_str(&env, "v1"),
+        &String::from_str(
+            &env,
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        ),
+        &0,
+    );
+
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+    let patient = Address::generate(&env);
+    let record_id = client.add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&env, DATA_HASH),
+    );
+
+    Setup {
+        env,
+        contract_id,
+        client,
+        admin,
+        patient,
+        record_id,
+    }
+}
+
+fn register(s: &Setup, role: Role) -> Address {
+    let user = Address::generate(&s.env);
+    s.client
+        .register_user(&s.admin, &user, &role, &String::from_str(&s.env, "User"));
+    user
+}
+
+fn emergency_entries(s: &Setup) -> soroban_sdk::Vec<audit::AuditEntry> {
+    s.env.as_contract(&s.contract_id, || {
+        let mut entries = soroban_sdk::Vec::new(&s.env);
+        for entry in audit::get_record_audit_log(&s.env, s.record_id).iter() {
+            if entry.action == AccessAction::EmergencyAccess {
+                entries.push_back(entry);
+            }
+        }
+        entries
+    })
+}
+
+#[test]
+fn test_responder_reads_without_consent_and_is_audited() {
+    let s = setup();
+    let responder = register(&s, Role::EmergencyResponder);
+    let justification = String::from_str(&s.env, "unconscious patient in ER");
+
+    // No consent or grant: the regular read path refuses.
+    assert!(s.client.try_get_record(&responder, &s.record_id).is_err());
+
+    let record =
+        s.client
+            .emergency_get_record(&responder, &s.patient, &s.record_id, &justification);
+    assert_eq!(record.data_hash, String::from_str(&s.env, DATA_HASH));
+
+    let entries = emergency_entries(&s);
+    assert_eq!(entries.len(), 1);
+    let entry = entries.get(0).unwrap();
+    assert_eq!(entry.actor, responder);
+    assert_eq!(entry.patient, s.patient);
+    assert_eq!(entry.result, AccessResult::Success);
+    assert_eq!(entry.reason, Some(justification));
+}
+
+#[test]
+fn test_non_responder_cannot_break_glass() {
+    let s = setup();
+    let justification = String::from_str(&s.env, "curious");
+
+    for caller in [register(&s, Role::Staff), Address::generate(&s.env)] {
+        assert!(matches!(
+            s.client
+                .try_emergency_get_record(&caller, &s.patient, &s.record_id, &justification),
+            Err(Ok(ContractError::Unauthorized))
+        ));
+    }
+}
+
+#[test]
+fn test_break_glass_requires_justification_and_matching_patient() {
+    let s = setup();
+    let responder = register(&s, Role::EmergencyResponder);
+
+    assert!(matches!(
+        s.client.try_emergency_get_record(
+            &responder,
+            &s.patient,
+            &s.record_id,
+            &String::from_str(&s.env, ""),
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    ));
+    assert!(matches!(
+        s.client.try_emergency_get_record(
+            &responder,
+            &Address::generate(&s.env),
+            &s.record_id,
+            &String::from_str(&s.env, "wrong chart"),
+        ),
+        Err(Ok(ContractError::RecordNotFound))
+    ));
+    assert!(emergency_entries(&s).is_empty());
+}
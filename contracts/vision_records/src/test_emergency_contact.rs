@@ -0,0 +1,205 @@
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+use super::{
+    patient_profile::{CONTACT_CHALLENGE_TTL, DEFAULT_CONTACT_REVERIFY_SECS},
+    ContractError, EmergencyContact, OptionalEmergencyContact, OptionalInsuranceInfo,
+    PatientProfile, VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    patient: Address,
+    contact: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let contact_address = Address::generate(&env);
+    seed_profile(&env, &contract_id, &patient);
+    client.update_emergency_contact(&patient, &patient, &Some(contact(&env, &contact_address)));
+
+    Setup {
+        env,
+        client,
+        admin,
+        patient,
+        contact: contact_address,
+    }
+}
+
+/// Writes a bare profile directly, since profiles have no creation entrypoint.
+fn seed_profile(env: &Env, contract_id: &Address, patient: &Address) {
+    let profile = PatientProfile {
+        patient: patient.clone(),
+        date_of_birth_hash: String::from_str(env, "dob"),
+        gender_hash: String::from_str(env, "gender"),
+        blood_type_hash: String::from_str(env, "blood"),
+        emergency_contact: OptionalEmergencyContact::None,
+        insurance_info: OptionalInsuranceInfo::None,
+        medical_history_refs: Vec::new(env),
+        created_at: env.ledger().timestamp(),
+        updated_at: env.ledger().timestamp(),
+        is_active: true,
+    };
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("PAT_PROF"), patient.clone()), &profile);
+    });
+}
+
+fn contact(env: &Env, address: &Address) -> EmergencyContact {
+    EmergencyContact {
+        address: address.clone(),
+        name: String::from_str(env, "John Doe"),
+        relationship: String::from_str(env, "Spouse"),
+        phone: String::from_str(env, "+1234567890"),
+        email: String::from_str(env, "john@example.com"),
+        // Caller-supplied flags are ignored; new contacts start unverified.
+        verified: true,
+        verified_at: 42,
+    }
+}
+
+fn stored_contact(s: &Setup) -> EmergencyContact {
+    match s.client.get_profile(&s.patient).emergency_contact {
+        OptionalEmergencyContact::Some(c) => c,
+        OptionalEmergencyContact::None => panic!("contact missing"),
+    }
+}
+
+/// Runs the full handshake: the patient requests, the contact answers.
+fn verify(s: &Setup) {
+    let challenge = s.client.request_contact_verification(&s.patient);
+    s.client
+        .confirm_emergency_contact(&s.contact, &s.patient, &challenge);
+}
+
+#[test]
+fn test_new_contact_is_unverified() {
+    let s = setup();
+    let c = stored_contact(&s);
+    assert!(!c.verified);
+    assert_eq!(c.verified_at, 0);
+}
+
+#[test]
+fn test_handshake_marks_contact_verified() {
+    let s = setup();
+    let challenge = s.client.request_contact_verification(&s.patient);
+    s.client
+        .confirm_emergency_contact(&s.contact, &s.patient, &challenge);
+
+    let c = stored_contact(&s);
+    assert!(c.verified);
+    assert_eq!(c.verified_at, 1_000);
+
+    // The challenge is single-use.
+    let result = s
+        .client
+        .try_confirm_emergency_contact(&s.contact, &s.patient, &challenge);
+    assert_eq!(result, Err(Ok(ContractError::ContactChallengeNotFound)));
+}
+
+#[test]
+fn test_patient_cannot_confirm_own_contact() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_update_emergency_contact(
+            &s.patient,
+            &s.patient,
+            &Some(contact(&s.env, &s.patient))
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+
+    let challenge = s.client.request_contact_verification(&s.patient);
+    for impostor in [s.patient.clone(), Address::generate(&s.env)] {
+        let result = s
+            .client
+            .try_confirm_emergency_contact(&impostor, &s.patient, &challenge);
+        assert_eq!(result, Err(Ok(ContractError::ContactChallengeMismatch)));
+    }
+    assert!(!stored_contact(&s).verified);
+}
+
+#[test]
+fn test_wrong_or_expired_response_is_rejected() {
+    let s = setup();
+    let challenge = s.client.request_contact_verification(&s.patient);
+
+    let stale = BytesN::from_array(&s.env, &[0u8; 32]);
+    let result = s
+        .client
+        .try_confirm_emergency_contact(&s.contact, &s.patient, &stale);
+    assert_eq!(result, Err(Ok(ContractError::ContactChallengeMismatch)));
+
+    s.env.ledger().set_timestamp(1_000 + CONTACT_CHALLENGE_TTL);
+    let result = s
+        .client
+        .try_confirm_emergency_contact(&s.contact, &s.patient, &challenge);
+    assert_eq!(result, Err(Ok(ContractError::ContactChallengeExpired)));
+    assert!(!stored_contact(&s).verified);
+}
+
+#[test]
+fn test_verification_lapses_and_resets_on_edit() {
+    let s = setup();
+    verify(&s);
+
+    s.env
+        .ledger()
+        .set_timestamp(1_000 + DEFAULT_CONTACT_REVERIFY_SECS);
+    assert!(!stored_contact(&s).verified);
+
+    // A shorter admin-configured period applies to fresh verifications.
+    s.client.set_contact_reverify_period(&s.admin, &100);
+    verify(&s);
+    assert!(stored_contact(&s).verified);
+
+    s.client
+        .update_emergency_contact(&s.patient, &s.patient, &Some(contact(&s.env, &s.contact)));
+    assert!(!stored_contact(&s).verified);
+}
+
+#[test]
+fn test_challenge_follows_stored_contact_address() {
+    let s = setup();
+    let challenge = s.client.request_contact_verification(&s.patient);
+
+    // Replacing the contact drops the pending challenge, and the new
+    // challenge can only be answered by the newly stored address.
+    let replacement = Address::generate(&s.env);
+    s.client
+        .update_emergency_contact(&s.patient, &s.patient, &Some(contact(&s.env, &replacement)));
+    let result = s
+        .client
+        .try_confirm_emergency_contact(&s.contact, &s.patient, &challenge);
+    assert_eq!(result, Err(Ok(ContractError::ContactChallengeNotFound)));
+
+    let challenge = s.client.request_contact_verification(&s.patient);
+    let result = s
+        .client
+        .try_confirm_emergency_contact(&s.contact, &s.patient, &challenge);
+    assert_eq!(result, Err(Ok(ContractError::ContactChallengeMismatch)));
+    s.client
+        .confirm_emergency_contact(&replacement, &s.patient, &challenge);
+    assert!(stored_contact(&s).verified);
+}
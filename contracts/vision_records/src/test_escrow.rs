@@ -0,0 +1,209 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    audit, escrow, rbac::Role, AccessAction, ContractError, RecordType, VisionRecordsContract,
+    VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, vec, Address, Env, String};
+
+const DELAY: u64 = escrow::MIN_ESCROW_DELAY;
+const DATA_HASH: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+struct Setup {
+    env: Env,
+    contract_id: Address,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    agents: [Address; 3],
+    record_id: u64,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_encryption_key(
+        &admin,
+        &String::from_str(&env, "v1"),
+        &String::from_str(
+            &env,
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        ),
+        &0,
+    );
+
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+    let patient = Address::generate(&env);
+    let record_id = client.add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&env, DATA_HASH),
+    );
+
+    let agents = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.set_escrow_config(
+        &admin,
+        &vec![
+            &env,
+            agents[0].clone(),
+            agents[1].clone(),
+            agents[2].clone(),
+        ],
+        &2,
+        &DELAY,
+    );
+
+    Setup {
+        env,
+        contract_id,
+        client,
+        admin,
+        agents,
+        record_id,
+    }
+}
+
+fn escrow_audit_count(s: &Setup) -> u32 {
+    s.env.as_contract(&s.contract_id, || {
+        audit::get_record_audit_log(&s.env, s.record_id)
+            .iter()
+            .filter(|e| e.action == AccessAction::EscrowDecryption)
+            .count() as u32
+    })
+}
+
+#[test]
+fn test_single_agent_cannot_decrypt() {
+    let s = setup();
+    let reason = String::from_str(&s.env, "court order 42");
+    s.client
+        .request_escrow_decryption(&s.agents[0], &s.record_id, &reason);
+
+    // Only the requester has approved, even well past the delay.
+    s.env.ledger().set_timestamp(DELAY * 10);
+    let result = s
+        .client
+        .try_execute_escrow_decryption(&s.agents[0], &s.record_id);
+    assert_eq!(result, Err(Ok(ContractError::EscrowQuorumNotMet)));
+
+    let result = s
+        .client
+        .try_approve_escrow_decryption(&s.agents[0], &s.record_id);
+    assert_eq!(result, Err(Ok(ContractError::EscrowAlreadyApproved)));
+
+    let outsider = Address::generate(&s.env);
+    let result = s
+        .client
+        .try_approve_escrow_decryption(&outsider, &s.record_id);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_quorum_decrypts_after_delay_with_every_step_audited() {
+    let s = setup();
+    let reason = String::from_str(&s.env, "court order 42");
+    s.client
+        .request_escrow_decryption(&s.agents[0], &s.record_id, &reason);
+    assert_eq!(escrow_audit_count(&s), 1);
+
+    let request = s
+        .client
+        .approve_escrow_decryption(&s.agents[1], &s.record_id);
+    assert_eq!(escrow_audit_count(&s), 2);
+    let unlocks_at = request.unlocks_at.expect("quorum starts the delay");
+    assert_eq!(unlocks_at, s.env.ledger().timestamp() + DELAY);
+
+    s.env.ledger().set_timestamp(unlocks_at - 1);
+    let result = s
+        .client
+        .try_execute_escrow_decryption(&s.agents[2], &s.record_id);
+    assert_eq!(result, Err(Ok(ContractError::EscrowDelayNotElapsed)));
+
+    s.env.ledger().set_timestamp(unlocks_at);
+    let plaintext = s
+        .client
+        .execute_escrow_decryption(&s.agents[2], &s.record_id);
+    assert_eq!(plaintext, String::from_str(&s.env, DATA_HASH));
+    assert_eq!(escrow_audit_count(&s), 3);
+
+    // The request is closed once executed.
+    assert!(s.client.get_escrow_request(&s.record_id).is_none());
+    let result = s
+        .client
+        .try_execute_escrow_decryption(&s.agents[2], &s.record_id);
+    assert_eq!(result, Err(Ok(ContractError::EscrowRequestNotFound)));
+}
+
+#[test]
+fn test_removed_agent_approvals_do_not_count() {
+    let s = setup();
+    let reason = String::from_str(&s.env, "discovery");
+    s.client
+        .request_escrow_decryption(&s.agents[0], &s.record_id, &reason);
+    let request = s
+        .client
+        .approve_escrow_decryption(&s.agents[1], &s.record_id);
+
+    s.client.set_escrow_config(
+        &s.admin,
+        &vec![&s.env, s.agents[1].clone(), s.agents[2].clone()],
+        &2,
+        &DELAY,
+    );
+    s.env.ledger().set_timestamp(request.unlocks_at.unwrap());
+    let result = s
+        .client
+        .try_execute_escrow_decryption(&s.agents[1], &s.record_id);
+    assert_eq!(result, Err(Ok(ContractError::EscrowQuorumNotMet)));
+}
+
+#[test]
+fn test_escrow_config_validation() {
+    let s = setup();
+    let agents = vec![&s.env, s.agents[0].clone(), s.agents[1].clone()];
+
+    let result = s
+        .client
+        .try_set_escrow_config(&s.admin, &agents, &1, &DELAY);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let result = s
+        .client
+        .try_set_escrow_config(&s.admin, &agents, &3, &DELAY);
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let result = s
+        .client
+        .try_set_escrow_config(&s.admin, &agents, &2, &(DELAY - 1));
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    let result = s
+        .client
+        .try_set_escrow_config(&s.agents[0], &agents, &2, &DELAY);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+
+    let reason = String::from_str(&s.env, "discovery");
+    s.client
+        .request_escrow_decryption(&s.agents[0], &s.record_id, &reason);
+    let result = s
+        .client
+        .try_request_escrow_decryption(&s.agents[1], &s.record_id, &reason);
+    assert_eq!(result, Err(Ok(ContractError::EscrowRequestExists)));
+}
@@ -0,0 +1,166 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    examination::{self, IntraocularPressure, SlitLampFindings, VisualAcuity},
+    ContractError, OptFundusPhotography, OptRetinalImaging, OptVisualField, RecordType, Role,
+    VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+
+const DATA_HASH: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    provider: Address,
+    patient: Address,
+    record_id: u64,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+
+    let patient = Address::generate(&env);
+    let record_id = client.add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&env, DATA_HASH),
+    );
+
+    Setup {
+        env,
+        client,
+        provider,
+        patient,
+        record_id,
+    }
+}
+
+fn iop(env: &Env, left_eye: u32) -> IntraocularPressure {
+    IntraocularPressure {
+        left_eye,
+        right_eye: 15,
+        method: String::from_str(env, "Goldmann"),
+        timestamp: 1000,
+    }
+}
+
+fn visual_acuity(env: &Env) -> VisualAcuity {
+    VisualAcuity {
+        uncorrected: examination::PhysicalMeasurement {
+            left_eye: String::from_str(env, "20/20"),
+            right_eye: String::from_str(env, "20/25"),
+        },
+        corrected: examination::OptPhysicalMeasurement::None,
+    }
+}
+
+fn slit_lamp(env: &Env) -> SlitLampFindings {
+    SlitLampFindings {
+        cornea: String::from_str(env, "clear"),
+        anterior_chamber: String::from_str(env, "deep"),
+        iris: String::from_str(env, "normal"),
+        lens: String::from_str(env, "clear"),
+    }
+}
+
+fn update(s: &Setup, expected_version: u64, left_iop: u32, notes: &str) {
+    s.client.update_examination_versioned(
+        &s.provider,
+        &s.record_id,
+        &expected_version,
+        &1u32,
+        &visual_acuity(&s.env),
+        &iop(&s.env, left_iop),
+        &slit_lamp(&s.env),
+        &OptVisualField::None,
+        &OptRetinalImaging::None,
+        &OptFundusPhotography::None,
+        &String::from_str(&s.env, notes),
+        &Vec::new(&s.env),
+    );
+}
+
+#[test]
+fn test_prior_version_is_readable_after_update() {
+    let s = setup();
+    s.client.add_eye_examination(
+        &s.provider,
+        &s.record_id,
+        &visual_acuity(&s.env),
+        &iop(&s.env, 14),
+        &slit_lamp(&s.env),
+        &OptVisualField::None,
+        &OptRetinalImaging::None,
+        &OptFundusPhotography::None,
+        &String::from_str(&s.env, "Baseline"),
+    );
+    assert_eq!(s.client.get_record_version_stamp(&s.record_id).version, 1);
+
+    update(&s, 1, 21, "Raised IOP");
+    update(&s, 2, 17, "After drops");
+
+    let v1 = s
+        .client
+        .get_examination_at_version(&s.patient, &s.record_id, &1);
+    assert_eq!(v1.iop.left_eye, 14);
+    assert_eq!(v1.clinical_notes, String::from_str(&s.env, "Baseline"));
+
+    let v2 = s
+        .client
+        .get_examination_at_version(&s.provider, &s.record_id, &2);
+    assert_eq!(v2.iop.left_eye, 21);
+    assert_eq!(v2.clinical_notes, String::from_str(&s.env, "Raised IOP"));
+
+    let v3 = s
+        .client
+        .get_examination_at_version(&s.provider, &s.record_id, &3);
+    assert_eq!(v3, s.client.get_eye_examination(&s.provider, &s.record_id));
+    assert_eq!(v3.iop.left_eye, 17);
+
+    assert!(matches!(
+        s.client
+            .try_get_examination_at_version(&s.provider, &s.record_id, &4),
+        Err(Ok(ContractError::RecordNotFound))
+    ));
+}
+
+#[test]
+fn test_history_uses_exam_read_acl() {
+    let s = setup();
+    update(&s, 1, 21, "First");
+
+    let stranger = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_get_examination_at_version(&stranger, &s.record_id, &2)
+        .is_err());
+    assert!(s
+        .client
+        .try_get_eye_examination(&stranger, &s.record_id)
+        .is_err());
+
+    // No examination existed before the first versioned write, so there is
+    // nothing to snapshot under version 1.
+    assert!(matches!(
+        s.client
+            .try_get_examination_at_version(&s.patient, &s.record_id, &1),
+        Err(Ok(ContractError::RecordNotFound))
+    ));
+}
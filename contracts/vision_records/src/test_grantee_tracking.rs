@@ -0,0 +1,89 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{AccessLevel, ConsentType, VisionRecordsContract, VisionRecordsContractClient};
+use soroban_sdk::{
+    symbol_short, testutils::Address as _, testutils::Ledger as _, Address, Env, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+fn setup() -> (Env, Address, VisionRecordsContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    let patient = Address::generate(&env);
+    (env, contract_id, client, patient)
+}
+
+fn tracked(env: &Env, contract_id: &Address, patient: &Address) -> Vec<Address> {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("ACC_LST"), patient.clone()))
+            .unwrap_or(Vec::new(env))
+    })
+}
+
+#[test]
+fn test_revoke_access_untracks_grantee() {
+    let (env, contract_id, client, patient) = setup();
+    let grantee = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.grant_access(&patient, &patient, &grantee, &AccessLevel::Read, &DAY);
+    client.grant_access(&patient, &patient, &other, &AccessLevel::Read, &DAY);
+    assert_eq!(tracked(&env, &contract_id, &patient).len(), 2);
+
+    client.revoke_access(&patient, &grantee);
+    let list = tracked(&env, &contract_id, &patient);
+    assert!(!list.contains(&grantee));
+    assert!(list.contains(&other));
+
+    // Re-granting tracks the grantee exactly once again.
+    client.grant_access(&patient, &patient, &grantee, &AccessLevel::Read, &DAY);
+    client.grant_access(&patient, &patient, &grantee, &AccessLevel::Write, &DAY);
+    let list = tracked(&env, &contract_id, &patient);
+    assert_eq!(list.len(), 2);
+    assert!(list.contains(&grantee));
+}
+
+#[test]
+fn test_revoke_access_keeps_grantee_with_consent() {
+    let (env, contract_id, client, patient) = setup();
+    let grantee = Address::generate(&env);
+
+    client.grant_consent(&patient, &grantee, &ConsentType::Treatment, &DAY);
+    client.grant_access(&patient, &patient, &grantee, &AccessLevel::Read, &DAY);
+    client.revoke_access(&patient, &grantee);
+    assert!(tracked(&env, &contract_id, &patient).contains(&grantee));
+}
+
+#[test]
+fn test_purge_expired_grants_drops_lapsed_grantees() {
+    let (env, contract_id, client, patient) = setup();
+    let short = Address::generate(&env);
+    let long = Address::generate(&env);
+    let consented = Address::generate(&env);
+
+    client.grant_access(&patient, &patient, &short, &AccessLevel::Read, &DAY);
+    client.grant_access(&patient, &patient, &long, &AccessLevel::Read, &(30 * DAY));
+    client.grant_consent(&patient, &consented, &ConsentType::Research, &(30 * DAY));
+    assert_eq!(client.purge_expired_grants(&patient), 0);
+
+    env.ledger().with_mut(|l| l.timestamp += 2 * DAY);
+    assert_eq!(client.purge_expired_grants(&patient), 1);
+
+    let list = tracked(&env, &contract_id, &patient);
+    assert_eq!(list.len(), 2);
+    assert!(!list.contains(&short));
+    assert!(list.contains(&long));
+    assert!(list.contains(&consented));
+    assert_eq!(client.check_access(&patient, &short), AccessLevel::None);
+
+    // Nothing left to drop.
+    assert_eq!(client.purge_expired_grants(&patient), 0);
+}
@@ -0,0 +1,127 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{ContractError, RecordType, Role, VisionRecordsContract, VisionRecordsContractClient};
+use key_manager::{KeyManagerContract, KeyManagerContractClient, KeyPolicy, KeyType};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+const EXAM_HASH: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+const RX_HASH: &str = "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o";
+
+fn setup() -> (Env, VisionRecordsContractClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+
+    (env, client, admin, provider)
+}
+
+fn set_key(env: &Env, client: &VisionRecordsContractClient, admin: &Address, version: &str) {
+    client.set_encryption_key(
+        admin,
+        &String::from_str(env, version),
+        &String::from_str(
+            env,
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        ),
+        &0,
+    );
+}
+
+fn add(env: &Env, client: &VisionRecordsContractClient, provider: &Address, hash: &str) -> u64 {
+    client.add_record(
+        provider,
+        &Address::generate(env),
+        provider,
+        &RecordType::Examination,
+        &String::from_str(env, hash),
+    )
+}
+
+#[test]
+fn test_record_keeps_key_version_across_rotation() {
+    let (env, client, admin, provider) = setup();
+    set_key(&env, &client, &admin, "v1");
+    let old_id = add(&env, &client, &provider, EXAM_HASH);
+
+    set_key(&env, &client, &admin, "v2");
+    let new_id = add(&env, &client, &provider, RX_HASH);
+
+    let old = client.get_record(&provider, &old_id);
+    assert_eq!(old.key_version, Some(String::from_str(&env, "v1")));
+    assert_eq!(old.data_hash, String::from_str(&env, EXAM_HASH));
+
+    let new = client.get_record(&provider, &new_id);
+    assert_eq!(new.key_version, Some(String::from_str(&env, "v2")));
+    assert_eq!(new.data_hash, String::from_str(&env, RX_HASH));
+}
+
+#[test]
+fn test_key_manager_version_is_recorded_and_decrypts_after_rotation() {
+    let (env, client, admin, provider) = setup();
+    set_key(&env, &client, &admin, "v1");
+
+    let key_manager = env.register(KeyManagerContract, ());
+    let km_client = KeyManagerContractClient::new(&env, &key_manager);
+    km_client.initialize(&admin, &Address::generate(&env));
+    let root_key_id = km_client.create_master_key(
+        &admin,
+        &KeyType::Encryption,
+        &KeyPolicy {
+            max_uses: 0,
+            not_before: 0,
+            not_after: 0,
+            allowed_ops: Vec::new(&env),
+            usage_budget_per_period: 0,
+            budget_period_seconds: 0,
+        },
+        &0,
+        &BytesN::from_array(&env, &[7; 32]),
+    );
+    client.set_key_manager(&admin, &key_manager, &root_key_id);
+
+    let old_id = add(&env, &client, &provider, EXAM_HASH);
+    km_client.rotate_key(&admin, &root_key_id);
+    let new_id = add(&env, &client, &provider, RX_HASH);
+
+    // The derived version is stored, not the local master key version.
+    let old = client.get_record(&provider, &old_id);
+    assert_eq!(old.key_version, Some(String::from_str(&env, "km:1")));
+    assert_eq!(old.data_hash, String::from_str(&env, EXAM_HASH));
+
+    let new = client.get_record(&provider, &new_id);
+    assert_eq!(new.key_version, Some(String::from_str(&env, "km:2")));
+    assert_eq!(new.data_hash, String::from_str(&env, RX_HASH));
+}
+
+#[test]
+fn test_local_key_names_cannot_shadow_key_manager_versions() {
+    let (env, client, admin, _provider) = setup();
+    for name in ["1", "42", "km:1", "km:v1"] {
+        assert_eq!(
+            client.try_set_encryption_key(
+                &admin,
+                &String::from_str(&env, name),
+                &String::from_str(
+                    &env,
+                    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+                ),
+                &0,
+            ),
+            Err(Ok(ContractError::InvalidInput))
+        );
+    }
+    set_key(&env, &client, &admin, "v1");
+}
@@ -258,6 +258,11 @@ fn test_stale_version_triggers_conflict_manual_review() {
         }
         other => panic!("Expected Conflicted, got {:?}", other),
     }
+
+    // The stale write was queued, not applied.
+    let exam = client.get_eye_examination(&provider, &record_id);
+    assert_eq!(exam.clinical_notes, String::from_str(&env, "First update"));
+    assert_eq!(client.get_record_version_stamp(&record_id).version, 2);
 }
 
 #[test]
@@ -346,6 +351,11 @@ fn test_last_writer_wins_strategy() {
     );
 
     assert!(matches!(outcome, UpdateOutcome::Applied(_)));
+
+    // The stale write landed on top of the first one.
+    let exam = client.get_eye_examination(&provider, &record_id);
+    assert_eq!(exam.clinical_notes, String::from_str(&env, "LWW override"));
+    assert_eq!(client.get_record_version_stamp(&record_id).version, 3);
 }
 
 #[test]
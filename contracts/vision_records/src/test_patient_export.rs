@@ -0,0 +1,143 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    portability::{self, MAX_EXPORT_PAGE},
+    rbac::Role,
+    ConsentType, ContractError, RecordType, VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+const DATA: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    contract_id: Address,
+    patient: Address,
+    provider: Address,
+}
+
+fn setup(records: u32) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Dr. Export"),
+    );
+    client.grant_consent(&patient, &provider, &ConsentType::Treatment, &86_400);
+
+    let data = String::from_str(&env, DATA);
+    for _ in 0..records {
+        client.add_record(
+            &provider,
+            &patient,
+            &provider,
+            &RecordType::Examination,
+            &data,
+        );
+    }
+
+    Setup {
+        env,
+        client,
+        contract_id,
+        patient,
+        provider,
+    }
+}
+
+#[test]
+fn test_export_includes_all_records_and_consents() {
+    let s = setup(3);
+    let export = s.client.export_patient_data(&s.patient, &0, &10, &None);
+
+    assert_eq!(export.total_records, 3);
+    assert_eq!(export.records.len(), 3);
+    assert!(!export.has_more);
+    for record in export.records.iter() {
+        assert_eq!(record.provider, s.provider);
+        assert_eq!(record.data, String::from_str(&s.env, DATA));
+    }
+
+    assert_eq!(export.consents.len(), 1);
+    let consent = export.consents.get(0).unwrap();
+    assert_eq!(consent.grantee, s.provider);
+    assert_eq!(consent.consent_type, ConsentType::Treatment);
+}
+
+#[test]
+fn test_export_pages_form_verifiable_chain() {
+    let s = setup(5);
+    let first = s.client.export_patient_data(&s.patient, &0, &2, &None);
+    let second = s
+        .client
+        .export_patient_data(&s.patient, &2, &2, &Some(first.export_hash.clone()));
+    let third = s
+        .client
+        .export_patient_data(&s.patient, &4, &2, &Some(second.export_hash.clone()));
+
+    assert!(first.has_more && second.has_more && !third.has_more);
+    assert_eq!(third.records.len(), 1);
+    assert_eq!(second.prev_hash, first.export_hash);
+    assert_eq!(third.prev_hash, second.export_hash);
+    // Consent history is only carried on the first page.
+    assert_eq!(first.consents.len(), 1);
+    assert!(second.consents.is_empty());
+
+    // A verifier can recompute the whole chain from the pages alone.
+    s.env.as_contract(&s.contract_id, || {
+        let mut hash = portability::genesis_hash(&s.env, &s.patient, &first.consents);
+        assert_eq!(hash, first.prev_hash);
+        for page in [&first, &second, &third] {
+            assert_eq!(hash, page.prev_hash);
+            for record in page.records.iter() {
+                hash = portability::chain_record(&s.env, &hash, &record);
+            }
+            assert_eq!(hash, page.export_hash);
+        }
+    });
+}
+
+#[test]
+fn test_export_page_size_is_capped() {
+    let s = setup(MAX_EXPORT_PAGE + 1);
+    let export = s
+        .client
+        .export_patient_data(&s.patient, &0, &u32::MAX, &None);
+    assert_eq!(export.records.len(), MAX_EXPORT_PAGE);
+    assert!(export.has_more);
+
+    let result = s.client.try_export_patient_data(&s.patient, &0, &0, &None);
+    assert!(matches!(result, Err(Ok(ContractError::InvalidInput))));
+}
+
+#[test]
+fn test_export_requires_prev_hash_exactly_after_first_page() {
+    let s = setup(3);
+    let first = s.client.export_patient_data(&s.patient, &0, &1, &None);
+
+    // Later pages chain from the caller's previous page, not from a replay.
+    let result = s.client.try_export_patient_data(&s.patient, &1, &1, &None);
+    assert!(matches!(result, Err(Ok(ContractError::InvalidInput))));
+    let result =
+        s.client
+            .try_export_patient_data(&s.patient, &0, &1, &Some(first.export_hash.clone()));
+    assert!(matches!(result, Err(Ok(ContractError::InvalidInput))));
+
+    // The chain over separate pages matches a single-page export.
+    let second = s
+        .client
+        .export_patient_data(&s.patient, &1, &2, &Some(first.export_hash));
+    let whole = s.client.export_patient_data(&s.patient, &0, &3, &None);
+    assert_eq!(second.export_hash, whole.export_hash);
+}
@@ -231,10 +231,13 @@ fn test_update_emergency_contact() {
 
     // Add emergency contact
     let contact = EmergencyContact {
+        address: Address::generate(&env),
         name: String::from_str(&env, "John Doe"),
         relationship: String::from_str(&env, "Spouse"),
         phone: String::from_str(&env, "+1234567890"),
         email: String::from_str(&env, "john@example.com"),
+        verified: false,
+        verified_at: 0,
     };
 
     client.update_emergency_contact(&patient, &patient, &Some(contact.clone()));
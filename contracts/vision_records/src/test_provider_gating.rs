@@ -0,0 +1,190 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    prescription::PrescriptionData,
+    provider::{self, Provider},
+    rbac::Role,
+    BatchRecordInput, ContractError, RecordType, VerificationStatus, VisionRecordsContract,
+    VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    contract_id: Address,
+    admin: Address,
+    patient: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let patient = Address::generate(&env);
+
+    Setup {
+        env,
+        client,
+        contract_id,
+        admin,
+        patient,
+    }
+}
+
+/// Registers an Optometrist and stores a provider entry with `status`.
+fn provider_with_status(s: &Setup, status: VerificationStatus) -> Address {
+    let address = Address::generate(&s.env);
+    s.client.register_user(
+        &s.admin,
+        &address,
+        &Role::Optometrist,
+        &String::from_str(&s.env, "Dr. Gate"),
+    );
+    let entry = Provider {
+        address: address.clone(),
+        name: String::from_str(&s.env, "Dr. Gate"),
+        licenses: Vec::new(&s.env),
+        specialties: Vec::new(&s.env),
+        certifications: Vec::new(&s.env),
+        locations: Vec::new(&s.env),
+        verification_status: status,
+        registered_at: 0,
+        verified_at: None,
+        verified_by: None,
+        is_active: true,
+    };
+    s.env
+        .as_contract(&s.contract_id, || provider::set_provider(&s.env, &entry));
+    address
+}
+
+fn try_add(s: &Setup, provider: &Address) -> Result<u64, ContractError> {
+    let hash = String::from_str(&s.env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+    match s.client.try_add_record(
+        provider,
+        &s.patient,
+        provider,
+        &RecordType::Examination,
+        &hash,
+    ) {
+        Ok(Ok(id)) => Ok(id),
+        Err(Ok(err)) => Err(err),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+fn rx(env: &Env) -> PrescriptionData {
+    PrescriptionData {
+        sphere: String::from_str(env, "-1.25"),
+        cylinder: String::from_str(env, "-0.50"),
+        axis: String::from_str(env, "90"),
+        add: String::from_str(env, "0"),
+        pd: String::from_str(env, "63"),
+    }
+}
+
+#[test]
+fn test_policy_off_by_default() {
+    let s = setup();
+    assert!(!s.client.get_require_verified_provider());
+
+    let pending = provider_with_status(&s, VerificationStatus::Pending);
+    assert!(try_add(&s, &pending).is_ok());
+}
+
+#[test]
+fn test_unverified_provider_blocked_when_policy_on() {
+    let s = setup();
+    s.client.set_require_verified_provider(&s.admin, &true);
+
+    let pending = provider_with_status(&s, VerificationStatus::Pending);
+    let suspended = provider_with_status(&s, VerificationStatus::Suspended);
+    assert_eq!(
+        try_add(&s, &pending),
+        Err(ContractError::ProviderNotVerified)
+    );
+    assert_eq!(
+        try_add(&s, &suspended),
+        Err(ContractError::ProviderNotVerified)
+    );
+
+    let result = s
+        .client
+        .try_prepare_add_prescription(&s.patient, &pending, &rx(&s.env));
+    assert_eq!(result, Err(Ok(ContractError::ProviderNotVerified)));
+
+    // The batch and two-phase write paths are gated the same way.
+    let hash = String::from_str(&s.env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+    let batch = vec![
+        &s.env,
+        BatchRecordInput {
+            patient: s.patient.clone(),
+            record_type: RecordType::Examination,
+            data_hash: hash.clone(),
+        },
+    ];
+    let result = s.client.try_add_records(&pending, &pending, &batch);
+    assert_eq!(result, Err(Ok(ContractError::ProviderNotVerified)));
+    let result = s.client.try_prepare_add_record(
+        &pending,
+        &s.patient,
+        &pending,
+        &RecordType::Examination,
+        &hash,
+    );
+    assert_eq!(result, Err(Ok(ContractError::ProviderNotVerified)));
+}
+
+#[test]
+fn test_verified_provider_can_write_when_policy_on() {
+    let s = setup();
+    s.client.set_require_verified_provider(&s.admin, &true);
+
+    let verified = provider_with_status(&s, VerificationStatus::Verified);
+    assert!(try_add(&s, &verified).is_ok());
+    let result = s
+        .client
+        .try_prepare_add_prescription(&s.patient, &verified, &rx(&s.env));
+    assert!(matches!(result, Ok(Ok(_))));
+}
+
+#[test]
+fn test_system_admin_overrides_policy() {
+    let s = setup();
+    s.client.set_require_verified_provider(&s.admin, &true);
+    let pending = provider_with_status(&s, VerificationStatus::Pending);
+
+    let hash = String::from_str(&s.env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+    let result = s.client.try_add_record(
+        &s.admin,
+        &s.patient,
+        &pending,
+        &RecordType::Examination,
+        &hash,
+    );
+    assert!(matches!(result, Ok(Ok(_))));
+
+    let batch = vec![
+        &s.env,
+        BatchRecordInput {
+            patient: s.patient.clone(),
+            record_type: RecordType::Examination,
+            data_hash: hash.clone(),
+        },
+    ];
+    let ids = s.client.add_records(&s.admin, &pending, &batch);
+    let record = s.client.get_record(&s.admin, &ids.get(0).unwrap());
+    assert_eq!(record.provider, pending);
+
+    let outsider = Address::generate(&s.env);
+    let result = s
+        .client
+        .try_set_require_verified_provider(&outsider, &false);
+    assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+}
@@ -0,0 +1,145 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    audit, AccessAction, AccessResult, ContractError, RecordType, VisionRecordsContract,
+    VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+const TARGET_SCAN: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+const SOURCE_SCAN: &str = "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o";
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    contract_id: Address,
+    admin: Address,
+    patient: Address,
+    source: Address,
+    target: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    Setup {
+        env: env.clone(),
+        client,
+        contract_id,
+        admin,
+        patient: Address::generate(&env),
+        source: Address::generate(&env),
+        target: Address::generate(&env),
+    }
+}
+
+fn add(s: &Setup, provider: &Address, data_hash: &str) -> u64 {
+    s.client.add_record(
+        &s.admin,
+        &s.patient,
+        provider,
+        &RecordType::Examination,
+        &String::from_str(&s.env, data_hash),
+    )
+}
+
+#[test]
+fn test_merge_reassigns_provider_and_skips_duplicates() {
+    let s = setup();
+    let kept = add(&s, &s.target, TARGET_SCAN);
+    let moved = add(&s, &s.source, SOURCE_SCAN);
+    let duplicate = add(&s, &s.source, TARGET_SCAN);
+    // Another patient's record is untouched by the merge.
+    let other_patient = Address::generate(&s.env);
+    s.client.add_record(
+        &s.admin,
+        &other_patient,
+        &s.source,
+        &RecordType::Examination,
+        &String::from_str(&s.env, SOURCE_SCAN),
+    );
+
+    let merged = s.client.merge_patient_records(
+        &s.admin,
+        &s.patient,
+        &s.source,
+        &s.target,
+        &vec![&s.env, moved, duplicate],
+    );
+    assert_eq!(merged, vec![&s.env, moved]);
+
+    let record = s.client.get_record(&s.patient, &moved);
+    assert_eq!(record.provider, s.target);
+    // The duplicate stays with the source provider.
+    let record = s.client.get_record(&s.patient, &duplicate);
+    assert_eq!(record.provider, s.source);
+
+    // The patient's list still holds each record exactly once.
+    let list = s.client.get_patient_records(&s.patient);
+    assert_eq!(list, vec![&s.env, kept, moved, duplicate]);
+
+    // Every move is audited; the skipped duplicate is recorded as denied.
+    let entries = s.env.as_contract(&s.contract_id, || {
+        audit::get_record_audit_log(&s.env, moved)
+    });
+    assert!(entries.iter().any(|e| e.actor == s.admin
+        && e.action == AccessAction::Write
+        && e.result == AccessResult::Success));
+    let entries = s.env.as_contract(&s.contract_id, || {
+        audit::get_record_audit_log(&s.env, duplicate)
+    });
+    assert!(entries
+        .iter()
+        .any(|e| e.action == AccessAction::Write && e.result == AccessResult::Denied));
+}
+
+#[test]
+fn test_merge_requires_provider_authority_and_matching_records() {
+    let s = setup();
+    let record_id = add(&s, &s.source, SOURCE_SCAN);
+    let ids = vec![&s.env, record_id];
+
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_merge_patient_records(&stranger, &s.patient, &s.source, &s.target, &ids),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    // Records must belong to the patient and the source provider.
+    assert_eq!(
+        s.client
+            .try_merge_patient_records(&s.admin, &s.patient, &s.target, &s.source, &ids),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        s.client.try_merge_patient_records(
+            &s.admin,
+            &Address::generate(&s.env),
+            &s.source,
+            &s.target,
+            &ids
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        s.client.try_merge_patient_records(
+            &s.admin,
+            &s.patient,
+            &s.source,
+            &s.target,
+            &vec![&s.env, 999]
+        ),
+        Err(Ok(ContractError::RecordNotFound))
+    );
+    assert_eq!(
+        s.client
+            .try_merge_patient_records(&s.admin, &s.patient, &s.source, &s.source, &ids),
+        Err(Ok(ContractError::InvalidInput))
+    );
+}
@@ -0,0 +1,145 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    patient_index::PATIENT_INDEX_CHUNK, RecordType, Role, VisionRecordsContract,
+    VisionRecordsContractClient, MAX_PATIENT_RECORDS_PAGE,
+};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, String, Vec};
+
+const DATA_HASH: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+fn setup(count: u32) -> (Env, VisionRecordsContractClient<'static>, Address, Vec<u64>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+
+    let patient = Address::generate(&env);
+    let mut ids = Vec::new(&env);
+    for _ in 0..count {
+        ids.push_back(client.add_record(
+            &provider,
+            &patient,
+            &provider,
+            &RecordType::Examination,
+            &String::from_str(&env, DATA_HASH),
+        ));
+    }
+
+    (env, client, patient, ids)
+}
+
+#[test]
+fn test_pages_follow_insertion_order() {
+    let (_env, client, patient, ids) = setup(5);
+
+    let first = client.get_patient_records_paged(&patient, &0, &2);
+    let second = client.get_patient_records_paged(&patient, &2, &2);
+    assert_eq!(first, ids.slice(0..2));
+    assert_eq!(second, ids.slice(2..4));
+
+    // The final page is partial.
+    let last = client.get_patient_records_paged(&patient, &4, &2);
+    assert_eq!(last, ids.slice(4..5));
+}
+
+#[test]
+fn test_offset_past_end_is_empty() {
+    let (env, client, patient, _ids) = setup(3);
+    for offset in [3, 4, u32::MAX] {
+        assert_eq!(
+            client.get_patient_records_paged(&patient, &offset, &10),
+            Vec::new(&env)
+        );
+    }
+    assert!(client
+        .get_patient_records_paged(&Address::generate(&env), &0, &10)
+        .is_empty());
+}
+
+#[test]
+fn test_limit_is_clamped() {
+    let (_env, client, patient, ids) = setup(MAX_PATIENT_RECORDS_PAGE + 5);
+
+    let page = client.get_patient_records_paged(&patient, &0, &u32::MAX);
+    assert_eq!(page.len(), MAX_PATIENT_RECORDS_PAGE);
+    assert_eq!(page, ids.slice(0..MAX_PATIENT_RECORDS_PAGE));
+
+    let rest = client.get_patient_records_paged(&patient, &MAX_PATIENT_RECORDS_PAGE, &u32::MAX);
+    assert_eq!(rest, ids.slice(MAX_PATIENT_RECORDS_PAGE..ids.len()));
+}
+
+#[test]
+fn test_pages_span_index_chunks() {
+    let (_env, client, patient, ids) = setup(2 * PATIENT_INDEX_CHUNK + 3);
+    assert_eq!(client.get_patient_record_count(&patient), ids.len());
+
+    let start = PATIENT_INDEX_CHUNK - 2;
+    let page = client.get_patient_records_paged(&patient, &start, &(PATIENT_INDEX_CHUNK + 4));
+    assert_eq!(page, ids.slice(start..start + PATIENT_INDEX_CHUNK + 4));
+    assert_eq!(client.get_patient_records(&patient), ids);
+}
+
+#[test]
+fn test_legacy_index_is_read_and_migrated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+
+    // An index written before chunking: one entry holding every id.
+    let patient = Address::generate(&env);
+    let mut legacy = Vec::new(&env);
+    for id in 0..PATIENT_INDEX_CHUNK + 1 {
+        legacy.push_back(1_000 + u64::from(id));
+    }
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("PAT_REC"), patient.clone()), &legacy);
+    });
+    assert_eq!(client.get_patient_record_count(&patient), legacy.len());
+    assert_eq!(
+        client.get_patient_records_paged(&patient, &1, &2),
+        legacy.slice(1..3)
+    );
+
+    // The next write splits it into chunks and appends after the old ids.
+    let id = client.add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&env, DATA_HASH),
+    );
+    legacy.push_back(id);
+    assert!(env.as_contract(&contract_id, || !env
+        .storage()
+        .persistent()
+        .has(&(symbol_short!("PAT_REC"), patient.clone()))));
+    assert_eq!(client.get_patient_records(&patient), legacy);
+    assert_eq!(
+        client.get_patient_records_paged(&patient, &PATIENT_INDEX_CHUNK, &5),
+        legacy.slice(PATIENT_INDEX_CHUNK..legacy.len())
+    );
+}
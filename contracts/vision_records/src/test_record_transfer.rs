@@ -0,0 +1,175 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    audit::{self, AccessAction},
+    ContractError, RecordType, Role, SensitivityLevel, VisionRecordsContract,
+    VisionRecordsContractClient,
+};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, Address, Env, String};
+
+const DATA_HASH: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+struct Setup {
+    env: Env,
+    contract_id: Address,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    old_provider: Address,
+    new_provider: Address,
+    patient: Address,
+    record_id: u64,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let register = |name: &str| {
+        let provider = Address::generate(&env);
+        client.register_user(
+            &admin,
+            &provider,
+            &Role::Optometrist,
+            &String::from_str(&env, name),
+        );
+        provider
+    };
+    let old_provider = register("Leaving");
+    let new_provider = register("Successor");
+
+    let patient = Address::generate(&env);
+    let record_id = client.add_record(
+        &old_provider,
+        &patient,
+        &old_provider,
+        &RecordType::Examination,
+        &String::from_str(&env, DATA_HASH),
+    );
+
+    Setup {
+        env,
+        contract_id,
+        client,
+        admin,
+        old_provider,
+        new_provider,
+        patient,
+        record_id,
+    }
+}
+
+#[test]
+fn test_transfer_moves_provider_rights() {
+    let s = setup();
+    s.env.ledger().with_mut(|l| l.timestamp += 100);
+
+    s.client
+        .transfer_record_ownership(&s.old_provider, &s.record_id, &s.new_provider);
+
+    let record = s.client.get_record(&s.new_provider, &s.record_id);
+    assert_eq!(record.provider, s.new_provider);
+    assert_eq!(record.patient, s.patient);
+    assert_eq!(record.updated_at, record.created_at + 100);
+
+    // The successor now passes the provider WriteRecord check on the record;
+    // there is no conflict 99, so it gets as far as the lookup.
+    s.client
+        .set_record_sensitivity(&s.new_provider, &s.record_id, &SensitivityLevel::Restricted);
+    assert!(matches!(
+        s.client
+            .try_resolve_conflict(&s.new_provider, &99, &s.record_id),
+        Err(Ok(ContractError::RecordNotFound))
+    ));
+
+    // The previous provider no longer has provider-based rights on it.
+    assert!(matches!(
+        s.client
+            .try_resolve_conflict(&s.old_provider, &99, &s.record_id),
+        Err(Ok(ContractError::Unauthorized))
+    ));
+    assert!(matches!(
+        s.client.try_set_record_sensitivity(
+            &s.old_provider,
+            &s.record_id,
+            &SensitivityLevel::Public
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    ));
+    assert!(matches!(
+        s.client
+            .try_transfer_record_ownership(&s.old_provider, &s.record_id, &s.old_provider),
+        Err(Ok(ContractError::Unauthorized))
+    ));
+
+    let transfers = s.env.as_contract(&s.contract_id, || {
+        audit::get_record_audit_log(&s.env, s.record_id)
+            .iter()
+            .filter(|e| {
+                e.action == AccessAction::Write
+                    && e.reason == Some(String::from_str(&s.env, "Record ownership transferred"))
+            })
+            .count()
+    });
+    assert_eq!(transfers, 1);
+}
+
+#[test]
+fn test_transfer_requires_provider_or_system_admin() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+
+    assert!(matches!(
+        s.client
+            .try_transfer_record_ownership(&s.new_provider, &s.record_id, &s.new_provider),
+        Err(Ok(ContractError::Unauthorized))
+    ));
+    assert!(matches!(
+        s.client
+            .try_transfer_record_ownership(&s.old_provider, &s.record_id, &s.old_provider),
+        Err(Ok(ContractError::InvalidInput))
+    ));
+    assert!(matches!(
+        s.client
+            .try_transfer_record_ownership(&s.admin, &(s.record_id + 1), &outsider),
+        Err(Ok(ContractError::RecordNotFound))
+    ));
+
+    // The target must hold a provider role, even for a SystemAdmin.
+    assert!(matches!(
+        s.client
+            .try_transfer_record_ownership(&s.admin, &s.record_id, &outsider),
+        Err(Ok(ContractError::InvalidInput))
+    ));
+    s.client.register_user(
+        &s.admin,
+        &outsider,
+        &Role::Ophthalmologist,
+        &String::from_str(&s.env, "Locum"),
+    );
+    s.client
+        .transfer_record_ownership(&s.admin, &s.record_id, &outsider);
+    let record = s.client.get_record(&outsider, &s.record_id);
+    assert_eq!(record.provider, outsider);
+}
+
+#[test]
+fn test_transfer_rejects_unverified_target() {
+    let s = setup();
+    s.client.set_require_verified_provider(&s.admin, &true);
+
+    // The successor has a provider role but no verified provider entry.
+    assert!(matches!(
+        s.client
+            .try_transfer_record_ownership(&s.old_provider, &s.record_id, &s.new_provider),
+        Err(Ok(ContractError::ProviderNotVerified))
+    ));
+    assert_eq!(
+        s.client.get_record(&s.patient, &s.record_id).provider,
+        s.old_provider
+    );
+}
@@ -0,0 +1,114 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    AccessLevel, ConsentType, ContractError, RecordType, Role, VisionRecordsContract,
+    VisionRecordsContractClient,
+};
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, Env, String};
+
+const ONE_YEAR: u64 = 31_536_000;
+const DATA_HASH: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    patient: Address,
+    exam_id: u64,
+    lab_id: u64,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let provider = Address::generate(&env);
+    client.register_user(
+        &admin,
+        &provider,
+        &Role::Optometrist,
+        &String::from_str(&env, "Doc"),
+    );
+
+    let patient = Address::generate(&env);
+    let add = |record_type: RecordType| {
+        client.add_record(
+            &provider,
+            &patient,
+            &provider,
+            &record_type,
+            &String::from_str(&env, DATA_HASH),
+        )
+    };
+    let exam_id = add(RecordType::Examination);
+    let lab_id = add(RecordType::LabResult);
+
+    Setup {
+        env,
+        client,
+        patient,
+        exam_id,
+        lab_id,
+    }
+}
+
+fn denied(s: &Setup, caller: &Address, record_id: u64) -> bool {
+    matches!(
+        s.client.try_get_record(caller, &record_id),
+        Err(Ok(ContractError::Unauthorized))
+    )
+}
+
+#[test]
+fn test_scoped_consent_only_covers_listed_types() {
+    let s = setup();
+    let researcher = Address::generate(&s.env);
+    s.client.grant_scoped_consent(
+        &s.patient,
+        &researcher,
+        &ConsentType::Research,
+        &ONE_YEAR,
+        &vec![&s.env, RecordType::LabResult],
+    );
+
+    let lab = s.client.get_record(&researcher, &s.lab_id);
+    assert_eq!(lab.record_type, RecordType::LabResult);
+    assert!(denied(&s, &researcher, s.exam_id));
+}
+
+#[test]
+fn test_unscoped_consent_covers_every_type() {
+    let s = setup();
+    let grantee = Address::generate(&s.env);
+    assert!(denied(&s, &grantee, s.exam_id));
+
+    s.client
+        .grant_consent(&s.patient, &grantee, &ConsentType::Treatment, &ONE_YEAR);
+    s.client.get_record(&grantee, &s.exam_id);
+    s.client.get_record(&grantee, &s.lab_id);
+}
+
+#[test]
+fn test_template_record_types_scope_the_consent() {
+    let s = setup();
+    let grantee = Address::generate(&s.env);
+    let id = symbol_short!("exams");
+    let types = vec![&s.env, RecordType::Examination];
+    s.client.create_consent_template(
+        &s.patient,
+        &id,
+        &ConsentType::Treatment,
+        &AccessLevel::Read,
+        &ONE_YEAR,
+        &types,
+    );
+
+    let consent = s.client.apply_consent_template(&s.patient, &grantee, &id);
+    assert_eq!(consent.record_types, types);
+    s.client.get_record(&grantee, &s.exam_id);
+    assert!(denied(&s, &grantee, s.lab_id));
+}
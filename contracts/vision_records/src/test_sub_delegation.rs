@@ -0,0 +1,255 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use super::{
+    AccessLevel, ContractError, RecordType, VisionRecordsContract, VisionRecordsContractClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+const START: u64 = 1_000;
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: VisionRecordsContractClient<'static>,
+    admin: Address,
+    patient: Address,
+    record_id: u64,
+    /// Holds a direct 30-day Admin grant from the patient.
+    clinic: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START);
+
+    let contract_id = env.register(VisionRecordsContract, ());
+    let client = VisionRecordsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let record_id = client.add_record(
+        &admin,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&env, "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+    );
+
+    let clinic = Address::generate(&env);
+    client.grant_record_access(
+        &patient,
+        &clinic,
+        &record_id,
+        &AccessLevel::Admin,
+        &(30 * DAY),
+    );
+
+    Setup {
+        env: env.clone(),
+        client,
+        admin,
+        patient,
+        record_id,
+        clinic,
+    }
+}
+
+#[test]
+fn test_sub_delegation_respects_depth_cap() {
+    let s = setup();
+    let specialist = Address::generate(&s.env);
+    let assistant = Address::generate(&s.env);
+    assert_eq!(s.client.get_max_sub_delegation_depth(), 1);
+
+    s.client.sub_delegate_access(
+        &s.clinic,
+        &s.patient,
+        &specialist,
+        &s.record_id,
+        &AccessLevel::Admin,
+        &DAY,
+    );
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &specialist),
+        AccessLevel::Admin
+    );
+
+    // The specialist sits at depth 1, the default cap.
+    assert_eq!(
+        s.client.try_sub_delegate_access(
+            &specialist,
+            &s.patient,
+            &assistant,
+            &s.record_id,
+            &AccessLevel::Read,
+            &DAY,
+        ),
+        Err(Ok(ContractError::SubDelegationDepthExceeded))
+    );
+
+    s.client.set_max_sub_delegation_depth(&s.admin, &2);
+    s.client.sub_delegate_access(
+        &specialist,
+        &s.patient,
+        &assistant,
+        &s.record_id,
+        &AccessLevel::Read,
+        &DAY,
+    );
+
+    let tree = s.client.get_sub_delegation_tree(&s.record_id);
+    assert_eq!(tree.len(), 2);
+    let first = tree.get(0).unwrap();
+    assert_eq!(
+        (first.grantor, first.grantee, first.depth),
+        (s.clinic.clone(), specialist.clone(), 1)
+    );
+    let second = tree.get(1).unwrap();
+    assert_eq!(
+        (second.grantor, second.grantee, second.depth),
+        (specialist, assistant, 2)
+    );
+
+    assert_eq!(
+        s.client.try_set_max_sub_delegation_depth(&s.clinic, &5),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_sub_delegation_cannot_escalate() {
+    let s = setup();
+    let specialist = Address::generate(&s.env);
+    let outsider = Address::generate(&s.env);
+    s.client.set_max_sub_delegation_depth(&s.admin, &3);
+
+    // Only Admin holders may re-share, so a Write sub-grant is a leaf.
+    s.client.sub_delegate_access(
+        &s.clinic,
+        &s.patient,
+        &specialist,
+        &s.record_id,
+        &AccessLevel::Write,
+        &DAY,
+    );
+    assert_eq!(
+        s.client.try_sub_delegate_access(
+            &specialist,
+            &s.patient,
+            &outsider,
+            &s.record_id,
+            &AccessLevel::Write,
+            &DAY,
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // A sub-grant may not outlive the grant it derives from.
+    assert_eq!(
+        s.client.try_sub_delegate_access(
+            &s.clinic,
+            &s.patient,
+            &outsider,
+            &s.record_id,
+            &AccessLevel::Read,
+            &(31 * DAY),
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+
+    // Nor overwrite access someone already holds.
+    assert_eq!(
+        s.client.try_sub_delegate_access(
+            &s.clinic,
+            &s.patient,
+            &specialist,
+            &s.record_id,
+            &AccessLevel::Admin,
+            &DAY,
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &specialist),
+        AccessLevel::Write
+    );
+
+    // A grantee without any access has nothing to share.
+    assert_eq!(
+        s.client.try_sub_delegate_access(
+            &outsider,
+            &s.patient,
+            &Address::generate(&s.env),
+            &s.record_id,
+            &AccessLevel::Read,
+            &DAY,
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_revoking_parent_cascades_to_sub_grants() {
+    let s = setup();
+    let specialist = Address::generate(&s.env);
+    let assistant = Address::generate(&s.env);
+    let reviewer = Address::generate(&s.env);
+    s.client.set_max_sub_delegation_depth(&s.admin, &2);
+
+    s.client.sub_delegate_access(
+        &s.clinic,
+        &s.patient,
+        &specialist,
+        &s.record_id,
+        &AccessLevel::Admin,
+        &(2 * DAY),
+    );
+    s.client.sub_delegate_access(
+        &specialist,
+        &s.patient,
+        &assistant,
+        &s.record_id,
+        &AccessLevel::Read,
+        &DAY,
+    );
+    s.client.sub_delegate_access(
+        &s.clinic,
+        &s.patient,
+        &reviewer,
+        &s.record_id,
+        &AccessLevel::Read,
+        &DAY,
+    );
+
+    // Revoking a mid-level grant takes only its own branch.
+    s.client
+        .revoke_record_access(&s.patient, &specialist, &s.record_id);
+    for gone in [&specialist, &assistant] {
+        assert_eq!(
+            s.client.check_record_access(&s.record_id, gone),
+            AccessLevel::None
+        );
+    }
+    assert_eq!(
+        s.client.check_record_access(&s.record_id, &reviewer),
+        AccessLevel::Read
+    );
+    assert_eq!(s.client.get_sub_delegation_tree(&s.record_id).len(), 1);
+
+    // Revoking the root grant clears the rest of the tree.
+    s.client
+        .revoke_record_access(&s.patient, &s.clinic, &s.record_id);
+    for gone in [&s.clinic, &reviewer] {
+        assert_eq!(
+            s.client.check_record_access(&s.record_id, gone),
+            AccessLevel::None
+        );
+    }
+    assert!(s.client.get_sub_delegation_tree(&s.record_id).is_empty());
+}
@@ -12,18 +12,22 @@
 //! ## Key Components
 //! - `ZkVerifierContract`: The main contract implementation handling access requests and auditing.
 //! - `Bn254Verifier`: The core library for verifying Groth16 proofs.
+//! - `PlonkVerifier`: A PLONK verifier usable alongside Groth16, selected per
+//!   call or through the contract's [`ProofSystemPolicy`].
 //! - `AuditTrail`: A persistence layer for logging successful verifications.
 //! - `ZkAccessHelper`: A utility for formatting binary proof data into interoperable requests.
 
 mod audit;
 pub mod events;
 mod helpers;
+pub mod plonk;
 pub mod verifier;
 pub mod vk;
 
 pub use crate::audit::{AuditRecord, AuditTrail};
 pub use crate::events::AccessRejectedEvent;
 pub use crate::helpers::{MerkleVerifier, ZkAccessHelper};
+pub use crate::plonk::PlonkVerifier;
 pub use crate::verifier::{Bn254Verifier, PoseidonHasher, Proof, ProofValidationError, ZkVerifier};
 pub use crate::vk::VerificationKey;
 
@@ -38,11 +42,36 @@ const PENDING_ADMIN: Symbol = symbol_short!("PEND_ADM");
 const RATE_CFG: Symbol = symbol_short!("RATECFG");
 const RATE_TRACK: Symbol = symbol_short!("RLTRK");
 const NONCE: Symbol = symbol_short!("NONCE");
+const NONCE_USED: Symbol = symbol_short!("NONCE_USD");
 const REENTRANCY_LOCK: Symbol = symbol_short!("REN_LOCK");
-
-/// Maximum number of public inputs accepted per proof verification.
+const PROOF_POLICY: Symbol = symbol_short!("PRF_POL");
+const MAX_INPUTS: Symbol = symbol_short!("MAX_INP");
+const RESOURCE_VK: Symbol = symbol_short!("VK_RES");
+const EXPIRY_SKEW: Symbol = symbol_short!("EXP_SKEW");
+const MAX_BATCH: Symbol = symbol_short!("MAX_BATCH");
+
+/// Maximum number of public inputs accepted per proof verification, unless
+/// the admin configures another limit with `set_max_public_inputs`.
 const MAX_PUBLIC_INPUTS: u32 = 16;
 
+/// Highest limit `set_max_public_inputs` accepts, bounding the work a single
+/// verification can be made to do.
+const PUBLIC_INPUTS_CEILING: u32 = 256;
+
+/// Furthest in the future a request's `expires_at` may lie, so that a proof
+/// cannot be made valid forever.
+pub const MAX_PROOF_TTL_SECONDS: u64 = 86_400;
+
+/// Largest clock-skew allowance `set_expiry_skew_seconds` accepts.
+pub const MAX_EXPIRY_SKEW_SECONDS: u64 = 3_600;
+
+/// Most requests `verify_batch_access_detailed` accepts in one call, unless
+/// the admin configures another limit with `set_max_batch_proofs`.
+pub const MAX_BATCH_PROOFS: u32 = 16;
+
+/// Highest limit `set_max_batch_proofs` accepts.
+pub const BATCH_PROOFS_CEILING: u32 = 512;
+
 /// Request structure for ZK access verification.
 // TODO: post-quantum migration - This struct currently hardcodes a Groth16 `Proof`.
 // Future PQ systems (like STARKs) will require an `enum ProofType` or dynamically sized bytes
@@ -64,6 +93,41 @@ pub struct AccessRequest {
     pub nonce: u64,
 }
 
+/// Proving system an access proof is checked with.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProofSystem {
+    Groth16,
+    Plonk,
+}
+
+/// Which proving systems `verify_access*` currently honours.
+///
+/// `default_system` is used by `verify_access` when the caller does not pick
+/// a system. After the default changes, `previous_system` stays accepted
+/// until `grace_ends_at`; from then on its proofs are rejected with
+/// `InvalidConfig` and only the new default is honoured.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProofSystemPolicy {
+    pub default_system: ProofSystem,
+    /// Default before the last change; equal to `default_system` if the
+    /// default has never changed.
+    pub previous_system: ProofSystem,
+    /// Length of the dual-acceptance window opened by the next default change.
+    pub migration_grace_period: u64,
+    pub grace_ends_at: u64,
+}
+
+impl ProofSystemPolicy {
+    fn accepts(&self, system: ProofSystem, now: u64) -> bool {
+        let retired = system == self.previous_system
+            && system != self.default_system
+            && now >= self.grace_ends_at;
+        !retired
+    }
+}
+
 /// Contract errors for the ZK verifier.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -93,6 +157,12 @@ pub enum ContractError {
     ProofRequiredForAuthLevel = 14,
     /// A nested call attempted to re-enter verification while already executing.
     ReentrantCall = 15,
+    /// The request's `expires_at` has passed, even allowing for clock skew.
+    ExpiredProof = 16,
+    /// The request's `expires_at` lies beyond `MAX_PROOF_TTL_SECONDS` from now.
+    ExpiryTooFar = 17,
+    /// A batch holds more requests than `get_max_batch_proofs` allows.
+    BatchTooLarge = 18,
 }
 
 /// Map low-level proof validation errors into contract-level errors.
@@ -129,17 +199,31 @@ fn is_all_zeros(data: &BytesN<32>) -> bool {
 
 /// Validate request shape before running proof verification.
 ///
-/// This performs lightweight structural checks on the `AccessRequest` envelope.
+/// This checks the request's expiry window and performs lightweight structural
+/// checks on the `AccessRequest` envelope.
 /// Deeper proof-component validation (zeroed, oversized, malformed coordinates)
 /// is delegated to [`Bn254Verifier::validate_proof_components`] which runs
 /// inside `verify_proof` and returns granular [`ProofValidationError`] variants
 /// that are mapped to [`ContractError`] via the `From` impl.
-fn validate_request(request: &AccessRequest) -> Result<(), ContractError> {
+fn validate_request(
+    env: &Env,
+    request: &AccessRequest,
+    max_public_inputs: u32,
+    expiry_skew: u64,
+) -> Result<(), ContractError> {
+    let now = env.ledger().timestamp();
+    if now > request.expires_at.saturating_add(expiry_skew) {
+        return Err(ContractError::ExpiredProof);
+    }
+    if request.expires_at > now.saturating_add(MAX_PROOF_TTL_SECONDS) {
+        return Err(ContractError::ExpiryTooFar);
+    }
+
     if request.public_inputs.is_empty() {
         return Err(ContractError::EmptyPublicInputs);
     }
 
-    if request.public_inputs.len() > MAX_PUBLIC_INPUTS {
+    if request.public_inputs.len() > max_public_inputs {
         return Err(ContractError::TooManyPublicInputs);
     }
 
@@ -336,6 +420,145 @@ impl ZkVerifierContract {
     pub fn get_verification_key(env: Env) -> Option<VerificationKey> {
         env.storage().instance().get(&symbol_short!("VK"))
     }
+
+    /// Sets the Verification Key used for proofs about `resource_id`, so one
+    /// verifier can serve several access circuits. Resources without their
+    /// own key fall back to the global one.
+    pub fn set_resource_verification_key(
+        env: Env,
+        caller: Address,
+        resource_id: BytesN<32>,
+        vk: VerificationKey,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller, "set_resource_verification_key")?;
+        env.storage()
+            .persistent()
+            .set(&(RESOURCE_VK, resource_id), &vk);
+        Ok(())
+    }
+
+    /// Gets the Verification Key registered for `resource_id`, if any. This
+    /// does not fall back to the global key.
+    pub fn get_resource_verification_key(
+        env: Env,
+        resource_id: BytesN<32>,
+    ) -> Option<VerificationKey> {
+        env.storage().persistent().get(&(RESOURCE_VK, resource_id))
+    }
+
+    /// Switch the proving system used by `verify_access`.
+    ///
+    /// The outgoing default stays accepted for `migration_grace_period`
+    /// seconds so that clients can move over; setting the current default
+    /// again leaves the policy unchanged.
+    pub fn set_default_proof_system(
+        env: Env,
+        caller: Address,
+        system: ProofSystem,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller, "set_default_proof_system")?;
+
+        let mut policy = Self::get_proof_system_policy(env.clone());
+        if policy.default_system != system {
+            policy.previous_system = policy.default_system;
+            policy.default_system = system;
+            policy.grace_ends_at = env
+                .ledger()
+                .timestamp()
+                .saturating_add(policy.migration_grace_period);
+            env.storage().instance().set(&PROOF_POLICY, &policy);
+        }
+        Ok(())
+    }
+
+    /// Set how long the previous proving system stays accepted after the
+    /// default changes. Does not affect a migration already in progress.
+    pub fn set_migration_grace_period(
+        env: Env,
+        caller: Address,
+        seconds: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller, "set_migration_grace_period")?;
+
+        let mut policy = Self::get_proof_system_policy(env.clone());
+        policy.migration_grace_period = seconds;
+        env.storage().instance().set(&PROOF_POLICY, &policy);
+        Ok(())
+    }
+
+    /// Return the proof-system policy; Groth16 only if never configured.
+    pub fn get_proof_system_policy(env: Env) -> ProofSystemPolicy {
+        env.storage()
+            .instance()
+            .get(&PROOF_POLICY)
+            .unwrap_or(ProofSystemPolicy {
+                default_system: ProofSystem::Groth16,
+                previous_system: ProofSystem::Groth16,
+                migration_grace_period: 0,
+                grace_ends_at: 0,
+            })
+    }
+    /// Set how many public inputs a proof may carry, for circuits that need
+    /// more than the default of 16. Must be between 1 and 256.
+    pub fn set_max_public_inputs(env: Env, caller: Address, n: u32) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller, "set_max_public_inputs")?;
+
+        if n == 0 || n > PUBLIC_INPUTS_CEILING {
+            return Err(ContractError::InvalidConfig);
+        }
+        env.storage().instance().set(&MAX_INPUTS, &n);
+        Ok(())
+    }
+
+    /// Return the public-input limit enforced on verification requests.
+    pub fn get_max_public_inputs(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&MAX_INPUTS)
+            .unwrap_or(MAX_PUBLIC_INPUTS)
+    }
+
+    /// Set how many requests one `verify_batch_access_detailed` call may
+    /// carry. Must be between 1 and `BATCH_PROOFS_CEILING`.
+    pub fn set_max_batch_proofs(env: Env, caller: Address, n: u32) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller, "set_max_batch_proofs")?;
+
+        if n == 0 || n > BATCH_PROOFS_CEILING {
+            return Err(ContractError::InvalidConfig);
+        }
+        env.storage().instance().set(&MAX_BATCH, &n);
+        Ok(())
+    }
+
+    /// Return the batch size limit enforced by `verify_batch_access_detailed`.
+    pub fn get_max_batch_proofs(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&MAX_BATCH)
+            .unwrap_or(MAX_BATCH_PROOFS)
+    }
+
+    /// Set how many seconds past `expires_at` a request is still accepted, to
+    /// absorb clock skew between provers and the ledger. Defaults to 0.
+    pub fn set_expiry_skew_seconds(
+        env: Env,
+        caller: Address,
+        skew_seconds: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller, "set_expiry_skew_seconds")?;
+
+        if skew_seconds > MAX_EXPIRY_SKEW_SECONDS {
+            return Err(ContractError::InvalidConfig);
+        }
+        env.storage().instance().set(&EXPIRY_SKEW, &skew_seconds);
+        Ok(())
+    }
+
+    /// Return the clock-skew allowance applied to request expiry.
+    pub fn get_expiry_skew_seconds(env: Env) -> u64 {
+        env.storage().instance().get(&EXPIRY_SKEW).unwrap_or(0)
+    }
+
     /// Return the current rate limiting configuration, if any.
     pub fn get_rate_limit_config(env: Env) -> Option<(u64, u64)> {
         env.storage().instance().get(&RATE_CFG)
@@ -437,24 +660,60 @@ impl ZkVerifierContract {
     /// This is the primary entry point for users to gain access to protected resources.
     /// It performs the following steps:
     /// 1. Authorizes the user.
-    /// 2. Validates the request shape.
-    /// 3. Checks whitelist and rate limits.
-    /// 4. Verifies the Groth16 proof via `Bn254Verifier`.
-    /// 5. Logs the access in the `AuditTrail` if successful.
+    /// 2. Checks the proving system against the [`ProofSystemPolicy`].
+    /// 3. Validates the request shape.
+    /// 4. Checks whitelist and rate limits.
+    /// 5. Verifies the proof with the policy's default system.
+    /// 6. Logs the access in the `AuditTrail` if successful.
     ///
     /// Returns `true` if the proof is valid and all checks pass, otherwise returns an error or `false`.
     pub fn verify_access(env: Env, request: AccessRequest) -> Result<bool, ContractError> {
+        let system = Self::get_proof_system_policy(env.clone()).default_system;
+        Self::verify_access_with(env, request, system)
+    }
+
+    /// Verifies `request` as a Groth16 proof via `Bn254Verifier`, regardless
+    /// of the default system. Rejected with `InvalidConfig` once Groth16 is
+    /// no longer accepted by the policy.
+    pub fn verify_access_groth16(env: Env, request: AccessRequest) -> Result<bool, ContractError> {
+        Self::verify_access_with(env, request, ProofSystem::Groth16)
+    }
+
+    fn verify_access_with(
+        env: Env,
+        request: AccessRequest,
+        system: ProofSystem,
+    ) -> Result<bool, ContractError> {
         let _guard = enter_reentrancy_guard(&env)?;
         common::pausable::require_not_paused(&env).map_err(|_| ContractError::Paused)?;
         request.user.require_auth();
 
         let nonce_key = (NONCE, request.user.clone());
+        let used_key = (NONCE_USED, request.user.clone());
         let current_nonce: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
-        if request.nonce != current_nonce {
-            return Err(ContractError::MalformedProofData);
+        let used_nonces: u64 = env.storage().persistent().get(&used_key).unwrap_or(0);
+        let (next_nonce, next_used) = common::nonce::advance_nonce_window(
+            current_nonce,
+            used_nonces,
+            request.nonce,
+            common::nonce::nonce_window(&env),
+        )
+        .map_err(|_| ContractError::MalformedProofData)?;
+
+        let policy = Self::get_proof_system_policy(env.clone());
+        if !policy.accepts(system, env.ledger().timestamp()) {
+            events::publish_access_rejected(
+                &env,
+                request.user.clone(),
+                request.resource_id.clone(),
+                ContractError::InvalidConfig,
+            );
+            return Err(ContractError::InvalidConfig);
         }
 
-        validate_request(&request).map_err(|err| {
+        let max_public_inputs = Self::get_max_public_inputs(env.clone());
+        let expiry_skew = Self::get_expiry_skew_seconds(env.clone());
+        validate_request(&env, &request, max_public_inputs, expiry_skew).map_err(|err| {
             events::publish_access_rejected(
                 &env,
                 request.user.clone(),
@@ -484,15 +743,32 @@ impl ZkVerifierContract {
             err
         })?;
 
-        Bn254Verifier::validate_proof_components(&request.proof, &request.public_inputs)
-            .map_err(map_proof_validation_error)?;
+        // TODO: post-quantum migration - A STARK system would be another `ProofSystem`
+        // variant, branching to `PostQuantumVerifier::verify_proof` or a native
+        // host-function call if STARK verification limits CPU budgets.
+        match system {
+            ProofSystem::Groth16 => {
+                Bn254Verifier::validate_proof_components(&request.proof, &request.public_inputs)
+            }
+            ProofSystem::Plonk => {
+                PlonkVerifier::validate_proof_components(&request.proof, &request.public_inputs)
+            }
+        }
+        .map_err(map_proof_validation_error)?;
 
-        // TODO: post-quantum migration - The verification branch below is hardcoded for BN254 Groth16.
-        // During migration, checking `request.proof_type` should branch to `PostQuantumVerifier::verify_proof`
-        // or a native host-function call if STARK verification limits CPU budgets.
-        let vk = Self::get_verification_key(env.clone()).ok_or(ContractError::InvalidConfig)?;
-        let is_valid =
-            Bn254Verifier::verify_proof(&env, &vk, &request.proof, &request.public_inputs);
+        let vk = Self::get_resource_verification_key(env.clone(), request.resource_id.clone())
+            .or_else(|| Self::get_verification_key(env.clone()))
+            .ok_or(ContractError::InvalidConfig)?;
+        let (is_valid, required_proof) = match system {
+            ProofSystem::Groth16 => (
+                Bn254Verifier::verify_proof(&env, &vk, &request.proof, &request.public_inputs),
+                "valid_groth16_proof",
+            ),
+            ProofSystem::Plonk => (
+                PlonkVerifier::verify_proof(&env, &vk, &request.proof, &request.public_inputs),
+                "valid_plonk_proof",
+            ),
+        };
         if is_valid {
             let proof_hash = PoseidonHasher::hash(&env, &request.public_inputs);
             AuditTrail::log_access(
@@ -502,20 +778,31 @@ impl ZkVerifierContract {
                 proof_hash,
                 request.expires_at,
             );
-            env.storage()
-                .persistent()
-                .set(&nonce_key, &current_nonce.saturating_add(1));
+            env.storage().persistent().set(&nonce_key, &next_nonce);
+            if next_used == 0 {
+                env.storage().persistent().remove(&used_key);
+            } else {
+                env.storage().persistent().set(&used_key, &next_used);
+            }
         } else {
-            Self::emit_access_violation(
-                &env,
-                &request.user,
-                "verify_access",
-                "valid_groth16_proof",
-            );
+            Self::emit_access_violation(&env, &request.user, "verify_access", required_proof);
         }
         Ok(is_valid)
     }
 
+    /// Accept any unused nonce up to `window` ahead of a user's counter, so
+    /// clients can submit several proofs without ordering them. A window of
+    /// 0, the default, requires strictly sequential nonces. At most 64.
+    pub fn set_nonce_window(env: Env, caller: Address, window: u64) -> Result<(), ContractError> {
+        Self::require_admin(&env, &caller, "set_nonce_window")?;
+        common::nonce::set_nonce_window(&env, window).map_err(|_| ContractError::InvalidConfig)
+    }
+
+    /// Return how far ahead of a user's counter nonces are accepted.
+    pub fn get_nonce_window(env: Env) -> u64 {
+        common::nonce::nonce_window(&env)
+    }
+
     pub fn get_nonce(env: Env, user: Address) -> u64 {
         env.storage().persistent().get(&(NONCE, user)).unwrap_or(0)
     }
@@ -539,10 +826,34 @@ impl ZkVerifierContract {
         Self::verify_access(env, request)
     }
 
+    /// Verifies `request` as a PLONK proof via `PlonkVerifier`, regardless
+    /// of the default system. Rejected with `InvalidConfig` while PLONK is
+    /// not accepted by the policy.
     pub fn verify_access_plonk(env: Env, request: AccessRequest) -> Result<bool, ContractError> {
-        // Until a dedicated PLONK verifier is wired, keep entrypoint parity
-        // with clients by using the Groth16 validation path.
-        Self::verify_access(env, request)
+        Self::verify_access_with(env, request, ProofSystem::Plonk)
+    }
+
+    /// Verifies each request as `verify_access` would and reports every
+    /// outcome as `(index, is_valid, rejection_reason)`, so callers can tell
+    /// which proofs failed and why. One rejected request does not stop the
+    /// rest of the batch.
+    pub fn verify_batch_access_detailed(
+        env: Env,
+        requests: Vec<AccessRequest>,
+    ) -> Result<Vec<(u32, bool, Option<ContractError>)>, ContractError> {
+        if requests.len() > Self::get_max_batch_proofs(env.clone()) {
+            return Err(ContractError::BatchTooLarge);
+        }
+
+        let mut results = Vec::new(&env);
+        for (index, request) in requests.iter().enumerate() {
+            let outcome = match Self::verify_access(env.clone(), request) {
+                Ok(is_valid) => (index as u32, is_valid, None),
+                Err(err) => (index as u32, false, Some(err)),
+            };
+            results.push_back(outcome);
+        }
+        Ok(results)
     }
 
     pub fn verify_data_inclusion(
@@ -0,0 +1,201 @@
+//! Integration tests for switching the default proof system with a
+//! migration grace period.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Vec,
+};
+use zk_verifier::verifier::{G1Point, G2Point, Proof};
+use zk_verifier::vk::VerificationKey;
+use zk_verifier::{
+    AccessRequest, ContractError, ProofSystem, ProofSystemPolicy, ZkVerifierContract,
+    ZkVerifierContractClient,
+};
+
+const GRACE: u64 = 3_600;
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+fn setup(env: &Env) -> (ZkVerifierContractClient<'static>, Address, Address) {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(ZkVerifierContract, ());
+    let client = ZkVerifierContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let user = Address::generate(env);
+    client.initialize(&admin);
+
+    // The mock verifiers only inspect the proof, so any key will do.
+    let g1 = G1Point {
+        x: BytesN::from_array(env, &[1u8; 32]),
+        y: BytesN::from_array(env, &[2u8; 32]),
+    };
+    let g2 = G2Point {
+        x: (
+            BytesN::from_array(env, &[3u8; 32]),
+            BytesN::from_array(env, &[4u8; 32]),
+        ),
+        y: (
+            BytesN::from_array(env, &[5u8; 32]),
+            BytesN::from_array(env, &[6u8; 32]),
+        ),
+    };
+    let mut ic = Vec::new(env);
+    ic.push_back(g1.clone());
+    ic.push_back(g1.clone());
+    let vk = VerificationKey {
+        alpha_g1: g1,
+        beta_g2: g2.clone(),
+        gamma_g2: g2.clone(),
+        delta_g2: g2,
+        ic,
+    };
+    client.set_verification_key(&admin, &vk);
+    (client, admin, user)
+}
+
+/// Build a request whose proof passes the mock verifier for `system`:
+/// Groth16 proofs carry marker byte 1, PLONK proofs marker byte 2.
+fn request(env: &Env, user: &Address, nonce: u64, system: ProofSystem) -> AccessRequest {
+    let marker = match system {
+        ProofSystem::Groth16 => 1u8,
+        ProofSystem::Plonk => 2u8,
+    };
+    let mut point = [0u8; 32];
+    point[0] = marker;
+    let limb = BytesN::from_array(env, &[1u8; 32]);
+
+    let mut public_inputs = Vec::new(env);
+    public_inputs.push_back(BytesN::from_array(env, &point));
+    AccessRequest {
+        user: user.clone(),
+        resource_id: BytesN::from_array(env, &[9u8; 32]),
+        proof: Proof {
+            a: G1Point {
+                x: BytesN::from_array(env, &point),
+                y: BytesN::from_array(env, &point),
+            },
+            b: G2Point {
+                x: (limb.clone(), limb.clone()),
+                y: (limb.clone(), limb),
+            },
+            c: G1Point {
+                x: BytesN::from_array(env, &point),
+                y: BytesN::from_array(env, &point),
+            },
+        },
+        public_inputs,
+        nonce,
+        expires_at: env.ledger().timestamp().saturating_add(600),
+    }
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn default_policy_is_groth16_without_migration() {
+    let env = Env::default();
+    let (client, _, user) = setup(&env);
+
+    assert_eq!(
+        client.get_proof_system_policy(),
+        ProofSystemPolicy {
+            default_system: ProofSystem::Groth16,
+            previous_system: ProofSystem::Groth16,
+            migration_grace_period: 0,
+            grace_ends_at: 0,
+        }
+    );
+
+    // Groth16 is used by default; a PLONK-only proof fails it.
+    assert!(!client.verify_access(&request(&env, &user, 0, ProofSystem::Plonk)));
+    assert!(client.verify_access(&request(&env, &user, 0, ProofSystem::Groth16)));
+    // PLONK stays available explicitly while nothing has been migrated away from.
+    assert!(client.verify_access_plonk(&request(&env, &user, 1, ProofSystem::Plonk)));
+}
+
+#[test]
+fn both_systems_verify_during_grace_period() {
+    let env = Env::default();
+    let (client, admin, user) = setup(&env);
+
+    client.set_migration_grace_period(&admin, &GRACE);
+    client.set_default_proof_system(&admin, &ProofSystem::Plonk);
+
+    let policy = client.get_proof_system_policy();
+    assert_eq!(policy.default_system, ProofSystem::Plonk);
+    assert_eq!(policy.previous_system, ProofSystem::Groth16);
+    assert_eq!(policy.grace_ends_at, 1_000 + GRACE);
+
+    // verify_access now routes to PLONK.
+    assert!(client.verify_access(&request(&env, &user, 0, ProofSystem::Plonk)));
+
+    // Clients still on Groth16 keep working until the window closes.
+    env.ledger().set_timestamp(1_000 + GRACE - 1);
+    assert!(client.verify_access_groth16(&request(&env, &user, 1, ProofSystem::Groth16)));
+    assert!(client.verify_access_plonk(&request(&env, &user, 2, ProofSystem::Plonk)));
+    assert_eq!(client.get_nonce(&user), 3);
+}
+
+#[test]
+fn only_new_default_is_honoured_after_grace_period() {
+    let env = Env::default();
+    let (client, admin, user) = setup(&env);
+
+    client.set_migration_grace_period(&admin, &GRACE);
+    client.set_default_proof_system(&admin, &ProofSystem::Plonk);
+    env.ledger().set_timestamp(1_000 + GRACE);
+
+    assert_eq!(
+        client.try_verify_access_groth16(&request(&env, &user, 0, ProofSystem::Groth16)),
+        Err(Ok(ContractError::InvalidConfig))
+    );
+    // A rejected system does not consume the nonce.
+    assert_eq!(client.get_nonce(&user), 0);
+
+    assert!(client.verify_access(&request(&env, &user, 0, ProofSystem::Plonk)));
+    assert!(client.verify_access_plonk(&request(&env, &user, 1, ProofSystem::Plonk)));
+
+    // Switching back opens a new window for PLONK, and Groth16 is honoured again.
+    client.set_default_proof_system(&admin, &ProofSystem::Groth16);
+    assert!(client.verify_access(&request(&env, &user, 2, ProofSystem::Groth16)));
+    assert!(client.verify_access_plonk(&request(&env, &user, 3, ProofSystem::Plonk)));
+}
+
+#[test]
+fn zero_grace_period_retires_old_system_immediately() {
+    let env = Env::default();
+    let (client, admin, user) = setup(&env);
+
+    client.set_default_proof_system(&admin, &ProofSystem::Plonk);
+    assert_eq!(
+        client.try_verify_access_groth16(&request(&env, &user, 0, ProofSystem::Groth16)),
+        Err(Ok(ContractError::InvalidConfig))
+    );
+
+    // Re-selecting the current default does not reopen a window.
+    client.set_migration_grace_period(&admin, &GRACE);
+    client.set_default_proof_system(&admin, &ProofSystem::Plonk);
+    assert_eq!(client.get_proof_system_policy().grace_ends_at, 1_000);
+}
+
+#[test]
+fn policy_changes_are_admin_only() {
+    let env = Env::default();
+    let (client, _, user) = setup(&env);
+
+    assert_eq!(
+        client.try_set_default_proof_system(&user, &ProofSystem::Plonk),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_migration_grace_period(&user, &GRACE),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.get_proof_system_policy().default_system,
+        ProofSystem::Groth16
+    );
+}
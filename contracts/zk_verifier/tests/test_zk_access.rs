@@ -10,6 +10,7 @@ use soroban_sdk::{
 use zk_verifier::vk::{G1Point, G2Point, VerificationKey};
 use zk_verifier::{
     AccessRejectedEvent, ContractError, ZkVerifierContract, ZkVerifierContractClient,
+    BATCH_PROOFS_CEILING, MAX_BATCH_PROOFS, MAX_EXPIRY_SKEW_SECONDS, MAX_PROOF_TTL_SECONDS,
 };
 use zk_verifier::{MerkleVerifier, ZkAccessHelper};
 
@@ -314,6 +315,83 @@ fn test_verify_access_cpu_budget_invalid_proof() {
     );
 }
 
+#[test]
+fn test_resource_verification_keys_override_global_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ZkVerifierContract, ());
+    let client = ZkVerifierContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let resource_a = [21u8; 32];
+    let resource_b = [22u8; 32];
+    let id_a = BytesN::from_array(&env, &resource_a);
+    let id_b = BytesN::from_array(&env, &resource_b);
+    let vk_a = setup_vk(&env);
+    let mut vk_b = setup_vk(&env);
+    vk_b.ic.push_back(vk_b.alpha_g1.clone());
+
+    let request_for = |resource_id: [u8; 32]| {
+        let mut proof_a = [0u8; 64];
+        proof_a[0] = 1;
+        proof_a[32] = 0x02;
+        let mut proof_b = [0u8; 128];
+        proof_b[0] = 1;
+        proof_b[32] = 0x02;
+        proof_b[64] = 0x03;
+        proof_b[96] = 0x04;
+        let mut proof_c = [0u8; 64];
+        proof_c[0] = 1;
+        proof_c[32] = 0x02;
+        let mut pi = [0u8; 32];
+        pi[0] = 1;
+        ZkAccessHelper::create_request(
+            &env,
+            Address::generate(&env),
+            resource_id,
+            proof_a,
+            proof_b,
+            proof_c,
+            &[&pi],
+            env.ledger().timestamp() + 1000,
+        )
+    };
+
+    // Only resource A has a key, and there is no global one to fall back on.
+    client.set_resource_verification_key(&admin, &id_a, &vk_a);
+    assert!(client.verify_access(&request_for(resource_a)));
+    assert!(matches!(
+        client.try_verify_access(&request_for(resource_b)),
+        Err(Ok(ContractError::InvalidConfig))
+    ));
+
+    // Once a global key exists, unregistered resources fall back to it.
+    client.set_verification_key(&admin, &vk_a);
+    assert!(client.verify_access(&request_for(resource_b)));
+
+    client.set_resource_verification_key(&admin, &id_b, &vk_b);
+    assert_eq!(
+        client.get_resource_verification_key(&id_a),
+        Some(vk_a.clone())
+    );
+    assert_eq!(
+        client.get_resource_verification_key(&id_b),
+        Some(vk_b.clone())
+    );
+    assert_eq!(
+        client.get_resource_verification_key(&BytesN::from_array(&env, &[23u8; 32])),
+        None
+    );
+
+    assert_eq!(
+        client.try_set_resource_verification_key(&Address::generate(&env), &id_a, &vk_b),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
 #[test]
 fn test_rate_limit_enforcement_and_reset() {
     let env = Env::default();
@@ -908,6 +986,331 @@ fn test_exactly_max_public_inputs_accepted() {
     );
 }
 
+#[test]
+fn test_raised_public_input_limit_accepts_larger_requests() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ZkVerifierContract, ());
+    let client = ZkVerifierContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    assert_eq!(client.get_max_public_inputs(), 16);
+
+    let request_with = |count: u32| {
+        let inputs: std::vec::Vec<[u8; 32]> = (0..count)
+            .map(|i| {
+                let mut buf = [0u8; 32];
+                buf[0] = (i % 255 + 1) as u8;
+                buf
+            })
+            .collect();
+        let input_refs: std::vec::Vec<&[u8; 32]> = inputs.iter().collect();
+        let mut a = [0u8; 64];
+        a[0] = 1;
+        let mut b = [0u8; 128];
+        b[0] = 1;
+        let mut c = [0u8; 64];
+        c[0] = 1;
+        ZkAccessHelper::create_request(
+            &env,
+            Address::generate(&env),
+            [19u8; 32],
+            a,
+            b,
+            c,
+            &input_refs,
+            env.ledger().timestamp() + 1000,
+        )
+    };
+
+    assert!(matches!(
+        client.try_verify_access(&request_with(24)),
+        Err(Ok(ContractError::TooManyPublicInputs))
+    ));
+
+    client.set_max_public_inputs(&admin, &32);
+    assert_eq!(client.get_max_public_inputs(), 32);
+    // 24 inputs now pass input-count validation; the bogus proof may still
+    // fail later, but not as too many inputs.
+    assert!(!matches!(
+        client.try_verify_access(&request_with(24)),
+        Err(Ok(ContractError::TooManyPublicInputs))
+    ));
+    assert!(matches!(
+        client.try_verify_access(&request_with(33)),
+        Err(Ok(ContractError::TooManyPublicInputs))
+    ));
+
+    // The limit is bounded on both sides and admin-only.
+    for n in [0u32, 257] {
+        assert_eq!(
+            client.try_set_max_public_inputs(&admin, &n),
+            Err(Ok(ContractError::InvalidConfig))
+        );
+    }
+    assert_eq!(
+        client.try_set_max_public_inputs(&Address::generate(&env), &64),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.get_max_public_inputs(), 32);
+}
+
+#[test]
+fn test_request_expiry_window_and_skew() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let contract_id = env.register(ZkVerifierContract, ());
+    let client = ZkVerifierContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_verification_key(&admin, &setup_vk(&env));
+    assert_eq!(client.get_expiry_skew_seconds(), 0);
+
+    let request_expiring = |expires_at: u64| {
+        let mut proof_a = [0u8; 64];
+        proof_a[0] = 1;
+        proof_a[32] = 0x02;
+        let mut proof_b = [0u8; 128];
+        proof_b[0] = 1;
+        proof_b[32] = 0x02;
+        proof_b[64] = 0x03;
+        proof_b[96] = 0x04;
+        let mut proof_c = [0u8; 64];
+        proof_c[0] = 1;
+        proof_c[32] = 0x02;
+        let mut pi = [0u8; 32];
+        pi[0] = 1;
+        ZkAccessHelper::create_request(
+            &env,
+            Address::generate(&env),
+            [24u8; 32],
+            proof_a,
+            proof_b,
+            proof_c,
+            &[&pi],
+            expires_at,
+        )
+    };
+
+    // A request is still valid in the second it expires, but not after.
+    let expires_at = 10_000;
+    assert!(client.verify_access(&request_expiring(expires_at)));
+    env.ledger().set_timestamp(expires_at + 1);
+    assert!(matches!(
+        client.try_verify_access(&request_expiring(expires_at)),
+        Err(Ok(ContractError::ExpiredProof))
+    ));
+
+    // A skew allowance stretches the boundary by exactly that much.
+    client.set_expiry_skew_seconds(&admin, &30);
+    assert_eq!(client.get_expiry_skew_seconds(), 30);
+    env.ledger().set_timestamp(expires_at + 30);
+    assert!(client.verify_access(&request_expiring(expires_at)));
+    env.ledger().set_timestamp(expires_at + 31);
+    assert!(matches!(
+        client.try_verify_access(&request_expiring(expires_at)),
+        Err(Ok(ContractError::ExpiredProof))
+    ));
+
+    // Expiry may not be pushed out past the maximum TTL.
+    let now = env.ledger().timestamp();
+    assert!(client.verify_access(&request_expiring(now + MAX_PROOF_TTL_SECONDS)));
+    assert!(matches!(
+        client.try_verify_access(&request_expiring(now + MAX_PROOF_TTL_SECONDS + 1)),
+        Err(Ok(ContractError::ExpiryTooFar))
+    ));
+
+    assert_eq!(
+        client.try_set_expiry_skew_seconds(&admin, &(MAX_EXPIRY_SKEW_SECONDS + 1)),
+        Err(Ok(ContractError::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_set_expiry_skew_seconds(&Address::generate(&env), &0),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_batch_access_reports_each_outcome() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let contract_id = env.register(ZkVerifierContract, ());
+    let client = ZkVerifierContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_verification_key(&admin, &setup_vk(&env));
+
+    let request_for = |user: &Address, expires_at: u64| {
+        let mut proof_a = [0u8; 64];
+        proof_a[0] = 1;
+        proof_a[32] = 0x02;
+        let mut proof_b = [0u8; 128];
+        proof_b[0] = 1;
+        proof_b[32] = 0x02;
+        proof_b[64] = 0x03;
+        proof_b[96] = 0x04;
+        let mut proof_c = [0u8; 64];
+        proof_c[0] = 1;
+        proof_c[32] = 0x02;
+        let mut pi = [0u8; 32];
+        pi[0] = 1;
+        ZkAccessHelper::create_request(
+            &env,
+            user.clone(),
+            [25u8; 32],
+            proof_a,
+            proof_b,
+            proof_c,
+            &[&pi],
+            expires_at,
+        )
+    };
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    client.set_whitelist_enabled(&admin, &true);
+    for user in [&alice, &bob, &carol] {
+        client.add_to_whitelist(&admin, user);
+    }
+
+    let mut requests = Vec::new(&env);
+    requests.push_back(request_for(&alice, 11_000));
+    requests.push_back(request_for(&bob, 9_999));
+    requests.push_back(request_for(&outsider, 11_000));
+    requests.push_back(request_for(&carol, 11_000));
+
+    let results = client.verify_batch_access_detailed(&requests);
+    assert_eq!(results.len(), 4);
+    assert_eq!(results.get(0).unwrap(), (0, true, None));
+    assert_eq!(
+        results.get(1).unwrap(),
+        (1, false, Some(ContractError::ExpiredProof))
+    );
+    assert_eq!(
+        results.get(2).unwrap(),
+        (2, false, Some(ContractError::Unauthorized))
+    );
+    assert_eq!(results.get(3).unwrap(), (3, true, None));
+
+    // Only the accepted proofs consumed a nonce.
+    assert_eq!(client.get_nonce(&alice), 1);
+    assert_eq!(client.get_nonce(&bob), 0);
+    assert_eq!(client.get_nonce(&carol), 1);
+
+    let mut oversized = Vec::new(&env);
+    for _ in 0..=MAX_BATCH_PROOFS {
+        oversized.push_back(request_for(&alice, 11_000));
+    }
+    assert_eq!(
+        client.try_verify_batch_access_detailed(&oversized),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+
+    // The limit is admin-configurable within `[1, BATCH_PROOFS_CEILING]`.
+    assert_eq!(client.get_max_batch_proofs(), MAX_BATCH_PROOFS);
+    client.set_max_batch_proofs(&admin, &2);
+    assert_eq!(client.get_max_batch_proofs(), 2);
+    assert_eq!(
+        client.try_verify_batch_access_detailed(&requests),
+        Err(Ok(ContractError::BatchTooLarge))
+    );
+    for n in [0u32, BATCH_PROOFS_CEILING + 1] {
+        assert_eq!(
+            client.try_set_max_batch_proofs(&admin, &n),
+            Err(Ok(ContractError::InvalidConfig))
+        );
+    }
+    assert_eq!(
+        client.try_set_max_batch_proofs(&outsider, &BATCH_PROOFS_CEILING),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_max_batch_proofs(&admin, &BATCH_PROOFS_CEILING);
+    assert_eq!(client.get_max_batch_proofs(), BATCH_PROOFS_CEILING);
+}
+
+#[test]
+fn test_nonce_window_accepts_out_of_order_submission() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ZkVerifierContract, ());
+    let client = ZkVerifierContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_verification_key(&admin, &setup_vk(&env));
+    assert_eq!(client.get_nonce_window(), 0);
+
+    let user = Address::generate(&env);
+    let request_with_nonce = |nonce: u64| {
+        let mut proof_a = [0u8; 64];
+        proof_a[0] = 1;
+        proof_a[32] = 0x02;
+        let mut proof_b = [0u8; 128];
+        proof_b[0] = 1;
+        proof_b[32] = 0x02;
+        proof_b[64] = 0x03;
+        proof_b[96] = 0x04;
+        let mut proof_c = [0u8; 64];
+        proof_c[0] = 1;
+        proof_c[32] = 0x02;
+        let mut pi = [0u8; 32];
+        pi[0] = 1;
+        let mut request = ZkAccessHelper::create_request(
+            &env,
+            user.clone(),
+            [26u8; 32],
+            proof_a,
+            proof_b,
+            proof_c,
+            &[&pi],
+            env.ledger().timestamp() + 1000,
+        );
+        request.nonce = nonce;
+        request
+    };
+
+    for nonce in 0..3 {
+        assert!(client.verify_access(&request_with_nonce(nonce)));
+    }
+    // Strict ordering by default: nonce 4 is a gap.
+    assert!(matches!(
+        client.try_verify_access(&request_with_nonce(4)),
+        Err(Ok(ContractError::MalformedProofData))
+    ));
+
+    client.set_nonce_window(&admin, &2);
+    for nonce in [5, 3, 4] {
+        assert!(client.verify_access(&request_with_nonce(nonce)));
+    }
+    assert_eq!(client.get_nonce(&user), 6);
+    for replay in [3, 5] {
+        assert!(matches!(
+            client.try_verify_access(&request_with_nonce(replay)),
+            Err(Ok(ContractError::MalformedProofData))
+        ));
+    }
+
+    assert_eq!(
+        client.try_set_nonce_window(&admin, &65),
+        Err(Ok(ContractError::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_set_nonce_window(&user, &1),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
 // #[test]
 // // #[ignore]
 // fn test_audit_chain_integrity() {